@@ -0,0 +1,43 @@
+//! Records build-time metadata for `frost version --full`: the version
+//! requirements of the protocol libraries and every feature declared in
+//! Cargo.toml, with whether it is enabled in this build.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+/// Libraries whose version requirements `frost version --full` reports.
+const LIBRARIES: [&str; 3] = ["gstp", "bc-envelope", "hubert"];
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let manifest_path = Path::new(&manifest_dir).join("Cargo.toml");
+    println!("cargo:rerun-if-changed={}", manifest_path.display());
+    let manifest: toml::Table =
+        fs::read_to_string(&manifest_path).unwrap().parse().unwrap();
+
+    let dependencies = manifest["dependencies"].as_table().unwrap();
+    let mut out = String::from("pub const LIBRARIES: &[(&str, &str)] = &[\n");
+    for name in LIBRARIES {
+        let requirement = match &dependencies[name] {
+            toml::Value::String(version) => version.as_str(),
+            dependency => dependency["version"].as_str().unwrap(),
+        };
+        writeln!(out, "    ({name:?}, {requirement:?}),").unwrap();
+    }
+    out.push_str("];\n");
+
+    out.push_str("pub const FEATURES: &[(&str, bool)] = &[\n");
+    let features = manifest.get("features").and_then(toml::Value::as_table);
+    for name in features.into_iter().flat_map(toml::Table::keys) {
+        if name == "default" {
+            continue;
+        }
+        let variable =
+            format!("CARGO_FEATURE_{}", name.to_uppercase().replace('-', "_"));
+        let enabled = env::var_os(variable).is_some();
+        writeln!(out, "    ({name:?}, {enabled}),").unwrap();
+    }
+    out.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("build_info.rs"), out).unwrap();
+}
@@ -480,6 +480,18 @@ frost registry owner set --registry "${registry_var}" "${owner_upper}_OWNER_DOC"
                 ),
             )
 
+        run_step(
+            shell,
+            "Listing Alice's participants",
+            f"""
+frost registry participant list --registry {qp(REGISTRIES["alice"])}
+""",
+            commentary=(
+                "Participants are listed by pet name with their XIDs; "
+                "`--output tsv` and `--output json` give machine-readable forms."
+            ),
+        )
+
         run_step(
             shell,
             "Composing Alice's preview DKG invite",
@@ -819,6 +831,19 @@ done
             ),
         )
 
+        run_step(
+            shell,
+            "Alice exports the group public key",
+            f"""
+frost dkg coordinator pubkey --registry {qp(REGISTRIES["alice"])} "${{ALICE_GROUP_ID}}"
+""",
+            commentary=(
+                "Alice prints the group verifying key from the collected finalize "
+                "responses for verifiers outside the group; `--format json` prints "
+                "the full FROST public key package instead."
+            ),
+        )
+
         # ── Signing session setup (start) ────────────────────────────────
 
         run_step(
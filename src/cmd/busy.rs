@@ -7,6 +7,9 @@
 //! - ✅ on success, ❌ on failure
 //! - Countdown timer for get operations (time remaining)
 //! - Count-up timer for put operations (elapsed time)
+//!
+//! Colors and emoji are replaced by ASCII markers when `--no-color` or
+//! `NO_COLOR` is in effect.
 
 use std::{
     io::IsTerminal,
@@ -23,7 +26,12 @@ use bc_envelope::Envelope;
 use indicatif::{ProgressBar, ProgressStyle};
 use tokio::runtime::Runtime;
 
-use crate::cmd::storage::StorageClient;
+use crate::cmd::{
+    is_no_color,
+    parallel::{failure_marker, spinner_template, success_marker},
+    spinner_tick,
+    storage::{StorageClient, StorageError},
+};
 
 /// Direction of the operation (get or put).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,11 +43,14 @@ pub enum Direction {
 }
 
 impl Direction {
-    /// Get the emoji prefix for this direction.
+    /// Get the emoji prefix for this direction (ASCII when color is
+    /// disabled).
     pub fn emoji(&self) -> &'static str {
-        match self {
-            Direction::Get => "⬇️",
-            Direction::Put => "⬆️",
+        match (self, is_no_color()) {
+            (Direction::Get, false) => "⬇️",
+            (Direction::Put, false) => "⬆️",
+            (Direction::Get, true) => "[get]",
+            (Direction::Put, true) => "[put]",
         }
     }
 }
@@ -77,23 +88,26 @@ impl BusyIndicator {
                 Direction::Get => {
                     if let Some(timeout) = timeout_seconds {
                         format!(
-                            "{}  {{spinner:.yellow}} {}... -{}s",
+                            "{}  {} {}... -{}s",
                             direction.emoji(),
+                            spinner_template(),
                             name,
                             timeout
                         )
                     } else {
                         format!(
-                            "{}  {{spinner:.yellow}} {}...",
+                            "{}  {} {}...",
                             direction.emoji(),
+                            spinner_template(),
                             name
                         )
                     }
                 }
                 Direction::Put => {
                     format!(
-                        "{}  {{spinner:.yellow}} {}... +0s",
+                        "{}  {} {}... +0s",
                         direction.emoji(),
+                        spinner_template(),
                         name
                     )
                 }
@@ -103,7 +117,9 @@ impl BusyIndicator {
                     .template(&template)
                     .expect("valid template"),
             );
-            bar.enable_steady_tick(Duration::from_millis(100));
+            if let Some(interval) = spinner_tick() {
+                bar.enable_steady_tick(interval);
+            }
             Some(bar)
         } else {
             // Non-interactive: no incremental output, we print final status
@@ -151,23 +167,26 @@ impl BusyIndicator {
                         if let Some(t) = timeout {
                             let remaining = t.saturating_sub(elapsed);
                             format!(
-                                "{}  {{spinner:.yellow}} {}... -{}s",
+                                "{}  {} {}... -{}s",
                                 direction.emoji(),
+                                spinner_template(),
                                 name,
                                 remaining
                             )
                         } else {
                             format!(
-                                "{}  {{spinner:.yellow}} {}...",
+                                "{}  {} {}...",
                                 direction.emoji(),
+                                spinner_template(),
                                 name
                             )
                         }
                     }
                     Direction::Put => {
                         format!(
-                            "{}  {{spinner:.yellow}} {}... +{}s",
+                            "{}  {} {}... +{}s",
                             direction.emoji(),
+                            spinner_template(),
                             name,
                             elapsed
                         )
@@ -191,8 +210,9 @@ impl BusyIndicator {
             if let Some(ref bar) = self.bar {
                 // Both get and put show elapsed time on success
                 let template = format!(
-                    "{}  ✅ {}: {}s",
+                    "{}  {} {}: {}s",
                     self.direction.emoji(),
+                    success_marker(),
                     self.name,
                     elapsed
                 );
@@ -208,8 +228,9 @@ impl BusyIndicator {
         } else {
             // Non-interactive: print complete status line
             eprintln!(
-                "{}  ✅ {}: {}s",
+                "{}  {} {}: {}s",
                 self.direction.emoji(),
+                success_marker(),
                 self.name,
                 elapsed
             );
@@ -223,8 +244,9 @@ impl BusyIndicator {
         if self.is_interactive {
             if let Some(ref bar) = self.bar {
                 let template = format!(
-                    "{}  ❌ {}: {}",
+                    "{}  {} {}: {}",
                     self.direction.emoji(),
+                    failure_marker(),
                     self.name,
                     msg
                 );
@@ -239,7 +261,13 @@ impl BusyIndicator {
             }
         } else {
             // Non-interactive: print complete status line
-            eprintln!("{}  ❌ {}: {}", self.direction.emoji(), self.name, msg);
+            eprintln!(
+                "{}  {} {}: {}",
+                self.direction.emoji(),
+                failure_marker(),
+                self.name,
+                msg
+            );
         }
     }
 
@@ -250,8 +278,9 @@ impl BusyIndicator {
         if self.is_interactive {
             if let Some(ref bar) = self.bar {
                 let template = format!(
-                    "{}  ❌ {}: Timeout",
+                    "{}  {} {}: Timeout",
                     self.direction.emoji(),
+                    failure_marker(),
                     self.name
                 );
                 bar.set_style(
@@ -265,7 +294,12 @@ impl BusyIndicator {
             }
         } else {
             // Non-interactive: print complete status line
-            eprintln!("{}  ❌ {}: Timeout", self.direction.emoji(), self.name);
+            eprintln!(
+                "{}  {} {}: Timeout",
+                self.direction.emoji(),
+                failure_marker(),
+                self.name
+            );
         }
     }
 }
@@ -302,7 +336,7 @@ pub fn get_with_indicator(
         }
     }
 
-    result
+    result.map_err(StorageError::into_anyhow)
 }
 
 /// Execute a put operation with busy indicator.
@@ -1,39 +1,185 @@
-use anyhow::{Result, bail};
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result, bail};
+use bc_components::ARID;
+use bc_envelope::prelude::*;
 use clap::Parser;
 use mainline::Testnet;
 use reqwest::Client;
-use serde_json::Value;
-use tokio::{
-    runtime::Runtime,
-    time::{Duration, timeout},
-};
+use serde_json::{Value, json};
+use tokio::time::{Duration, timeout};
 
-use crate::cmd::storage::{StorageSelection, StorageSelector};
+use crate::cmd::{
+    dkg::common::parse_arid_ur,
+    parallel::{ProbeStatus, parallel_probe},
+    storage::{
+        StorageClient, StorageSelection, StorageSelector, shared_runtime,
+    },
+};
 
 #[derive(Debug, Parser)]
 #[doc(hidden)]
 pub struct CommandArgs {
     #[command(flatten)]
     storage: StorageSelector,
+
+    /// Instead of checking backend availability, probe whether each ARID in
+    /// this file (one ur:arid per line; blank lines and `#` comments are
+    /// ignored) has a value stored
+    #[arg(long = "arids", value_name = "FILE")]
+    arids: Option<PathBuf>,
+
+    /// Maximum number of ARID probes in flight at once
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 8,
+        requires = "arids",
+        value_parser = clap::value_parser!(u16).range(1..=256)
+    )]
+    concurrency: u16,
+
+    /// Print ARID probe results as JSON
+    #[arg(long, requires = "arids")]
+    json: bool,
 }
 
 impl CommandArgs {
     pub fn exec(self) -> Result<()> {
         let selection = self.storage.resolve()?;
-        let runtime = Runtime::new()?;
-        runtime.block_on(async move { run_check(selection).await })
+        let runtime = shared_runtime()?;
+        match &self.arids {
+            Some(path) => {
+                let text = fs::read_to_string(path).with_context(|| {
+                    format!("Failed to read {}", path.display())
+                })?;
+                let arids = parse_arid_list(&text)?;
+                let results = runtime.block_on(async {
+                    let client = StorageClient::shared(selection).await?;
+                    anyhow::Ok(
+                        parallel_probe(
+                            client,
+                            arids,
+                            usize::from(self.concurrency),
+                        )
+                        .await,
+                    )
+                })?;
+                report_probes(&results, self.json)
+            }
+            None => runtime.block_on(async move { run_check(selection).await }),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ARID probes
+// -----------------------------------------------------------------------------
+
+/// Parses a list of ARIDs, one per line. Blank lines and lines starting with
+/// `#` are skipped.
+pub fn parse_arid_list(text: &str) -> Result<Vec<ARID>> {
+    let mut arids = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let arid = parse_arid_ur(line)
+            .with_context(|| format!("Invalid ARID on line {}", index + 1))?;
+        arids.push(arid);
+    }
+    if arids.is_empty() {
+        bail!("No ARIDs to check");
+    }
+    Ok(arids)
+}
+
+/// Prints probe results and a summary, failing unless every ARID is present.
+fn report_probes(results: &[(ARID, ProbeStatus)], as_json: bool) -> Result<()> {
+    let present = results
+        .iter()
+        .filter(|(_, status)| *status == ProbeStatus::Present)
+        .count();
+    let absent = results
+        .iter()
+        .filter(|(_, status)| *status == ProbeStatus::Absent)
+        .count();
+    let errors = results.len() - present - absent;
+
+    if as_json {
+        let entries: Vec<Value> = results
+            .iter()
+            .map(|(arid, status)| match status {
+                ProbeStatus::Present => {
+                    json!({ "arid": arid.ur_string(), "status": "present" })
+                }
+                ProbeStatus::Absent => {
+                    json!({ "arid": arid.ur_string(), "status": "absent" })
+                }
+                ProbeStatus::Error(error) => json!({
+                    "arid": arid.ur_string(),
+                    "status": "error",
+                    "error": error,
+                }),
+            })
+            .collect();
+        let report = json!({
+            "present": present,
+            "absent": absent,
+            "errors": errors,
+            "arids": entries,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for (arid, status) in results {
+            match status {
+                ProbeStatus::Present => {
+                    println!("present {}", arid.ur_string())
+                }
+                ProbeStatus::Absent => println!("absent  {}", arid.ur_string()),
+                ProbeStatus::Error(error) => {
+                    println!("error   {}: {}", arid.ur_string(), error)
+                }
+            }
+        }
+        println!("{present} present, {absent} absent, {errors} errors");
     }
+
+    if present < results.len() {
+        bail!(
+            "{} of {} ARIDs are not present",
+            results.len() - present,
+            results.len()
+        );
+    }
+    Ok(())
 }
 
+// -----------------------------------------------------------------------------
+// Backend availability
+// -----------------------------------------------------------------------------
+
 async fn run_check(selection: StorageSelection) -> Result<()> {
+    match check_storage(selection).await {
+        Ok(message) => {
+            println!("✓ {message}");
+            Ok(())
+        }
+        Err(e) => bail!("✗ {e}"),
+    }
+}
+
+/// Checks that the selected backend is reachable, returning a description
+/// of what answered.
+pub async fn check_storage(selection: StorageSelection) -> Result<String> {
     match selection {
         StorageSelection::Mainline => check_mainline().await,
         StorageSelection::Ipfs { port } => check_ipfs(port).await,
         StorageSelection::Hybrid { port } => {
             check_mainline().await?;
             check_ipfs(port).await?;
-            println!("✓ Hybrid storage is available (DHT + IPFS)");
-            Ok(())
+            Ok("Hybrid storage is available (DHT + IPFS)".to_string())
         }
         StorageSelection::Server { host, port } => {
             check_server(&host, port).await
@@ -41,20 +187,15 @@ async fn run_check(selection: StorageSelection) -> Result<()> {
     }
 }
 
-async fn check_mainline() -> Result<()> {
+async fn check_mainline() -> Result<String> {
     // Try to connect to mainline DHT using testnet
     match Testnet::new_async(5).await {
-        Ok(_) => {
-            println!("✓ Mainline DHT is available");
-            Ok(())
-        }
-        Err(e) => {
-            bail!("✗ Mainline DHT is not available: {}", e)
-        }
+        Ok(_) => Ok("Mainline DHT is available".to_string()),
+        Err(e) => bail!("Mainline DHT is not available: {}", e),
     }
 }
 
-async fn check_ipfs(port: u16) -> Result<()> {
+async fn check_ipfs(port: u16) -> Result<String> {
     let client = Client::new();
     let url = format!("http://127.0.0.1:{}/api/v0/version", port);
     match client
@@ -65,19 +206,18 @@ async fn check_ipfs(port: u16) -> Result<()> {
     {
         Ok(response) => {
             if response.status().is_success() {
-                println!("✓ IPFS is available at 127.0.0.1:{}", port);
-                Ok(())
+                Ok(format!("IPFS is available at 127.0.0.1:{}", port))
             } else {
-                bail!("✗ IPFS daemon returned error: {}", response.status())
+                bail!("IPFS daemon returned error: {}", response.status())
             }
         }
         Err(e) => {
-            bail!("✗ IPFS is not available at 127.0.0.1:{}: {}", port, e)
+            bail!("IPFS is not available at 127.0.0.1:{}: {}", port, e)
         }
     }
 }
 
-async fn check_server(host: &str, port: u16) -> Result<()> {
+async fn check_server(host: &str, port: u16) -> Result<String> {
     let url = format!("http://{}:{}/health", host, port);
     let client = Client::new();
 
@@ -95,35 +235,34 @@ async fn check_server(host: &str, port: u16) -> Result<()> {
                                 .get("version")
                                 .and_then(|v| v.as_str())
                                 .unwrap_or("unknown");
-                            println!(
-                                "✓ Hubert server is available at {}:{} (version {})",
+                            Ok(format!(
+                                "Hubert server is available at {}:{} (version {})",
                                 host, port, version
-                            );
-                            Ok(())
+                            ))
                         } else {
                             bail!(
-                                "✗ Server at {}:{} is not a Hubert server",
+                                "Server at {}:{} is not a Hubert server",
                                 host,
                                 port
                             );
                         }
                     } else {
                         bail!(
-                            "✗ Server at {}:{} returned invalid health response",
+                            "Server at {}:{} returned invalid health response",
                             host,
                             port
                         );
                     }
                 } else {
                     bail!(
-                        "✗ Server at {}:{} returned invalid health response",
+                        "Server at {}:{} returned invalid health response",
                         host,
                         port
                     );
                 }
             } else {
                 bail!(
-                    "✗ Server at {}:{} is not available (status: {})",
+                    "Server at {}:{} is not available (status: {})",
                     host,
                     port,
                     response.status()
@@ -131,11 +270,11 @@ async fn check_server(host: &str, port: u16) -> Result<()> {
             }
         }
         Ok(Err(e)) => {
-            bail!("✗ Server is not available at {}:{}: {}", host, port, e)
+            bail!("Server is not available at {}:{}: {}", host, port, e)
         }
         Err(_) => {
             bail!(
-                "✗ Server is not available at {}:{}: connection timeout",
+                "Server is not available at {}:{}: connection timeout",
                 host,
                 port
             )
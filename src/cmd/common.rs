@@ -6,16 +6,36 @@
 //! - Storage backend selection
 //! - Verifying key conversion
 //! - Group state directory helpers
+//! - Atomic state file writes
+//! - Group state locking
+//! - Confirmation prompts
+//! - Registry passphrase
+//! - Clock skew warnings
+//! - Collection deadlines
+//! - Partial collections
+//! - Display order of participants
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, File, OpenOptions},
+    io::{self, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use anyhow::{Context, Result, anyhow, bail};
-use bc_components::{ARID, Ed25519PublicKey, SigningPublicKey};
-use bc_envelope::prelude::CBOR;
+use bc_components::{ARID, Ed25519PublicKey, SigningPublicKey, XID};
+use bc_envelope::prelude::{CBOR, Date};
 use bc_ur::prelude::UR;
-use clap::Args;
+use clap::{Args, ValueEnum};
 
-use super::storage::{StorageBackend, StorageSelection, StorageSelector};
+use super::{
+    clock_skew_tolerance, is_assume_yes, is_no_input, is_verbose,
+    passphrase_file,
+    storage::{StorageBackend, StorageSelection, StorageSelector},
+};
+use crate::registry::{GroupRecord, Passphrase, Registry};
 
 // -----------------------------------------------------------------------------
 // ARID/UR parsing
@@ -42,6 +62,49 @@ pub fn parse_arid_ur(input: &str) -> Result<ARID> {
     })
 }
 
+/// Resolves a group argument: the name of a group in `registry` (see
+/// `frost registry group rename`), otherwise a `ur:arid`.
+pub fn parse_group_id(registry: &Registry, input: &str) -> Result<ARID> {
+    let trimmed = input.trim();
+    if let Some((group_id, _)) = registry.group_by_name(trimmed) {
+        return Ok(group_id);
+    }
+    if !trimmed.to_ascii_lowercase().starts_with("ur:") {
+        bail!("No group named '{trimmed}' in the registry");
+    }
+    parse_arid_ur(trimmed)
+}
+
+/// Set once a UR has been read from stdin by [`read_ur_input`].
+static STDIN_CONSUMED: AtomicBool = AtomicBool::new(false);
+
+/// Resolves a UR positional argument, reading it from stdin when the argument
+/// is `-`.
+///
+/// This lets commands be chained in a pipeline without temporary files.
+/// Stdin must hold exactly one UR; surrounding whitespace and the trailing
+/// newline are trimmed. Only one argument per invocation may be `-`.
+pub fn read_ur_input(input: &str) -> Result<String> {
+    if input != "-" {
+        return Ok(input.to_owned());
+    }
+    if STDIN_CONSUMED.swap(true, Ordering::SeqCst) {
+        bail!("Only one argument per invocation can be read from stdin");
+    }
+    let mut buffer = String::new();
+    io::stdin()
+        .read_to_string(&mut buffer)
+        .context("Failed to read UR from stdin")?;
+    let mut urs = buffer.split_whitespace();
+    match (urs.next(), urs.next()) {
+        (Some(ur), None) => Ok(ur.to_owned()),
+        (None, _) => bail!("Expected a UR on stdin, found nothing"),
+        (Some(_), Some(_)) => {
+            bail!("Expected a single UR on stdin, found several")
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Storage selection
 // -----------------------------------------------------------------------------
@@ -80,6 +143,28 @@ impl OptionalStorageSelector {
 
         Ok(None)
     }
+
+    /// The selection as command-line options, for running another command
+    /// against the same backend.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(storage) = self.storage {
+            let value = storage
+                .to_possible_value()
+                .expect("storage backends are never skipped");
+            args.extend([
+                "--storage".to_string(),
+                value.get_name().to_string(),
+            ]);
+        }
+        if let Some(host) = &self.host {
+            args.extend(["--host".to_string(), host.clone()]);
+        }
+        if let Some(port) = self.port {
+            args.extend(["--port".to_string(), port.to_string()]);
+        }
+        args
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -102,13 +187,610 @@ pub fn signing_key_from_verifying(
 // Group state directory
 // -----------------------------------------------------------------------------
 
-/// Returns the group state directory for a DKG group.
+/// Locates the state files kept alongside a registry.
+///
+/// Layout:
+/// - `{registry_dir}/group-state/{group_id.hex()}/`: DKG files for a group
+/// - `.../signing/{session_id.hex()}/`: state for one signing session
+///
+/// Every command derives state paths through this type so the layout is
+/// defined in one place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupStatePaths {
+    root: PathBuf,
+}
+
+impl GroupStatePaths {
+    pub fn new(registry_path: &Path) -> Self {
+        let base = registry_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+        Self { root: base.join("group-state") }
+    }
+
+    /// The directory holding every group's state.
+    pub fn root(&self) -> &Path { &self.root }
+
+    /// Path: `{root}/{group_id.hex()}`
+    pub fn group_dir(&self, group_id: &ARID) -> PathBuf {
+        self.root.join(group_id.hex())
+    }
+
+    /// Path: `{root}/{group_id.hex()}/signing`
+    pub fn signing_dir(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("signing")
+    }
+
+    /// Path: `{root}/{group_id.hex()}/signing/{session_id.hex()}`
+    pub fn session_dir(&self, group_id: &ARID, session_id: &ARID) -> PathBuf {
+        self.signing_dir(group_id).join(session_id.hex())
+    }
+
+    /// The hinted group, or every group that has a state directory.
+    pub fn candidate_groups(&self, hint: Option<ARID>) -> Result<Vec<ARID>> {
+        if let Some(group_id) = hint {
+            return Ok(vec![group_id]);
+        }
+        let mut groups = Vec::new();
+        if !self.root.exists() {
+            return Ok(groups);
+        }
+        for entry in fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str()
+                && name.len() == 64
+                && name.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                groups.push(ARID::from_hex(name));
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Lists the signing sessions that have a state directory under
+    /// `group_id`.
+    pub fn sessions(&self, group_id: &ARID) -> Result<Vec<ARID>> {
+        let mut sessions = Vec::new();
+        let signing_dir = self.signing_dir(group_id);
+        if !signing_dir.exists() {
+            return Ok(sessions);
+        }
+        for entry in fs::read_dir(&signing_dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str()
+                && name.len() == 64
+                && name.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                sessions.push(ARID::from_hex(name));
+            }
+        }
+        Ok(sessions)
+    }
+
+    // DKG state
+
+    pub fn round1_secret_path(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("round1_secret.json")
+    }
+
+    pub fn round1_package_path(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("round1_package.json")
+    }
+
+    pub fn round2_secret_path(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("round2_secret.json")
+    }
+
+    pub fn collected_round1_path(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("collected_round1.json")
+    }
+
+    /// Round 1 packages saved by a `--continue-on-error` collection that
+    /// failed, kept apart from `collected_round1.json` so nothing mistakes
+    /// them for a complete Round 1.
+    pub fn partial_round1_path(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("partial_round1.json")
+    }
+
+    pub fn collected_round2_path(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("collected_round2.json")
+    }
+
+    pub fn collected_finalize_path(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("collected_finalize.json")
+    }
+
+    pub fn key_package_path(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("key_package.json")
+    }
+
+    pub fn public_key_package_path(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("public_key_package.json")
+    }
+
+    /// Directory of sealed key shares dealt by `frost keygen`.
+    pub fn dealt_shares_dir(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("dealt")
+    }
+
+    pub fn dealt_share_path(
+        &self,
+        group_id: &ARID,
+        participant: &XID,
+    ) -> PathBuf {
+        let hex: String = participant
+            .data()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        self.dealt_shares_dir(group_id).join(format!("{hex}.ur"))
+    }
+
+    // Signing session state
+
+    pub fn start_state_path(
+        &self,
+        group_id: &ARID,
+        session_id: &ARID,
+    ) -> PathBuf {
+        self.session_dir(group_id, session_id).join("start.json")
+    }
+
+    pub fn commitments_path(
+        &self,
+        group_id: &ARID,
+        session_id: &ARID,
+    ) -> PathBuf {
+        self.session_dir(group_id, session_id)
+            .join("commitments.json")
+    }
+
+    /// Commitments saved while `sign coordinator round1` is still collecting,
+    /// kept apart from `commitments.json` so a run that fails for lack of
+    /// quorum leaves nothing that `sign coordinator round2` would use.
+    pub fn partial_commitments_path(
+        &self,
+        group_id: &ARID,
+        session_id: &ARID,
+    ) -> PathBuf {
+        self.session_dir(group_id, session_id)
+            .join("partial_commitments.json")
+    }
+
+    pub fn receive_state_path(
+        &self,
+        group_id: &ARID,
+        session_id: &ARID,
+    ) -> PathBuf {
+        self.session_dir(group_id, session_id)
+            .join("sign_receive.json")
+    }
+
+    pub fn commit_state_path(
+        &self,
+        group_id: &ARID,
+        session_id: &ARID,
+    ) -> PathBuf {
+        self.session_dir(group_id, session_id).join("commit.json")
+    }
+
+    pub fn share_state_path(
+        &self,
+        group_id: &ARID,
+        session_id: &ARID,
+    ) -> PathBuf {
+        self.session_dir(group_id, session_id).join("share.json")
+    }
+
+    pub fn final_state_path(
+        &self,
+        group_id: &ARID,
+        session_id: &ARID,
+    ) -> PathBuf {
+        self.session_dir(group_id, session_id).join("final.json")
+    }
+
+    /// Path: `{root}/{group_id.hex()}/group.lock`
+    pub fn lock_path(&self, group_id: &ARID) -> PathBuf {
+        self.group_dir(group_id).join("group.lock")
+    }
+
+    /// Takes the exclusive lock on `group_id`'s state directory, blocking
+    /// until any other holder releases it.
+    ///
+    /// Hold the returned guard across a read-modify-write of a session JSON
+    /// file so that concurrent commands (e.g. a watch loop and a manual step)
+    /// cannot overwrite each other's changes.
+    pub fn lock_group(&self, group_id: &ARID) -> Result<GroupStateLock> {
+        let dir = self.group_dir(group_id);
+        fs::create_dir_all(&dir).with_context(|| {
+            format!("Failed to create group state directory {}", dir.display())
+        })?;
+        let path = self.lock_path(group_id);
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        file.lock()
+            .with_context(|| format!("Failed to lock {}", path.display()))?;
+        Ok(GroupStateLock { file })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Group state locking
+// -----------------------------------------------------------------------------
+
+/// Exclusive lock on a group's state directory, released on drop.
+///
+/// Obtained from [`GroupStatePaths::lock_group`].
+#[derive(Debug)]
+pub struct GroupStateLock {
+    file: File,
+}
+
+impl Drop for GroupStateLock {
+    fn drop(&mut self) { let _ = self.file.unlock(); }
+}
+
+// -----------------------------------------------------------------------------
+// Atomic file writes
+// -----------------------------------------------------------------------------
+
+/// Writes `contents` to `path` without ever leaving a partially written file.
+///
+/// The data is first written to a uniquely named temporary file in the same
+/// directory, which is then renamed over `path`, so concurrent writers never
+/// share a temporary file. If either step fails the temporary file is removed
+/// and any previous (complete) file at `path` is left untouched. On Unix the
+/// written file is readable only by its owner.
+pub fn write_atomically(
+    path: impl AsRef<Path>,
+    contents: impl AsRef<[u8]>,
+) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+    temp.write_all(contents.as_ref())?;
+    temp.persist(path).map(|_| ()).map_err(|err| err.error)
+}
+
+// -----------------------------------------------------------------------------
+// Confirmation prompts
+// -----------------------------------------------------------------------------
+
+/// Asks the user to confirm by typing `yes` after `prompt`, returning whether
+/// they did.
 ///
-/// Path: `{registry_dir}/group-state/{group_id.hex()}`
-pub fn group_state_dir(registry_path: &Path, group_id: &ARID) -> PathBuf {
-    let base = registry_path
-        .parent()
-        .map(Path::to_path_buf)
-        .unwrap_or_else(|| PathBuf::from("."));
-    base.join("group-state").join(group_id.hex())
+/// `--no-input` turns the prompt into an error so scripts never block and
+/// takes precedence over `--yes`, which confirms without reading stdin. When
+/// stdin is not a terminal an answer may still be piped in, but reaching end
+/// of input without one is an error rather than a silent refusal.
+pub fn confirm(prompt: &str) -> Result<bool> {
+    Ok(read_answer(prompt)?.is_none_or(|answer| answer == "yes"))
+}
+
+/// Asks a `[Y/n]` question after `prompt`, where an empty answer means yes.
+///
+/// Follows the same `--no-input` and `--yes` rules as [`confirm`].
+pub fn ask(prompt: &str) -> Result<bool> {
+    Ok(match read_answer(prompt)? {
+        None => true,
+        Some(answer) => {
+            matches!(answer.to_lowercase().as_str(), "" | "y" | "yes")
+        }
+    })
+}
+
+/// Reads one trimmed answer to `prompt`, or `None` when `--yes` answers it.
+fn read_answer(prompt: &str) -> Result<Option<String>> {
+    if is_no_input() {
+        bail!(
+            "Confirmation required but --no-input was given; pass --yes \
+             instead to confirm non-interactively"
+        );
+    }
+    if is_assume_yes() {
+        eprintln!("{prompt} yes (--yes)");
+        return Ok(None);
+    }
+
+    eprint!("{prompt} ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    let read = io::stdin()
+        .read_line(&mut answer)
+        .context("Failed to read confirmation")?;
+    if read == 0 && !io::stdin().is_terminal() {
+        eprintln!();
+        bail!(
+            "No confirmation on stdin; pass --yes to confirm \
+             non-interactively or --no-input to fail without prompting"
+        );
+    }
+    Ok(Some(answer.trim().to_string()))
+}
+
+// -----------------------------------------------------------------------------
+// Registry passphrase
+// -----------------------------------------------------------------------------
+
+/// Environment variable holding the passphrase for an encrypted registry.
+pub const PASSPHRASE_ENV: &str = "FROST_PASSPHRASE";
+
+/// Supplies the passphrase for an encrypted registry: the first line of
+/// `--passphrase-file`, then `FROST_PASSPHRASE`, then a prompt on the
+/// terminal. A `new` passphrase is asked for twice so a typo cannot lock the
+/// registry.
+pub fn registry_passphrase(new: bool) -> Result<Passphrase> {
+    if let Some(path) = passphrase_file() {
+        let contents = fs::read_to_string(&path).with_context(|| {
+            format!("Failed to read passphrase file {}", path.display())
+        })?;
+        return Passphrase::new(contents.lines().next().unwrap_or_default());
+    }
+    if let Ok(passphrase) = env::var(PASSPHRASE_ENV) {
+        return Passphrase::new(passphrase);
+    }
+    if is_no_input() || !io::stdin().is_terminal() {
+        bail!(
+            "The registry is encrypted; pass --passphrase-file or set \
+             {PASSPHRASE_ENV} to supply its passphrase without prompting"
+        );
+    }
+
+    let passphrase = rpassword::prompt_password("Registry passphrase: ")
+        .context("Failed to read passphrase")?;
+    if new {
+        let again = rpassword::prompt_password("Repeat passphrase: ")
+            .context("Failed to read passphrase")?;
+        if again != passphrase {
+            bail!("Passphrases do not match");
+        }
+    }
+    Passphrase::new(passphrase)
+}
+
+/// Loads the registry at `path`, asking for its passphrase with
+/// [`registry_passphrase`] only if the file is encrypted.
+pub fn load_registry(path: &Path) -> Result<Registry> {
+    Registry::load_with_passphrase(path, || registry_passphrase(false))
+}
+
+// -----------------------------------------------------------------------------
+// Clock skew
+// -----------------------------------------------------------------------------
+
+/// Reports a received message whose issuance date lies further in the future
+/// than `--clock-skew-tolerance`, which means this machine's clock (or the
+/// sender's) is probably wrong. With `--verbose` the delta is always shown.
+///
+/// Expiry checks compare against the local clock, so skew otherwise shows up
+/// only as a baffling rejection or a silently accepted stale message.
+pub fn check_clock_skew(label: &str, issued: Option<&Date>, now: &Date) {
+    let Some(issued) = issued else {
+        return;
+    };
+    let ahead = issued.timestamp() - now.timestamp();
+    if is_verbose() {
+        eprintln!(
+            "{label} issued at {issued}; local time is {now} ({ahead:+.0}s)"
+        );
+    }
+    if ahead > clock_skew_tolerance().as_secs_f64() {
+        eprintln!(
+            "Warning: {label} was issued {ahead:.0}s in the future by this \
+             machine's clock; check your system clock"
+        );
+    }
+}
+
+/// The error for a message whose `validUntil` has passed by the local clock.
+pub fn expired_error(
+    label: &str,
+    valid_until: &Date,
+    now: &Date,
+) -> anyhow::Error {
+    anyhow!(
+        "{label} expired at {valid_until} (local time {now}); if it was sent \
+         recently, check your system clock"
+    )
+}
+
+// -----------------------------------------------------------------------------
+// Collection deadlines
+// -----------------------------------------------------------------------------
+
+/// How long a collect command waits for responses: a relative `--timeout`
+/// for each response, or an absolute `--deadline` shared by all of them.
+#[derive(Debug, Clone, Default)]
+pub struct CollectionTimeout {
+    timeout: Option<u64>,
+    deadline: Option<Date>,
+}
+
+impl CollectionTimeout {
+    /// Seconds to wait for the next response.
+    ///
+    /// Under a deadline this is recomputed on each call as the whole seconds
+    /// remaining, rounded up, so a sequential collection never waits past the
+    /// deadline however long the earlier responses took. Once it has passed,
+    /// each remaining response gets a single lookup.
+    pub fn seconds(&self) -> Option<u64> {
+        let Some(deadline) = &self.deadline else {
+            return self.timeout;
+        };
+        let remaining = deadline.timestamp() - Date::now().timestamp();
+        Some(remaining.max(0.0).ceil() as u64)
+    }
+}
+
+/// Resolves how long a collect command waits, from either a relative
+/// `--timeout` or an absolute RFC 3339 `--deadline`.
+///
+/// A deadline that has already passed is rejected.
+pub fn collection_timeout(
+    timeout: Option<u64>,
+    deadline: Option<&str>,
+) -> Result<CollectionTimeout> {
+    let Some(deadline) = deadline else {
+        return Ok(CollectionTimeout { timeout, deadline: None });
+    };
+    let deadline = Date::from_string(deadline).with_context(|| {
+        format!("Invalid --deadline date (expected RFC 3339): {deadline}")
+    })?;
+    let now = Date::now();
+    let remaining = deadline.timestamp() - now.timestamp();
+    if remaining <= 0.0 {
+        bail!("--deadline {deadline} has already passed (local time {now})");
+    }
+    if is_verbose() {
+        eprintln!("Collecting until {deadline} ({remaining:.0}s from now)");
+    }
+    Ok(CollectionTimeout { timeout: None, deadline: Some(deadline) })
+}
+
+/// Parses `--slow-participant PARTICIPANT:SECONDS` arguments into
+/// per-participant timeouts.
+///
+/// A participant is a pet name or `ur:xid` and must be a member of the
+/// group; naming one twice is an error.
+pub fn slow_participant_timeouts(
+    registry: &Registry,
+    group_record: &GroupRecord,
+    specs: &[String],
+) -> Result<HashMap<XID, u64>> {
+    let mut timeouts = HashMap::new();
+    for spec in specs {
+        let (participant, seconds) =
+            spec.trim().rsplit_once(':').with_context(|| {
+                format!(
+                    "Invalid --slow-participant {spec}; expected \
+                     PARTICIPANT:SECONDS"
+                )
+            })?;
+        let seconds: u64 = seconds.parse().with_context(|| {
+            format!("Invalid --slow-participant timeout in {spec}")
+        })?;
+        let xid = if let Ok(xid) = XID::from_ur_string(participant) {
+            xid
+        } else if let Some((xid, _)) =
+            registry.participant_by_pet_name(participant)
+        {
+            *xid
+        } else if let Some(owner) = registry.owner()
+            && owner.pet_name() == Some(participant)
+        {
+            owner.xid()
+        } else {
+            bail!("Participant with pet name '{participant}' not found");
+        };
+        if !group_record.participants().iter().any(|p| *p.xid() == xid) {
+            bail!(
+                "--slow-participant {participant} is not a member of this \
+                 group"
+            );
+        }
+        if timeouts.insert(xid, seconds).is_some() {
+            bail!(
+                "--slow-participant given more than once for {}",
+                xid.ur_string()
+            );
+        }
+    }
+    Ok(timeouts)
+}
+
+// -----------------------------------------------------------------------------
+// Partial collections
+// -----------------------------------------------------------------------------
+
+/// Process exit status when `--continue-on-error` saved a partial collection.
+pub const PARTIAL_COLLECTION_EXIT_CODE: u8 = 3;
+
+/// A collection that failed for some participants after saving what the
+/// others sent, so the run exits with [`PARTIAL_COLLECTION_EXIT_CODE`]
+/// rather than the generic failure status.
+#[derive(Debug)]
+pub struct PartialCollection {
+    pub phase: &'static str,
+    pub collected: usize,
+    pub failed: usize,
+    pub path: PathBuf,
+}
+
+impl std::fmt::Display for PartialCollection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} collection incomplete: {} responses failed; saved the {} \
+             collected to {}",
+            self.phase,
+            self.failed,
+            self.collected,
+            self.path.display()
+        )
+    }
+}
+
+impl std::error::Error for PartialCollection {}
+
+// -----------------------------------------------------------------------------
+// Display order
+// -----------------------------------------------------------------------------
+
+/// Sorts participants for human-facing listings: by display name (the pet
+/// name, or the XID UR when there is none), then by XID UR so equal names
+/// keep a stable order.
+///
+/// This is display order only. FROST identifiers and request dispatch stay
+/// in XID order.
+pub fn sort_for_display<T>(
+    items: &mut [T],
+    name_and_xid: impl Fn(&T) -> (String, XID),
+) {
+    items.sort_by_cached_key(|item| {
+        let (name, xid) = name_and_xid(item);
+        (name, xid.ur_string())
+    });
+}
+
+// -----------------------------------------------------------------------------
+// List output
+// -----------------------------------------------------------------------------
+
+/// How a listing command prints its records.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Readable lines, with details indented under each record
+    #[default]
+    Human,
+    /// JSON, in the same layout the registry stores
+    Json,
+    /// One tab-separated record per line without a header, in the column
+    /// order the command documents
+    Tsv,
+}
+
+/// Joins `fields` into one TSV record. Tabs and line breaks inside a field
+/// become spaces, so every record stays on one line with the same number of
+/// columns.
+pub fn tsv_record<'a>(fields: impl IntoIterator<Item = &'a str>) -> String {
+    fields
+        .into_iter()
+        .map(|field| field.replace(['\t', '\r', '\n'], " "))
+        .collect::<Vec<_>>()
+        .join("\t")
 }
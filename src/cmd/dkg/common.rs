@@ -5,21 +5,27 @@
 //!
 //! For cross-cutting utilities shared with signing, see [`crate::cmd::common`].
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use anyhow::{Context, Result, bail};
-use bc_components::{XID, XIDProvider};
+use bc_components::{ARID, PrivateKeys, SigningPublicKey, XID, XIDProvider};
 use bc_envelope::prelude::*;
 use bc_ur::prelude::UR;
 use bc_xid::XIDDocument;
+use frost_ed25519 as frost;
+use gstp::{SealedRequest, SealedResponse};
 
 // Re-export cross-cutting utilities for convenience
 pub use super::super::common::{
-    OptionalStorageSelector, group_state_dir, parse_arid_ur,
-    signing_key_from_verifying,
+    GroupStatePaths, OptionalStorageSelector, parse_arid_ur, parse_group_id,
+    read_ur_input, signing_key_from_verifying,
 };
-use crate::registry::{
-    GroupParticipant, OwnerRecord, ParticipantRecord, Registry,
+use crate::{
+    cmd::{common::sort_for_display, is_strict_sender},
+    dkg::ShareRepair,
+    registry::{
+        GroupParticipant, GroupRecord, OwnerRecord, ParticipantRecord, Registry,
+    },
 };
 
 // -----------------------------------------------------------------------------
@@ -115,6 +121,70 @@ pub fn resolve_sender_name(
     })
 }
 
+// -----------------------------------------------------------------------------
+// Coordinator checks
+// -----------------------------------------------------------------------------
+
+/// Fails unless the registry owner coordinates the group. Every coordinator
+/// command runs this before touching group state.
+pub fn ensure_owner_is_coordinator(
+    group_record: &GroupRecord,
+    owner: &OwnerRecord,
+) -> Result<()> {
+    if group_record.coordinator().xid() != &owner.xid() {
+        bail!(
+            "Only the group's coordinator can run this command. \
+             Coordinator: {}, Owner: {}",
+            group_record.coordinator().xid().ur_string(),
+            owner.xid().ur_string()
+        );
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// Sender checks
+// -----------------------------------------------------------------------------
+
+/// Under `--strict-sender`, requires the document a message was sealed with
+/// to carry the inception key registered for its XID. Callers match the XID
+/// against the coordinator or participant the protocol step expects; this
+/// also rejects a message sealed with a different key under that XID.
+pub fn check_strict_sender(
+    registry: &Registry,
+    sender: &XIDDocument,
+) -> Result<()> {
+    if !is_strict_sender() {
+        return Ok(());
+    }
+    let xid = sender.xid();
+    let registered = match registry.owner() {
+        Some(owner) if owner.xid() == xid => owner
+            .xid_document()
+            .inception_key()
+            .map(|key| key.public_keys().clone()),
+        _ => registry
+            .participant(&xid)
+            .map(|record| record.public_keys().clone()),
+    }
+    .with_context(|| {
+        format!(
+            "Strict sender check failed: {} is not in the registry",
+            xid.ur_string()
+        )
+    })?;
+    let presented = sender.inception_key().map(|key| key.public_keys());
+    if presented != Some(&registered) {
+        bail!(
+            "Strict sender check failed: {} sent a key that differs from its \
+             registered XID document; if they re-keyed, update their \
+             registry entry",
+            xid.ur_string()
+        );
+    }
+    Ok(())
+}
+
 // -----------------------------------------------------------------------------
 // Group participant building
 // -----------------------------------------------------------------------------
@@ -162,10 +232,8 @@ pub fn participant_names_from_registry(
     owner_xid: &XID,
     owner_pet_name: Option<&str>,
 ) -> Result<Vec<String>> {
-    let mut docs: Vec<XIDDocument> = participants.to_vec();
-    docs.sort_by_key(|doc| doc.xid());
-
-    docs.iter()
+    let mut names = participants
+        .iter()
         .map(|document| {
             let xid = document.xid();
             let is_owner = xid == *owner_xid;
@@ -185,9 +253,17 @@ pub fn participant_names_from_registry(
                     .map(|n| n.to_owned())
                     .unwrap_or_else(|| xid.ur_string())
             };
-            Ok(format_name_with_owner_marker(name, is_owner))
+            Ok((name, xid, is_owner))
         })
-        .collect()
+        .collect::<Result<Vec<_>>>()?;
+    sort_for_display(&mut names, |(name, xid, _)| (name.clone(), *xid));
+
+    Ok(names
+        .into_iter()
+        .map(|(name, _, is_owner)| {
+            format_name_with_owner_marker(name, is_owner)
+        })
+        .collect())
 }
 
 // -----------------------------------------------------------------------------
@@ -208,3 +284,410 @@ pub fn parse_envelope_ur(input: &str) -> Result<Envelope> {
         .or_else(|_| Envelope::from_untagged_cbor(ur.cbor()))
         .context("Invalid envelope payload")
 }
+
+// -----------------------------------------------------------------------------
+// Group parameter checks
+// -----------------------------------------------------------------------------
+
+/// Restates the group's agreed charter and threshold in a later-round request.
+pub fn with_group_parameters(
+    request: SealedRequest,
+    group_record: &GroupRecord,
+) -> SealedRequest {
+    request
+        .with_parameter("minSigners", group_record.min_signers() as u64)
+        .with_parameter("charter", group_record.charter().to_owned())
+}
+
+/// Checks that a later-round request carries the charter and threshold
+/// agreed at invite time, so a coordinator cannot change them mid-protocol.
+///
+/// A request that omits either one is refused: leaving them out would
+/// otherwise skip the check.
+pub fn verify_group_parameters(
+    request: &SealedRequest,
+    group_record: &GroupRecord,
+) -> Result<()> {
+    let min_signers: u64 =
+        request
+            .extract_object_for_parameter("minSigners")
+            .context("Request does not restate the group's min signers")?;
+    let agreed = group_record.min_signers();
+    if usize::try_from(min_signers).ok() != Some(agreed) {
+        bail!(
+            "Coordinator changed min signers from {agreed} to {min_signers} \
+             after the invite"
+        );
+    }
+    let charter: String = request
+        .extract_object_for_parameter("charter")
+        .context("Request does not restate the group charter")?;
+    if charter != group_record.charter() {
+        bail!("Coordinator changed the group charter after the invite");
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// Continuation checks
+// -----------------------------------------------------------------------------
+
+/// Checks that a coordinator request returns the continuation from this
+/// participant's previous response.
+///
+/// Each response carries, as its GSTP state, the ARID the participant will
+/// listen on for the next request; only the participant can open it, and the
+/// coordinator must send it back unchanged. A request that omits it, or
+/// whose continuation names another ARID, does not answer that response: it
+/// was injected by a third party or replayed from another step or group.
+pub fn check_request_continuation(
+    request: &SealedRequest,
+    listening_at_arid: &ARID,
+) -> Result<()> {
+    let state = request.state().context(
+        "Request does not return the continuation from your previous \
+         response; it may have been injected or replayed",
+    )?;
+    let expected: ARID = state
+        .extract_subject()
+        .context("Request continuation does not hold an ARID")?;
+    if expected != *listening_at_arid {
+        bail!(
+            "Request continuation expects it at {}, but it was fetched from \
+             {}; it may have been replayed from another step",
+            expected.ur_string(),
+            listening_at_arid.ur_string()
+        );
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// Round 1 package checks
+// -----------------------------------------------------------------------------
+
+/// Checks that each Round 1 package commits to exactly `min_signers`
+/// polynomial coefficients before the packages are forwarded for Round 2.
+///
+/// A participant who ran Round 1 with a different threshold would otherwise
+/// corrupt every participant's key derivation.
+pub fn check_round1_commitments(
+    packages: &[(XID, frost::keys::dkg::round1::Package)],
+    min_signers: usize,
+) -> Result<()> {
+    for (xid, package) in packages {
+        let coefficients = commitment_len(xid, package)?;
+        if coefficients != min_signers {
+            bail!(
+                "Round 1 package from {} commits to {coefficients} \
+                 coefficients, but the group's min signers is {min_signers}; \
+                 the participant may have run Round 1 with the wrong threshold",
+                xid.ur_string()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Checks Round 1 packages against `group_record`'s threshold.
+///
+/// A refresh shares a polynomial whose constant term is zero, and FROST leaves
+/// that term's commitment out, so each refresh package commits to one
+/// coefficient fewer than `min_signers`.
+pub fn check_group_round1_commitments(
+    packages: &[(XID, frost::keys::dkg::round1::Package)],
+    group_record: &GroupRecord,
+) -> Result<()> {
+    let min_signers = group_record.min_signers();
+    if group_record.refresh_of().is_none() {
+        return check_round1_commitments(packages, min_signers);
+    }
+    for (xid, package) in packages {
+        let coefficients = commitment_len(xid, package)?;
+        if coefficients + 1 != min_signers {
+            bail!(
+                "Refresh Round 1 package from {} commits to {coefficients} \
+                 coefficients, but refreshing a group with min signers \
+                 {min_signers} takes {}",
+                xid.ur_string(),
+                min_signers - 1
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Fails unless the Round 1 packages come from exactly the group's signers.
+///
+/// The invite fixed whether the coordinator signs: a coordinator that signs
+/// must have answered its own invite, and one that only coordinates must not
+/// appear among the packages.
+pub fn check_round1_signers(
+    packages: &[(XID, frost::keys::dkg::round1::Package)],
+    group_record: &GroupRecord,
+) -> Result<()> {
+    let coordinator = group_record.coordinator().xid();
+    let collected: HashSet<&XID> =
+        packages.iter().map(|(xid, _)| xid).collect();
+    if group_record.coordinator_signs() {
+        if !collected.contains(coordinator) {
+            bail!(
+                "The coordinator {} is a signer in this group but its own \
+                 Round 1 package was not collected; answer the invite with \
+                 `frost dkg participant round1` first",
+                coordinator.ur_string()
+            );
+        }
+    } else if collected.contains(coordinator) {
+        bail!(
+            "Collected a Round 1 package from the coordinator {}, but the \
+             group was invited with the coordinator only coordinating",
+            coordinator.ur_string()
+        );
+    }
+    let expected: HashSet<&XID> = group_record
+        .participants()
+        .iter()
+        .map(|participant| participant.xid())
+        .collect();
+    if let Some(xid) = expected.difference(&collected).next() {
+        bail!("No Round 1 package from invited signer {}", xid.ur_string());
+    }
+    if let Some(xid) = collected.difference(&expected).next() {
+        bail!(
+            "Round 1 package from {}, who was not invited as a signer",
+            xid.ur_string()
+        );
+    }
+    Ok(())
+}
+
+fn commitment_len(
+    xid: &XID,
+    package: &frost::keys::dkg::round1::Package,
+) -> Result<usize> {
+    Ok(package
+        .commitment()
+        .serialize()
+        .with_context(|| {
+            format!(
+                "Round 1 package from {} has an invalid commitment",
+                xid.ur_string()
+            )
+        })?
+        .len())
+}
+
+// -----------------------------------------------------------------------------
+// Refresh checks
+// -----------------------------------------------------------------------------
+
+/// FROST identifiers for the participants of a refresh of `original`.
+///
+/// A refresh only rotates shares, so every participant keeps the identifier
+/// it holds in the original group, whatever the new roster would derive.
+pub fn refresh_identifiers(
+    original: &GroupRecord,
+    participants: &[XID],
+) -> Result<HashMap<XID, frost::Identifier>> {
+    let identifiers = original.identifiers()?;
+    participants
+        .iter()
+        .map(|xid| {
+            let identifier = identifiers.get(xid).with_context(|| {
+                format!(
+                    "{} is not a participant in the group being refreshed",
+                    xid.ur_string()
+                )
+            })?;
+            Ok((*xid, *identifier))
+        })
+        .collect()
+}
+
+/// The [`ShareRepair`] for a refresh of `original` by `participants` that
+/// gives `replacement` the share `replaces` held.
+///
+/// Every other participant helps rebuild the share, so there must be at
+/// least the group's min signers of them.
+pub fn share_repair(
+    original: &GroupRecord,
+    participants: &[XID],
+    replacement: XID,
+    replaces: XID,
+) -> Result<ShareRepair> {
+    let original_identifiers = original.identifiers()?;
+    if original_identifiers.contains_key(&replacement) {
+        bail!(
+            "{} already has a place in the group being refreshed; the \
+             replacement must be a new participant",
+            replacement.ur_string()
+        );
+    }
+    if !original.participants().iter().any(|p| p.xid() == &replaces) {
+        bail!(
+            "{} is not a participant in the group being refreshed",
+            replaces.ur_string()
+        );
+    }
+    if !participants.contains(&replacement) || participants.contains(&replaces)
+    {
+        bail!(
+            "A share repair refresh must include the replacement {} instead \
+             of {}",
+            replacement.ur_string(),
+            replaces.ur_string()
+        );
+    }
+    let helpers: Vec<XID> = participants
+        .iter()
+        .copied()
+        .filter(|xid| *xid != replacement)
+        .collect();
+    if helpers.len() < original.min_signers() {
+        bail!(
+            "Only {} participants would help rebuild the lost share, fewer \
+             than the group's {} min signers",
+            helpers.len(),
+            original.min_signers()
+        );
+    }
+    let mut identifiers: BTreeMap<XID, frost::Identifier> =
+        refresh_identifiers(original, &helpers)?
+            .into_iter()
+            .collect();
+    identifiers.insert(replacement, original_identifiers[&replaces]);
+    Ok(ShareRepair { replacement, replaces, identifiers })
+}
+
+/// The participant of `group_record`, a refresh, that a share repair gives a
+/// lost share to: the one who is not a participant of the group being
+/// refreshed. `None` for a plain refresh or a group that is not a refresh.
+pub fn refresh_replacement(
+    registry: &Registry,
+    group_record: &GroupRecord,
+) -> Result<Option<XID>> {
+    let Some(original_id) = group_record.refresh_of() else {
+        return Ok(None);
+    };
+    let original = refreshed_group(registry, &original_id)?;
+    Ok(group_record
+        .participants()
+        .iter()
+        .map(|participant| *participant.xid())
+        .find(|xid| !original.participants().iter().any(|p| p.xid() == xid)))
+}
+
+/// Fails unless `key`, the verifying key a DKG produced for `group_record`,
+/// is the key of the group it refreshes. Groups that are not refreshes pass.
+pub fn check_refreshed_verifying_key(
+    registry: &Registry,
+    group_record: &GroupRecord,
+    key: &SigningPublicKey,
+) -> Result<()> {
+    let Some(original_id) = group_record.refresh_of() else {
+        return Ok(());
+    };
+    let original_key = refreshed_group(registry, &original_id)?
+        .verifying_key()
+        .context("The group being refreshed has no verifying key")?;
+    if original_key != key {
+        bail!(
+            "Refresh produced verifying key {} but group {} has {}; the \
+             refreshed shares must not be used",
+            key.ur_string(),
+            original_id.ur_string(),
+            original_key.ur_string()
+        );
+    }
+    Ok(())
+}
+
+/// The finalized group a refresh rotates the shares of.
+pub fn refreshed_group<'a>(
+    registry: &'a Registry,
+    group_id: &ARID,
+) -> Result<&'a GroupRecord> {
+    let group = registry.group(group_id).with_context(|| {
+        format!(
+            "Group {} being refreshed is not in the registry",
+            group_id.ur_string()
+        )
+    })?;
+    if !group.status().is_finalized() {
+        bail!(
+            "Group {} being refreshed has not finished its DKG",
+            group_id.ur_string()
+        );
+    }
+    Ok(group)
+}
+
+// -----------------------------------------------------------------------------
+// Response recipients
+// -----------------------------------------------------------------------------
+
+/// Returns the XID document a response to `request` should be sealed to.
+///
+/// Responses are sealed to the request sender unless the request names a
+/// different `recipient` (for example, a separate aggregator). A named
+/// recipient must be a participant in the registry, since its encryption key
+/// comes from there.
+pub fn response_recipient(
+    request: &SealedRequest,
+    registry: &Registry,
+    sender_doc: XIDDocument,
+) -> Result<XIDDocument> {
+    let Some(envelope) = request.objects_for_parameter("recipient").first()
+    else {
+        return Ok(sender_doc);
+    };
+    let xid: XID = envelope
+        .extract_subject()
+        .context("Invalid recipient parameter in request")?;
+    if xid == sender_doc.xid() {
+        return Ok(sender_doc);
+    }
+    let record = registry.participant(&xid).with_context(|| {
+        format!(
+            "Response recipient {} is not a participant in the registry",
+            xid.ur_string()
+        )
+    })?;
+    Ok(record.xid_document().clone())
+}
+
+/// Resolves `--also-notify` participants, who can open a response alongside
+/// its recipient (for example, a backup coordinator or an observer). Each
+/// must be in the registry, since its encryption key comes from there.
+pub fn resolve_also_notify(
+    registry: &Registry,
+    inputs: &[String],
+) -> Result<Vec<XIDDocument>> {
+    Ok(resolve_participants(registry, inputs)?
+        .into_iter()
+        .map(|(_, record)| record.xid_document().clone())
+        .collect())
+}
+
+/// Signs `response` and seals it to `recipient` and every `also_notify`
+/// party, any of whom can decrypt it.
+pub fn seal_response(
+    response: &SealedResponse,
+    valid_until: Option<Date>,
+    signer_private_keys: &PrivateKeys,
+    recipient: &XIDDocument,
+    also_notify: &[XIDDocument],
+) -> Result<Envelope> {
+    let mut recipients = vec![recipient];
+    for document in also_notify {
+        if recipients.iter().all(|r| r.xid() != document.xid()) {
+            recipients.push(document);
+        }
+    }
+    Ok(response.to_envelope_for_recipients(
+        valid_until,
+        Some(signer_private_keys),
+        &recipients,
+    )?)
+}
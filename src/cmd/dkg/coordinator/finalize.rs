@@ -1,4 +1,10 @@
-use std::{fs, path::Path, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
 
 use anyhow::{Context, Result, bail};
 use bc_components::{ARID, JSON, SigningPublicKey, XID};
@@ -10,16 +16,27 @@ use tokio::runtime::Runtime;
 use crate::{
     cmd::{
         busy::get_with_indicator,
+        common::{
+            CollectionTimeout, PartialCollection, collection_timeout,
+            load_registry, write_atomically,
+        },
         dkg::common::{
-            OptionalStorageSelector, group_state_dir, parse_arid_ur,
-            signing_key_from_verifying,
+            GroupStatePaths, OptionalStorageSelector,
+            check_refreshed_verifying_key, ensure_owner_is_coordinator,
+            parse_group_id, read_ur_input, signing_key_from_verifying,
         },
         is_verbose,
-        parallel::{CollectionResult, ParallelFetchConfig, parallel_fetch},
+        parallel::{
+            CollectionResult, FetchError, ParallelFetchConfig, parallel_fetch,
+        },
         registry::participants_file_path,
-        storage::StorageClient,
+        report::{CollectionReport, report_name},
+        storage::{
+            StorageClient, cleanup_collected, decompress_envelope,
+            shared_runtime,
+        },
     },
-    registry::Registry,
+    registry::{PendingRequests, Registry},
 };
 
 /// Collect finalize responses (key/public key packages) from all participants
@@ -38,11 +55,31 @@ pub struct CommandArgs {
     #[arg(long = "timeout", value_name = "SECONDS")]
     timeout: Option<u64>,
 
+    /// Collect until this RFC 3339 date and time instead of waiting a
+    /// relative --timeout
+    #[arg(long = "deadline", value_name = "DATE", conflicts_with = "timeout")]
+    deadline: Option<String>,
+
     /// Use parallel fetch with interactive progress display
     #[arg(long)]
     parallel: bool,
 
+    /// Delete collected responses from storage once they are saved locally
+    #[arg(long = "cleanup")]
+    cleanup: bool,
+
+    /// Merge this pass's responses into an existing collected_finalize.json,
+    /// fetching only participants not already in it
+    #[arg(long = "merge")]
+    merge: bool,
+
+    /// Write a JSON report of the collection (per-participant status and
+    /// timing, output files) to this path, even if collection fails
+    #[arg(long = "report", value_name = "PATH")]
+    report: Option<PathBuf>,
+
     /// Group ID to collect finalize responses for
+    /// (`-` reads it from stdin)
     #[arg(value_name = "GROUP_ID")]
     group_id: String,
 }
@@ -52,10 +89,12 @@ impl CommandArgs {
         let selection = self.storage.resolve()?;
         let selection = selection
             .context("Hubert storage is required for finalize collect")?;
+        let timeout =
+            collection_timeout(self.timeout, self.deadline.as_deref())?;
 
         let registry_path = participants_file_path(self.registry.clone())?;
         let mut registry =
-            Registry::load(&registry_path).with_context(|| {
+            load_registry(&registry_path).with_context(|| {
                 format!(
                     "Failed to load registry at {}",
                     registry_path.display()
@@ -67,19 +106,14 @@ impl CommandArgs {
             .context("Registry owner is required")?
             .clone();
 
-        let group_id = parse_arid_ur(&self.group_id)?;
+        let group_id =
+            parse_group_id(&registry, &read_ur_input(&self.group_id)?)?;
         let group_record = registry
             .group(&group_id)
             .context("Group not found in registry")?
             .clone();
 
-        if group_record.coordinator().xid() != &owner.xid() {
-            bail!(
-                "Only the coordinator can collect finalize responses. Coordinator: {}, Owner: {}",
-                group_record.coordinator().xid().ur_string(),
-                owner.xid().ur_string()
-            );
-        }
+        ensure_owner_is_coordinator(&group_record, &owner)?;
 
         let pending_requests = group_record.pending_requests();
         if pending_requests.is_empty() {
@@ -89,193 +123,147 @@ impl CommandArgs {
             );
         }
 
-        let runtime = Runtime::new()?;
-        let client = runtime.block_on(async {
-            StorageClient::from_selection(selection).await
-        })?;
+        let runtime = shared_runtime()?;
+        let client = runtime
+            .block_on(async { StorageClient::shared(selection).await })?;
 
         let coordinator_keys = owner
             .xid_document()
             .inception_private_keys()
             .context("Coordinator XID document has no private keys")?;
 
-        if self.parallel {
-            // Parallel path with progress display
-            let client = Arc::new(client);
+        let collected_path = GroupStatePaths::new(&registry_path)
+            .collected_finalize_path(&group_id);
+        let already_collected: BTreeSet<XID> = if self.merge {
+            load_collected_finalize(&collected_path)?
+                .into_keys()
+                .collect()
+        } else {
+            BTreeSet::new()
+        };
 
-            let collection = runtime.block_on(async {
+        let collection = if self.parallel {
+            // Parallel path with progress display
+            runtime.block_on(async {
                 collect_finalize_parallel(
                     Arc::clone(&client),
                     &registry,
                     pending_requests,
+                    &already_collected,
                     coordinator_keys,
                     &group_id,
-                    self.timeout,
+                    timeout.seconds(),
                 )
                 .await
-            })?;
+            })?
+        } else {
+            collect_finalize(
+                runtime,
+                &client,
+                &registry,
+                pending_requests,
+                &already_collected,
+                coordinator_keys,
+                &group_id,
+                &timeout,
+            )
+        };
 
+        let mut report = CollectionReport::from_collection(
+            FINALIZE_PHASE,
+            &group_id,
+            &collection,
+            |xid| report_name(&registry, xid),
+        );
+        let fetched: Vec<XID> =
+            collection.successes.iter().map(|(xid, _)| *xid).collect();
+        let result = if self.merge {
+            merge_finalize_results(
+                &registry_path,
+                &mut registry,
+                &group_id,
+                pending_requests,
+                collection.successes,
+            )
+        } else {
             finalize_collection_results(
                 &collection,
                 &registry_path,
                 &mut registry,
                 &group_id,
-            )?;
-        } else {
-            // Sequential path (original behavior)
-            let mut collected: Vec<FinalizeEntry> = Vec::new();
-            let mut errors: Vec<(XID, String)> = Vec::new();
-            let mut group_verifying_key: Option<SigningPublicKey> = None;
-
-            if is_verbose() {
-                eprintln!(
-                    "Collecting finalize responses from {} participants...",
-                    pending_requests.len()
-                );
-            }
-
-            for (participant_xid, collect_from_arid) in
-                pending_requests.iter_collect()
-            {
-                let name = registry
-                    .participant(participant_xid)
-                    .and_then(|r| r.pet_name().map(|s| s.to_owned()))
-                    .unwrap_or_else(|| participant_xid.ur_string());
-
-                match fetch_finalize_response(
-                    &runtime,
+            )
+        };
+        // A merge pass writes collected_finalize.json even when participants
+        // are still missing
+        let written = match &result {
+            Ok(()) => true,
+            Err(err) => err.downcast_ref::<PartialCollection>().is_some(),
+        };
+        if written {
+            report.add_output(&collected_path);
+            if self.cleanup {
+                runtime.block_on(cleanup_collected(
                     &client,
-                    collect_from_arid,
-                    self.timeout,
-                    coordinator_keys,
-                    &group_id,
-                    participant_xid,
-                    &name,
-                ) {
-                    Ok(entry) => match signing_key_from_verifying(
-                        entry.public_key_package.verifying_key(),
-                    ) {
-                        Ok(signing_key) => {
-                            if let Some(existing) = &group_verifying_key {
-                                if existing != &signing_key {
-                                    if is_verbose() {
-                                        eprintln!(
-                                            "error: group verifying key mismatch"
-                                        );
-                                    }
-                                    errors.push((
-                                        *participant_xid,
-                                        "Group verifying key mismatch across responses"
-                                            .to_string(),
-                                    ));
-                                    continue;
-                                }
-                            } else {
-                                group_verifying_key = Some(signing_key);
-                            }
-
-                            collected.push(entry);
-                        }
-                        Err(err) => {
-                            if is_verbose() {
-                                eprintln!("error: {}", err);
-                            }
-                            errors.push((*participant_xid, err.to_string()));
-                        }
-                    },
-                    Err(err) => {
-                        if is_verbose() {
-                            eprintln!("error: {}", err);
-                        }
-                        errors.push((*participant_xid, err.to_string()));
-                    }
-                }
-            }
-
-            if !errors.is_empty() {
-                if is_verbose() {
-                    eprintln!();
-                    eprintln!(
-                        "Failed to collect from {} participants:",
-                        errors.len()
-                    );
-                    for (xid, error) in &errors {
-                        eprintln!("  {}: {}", xid.ur_string(), error);
-                    }
-                }
-                bail!(
-                    "Finalize collection incomplete: {} of {} responses failed",
-                    errors.len(),
-                    pending_requests.len()
-                );
+                    &pending_requests.collect_arids_for(&fetched),
+                ));
             }
+        }
+        report.write_to(self.report.as_deref())?;
+        result
+    }
+}
 
-            // Persist collected finalize data
-            let state_dir = group_state_dir(&registry_path, &group_id);
-            fs::create_dir_all(&state_dir).with_context(|| {
-                format!(
-                    "Failed to create group state directory {}",
-                    state_dir.display()
-                )
-            })?;
+/// Phase name recorded in `--report` output.
+const FINALIZE_PHASE: &str = "dkg_finalize";
 
-            let collected_path = state_dir.join("collected_finalize.json");
-            let mut root = serde_json::Map::new();
-            for entry in &collected {
-                let mut m = serde_json::Map::new();
-                m.insert(
-                    "key_package".to_string(),
-                    serde_json::to_value(&entry.key_package)
-                        .expect("key_package JSON"),
-                );
-                m.insert(
-                    "public_key_package".to_string(),
-                    serde_json::to_value(&entry.public_key_package)
-                        .expect("public key JSON"),
-                );
-                root.insert(
-                    entry.participant.ur_string(),
-                    serde_json::Value::Object(m),
-                );
-            }
-            fs::write(&collected_path, serde_json::to_vec_pretty(&root)?)
-                .with_context(|| {
-                    format!("Failed to write {}", collected_path.display())
-                })?;
+/// Collect finalize responses one participant at a time, skipping
+/// participants whose packages are already collected.
+#[allow(clippy::too_many_arguments)]
+fn collect_finalize(
+    runtime: &Runtime,
+    client: &StorageClient,
+    registry: &Registry,
+    pending_requests: &PendingRequests,
+    already_collected: &BTreeSet<XID>,
+    coordinator_keys: &bc_components::PrivateKeys,
+    group_id: &ARID,
+    timeout: &CollectionTimeout,
+) -> CollectionResult<FinalizeResponseData> {
+    if is_verbose() {
+        eprintln!(
+            "Collecting finalize responses from {} participants...",
+            pending_requests.len()
+        );
+    }
 
-            // Update registry pending requests cleared
-            let group_record = registry
-                .group_mut(&group_id)
-                .context("Group not found in registry")?;
-            if let Some(key) = &group_verifying_key {
-                group_record.set_verifying_key(key.clone());
-            }
-            group_record.clear_pending_requests();
-            registry.save(&registry_path)?;
-
-            if is_verbose() {
-                eprintln!();
-                eprintln!(
-                    "Collected {} finalize responses. Saved to {}",
-                    collected.len(),
-                    collected_path.display()
-                );
-                if let Some(key) = group_verifying_key {
-                    eprintln!("{}", key.ur_string());
-                }
-            } else if let Some(key) = group_verifying_key {
-                println!("{}", key.ur_string());
-            }
+    let mut collection = CollectionResult::new();
+    for (participant_xid, collect_from_arid) in pending_requests.iter_collect()
+    {
+        if already_collected.contains(participant_xid) {
+            continue;
         }
-
-        Ok(())
+        let name = report_name(registry, participant_xid)
+            .unwrap_or_else(|| participant_xid.ur_string());
+
+        let started = Instant::now();
+        let result = fetch_finalize_response(
+            runtime,
+            client,
+            collect_from_arid,
+            timeout.seconds(),
+            coordinator_keys,
+            group_id,
+            participant_xid,
+            &name,
+        );
+        if is_verbose()
+            && let Err(err) = &result
+        {
+            eprintln!("error: {}", err);
+        }
+        collection.record(*participant_xid, result, started.elapsed());
     }
-}
-
-struct FinalizeEntry {
-    participant: XID,
-    key_package: frost_ed25519::keys::KeyPackage,
-    public_key_package: frost_ed25519::keys::PublicKeyPackage,
+    collection
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -288,7 +276,7 @@ fn fetch_finalize_response(
     expected_group: &ARID,
     expected_participant: &XID,
     participant_name: &str,
-) -> Result<FinalizeEntry> {
+) -> Result<FinalizeResponseData> {
     let envelope = get_with_indicator(
         runtime,
         client,
@@ -296,65 +284,14 @@ fn fetch_finalize_response(
         participant_name,
         timeout,
     )?
-    .context("Finalize response not found in Hubert storage")?;
+    .ok_or(FetchError::NotYetAvailable)?;
 
-    let now = Date::now();
-    let sealed = SealedResponse::try_from_encrypted_envelope(
+    validate_and_extract_finalize_response(
         &envelope,
-        None,
-        Some(now),
         coordinator_keys,
-    )?;
-
-    if let Ok(error) = sealed.error() {
-        let reason = error
-            .object_for_predicate("reason")
-            .ok()
-            .and_then(|e| e.extract_subject::<String>().ok())
-            .unwrap_or_else(|| "unknown reason".to_string());
-        bail!("Participant reported error: {}", reason);
-    }
-
-    let result = sealed.result().context("Finalize response has no result")?;
-    result
-        .check_subject_unit()?
-        .check_type("dkgFinalizeResponse")?;
-
-    let group_id: ARID = result.extract_object_for_predicate("group")?;
-    if &group_id != expected_group {
-        bail!(
-            "Group {} does not match expected {}",
-            group_id.ur_string(),
-            expected_group.ur_string()
-        );
-    }
-
-    let participant_xid: XID =
-        result.extract_object_for_predicate("participant")?;
-    if &participant_xid != expected_participant {
-        bail!(
-            "Participant {} does not match expected {}",
-            participant_xid.ur_string(),
-            expected_participant.ur_string()
-        );
-    }
-
-    let key_json: JSON = result.extract_object_for_predicate("key_package")?;
-    let pub_json: JSON =
-        result.extract_object_for_predicate("public_key_package")?;
-
-    let key_package: frost_ed25519::keys::KeyPackage =
-        serde_json::from_slice(key_json.as_bytes())
-            .context("Failed to parse key_package")?;
-    let public_key_package: frost_ed25519::keys::PublicKeyPackage =
-        serde_json::from_slice(pub_json.as_bytes())
-            .context("Failed to parse public_key_package")?;
-
-    Ok(FinalizeEntry {
-        participant: participant_xid,
-        key_package,
-        public_key_package,
-    })
+        expected_group,
+        expected_participant,
+    )
 }
 
 // -----------------------------------------------------------------------------
@@ -362,22 +299,26 @@ fn fetch_finalize_response(
 // -----------------------------------------------------------------------------
 
 /// Data extracted from a successful finalize response.
-struct FinalizeResponseData {
-    key_package: frost_ed25519::keys::KeyPackage,
-    public_key_package: frost_ed25519::keys::PublicKeyPackage,
+#[derive(Debug, Clone)]
+pub struct FinalizeResponseData {
+    pub key_package: frost_ed25519::keys::KeyPackage,
+    pub public_key_package: frost_ed25519::keys::PublicKeyPackage,
 }
 
-/// Collect finalize responses in parallel with progress display.
+/// Collect finalize responses in parallel with progress display, skipping
+/// participants whose packages are already collected.
 async fn collect_finalize_parallel(
     client: Arc<StorageClient>,
     registry: &Registry,
-    pending_requests: &crate::registry::PendingRequests,
+    pending_requests: &PendingRequests,
+    already_collected: &BTreeSet<XID>,
     coordinator_keys: &bc_components::PrivateKeys,
     expected_group_id: &ARID,
     timeout: Option<u64>,
 ) -> Result<CollectionResult<FinalizeResponseData>> {
     let requests: Vec<(XID, ARID, String)> = pending_requests
         .iter_collect()
+        .filter(|(xid, _)| !already_collected.contains(*xid))
         .map(|(xid, arid)| {
             let name = registry
                 .participant(xid)
@@ -416,7 +357,8 @@ fn validate_and_extract_finalize_response(
         None,
         Some(now),
         coordinator_keys,
-    )?;
+    )
+    .map_err(FetchError::decode)?;
 
     if let Ok(error) = sealed.error() {
         let reason = error
@@ -424,10 +366,18 @@ fn validate_and_extract_finalize_response(
             .ok()
             .and_then(|e| e.extract_subject::<String>().ok())
             .unwrap_or_else(|| "unknown reason".to_string());
-        bail!("Participant reported error: {}", reason);
+        return Err(FetchError::Rejected(format!(
+            "Participant reported error: {reason}"
+        ))
+        .into());
     }
 
-    let result = sealed.result().context("Finalize response has no result")?;
+    let result = decompress_envelope(
+        sealed
+            .result()
+            .context("Finalize response has no result")?
+            .clone(),
+    )?;
     result
         .check_subject_unit()?
         .check_type("dkgFinalizeResponse")?;
@@ -533,8 +483,16 @@ fn finalize_collection_results(
         }
     }
 
+    if let Some(key) = &group_verifying_key {
+        let group_record = registry
+            .group(group_id)
+            .context("Group not found in registry")?;
+        check_refreshed_verifying_key(registry, group_record, key)?;
+    }
+
     // Persist collected finalize data
-    let state_dir = group_state_dir(registry_path, group_id);
+    let paths = GroupStatePaths::new(registry_path);
+    let state_dir = paths.group_dir(group_id);
     fs::create_dir_all(&state_dir).with_context(|| {
         format!(
             "Failed to create group state directory {}",
@@ -542,7 +500,7 @@ fn finalize_collection_results(
         )
     })?;
 
-    let collected_path = state_dir.join("collected_finalize.json");
+    let collected_path = paths.collected_finalize_path(group_id);
     let mut root = serde_json::Map::new();
     for (xid, data) in &collection.successes {
         let mut m = serde_json::Map::new();
@@ -557,7 +515,7 @@ fn finalize_collection_results(
         );
         root.insert(xid.ur_string(), serde_json::Value::Object(m));
     }
-    fs::write(&collected_path, serde_json::to_vec_pretty(&root)?)
+    write_atomically(&collected_path, serde_json::to_vec_pretty(&root)?)
         .with_context(|| {
             format!("Failed to write {}", collected_path.display())
         })?;
@@ -588,3 +546,172 @@ fn finalize_collection_results(
 
     Ok(())
 }
+
+// -----------------------------------------------------------------------------
+// Merged collections
+// -----------------------------------------------------------------------------
+
+/// Finalize packages by participant, as saved in collected_finalize.json.
+pub type CollectedFinalize = BTreeMap<XID, FinalizeResponseData>;
+
+/// Reads collected_finalize.json, or nothing if no pass has written it yet.
+pub fn load_collected_finalize(path: &Path) -> Result<CollectedFinalize> {
+    let mut collected = CollectedFinalize::new();
+    if !path.exists() {
+        return Ok(collected);
+    }
+    let root: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(
+            &fs::read(path).with_context(|| {
+                format!("Failed to read {}", path.display())
+            })?,
+        )
+        .context("Invalid collected_finalize.json")?;
+    for (xid_str, value) in root {
+        let xid = XID::from_ur_string(&xid_str)
+            .context("Invalid participant XID in collected_finalize.json")?;
+        let key_package = serde_json::from_value(
+            value.get("key_package").cloned().with_context(|| {
+                format!("key_package missing for {xid_str}")
+            })?,
+        )
+        .context("Failed to parse key_package")?;
+        let public_key_package = serde_json::from_value(
+            value.get("public_key_package").cloned().with_context(|| {
+                format!("public_key_package missing for {xid_str}")
+            })?,
+        )
+        .context("Failed to parse public_key_package")?;
+        collected.insert(
+            xid,
+            FinalizeResponseData { key_package, public_key_package },
+        );
+    }
+    Ok(collected)
+}
+
+/// Adds newly collected packages to `existing`, replacing any earlier entry
+/// for the same participant, and returns the group verifying key they all
+/// agree on. Fails without changing `existing` if the combined set holds
+/// more than one verifying key.
+pub fn merge_collected_finalize(
+    existing: &mut CollectedFinalize,
+    collected: impl IntoIterator<Item = (XID, FinalizeResponseData)>,
+) -> Result<Option<SigningPublicKey>> {
+    let mut merged = existing.clone();
+    merged.extend(collected);
+
+    let mut group_verifying_key: Option<(XID, SigningPublicKey)> = None;
+    for (xid, data) in &merged {
+        let signing_key =
+            signing_key_from_verifying(data.public_key_package.verifying_key())
+                .with_context(|| {
+                    format!(
+                        "Failed to extract verifying key for {}",
+                        xid.ur_string()
+                    )
+                })?;
+        match &group_verifying_key {
+            Some((first, key)) if key != &signing_key => bail!(
+                "Merged finalize responses disagree on the group verifying \
+                 key: {} and {} report different keys",
+                first.ur_string(),
+                xid.ur_string()
+            ),
+            Some(_) => {}
+            None => group_verifying_key = Some((*xid, signing_key)),
+        }
+    }
+
+    *existing = merged;
+    Ok(group_verifying_key.map(|(_, key)| key))
+}
+
+fn write_collected_finalize(
+    path: &Path,
+    collected: &CollectedFinalize,
+) -> Result<()> {
+    let mut root = serde_json::Map::new();
+    for (xid, data) in collected {
+        let mut m = serde_json::Map::new();
+        m.insert(
+            "key_package".to_string(),
+            serde_json::to_value(&data.key_package).expect("key_package JSON"),
+        );
+        m.insert(
+            "public_key_package".to_string(),
+            serde_json::to_value(&data.public_key_package)
+                .expect("public key JSON"),
+        );
+        root.insert(xid.ur_string(), serde_json::Value::Object(m));
+    }
+    write_atomically(path, serde_json::to_vec_pretty(&root)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// `--merge`: folds this pass's responses into collected_finalize.json and,
+/// once every pending participant is in it, records the group verifying key
+/// as a full collection would. Otherwise returns a [`PartialCollection`] and
+/// leaves the pending requests in place for the next pass.
+fn merge_finalize_results(
+    registry_path: &Path,
+    registry: &mut Registry,
+    group_id: &ARID,
+    pending_requests: &PendingRequests,
+    collected: impl IntoIterator<Item = (XID, FinalizeResponseData)>,
+) -> Result<()> {
+    let paths = GroupStatePaths::new(registry_path);
+    let collected_path = paths.collected_finalize_path(group_id);
+
+    // Reread under the lock so a concurrent pass's entries are not lost
+    let lock = paths.lock_group(group_id)?;
+    let mut merged = load_collected_finalize(&collected_path)?;
+    let group_verifying_key = merge_collected_finalize(&mut merged, collected)?;
+    if let Some(key) = &group_verifying_key {
+        let group_record = registry
+            .group(group_id)
+            .context("Group not found in registry")?;
+        check_refreshed_verifying_key(registry, group_record, key)?;
+    }
+    write_collected_finalize(&collected_path, &merged)?;
+    drop(lock);
+
+    let missing = pending_requests
+        .iter_collect()
+        .filter(|(xid, _)| !merged.contains_key(*xid))
+        .count();
+    if missing > 0 {
+        return Err(PartialCollection {
+            phase: "Finalize",
+            collected: merged.len(),
+            failed: missing,
+            path: collected_path,
+        }
+        .into());
+    }
+
+    let group_record = registry
+        .group_mut(group_id)
+        .context("Group not found in registry")?;
+    if let Some(key) = &group_verifying_key {
+        group_record.set_verifying_key(key.clone());
+    }
+    group_record.clear_pending_requests();
+    registry.save(registry_path)?;
+
+    if is_verbose() {
+        eprintln!();
+        eprintln!(
+            "Merged {} finalize responses. Saved to {}",
+            merged.len(),
+            collected_path.display()
+        );
+        if let Some(key) = group_verifying_key {
+            eprintln!("{}", key.ur_string());
+        }
+    } else if let Some(key) = group_verifying_key {
+        println!("{}", key.ur_string());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use anyhow::{Context, Result, bail};
+use bc_components::{ARID, XIDProvider};
+use bc_envelope::prelude::*;
+use bc_xid::{XIDDocument, XIDVerifySignature};
+use clap::Parser;
+
+use crate::{
+    cmd::{
+        busy::get_with_indicator,
+        common::load_registry,
+        dkg::common::{
+            OptionalStorageSelector, parse_arid_ur, parse_envelope_ur,
+            participant_names_from_registry, read_ur_input,
+        },
+        registry::participants_file_path,
+        storage::{StorageClient, shared_runtime},
+    },
+    dkg::{
+        CharterStatement, IdentifierScheme, extract_charter_attestation,
+        extract_identifier_scheme, extract_valid_from,
+    },
+};
+
+/// Inspect a DKG invite composed by this coordinator.
+///
+/// A preview envelope (`invite --preview`) is decoded and its charter,
+/// minimum signers, validity, and participants are displayed. A sealed invite
+/// (given directly or fetched from Hubert by ARID) is encrypted to the
+/// participants only, so just its envelope metadata is shown.
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(flatten)]
+    storage: OptionalStorageSelector,
+
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Wait up to this many seconds for the invite to appear
+    #[arg(long = "timeout", value_name = "SECONDS")]
+    timeout: Option<u64>,
+
+    /// Invite ARID or envelope (ur:arid or ur:envelope)
+    /// (`-` reads it from stdin)
+    #[arg(value_name = "INVITE")]
+    invite: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let selection = self.storage.resolve()?;
+        if selection.is_none() && self.timeout.is_some() {
+            bail!("--timeout requires Hubert storage parameters");
+        }
+
+        let registry_path = participants_file_path(self.registry.clone())?;
+        let registry = load_registry(&registry_path).with_context(|| {
+            format!("Failed to load registry at {}", registry_path.display())
+        })?;
+        let owner = registry
+            .owner()
+            .context("Registry owner is required to inspect invites")?
+            .clone();
+
+        let invite = read_ur_input(&self.invite)?;
+        let envelope = if let Ok(arid) = parse_arid_ur(&invite) {
+            let selection = selection.context(
+                "Hubert storage parameters are required to retrieve invites by ARID",
+            )?;
+            let runtime = shared_runtime()?;
+            let client = runtime
+                .block_on(async { StorageClient::shared(selection).await })?;
+            get_with_indicator(runtime, &client, &arid, "Invite", self.timeout)?
+                .context("Nothing posted yet at the invite ARID")?
+        } else {
+            parse_envelope_ur(&invite)?
+        };
+
+        if envelope.subject().is_encrypted() {
+            let recipients = envelope
+                .recipients()
+                .context("Sealed invite has no readable recipients")?;
+            println!("Sealed: yes");
+            println!("Recipients: {}", recipients.len());
+            return Ok(());
+        }
+
+        let invite = decode_preview_invite(&envelope, owner.xid_document())?;
+        let participant_names = participant_names_from_registry(
+            &registry,
+            &invite.participants,
+            &owner.xid(),
+            owner.pet_name(),
+        )?;
+
+        println!("Sealed: no");
+        println!("Group: {}", invite.group_id.ur_string());
+        println!("Charter: {}", invite.charter);
+        println!("Min signers: {}", invite.min_signers);
+        if let Some(valid_from) = invite.valid_from {
+            println!("Valid from: {valid_from}");
+        }
+        println!("Valid until: {}", invite.valid_until);
+        println!("Identifier scheme: {}", invite.identifier_scheme);
+        println!("Participants: {}", participant_names.join(", "));
+        println!(
+            "Charter attestation: {}",
+            if invite.charter_signed {
+                "verified"
+            } else {
+                "none"
+            }
+        );
+        if invite.valid_until <= Date::now() {
+            eprintln!("Warning: invite has expired");
+        }
+
+        Ok(())
+    }
+}
+
+struct PreviewInvite {
+    group_id: ARID,
+    charter: String,
+    min_signers: usize,
+    valid_until: Date,
+    valid_from: Option<Date>,
+    participants: Vec<XIDDocument>,
+    charter_signed: bool,
+    identifier_scheme: IdentifierScheme,
+}
+
+/// Decode the signed but unencrypted invite produced by `invite --preview`,
+/// checking that it was signed by the registry owner.
+fn decode_preview_invite(
+    envelope: &Envelope,
+    owner: &XIDDocument,
+) -> Result<PreviewInvite> {
+    let signing_key = owner
+        .inception_key()
+        .context("Registry owner XID document missing inception key")?
+        .public_keys()
+        .signing_public_key();
+    let request_envelope = envelope
+        .verify_signature_from(signing_key)
+        .context("Invite is not signed by the registry owner")?
+        .try_unwrap()?;
+    let request = Request::try_from(request_envelope)
+        .context("Invite is not a GSTP request")?;
+    if request.function() != &Function::from("dkgInvite") {
+        bail!("Unexpected invite function");
+    }
+
+    let mut participants = Vec::new();
+    for participant in request.objects_for_parameter("participant") {
+        let xid_document_envelope = participant.try_unwrap()?;
+        participants.push(XIDDocument::from_envelope(
+            &xid_document_envelope,
+            None,
+            XIDVerifySignature::Inception,
+        )?);
+    }
+
+    let group_id: ARID = request.extract_object_for_parameter("group")?;
+    let charter: String = request.extract_object_for_parameter("charter")?;
+    let min_signers: usize =
+        request.extract_object_for_parameter("minSigners")?;
+    let attestation = extract_charter_attestation(&request)?;
+    if let Some(attestation) = &attestation {
+        let expected = CharterStatement::new(
+            group_id,
+            charter.clone(),
+            min_signers,
+            owner.xid(),
+            participants.iter().map(|p| p.xid()).collect(),
+        );
+        CharterStatement::verify(attestation, owner)?
+            .check_matches(&expected)?;
+    }
+
+    Ok(PreviewInvite {
+        group_id,
+        charter,
+        min_signers,
+        valid_until: request.extract_object_for_parameter("validUntil")?,
+        valid_from: extract_valid_from(&request)?,
+        participants,
+        charter_signed: attestation.is_some(),
+        identifier_scheme: extract_identifier_scheme(&request)?,
+    })
+}
@@ -1,22 +1,42 @@
-use std::time::Duration;
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    str::FromStr,
+    time::Duration,
+};
 
 use anyhow::{Context, Result, bail};
 use bc_components::{ARID, XID};
 use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Parser;
-use tokio::runtime::Runtime;
 
 use crate::{
     DkgInvite,
     cmd::{
         busy::put_with_indicator,
-        dkg::common::{OptionalStorageSelector, resolve_participants},
+        common::{confirm, load_registry, write_atomically},
+        dkg::common::{
+            OptionalStorageSelector, participant_names_from_registry,
+            resolve_participants,
+        },
         registry::participants_file_path,
-        storage::StorageClient,
+        storage::{StorageClient, shared_runtime},
     },
+    dkg::IdentifierScheme,
     registry::{GroupParticipant, GroupRecord, PendingRequests, Registry},
 };
 
+/// Default upper bound on the number of invited participants.
+///
+/// Every participant exchanges a Round 2 package with every other, so an
+/// accidentally huge invite is expensive. Larger groups, up to the `u16`
+/// identifier ceiling, can be requested with `--max-participants`.
+const DEFAULT_MAX_PARTICIPANTS: u16 = 255;
+
+/// How long participants have to respond once an invite is open.
+const INVITE_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
 /// Compose or send a DKG invite.
 #[derive(Debug, Parser)]
 #[group(skip)]
@@ -32,16 +52,74 @@ pub struct CommandArgs {
     #[arg(long = "min-signers", value_name = "N")]
     min_signers: Option<usize>,
 
+    /// Refuse invites listing more than N participants (at most 65535)
+    #[arg(
+        long = "max-participants",
+        value_name = "N",
+        default_value_t = DEFAULT_MAX_PARTICIPANTS,
+        value_parser = clap::value_parser!(u16).range(2..)
+    )]
+    max_participants: u16,
+
+    /// Defer the invite: participants cannot respond before this RFC 3339
+    /// date and time. The invite then expires an hour after it opens
+    #[arg(
+        long = "not-before",
+        value_name = "RFC3339",
+        value_parser = parse_not_before
+    )]
+    not_before: Option<Date>,
+
+    /// How participants derive FROST identifiers: `index` (position in XID
+    /// order) or `hashed` (from each participant's XID, independent of the
+    /// rest of the roster)
+    #[arg(
+        long = "identifier-scheme",
+        value_name = "SCHEME",
+        default_value = "index",
+        value_parser = IdentifierScheme::from_str
+    )]
+    identifier_scheme: IdentifierScheme,
+
     /// Charter statement for the DKG group
     #[arg(long = "charter", value_name = "STRING", default_value = "")]
     charter: String,
 
+    /// Friendly name for the group that later commands accept in place of
+    /// its ur:arid (requires Hubert storage)
+    #[arg(long = "name", value_name = "NAME")]
+    name: Option<String>,
+
+    /// Only coordinate: fail if the registry owner is among the
+    /// participants. Without it, listing yourself makes you a signer who
+    /// answers the invite with `dkg participant round1` like everyone else
+    #[arg(long = "external-coordinator")]
+    external_coordinator: bool,
+
     /// Print the preview invite envelope UR instead of the sealed envelope
     #[arg(long = "preview")]
     preview: bool,
 
+    /// Print who is being invited and on what terms before posting, and ask
+    /// for confirmation when stdin is a terminal
+    #[arg(long = "review")]
+    review: bool,
+
+    /// Write the invite ARID and each participant's response ARID to this
+    /// JSON file before posting (requires Hubert storage)
+    #[arg(long = "arid-file", value_name = "PATH")]
+    arid_file: Option<String>,
+
+    /// Read additional participants from a file (one pet name or ur:xid per
+    /// line; blank lines and `#` comments are ignored)
+    #[arg(long = "participants-file", value_name = "PATH")]
+    participants_file: Option<String>,
+
     /// Participants to include, by pet name or ur:xid identifier
-    #[arg(required = true, value_name = "PARTICIPANT")]
+    #[arg(
+        required_unless_present = "participants_file",
+        value_name = "PARTICIPANT"
+    )]
     participants: Vec<String>,
 }
 
@@ -51,22 +129,67 @@ impl CommandArgs {
         if selection.is_some() && self.preview {
             bail!("--preview cannot be used with Hubert storage options");
         }
+        if selection.is_none() && self.arid_file.is_some() {
+            bail!("--arid-file requires Hubert storage options");
+        }
+        if selection.is_none() && self.name.is_some() {
+            bail!("--name requires Hubert storage options");
+        }
 
         let registry_path = participants_file_path(self.registry.clone())?;
         let mut registry =
-            Registry::load(&registry_path).with_context(|| {
+            load_registry(&registry_path).with_context(|| {
                 format!(
                     "Failed to load registry at {}",
                     registry_path.display()
                 )
             })?;
 
+        let mut participants = self.participants;
+        if let Some(path) = &self.participants_file {
+            participants.extend(read_participants_file(path)?);
+        }
+
         let invite_data = build_invite(
             &registry,
             self.min_signers,
+            usize::from(self.max_participants),
+            self.not_before,
+            self.identifier_scheme,
             self.charter,
-            self.participants,
+            participants,
+            self.external_coordinator,
         )?;
+        let participant_count = invite_data.participant_xids.len();
+        if invite_data.invite.min_signers() == participant_count {
+            eprintln!(
+                "Note: this is an n-of-n group ({participant_count} of \
+                 {participant_count}); all participants must sign. Pass \
+                 --min-signers for a smaller threshold."
+            );
+        }
+        let owner_xid = registry
+            .owner()
+            .context("Registry owner is required to issue invites")?
+            .xid();
+        if invite_data.participant_xids.contains(&owner_xid) {
+            eprintln!(
+                "Note: you are a signer in this group as well as its \
+                 coordinator. Answer the invite with `frost dkg participant \
+                 round1` before collecting Round 1, or pass \
+                 --external-coordinator to only coordinate."
+            );
+        }
+
+        if self.review {
+            print_invite_review(&registry, &invite_data.invite)?;
+            if selection.is_some()
+                && io::stdin().is_terminal()
+                && !confirm("Type 'yes' to send this invite:")?
+            {
+                bail!("Invite not confirmed; nothing was sent");
+            }
+        }
 
         if let Some(selection) = selection {
             // Save group record with pending_requests for Round 1 collection
@@ -86,20 +209,37 @@ impl CommandArgs {
                 coordinator,
                 participants,
             );
+            let arid = ARID::new();
+            if let Some(path) = &self.arid_file {
+                write_arid_file(
+                    path,
+                    &invite_data.invite.group_id(),
+                    &arid,
+                    &invite_data.pending_requests,
+                )?;
+            }
             group_record.set_pending_requests(invite_data.pending_requests);
-            registry
-                .record_group(invite_data.invite.group_id(), group_record)?;
+            group_record
+                .set_identifier_scheme(invite_data.invite.identifier_scheme());
+            group_record.set_identifiers(group_record.identifiers()?);
+            if let Some(attestation) = invite_data.invite.charter_attestation()
+            {
+                group_record.set_charter_attestation(attestation.ur_string());
+            }
+            let group_id = invite_data.invite.group_id();
+            registry.record_group(group_id, group_record)?;
+            if let Some(name) = self.name {
+                registry.set_group_name(&group_id, Some(name))?;
+            }
             registry.save(&registry_path)?;
 
             let envelope = invite_data.invite.to_envelope()?;
-            let arid = ARID::new();
 
-            let runtime = Runtime::new()?;
-            let client = runtime.block_on(async {
-                StorageClient::from_selection(selection).await
-            })?;
+            let runtime = shared_runtime()?;
+            let client = runtime
+                .block_on(async { StorageClient::shared(selection).await })?;
             put_with_indicator(
-                &runtime,
+                runtime,
                 &client,
                 &arid,
                 &envelope,
@@ -119,19 +259,118 @@ impl CommandArgs {
     }
 }
 
-struct InviteData {
-    invite: DkgInvite,
-    participant_xids: Vec<XID>,
-    pending_requests: PendingRequests,
+/// Prints the resolved committee and invite terms so the coordinator can
+/// check them before anything is posted.
+fn print_invite_review(registry: &Registry, invite: &DkgInvite) -> Result<()> {
+    let owner = registry
+        .owner()
+        .context("Registry owner is required to issue invites")?;
+    let documents: Vec<XIDDocument> = invite
+        .participants()
+        .iter()
+        .map(|participant| participant.xid_document().clone())
+        .collect();
+    let names = participant_names_from_registry(
+        registry,
+        &documents,
+        &owner.xid(),
+        owner.pet_name(),
+    )?;
+    eprintln!("Participants: {}", names.join(", "));
+    eprintln!("Min signers: {}", invite.min_signers());
+    eprintln!("Charter: {}", invite.charter());
+    if let Some(valid_from) = invite.valid_from() {
+        eprintln!("Valid from: {valid_from}");
+    }
+    eprintln!("Valid until: {}", invite.valid_until());
+    Ok(())
+}
+
+fn parse_not_before(value: &str) -> Result<Date> {
+    Date::from_string(value).with_context(|| {
+        format!("Invalid --not-before date (expected RFC 3339): {value}")
+    })
+}
+
+/// Records where the invite was posted and where each participant will
+/// respond, so a ceremony can be recovered without the registry.
+fn write_arid_file(
+    path: &str,
+    group_id: &ARID,
+    invite_arid: &ARID,
+    pending_requests: &PendingRequests,
+) -> Result<()> {
+    let mut participants = serde_json::Map::new();
+    for (xid, arid) in pending_requests.iter_collect() {
+        participants.insert(
+            xid.ur_string(),
+            serde_json::Value::String(arid.ur_string()),
+        );
+    }
+    let mut root = serde_json::Map::new();
+    root.insert(
+        "group".to_string(),
+        serde_json::Value::String(group_id.ur_string()),
+    );
+    root.insert(
+        "invite_arid".to_string(),
+        serde_json::Value::String(invite_arid.ur_string()),
+    );
+    root.insert(
+        "participants".to_string(),
+        serde_json::Value::Object(participants),
+    );
+    write_atomically(path, serde_json::to_vec_pretty(&root)?)
+        .with_context(|| format!("Failed to write ARID file {path}"))
 }
 
-fn build_invite(
+fn read_participants_file(path: &str) -> Result<Vec<String>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read participants file {path}"))?;
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_owned)
+        .collect())
+}
+
+pub(super) struct InviteData {
+    pub(super) invite: DkgInvite,
+    pub(super) participant_xids: Vec<XID>,
+    pub(super) pending_requests: PendingRequests,
+}
+
+pub(super) fn build_invite(
     registry: &Registry,
     min_signers_arg: Option<usize>,
+    max_participants: usize,
+    not_before: Option<Date>,
+    identifier_scheme: IdentifierScheme,
     charter: String,
     participants: Vec<String>,
+    external_coordinator: bool,
 ) -> Result<InviteData> {
+    let owner = registry
+        .owner()
+        .context("Registry owner is required to issue invites")?;
     let resolved = resolve_participants(registry, &participants)?;
+    // A coordinator that lists itself signs and answers its own invite; an
+    // external coordinator only orchestrates and must not be a signer, so it
+    // takes no identifier and does not count toward the threshold.
+    if external_coordinator
+        && let Some((_, record)) =
+            resolved.iter().find(|(xid, _)| *xid == owner.xid())
+    {
+        bail!(
+            "The coordinator cannot also be a DKG participant: {} \
+             (--external-coordinator)",
+            record
+                .pet_name()
+                .map(str::to_owned)
+                .unwrap_or_else(|| owner.xid().ur_string())
+        );
+    }
     let participant_docs: Vec<String> = resolved
         .iter()
         .map(|(_, record)| record.xid_document_ur().to_owned())
@@ -153,6 +392,14 @@ fn build_invite(
     if participant_count < 2 {
         bail!("At least two participants are required for a DKG invite");
     }
+    if participant_count > max_participants {
+        bail!(
+            "Invite lists {} participants, more than the limit of {}; pass \
+             --max-participants to allow a larger group",
+            participant_count,
+            max_participants
+        );
+    }
     let min_signers = min_signers_arg.unwrap_or(participant_count);
     if min_signers < 2 {
         bail!("--min-signers must be at least 2");
@@ -161,21 +408,30 @@ fn build_invite(
         bail!("--min-signers cannot exceed participant count");
     }
 
-    let invite = DkgInvite::new(
+    let now = Date::now();
+    if not_before.is_some_and(|not_before| not_before <= now) {
+        bail!("--not-before must be in the future");
+    }
+    // A deferred invite stays open for the same hour, counted from when it
+    // opens rather than from when it was sent
+    let valid_until = not_before.unwrap_or(now) + INVITE_LIFETIME;
+
+    let mut invite = DkgInvite::new(
         ARID::new(),
-        registry
-            .owner()
-            .context("Registry owner is required to issue invites")?
-            .xid_document()
-            .clone(),
+        owner.xid_document().clone(),
         ARID::new(),
-        Date::now(),
-        Date::with_duration_from_now(Duration::from_secs(60 * 60)),
+        now,
+        valid_until,
         min_signers,
         charter,
         participant_docs,
         collect_from_arids,
-    )?;
+    )?
+    .with_identifier_scheme(identifier_scheme)?
+    .with_signed_charter()?;
+    if let Some(not_before) = not_before {
+        invite = invite.with_valid_from(not_before)?;
+    }
 
     Ok(InviteData { invite, participant_xids, pending_requests })
 }
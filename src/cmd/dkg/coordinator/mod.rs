@@ -1,5 +1,8 @@
 pub mod finalize;
+pub mod inspect;
 pub mod invite;
+pub mod pubkey;
+pub mod refresh;
 pub mod round1;
 pub mod round2;
 
@@ -18,21 +21,30 @@ pub struct CommandArgs {
 enum Commands {
     /// Coordinator DKG invite operations
     Invite(invite::CommandArgs),
+    /// Inspect a DKG invite composed by this coordinator
+    Inspect(inspect::CommandArgs),
     /// DKG Round 1 collection and Round 2 dispatch
     Round1(round1::CommandArgs),
     /// DKG Round 2 collection and finalize dispatch
     Round2(round2::CommandArgs),
     /// DKG finalize response collection
     Finalize(finalize::CommandArgs),
+    /// Export the group public key for verifiers
+    Pubkey(pubkey::CommandArgs),
+    /// Rotate a finished group's key shares, keeping its verifying key
+    Refresh(refresh::CommandArgs),
 }
 
 impl CommandArgs {
     pub fn exec(self) -> Result<()> {
         match self.command {
             Commands::Invite(args) => args.exec(),
+            Commands::Inspect(args) => args.exec(),
             Commands::Round1(args) => args.exec(),
             Commands::Round2(args) => args.exec(),
             Commands::Finalize(args) => args.exec(),
+            Commands::Pubkey(args) => args.exec(),
+            Commands::Refresh(args) => args.exec(),
         }
     }
 }
@@ -0,0 +1,123 @@
+use std::fs;
+
+use anyhow::{Context, Result, bail};
+use bc_envelope::prelude::*;
+use clap::{Parser, ValueEnum};
+use frost_ed25519 as frost;
+
+use crate::cmd::{
+    common::load_registry,
+    dkg::common::{
+        GroupStatePaths, parse_group_id, read_ur_input,
+        signing_key_from_verifying,
+    },
+    registry::participants_file_path,
+};
+
+/// Output format for the group public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[doc(hidden)]
+pub enum PubkeyFormat {
+    /// `ur:signing-public-key` for the group verifying key
+    Ur,
+    /// FROST `PublicKeyPackage` as JSON
+    Json,
+}
+
+/// Export the group's public verification material from collected finalize
+/// responses (coordinator only).
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Output format
+    #[arg(long = "format", value_enum, default_value = "ur")]
+    format: PubkeyFormat,
+
+    /// Group ID to export the public key for
+    /// (`-` reads it from stdin)
+    #[arg(value_name = "GROUP_ID")]
+    group_id: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let registry_path = participants_file_path(self.registry.clone())?;
+        let registry = load_registry(&registry_path).with_context(|| {
+            format!("Failed to load registry at {}", registry_path.display())
+        })?;
+
+        let group_id =
+            parse_group_id(&registry, &read_ur_input(&self.group_id)?)?;
+        let group_record = registry
+            .group(&group_id)
+            .context("Group not found in registry")?;
+
+        let collected_path = GroupStatePaths::new(&registry_path)
+            .collected_finalize_path(&group_id);
+        if !collected_path.exists() {
+            bail!(
+                "collected_finalize.json not found at {}. Run `frost dkg coordinator finalize` first",
+                collected_path.display()
+            );
+        }
+
+        let raw: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(&fs::read(&collected_path).with_context(
+                || format!("Failed to read {}", collected_path.display()),
+            )?)
+            .context("Invalid collected_finalize.json")?;
+
+        let mut public_key_package: Option<frost::keys::PublicKeyPackage> =
+            None;
+        for (xid_str, entry) in &raw {
+            let value = entry
+                .as_object()
+                .and_then(|m| m.get("public_key_package"))
+                .cloned()
+                .with_context(|| {
+                    format!(
+                        "public_key_package missing for {xid_str} in collected_finalize.json"
+                    )
+                })?;
+            let pkg: frost::keys::PublicKeyPackage =
+                serde_json::from_value(value).with_context(|| {
+                    format!("Failed to parse public_key_package for {xid_str}")
+                })?;
+            match &public_key_package {
+                Some(existing) if existing != &pkg => {
+                    bail!(
+                        "Public key package for {xid_str} does not match other participants"
+                    );
+                }
+                Some(_) => {}
+                None => public_key_package = Some(pkg),
+            }
+        }
+        let public_key_package =
+            public_key_package.context("collected_finalize.json is empty")?;
+
+        let verifying_key =
+            signing_key_from_verifying(public_key_package.verifying_key())?;
+        if let Some(recorded) = group_record.verifying_key()
+            && recorded != &verifying_key
+        {
+            bail!(
+                "Group verifying key in registry does not match collected_finalize.json"
+            );
+        }
+
+        match self.format {
+            PubkeyFormat::Ur => println!("{}", verifying_key.ur_string()),
+            PubkeyFormat::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&public_key_package)?
+            ),
+        }
+
+        Ok(())
+    }
+}
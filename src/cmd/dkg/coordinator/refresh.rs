@@ -0,0 +1,321 @@
+use anyhow::{Context, Result, bail};
+use bc_components::{ARID, XID};
+use bc_envelope::prelude::*;
+use clap::Parser;
+
+use super::invite::build_invite;
+use crate::{
+    cmd::{
+        busy::put_with_indicator,
+        common::{confirm, load_registry},
+        dkg::common::{
+            OptionalStorageSelector, ensure_owner_is_coordinator,
+            parse_group_id, refresh_identifiers, refreshed_group,
+            resolve_participants, share_repair,
+        },
+        registry::participants_file_path,
+        storage::{StorageClient, shared_runtime},
+    },
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+
+/// Rotate the key shares of a finished group (coordinator).
+///
+/// Sends a DKG invite for a new group that refreshes GROUP_ID. Participants
+/// and coordinator then run the usual round1, round2, and finalize commands,
+/// but each participant's new share is its old share plus a share of zero,
+/// so the new group keeps the old group's verifying key and identifiers.
+///
+/// With `--replace-participant`, the refresh also rebuilds the share of a
+/// participant who lost it for a new participant, using FROST's repairable
+/// threshold scheme: the other participants send the coordinator pieces of
+/// their shares alongside their Round 1 and Round 2 packages, and finalize
+/// hands the replacement the pieces that sum to the lost share.
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(flatten)]
+    storage: OptionalStorageSelector,
+
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Leave a participant out of the refresh, by pet name or ur:xid. Their
+    /// old share no longer combines with the refreshed ones (repeatable)
+    #[arg(long = "remove-participant", value_name = "PARTICIPANT")]
+    remove_participants: Vec<String>,
+
+    /// Rebuild the share of a participant who lost it, by pet name or
+    /// ur:xid, for the participant named by --replacement; the other
+    /// participants rebuild it and the lost share stops working
+    #[arg(
+        long = "replace-participant",
+        value_name = "PARTICIPANT",
+        requires = "replacement"
+    )]
+    replace_participant: Option<String>,
+
+    /// Registered participant, by pet name or ur:xid, who takes over the
+    /// identifier and share of --replace-participant
+    #[arg(
+        long = "replacement",
+        value_name = "PARTICIPANT",
+        requires = "replace_participant"
+    )]
+    replacement: Option<String>,
+
+    /// Friendly name for the refreshed group (requires Hubert storage)
+    #[arg(long = "name", value_name = "NAME")]
+    name: Option<String>,
+
+    /// Print the preview invite envelope UR instead of the sealed envelope
+    #[arg(long = "preview")]
+    preview: bool,
+
+    /// Group ID or name of the group to refresh
+    #[arg(value_name = "GROUP_ID")]
+    group_id: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let selection = self.storage.resolve()?;
+        if selection.is_some() && self.preview {
+            bail!("--preview cannot be used with Hubert storage options");
+        }
+        if selection.is_none() && self.name.is_some() {
+            bail!("--name requires Hubert storage options");
+        }
+
+        let registry_path = participants_file_path(self.registry.clone())?;
+        let mut registry =
+            load_registry(&registry_path).with_context(|| {
+                format!(
+                    "Failed to load registry at {}",
+                    registry_path.display()
+                )
+            })?;
+        let owner = registry
+            .owner()
+            .context("Registry owner is required to refresh a group")?
+            .clone();
+        let group_id = parse_group_id(&registry, &self.group_id)?;
+        let original = refreshed_group(&registry, &group_id)?.clone();
+        ensure_owner_is_coordinator(&original, &owner)?;
+
+        let removed = removed_participants(
+            &registry,
+            &original,
+            &self.remove_participants,
+        )?;
+        let replaced = match (&self.replace_participant, &self.replacement) {
+            (Some(lost), Some(replacement)) => Some(replaced_participant(
+                &registry,
+                &original,
+                &removed,
+                lost,
+                replacement,
+            )?),
+            _ => None,
+        };
+        let mut participant_xids: Vec<XID> = original
+            .participants()
+            .iter()
+            .map(|participant| *participant.xid())
+            .filter(|xid| !removed.contains(xid))
+            .filter(|xid| replaced.is_none_or(|(lost, _)| lost != *xid))
+            .collect();
+        if let Some((_, replacement)) = replaced {
+            participant_xids.push(replacement);
+        }
+        if participant_xids.len() < original.min_signers() {
+            bail!(
+                "Only {} participants would remain, fewer than the group's \
+                 {} min signers; a refresh cannot lower the threshold",
+                participant_xids.len(),
+                original.min_signers()
+            );
+        }
+        let repair = replaced
+            .map(|(lost, replacement)| {
+                share_repair(&original, &participant_xids, replacement, lost)
+            })
+            .transpose()?;
+
+        let invite_data = build_invite(
+            &registry,
+            Some(original.min_signers()),
+            usize::MAX,
+            None,
+            original.identifier_scheme(),
+            original.charter().to_owned(),
+            participant_xids.iter().map(|xid| xid.ur_string()).collect(),
+            false,
+        )?;
+        let mut invite = invite_data.invite.with_refresh_of(group_id);
+        if let Some(repair) = &repair {
+            invite = invite.with_share_repair(repair.clone())?;
+        }
+
+        if let Some(selection) = selection {
+            print_refresh_summary(
+                &registry, &group_id, &original, &removed, replaced,
+            );
+            if !confirm("Type 'yes' to start the refresh:")? {
+                bail!("Refresh not confirmed; no invite was sent");
+            }
+
+            let participants: Vec<GroupParticipant> = invite_data
+                .participant_xids
+                .iter()
+                .map(|xid| GroupParticipant::new(*xid))
+                .collect();
+            let mut group_record = GroupRecord::new(
+                invite.charter().to_owned(),
+                invite.min_signers(),
+                GroupParticipant::new(owner.xid()),
+                participants,
+            );
+            group_record.set_pending_requests(invite_data.pending_requests);
+            group_record.set_identifier_scheme(invite.identifier_scheme());
+            group_record.set_identifiers(match &repair {
+                Some(repair) => {
+                    repair.identifiers.clone().into_iter().collect()
+                }
+                None => refresh_identifiers(
+                    &original,
+                    &invite_data.participant_xids,
+                )?,
+            });
+            group_record.set_provenance(original.provenance());
+            group_record.set_refresh_of(group_id);
+            if let Some(attestation) = invite.charter_attestation() {
+                group_record.set_charter_attestation(attestation.ur_string());
+            }
+            let refresh_id = invite.group_id();
+            registry.record_group(refresh_id, group_record)?;
+            if let Some(name) = self.name {
+                registry.set_group_name(&refresh_id, Some(name))?;
+            }
+            registry.save(&registry_path)?;
+
+            let envelope = invite.to_envelope()?;
+            let arid = ARID::new();
+            let runtime = shared_runtime()?;
+            let client = runtime
+                .block_on(async { StorageClient::shared(selection).await })?;
+            put_with_indicator(
+                runtime,
+                &client,
+                &arid,
+                &envelope,
+                "DKG refresh invite",
+            )?;
+
+            eprintln!("Refreshed group ID: {}", refresh_id.ur_string());
+            println!("{}", arid.ur_string());
+        } else if self.preview {
+            println!("{}", invite.to_unsealed_envelope()?.ur_string());
+        } else {
+            println!("{}", invite.to_envelope()?.ur_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolves `--remove-participant` arguments, each of which must name a
+/// participant of the group being refreshed.
+fn removed_participants(
+    registry: &Registry,
+    original: &GroupRecord,
+    inputs: &[String],
+) -> Result<Vec<XID>> {
+    let members: Vec<XID> = original
+        .participants()
+        .iter()
+        .map(|participant| *participant.xid())
+        .collect();
+    resolve_participants(registry, inputs)?
+        .into_iter()
+        .map(|(xid, record)| {
+            if !members.contains(&xid) {
+                bail!(
+                    "{} is not a participant in the group being refreshed",
+                    record
+                        .pet_name()
+                        .map(str::to_owned)
+                        .unwrap_or_else(|| xid.ur_string())
+                );
+            }
+            Ok(xid)
+        })
+        .collect()
+}
+
+/// Resolves `--replace-participant` and `--replacement` to the XIDs of the
+/// lost participant and its replacement.
+fn replaced_participant(
+    registry: &Registry,
+    original: &GroupRecord,
+    removed: &[XID],
+    lost: &str,
+    replacement: &str,
+) -> Result<(XID, XID)> {
+    let lost = removed_participants(registry, original, &[lost.to_owned()])?[0];
+    if removed.contains(&lost) {
+        bail!(
+            "{} cannot be both removed and replaced",
+            participant_name(registry, &lost)
+        );
+    }
+    let (replacement, _) =
+        resolve_participants(registry, &[replacement.to_owned()])?.remove(0);
+    Ok((lost, replacement))
+}
+
+fn participant_name(registry: &Registry, xid: &XID) -> String {
+    registry
+        .participant(xid)
+        .and_then(|record| record.pet_name().map(str::to_owned))
+        .unwrap_or_else(|| xid.ur_string())
+}
+
+/// Explains what the refresh does and does not protect against before the
+/// coordinator confirms it.
+fn print_refresh_summary(
+    registry: &Registry,
+    group_id: &ARID,
+    original: &GroupRecord,
+    removed: &[XID],
+    replaced: Option<(XID, XID)>,
+) {
+    eprintln!(
+        "Refreshing group {}: {} participants receive new shares of the same \
+         verifying key, {} of which must still sign.",
+        group_id.ur_string(),
+        original.participants().len() - removed.len(),
+        original.min_signers()
+    );
+    for xid in removed {
+        eprintln!(
+            "Removing {}: their share stops working with the new one.",
+            participant_name(registry, xid)
+        );
+    }
+    if let Some((lost, replacement)) = replaced {
+        eprintln!(
+            "Replacing {} with {}: the other participants rebuild the lost \
+             share for the replacement, and the pieces they send pass \
+             through you; the lost share stops working with the new ones.",
+            participant_name(registry, &lost),
+            participant_name(registry, &replacement)
+        );
+    }
+    eprintln!(
+        "Old shares keep working together until participants delete them, \
+         and finalize sends each new key package to you as the original DKG \
+         did."
+    );
+}
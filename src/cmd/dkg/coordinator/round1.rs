@@ -1,8 +1,9 @@
 use std::{
+    collections::{BTreeSet, HashMap},
     fs,
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result, bail};
@@ -17,15 +18,29 @@ use tokio::runtime::Runtime;
 use crate::{
     cmd::{
         busy::{get_with_indicator, put_with_indicator},
+        common::{
+            CollectionTimeout, PartialCollection, collection_timeout,
+            load_registry, write_atomically,
+        },
         dkg::common::{
-            OptionalStorageSelector, group_state_dir, parse_arid_ur,
+            GroupStatePaths, OptionalStorageSelector,
+            check_group_round1_commitments, check_round1_signers,
+            ensure_owner_is_coordinator, parse_group_id, read_ur_input,
+            refresh_replacement, resolve_participants, with_group_parameters,
         },
         is_verbose,
-        parallel::{CollectionResult, ParallelFetchConfig, parallel_fetch},
+        parallel::{
+            CollectionResult, FetchError, ParallelFetchConfig, parallel_fetch,
+        },
         registry::participants_file_path,
-        storage::StorageClient,
+        report::{CollectionReport, ParticipantStatus, report_name},
+        storage::{
+            StorageClient, cleanup_collected, compress_payload,
+            decompress_envelope, shared_runtime,
+        },
     },
-    registry::{PendingRequests, Registry},
+    dkg::{RepairScalar, extract_repair_scalar, repair_scalar_envelope},
+    registry::{GroupRecord, PendingRequests, Registry},
 };
 
 /// Collect Round 1 responses and dispatch Round 2 requests (coordinator).
@@ -43,6 +58,11 @@ pub struct CommandArgs {
     #[arg(long = "timeout", value_name = "SECONDS")]
     timeout: Option<u64>,
 
+    /// Collect until this RFC 3339 date and time instead of waiting a
+    /// relative --timeout
+    #[arg(long = "deadline", value_name = "DATE", conflicts_with = "timeout")]
+    deadline: Option<String>,
+
     /// Preview one of the Round 2 requests while sending
     #[arg(long = "preview")]
     preview: bool,
@@ -51,7 +71,35 @@ pub struct CommandArgs {
     #[arg(long)]
     parallel: bool,
 
+    /// Delete collected responses from storage once they are saved locally
+    #[arg(long = "cleanup")]
+    cleanup: bool,
+
+    /// Remove a non-responsive participant (XID or pet name) from the group
+    /// before dispatching Round 2; may be repeated. The group is generated
+    /// without them and any Round 1 package they sent is discarded
+    #[arg(long = "exclude", value_name = "PARTICIPANT")]
+    exclude: Vec<String>,
+
+    /// Exclude participants who reject the invite, as with --exclude, and
+    /// continue if at least min signers remain. Without this flag any
+    /// rejection fails the collection
+    #[arg(long = "tolerate-rejections")]
+    tolerate_rejections: bool,
+
+    /// When some participants fail, still save the packages that were
+    /// collected to partial_round1.json, list every failure, and exit with
+    /// status 3 without dispatching Round 2
+    #[arg(long = "continue-on-error")]
+    continue_on_error: bool,
+
+    /// Write a JSON report of the collection (per-participant status and
+    /// timing, output files) to this path, even if collection fails
+    #[arg(long = "report", value_name = "PATH")]
+    report: Option<PathBuf>,
+
     /// Group ID to collect Round 1 responses for
+    /// (`-` reads it from stdin)
     #[arg(value_name = "GROUP_ID")]
     group_id: String,
 }
@@ -61,10 +109,12 @@ impl CommandArgs {
         let selection = self.storage.resolve()?;
         let selection =
             selection.context("Hubert storage is required for round1")?;
+        let timeout =
+            collection_timeout(self.timeout, self.deadline.as_deref())?;
 
         let registry_path = participants_file_path(self.registry.clone())?;
         let mut registry =
-            Registry::load(&registry_path).with_context(|| {
+            load_registry(&registry_path).with_context(|| {
                 format!(
                     "Failed to load registry at {}",
                     registry_path.display()
@@ -77,13 +127,23 @@ impl CommandArgs {
             .clone();
         let owner_doc = owner.xid_document().clone();
 
-        let group_id = parse_arid_ur(&self.group_id)?;
-        let group_record = registry
+        let group_id =
+            parse_group_id(&registry, &read_ur_input(&self.group_id)?)?;
+        let mut group_record = registry
             .group(&group_id)
             .context("Group not found in registry")?
             .clone();
 
-        validate_coordinator(&group_record, &owner)?;
+        ensure_owner_is_coordinator(&group_record, &owner)?;
+
+        if !self.exclude.is_empty() {
+            exclude_participants(&registry, &mut group_record, &self.exclude)?;
+            // Saved along with the Round 2 pending requests once collection
+            // succeeds.
+            *registry
+                .group_mut(&group_id)
+                .context("Group not found in registry")? = group_record.clone();
+        }
 
         let pending_requests = group_record.pending_requests();
         if pending_requests.is_empty() {
@@ -93,25 +153,47 @@ impl CommandArgs {
             );
         }
 
-        let runtime = Runtime::new()?;
-        let client = runtime.block_on(async {
-            StorageClient::from_selection(selection).await
-        })?;
+        let runtime = shared_runtime()?;
+        let client = runtime
+            .block_on(async { StorageClient::shared(selection).await })?;
 
         if self.parallel {
             // Parallel path with progress display
-            let client = Arc::new(client);
-            let collection = runtime.block_on(async {
+            let mut collection = runtime.block_on(async {
                 collect_round1_responses_parallel(
                     Arc::clone(&client),
                     &registry,
                     pending_requests,
                     &owner_doc,
                     &group_id,
-                    self.timeout,
+                    timeout.seconds(),
                 )
                 .await
             })?;
+            let report = self.report.as_ref().map(|path| {
+                let report = CollectionReport::from_collection(
+                    ROUND1_PHASE,
+                    &group_id,
+                    &collection,
+                    |xid| report_name(&registry, xid),
+                );
+                (path, report)
+            });
+
+            // The report still records the rejections when too few
+            // participants remain to continue
+            if self.tolerate_rejections
+                && let Err(err) = tolerate_rejections(
+                    &mut registry,
+                    &group_id,
+                    &mut collection,
+                )
+            {
+                if let Some((path, report)) = &report {
+                    report.write(path)?;
+                }
+                return Err(err);
+            }
 
             // Extract packages for persistence
             let packages: Vec<(XID, frost::keys::dkg::round1::Package)> =
@@ -121,8 +203,51 @@ impl CommandArgs {
                     .map(|(xid, data)| (*xid, data.package.clone()))
                     .collect();
 
-            let display_path =
-                persist_round1_packages(&registry_path, &group_id, &packages)?;
+            if self.continue_on_error && !collection.all_succeeded() {
+                let display_path = persist_round1_packages(
+                    &registry_path,
+                    &group_id,
+                    &packages,
+                    true,
+                )?;
+                if let Some((path, mut report)) = report {
+                    report.add_output(&display_path);
+                    report.write(path)?;
+                }
+                print_collection_failures(&collection);
+                return Err(PartialCollection {
+                    phase: ROUND1_LABEL,
+                    collected: packages.len(),
+                    failed: collection.total() - packages.len(),
+                    path: display_path,
+                }
+                .into());
+            }
+            check_group_round1_commitments(&packages, &group_record)?;
+            let collected_group = registry
+                .group(&group_id)
+                .context("Group not found in registry")?;
+            check_round1_signers(&packages, collected_group)?;
+            check_repair_deltas(
+                &registry,
+                collected_group,
+                &collection
+                    .successes
+                    .iter()
+                    .map(|(xid, data)| (*xid, data.repair_deltas.clone()))
+                    .collect::<RepairDeltas>(),
+            )?;
+
+            let display_path = persist_round1_packages(
+                &registry_path,
+                &group_id,
+                &packages,
+                false,
+            )?;
+            if let Some((path, mut report)) = report {
+                report.add_output(&display_path);
+                report.write(path)?;
+            }
 
             update_pending_for_round2_from_collection(
                 &mut registry,
@@ -130,6 +255,14 @@ impl CommandArgs {
                 &group_id,
                 &collection.successes,
             )?;
+            if self.cleanup {
+                let collected: Vec<XID> =
+                    collection.successes.iter().map(|(xid, _)| *xid).collect();
+                runtime.block_on(cleanup_collected(
+                    &client,
+                    &pending_requests.collect_arids_for(&collected),
+                ));
+            }
 
             let preview = runtime.block_on(async {
                 dispatch_round2_requests_parallel(
@@ -148,7 +281,7 @@ impl CommandArgs {
         } else {
             // Sequential path (original behavior)
             let mut ctx = Round1Context {
-                runtime: &runtime,
+                runtime,
                 client: &client,
                 registry_path: &registry_path,
                 registry: &mut registry,
@@ -156,16 +289,49 @@ impl CommandArgs {
                 group_id: &group_id,
             };
 
+            let mut report = CollectionReport::new(ROUND1_PHASE, &group_id);
             let collection = collect_round1_responses(
                 &mut ctx,
                 pending_requests,
-                self.timeout,
-            )?;
+                &timeout,
+                self.tolerate_rejections,
+                self.continue_on_error,
+                &mut report,
+            );
+            if let Some(path) = &self.report {
+                match &collection {
+                    Ok(collection) => {
+                        report.add_output(&collection.display_path);
+                    }
+                    Err(err) => {
+                        if let Some(partial) =
+                            err.downcast_ref::<PartialCollection>()
+                        {
+                            report.add_output(&partial.path);
+                        }
+                    }
+                }
+                report.write(path)?;
+            }
+            let collection = collection?;
+            if self.cleanup {
+                let collected: Vec<XID> =
+                    collection.packages.iter().map(|(xid, _)| *xid).collect();
+                runtime.block_on(cleanup_collected(
+                    &client,
+                    &pending_requests.collect_arids_for(&collected),
+                ));
+            }
 
             let preview =
                 dispatch_round2_requests(&mut ctx, &collection, self.preview)?;
 
-            print_summary(&collection, preview);
+            print_summary(
+                collection.packages.len(),
+                &collection.display_path,
+                collection.next_response_arids.len(),
+                preview,
+            );
         }
 
         Ok(())
@@ -185,34 +351,134 @@ struct Round1Context<'a> {
     group_id: &'a ARID,
 }
 
+/// Phase name recorded in `--report` output.
+const ROUND1_PHASE: &str = "dkg_round1";
+
+/// Phase name shown in collection errors.
+const ROUND1_LABEL: &str = "Round 1";
+
 type Round1Package = (XID, frost::keys::dkg::round1::Package);
 type NextResponseArid = (XID, ARID);
+/// Each participant's GSTP continuation, returned with its Round 2 request.
+type Continuations = HashMap<XID, Envelope>;
+/// Each helper's share repair deltas, by the helper they are addressed to.
+type RepairDeltas = HashMap<XID, Vec<(XID, RepairScalar)>>;
 
 struct Round1Collection {
     packages: Vec<Round1Package>,
     next_response_arids: Vec<NextResponseArid>,
+    continuations: Continuations,
+    repair_deltas: RepairDeltas,
     display_path: PathBuf,
 }
 
 // -----------------------------------------------------------------------------
-// Validation
+// Participant exclusion
 // -----------------------------------------------------------------------------
 
-fn validate_coordinator(
-    group_record: &crate::registry::GroupRecord,
-    owner: &crate::registry::OwnerRecord,
+/// Remove the named participants from the group, warning that the DKG will
+/// continue with a reduced roster.
+fn exclude_participants(
+    registry: &Registry,
+    group_record: &mut GroupRecord,
+    inputs: &[String],
+) -> Result<()> {
+    let excluded: Vec<(XID, String)> = resolve_participants(registry, inputs)?
+        .into_iter()
+        .map(|(xid, record)| {
+            let name = record
+                .pet_name()
+                .map(|s| s.to_owned())
+                .unwrap_or_else(|| xid.ur_string());
+            (xid, name)
+        })
+        .collect();
+    exclude_from_group(group_record, &excluded)
+}
+
+/// `--tolerate-rejections` for a parallel collection: moves the rejections
+/// out of `collection` and excludes the rejecting participants from the
+/// group, so the rest of the collection is summarized as complete. Fails if
+/// too few participants would remain.
+pub fn tolerate_rejections<T>(
+    registry: &mut Registry,
+    group_id: &ARID,
+    collection: &mut CollectionResult<T>,
+) -> Result<()> {
+    if collection.rejections.is_empty() {
+        return Ok(());
+    }
+    let rejections = std::mem::take(&mut collection.rejections);
+    exclude_rejected(registry, group_id, &rejections)
+}
+
+/// Remove participants who rejected the invite from the registry's copy of
+/// the group.
+fn exclude_rejected(
+    registry: &mut Registry,
+    group_id: &ARID,
+    rejections: &[(XID, String)],
+) -> Result<()> {
+    let mut excluded = Vec::new();
+    for (xid, reason) in rejections {
+        let name = registry
+            .participant(xid)
+            .and_then(|r| r.pet_name().map(|s| s.to_owned()))
+            .unwrap_or_else(|| xid.ur_string());
+        eprintln!("{name}: {reason}");
+        excluded.push((*xid, name));
+    }
+    let group_record = registry
+        .group_mut(group_id)
+        .context("Group not found in registry")?;
+    exclude_from_group(group_record, &excluded)
+}
+
+fn exclude_from_group(
+    group_record: &mut GroupRecord,
+    excluded: &[(XID, String)],
 ) -> Result<()> {
-    if group_record.coordinator().xid() != &owner.xid() {
+    let original_count = group_record.participants().len();
+    let mut names = Vec::new();
+    for (xid, name) in excluded {
+        if !group_record.exclude_participant(xid) {
+            bail!("{name} is not a participant in this group");
+        }
+        names.push(name.as_str());
+    }
+
+    let remaining = group_record.participants().len();
+    if remaining < group_record.min_signers() {
         bail!(
-            "Only the coordinator can collect and send Round 2 requests. \
-             Coordinator: {}, Owner: {}",
-            group_record.coordinator().xid().ur_string(),
-            owner.xid().ur_string()
+            "Excluding {} would leave {} participants, fewer than min signers {}",
+            names.join(", "),
+            remaining,
+            group_record.min_signers()
         );
     }
+
+    eprintln!(
+        "Warning: excluding {} from this group. Any Round 1 packages they \
+         sent are discarded and the DKG continues with {} of {} participants \
+         (max signers {}, min signers {}); excluded participants will hold no \
+         key share.",
+        names.join(", "),
+        remaining,
+        original_count,
+        remaining,
+        group_record.min_signers()
+    );
+
     Ok(())
 }
 
+fn excluded_xids(registry: &Registry, group_id: &ARID) -> Result<Vec<XID>> {
+    let group_record = registry
+        .group(group_id)
+        .context("Group not found in registry")?;
+    Ok(group_record.excluded().iter().map(|p| *p.xid()).collect())
+}
+
 // -----------------------------------------------------------------------------
 // Round 1 collection
 // -----------------------------------------------------------------------------
@@ -220,7 +486,10 @@ fn validate_coordinator(
 fn collect_round1_responses(
     ctx: &mut Round1Context<'_>,
     pending_requests: &PendingRequests,
-    timeout: Option<u64>,
+    timeout: &CollectionTimeout,
+    tolerate_rejections: bool,
+    continue_on_error: bool,
+    report: &mut CollectionReport,
 ) -> Result<Round1Collection> {
     if is_verbose() {
         eprintln!(
@@ -229,24 +498,75 @@ fn collect_round1_responses(
         );
     }
 
-    let (packages, next_response_arids) =
-        fetch_all_round1_packages(ctx, pending_requests, timeout)?;
-
-    let display_path =
-        persist_round1_packages(ctx.registry_path, ctx.group_id, &packages)?;
+    let (
+        packages,
+        next_response_arids,
+        continuations,
+        repair_deltas,
+        rejections,
+    ) = fetch_all_round1_packages(
+        ctx,
+        pending_requests,
+        timeout,
+        tolerate_rejections,
+        continue_on_error,
+        report,
+    )?;
+    if !rejections.is_empty() {
+        exclude_rejected(ctx.registry, ctx.group_id, &rejections)?;
+    }
+    let group_record = ctx
+        .registry
+        .group(ctx.group_id)
+        .context("Group not found in registry")?;
+    check_group_round1_commitments(&packages, group_record)?;
+    check_round1_signers(&packages, group_record)?;
+    check_repair_deltas(ctx.registry, group_record, &repair_deltas)?;
+
+    let display_path = persist_round1_packages(
+        ctx.registry_path,
+        ctx.group_id,
+        &packages,
+        false,
+    )?;
 
     update_pending_for_round2(ctx, &next_response_arids)?;
 
-    Ok(Round1Collection { packages, next_response_arids, display_path })
+    Ok(Round1Collection {
+        packages,
+        next_response_arids,
+        continuations,
+        repair_deltas,
+        display_path,
+    })
 }
 
+/// Fetch every pending Round 1 response.
+///
+/// With `tolerate_rejections`, rejections are returned alongside the
+/// packages instead of failing the collection. With `continue_on_error`, a
+/// failed collection still saves the packages that arrived and returns a
+/// [`PartialCollection`]. Every participant's outcome is recorded in
+/// `report`.
 fn fetch_all_round1_packages(
     ctx: &Round1Context<'_>,
     pending_requests: &PendingRequests,
-    timeout: Option<u64>,
-) -> Result<(Vec<Round1Package>, Vec<NextResponseArid>)> {
+    timeout: &CollectionTimeout,
+    tolerate_rejections: bool,
+    continue_on_error: bool,
+    report: &mut CollectionReport,
+) -> Result<(
+    Vec<Round1Package>,
+    Vec<NextResponseArid>,
+    Continuations,
+    RepairDeltas,
+    Vec<(XID, String)>,
+)> {
     let mut round1_packages = Vec::new();
     let mut next_response_arids = Vec::new();
+    let mut continuations = Continuations::new();
+    let mut repair_deltas = RepairDeltas::new();
+    let mut rejections: Vec<(XID, String)> = Vec::new();
     let mut errors: Vec<(XID, String)> = Vec::new();
 
     for (participant_xid, collect_from_arid) in pending_requests.iter_collect()
@@ -261,22 +581,44 @@ fn fetch_all_round1_packages(
             })
             .unwrap_or_else(|| participant_xid.ur_string());
 
-        match fetch_and_validate_response(
+        let started = Instant::now();
+        let result = fetch_and_validate_response(
             ctx.runtime,
             ctx.client,
             collect_from_arid,
-            timeout,
+            timeout.seconds(),
             ctx.owner_doc,
             ctx.group_id,
             &participant_name,
-        ) {
-            Ok((package, next_arid)) => {
-                round1_packages.push((*participant_xid, package));
-                next_response_arids.push((*participant_xid, next_arid));
-            }
-            Err(e) => {
-                errors.push((*participant_xid, e.to_string()));
+        );
+        let (status, reason) = match &result {
+            Ok(_) => (ParticipantStatus::Success, None),
+            Err(e) => (ParticipantStatus::from_error(e), Some(e.to_string())),
+        };
+        report.record(
+            participant_xid,
+            report_name(ctx.registry, participant_xid),
+            status,
+            reason,
+            Some(started.elapsed()),
+        );
+
+        match result {
+            Ok(data) => {
+                round1_packages.push((*participant_xid, data.package));
+                next_response_arids
+                    .push((*participant_xid, data.next_response_arid));
+                if let Some(continuation) = data.continuation {
+                    continuations.insert(*participant_xid, continuation);
+                }
+                repair_deltas.insert(*participant_xid, data.repair_deltas);
             }
+            Err(e) => match e.downcast_ref::<FetchError>() {
+                Some(FetchError::Rejected(reason)) if tolerate_rejections => {
+                    rejections.push((*participant_xid, reason.clone()));
+                }
+                _ => errors.push((*participant_xid, e.to_string())),
+            },
         }
     }
 
@@ -288,6 +630,21 @@ fn fetch_all_round1_packages(
         for (xid, error) in &errors {
             eprintln!("  {}: {}", xid.ur_string(), error);
         }
+        if continue_on_error {
+            let display_path = persist_round1_packages(
+                ctx.registry_path,
+                ctx.group_id,
+                &round1_packages,
+                true,
+            )?;
+            return Err(PartialCollection {
+                phase: ROUND1_LABEL,
+                collected: round1_packages.len(),
+                failed: errors.len(),
+                path: display_path,
+            }
+            .into());
+        }
         bail!(
             "Round 1 collection incomplete: {} of {} responses failed",
             errors.len(),
@@ -295,15 +652,25 @@ fn fetch_all_round1_packages(
         );
     }
 
-    Ok((round1_packages, next_response_arids))
+    Ok((
+        round1_packages,
+        next_response_arids,
+        continuations,
+        repair_deltas,
+        rejections,
+    ))
 }
 
+/// Saves the collected Round 1 packages to `collected_round1.json`, or with
+/// `partial` to `partial_round1.json`, which a complete collection removes.
 fn persist_round1_packages(
     registry_path: &Path,
     group_id: &ARID,
     packages: &[(XID, frost::keys::dkg::round1::Package)],
+    partial: bool,
 ) -> Result<PathBuf> {
-    let packages_dir = group_state_dir(registry_path, group_id);
+    let paths = GroupStatePaths::new(registry_path);
+    let packages_dir = paths.group_dir(group_id);
     fs::create_dir_all(&packages_dir).with_context(|| {
         format!(
             "Failed to create group state directory {}",
@@ -311,7 +678,11 @@ fn persist_round1_packages(
         )
     })?;
 
-    let round1_packages_path = packages_dir.join("collected_round1.json");
+    let round1_packages_path = if partial {
+        paths.partial_round1_path(group_id)
+    } else {
+        paths.collected_round1_path(group_id)
+    };
     let packages_json: serde_json::Map<String, serde_json::Value> = packages
         .iter()
         .map(|(xid, package)| {
@@ -322,13 +693,21 @@ fn persist_round1_packages(
             )
         })
         .collect();
-    fs::write(
+    write_atomically(
         &round1_packages_path,
         serde_json::to_vec_pretty(&packages_json)?,
     )
     .with_context(|| {
         format!("Failed to write {}", round1_packages_path.display())
     })?;
+    if !partial {
+        let partial_path = paths.partial_round1_path(group_id);
+        if partial_path.exists() {
+            fs::remove_file(&partial_path).with_context(|| {
+                format!("Failed to remove {}", partial_path.display())
+            })?;
+        }
+    }
 
     let display_path = std::env::current_dir()
         .ok()
@@ -376,6 +755,12 @@ fn dispatch_round2_requests(
         ctx.registry,
         &collection.next_response_arids,
     )?;
+    let excluded = excluded_xids(ctx.registry, ctx.group_id)?;
+    let group_record = ctx
+        .registry
+        .group(ctx.group_id)
+        .context("Group not found in registry")?
+        .clone();
 
     if is_verbose() {
         eprintln!(
@@ -401,8 +786,12 @@ fn dispatch_round2_requests(
         let request = build_round2_request_for_participant(
             ctx.owner_doc,
             ctx.group_id,
+            &group_record,
             &collection.packages,
+            &excluded,
+            &repair_deltas_for(xid, &collection.repair_deltas),
             *collect_from_arid,
+            collection.continuations.get(xid),
         )?;
 
         if preview && preview_output.is_none() {
@@ -439,6 +828,8 @@ fn build_round2_participant_info(
     registry: &Registry,
     next_response_arids: &[(XID, ARID)],
 ) -> Result<Vec<(XID, XIDDocument, ARID, ARID)>> {
+    let mut next_response_arids = next_response_arids.to_vec();
+    next_response_arids.sort_by_key(|(xid, _)| *xid);
     next_response_arids
         .iter()
         .map(|(xid, send_to_arid)| {
@@ -478,30 +869,35 @@ fn update_pending_for_round2_collection(
 // Output
 // -----------------------------------------------------------------------------
 
+/// Prints the outcome of a Round 1 collection.
+///
+/// Without `--preview`, stdout carries only the path of the collected
+/// packages, in both sequential and parallel modes, so scripts can rely on
+/// it; the human-readable summary goes to stderr with `--verbose`. With
+/// `--preview`, stdout carries the preview UR instead.
 fn print_summary(
-    collection: &Round1Collection,
+    collected: usize,
+    display_path: &Path,
+    sent: usize,
     preview: Option<(String, String)>,
 ) {
+    let summary = format!(
+        "Collected {collected} Round 1 packages to {} and sent {sent} Round 2 requests.",
+        display_path.display()
+    );
     if let Some((participant_name, ur)) = preview {
         if is_verbose() {
             eprintln!("# Round 2 preview for {}", participant_name);
             eprintln!();
         }
-        eprintln!(
-            "Collected {} Round 1 packages to {} and sent {} Round 2 requests.",
-            collection.packages.len(),
-            collection.display_path.display(),
-            collection.next_response_arids.len()
-        );
+        eprintln!("{summary}");
         println!("{ur}");
-    } else if is_verbose() {
-        eprintln!();
-        eprintln!(
-            "Collected {} Round 1 packages to {} and sent {} Round 2 requests.",
-            collection.packages.len(),
-            collection.display_path.display(),
-            collection.next_response_arids.len()
-        );
+    } else {
+        if is_verbose() {
+            eprintln!();
+            eprintln!("{summary}");
+        }
+        println!("{}", display_path.display());
     }
 }
 
@@ -517,7 +913,7 @@ fn fetch_and_validate_response(
     coordinator: &XIDDocument,
     expected_group_id: &ARID,
     participant_name: &str,
-) -> Result<(frost::keys::dkg::round1::Package, ARID)> {
+) -> Result<Round1ResponseData> {
     let envelope = get_with_indicator(
         runtime,
         client,
@@ -525,7 +921,7 @@ fn fetch_and_validate_response(
         participant_name,
         timeout,
     )?
-    .context("Response not found in Hubert storage")?;
+    .ok_or(FetchError::NotYetAvailable)?;
 
     let coordinator_private_keys =
         coordinator.inception_private_keys().ok_or_else(|| {
@@ -540,29 +936,53 @@ fn fetch_and_validate_response(
         None,
         Some(now),
         coordinator_private_keys,
-    )?;
+    )
+    .map_err(FetchError::decode)?;
+    round1_response_data(&sealed_response, expected_group_id)
+}
 
+/// Extracts the Round 1 package, the next response ARID, and the
+/// participant's continuation from a decrypted invite response.
+fn round1_response_data(
+    sealed_response: &SealedResponse,
+    expected_group_id: &ARID,
+) -> Result<Round1ResponseData> {
     if let Ok(error) = sealed_response.error() {
         let reason = error
             .object_for_predicate("reason")
             .ok()
             .and_then(|e| e.extract_subject::<String>().ok())
             .unwrap_or_else(|| "unknown reason".to_string());
-        bail!("Participant rejected invite: {}", reason);
+        return Err(FetchError::Rejected(format!(
+            "Participant rejected invite: {reason}"
+        ))
+        .into());
     }
 
-    let result = sealed_response
-        .result()
-        .context("Response has no result envelope")?;
+    let result = decompress_envelope(
+        sealed_response
+            .result()
+            .context("Response has no result envelope")?
+            .clone(),
+    )?;
 
-    validate_round1_response(result, expected_group_id)?;
+    validate_round1_response(&result, expected_group_id)?;
 
     let next_response_arid: ARID =
         result.extract_object_for_predicate("response_arid")?;
+    let package = extract_round1_package(&result)?;
+    let mut repair_deltas = Vec::new();
+    for delta in result.objects_for_predicate("repairDelta") {
+        let recipient: XID = delta.extract_object_for_predicate("recipient")?;
+        repair_deltas.push((recipient, extract_repair_scalar(&delta)?));
+    }
 
-    let round1_package = extract_round1_package(result)?;
-
-    Ok((round1_package, next_response_arid))
+    Ok(Round1ResponseData {
+        package,
+        next_response_arid,
+        continuation: sealed_response.peer_continuation().cloned(),
+        repair_deltas,
+    })
 }
 
 fn validate_round1_response(
@@ -597,28 +1017,117 @@ fn extract_round1_package(
         .context("Failed to deserialize Round 1 package")
 }
 
+/// Fails unless the share repair deltas collected with Round 1 let the
+/// replacement rebuild its share: every other participant sent one delta for
+/// each of them, and none was excluded. A refresh without a replacement must
+/// carry no deltas.
+fn check_repair_deltas(
+    registry: &Registry,
+    group_record: &GroupRecord,
+    repair_deltas: &RepairDeltas,
+) -> Result<()> {
+    let Some(replacement) = refresh_replacement(registry, group_record)? else {
+        if repair_deltas.values().any(|deltas| !deltas.is_empty()) {
+            bail!(
+                "Round 1 responses carry share repair deltas, but this group \
+                 replaces no participant"
+            );
+        }
+        return Ok(());
+    };
+    if !group_record.excluded().is_empty() {
+        bail!(
+            "Every other participant helps rebuild the share of {}, so none \
+             can be excluded from this refresh",
+            replacement.ur_string()
+        );
+    }
+    let helpers: BTreeSet<XID> = group_record
+        .participants()
+        .iter()
+        .map(|participant| *participant.xid())
+        .filter(|xid| *xid != replacement)
+        .collect();
+    for (xid, deltas) in repair_deltas {
+        let recipients: BTreeSet<XID> =
+            deltas.iter().map(|(recipient, _)| *recipient).collect();
+        let expected = if *xid == replacement {
+            BTreeSet::new()
+        } else {
+            helpers.clone()
+        };
+        if recipients != expected || deltas.len() != expected.len() {
+            bail!(
+                "Round 1 response from {} does not carry one share repair \
+                 delta for each helper",
+                xid.ur_string()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The deltas addressed to `recipient`, with the helper that sent each.
+fn repair_deltas_for(
+    recipient: &XID,
+    repair_deltas: &RepairDeltas,
+) -> Vec<(XID, RepairScalar)> {
+    let mut deltas: Vec<(XID, RepairScalar)> = repair_deltas
+        .iter()
+        .flat_map(|(sender, deltas)| {
+            deltas
+                .iter()
+                .filter(|(rcpt, _)| rcpt == recipient)
+                .map(|(_, delta)| (*sender, *delta))
+        })
+        .collect();
+    deltas.sort_by_key(|(sender, _)| *sender);
+    deltas
+}
+
 // -----------------------------------------------------------------------------
 // Round 2 request building
 // -----------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 fn build_round2_request_for_participant(
     sender: &XIDDocument,
     group_id: &ARID,
+    group_record: &GroupRecord,
     round1_packages: &[(XID, frost::keys::dkg::round1::Package)],
+    excluded: &[XID],
+    repair_deltas: &[(XID, RepairScalar)],
     response_arid: ARID,
+    continuation: Option<&Envelope>,
 ) -> Result<SealedRequest> {
-    let mut request = SealedRequest::new("dkgRound2", ARID::new(), sender)
-        .with_parameter("group", *group_id)
-        .with_parameter("responseArid", response_arid);
+    let mut request = with_group_parameters(
+        SealedRequest::new("dkgRound2", ARID::new(), sender)
+            .with_parameter("group", *group_id)
+            .with_parameter("responseArid", response_arid)
+            .with_peer_continuation(continuation),
+        group_record,
+    );
 
     for (xid, package) in round1_packages {
         let encoded = serde_json::to_vec(package)?;
         let json = bc_components::JSON::from_data(encoded);
-        let package_envelope =
-            Envelope::new(CBOR::from(json)).add_assertion("participant", *xid);
+        let package_envelope = compress_payload(
+            Envelope::new(CBOR::from(json)).add_assertion("participant", *xid),
+        )?;
         request = request.with_parameter("round1Package", package_envelope);
     }
 
+    for xid in excluded {
+        request = request.with_parameter("excludedParticipant", *xid);
+    }
+
+    for (helper, delta) in repair_deltas {
+        request = request.with_parameter(
+            "repairDelta",
+            repair_scalar_envelope(delta).add_assertion("sender", *helper),
+        );
+    }
+
     Ok(request)
 }
 
@@ -630,6 +1139,10 @@ fn build_round2_request_for_participant(
 struct Round1ResponseData {
     package: frost::keys::dkg::round1::Package,
     next_response_arid: ARID,
+    /// The participant's GSTP continuation, returned with the next request
+    continuation: Option<Envelope>,
+    /// Share repair deltas for each helper, if the participant is one
+    repair_deltas: Vec<(XID, RepairScalar)>,
 }
 
 /// Collect Round 1 responses in parallel with progress display.
@@ -682,28 +1195,9 @@ fn validate_and_extract_round1_response(
         None,
         Some(now),
         coordinator_keys,
-    )?;
-
-    if let Ok(error) = sealed_response.error() {
-        let reason = error
-            .object_for_predicate("reason")
-            .ok()
-            .and_then(|e| e.extract_subject::<String>().ok())
-            .unwrap_or_else(|| "unknown reason".to_string());
-        bail!("Participant rejected invite: {}", reason);
-    }
-
-    let result = sealed_response
-        .result()
-        .context("Response has no result envelope")?;
-
-    validate_round1_response(result, expected_group_id)?;
-
-    let next_response_arid: ARID =
-        result.extract_object_for_predicate("response_arid")?;
-    let package = extract_round1_package(result)?;
-
-    Ok(Round1ResponseData { package, next_response_arid })
+    )
+    .map_err(FetchError::decode)?;
+    round1_response_data(&sealed_response, expected_group_id)
 }
 
 /// Update pending requests from parallel collection results.
@@ -749,6 +1243,15 @@ async fn dispatch_round2_requests_parallel(
             .iter()
             .map(|(xid, data)| (*xid, data.package.clone()))
             .collect();
+    let excluded = excluded_xids(registry, group_id)?;
+    let group_record = registry
+        .group(group_id)
+        .context("Group not found in registry")?
+        .clone();
+    let repair_deltas: RepairDeltas = successes
+        .iter()
+        .map(|(xid, data)| (*xid, data.repair_deltas.clone()))
+        .collect();
 
     // Build participant info and messages
     let mut messages: Vec<(XID, ARID, Envelope, String)> = Vec::new();
@@ -777,8 +1280,12 @@ async fn dispatch_round2_requests_parallel(
         let request = build_round2_request_for_participant(
             coordinator,
             group_id,
+            &group_record,
             &round1_packages,
+            &excluded,
+            &repair_deltas_for(xid, &repair_deltas),
             collect_from_arid,
+            data.continuation.as_ref(),
         )?;
 
         if preview && preview_output.is_none() {
@@ -849,7 +1356,25 @@ fn print_summary_parallel(
     display_path: &Path,
     preview: Option<(String, String)>,
 ) {
-    // Report any failures
+    print_collection_failures(collection);
+
+    if !collection.all_succeeded() {
+        eprintln!();
+        bail_with_collection_summary(collection);
+    }
+
+    print_summary(
+        collection.successes.len(),
+        display_path,
+        collection.successes.len(),
+        preview,
+    );
+}
+
+/// Lists every participant whose response was not collected.
+fn print_collection_failures(
+    collection: &CollectionResult<Round1ResponseData>,
+) {
     if !collection.rejections.is_empty() {
         eprintln!();
         eprintln!("Rejections:");
@@ -871,33 +1396,6 @@ fn print_summary_parallel(
             eprintln!("  {}", xid.ur_string());
         }
     }
-
-    if !collection.all_succeeded() {
-        eprintln!();
-        bail_with_collection_summary(collection);
-    }
-
-    if let Some((participant_name, ur)) = preview {
-        if is_verbose() {
-            eprintln!("# Round 2 preview for {}", participant_name);
-            eprintln!();
-        }
-        eprintln!(
-            "Collected {} Round 1 packages to {} and sent {} Round 2 requests.",
-            collection.successes.len(),
-            display_path.display(),
-            collection.successes.len()
-        );
-        println!("{ur}");
-    } else if is_verbose() {
-        eprintln!();
-        eprintln!(
-            "Collected {} Round 1 packages to {} and sent {} Round 2 requests.",
-            collection.successes.len(),
-            display_path.display(),
-            collection.successes.len()
-        );
-    }
 }
 
 fn bail_with_collection_summary(
@@ -1,9 +1,9 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     fs,
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result, bail};
@@ -17,15 +17,29 @@ use tokio::runtime::Runtime;
 
 use crate::{
     cmd::{
+        common::{
+            CollectionTimeout, collection_timeout, load_registry,
+            write_atomically,
+        },
         dkg::common::{
-            OptionalStorageSelector, group_state_dir, parse_arid_ur,
+            GroupStatePaths, OptionalStorageSelector,
+            ensure_owner_is_coordinator, parse_group_id, read_ur_input,
+            refresh_replacement, with_group_parameters,
         },
         is_verbose,
-        parallel::{CollectionResult, ParallelFetchConfig, parallel_fetch},
+        parallel::{
+            CollectionResult, FetchError, ParallelFetchConfig, parallel_fetch,
+        },
         registry::participants_file_path,
-        storage::StorageClient,
+        report::{CollectionReport, report_name},
+        sign::common::load_public_key_package,
+        storage::{
+            StorageClient, StorageError, cleanup_collected, compress_payload,
+            decompress_envelope, shared_runtime,
+        },
     },
-    registry::{PendingRequests, Registry},
+    dkg::{RepairScalar, extract_repair_scalar, repair_scalar_envelope},
+    registry::{GroupRecord, PendingRequests, Registry},
 };
 
 /// Collect Round 2 responses and send finalize packages (coordinator).
@@ -43,6 +57,11 @@ pub struct CommandArgs {
     #[arg(long = "timeout", value_name = "SECONDS")]
     timeout: Option<u64>,
 
+    /// Collect until this RFC 3339 date and time instead of waiting a
+    /// relative --timeout
+    #[arg(long = "deadline", value_name = "DATE", conflicts_with = "timeout")]
+    deadline: Option<String>,
+
     /// Preview one of the finalize requests while sending
     #[arg(long = "preview")]
     preview: bool,
@@ -51,7 +70,36 @@ pub struct CommandArgs {
     #[arg(long)]
     parallel: bool,
 
+    /// Delete collected responses from storage once they are saved locally
+    #[arg(long = "cleanup")]
+    cleanup: bool,
+
+    /// Collect and persist Round 2 packages without sending finalize
+    /// requests; a later run without this flag sends them
+    #[arg(long = "packages-only", conflicts_with = "preview")]
+    packages_only: bool,
+
+    /// Skip collection and send finalize requests rebuilt from
+    /// collected_round2.json alone, whatever the registry's pending state;
+    /// for dispatching from a later run or another machine sharing the
+    /// group state directory
+    #[arg(
+        long = "from-collected",
+        conflicts_with_all = ["packages_only", "cleanup", "timeout", "deadline"]
+    )]
+    from_collected: bool,
+
+    /// Write a JSON report of the collection (per-participant status and
+    /// timing, output files) to this path, even if collection fails
+    #[arg(
+        long = "report",
+        value_name = "PATH",
+        conflicts_with = "from_collected"
+    )]
+    report: Option<PathBuf>,
+
     /// Group ID to collect Round 2 responses for
+    /// (`-` reads it from stdin)
     #[arg(value_name = "GROUP_ID")]
     group_id: String,
 }
@@ -61,10 +109,12 @@ impl CommandArgs {
         let selection = self.storage.resolve()?;
         let selection =
             selection.context("Hubert storage is required for round2")?;
+        let timeout =
+            collection_timeout(self.timeout, self.deadline.as_deref())?;
 
         let registry_path = participants_file_path(self.registry.clone())?;
         let mut registry =
-            Registry::load(&registry_path).with_context(|| {
+            load_registry(&registry_path).with_context(|| {
                 format!(
                     "Failed to load registry at {}",
                     registry_path.display()
@@ -77,39 +127,94 @@ impl CommandArgs {
             .clone();
         let owner_doc = owner.xid_document().clone();
 
-        let group_id = parse_arid_ur(&self.group_id)?;
+        let group_id =
+            parse_group_id(&registry, &read_ur_input(&self.group_id)?)?;
         let group_record = registry
             .group(&group_id)
             .context("Group not found in registry")?
             .clone();
 
-        // Verify we are the coordinator
-        if group_record.coordinator().xid() != &owner.xid() {
-            bail!(
-                "Only the coordinator can collect Round 2 responses and send finalize packages. \
-                 Coordinator: {}, Owner: {}",
-                group_record.coordinator().xid().ur_string(),
-                owner.xid().ur_string()
-            );
-        }
+        ensure_owner_is_coordinator(&group_record, &owner)?;
 
         let pending_requests = group_record.pending_requests();
-        if pending_requests.is_empty() {
+        if self.from_collected {
+            ensure_finalize_not_sent(&registry_path, &group_id, &group_record)?;
+        } else if pending_requests.is_empty() {
             bail!(
                 "No pending requests for this group. \
                  Did you run 'frost dkg coordinator round1'?"
             );
         }
 
-        let runtime = Runtime::new()?;
-        let client = runtime.block_on(async {
-            StorageClient::from_selection(selection).await
-        })?;
+        let runtime = shared_runtime()?;
+        let client = runtime
+            .block_on(async { StorageClient::shared(selection).await })?;
+
+        // A previous `--packages-only` run persisted the packages and left
+        // the pending requests in the send phase, or the caller asked to
+        // dispatch from the persisted packages; dispatch from disk.
+        if self.from_collected || pending_requests.is_send_only() {
+            if self.packages_only {
+                bail!(
+                    "Round 2 packages have already been collected for this \
+                     group. Run round2 without --packages-only to send \
+                     finalize requests."
+                );
+            }
+            let (successes, display_path) =
+                load_round2_packages(&registry_path, &group_id)?;
+            if self.from_collected {
+                check_collected_covers_group(&group_record, &successes)?;
+            }
+            // --from-collected may run against a registry that never
+            // collected this Round 2; start with every participant unsent.
+            // A registry that has already sent some requests keeps them
+            // marked so they are not sent twice.
+            if !pending_requests.is_send_only() {
+                update_pending_for_finalize_from_collection(
+                    &mut registry,
+                    &registry_path,
+                    &group_id,
+                    &successes,
+                )?;
+            }
+            let preview = if self.parallel {
+                runtime.block_on(async {
+                    dispatch_finalize_requests_parallel(
+                        Arc::clone(&client),
+                        &mut registry,
+                        &registry_path,
+                        &owner_doc,
+                        &group_id,
+                        &successes,
+                        self.preview,
+                    )
+                    .await
+                })?
+            } else {
+                send_finalize_requests(
+                    runtime,
+                    &client,
+                    &registry_path,
+                    &mut registry,
+                    &owner_doc,
+                    &group_id,
+                    &Round2Collection::from_successes(successes, display_path),
+                    self.preview,
+                )?
+            };
+            if let Some((participant_name, ur)) = preview {
+                if is_verbose() {
+                    eprintln!("# Finalize preview for {}", participant_name);
+                    eprintln!();
+                }
+                println!("{ur}");
+            }
+            return Ok(());
+        }
 
         if self.parallel {
             // Parallel path with progress display
-            let client = Arc::new(client);
-
             let collection = runtime.block_on(async {
                 collect_round2_parallel(
                     Arc::clone(&client),
@@ -117,17 +222,29 @@ impl CommandArgs {
                     pending_requests,
                     &owner_doc,
                     &group_id,
-                    self.timeout,
+                    timeout.seconds(),
                 )
                 .await
             })?;
 
+            let mut report = CollectionReport::from_collection(
+                ROUND2_PHASE,
+                &group_id,
+                &collection,
+                |xid| report_name(&registry, xid),
+            );
+
             // Persist collected data
             let display_path = persist_round2_packages(
                 &registry_path,
                 &group_id,
                 &collection.successes,
-            )?;
+            );
+            if let Ok(display_path) = &display_path {
+                report.add_output(display_path);
+            }
+            report.write_to(self.report.as_deref())?;
+            let display_path = display_path?;
 
             update_pending_for_finalize_from_collection(
                 &mut registry,
@@ -135,6 +252,20 @@ impl CommandArgs {
                 &group_id,
                 &collection.successes,
             )?;
+            if self.cleanup {
+                let collected: Vec<XID> =
+                    collection.successes.iter().map(|(xid, _)| *xid).collect();
+                runtime.block_on(cleanup_collected(
+                    &client,
+                    &pending_requests.collect_arids_for(&collected),
+                ));
+            }
+
+            if self.packages_only {
+                report_collection_failures(&collection);
+                println!("{}", display_path.display());
+                return Ok(());
+            }
 
             let preview = runtime.block_on(async {
                 dispatch_finalize_requests_parallel(
@@ -154,19 +285,33 @@ impl CommandArgs {
             // Sequential path (original behavior)
             // Phase 1: Collect Round 2 responses
             let collection = collect_round2(
-                &runtime,
+                runtime,
                 &client,
                 &registry_path,
                 &mut registry,
                 &owner_doc,
                 &group_id,
                 pending_requests,
-                self.timeout,
+                &timeout,
+                self.report.as_deref(),
             )?;
+            if self.cleanup {
+                let collected: Vec<XID> =
+                    collection.packages.keys().copied().collect();
+                runtime.block_on(cleanup_collected(
+                    &client,
+                    &pending_requests.collect_arids_for(&collected),
+                ));
+            }
+
+            if self.packages_only {
+                println!("{}", collection.display_path.display());
+                return Ok(());
+            }
 
             // Phase 2: Send finalize packages
             let preview = send_finalize_requests(
-                &runtime,
+                runtime,
                 &client,
                 &registry_path,
                 &mut registry,
@@ -205,13 +350,54 @@ impl CommandArgs {
 
 struct Round2Collection {
     /// All Round 2 packages: sender XID -> (recipient XID -> package)
-    packages: HashMap<XID, Vec<(XID, frost::keys::dkg::round2::Package)>>,
+    packages: BTreeMap<XID, Vec<(XID, frost::keys::dkg::round2::Package)>>,
     /// Where each participant wants to receive finalize requests
     next_response_arids: Vec<(XID, ARID)>,
+    /// Each participant's GSTP continuation, returned with its finalize
+    /// request
+    continuations: HashMap<XID, Envelope>,
+    /// Each share repair helper's sigma
+    repair_sigmas: Vec<(XID, RepairScalar)>,
     /// Display path for collected_round2.json
     display_path: PathBuf,
 }
 
+impl Round2Collection {
+    fn from_successes(
+        successes: Vec<(XID, Round2ResponseData)>,
+        display_path: PathBuf,
+    ) -> Self {
+        let mut packages = BTreeMap::new();
+        let mut next_response_arids = Vec::new();
+        let mut continuations = HashMap::new();
+        let mut repair_sigmas = Vec::new();
+        for (xid, data) in successes {
+            packages.insert(xid, data.packages);
+            next_response_arids.push((xid, data.next_response_arid));
+            if let Some(continuation) = data.continuation {
+                continuations.insert(xid, continuation);
+            }
+            if let Some(sigma) = data.repair_sigma {
+                repair_sigmas.push((xid, sigma));
+            }
+        }
+        Self {
+            packages,
+            next_response_arids,
+            continuations,
+            repair_sigmas,
+            display_path,
+        }
+    }
+}
+
+/// Phase name recorded in `--report` output.
+const ROUND2_PHASE: &str = "dkg_round2";
+
+/// Collect every participant's Round 2 response, persist the packages, and
+/// move the pending requests to the finalize send phase. Any rejection,
+/// error, or timeout fails the collection; the report at `report_path`, if
+/// any, is written either way.
 #[allow(clippy::too_many_arguments)]
 fn collect_round2(
     runtime: &Runtime,
@@ -221,7 +407,8 @@ fn collect_round2(
     owner: &XIDDocument,
     group_id: &ARID,
     pending_requests: &PendingRequests,
-    timeout: Option<u64>,
+    timeout: &CollectionTimeout,
+    report_path: Option<&Path>,
 ) -> Result<Round2Collection> {
     if is_verbose() {
         eprintln!(
@@ -230,135 +417,88 @@ fn collect_round2(
         );
     }
 
-    let mut all_packages: HashMap<
-        XID,
-        Vec<(XID, frost::keys::dkg::round2::Package)>,
-    > = HashMap::new();
-    let mut next_response_arids: Vec<(XID, ARID)> = Vec::new();
-    let mut errors: Vec<(XID, String)> = Vec::new();
-
+    let mut collection = CollectionResult::new();
     for (participant_xid, collect_from_arid) in pending_requests.iter_collect()
     {
-        let participant_name = registry
-            .participant(participant_xid)
-            .map(|r| {
-                r.pet_name()
-                    .map(|s| s.to_owned())
-                    .unwrap_or_else(|| participant_xid.ur_string())
-            })
-            .unwrap_or_else(|| participant_xid.ur_string());
-
         if is_verbose() {
+            let participant_name = report_name(registry, participant_xid)
+                .unwrap_or_else(|| participant_xid.ur_string());
             eprintln!("{}...", participant_name);
         }
 
-        match fetch_round2_response(
+        let started = Instant::now();
+        let result = fetch_round2_response(
             runtime,
             client,
             collect_from_arid,
-            timeout,
+            timeout.seconds(),
             owner,
             group_id,
             participant_xid,
-        ) {
-            Ok(collected) => {
-                all_packages.insert(*participant_xid, collected.packages);
-                next_response_arids
-                    .push((*participant_xid, collected.next_response_arid));
-            }
-            Err(e) => {
-                if is_verbose() {
-                    eprintln!("error: {}", e);
-                }
-                errors.push((*participant_xid, e.to_string()));
-            }
-        }
-    }
-
-    if !errors.is_empty() {
-        if is_verbose() {
-            eprintln!();
-            eprintln!("Failed to collect from {} participants:", errors.len());
-        }
-        for (xid, error) in &errors {
-            eprintln!("  {}: {}", xid.ur_string(), error);
-        }
-        bail!(
-            "Round 2 collection incomplete: {} of {} responses failed",
-            errors.len(),
-            pending_requests.len()
         );
-    }
-
-    // Persist collected round2 packages keyed by sender XID
-    let state_dir = group_state_dir(registry_path, group_id);
-    fs::create_dir_all(&state_dir).with_context(|| {
-        format!(
-            "Failed to create group state directory {}",
-            state_dir.display()
-        )
-    })?;
-
-    let collected_path = state_dir.join("collected_round2.json");
-    let mut root = serde_json::Map::new();
-    for (sender, packages) in &all_packages {
-        let mut sender_map = serde_json::Map::new();
-        // Find the response_arid for this sender
-        let response_arid = next_response_arids
-            .iter()
-            .find(|(xid, _)| xid == sender)
-            .map(|(_, arid)| arid)
-            .expect("sender must have response_arid");
-        sender_map.insert(
-            "response_arid".to_string(),
-            serde_json::Value::String(response_arid.ur_string()),
-        );
-        let mut packages_json = serde_json::Map::new();
-        for (recipient, package) in packages {
-            packages_json.insert(
-                recipient.ur_string(),
-                serde_json::to_value(package).expect("round2 package JSON"),
-            );
+        if is_verbose()
+            && let Err(e) = &result
+        {
+            eprintln!("error: {}", e);
         }
-        sender_map.insert(
-            "packages".to_string(),
-            serde_json::Value::Object(packages_json),
-        );
-        root.insert(sender.ur_string(), serde_json::Value::Object(sender_map));
+        collection.record(*participant_xid, result, started.elapsed());
     }
-    fs::write(&collected_path, serde_json::to_vec_pretty(&root)?)
-        .with_context(|| {
-            format!("Failed to write {}", collected_path.display())
-        })?;
 
-    // Update pending_requests with the ARIDs where participants want to receive
-    // finalize requests.
-    let mut new_pending = PendingRequests::new();
-    for (xid, send_to_arid) in &next_response_arids {
-        new_pending.add_send_only(*xid, *send_to_arid);
+    let mut report = CollectionReport::from_collection(
+        ROUND2_PHASE,
+        group_id,
+        &collection,
+        |xid| report_name(registry, xid),
+    );
+    if let Err(err) = check_round2_collection(&collection) {
+        report.write_to(report_path)?;
+        return Err(err);
     }
-    let group_record = registry
-        .group_mut(group_id)
-        .context("Group not found in registry")?;
-    group_record.set_pending_requests(new_pending);
-    registry.save(registry_path)?;
 
-    let display_path = std::env::current_dir()
-        .ok()
-        .and_then(|cwd| collected_path.strip_prefix(&cwd).ok())
-        .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| collected_path.clone());
+    let display_path =
+        persist_round2_packages(registry_path, group_id, &collection.successes);
+    if let Ok(display_path) = &display_path {
+        report.add_output(display_path);
+    }
+    report.write_to(report_path)?;
+    let display_path = display_path?;
+
+    update_pending_for_finalize_from_collection(
+        registry,
+        registry_path,
+        group_id,
+        &collection.successes,
+    )?;
 
-    Ok(Round2Collection {
-        packages: all_packages,
-        next_response_arids,
+    Ok(Round2Collection::from_successes(
+        collection.successes,
         display_path,
-    })
+    ))
 }
 
-struct CollectedRound2Entry {
-    packages: Vec<(XID, frost::keys::dkg::round2::Package)>,
-    next_response_arid: ARID,
+/// Fails, listing every failure, unless each participant sent a Round 2
+/// response; a missing participant's packages would leave the others unable
+/// to finalize.
+pub fn check_round2_collection<T>(
+    collection: &CollectionResult<T>,
+) -> Result<()> {
+    if collection.all_succeeded() {
+        return Ok(());
+    }
+    print_collection_failures(
+        &collection.rejections,
+        &collection.errors,
+        &collection.timeouts,
+    );
+    bail!(
+        "Round 2 collection incomplete: {} of {} responses failed ({} \
+         rejected, {} errors, {} timeouts)",
+        collection.total() - collection.successes.len(),
+        collection.total(),
+        collection.rejections.len(),
+        collection.errors.len(),
+        collection.timeouts.len()
+    );
 }
 
 fn fetch_round2_response(
@@ -369,76 +509,23 @@ fn fetch_round2_response(
     coordinator: &XIDDocument,
     expected_group: &ARID,
     expected_sender: &XID,
-) -> Result<CollectedRound2Entry> {
-    let envelope = runtime.block_on(async {
-        client
-            .get(arid, timeout)
-            .await?
-            .context("Response not found in Hubert storage")
-    })?;
+) -> Result<Round2ResponseData> {
+    let envelope = runtime
+        .block_on(async { client.get(arid, timeout).await })
+        .map_err(StorageError::into_anyhow)?
+        .ok_or(FetchError::NotYetAvailable)?;
 
     let coordinator_keys =
         coordinator.inception_private_keys().ok_or_else(|| {
             anyhow::anyhow!("Coordinator XID document has no private keys")
         })?;
 
-    let now = Date::now();
-    let sealed = SealedResponse::try_from_encrypted_envelope(
+    validate_and_extract_round2_response(
         &envelope,
-        None,
-        Some(now),
         coordinator_keys,
-    )?;
-
-    if let Ok(error) = sealed.error() {
-        let reason = error
-            .object_for_predicate("reason")
-            .ok()
-            .and_then(|e| e.extract_subject::<String>().ok())
-            .unwrap_or_else(|| "unknown reason".to_string());
-        bail!("Participant reported error: {}", reason);
-    }
-
-    let result = sealed.result().context("Response has no result envelope")?;
-
-    result
-        .check_subject_unit()?
-        .check_type("dkgRound2Response")?;
-
-    let group_id: ARID = result.extract_object_for_predicate("group")?;
-    if &group_id != expected_group {
-        bail!(
-            "Response group ID {} does not match expected {}",
-            group_id.ur_string(),
-            expected_group.ur_string()
-        );
-    }
-
-    let sender_xid: XID = result.extract_object_for_predicate("participant")?;
-    if &sender_xid != expected_sender {
-        bail!(
-            "Response participant {} does not match expected {}",
-            sender_xid.ur_string(),
-            expected_sender.ur_string()
-        );
-    }
-
-    let next_response_arid: ARID =
-        result.extract_object_for_predicate("response_arid")?;
-
-    let mut packages = Vec::new();
-    for pkg_env in result.objects_for_predicate("round2Package") {
-        let recipient: XID =
-            pkg_env.extract_object_for_predicate("recipient")?;
-        let pkg_json: bc_components::JSON =
-            pkg_env.extract_subject().context("round2Package missing")?;
-        let pkg: frost::keys::dkg::round2::Package =
-            serde_json::from_slice(pkg_json.as_bytes())
-                .context("Failed to deserialize round2 package")?;
-        packages.push((recipient, pkg));
-    }
-
-    Ok(CollectedRound2Entry { packages, next_response_arid })
+        expected_group,
+        expected_sender,
+    )
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -460,24 +547,32 @@ fn send_finalize_requests(
         Date::with_duration_from_now(Duration::from_secs(60 * 60));
 
     // Build participant info: (XID, XIDDocument, send_to_arid,
-    // collect_from_arid)
-    let participant_info: Vec<(XID, XIDDocument, ARID, ARID)> = collection
-        .next_response_arids
-        .iter()
-        .map(|(xid, send_to_arid)| {
-            let doc = registry
-                .participant(xid)
-                .map(|r| r.xid_document().clone())
-                .ok_or_else(|| {
-                    anyhow::anyhow!(
-                        "Participant {} not found in registry",
-                        xid.ur_string()
-                    )
-                })?;
-            let collect_from_arid = ARID::new();
-            Ok((*xid, doc, *send_to_arid, collect_from_arid))
-        })
-        .collect::<Result<Vec<_>>>()?;
+    // collect_from_arid), ordered by XID so the preview is reproducible
+    let mut next_response_arids = collection.next_response_arids.clone();
+    next_response_arids.sort_by_key(|(xid, _)| *xid);
+    let pending_requests = registry
+        .group(group_id)
+        .context("Group not found in registry")?
+        .pending_requests()
+        .clone();
+    let participant_info: Vec<(XID, XIDDocument, ARID, ARID)> =
+        next_response_arids
+            .iter()
+            .filter(|(xid, _)| pending_requests.is_unsent(xid))
+            .map(|(xid, send_to_arid)| {
+                let doc = registry
+                    .participant(xid)
+                    .map(|r| r.xid_document().clone())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Participant {} not found in registry",
+                            xid.ur_string()
+                        )
+                    })?;
+                let collect_from_arid = ARID::new();
+                Ok((*xid, doc, *send_to_arid, collect_from_arid))
+            })
+            .collect::<Result<Vec<_>>>()?;
 
     if is_verbose() {
         eprintln!(
@@ -489,6 +584,16 @@ fn send_finalize_requests(
         eprintln!();
     }
 
+    let group_record = registry
+        .group(group_id)
+        .context("Group not found in registry")?
+        .clone();
+    let repair = repair_finalize(
+        registry,
+        registry_path,
+        &group_record,
+        &collection.repair_sigmas,
+    )?;
     let mut preview_output: Option<(String, String)> = None;
 
     for (xid, recipient_doc, send_to_arid, collect_from_arid) in
@@ -510,8 +615,11 @@ fn send_finalize_requests(
         let request = build_finalize_request_for_participant(
             coordinator_doc,
             group_id,
+            &group_record,
             *collect_from_arid,
             &packages_for_recipient,
+            repair.as_ref().filter(|repair| repair.replacement == *xid),
+            collection.continuations.get(xid),
         )?;
 
         if preview && preview_output.is_none() {
@@ -533,25 +641,44 @@ fn send_finalize_requests(
         runtime.block_on(async {
             client.put(send_to_arid, &sealed_envelope).await
         })?;
+        record_finalize_sent(
+            registry,
+            registry_path,
+            group_id,
+            &[(*xid, *collect_from_arid)],
+        )?;
     }
 
-    // Build pending requests for finalize response collection
-    let mut new_pending_requests = PendingRequests::new();
-    for (xid, _, _, collect_from_arid) in &participant_info {
-        new_pending_requests.add_collect_only(*xid, *collect_from_arid);
-    }
+    Ok(preview_output)
+}
+
+/// Record that finalize requests went out to `sent`, so the group collects
+/// their responses from the given ARIDs.
+///
+/// A participant's listening ARID cannot be written twice, so after a failed
+/// send the next round2 run dispatches only to the participants still
+/// recorded as unsent. Once every request is out, the group is collecting
+/// finalize responses.
+fn record_finalize_sent(
+    registry: &mut Registry,
+    registry_path: &Path,
+    group_id: &ARID,
+    sent: &[(XID, ARID)],
+) -> Result<()> {
     let group_record = registry
         .group_mut(group_id)
         .context("Group not found in registry")?;
-    group_record.set_pending_requests(new_pending_requests);
-    registry.save(registry_path)?;
-
-    Ok(preview_output)
+    let mut pending_requests = group_record.pending_requests().clone();
+    for (xid, collect_from_arid) in sent {
+        pending_requests.mark_sent(xid, *collect_from_arid);
+    }
+    group_record.set_pending_requests(pending_requests);
+    registry.save(registry_path)
 }
 
 fn gather_packages_for_recipient(
     recipient: &XID,
-    all_packages: &HashMap<XID, Vec<(XID, frost::keys::dkg::round2::Package)>>,
+    all_packages: &BTreeMap<XID, Vec<(XID, frost::keys::dkg::round2::Package)>>,
 ) -> Result<Vec<(XID, frost::keys::dkg::round2::Package)>> {
     let mut result = Vec::new();
     for (sender, packages) in all_packages {
@@ -570,26 +697,111 @@ fn gather_packages_for_recipient(
     Ok(result)
 }
 
+/// What finalize sends the replacement in a share repair, which has no share
+/// of the group being refreshed to start from.
+struct RepairFinalize {
+    replacement: XID,
+    /// Each helper's sigma, which sum to the lost share
+    sigmas: Vec<(XID, RepairScalar)>,
+    /// The public key package of the group being refreshed
+    public_key_package: frost::keys::PublicKeyPackage,
+}
+
+/// The share repair data for the replacement's finalize request, or `None`
+/// if the group replaces no participant. Fails unless every other
+/// participant sent a sigma with its Round 2 response.
+fn repair_finalize(
+    registry: &Registry,
+    registry_path: &Path,
+    group_record: &GroupRecord,
+    sigmas: &[(XID, RepairScalar)],
+) -> Result<Option<RepairFinalize>> {
+    let Some(replacement) = refresh_replacement(registry, group_record)? else {
+        if !sigmas.is_empty() {
+            bail!(
+                "Round 2 responses carry share repair sigmas, but this group \
+                 replaces no participant"
+            );
+        }
+        return Ok(None);
+    };
+    let helpers: BTreeSet<XID> = group_record
+        .participants()
+        .iter()
+        .map(|participant| *participant.xid())
+        .filter(|xid| *xid != replacement)
+        .collect();
+    let senders: BTreeSet<XID> = sigmas.iter().map(|(xid, _)| *xid).collect();
+    if senders != helpers || sigmas.len() != helpers.len() {
+        bail!(
+            "Share repair needs one sigma from each of the {} other \
+             participants, but Round 2 collected {}",
+            helpers.len(),
+            sigmas.len()
+        );
+    }
+    let original_id = group_record
+        .refresh_of()
+        .context("Share repair in a group that is not a refresh")?;
+    let public_key_package = load_public_key_package(
+        &GroupStatePaths::new(registry_path),
+        &original_id,
+    )?;
+    Ok(Some(RepairFinalize {
+        replacement,
+        sigmas: sigmas.to_vec(),
+        public_key_package,
+    }))
+}
+
 /// Build a finalize request for a participant, including the response ARID
-/// where they should post their finalize response.
+/// where they should post their finalize response and the continuation from
+/// their Round 2 response. A share repair's replacement also receives the
+/// helpers' sigmas and the refreshed group's public key package.
+#[allow(clippy::too_many_arguments)]
 fn build_finalize_request_for_participant(
     sender: &XIDDocument,
     group_id: &ARID,
+    group_record: &GroupRecord,
     response_arid: ARID,
     packages: &[(XID, frost::keys::dkg::round2::Package)],
+    repair: Option<&RepairFinalize>,
+    continuation: Option<&Envelope>,
 ) -> Result<SealedRequest> {
-    let mut request = SealedRequest::new("dkgFinalize", ARID::new(), sender)
-        .with_parameter("group", *group_id)
-        .with_parameter("responseArid", response_arid);
+    let mut request = with_group_parameters(
+        SealedRequest::new("dkgFinalize", ARID::new(), sender)
+            .with_parameter("group", *group_id)
+            .with_parameter("responseArid", response_arid)
+            .with_peer_continuation(continuation),
+        group_record,
+    );
 
     for (pkg_sender, package) in packages {
         let encoded = serde_json::to_vec(package)?;
         let json = bc_components::JSON::from_data(encoded);
-        let pkg_envelope = Envelope::new(CBOR::from(json))
-            .add_assertion("sender", *pkg_sender);
+        let pkg_envelope = compress_payload(
+            Envelope::new(CBOR::from(json))
+                .add_assertion("sender", *pkg_sender),
+        )?;
         request = request.with_parameter("round2Package", pkg_envelope);
     }
 
+    if let Some(repair) = repair {
+        for (helper, sigma) in &repair.sigmas {
+            request = request.with_parameter(
+                "repairSigma",
+                repair_scalar_envelope(sigma).add_assertion("sender", *helper),
+            );
+        }
+        let json = bc_components::JSON::from_data(serde_json::to_vec(
+            &repair.public_key_package,
+        )?);
+        request = request.with_parameter(
+            "publicKeyPackage",
+            compress_payload(Envelope::new(CBOR::from(json)))?,
+        );
+    }
+
     Ok(request)
 }
 
@@ -601,6 +813,10 @@ fn build_finalize_request_for_participant(
 struct Round2ResponseData {
     packages: Vec<(XID, frost::keys::dkg::round2::Package)>,
     next_response_arid: ARID,
+    /// The participant's GSTP continuation, returned with the next request
+    continuation: Option<Envelope>,
+    /// The participant's sigma, if it helps in a share repair
+    repair_sigma: Option<RepairScalar>,
 }
 
 /// Collect Round 2 responses in parallel with progress display.
@@ -655,7 +871,8 @@ fn validate_and_extract_round2_response(
         None,
         Some(now),
         coordinator_keys,
-    )?;
+    )
+    .map_err(FetchError::decode)?;
 
     if let Ok(error) = sealed.error() {
         let reason = error
@@ -663,10 +880,18 @@ fn validate_and_extract_round2_response(
             .ok()
             .and_then(|e| e.extract_subject::<String>().ok())
             .unwrap_or_else(|| "unknown reason".to_string());
-        bail!("Participant reported error: {}", reason);
+        return Err(FetchError::Rejected(format!(
+            "Participant reported error: {reason}"
+        ))
+        .into());
     }
 
-    let result = sealed.result().context("Response has no result envelope")?;
+    let result = decompress_envelope(
+        sealed
+            .result()
+            .context("Response has no result envelope")?
+            .clone(),
+    )?;
 
     result
         .check_subject_unit()?
@@ -704,8 +929,18 @@ fn validate_and_extract_round2_response(
                 .context("Failed to deserialize round2 package")?;
         packages.push((recipient, pkg));
     }
-
-    Ok(Round2ResponseData { packages, next_response_arid })
+    let repair_sigma = result
+        .objects_for_predicate("repairSigma")
+        .first()
+        .map(extract_repair_scalar)
+        .transpose()?;
+
+    Ok(Round2ResponseData {
+        packages,
+        next_response_arid,
+        continuation: sealed.peer_continuation().cloned(),
+        repair_sigma,
+    })
 }
 
 /// Persist Round 2 packages from parallel collection results.
@@ -714,7 +949,8 @@ fn persist_round2_packages(
     group_id: &ARID,
     successes: &[(XID, Round2ResponseData)],
 ) -> Result<PathBuf> {
-    let state_dir = group_state_dir(registry_path, group_id);
+    let paths = GroupStatePaths::new(registry_path);
+    let state_dir = paths.group_dir(group_id);
     fs::create_dir_all(&state_dir).with_context(|| {
         format!(
             "Failed to create group state directory {}",
@@ -722,7 +958,7 @@ fn persist_round2_packages(
         )
     })?;
 
-    let collected_path = state_dir.join("collected_round2.json");
+    let collected_path = paths.collected_round2_path(group_id);
     let mut root = serde_json::Map::new();
     for (sender, data) in successes {
         let mut sender_map = serde_json::Map::new();
@@ -730,6 +966,20 @@ fn persist_round2_packages(
             "response_arid".to_string(),
             serde_json::Value::String(data.next_response_arid.ur_string()),
         );
+        if let Some(continuation) = &data.continuation {
+            sender_map.insert(
+                "continuation".to_string(),
+                serde_json::Value::String(continuation.ur_string()),
+            );
+        }
+        if let Some(sigma) = &data.repair_sigma {
+            sender_map.insert(
+                "repair_sigma".to_string(),
+                serde_json::Value::String(
+                    repair_scalar_envelope(sigma).ur_string(),
+                ),
+            );
+        }
         let mut packages_json = serde_json::Map::new();
         for (recipient, package) in &data.packages {
             packages_json.insert(
@@ -743,7 +993,7 @@ fn persist_round2_packages(
         );
         root.insert(sender.ur_string(), serde_json::Value::Object(sender_map));
     }
-    fs::write(&collected_path, serde_json::to_vec_pretty(&root)?)
+    write_atomically(&collected_path, serde_json::to_vec_pretty(&root)?)
         .with_context(|| {
             format!("Failed to write {}", collected_path.display())
         })?;
@@ -795,7 +1045,7 @@ async fn dispatch_finalize_requests_parallel(
         Date::with_duration_from_now(Duration::from_secs(60 * 60));
 
     // Build all_packages map for gather_packages_for_recipient
-    let all_packages: HashMap<
+    let all_packages: BTreeMap<
         XID,
         Vec<(XID, frost::keys::dkg::round2::Package)>,
     > = successes
@@ -803,12 +1053,31 @@ async fn dispatch_finalize_requests_parallel(
         .map(|(xid, data)| (*xid, data.packages.clone()))
         .collect();
 
+    let group_record = registry
+        .group(group_id)
+        .context("Group not found in registry")?
+        .clone();
+    let repair_sigmas: Vec<(XID, RepairScalar)> = successes
+        .iter()
+        .filter_map(|(xid, data)| data.repair_sigma.map(|sigma| (*xid, sigma)))
+        .collect();
+    let repair = repair_finalize(
+        registry,
+        registry_path,
+        &group_record,
+        &repair_sigmas,
+    )?;
+
     // Build messages
     let mut messages: Vec<(XID, ARID, Envelope, String)> = Vec::new();
     let mut collect_arids: Vec<(XID, ARID)> = Vec::new();
     let mut preview_output: Option<(String, String)> = None;
 
+    let pending_requests = group_record.pending_requests();
     for (xid, data) in successes {
+        if !pending_requests.is_unsent(xid) {
+            continue;
+        }
         let recipient_doc = registry
             .participant(xid)
             .map(|r| r.xid_document().clone())
@@ -833,8 +1102,11 @@ async fn dispatch_finalize_requests_parallel(
         let request = build_finalize_request_for_participant(
             coordinator,
             group_id,
+            &group_record,
             collect_from_arid,
             &packages_for_recipient,
+            repair.as_ref().filter(|repair| repair.replacement == *xid),
+            data.continuation.as_ref(),
         )?;
 
         if preview && preview_output.is_none() {
@@ -867,7 +1139,17 @@ async fn dispatch_finalize_requests_parallel(
     // Send all messages in parallel
     let send_results = parallel_send(client, messages).await;
 
-    // Check for send failures
+    // Record the requests that went out before reporting any that did not
+    let sent: Vec<(XID, ARID)> = collect_arids
+        .into_iter()
+        .filter(|(xid, _)| {
+            send_results
+                .iter()
+                .any(|(sent_xid, result)| sent_xid == xid && result.is_ok())
+        })
+        .collect();
+    record_finalize_sent(registry, registry_path, group_id, &sent)?;
+
     let failures: Vec<_> = send_results
         .iter()
         .filter_map(|(xid, result)| {
@@ -885,58 +1167,199 @@ async fn dispatch_finalize_requests_parallel(
         );
     }
 
-    // Update pending requests for finalize response collection
-    let mut new_pending_requests = PendingRequests::new();
-    for (xid, collect_from_arid) in &collect_arids {
-        new_pending_requests.add_collect_only(*xid, *collect_from_arid);
+    Ok(preview_output)
+}
+
+/// Read back the packages persisted by an earlier collection, in the format
+/// written by `persist_round2_packages`.
+fn load_round2_packages(
+    registry_path: &Path,
+    group_id: &ARID,
+) -> Result<(Vec<(XID, Round2ResponseData)>, PathBuf)> {
+    let collected_path =
+        GroupStatePaths::new(registry_path).collected_round2_path(group_id);
+    if !collected_path.exists() {
+        bail!(
+            "Round 2 packages not found at {}. Did you run round2 \
+             --packages-only?",
+            collected_path.display()
+        );
     }
-    let group_record = registry
-        .group_mut(group_id)
-        .context("Group not found in registry")?;
-    group_record.set_pending_requests(new_pending_requests);
-    registry.save(registry_path)?;
+    let root: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(&fs::read(&collected_path).with_context(
+            || format!("Failed to read {}", collected_path.display()),
+        )?)
+        .context("Failed to parse collected_round2.json")?;
+
+    let mut successes = Vec::new();
+    for (sender, entry) in &root {
+        let sender = XID::from_ur_string(sender)
+            .context("Invalid XID in collected_round2.json")?;
+        let next_response_arid = entry
+            .get("response_arid")
+            .and_then(|value| value.as_str())
+            .context("collected_round2.json entry missing response_arid")?;
+        let next_response_arid = ARID::from_ur_string(next_response_arid)
+            .context("Invalid response ARID in collected_round2.json")?;
+        let continuation = entry
+            .get("continuation")
+            .and_then(|value| value.as_str())
+            .map(Envelope::from_ur_string)
+            .transpose()
+            .context("Invalid continuation in collected_round2.json")?;
+        let repair_sigma = entry
+            .get("repair_sigma")
+            .and_then(|value| value.as_str())
+            .map(|ur| extract_repair_scalar(&Envelope::from_ur_string(ur)?))
+            .transpose()
+            .context("Invalid repair sigma in collected_round2.json")?;
+        let packages_json = entry
+            .get("packages")
+            .and_then(|value| value.as_object())
+            .context("collected_round2.json entry missing packages")?;
+        let mut packages = Vec::new();
+        for (recipient, package) in packages_json {
+            let recipient = XID::from_ur_string(recipient)
+                .context("Invalid XID in collected_round2.json")?;
+            let package: frost::keys::dkg::round2::Package =
+                serde_json::from_value(package.clone())
+                    .context("Failed to parse Round 2 package")?;
+            packages.push((recipient, package));
+        }
+        successes.push((
+            sender,
+            Round2ResponseData {
+                packages,
+                next_response_arid,
+                continuation,
+                repair_sigma,
+            },
+        ));
+    }
+    // UR strings do not sort like XIDs; match the order of a live collection
+    successes.sort_by_key(|(xid, _)| *xid);
 
-    Ok(preview_output)
+    let display_path = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| collected_path.strip_prefix(&cwd).ok())
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| collected_path.clone());
+
+    Ok((successes, display_path))
 }
 
-/// Print summary for parallel collection.
-fn print_summary_parallel(
+/// Refuses `--from-collected` once the group is past the finalize dispatch,
+/// since re-sending would address requests to ARIDs already written. That
+/// covers a registry whose pending requests only await finalize responses.
+fn ensure_finalize_not_sent(
+    registry_path: &Path,
+    group_id: &ARID,
+    group_record: &GroupRecord,
+) -> Result<()> {
+    let finalize_path =
+        GroupStatePaths::new(registry_path).collected_finalize_path(group_id);
+    if group_record.status().is_finalized() || finalize_path.exists() {
+        bail!(
+            "Finalize responses have already been collected for this group; \
+             there is nothing left to send"
+        );
+    }
+    if group_record.pending_requests().is_collect_only() {
+        bail!(
+            "Finalize requests have already been sent to every participant; \
+             collect their responses with `frost dkg coordinator finalize`"
+        );
+    }
+    Ok(())
+}
+
+/// Requires the persisted Round 2 packages to come from exactly the group's
+/// participants, so a stale or foreign collected_round2.json is not sent.
+fn check_collected_covers_group(
+    group_record: &GroupRecord,
+    successes: &[(XID, Round2ResponseData)],
+) -> Result<()> {
+    let expected: BTreeSet<XID> = group_record
+        .participants()
+        .iter()
+        .map(|participant| *participant.xid())
+        .collect();
+    let collected: BTreeSet<XID> =
+        successes.iter().map(|(xid, _)| *xid).collect();
+    if collected != expected {
+        bail!(
+            "collected_round2.json holds packages from {} participants, but \
+             the group has {}; it does not belong to this group's current \
+             Round 2",
+            collected.len(),
+            expected.len()
+        );
+    }
+    Ok(())
+}
+
+/// Report rejections, errors, and timeouts from a parallel collection.
+/// Returns `true` if every participant responded successfully.
+fn report_collection_failures(
     collection: &CollectionResult<Round2ResponseData>,
-    display_path: &Path,
-    preview: Option<(String, String)>,
+) -> bool {
+    print_collection_failures(
+        &collection.rejections,
+        &collection.errors,
+        &collection.timeouts,
+    );
+
+    if !collection.all_succeeded() {
+        eprintln!();
+        eprintln!(
+            "Round 2 collection incomplete: {} succeeded, {} rejected, {} errors, {} timeouts",
+            collection.successes.len(),
+            collection.rejections.len(),
+            collection.errors.len(),
+            collection.timeouts.len()
+        );
+        return false;
+    }
+    true
+}
+
+/// List participants who rejected, failed, or timed out, each under its own
+/// heading. Shared by the sequential and parallel collections.
+fn print_collection_failures(
+    rejections: &[(XID, String)],
+    errors: &[(XID, String)],
+    timeouts: &[XID],
 ) {
-    // Report any failures
-    if !collection.rejections.is_empty() {
+    if !rejections.is_empty() {
         eprintln!();
         eprintln!("Rejections:");
-        for (xid, reason) in &collection.rejections {
+        for (xid, reason) in rejections {
             eprintln!("  {}: {}", xid.ur_string(), reason);
         }
     }
-    if !collection.errors.is_empty() {
+    if !errors.is_empty() {
         eprintln!();
         eprintln!("Errors:");
-        for (xid, error) in &collection.errors {
+        for (xid, error) in errors {
             eprintln!("  {}: {}", xid.ur_string(), error);
         }
     }
-    if !collection.timeouts.is_empty() {
+    if !timeouts.is_empty() {
         eprintln!();
         eprintln!("Timeouts:");
-        for xid in &collection.timeouts {
+        for xid in timeouts {
             eprintln!("  {}", xid.ur_string());
         }
     }
+}
 
-    if !collection.all_succeeded() {
-        eprintln!();
-        eprintln!(
-            "Round 2 collection incomplete: {} succeeded, {} rejected, {} errors, {} timeouts",
-            collection.successes.len(),
-            collection.rejections.len(),
-            collection.errors.len(),
-            collection.timeouts.len()
-        );
+/// Print summary for parallel collection.
+fn print_summary_parallel(
+    collection: &CollectionResult<Round2ResponseData>,
+    display_path: &Path,
+    preview: Option<(String, String)>,
+) {
+    if !report_collection_failures(collection) {
         return;
     }
 
@@ -1,6 +1,8 @@
 pub mod common;
 pub mod coordinator;
 pub mod participant;
+pub mod verify_transcript;
+pub mod wizard;
 
 use anyhow::Result;
 use clap::{Args, Subcommand};
@@ -20,6 +22,10 @@ enum Commands {
     Coordinator(coordinator::CommandArgs),
     /// Participant-only DKG commands
     Participant(participant::CommandArgs),
+    /// Re-derive a finished group's verifying key from its Round 1 packages
+    VerifyTranscript(verify_transcript::CommandArgs),
+    /// Walk the coordinator through the remaining DKG steps interactively
+    Wizard(wizard::CommandArgs),
 }
 
 impl CommandArgs {
@@ -27,6 +33,8 @@ impl CommandArgs {
         match self.command {
             Commands::Coordinator(args) => args.exec(),
             Commands::Participant(args) => args.exec(),
+            Commands::VerifyTranscript(args) => args.exec(),
+            Commands::Wizard(args) => args.exec(),
         }
     }
 }
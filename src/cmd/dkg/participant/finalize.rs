@@ -1,7 +1,7 @@
-use std::{collections::BTreeMap, fs};
+use std::{collections::BTreeMap, fs, path::Path};
 
 use anyhow::{Context, Result, bail};
-use bc_components::{ARID, JSON, XID, XIDProvider};
+use bc_components::{ARID, JSON, SigningPublicKey, XID, XIDProvider};
 use bc_envelope::prelude::*;
 use clap::Parser;
 use frost_ed25519 as frost;
@@ -14,15 +14,24 @@ use tokio::runtime::Runtime;
 use crate::{
     cmd::{
         busy::{get_with_indicator, put_with_indicator},
+        common::{load_registry, write_atomically},
         dkg::common::{
-            OptionalStorageSelector, group_state_dir, parse_arid_ur,
-            signing_key_from_verifying,
+            GroupStatePaths, OptionalStorageSelector,
+            check_refreshed_verifying_key, check_request_continuation,
+            check_strict_sender, parse_group_id, read_ur_input,
+            refreshed_group, response_recipient, seal_response,
+            signing_key_from_verifying, verify_group_parameters,
         },
         is_verbose,
         registry::participants_file_path,
-        storage::StorageClient,
+        sign::common::load_public_key_package,
+        storage::{
+            StorageClient, compress_payload, decompress_envelope,
+            shared_runtime,
+        },
     },
-    registry::Registry,
+    dkg::{extract_repair_scalar, repaired_key_package},
+    registry::{GroupProvenance, GroupRecord, OwnerRecord, Registry},
 };
 
 /// Respond to finalize request (participant only).
@@ -44,9 +53,15 @@ pub struct CommandArgs {
     #[arg(long = "preview")]
     preview: bool,
 
+    /// Respond for every group awaiting a finalize request instead of a
+    /// single group (`--timeout` applies to each group in turn)
+    #[arg(long = "all", conflicts_with_all = ["group_id", "preview"])]
+    all: bool,
+
     /// Group ID to respond for
-    #[arg(value_name = "GROUP_ID")]
-    group_id: String,
+    /// (`-` reads it from stdin)
+    #[arg(value_name = "GROUP_ID", required_unless_present = "all")]
+    group_id: Option<String>,
 }
 
 impl CommandArgs {
@@ -57,7 +72,7 @@ impl CommandArgs {
 
         let registry_path = participants_file_path(self.registry.clone())?;
         let mut registry =
-            Registry::load(&registry_path).with_context(|| {
+            load_registry(&registry_path).with_context(|| {
                 format!(
                     "Failed to load registry at {}",
                     registry_path.display()
@@ -69,276 +84,536 @@ impl CommandArgs {
             .context("Registry owner is required")?
             .clone();
 
-        let group_id = parse_arid_ur(&self.group_id)?;
-        let group_record = registry
-            .group(&group_id)
-            .context("Group not found in registry")?
-            .clone();
+        let runtime = shared_runtime()?;
+        let client = runtime
+            .block_on(async { StorageClient::shared(selection).await })?;
+        let ctx = RespondContext {
+            runtime,
+            client: &client,
+            registry_path: &registry_path,
+            owner: &owner,
+            timeout: self.timeout,
+            preview: self.preview,
+        };
+
+        if self.all {
+            return respond_all(&ctx, &mut registry);
+        }
 
-        let listening_at_arid = group_record.listening_at_arid().context(
-            "No listening ARID for this group. Did you receive finalize send?",
+        let group_id = parse_group_id(
+            &registry,
+            &read_ur_input(
+                self.group_id
+                    .as_deref()
+                    .context("GROUP_ID is required unless --all is given")?,
+            )?,
         )?;
-
-        // Load Round 2 secret
-        let state_dir = group_state_dir(&registry_path, &group_id);
-        let round2_secret_path = state_dir.join("round2_secret.json");
-        if !round2_secret_path.exists() {
-            bail!(
-                "Round 2 secret not found at {}. Did you run round2?",
-                round2_secret_path.display()
-            );
-        }
-        let round2_secret: frost::keys::dkg::round2::SecretPackage =
-            serde_json::from_slice(&fs::read(&round2_secret_path)?)?;
-
-        // Load collected Round 1 packages (from earlier phases)
-        let round1_path = state_dir.join("collected_round1.json");
-        if !round1_path.exists() {
-            bail!(
-                "Round 1 packages not found at {}. Did you receive earlier phases?",
-                round1_path.display()
-            );
+        if let Some(key) = respond_for_group(&ctx, &mut registry, &group_id)? {
+            if is_verbose() {
+                eprintln!("{}", key.ur_string());
+            } else {
+                println!("{}", key.ur_string());
+            }
         }
-        let round1_json: serde_json::Map<String, serde_json::Value> =
-            serde_json::from_slice(&fs::read(&round1_path).with_context(
-                || format!("Failed to read {}", round1_path.display()),
-            )?)
-            .context("Failed to parse collected_round1.json")?;
 
-        if is_verbose() {
-            eprintln!("Fetching finalize request from Hubert...");
-        }
+        Ok(())
+    }
+}
 
-        let runtime = Runtime::new()?;
-        let client = runtime.block_on(async {
-            StorageClient::from_selection(selection).await
-        })?;
+// -----------------------------------------------------------------------------
+// Batch mode
+// -----------------------------------------------------------------------------
+
+/// Groups that have completed Round 2 and are listening for the
+/// coordinator's finalize request.
+fn groups_awaiting_finalize(registry: &Registry) -> Vec<ARID> {
+    registry
+        .groups()
+        .filter(|(_, record)| {
+            record.listening_at_arid().is_some()
+                && record.contributions().round2_secret.is_some()
+                && record.contributions().key_package.is_none()
+        })
+        .map(|(group_id, _)| group_id)
+        .collect()
+}
 
-        let request_envelope = get_with_indicator(
-            &runtime,
-            &client,
-            &listening_at_arid,
-            "Finalize request",
-            self.timeout,
-        )?
-        .context("Finalize request not found in Hubert storage")?;
-
-        let owner_keys = owner
-            .xid_document()
-            .inception_private_keys()
-            .context("Owner XID document has no private keys")?;
-
-        let now = Date::now();
-        let sealed_request = SealedRequest::try_from_envelope(
-            &request_envelope,
-            None,
-            Some(now),
-            owner_keys,
-        )?;
+/// Respond to the finalize request of every group awaiting one, printing a
+/// per-group summary. A failure for one group does not stop the others.
+fn respond_all(
+    ctx: &RespondContext<'_>,
+    registry: &mut Registry,
+) -> Result<()> {
+    let group_ids = groups_awaiting_finalize(registry);
+    if group_ids.is_empty() {
+        eprintln!("No groups are awaiting a finalize request.");
+        return Ok(());
+    }
 
-        if sealed_request.function() != &Function::from("dkgFinalize") {
-            bail!("Unexpected request function: {}", sealed_request.function());
+    let mut failures = 0;
+    for group_id in &group_ids {
+        match respond_for_group(ctx, registry, group_id) {
+            Ok(Some(key)) => {
+                println!("{}: {}", group_id.ur_string(), key.ur_string());
+            }
+            Ok(None) => {}
+            Err(err) => {
+                failures += 1;
+                eprintln!("{}: failed: {err:#}", group_id.ur_string());
+            }
         }
+    }
 
-        // Verify coordinator sender
-        let expected_coordinator = group_record.coordinator().xid();
-        if sealed_request.sender().xid() != *expected_coordinator {
-            bail!(
-                "Unexpected request sender: {} (expected coordinator {})",
-                sealed_request.sender().xid().ur_string(),
-                expected_coordinator.ur_string()
-            );
-        }
+    if failures > 0 {
+        bail!(
+            "Finalize failed for {} of {} groups",
+            failures,
+            group_ids.len()
+        );
+    }
+    Ok(())
+}
 
-        let request_group_id: ARID =
-            sealed_request.extract_object_for_parameter("group")?;
-        if request_group_id != group_id {
-            bail!(
-                "Request group ID {} does not match expected {}",
-                request_group_id.ur_string(),
-                group_id.ur_string()
-            );
-        }
+// -----------------------------------------------------------------------------
+// Finalize response
+// -----------------------------------------------------------------------------
 
-        let response_arid: ARID =
-            sealed_request.extract_object_for_parameter("responseArid")?;
-
-        // Build identifier mapping
-        let mut sorted_xids: Vec<XID> = group_record
-            .participants()
-            .iter()
-            .map(|p| *p.xid())
-            .collect();
-        if !sorted_xids.contains(&owner.xid()) {
-            sorted_xids.push(owner.xid());
-        }
-        sorted_xids.sort();
-        sorted_xids.dedup();
-
-        let xid_to_id: std::collections::HashMap<XID, frost::Identifier> =
-            sorted_xids
-                .iter()
-                .enumerate()
-                .map(|(i, xid)| {
-                    let id =
-                        frost::Identifier::try_from((i + 1) as u16).unwrap();
-                    (*xid, id)
-                })
-                .collect();
-
-        // Round1 packages map (exclude self)
-        let mut round1_map: BTreeMap<
-            frost::Identifier,
-            frost::keys::dkg::round1::Package,
-        > = BTreeMap::new();
-        for (xid_str, value) in &round1_json {
-            let xid = XID::from_ur_string(xid_str)
-                .context("Invalid XID in collected_round1.json")?;
-            if xid == owner.xid() {
-                continue;
-            }
-            let id = xid_to_id.get(&xid).ok_or_else(|| {
-                anyhow::anyhow!("Unknown participant XID {}", xid.ur_string())
-            })?;
-            let pkg: frost::keys::dkg::round1::Package =
-                serde_json::from_value(value.clone())
-                    .context("Failed to parse Round 1 package")?;
-            round1_map.insert(*id, pkg);
-        }
+struct RespondContext<'a> {
+    runtime: &'a Runtime,
+    client: &'a StorageClient,
+    registry_path: &'a Path,
+    owner: &'a OwnerRecord,
+    timeout: Option<u64>,
+    preview: bool,
+}
 
-        // Round2 packages extracted from request (exclude self)
-        let mut round2_map: BTreeMap<
-            frost::Identifier,
-            frost::keys::dkg::round2::Package,
-        > = BTreeMap::new();
-        for pkg_env in sealed_request.objects_for_parameter("round2Package") {
-            let sender_xid: XID =
-                pkg_env.extract_object_for_predicate("sender")?;
-            if sender_xid == owner.xid() {
-                continue;
-            }
-            let id = xid_to_id.get(&sender_xid).ok_or_else(|| {
-                anyhow::anyhow!("Unknown sender XID {}", sender_xid.ur_string())
-            })?;
-            let pkg_json: JSON =
-                pkg_env.extract_subject().context("round2Package missing")?;
-            let pkg: frost::keys::dkg::round2::Package =
-                serde_json::from_slice(pkg_json.as_bytes())
-                    .context("Failed to deserialize round2 package")?;
-            round2_map.insert(*id, pkg);
-        }
+/// Fetch the finalize request for one group, run DKG part3, and post the
+/// response. Returns the group verifying key, or `None` in preview mode.
+fn respond_for_group(
+    ctx: &RespondContext<'_>,
+    registry: &mut Registry,
+    group_id: &ARID,
+) -> Result<Option<SigningPublicKey>> {
+    let group_record = registry
+        .group(group_id)
+        .context("Group not found in registry")?
+        .clone();
+
+    let listening_at_arid = group_record.listening_at_arid().context(
+        "No listening ARID for this group. Did you receive finalize send?",
+    )?;
+
+    // Load Round 2 secret
+    let paths = GroupStatePaths::new(ctx.registry_path);
+    let round2_secret_path = paths.round2_secret_path(group_id);
+    if !round2_secret_path.exists() {
+        bail!(
+            "Round 2 secret not found at {}. Did you run round2?",
+            round2_secret_path.display()
+        );
+    }
+    let round2_secret: frost::keys::dkg::round2::SecretPackage =
+        serde_json::from_slice(&fs::read(&round2_secret_path)?)?;
+
+    // Load collected Round 1 packages (from earlier phases)
+    let round1_path = paths.collected_round1_path(group_id);
+    if !round1_path.exists() {
+        bail!(
+            "Round 1 packages not found at {}. Did you receive earlier phases?",
+            round1_path.display()
+        );
+    }
+    let round1_json: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(&fs::read(&round1_path).with_context(|| {
+            format!("Failed to read {}", round1_path.display())
+        })?)
+        .context("Failed to parse collected_round1.json")?;
+
+    if is_verbose() {
+        eprintln!("Fetching finalize request from Hubert...");
+    }
 
-        if is_verbose() {
-            eprintln!(
-                "Received {} Round 2 packages. Running DKG part3...",
-                round2_map.len()
-            );
+    let request_envelope = get_with_indicator(
+        ctx.runtime,
+        ctx.client,
+        &listening_at_arid,
+        "Finalize request",
+        ctx.timeout,
+    )?
+    .context("Nothing posted yet at the finalize request ARID")?;
+
+    let owner_keys = ctx
+        .owner
+        .xid_document()
+        .inception_private_keys()
+        .context("Owner XID document has no private keys")?;
+
+    let now = Date::now();
+    let sealed_request = SealedRequest::try_from_envelope(
+        &request_envelope,
+        None,
+        Some(now),
+        owner_keys,
+    )?;
+
+    if sealed_request.function() != &Function::from("dkgFinalize") {
+        bail!("Unexpected request function: {}", sealed_request.function());
+    }
+
+    // Verify coordinator sender
+    let expected_coordinator = group_record.coordinator().xid();
+    if sealed_request.sender().xid() != *expected_coordinator {
+        bail!(
+            "Unexpected request sender: {} (expected coordinator {})",
+            sealed_request.sender().xid().ur_string(),
+            expected_coordinator.ur_string()
+        );
+    }
+    check_strict_sender(registry, sealed_request.sender())?;
+
+    let request_group_id: ARID =
+        sealed_request.extract_object_for_parameter("group")?;
+    if request_group_id != *group_id {
+        bail!(
+            "Request group ID {} does not match expected {}",
+            request_group_id.ur_string(),
+            group_id.ur_string()
+        );
+    }
+    verify_group_parameters(&sealed_request, &group_record)?;
+    check_request_continuation(&sealed_request, &listening_at_arid)?;
+
+    let response_arid: ARID =
+        sealed_request.extract_object_for_parameter("responseArid")?;
+
+    // Identifier mapping recorded when the invite was answered
+    let xid_to_id: std::collections::HashMap<XID, frost::Identifier> =
+        group_record.identifiers()?;
+
+    // Round1 packages map (exclude self)
+    let mut round1_map: BTreeMap<
+        frost::Identifier,
+        frost::keys::dkg::round1::Package,
+    > = BTreeMap::new();
+    for (xid_str, value) in &round1_json {
+        let xid = XID::from_ur_string(xid_str)
+            .context("Invalid XID in collected_round1.json")?;
+        if xid == ctx.owner.xid() {
+            continue;
         }
+        let id = xid_to_id.get(&xid).ok_or_else(|| {
+            anyhow::anyhow!("Unknown participant XID {}", xid.ur_string())
+        })?;
+        let pkg: frost::keys::dkg::round1::Package =
+            serde_json::from_value(value.clone())
+                .context("Failed to parse Round 1 package")?;
+        round1_map.insert(*id, pkg);
+    }
 
-        let (key_package, public_key_package) =
-            frost::keys::dkg::part3(&round2_secret, &round1_map, &round2_map)
-                .map_err(|e| anyhow::anyhow!("FROST DKG part3 failed: {}", e))?;
+    // Round2 packages extracted from request (exclude self)
+    let mut round2_map: BTreeMap<
+        frost::Identifier,
+        frost::keys::dkg::round2::Package,
+    > = BTreeMap::new();
+    for pkg_env in sealed_request.objects_for_parameter("round2Package") {
+        let pkg_env = decompress_envelope(pkg_env)?;
+        let sender_xid: XID = pkg_env.extract_object_for_predicate("sender")?;
+        if sender_xid == ctx.owner.xid() {
+            continue;
+        }
+        let id = xid_to_id.get(&sender_xid).ok_or_else(|| {
+            anyhow::anyhow!("Unknown sender XID {}", sender_xid.ur_string())
+        })?;
+        let pkg_json: JSON =
+            pkg_env.extract_subject().context("round2Package missing")?;
+        let pkg: frost::keys::dkg::round2::Package =
+            serde_json::from_slice(pkg_json.as_bytes())
+                .context("Failed to deserialize round2 package")?;
+        round2_map.insert(*id, pkg);
+    }
 
-        let group_verifying_key =
-            signing_key_from_verifying(public_key_package.verifying_key())
-                .context("Failed to derive group verifying key")?;
+    if is_verbose() {
+        eprintln!(
+            "Received {} Round 2 packages. Running DKG part3...",
+            round2_map.len()
+        );
+    }
 
-        if is_verbose() {
-            eprintln!("Generated key package and public key package.");
+    // A repaired share has no group being refreshed in this registry: the
+    // finalize request carries what rebuilds it
+    let repaired = group_record.provenance() == GroupProvenance::Repaired;
+    let (key_package, public_key_package) = match group_record.refresh_of() {
+        Some(_) if repaired => repair_shares(
+            &paths,
+            group_id,
+            &group_record,
+            &ctx.owner.xid(),
+            &sealed_request,
+            &round2_secret,
+            &round1_map,
+            &round2_map,
+        )?,
+        Some(original_id) => refresh_shares(
+            registry,
+            &paths,
+            &original_id,
+            &round2_secret,
+            &round1_map,
+            &round2_map,
+        )?,
+        None => {
+            frost::keys::dkg::part3(&round2_secret, &round1_map, &round2_map)
+                .map_err(|e| anyhow::anyhow!("FROST DKG part3 failed: {}", e))?
         }
-
-        // Persist key packages
-        let key_package_path = state_dir.join("key_package.json");
-        let public_key_package_path = state_dir.join("public_key_package.json");
-        fs::write(&key_package_path, serde_json::to_vec_pretty(&key_package)?)?;
-        fs::write(
-            &public_key_package_path,
-            serde_json::to_vec_pretty(&public_key_package)?,
+    };
+
+    let group_verifying_key =
+        signing_key_from_verifying(public_key_package.verifying_key())
+            .context("Failed to derive group verifying key")?;
+    if !repaired {
+        check_refreshed_verifying_key(
+            registry,
+            &group_record,
+            &group_verifying_key,
         )?;
+    }
 
-        // Build response
-        let response_body = build_response_body(
-            &group_id,
-            &owner.xid(),
-            &key_package,
-            &public_key_package,
-        )?;
+    if is_verbose() {
+        eprintln!("Generated key package and public key package.");
+    }
 
-        let signer_keys = owner
-            .xid_document()
-            .inception_private_keys()
-            .context("Owner XID document has no signing keys")?;
-
-        // Coordinator doc
-        let coordinator_xid = group_record.coordinator().xid();
-        let coordinator_doc = registry
-            .participant(coordinator_xid)
-            .map(|r| r.xid_document().clone())
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Coordinator {} not found in registry",
-                    coordinator_xid.ur_string()
-                )
-            })?;
+    // Persist key packages
+    let key_package_path = paths.key_package_path(group_id);
+    let public_key_package_path = paths.public_key_package_path(group_id);
+    write_atomically(
+        &key_package_path,
+        serde_json::to_vec_pretty(&key_package)?,
+    )?;
+    write_atomically(
+        &public_key_package_path,
+        serde_json::to_vec_pretty(&public_key_package)?,
+    )?;
+
+    // Build response
+    let response_body = build_response_body(
+        group_id,
+        &ctx.owner.xid(),
+        &key_package,
+        &public_key_package,
+    )?;
+
+    let signer_keys = ctx
+        .owner
+        .xid_document()
+        .inception_private_keys()
+        .context("Owner XID document has no signing keys")?;
+
+    // Coordinator doc
+    let coordinator_xid = group_record.coordinator().xid();
+    let coordinator_doc = registry
+        .participant(coordinator_xid)
+        .map(|r| r.xid_document().clone())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Coordinator {} not found in registry",
+                coordinator_xid.ur_string()
+            )
+        })?;
+    let recipient_doc =
+        response_recipient(&sealed_request, registry, coordinator_doc)?;
 
-        let sealed_response = SealedResponse::new_success(
-            sealed_request.id(),
-            owner.xid_document().clone(),
-        )
-        .with_result(response_body)
-        .with_peer_continuation(sealed_request.peer_continuation());
+    let sealed_response = SealedResponse::new_success(
+        sealed_request.id(),
+        ctx.owner.xid_document().clone(),
+    )
+    .with_result(compress_payload(response_body)?)
+    .with_peer_continuation(sealed_request.peer_continuation());
 
-        if self.preview {
-            if is_verbose() {
-                eprintln!("{}", group_verifying_key.ur_string());
-            }
-            let unsealed_envelope =
-                sealed_response.to_envelope(None, Some(signer_keys), None)?;
-            println!("{}", unsealed_envelope.ur_string());
-            return Ok(());
+    if ctx.preview {
+        if is_verbose() {
+            eprintln!("{}", group_verifying_key.ur_string());
         }
+        let unsealed_envelope =
+            sealed_response.to_envelope(None, Some(signer_keys), None)?;
+        println!("{}", unsealed_envelope.ur_string());
+        return Ok(None);
+    }
 
-        let response_envelope = sealed_response.to_envelope(
-            None,
-            Some(signer_keys),
-            Some(&coordinator_doc),
-        )?;
+    let response_envelope = seal_response(
+        &sealed_response,
+        None,
+        signer_keys,
+        &recipient_doc,
+        // The key package holds this participant's signing share, so the
+        // response is sealed to its recipient only
+        &[],
+    )?;
+
+    put_with_indicator(
+        ctx.runtime,
+        ctx.client,
+        &response_arid,
+        &response_envelope,
+        "Finalize Response",
+    )?;
+
+    // Update registry contributions
+    let refresh_of = group_record.refresh_of();
+    let group_record = registry
+        .group_mut(group_id)
+        .context("Group not found in registry")?;
+    let mut contributions = group_record.contributions().clone();
+    contributions.key_package =
+        Some(key_package_path.to_string_lossy().into_owned());
+    group_record.set_contributions(contributions);
+    group_record.clear_listening_at_arid();
+    group_record.set_verifying_key(group_verifying_key.clone());
+    registry.save(ctx.registry_path)?;
+
+    if is_verbose() {
+        eprintln!("Posted finalize response to {}", response_arid.ur_string());
+    }
+    if let Some(original_id) = refresh_of
+        && repaired
+    {
+        eprintln!(
+            "Rebuilt and refreshed the share of group {} that you replace.",
+            original_id.ur_string()
+        );
+    } else if let Some(original_id) = refresh_of {
+        eprintln!(
+            "Refreshed your share of group {}. Once the refreshed group has \
+             signed, delete the old share: {}",
+            original_id.ur_string(),
+            paths.key_package_path(&original_id).display()
+        );
+    }
 
-        put_with_indicator(
-            &runtime,
-            &client,
-            &response_arid,
-            &response_envelope,
-            "Finalize Response",
-        )?;
+    Ok(Some(group_verifying_key))
+}
 
-        // Update registry contributions
-        let group_record = registry
-            .group_mut(&group_id)
-            .context("Group not found in registry")?;
-        let mut contributions = group_record.contributions().clone();
-        contributions.key_package =
-            Some(key_package_path.to_string_lossy().into_owned());
-        group_record.set_contributions(contributions);
-        group_record.clear_listening_at_arid();
-        group_record.set_verifying_key(group_verifying_key);
-        let group_key = group_record.verifying_key().cloned();
-        registry.save(&registry_path)?;
+/// Runs the refresh variant of DKG part3, adding this participant's share of
+/// zero to its key package in the group being refreshed.
+fn refresh_shares(
+    registry: &Registry,
+    paths: &GroupStatePaths,
+    original_id: &ARID,
+    round2_secret: &frost::keys::dkg::round2::SecretPackage,
+    round1_map: &BTreeMap<frost::Identifier, frost::keys::dkg::round1::Package>,
+    round2_map: &BTreeMap<frost::Identifier, frost::keys::dkg::round2::Package>,
+) -> Result<(frost::keys::KeyPackage, frost::keys::PublicKeyPackage)> {
+    let original = refreshed_group(registry, original_id)?;
+    let key_package_path = original
+        .contributions()
+        .key_package
+        .as_deref()
+        .with_context(|| {
+            format!(
+                "No key package for group {}; there is no share to refresh",
+                original_id.ur_string()
+            )
+        })?;
+    let old_key_package: frost::keys::KeyPackage = serde_json::from_slice(
+        &fs::read(key_package_path)
+            .with_context(|| format!("Failed to read {key_package_path}"))?,
+    )
+    .with_context(|| format!("Failed to parse {key_package_path}"))?;
+    let old_public_key_package = load_public_key_package(paths, original_id)?;
+    frost::keys::refresh::refresh_dkg_shares(
+        round2_secret,
+        round1_map,
+        round2_map,
+        old_public_key_package,
+        old_key_package,
+    )
+    .map_err(|e| anyhow::anyhow!("FROST DKG refresh failed: {}", e))
+}
 
-        if is_verbose() {
-            eprintln!(
-                "Posted finalize response to {}",
-                response_arid.ur_string()
-            );
-            if let Some(key) = group_key.as_ref() {
-                eprintln!("{}", key.ur_string());
-            }
-        } else if let Some(key) = group_key.as_ref() {
-            println!("{}", key.ur_string());
+/// Rebuilds the lost share a share repair hands this participant from the
+/// helpers' sigmas in the finalize request, then refreshes it like any other
+/// share. The request's public key package, the group being refreshed, must
+/// agree with the rebuilt share and keep its verifying key.
+#[allow(clippy::too_many_arguments)]
+fn repair_shares(
+    paths: &GroupStatePaths,
+    group_id: &ARID,
+    group_record: &GroupRecord,
+    owner: &XID,
+    request: &SealedRequest,
+    round2_secret: &frost::keys::dkg::round2::SecretPackage,
+    round1_map: &BTreeMap<frost::Identifier, frost::keys::dkg::round1::Package>,
+    round2_map: &BTreeMap<frost::Identifier, frost::keys::dkg::round2::Package>,
+) -> Result<(frost::keys::KeyPackage, frost::keys::PublicKeyPackage)> {
+    let participants: Vec<XID> = group_record
+        .participants()
+        .iter()
+        .map(|participant| *participant.xid())
+        .filter(|xid| xid != owner)
+        .collect();
+    let mut senders = Vec::new();
+    let mut sigmas = Vec::new();
+    for envelope in request.objects_for_parameter("repairSigma") {
+        let sender: XID = envelope.extract_object_for_predicate("sender")?;
+        if !participants.contains(&sender) || senders.contains(&sender) {
+            bail!("Unexpected share repair sigma from {}", sender.ur_string());
         }
-
-        Ok(())
+        senders.push(sender);
+        sigmas.push(extract_repair_scalar(&envelope)?);
+    }
+    if senders.len() != participants.len() {
+        bail!(
+            "Finalize request carries {} share repair sigmas; rebuilding \
+             your share needs one from each of the {} other participants",
+            senders.len(),
+            participants.len()
+        );
+    }
+    let pkg_json: JSON = decompress_envelope(
+        request
+            .object_for_parameter("publicKeyPackage")
+            .context("Finalize request has no public key package to repair")?,
+    )?
+    .extract_subject()?;
+    let old_public_key_package: frost::keys::PublicKeyPackage =
+        serde_json::from_slice(pkg_json.as_bytes())
+            .context("Failed to parse the repaired group's key package")?;
+
+    let identifier = *group_record
+        .identifiers()?
+        .get(owner)
+        .context("No FROST identifier for this participant")?;
+    let own_package_path = paths.round1_package_path(group_id);
+    let own_package: frost::keys::dkg::round1::Package =
+        serde_json::from_slice(&fs::read(&own_package_path).with_context(
+            || format!("Failed to read {}", own_package_path.display()),
+        )?)
+        .with_context(|| {
+            format!("Failed to parse {}", own_package_path.display())
+        })?;
+    let old_key_package = repaired_key_package(
+        &sigmas,
+        identifier,
+        own_package.commitment(),
+        &old_public_key_package,
+        group_record.min_signers() as u16,
+    )?;
+    let verifying_key = *old_public_key_package.verifying_key();
+    let (key_package, public_key_package) =
+        frost::keys::refresh::refresh_dkg_shares(
+            round2_secret,
+            round1_map,
+            round2_map,
+            old_public_key_package,
+            old_key_package,
+        )
+        .map_err(|e| anyhow::anyhow!("FROST DKG refresh failed: {}", e))?;
+    if *public_key_package.verifying_key() != verifying_key {
+        bail!(
+            "Share repair produced a different verifying key; the repaired \
+             share must not be used"
+        );
     }
+    Ok((key_package, public_key_package))
 }
 
 fn build_response_body(
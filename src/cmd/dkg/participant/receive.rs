@@ -1,22 +1,25 @@
+use std::collections::HashSet;
+
 use anyhow::{Context, Result, bail};
-use bc_components::{ARID, XIDProvider};
+use bc_components::{ARID, XID, XIDProvider};
 use bc_envelope::prelude::*;
 use bc_xid::{XIDDocument, XIDVerifySignature};
 use clap::Parser;
 use gstp::{SealedRequest, SealedRequestBehavior};
-use tokio::runtime::Runtime;
 
 use crate::{
     DkgInvitation,
     cmd::{
         busy::get_with_indicator,
+        common::{check_clock_skew, expired_error, load_registry},
         dkg::common::{
-            OptionalStorageSelector, parse_arid_ur, parse_envelope_ur,
-            participant_names_from_registry, resolve_sender,
-            resolve_sender_name,
+            OptionalStorageSelector, check_strict_sender, parse_arid_ur,
+            parse_envelope_ur, participant_names_from_registry, read_ur_input,
+            resolve_sender, resolve_sender_name,
         },
+        is_strict_sender,
         registry::participants_file_path,
-        storage::{StorageClient, StorageSelection},
+        storage::{StorageClient, StorageSelection, shared_runtime},
     },
     registry::Registry,
 };
@@ -50,6 +53,7 @@ pub struct CommandArgs {
     sender: Option<String>,
 
     /// Invite ARID or envelope (ur:arid or ur:envelope)
+    /// (`-` reads it from stdin)
     #[arg(value_name = "INVITE")]
     invite: String,
 }
@@ -62,7 +66,7 @@ impl CommandArgs {
         }
 
         let registry_path = participants_file_path(self.registry.clone())?;
-        let registry = Registry::load(&registry_path).with_context(|| {
+        let registry = load_registry(&registry_path).with_context(|| {
             format!("Failed to load registry at {}", registry_path.display())
         })?;
         let owner = registry
@@ -76,7 +80,7 @@ impl CommandArgs {
 
         let invite_envelope = resolve_invite_envelope(
             selection.clone(),
-            &self.invite,
+            &read_ur_input(&self.invite)?,
             self.timeout,
         )?;
 
@@ -104,10 +108,38 @@ impl CommandArgs {
         if self.info {
             eprintln!("Charter: {}", details.invitation.charter());
             eprintln!("Min signers: {}", details.invitation.min_signers());
+            match details.invitation.valid_from() {
+                Some(valid_from) => eprintln!("Valid from: {valid_from}"),
+                None => eprintln!("Valid from: immediately"),
+            }
+            eprintln!("Valid until: {}", details.invitation.valid_until());
+            eprintln!(
+                "Identifier scheme: {}",
+                details.invitation.identifier_scheme()
+            );
             if let Some(name) = coordinator_name {
                 eprintln!("Coordinator: {}", name);
             }
             eprintln!("Participants: {}", participant_names.join(", "));
+            if details.invitation.charter_attestation().is_some() {
+                eprintln!("Charter attestation: verified");
+            }
+            if let Some(original_id) = details.invitation.refresh_of() {
+                eprintln!("Refreshes group: {}", original_id.ur_string());
+            }
+            if let Some(repair) = details.invitation.share_repair() {
+                let name = |xid: &XID| {
+                    registry
+                        .participant(xid)
+                        .and_then(|record| record.pet_name().map(str::to_owned))
+                        .unwrap_or_else(|| xid.ur_string())
+                };
+                eprintln!(
+                    "Replaces: {} with {}",
+                    name(&repair.replaces),
+                    name(&repair.replacement)
+                );
+            }
         }
 
         Ok(())
@@ -126,14 +158,13 @@ fn resolve_invite_envelope(
 ) -> Result<Envelope> {
     if let Some(selection) = selection {
         if let Ok(arid) = parse_arid_ur(invite) {
-            let runtime = Runtime::new()?;
-            let client = runtime.block_on(async {
-                StorageClient::from_selection(selection).await
-            })?;
+            let runtime = shared_runtime()?;
+            let client = runtime
+                .block_on(async { StorageClient::shared(selection).await })?;
             return get_with_indicator(
-                &runtime, &client, &arid, "Invite", timeout,
+                runtime, &client, &arid, "Invite", timeout,
             )?
-            .context("Invite not found in Hubert storage");
+            .context("Nothing posted yet at the invite ARID");
         }
         if timeout.is_some() {
             bail!(
@@ -177,6 +208,11 @@ pub fn decode_invite_details(
         if sender_document.xid() != expected.xid() {
             bail!("Invite sender does not match expected sender");
         }
+    } else if is_strict_sender() {
+        bail!(
+            "--strict-sender requires --sender to name who the invite must \
+             come from"
+        );
     } else {
         let sender_xid = sender_document.xid();
         let known_owner = registry
@@ -191,15 +227,17 @@ pub fn decode_invite_details(
             );
         }
     }
+    check_strict_sender(registry, &sender_document)?;
     if sealed_request.request().function() != &Function::from("dkgInvite") {
         bail!("Unexpected invite function");
     }
+    check_clock_skew("Invitation", sealed_request.request().date(), &now);
 
     let valid_until: Date = sealed_request
         .request()
         .extract_object_for_parameter("validUntil")?;
     if valid_until <= now {
-        bail!("Invitation expired");
+        return Err(expired_error("Invitation", &valid_until, &now));
     }
 
     let min_signers: usize = sealed_request
@@ -221,7 +259,13 @@ pub fn decode_invite_details(
         bail!("min_signers exceeds participant count");
     }
 
+    // Each response ARID is encrypted to its own participant, so only our
+    // own plaintext ARID is visible. An encrypted subject keeps the digest of
+    // its plaintext, so a reused ARID still shows up as a repeated subject
+    // digest even though each copy is sealed to a different recipient.
     let mut participant_docs = Vec::new();
+    let mut seen_xids = HashSet::new();
+    let mut seen_response_arids = HashSet::new();
     let mut response_arid: Option<ARID> = None;
     for participant in participant_objects {
         let xid_document_envelope = participant.try_unwrap()?;
@@ -230,9 +274,20 @@ pub fn decode_invite_details(
             None,
             XIDVerifySignature::Inception,
         )?;
+        if !seen_xids.insert(xid_document.xid()) {
+            bail!(
+                "Invite lists participant {} more than once",
+                xid_document.xid().ur_string()
+            );
+        }
+        let encrypted_response_arid =
+            participant.object_for_predicate("response_arid")?;
+        if !seen_response_arids
+            .insert(encrypted_response_arid.subject().digest())
+        {
+            bail!("Invite reuses a response ARID across participants");
+        }
         if xid_document.xid() == recipient.xid() {
-            let encrypted_response_arid =
-                participant.object_for_predicate("response_arid")?;
             let response_arid_envelope = encrypted_response_arid
                 .decrypt_to_recipient(recipient_private_keys)?;
             response_arid =
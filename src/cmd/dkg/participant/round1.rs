@@ -4,23 +4,27 @@ use anyhow::{Context, Result, bail};
 use bc_components::{ARID, JSON, XID, XIDProvider};
 use bc_envelope::prelude::*;
 use clap::Parser;
-use frost_ed25519::{self as frost, Identifier, rand_core::OsRng};
+use frost_ed25519::{self as frost, rand_core::OsRng};
 use gstp::{SealedResponse, SealedResponseBehavior};
-use tokio::runtime::Runtime;
 
-use super::receive::decode_invite_details;
+use super::receive::{InviteDetails, decode_invite_details};
 use crate::{
     cmd::{
         busy::{get_with_indicator, put_with_indicator},
+        common::{load_registry, write_atomically},
         dkg::common::{
-            OptionalStorageSelector, build_group_participants,
-            group_participant_from_registry, group_state_dir, parse_arid_ur,
-            resolve_sender,
+            GroupStatePaths, OptionalStorageSelector, build_group_participants,
+            group_participant_from_registry, parse_arid_ur, read_ur_input,
+            refresh_identifiers, refreshed_group, resolve_also_notify,
+            resolve_sender, seal_response, share_repair,
         },
         registry::participants_file_path,
-        storage::{StorageClient, StorageSelection},
+        storage::{
+            StorageClient, StorageSelection, compress_payload, shared_runtime,
+        },
     },
-    registry::{ContributionPaths, GroupRecord, Registry},
+    dkg::{RepairScalar, ShareRepair, repair_deltas, repair_scalar_envelope},
+    registry::{ContributionPaths, GroupProvenance, GroupRecord, Registry},
 };
 
 /// Respond to a DKG invite (Round 1).
@@ -44,6 +48,15 @@ pub struct CommandArgs {
     #[arg(long = "response-arid", value_name = "UR:ARID")]
     response_arid: Option<String>,
 
+    /// Read the next-response ARID from this file, or generate one and write
+    /// it there if the file does not exist
+    #[arg(
+        long = "response-arid-file",
+        value_name = "PATH",
+        conflicts_with = "response_arid"
+    )]
+    response_arid_file: Option<String>,
+
     /// Print the preview response envelope UR instead of the sealed envelope
     /// (local-only)
     #[arg(long = "preview")]
@@ -53,12 +66,44 @@ pub struct CommandArgs {
     #[arg(long = "reject", value_name = "REASON")]
     reject_reason: Option<String>,
 
+    /// Accept only if the invite passes the auto-accept policy, otherwise
+    /// reject it with the reason the policy failed
+    #[arg(long = "auto-accept", conflicts_with = "reject_reason")]
+    auto_accept: bool,
+
+    /// Auto-accept policy: lowest acceptable min signers (default 2)
+    #[arg(long = "min-threshold", value_name = "N", requires = "auto_accept")]
+    min_threshold: Option<usize>,
+
+    /// Auto-accept policy: highest acceptable min signers (default: no limit)
+    #[arg(long = "max-threshold", value_name = "N", requires = "auto_accept")]
+    max_threshold: Option<usize>,
+
+    /// Auto-accept policy: most participants an invite may list (default 255)
+    #[arg(
+        long = "max-participants",
+        value_name = "N",
+        requires = "auto_accept"
+    )]
+    max_participants: Option<usize>,
+
     /// Optionally require the invite to come from this sender (ur:xid or pet
     /// name in registry)
     #[arg(long = "sender", value_name = "SENDER")]
     sender: Option<String>,
 
+    /// Also seal the response to this registered participant, such as a
+    /// backup coordinator or an observer (repeatable)
+    #[arg(long = "also-notify", value_name = "PARTICIPANT")]
+    also_notify: Vec<String>,
+
+    /// Generate a new Round 1 package even if one was already sent for this
+    /// group (the coordinator must then collect Round 1 again)
+    #[arg(long = "force", conflicts_with = "reject_reason")]
+    force: bool,
+
     /// Invite ARID or envelope (ur:arid or ur:envelope)
+    /// (`-` reads it from stdin)
     #[arg(value_name = "INVITE")]
     invite: String,
 }
@@ -74,7 +119,7 @@ impl CommandArgs {
         }
         let registry_path = participants_file_path(self.registry.clone())?;
         let mut registry =
-            Registry::load(&registry_path).with_context(|| {
+            load_registry(&registry_path).with_context(|| {
                 format!(
                     "Failed to load registry at {}",
                     registry_path.display()
@@ -88,14 +133,19 @@ impl CommandArgs {
             Some(raw) => Some(resolve_sender(&registry, raw)?),
             None => None,
         };
-        let next_response_arid = match &self.response_arid {
-            Some(raw) => parse_arid_ur(raw)?,
-            None => ARID::new(),
-        };
+        let also_notify = resolve_also_notify(&registry, &self.also_notify)?;
+        let next_response_arid =
+            match (&self.response_arid, &self.response_arid_file) {
+                (Some(raw), _) => parse_arid_ur(raw)?,
+                (None, Some(path)) => {
+                    read_or_create_response_arid(path, !self.preview)?
+                }
+                (None, None) => ARID::new(),
+            };
 
         let invite_envelope = resolve_invite_envelope(
             selection.clone(),
-            &self.invite,
+            &read_ur_input(&self.invite)?,
             self.timeout,
         )?;
 
@@ -107,16 +157,94 @@ impl CommandArgs {
             owner.xid_document(),
             expected_sender,
         )?;
+        details.invitation.ensure_open(now)?;
+
+        let policy_violation = if self.auto_accept {
+            AcceptPolicy {
+                min_threshold: self.min_threshold.unwrap_or(2),
+                max_threshold: self.max_threshold,
+                max_participants: self
+                    .max_participants
+                    .unwrap_or(DEFAULT_MAX_PARTICIPANTS),
+            }
+            .check(&registry, &details)
+        } else {
+            None
+        };
+        let reject_reason = match &policy_violation {
+            Some(violation) => {
+                eprintln!("Auto-rejecting invite: {violation}");
+                Some(violation.to_string())
+            }
+            None => self.reject_reason.clone(),
+        };
 
         let mut sorted_participants = details.participants.clone();
         sorted_participants.sort_by_key(|doc| doc.xid());
-        let owner_index = sorted_participants
-            .iter()
-            .position(|doc| doc.xid() == owner.xid())
+        let sorted_xids: Vec<XID> =
+            sorted_participants.iter().map(|doc| doc.xid()).collect();
+        // A coordinator answers its own invite only if it listed itself as
+        // a signer
+        if details.invitation.sender().xid() == owner.xid()
+            && !sorted_xids.contains(&owner.xid())
+        {
+            bail!(
+                "You coordinate this invite but are not one of its signers; \
+                 only the listed participants answer it"
+            );
+        }
+        // A share repair's replacement holds no share of the group being
+        // refreshed; everyone else helps rebuild the lost one from theirs
+        let repair = details
+            .invitation
+            .share_repair()
+            .filter(|_| reject_reason.is_none());
+        let replacing =
+            repair.is_some_and(|repair| repair.replacement == owner.xid());
+        if repair.is_some() && !replacing && !also_notify.is_empty() {
+            bail!(
+                "This invite rebuilds a lost share, and your response carries \
+                 pieces of your own share for it; --also-notify cannot be used"
+            );
+        }
+        let original = match details.invitation.refresh_of() {
+            Some(original_id) if reject_reason.is_none() && !replacing => {
+                Some(check_refresh_invite(&registry, &original_id, &details)?)
+            }
+            _ => None,
+        };
+        let identifiers = match (&original, repair) {
+            (Some(original), Some(repair)) => {
+                check_share_repair(original, &sorted_xids, repair)?;
+                repair.identifiers.clone().into_iter().collect()
+            }
+            (Some(original), None) => {
+                refresh_identifiers(original, &sorted_xids)?
+            }
+            (None, Some(repair)) => {
+                eprintln!(
+                    "This invite rebuilds the share {} held in group {} for \
+                     you; the group verifying key does not change.",
+                    repair.replaces.ur_string(),
+                    details
+                        .invitation
+                        .refresh_of()
+                        .context(
+                            "Share repair in an invite that is not a refresh"
+                        )?
+                        .ur_string()
+                );
+                repair.identifiers.clone().into_iter().collect()
+            }
+            (None, None) => details
+                .invitation
+                .identifier_scheme()
+                .identifiers(&sorted_xids)?,
+        };
+        let refresh = original.is_some() || replacing;
+        let identifier = *identifiers
+            .get(&owner.xid())
             .context("Invite does not include the registry owner")?;
-        let identifier_index = u16::try_from(owner_index + 1)
-            .context("Too many participants for identifiers")?;
-        let identifier = Identifier::try_from(identifier_index)?;
         let total = u16::try_from(sorted_participants.len())
             .context("Too many participants for FROST identifiers")?;
         let min_signers = u16::try_from(details.invitation.min_signers())
@@ -134,18 +262,43 @@ impl CommandArgs {
         // Only generate actual round1 state if we're going to post to storage
         let is_posting = selection.is_some();
 
-        let (response_body, _round1_package_opt) = if self
-            .reject_reason
-            .is_none()
+        let (response_body, _round1_package_opt) = if reject_reason.is_none()
             && is_posting
         {
-            // Actually posting - generate and persist round1 state
-            let (round1_secret, round1_package) =
-                frost::keys::dkg::part1(identifier, total, min_signers, OsRng)?;
-            let contributions = persist_round1_state(
-                &registry_path,
-                &details.invitation.group_id(),
-                &round1_secret,
+            // Actually posting - reuse or generate and persist round1 state.
+            // A fresh package would invalidate one the coordinator may
+            // already have collected.
+            let existing = if self.force {
+                None
+            } else {
+                load_round1_state(
+                    &registry_path,
+                    &details.invitation.group_id(),
+                )?
+            };
+            let (round1_package, contributions) = match existing {
+                Some(state) => {
+                    eprintln!(
+                        "Re-sending the Round 1 package already generated \
+                         for this group; use --force to generate a new one"
+                    );
+                    state
+                }
+                None => {
+                    let (round1_secret, round1_package) =
+                        run_part1(refresh, identifier, total, min_signers)?;
+                    let contributions = persist_round1_state(
+                        &registry_path,
+                        &details.invitation.group_id(),
+                        &round1_secret,
+                        &round1_package,
+                    )?;
+                    (round1_package, contributions)
+                }
+            };
+            let deltas = helper_repair_deltas(
+                original.as_ref(),
+                repair,
                 &round1_package,
             )?;
             let body = build_response_body(
@@ -153,6 +306,7 @@ impl CommandArgs {
                 owner.xid(),
                 next_response_arid,
                 Some(&round1_package),
+                &deltas,
             )?;
 
             let mut group_record = GroupRecord::new(
@@ -164,20 +318,45 @@ impl CommandArgs {
             group_record.set_contributions(contributions);
             // Set the ARID where we're listening for the Round 2 request
             group_record.set_listening_at_arid(next_response_arid);
+            group_record
+                .set_identifier_scheme(details.invitation.identifier_scheme());
+            group_record.set_identifiers(identifiers);
+            if let Some(attestation) = details.invitation.charter_attestation()
+            {
+                group_record.set_charter_attestation(attestation.ur_string());
+            }
+            if let (Some(original_id), Some(original)) =
+                (details.invitation.refresh_of(), &original)
+            {
+                group_record.set_refresh_of(original_id);
+                group_record.set_provenance(original.provenance());
+            }
+            if let (Some(original_id), true) =
+                (details.invitation.refresh_of(), replacing)
+            {
+                group_record.set_refresh_of(original_id);
+                group_record.set_provenance(GroupProvenance::Repaired);
+            }
             registry
                 .record_group(details.invitation.group_id(), group_record)?;
             registry.save(&registry_path)?;
 
             (body, Some(round1_package))
-        } else if self.reject_reason.is_none() {
+        } else if reject_reason.is_none() {
             // Preview mode - generate dummy round1 for envelope structure only
             let (_, round1_package) =
-                frost::keys::dkg::part1(identifier, total, min_signers, OsRng)?;
+                run_part1(refresh, identifier, total, min_signers)?;
+            let deltas = helper_repair_deltas(
+                original.as_ref(),
+                repair,
+                &round1_package,
+            )?;
             let body = build_response_body(
                 details.invitation.group_id(),
                 owner.xid(),
                 next_response_arid,
                 Some(&round1_package),
+                &deltas,
             )?;
             (body, None)
         } else {
@@ -187,6 +366,7 @@ impl CommandArgs {
                 owner.xid(),
                 next_response_arid,
                 None,
+                &[],
             )?;
             (body, None)
         };
@@ -195,11 +375,15 @@ impl CommandArgs {
             .xid_document()
             .inception_private_keys()
             .context("Owner XID document has no signing keys")?;
-        let mut sealed = if let Some(ref reason) = self.reject_reason {
-            let error_body = Envelope::new("dkgInviteReject")
+        let mut sealed = if let Some(ref reason) = reject_reason {
+            let mut error_body = Envelope::new("dkgInviteReject")
                 .add_assertion("group", details.invitation.group_id())
                 .add_assertion("response_arid", next_response_arid)
                 .add_assertion("reason", reason.clone());
+            if let Some(violation) = &policy_violation {
+                error_body = error_body
+                    .add_assertion("policyViolation", violation.code());
+            }
             SealedResponse::new_failure(
                 details.invitation.request_id(),
                 owner.xid_document().clone(),
@@ -211,25 +395,28 @@ impl CommandArgs {
                 details.invitation.request_id(),
                 owner.xid_document().clone(),
             )
-            .with_result(response_body.clone())
+            .with_result(compress_payload(response_body.clone())?)
             .with_state(next_response_arid)
         };
         sealed = sealed
             .with_peer_continuation(details.invitation.peer_continuation());
 
         if let Some(selection) = selection {
-            let response_envelope = sealed.to_envelope(
-                Some(details.invitation.valid_until()),
-                Some(signer_private_keys),
-                Some(&details.invitation.sender()),
+            // The expiry sealed here applies to our continuation, which the
+            // Round 2 request returns after the invite's response deadline
+            let response_envelope = seal_response(
+                &sealed,
+                None,
+                signer_private_keys,
+                &details.invitation.sender(),
+                &also_notify,
             )?;
             let response_target = details.invitation.response_arid();
-            let runtime = Runtime::new()?;
-            let client = runtime.block_on(async {
-                StorageClient::from_selection(selection).await
-            })?;
+            let runtime = shared_runtime()?;
+            let client = runtime
+                .block_on(async { StorageClient::shared(selection).await })?;
             put_with_indicator(
-                &runtime,
+                runtime,
                 &client,
                 &response_target,
                 &response_envelope,
@@ -241,10 +428,12 @@ impl CommandArgs {
                 sealed.to_envelope(None, Some(signer_private_keys), None)?;
             println!("{}", unsealed_envelope.ur_string());
         } else {
-            let response_envelope = sealed.to_envelope(
-                Some(details.invitation.valid_until()),
-                Some(signer_private_keys),
-                Some(&details.invitation.sender()),
+            let response_envelope = seal_response(
+                &sealed,
+                None,
+                signer_private_keys,
+                &details.invitation.sender(),
+                &also_notify,
             )?;
             println!("{}", response_envelope.ur_string());
         }
@@ -253,6 +442,279 @@ impl CommandArgs {
     }
 }
 
+/// Checks that a refresh invite comes from the coordinator of the group it
+/// refreshes, keeps that group's threshold, and that this participant holds
+/// a share to refresh. Returns the group being refreshed.
+fn check_refresh_invite(
+    registry: &Registry,
+    original_id: &ARID,
+    details: &InviteDetails,
+) -> Result<GroupRecord> {
+    let original = refreshed_group(registry, original_id)?;
+    let sender = details.invitation.sender().xid();
+    if original.coordinator().xid() != &sender {
+        bail!(
+            "Refresh invite for group {} comes from {}, not the group's \
+             coordinator",
+            original_id.ur_string(),
+            sender.ur_string()
+        );
+    }
+    if details.invitation.min_signers() != original.min_signers() {
+        bail!(
+            "Refresh invite asks for {} signers but group {} has {}; a \
+             refresh cannot change the threshold",
+            details.invitation.min_signers(),
+            original_id.ur_string(),
+            original.min_signers()
+        );
+    }
+    if original.contributions().key_package.is_none() {
+        bail!(
+            "No key package for group {}; there is no share to refresh",
+            original_id.ur_string()
+        );
+    }
+    eprintln!(
+        "This invite refreshes your share of group {}; the group verifying \
+         key does not change.",
+        original_id.ur_string()
+    );
+    Ok(original.clone())
+}
+
+/// Checks a helper's copy of a share repair against the group being
+/// refreshed: the replacement takes the identifier of the participant it
+/// replaces, and everyone else keeps their own.
+fn check_share_repair(
+    original: &GroupRecord,
+    participants: &[XID],
+    repair: &ShareRepair,
+) -> Result<()> {
+    let expected = share_repair(
+        original,
+        participants,
+        repair.replacement,
+        repair.replaces,
+    )?;
+    if expected != *repair {
+        bail!(
+            "The invite's share repair does not match your record of the \
+             group being refreshed"
+        );
+    }
+    eprintln!(
+        "This refresh also rebuilds the share {} held for {}; your Round 1 \
+         and Round 2 responses carry pieces of your share for it.",
+        repair.replaces.ur_string(),
+        repair.replacement.ur_string()
+    );
+    Ok(())
+}
+
+/// A helper's Round 1 deltas for a share repair, addressed to each helper by
+/// XID; empty unless this participant helps rebuild a lost share.
+fn helper_repair_deltas(
+    original: Option<&GroupRecord>,
+    repair: Option<&ShareRepair>,
+    round1_package: &frost::keys::dkg::round1::Package,
+) -> Result<Vec<(XID, RepairScalar)>> {
+    let (Some(original), Some(repair)) = (original, repair) else {
+        return Ok(Vec::new());
+    };
+    let key_package_path = original
+        .contributions()
+        .key_package
+        .as_deref()
+        .context("No key package for the group being refreshed")?;
+    let key_package: frost::keys::KeyPackage = serde_json::from_slice(
+        &fs::read(key_package_path)
+            .with_context(|| format!("Failed to read {key_package_path}"))?,
+    )
+    .with_context(|| format!("Failed to parse {key_package_path}"))?;
+    let deltas = repair_deltas(
+        &repair.helpers(),
+        &key_package,
+        round1_package.commitment(),
+        repair.identifier()?,
+    )?;
+    repair
+        .identifiers
+        .iter()
+        .filter(|(xid, _)| **xid != repair.replacement)
+        .map(|(xid, identifier)| {
+            let delta = deltas.get(identifier).with_context(|| {
+                format!("No repair delta for {}", xid.ur_string())
+            })?;
+            Ok((*xid, *delta))
+        })
+        .collect()
+}
+
+/// Runs DKG part1, or for a refresh its variant that shares a polynomial
+/// with a zero constant term.
+fn run_part1(
+    refresh: bool,
+    identifier: frost::Identifier,
+    total: u16,
+    min_signers: u16,
+) -> Result<(
+    frost::keys::dkg::round1::SecretPackage,
+    frost::keys::dkg::round1::Package,
+)> {
+    if refresh {
+        Ok(frost::keys::refresh::refresh_dkg_part1(
+            identifier,
+            total,
+            min_signers,
+            OsRng,
+        )?)
+    } else {
+        Ok(frost::keys::dkg::part1(
+            identifier,
+            total,
+            min_signers,
+            OsRng,
+        )?)
+    }
+}
+
+/// Returns the ARID recorded in `path`, or generates a new one and records it
+/// there, so repeated responses keep listening at the same ARID. A preview
+/// (`record` false) generates one without creating the file.
+fn read_or_create_response_arid(path: &str, record: bool) -> Result<ARID> {
+    match fs::read_to_string(path) {
+        Ok(contents) => {
+            let mut urs = contents.split_whitespace();
+            let (Some(ur), None) = (urs.next(), urs.next()) else {
+                bail!(
+                    "Response ARID file {path} must contain a single ur:arid"
+                );
+            };
+            parse_arid_ur(ur)
+                .with_context(|| format!("Invalid response ARID file {path}"))
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            let arid = ARID::new();
+            if !record {
+                return Ok(arid);
+            }
+            write_atomically(path, format!("{}\n", arid.ur_string()))
+                .with_context(|| {
+                    format!("Failed to write response ARID file {path}")
+                })?;
+            Ok(arid)
+        }
+        Err(err) => Err(err).with_context(|| {
+            format!("Failed to read response ARID file {path}")
+        }),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Auto-accept policy
+// -----------------------------------------------------------------------------
+
+/// Default cap on the number of participants an auto-accepted invite may list.
+const DEFAULT_MAX_PARTICIPANTS: usize = 255;
+
+/// Limits an invite must satisfy to be accepted by `--auto-accept`.
+struct AcceptPolicy {
+    min_threshold: usize,
+    max_threshold: Option<usize>,
+    max_participants: usize,
+}
+
+/// The first policy rule an invite failed.
+#[derive(Debug)]
+enum PolicyViolation {
+    UnknownSender(XID),
+    ThresholdTooLow { min_signers: usize, limit: usize },
+    ThresholdTooHigh { min_signers: usize, limit: usize },
+    TooManyParticipants { count: usize, limit: usize },
+}
+
+impl PolicyViolation {
+    /// Machine-readable identifier carried in the rejection envelope.
+    fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownSender(_) => "unknownSender",
+            Self::ThresholdTooLow { .. } => "thresholdTooLow",
+            Self::ThresholdTooHigh { .. } => "thresholdTooHigh",
+            Self::TooManyParticipants { .. } => "tooManyParticipants",
+        }
+    }
+}
+
+impl std::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownSender(xid) => write!(
+                f,
+                "Invite sender {} is not in the registry",
+                xid.ur_string()
+            ),
+            Self::ThresholdTooLow { min_signers, limit } => write!(
+                f,
+                "Min signers {min_signers} is below the accepted minimum \
+                 {limit}"
+            ),
+            Self::ThresholdTooHigh { min_signers, limit } => write!(
+                f,
+                "Min signers {min_signers} is above the accepted maximum \
+                 {limit}"
+            ),
+            Self::TooManyParticipants { count, limit } => write!(
+                f,
+                "Invite lists {count} participants, more than the accepted \
+                 maximum of {limit}"
+            ),
+        }
+    }
+}
+
+impl AcceptPolicy {
+    fn check(
+        &self,
+        registry: &Registry,
+        details: &InviteDetails,
+    ) -> Option<PolicyViolation> {
+        let sender = details.invitation.sender().xid();
+        let known_owner = registry
+            .owner()
+            .map(|owner| owner.xid() == sender)
+            .unwrap_or(false);
+        if !known_owner && registry.participant(&sender).is_none() {
+            return Some(PolicyViolation::UnknownSender(sender));
+        }
+
+        let min_signers = details.invitation.min_signers();
+        if min_signers < self.min_threshold {
+            return Some(PolicyViolation::ThresholdTooLow {
+                min_signers,
+                limit: self.min_threshold,
+            });
+        }
+        if let Some(limit) =
+            self.max_threshold.filter(|limit| min_signers > *limit)
+        {
+            return Some(PolicyViolation::ThresholdTooHigh {
+                min_signers,
+                limit,
+            });
+        }
+
+        let count = details.participants.len();
+        if count > self.max_participants {
+            return Some(PolicyViolation::TooManyParticipants {
+                count,
+                limit: self.max_participants,
+            });
+        }
+        None
+    }
+}
+
 fn resolve_invite_envelope(
     selection: Option<StorageSelection>,
     invite: &str,
@@ -262,14 +724,13 @@ fn resolve_invite_envelope(
 
     if let Some(selection) = selection {
         if let Ok(arid) = parse_arid_ur(invite) {
-            let runtime = Runtime::new()?;
-            let client = runtime.block_on(async {
-                StorageClient::from_selection(selection).await
-            })?;
+            let runtime = shared_runtime()?;
+            let client = runtime
+                .block_on(async { StorageClient::shared(selection).await })?;
             return get_with_indicator(
-                &runtime, &client, &arid, "Invite", timeout,
+                runtime, &client, &arid, "Invite", timeout,
             )?
-            .context("Invite not found in Hubert storage");
+            .context("Nothing posted yet at the invite ARID");
         }
         if timeout.is_some() {
             bail!(
@@ -292,6 +753,7 @@ fn build_response_body(
     participant: XID,
     response_arid: ARID,
     round1_package: Option<&frost::keys::dkg::round1::Package>,
+    repair_deltas: &[(XID, RepairScalar)],
 ) -> Result<Envelope> {
     let mut envelope = Envelope::unit()
         .add_type("dkgRound1Response")
@@ -304,26 +766,68 @@ fn build_response_body(
         let cbor: CBOR = json.into();
         envelope = envelope.add_assertion("round1_package", cbor);
     }
+    for (recipient, delta) in repair_deltas {
+        envelope = envelope.add_assertion(
+            "repairDelta",
+            repair_scalar_envelope(delta)
+                .add_assertion("recipient", *recipient),
+        );
+    }
     Ok(envelope)
 }
 
+/// Loads the Round 1 package persisted by an earlier response to this
+/// group's invite, if any.
+fn load_round1_state(
+    registry_path: &Path,
+    group_id: &ARID,
+) -> Result<Option<(frost::keys::dkg::round1::Package, ContributionPaths)>> {
+    let paths = GroupStatePaths::new(registry_path);
+    let package_path = paths.round1_package_path(group_id);
+    if !package_path.exists() {
+        return Ok(None);
+    }
+    let secret_path = paths.round1_secret_path(group_id);
+    if !secret_path.exists() {
+        bail!(
+            "Round 1 package exists at {} but its secret is missing; use \
+             --force to generate a new package",
+            package_path.display()
+        );
+    }
+    let package: frost::keys::dkg::round1::Package =
+        serde_json::from_slice(&fs::read(&package_path).with_context(
+            || format!("Failed to read {}", package_path.display()),
+        )?)
+        .with_context(|| format!("Invalid {}", package_path.display()))?;
+
+    Ok(Some((
+        package,
+        ContributionPaths {
+            round1_secret: Some(secret_path.to_string_lossy().into_owned()),
+            round1_package: Some(package_path.to_string_lossy().into_owned()),
+            round2_secret: None,
+            key_package: None,
+        },
+    )))
+}
+
 fn persist_round1_state(
     registry_path: &Path,
     group_id: &ARID,
     round1_secret: &frost::keys::dkg::round1::SecretPackage,
     round1_package: &frost::keys::dkg::round1::Package,
 ) -> Result<ContributionPaths> {
-    let dir = group_state_dir(registry_path, group_id);
+    let paths = GroupStatePaths::new(registry_path);
+    let dir = paths.group_dir(group_id);
     fs::create_dir_all(&dir).with_context(|| {
         format!("Failed to create group state directory {}", dir.display())
     })?;
-    let secret_path = dir.join("round1_secret.json");
-    let package_path = dir.join("round1_package.json");
-    fs::write(&secret_path, serde_json::to_vec_pretty(round1_secret)?)
-        .with_context(|| {
-            format!("Failed to write {}", secret_path.display())
-        })?;
-    fs::write(&package_path, serde_json::to_vec_pretty(round1_package)?)
+    let secret_path = paths.round1_secret_path(group_id);
+    let package_path = paths.round1_package_path(group_id);
+    write_atomically(&secret_path, serde_json::to_vec_pretty(round1_secret)?)
+        .with_context(|| format!("Failed to write {}", secret_path.display()))?;
+    write_atomically(&package_path, serde_json::to_vec_pretty(round1_package)?)
         .with_context(|| {
             format!("Failed to write {}", package_path.display())
         })?;
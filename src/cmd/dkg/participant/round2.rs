@@ -1,4 +1,7 @@
-use std::{collections::BTreeMap, fs};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+};
 
 use anyhow::{Context, Result, bail};
 use bc_components::{ARID, JSON, XID, XIDProvider};
@@ -9,19 +12,28 @@ use gstp::{
     SealedRequest, SealedRequestBehavior, SealedResponse,
     SealedResponseBehavior,
 };
-use tokio::runtime::Runtime;
 
 use crate::{
     cmd::{
         busy::{get_with_indicator, put_with_indicator},
+        common::{load_registry, write_atomically},
         dkg::common::{
-            OptionalStorageSelector, group_state_dir, parse_arid_ur,
+            GroupStatePaths, OptionalStorageSelector,
+            check_request_continuation, check_strict_sender, parse_group_id,
+            read_ur_input, response_recipient, seal_response,
+            verify_group_parameters,
         },
         is_verbose,
         registry::participants_file_path,
-        storage::StorageClient,
+        storage::{
+            StorageClient, compress_payload, decompress_envelope,
+            shared_runtime,
+        },
+    },
+    dkg::{
+        RepairScalar, extract_repair_scalar, repair_scalar_envelope,
+        repair_sigma,
     },
-    registry::Registry,
 };
 
 /// Respond to a Round 2 request (participant only).
@@ -47,6 +59,7 @@ pub struct CommandArgs {
     preview: bool,
 
     /// Group ID to respond to Round 2 for
+    /// (`-` reads it from stdin)
     #[arg(value_name = "GROUP_ID")]
     group_id: String,
 }
@@ -59,7 +72,7 @@ impl CommandArgs {
 
         let registry_path = participants_file_path(self.registry.clone())?;
         let mut registry =
-            Registry::load(&registry_path).with_context(|| {
+            load_registry(&registry_path).with_context(|| {
                 format!(
                     "Failed to load registry at {}",
                     registry_path.display()
@@ -71,8 +84,9 @@ impl CommandArgs {
             .context("Registry owner is required")?
             .clone();
 
-        let group_id = parse_arid_ur(&self.group_id)?;
-        let group_record = registry
+        let group_id =
+            parse_group_id(&registry, &read_ur_input(&self.group_id)?)?;
+        let mut group_record = registry
             .group(&group_id)
             .context("Group not found in registry")?
             .clone();
@@ -84,8 +98,8 @@ impl CommandArgs {
         )?;
 
         // Load our Round 1 secret
-        let packages_dir = group_state_dir(&registry_path, &group_id);
-        let round1_secret_path = packages_dir.join("round1_secret.json");
+        let paths = GroupStatePaths::new(&registry_path);
+        let round1_secret_path = paths.round1_secret_path(&group_id);
         if !round1_secret_path.exists() {
             bail!(
                 "Round 1 secret not found at {}. \
@@ -100,20 +114,19 @@ impl CommandArgs {
             eprintln!("Fetching Round 2 request from Hubert...");
         }
 
-        let runtime = Runtime::new()?;
-        let client = runtime.block_on(async {
-            StorageClient::from_selection(selection).await
-        })?;
+        let runtime = shared_runtime()?;
+        let client = runtime
+            .block_on(async { StorageClient::shared(selection).await })?;
 
         // Fetch the Round 2 request from where we're listening
         let request_envelope = get_with_indicator(
-            &runtime,
+            runtime,
             &client,
             &listening_at_arid,
             "Round 2 request",
             self.timeout,
         )?
-        .context("Round 2 request not found in Hubert storage")?;
+        .context("Nothing posted yet at the Round 2 request ARID")?;
 
         // Decrypt and validate the request
         let owner_private_keys = owner
@@ -142,6 +155,7 @@ impl CommandArgs {
                 expected_coordinator.ur_string()
             );
         }
+        check_strict_sender(&registry, sealed_request.sender())?;
 
         let request_group_id: ARID =
             sealed_request.extract_object_for_parameter("group")?;
@@ -152,15 +166,34 @@ impl CommandArgs {
                 group_id.ur_string()
             );
         }
+        verify_group_parameters(&sealed_request, &group_record)?;
+        check_request_continuation(&sealed_request, &listening_at_arid)?;
 
         // Extract where we should post our response
         let response_arid: ARID =
             sealed_request.extract_object_for_parameter("responseArid")?;
 
+        // Apply any exclusions the coordinator made after Round 1
+        let excluded = excluded_participants(&sealed_request)?;
+        let round1_secret = if excluded.is_empty() {
+            round1_secret
+        } else {
+            apply_exclusions(
+                &mut group_record,
+                &owner.xid(),
+                &excluded,
+                round1_secret,
+            )?
+        };
+
         // Extract Round 1 packages from the request
         let (round1_packages, round1_packages_by_xid) =
             extract_round1_packages(&sealed_request, &group_record, &owner)?;
 
+        // A helper in a share repair sums the deltas addressed to it
+        let sigma = extract_repair_deltas(&sealed_request, &group_record)?
+            .map(|deltas| repair_sigma(&deltas));
+
         if is_verbose() {
             eprintln!(
                 "Received {} Round 1 packages. Running DKG part2...",
@@ -171,11 +204,18 @@ impl CommandArgs {
         // Allocate next response ARID for the finalize phase
         let next_response_arid = ARID::new();
 
-        // Run FROST DKG part2
+        // Run FROST DKG part2, or its refresh variant when rotating the
+        // shares of an existing group
         let (round2_secret, round2_packages) =
-            frost::keys::dkg::part2(round1_secret, &round1_packages).map_err(
-                |e| anyhow::anyhow!("FROST DKG part2 failed: {}", e),
-            )?;
+            if group_record.refresh_of().is_some() {
+                frost::keys::refresh::refresh_dkg_part2(
+                    round1_secret,
+                    &round1_packages,
+                )
+            } else {
+                frost::keys::dkg::part2(round1_secret, &round1_packages)
+            }
+            .map_err(|e| anyhow::anyhow!("FROST DKG part2 failed: {}", e))?;
 
         if is_verbose() {
             eprintln!("Generated {} Round 2 packages.", round2_packages.len());
@@ -188,6 +228,7 @@ impl CommandArgs {
             &next_response_arid,
             &round2_packages,
             &group_record,
+            sigma.as_ref(),
         )?;
 
         let signer_private_keys = owner
@@ -206,12 +247,15 @@ impl CommandArgs {
                     coordinator_xid.ur_string()
                 )
             })?;
+        let recipient_doc =
+            response_recipient(&sealed_request, &registry, coordinator_doc)?;
 
         let sealed_response = SealedResponse::new_success(
             sealed_request.id(),
             owner.xid_document().clone(),
         )
-        .with_result(response_body)
+        .with_result(compress_payload(response_body)?)
+        .with_state(next_response_arid)
         .with_peer_continuation(sealed_request.peer_continuation());
 
         if self.preview {
@@ -226,14 +270,14 @@ impl CommandArgs {
         }
 
         // Persist Round 2 secret
-        let round2_secret_path = packages_dir.join("round2_secret.json");
-        fs::write(
+        let round2_secret_path = paths.round2_secret_path(&group_id);
+        write_atomically(
             &round2_secret_path,
             serde_json::to_vec_pretty(&round2_secret)?,
         )?;
 
         // Persist received Round 1 packages for finalize phase
-        let round1_packages_path = packages_dir.join("collected_round1.json");
+        let round1_packages_path = paths.collected_round1_path(&group_id);
         let round1_json: serde_json::Map<String, serde_json::Value> =
             round1_packages_by_xid
                 .iter()
@@ -245,20 +289,24 @@ impl CommandArgs {
                     )
                 })
                 .collect();
-        fs::write(
+        write_atomically(
             &round1_packages_path,
             serde_json::to_vec_pretty(&round1_json)?,
         )?;
 
-        let response_envelope = sealed_response.to_envelope(
+        let response_envelope = seal_response(
+            &sealed_response,
             None, // No expiration for responses
-            Some(signer_private_keys),
-            Some(&coordinator_doc),
+            signer_private_keys,
+            &recipient_doc,
+            // Round 2 packages are secret shares meant for each recipient
+            // alone, so the response is sealed to its recipient only
+            &[],
         )?;
 
         // Post the response
         put_with_indicator(
-            &runtime,
+            runtime,
             &client,
             &response_arid,
             &response_envelope,
@@ -269,6 +317,9 @@ impl CommandArgs {
         let group_record = registry
             .group_mut(&group_id)
             .context("Group not found in registry")?;
+        for xid in &excluded {
+            group_record.exclude_participant(xid);
+        }
         let mut contributions = group_record.contributions().clone();
         contributions.round2_secret =
             Some(round2_secret_path.to_string_lossy().to_string());
@@ -295,28 +346,9 @@ fn extract_round1_packages(
     group_record: &crate::registry::GroupRecord,
     owner: &crate::registry::OwnerRecord,
 ) -> Result<Round1Packages> {
-    // Build XID -> Identifier mapping based on sorted participant order
-    let mut sorted_xids: Vec<XID> = group_record
-        .participants()
-        .iter()
-        .map(|p| *p.xid())
-        .collect();
-    if !sorted_xids.contains(&owner.xid()) {
-        sorted_xids.push(owner.xid());
-    }
-    sorted_xids.sort();
-    sorted_xids.dedup();
-
+    // XID -> Identifier mapping recorded when the invite was answered
     let xid_to_identifier: std::collections::HashMap<XID, Identifier> =
-        sorted_xids
-            .iter()
-            .enumerate()
-            .map(|(i, xid)| {
-                let identifier = Identifier::try_from((i + 1) as u16)
-                    .expect("Valid identifier");
-                (*xid, identifier)
-            })
-            .collect();
+        group_record.identifiers()?;
 
     let my_xid = owner.xid();
 
@@ -324,6 +356,7 @@ fn extract_round1_packages(
     let mut packages = BTreeMap::new();
     let mut packages_by_xid = Vec::new();
     for package_envelope in request.objects_for_parameter("round1Package") {
+        let package_envelope = decompress_envelope(package_envelope)?;
         // Extract participant XID
         let participant_xid: XID =
             package_envelope.extract_object_for_predicate("participant")?;
@@ -353,7 +386,11 @@ fn extract_round1_packages(
         packages_by_xid.push((participant_xid, package_for_storage));
     }
 
-    let expected_packages = xid_to_identifier.len().saturating_sub(1);
+    let expected_packages = group_record
+        .participants()
+        .iter()
+        .filter(|p| *p.xid() != my_xid)
+        .count();
     if packages.len() != expected_packages {
         bail!(
             "Expected {} Round 1 packages, found {}",
@@ -365,6 +402,107 @@ fn extract_round1_packages(
     Ok((packages, packages_by_xid))
 }
 
+/// XIDs the coordinator removed from the group after Round 1.
+fn excluded_participants(request: &SealedRequest) -> Result<Vec<XID>> {
+    let mut excluded = Vec::new();
+    for envelope in request.objects_for_parameter("excludedParticipant") {
+        excluded.push(envelope.extract_subject::<XID>()?);
+    }
+    Ok(excluded)
+}
+
+/// Remove excluded participants from the local group record and reduce the
+/// Round 1 secret's `max_signers` to match, so part2 expects packages only
+/// from the remaining participants. Identifiers are unchanged because
+/// excluded participants keep their place in the identifier order.
+fn apply_exclusions(
+    group_record: &mut crate::registry::GroupRecord,
+    owner_xid: &XID,
+    excluded: &[XID],
+    round1_secret: frost::keys::dkg::round1::SecretPackage,
+) -> Result<frost::keys::dkg::round1::SecretPackage> {
+    for xid in excluded {
+        if xid == owner_xid {
+            bail!("The coordinator excluded this participant from the group");
+        }
+        if group_record.excluded().iter().any(|p| p.xid() == xid) {
+            continue;
+        }
+        if !group_record.exclude_participant(xid) {
+            bail!(
+                "Excluded participant {} is not a member of this group",
+                xid.ur_string()
+            );
+        }
+    }
+
+    let mut remaining: Vec<XID> = group_record
+        .participants()
+        .iter()
+        .map(|p| *p.xid())
+        .collect();
+    if !remaining.contains(owner_xid) {
+        remaining.push(*owner_xid);
+    }
+    if remaining.len() < group_record.min_signers() {
+        bail!(
+            "Only {} participants remain after exclusions, fewer than min signers {}",
+            remaining.len(),
+            group_record.min_signers()
+        );
+    }
+
+    eprintln!(
+        "Warning: the coordinator excluded {} participant(s) from this group; \
+         continuing with {} participants.",
+        excluded.len(),
+        remaining.len()
+    );
+
+    let max_signers = u16::try_from(remaining.len())
+        .context("Too many participants for FROST identifiers")?;
+    let mut secret = serde_json::to_value(&round1_secret)?;
+    *secret
+        .get_mut("max_signers")
+        .context("Round 1 secret is missing max_signers")? = max_signers.into();
+    serde_json::from_value(secret).context("Failed to update Round 1 secret")
+}
+
+/// The share repair deltas the other helpers addressed to this participant,
+/// or `None` if it is not a helper in a share repair.
+fn extract_repair_deltas(
+    request: &SealedRequest,
+    group_record: &crate::registry::GroupRecord,
+) -> Result<Option<Vec<RepairScalar>>> {
+    let objects = request.objects_for_parameter("repairDelta");
+    if objects.is_empty() {
+        return Ok(None);
+    }
+    if group_record.refresh_of().is_none() {
+        bail!("Share repair deltas in a Round 2 request that is not a refresh");
+    }
+    let mut senders = BTreeSet::new();
+    let mut deltas = Vec::new();
+    for object in objects {
+        let sender: XID = object.extract_object_for_predicate("sender")?;
+        if !group_record
+            .participants()
+            .iter()
+            .any(|p| *p.xid() == sender)
+        {
+            bail!(
+                "Share repair delta from {}, who is not a participant",
+                sender.ur_string()
+            );
+        }
+        if !senders.insert(sender) {
+            bail!("Two share repair deltas from {}", sender.ur_string());
+        }
+        deltas.push(extract_repair_scalar(&object)?);
+    }
+    Ok(Some(deltas))
+}
+
 type Round1Packages = (
     BTreeMap<Identifier, frost::keys::dkg::round1::Package>,
     Vec<(XID, frost::keys::dkg::round1::Package)>,
@@ -377,28 +515,14 @@ fn build_response_body(
     response_arid: &ARID,
     round2_packages: &BTreeMap<Identifier, frost::keys::dkg::round2::Package>,
     group_record: &crate::registry::GroupRecord,
+    repair_sigma: Option<&RepairScalar>,
 ) -> Result<Envelope> {
     // Build Identifier -> XID mapping
-    let mut sorted_xids: Vec<XID> = group_record
-        .participants()
-        .iter()
-        .map(|p| *p.xid())
-        .collect();
-    if !sorted_xids.contains(participant_xid) {
-        sorted_xids.push(*participant_xid);
-    }
-    sorted_xids.sort();
-    sorted_xids.dedup();
-
     let identifier_to_xid: std::collections::HashMap<Identifier, XID> =
-        sorted_xids
-            .iter()
-            .enumerate()
-            .map(|(i, xid)| {
-                let identifier = Identifier::try_from((i + 1) as u16)
-                    .expect("Valid identifier");
-                (identifier, *xid)
-            })
+        group_record
+            .identifiers()?
+            .into_iter()
+            .map(|(xid, identifier)| (identifier, xid))
             .collect();
 
     let mut envelope = Envelope::unit()
@@ -420,6 +544,10 @@ fn build_response_body(
             .add_assertion("recipient", *recipient_xid);
         envelope = envelope.add_assertion("round2Package", package_envelope);
     }
+    if let Some(sigma) = repair_sigma {
+        envelope = envelope
+            .add_assertion("repairSigma", repair_scalar_envelope(sigma));
+    }
 
     Ok(envelope)
 }
@@ -0,0 +1,215 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use bc_components::XID;
+use bc_envelope::prelude::*;
+use clap::Parser;
+use frost_ed25519 as frost;
+
+use crate::cmd::{
+    common::load_registry,
+    dkg::common::{
+        GroupStatePaths, check_round1_commitments, parse_group_id,
+        read_ur_input, signing_key_from_verifying,
+    },
+    is_verbose,
+    registry::participants_file_path,
+};
+
+/// Re-derive a finished group's verifying key from its Round 1 packages.
+///
+/// The group verifying key is the sum of every participant's Round 1
+/// commitment to their secret, so the recorded packages alone determine it.
+/// A mismatch with the key stored in the registry means the packages or the
+/// registry were tampered with or corrupted.
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Group ID to verify
+    /// (`-` reads it from stdin)
+    #[arg(long = "group", value_name = "UR:ARID")]
+    group_id: String,
+
+    /// Directory holding the group's `collected_round1.json` (defaults to the
+    /// group's state directory next to the registry)
+    #[arg(value_name = "DIR")]
+    dir: Option<PathBuf>,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let registry_path = participants_file_path(self.registry.clone())?;
+        let registry = load_registry(&registry_path).with_context(|| {
+            format!("Failed to load registry at {}", registry_path.display())
+        })?;
+
+        let group_id =
+            parse_group_id(&registry, &read_ur_input(&self.group_id)?)?;
+        let group_record = registry
+            .group(&group_id)
+            .context("Group not found in registry")?;
+        let Some(recorded_key) = group_record.verifying_key() else {
+            bail!(
+                "Group {} has no verifying key yet; finish the DKG before \
+                 verifying it",
+                group_id.ur_string()
+            );
+        };
+        if let Some(original_id) = group_record.refresh_of() {
+            bail!(
+                "Group {} is a refresh of {}; its Round 1 packages only rotate \
+                 shares, so verify the transcript of {} instead",
+                group_id.ur_string(),
+                original_id.ur_string(),
+                original_id.ur_string()
+            );
+        }
+
+        // A transcript copied elsewhere keeps the state directory's layout
+        let paths = GroupStatePaths::new(&registry_path);
+        let locate = |path: PathBuf| match &self.dir {
+            Some(dir) => dir.join(path.file_name().unwrap_or_default()),
+            None => path,
+        };
+        let dir = self
+            .dir
+            .clone()
+            .unwrap_or_else(|| paths.group_dir(&group_id));
+        let mut packages = read_round1_packages(&locate(
+            paths.collected_round1_path(&group_id),
+        ))?;
+
+        // Participants record their own package separately from the ones the
+        // coordinator forwarded to them.
+        if let Some(owner) = registry.owner()
+            && !packages.contains_key(&owner.xid())
+        {
+            let own_path = locate(paths.round1_package_path(&group_id));
+            if own_path.exists() {
+                let package =
+                    serde_json::from_slice(&fs::read(&own_path).with_context(
+                        || format!("Failed to read {}", own_path.display()),
+                    )?)
+                    .with_context(|| {
+                        format!("Failed to parse {}", own_path.display())
+                    })?;
+                packages.insert(owner.xid(), package);
+            }
+        }
+
+        let identifiers = group_record.identifiers()?;
+        let participants: Vec<XID> = group_record
+            .participants()
+            .iter()
+            .map(|p| *p.xid())
+            .collect();
+        for xid in packages.keys() {
+            if !participants.contains(xid) {
+                bail!(
+                    "Transcript has a Round 1 package from {}, who is not a \
+                     participant in the group",
+                    xid.ur_string()
+                );
+            }
+        }
+
+        let mut ordered = Vec::with_capacity(participants.len());
+        let mut commitments = BTreeMap::new();
+        for xid in &participants {
+            let package = packages.get(xid).with_context(|| {
+                format!(
+                    "Transcript has no Round 1 package from {}",
+                    xid.ur_string()
+                )
+            })?;
+            let identifier = identifiers.get(xid).with_context(|| {
+                format!("No FROST identifier for {}", xid.ur_string())
+            })?;
+            commitments.insert(*identifier, package.commitment());
+            ordered.push((*xid, package.clone()));
+        }
+        check_round1_commitments(&ordered, group_record.min_signers())?;
+
+        let public_key_package =
+            frost::keys::PublicKeyPackage::from_dkg_commitments(&commitments)
+                .map_err(|e| anyhow!("Failed to derive group key: {e}"))?;
+        let derived_key =
+            signing_key_from_verifying(public_key_package.verifying_key())?;
+        if &derived_key != recorded_key {
+            bail!(
+                "Group verifying key in registry {} does not match the key {} \
+                 derived from the transcript",
+                recorded_key.ur_string(),
+                derived_key.ur_string()
+            );
+        }
+
+        let own_public_path = locate(paths.public_key_package_path(&group_id));
+        if own_public_path.exists() {
+            let stored: frost::keys::PublicKeyPackage = serde_json::from_slice(
+                &fs::read(&own_public_path).with_context(|| {
+                    format!("Failed to read {}", own_public_path.display())
+                })?,
+            )
+            .with_context(|| {
+                format!("Failed to parse {}", own_public_path.display())
+            })?;
+            if stored.verifying_key() != public_key_package.verifying_key() {
+                bail!(
+                    "{} does not match the key derived from the transcript",
+                    own_public_path.display()
+                );
+            }
+        }
+
+        if is_verbose() {
+            eprintln!(
+                "Replayed Round 1 packages from {} participants in {}",
+                participants.len(),
+                dir.display()
+            );
+        }
+        println!("{}", derived_key.ur_string());
+
+        Ok(())
+    }
+}
+
+/// Reads the collected Round 1 packages at `path`, keyed by participant XID.
+fn read_round1_packages(
+    path: &Path,
+) -> Result<BTreeMap<XID, frost::keys::dkg::round1::Package>> {
+    if !path.exists() {
+        bail!(
+            "{} not found; Round 1 must have been collected or received to \
+             verify the group",
+            path.display()
+        );
+    }
+    let raw: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(
+            &fs::read(&path).with_context(|| {
+                format!("Failed to read {}", path.display())
+            })?,
+        )
+        .context("Failed to parse collected_round1.json")?;
+
+    let mut packages = BTreeMap::new();
+    for (xid_str, value) in raw {
+        let xid = XID::from_ur_string(&xid_str)
+            .context("Invalid XID in collected_round1.json")?;
+        let package = serde_json::from_value(value).with_context(|| {
+            format!("Failed to parse Round 1 package for {xid_str}")
+        })?;
+        packages.insert(xid, package);
+    }
+    Ok(packages)
+}
@@ -0,0 +1,233 @@
+use anyhow::{Context, Result, bail};
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use clap::Parser;
+
+use crate::{
+    cmd::{
+        common::{self, ask},
+        dkg::{
+            OptionalStorageSelector,
+            common::{
+                GroupStatePaths, ensure_owner_is_coordinator, parse_group_id,
+                read_ur_input,
+            },
+            coordinator::{finalize, round1, round2},
+        },
+        registry::participants_file_path,
+    },
+    registry::{GroupRecord, Registry},
+};
+
+/// Walk a coordinator through the rest of a DKG, one step at a time.
+///
+/// Starting from a sent invite, the wizard works out which coordinator step
+/// comes next from the group's state, shows who it is waiting on, and asks
+/// before running it. Each step is the ordinary `frost dkg coordinator`
+/// command, so declining leaves the group ready to continue by hand.
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(flatten)]
+    storage: OptionalStorageSelector,
+
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Wait up to this many seconds for each response at every step
+    #[arg(long = "timeout", value_name = "SECONDS")]
+    timeout: Option<u64>,
+
+    /// Collect each step's responses concurrently
+    #[arg(long)]
+    parallel: bool,
+
+    /// Group ID (`-` reads it from stdin)
+    #[arg(value_name = "GROUP_ID")]
+    group_id: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let registry_path = participants_file_path(self.registry.clone())?;
+        let paths = GroupStatePaths::new(&registry_path);
+        let first = load_registry(&registry_path)?;
+        let group_id = parse_group_id(&first, &read_ur_input(&self.group_id)?)?;
+
+        let mut last_step = None;
+        loop {
+            let registry = first.reload(&registry_path).with_context(|| {
+                format!(
+                    "Failed to load registry at {}",
+                    registry_path.display()
+                )
+            })?;
+            let owner =
+                registry.owner().context("Registry owner is required")?;
+            let group_record = registry
+                .group(&group_id)
+                .context("Group not found in registry")?;
+            ensure_owner_is_coordinator(group_record, owner)?;
+
+            let step = next_step(&paths, &group_id, group_record);
+            if step == WizardStep::Round1
+                && group_record.pending_requests().is_empty()
+            {
+                bail!(
+                    "No invite has been sent for this group; run `frost dkg \
+                     coordinator invite` first"
+                );
+            }
+            print_step(&registry, &group_id, group_record, step);
+
+            let Some(command) = step.command() else {
+                return Ok(());
+            };
+            if last_step == Some(step) {
+                bail!(
+                    "{command} did not advance the group; resolve the problem \
+                     above and run the wizard again"
+                );
+            }
+            if !ask(&format!("Run dkg coordinator {command} now? [Y/n]"))? {
+                eprintln!(
+                    "Stopped before {command}. Run `frost dkg wizard` again \
+                     or `frost dkg coordinator {command}` to continue."
+                );
+                return Ok(());
+            }
+            self.run(step, &group_id)?;
+            last_step = Some(step);
+        }
+    }
+
+    /// Runs `step` as the standalone command would, forwarding this
+    /// command's options.
+    fn run(&self, step: WizardStep, group_id: &ARID) -> Result<()> {
+        let command = step.command().context("Nothing left to run")?;
+        let mut args = vec![format!("frost dkg coordinator {command}")];
+        args.extend(self.storage.to_args());
+        if let Some(registry) = &self.registry {
+            args.extend(["--registry".to_string(), registry.clone()]);
+        }
+        if let Some(timeout) = self.timeout {
+            args.extend(["--timeout".to_string(), timeout.to_string()]);
+        }
+        if self.parallel {
+            args.push("--parallel".to_string());
+        }
+        args.push(group_id.ur_string());
+
+        match step {
+            WizardStep::Round1 => {
+                round1::CommandArgs::try_parse_from(args)?.exec()
+            }
+            WizardStep::Round2 => {
+                round2::CommandArgs::try_parse_from(args)?.exec()
+            }
+            WizardStep::Finalize => {
+                finalize::CommandArgs::try_parse_from(args)?.exec()
+            }
+            WizardStep::Done => Ok(()),
+        }
+    }
+}
+
+/// The next coordinator step of a group's DKG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStep {
+    /// Collect invite responses and send Round 2 requests
+    Round1,
+    /// Collect Round 2 responses and send finalize requests
+    Round2,
+    /// Collect finalize responses and record the verifying key
+    Finalize,
+    /// The group has its verifying key
+    Done,
+}
+
+impl WizardStep {
+    /// The `frost dkg coordinator` subcommand that performs this step.
+    pub fn command(&self) -> Option<&'static str> {
+        match self {
+            WizardStep::Round1 => Some("round1"),
+            WizardStep::Round2 => Some("round2"),
+            WizardStep::Finalize => Some("finalize"),
+            WizardStep::Done => None,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            WizardStep::Round1 => {
+                "collect invite responses and send Round 2 requests"
+            }
+            WizardStep::Round2 => {
+                "collect Round 2 responses and send finalize requests"
+            }
+            WizardStep::Finalize => {
+                "collect finalize responses and record the group key"
+            }
+            WizardStep::Done => "nothing; the DKG is finished",
+        }
+    }
+}
+
+/// Works out the coordinator's next step from the files each step leaves
+/// behind: Round 1 packages after `round1`, Round 2 packages after `round2`
+/// (with finalize requests sent unless `--packages-only` stopped short), and
+/// the verifying key after `finalize`.
+pub fn next_step(
+    paths: &GroupStatePaths,
+    group_id: &ARID,
+    group_record: &GroupRecord,
+) -> WizardStep {
+    if group_record.status().is_finalized() {
+        WizardStep::Done
+    } else if paths.collected_round2_path(group_id).exists()
+        && !group_record.pending_requests().is_send_only()
+    {
+        WizardStep::Finalize
+    } else if paths.collected_round1_path(group_id).exists() {
+        WizardStep::Round2
+    } else {
+        WizardStep::Round1
+    }
+}
+
+fn print_step(
+    registry: &Registry,
+    group_id: &ARID,
+    group_record: &GroupRecord,
+    step: WizardStep,
+) {
+    eprintln!();
+    eprintln!("Group: {}", group_id.ur_string());
+    eprintln!(
+        "Participants: {} ({} needed to sign)",
+        group_record.participants().len(),
+        group_record.min_signers()
+    );
+    eprintln!("Next: {}", step.description());
+
+    if let Some(key) = group_record.verifying_key() {
+        eprintln!("Verifying key: {}", key.ur_string());
+        return;
+    }
+    let pending = group_record.pending_requests();
+    eprintln!("Waiting on {} participants:", pending.len());
+    for (xid, arid) in pending.iter_collect() {
+        let name = registry
+            .participant(xid)
+            .and_then(|r| r.pet_name().map(|s| s.to_owned()))
+            .unwrap_or_else(|| xid.ur_string());
+        eprintln!("  {name}: {}", arid.ur_string());
+    }
+}
+
+fn load_registry(registry_path: &std::path::Path) -> Result<Registry> {
+    common::load_registry(registry_path).with_context(|| {
+        format!("Failed to load registry at {}", registry_path.display())
+    })
+}
@@ -0,0 +1,321 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use clap::Parser;
+
+use crate::{
+    cmd::{
+        check::check_storage,
+        common::{GroupStatePaths, OptionalStorageSelector, load_registry},
+        is_no_color,
+        registry::participants_file_path,
+        storage::{StorageSelection, shared_runtime},
+    },
+    registry::{Registry, is_encrypted_registry},
+};
+
+/// Diagnose the registry, storage backend, and local group state.
+///
+/// Prints a checklist of everything that was examined, with a hint for each
+/// problem found, and exits nonzero if any check failed.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Storage backend to check for reachability (skipped when omitted)
+    #[command(flatten)]
+    storage: OptionalStorageSelector,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let selection = self.storage.resolve()?;
+        let registry_path = participants_file_path(self.registry)?;
+        let paths = GroupStatePaths::new(&registry_path);
+
+        let mut checklist = Checklist::default();
+        let registry = check_registry(&registry_path, &mut checklist);
+        if let Some(registry) = &registry {
+            check_owner(registry, &mut checklist);
+        }
+        check_storage_backend(selection, &mut checklist)?;
+        if let Some(registry) = &registry {
+            check_group_state(registry, &paths, &mut checklist)?;
+        }
+        check_secret_permissions(&registry_path, &paths, &mut checklist)?;
+
+        checklist.print();
+        let failures = checklist.failures();
+        if failures > 0 {
+            bail!(
+                "{failures} check{} failed",
+                if failures == 1 { "" } else { "s" }
+            );
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Checklist
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl Status {
+    fn marker(self) -> &'static str {
+        match (self, is_no_color()) {
+            (Status::Pass, false) => "✓",
+            (Status::Warn, false) => "⚠",
+            (Status::Fail, false) => "✗",
+            (Status::Pass, true) => "[pass]",
+            (Status::Warn, true) => "[warn]",
+            (Status::Fail, true) => "[fail]",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Finding {
+    status: Status,
+    message: String,
+    hint: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct Checklist {
+    findings: Vec<Finding>,
+}
+
+impl Checklist {
+    fn pass(&mut self, message: impl Into<String>) {
+        self.push(Status::Pass, message.into(), None);
+    }
+
+    fn warn(&mut self, message: impl Into<String>, hint: impl Into<String>) {
+        self.push(Status::Warn, message.into(), Some(hint.into()));
+    }
+
+    fn fail(&mut self, message: impl Into<String>, hint: impl Into<String>) {
+        self.push(Status::Fail, message.into(), Some(hint.into()));
+    }
+
+    fn push(&mut self, status: Status, message: String, hint: Option<String>) {
+        self.findings.push(Finding { status, message, hint });
+    }
+
+    fn failures(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|finding| finding.status == Status::Fail)
+            .count()
+    }
+
+    fn print(&self) {
+        for finding in &self.findings {
+            println!("{} {}", finding.status.marker(), finding.message);
+            if let Some(hint) = &finding.hint {
+                println!("    hint: {hint}");
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Checks
+// -----------------------------------------------------------------------------
+
+fn check_registry(path: &Path, checklist: &mut Checklist) -> Option<Registry> {
+    if !path.exists() {
+        checklist.fail(
+            format!("No registry at {}", path.display()),
+            "Create one with `frost registry owner set`, or point --registry \
+             at an existing registry",
+        );
+        return None;
+    }
+    match load_registry(path) {
+        Ok(registry) => {
+            checklist.pass(format!(
+                "Registry loads from {} ({} participants, {} groups)",
+                path.display(),
+                registry.participants().len(),
+                registry.groups().count()
+            ));
+            Some(registry)
+        }
+        Err(e) => {
+            let hint = if registry_is_encrypted(path) {
+                "Supply the passphrase with --passphrase-file or \
+                 FROST_PASSPHRASE"
+            } else {
+                "Restore the registry from a backup or an export"
+            };
+            checklist.fail(
+                format!("Registry at {} does not load: {e:#}", path.display()),
+                hint,
+            );
+            None
+        }
+    }
+}
+
+fn registry_is_encrypted(path: &Path) -> bool {
+    std::fs::read(path).is_ok_and(|data| is_encrypted_registry(&data))
+}
+
+fn check_owner(registry: &Registry, checklist: &mut Checklist) {
+    match registry.owner() {
+        None => checklist.fail(
+            "No owner is set in the registry",
+            "Set one with `frost registry owner set <XID_DOCUMENT>`, using a \
+             document that includes private keys",
+        ),
+        Some(owner)
+            if owner.xid_document().inception_private_keys().is_none() =>
+        {
+            checklist.fail(
+                format!(
+                    "Owner {} has no private keys",
+                    owner.xid().ur_string()
+                ),
+                "Set the owner again from a document that includes private \
+                 keys",
+            )
+        }
+        Some(owner) => checklist.pass(format!(
+            "Owner {} is set with private keys",
+            owner.xid().ur_string()
+        )),
+    }
+}
+
+fn check_storage_backend(
+    selection: Option<StorageSelection>,
+    checklist: &mut Checklist,
+) -> Result<()> {
+    let Some(selection) = selection else {
+        checklist.warn(
+            "No storage backend selected; reachability not checked",
+            "Pass --storage (and --host/--port) to check the backend you use",
+        );
+        return Ok(());
+    };
+    match shared_runtime()?.block_on(check_storage(selection)) {
+        Ok(message) => checklist.pass(message),
+        Err(e) => checklist.fail(
+            format!("{e:#}"),
+            "Start the backend, or select a reachable one with --storage",
+        ),
+    }
+    Ok(())
+}
+
+fn check_group_state(
+    registry: &Registry,
+    paths: &GroupStatePaths,
+    checklist: &mut Checklist,
+) -> Result<()> {
+    let state_groups = paths.candidate_groups(None)?;
+    let orphans: Vec<&ARID> = state_groups
+        .iter()
+        .filter(|group_id| registry.group(group_id).is_none())
+        .collect();
+    for group_id in &orphans {
+        checklist.warn(
+            format!(
+                "State directory {} has no matching group in the registry",
+                paths.group_dir(group_id).display()
+            ),
+            "Remove the directory if the group was abandoned, or restore the \
+             registry that recorded it",
+        );
+    }
+    if orphans.is_empty() {
+        checklist.pass(format!(
+            "{} group state director{} match registry groups",
+            state_groups.len(),
+            if state_groups.len() == 1 { "y" } else { "ies" }
+        ));
+    }
+    Ok(())
+}
+
+/// Files holding private keys or secret protocol state: the registry itself
+/// (which carries the owner's private keys) and the per-group DKG secrets,
+/// key packages, dealt shares, and signing nonces.
+fn secret_files(
+    registry_path: &Path,
+    paths: &GroupStatePaths,
+) -> Result<Vec<PathBuf>> {
+    let mut files = vec![registry_path.to_path_buf()];
+    for group_id in paths.candidate_groups(None)? {
+        files.push(paths.round1_secret_path(&group_id));
+        files.push(paths.round2_secret_path(&group_id));
+        files.push(paths.key_package_path(&group_id));
+        let dealt_dir = paths.dealt_shares_dir(&group_id);
+        if dealt_dir.is_dir() {
+            for entry in std::fs::read_dir(&dealt_dir)? {
+                files.push(entry?.path());
+            }
+        }
+        for session_id in paths.sessions(&group_id)? {
+            files.push(paths.commit_state_path(&group_id, &session_id));
+        }
+    }
+    files.retain(|path| path.is_file());
+    Ok(files)
+}
+
+#[cfg(unix)]
+fn check_secret_permissions(
+    registry_path: &Path,
+    paths: &GroupStatePaths,
+    checklist: &mut Checklist,
+) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let files = secret_files(registry_path, paths)?;
+    let mut exposed = 0;
+    for path in &files {
+        let mode = std::fs::metadata(path)?.permissions().mode();
+        if mode & 0o004 != 0 {
+            exposed += 1;
+            checklist.warn(
+                format!("{} is readable by all users", path.display()),
+                format!("chmod 600 {}", path.display()),
+            );
+        }
+    }
+    if exposed == 0 {
+        checklist.pass(format!(
+            "{} secret file{} not world-readable",
+            files.len(),
+            if files.len() == 1 { " is" } else { "s are" }
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_secret_permissions(
+    _registry_path: &Path,
+    _paths: &GroupStatePaths,
+    checklist: &mut Checklist,
+) -> Result<()> {
+    checklist.warn(
+        "Secret file permissions not checked on this platform",
+        "Make sure the registry and group state directories are private",
+    );
+    Ok(())
+}
@@ -0,0 +1,30 @@
+pub mod send;
+pub mod status;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+/// Participant presence heartbeats.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    /// Post signed heartbeats showing this participant is online
+    Send(send::CommandArgs),
+    /// Show when each group member last posted a heartbeat
+    Status(status::CommandArgs),
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        match self.command {
+            Commands::Send(args) => args.exec(),
+            Commands::Status(args) => args.exec(),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use std::{thread, time::Duration};
+
+use anyhow::{Context, Result, bail};
+use bc_envelope::prelude::*;
+use clap::Parser;
+
+use crate::{
+    cmd::{
+        busy::put_with_indicator,
+        common::load_registry,
+        dkg::common::{OptionalStorageSelector, parse_group_id, read_ur_input},
+        is_verbose,
+        registry::participants_file_path,
+        storage::{StorageClient, shared_runtime},
+    },
+    dkg::{HEARTBEAT_SLOT_SECS, Heartbeat},
+};
+
+/// Post a signed heartbeat showing this participant is online.
+///
+/// The heartbeat goes to a status ARID derived from a secret the owner
+/// shares with the group's coordinator, the group ID, the owner's XID, and
+/// the current minute, where `frost heartbeat status` looks for it. With
+/// `--watch`, keeps posting one every `--every` minutes until interrupted.
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(flatten)]
+    storage: OptionalStorageSelector,
+
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Keep running, posting a heartbeat every --every minutes
+    #[arg(long = "watch")]
+    watch: bool,
+
+    /// Minutes between heartbeats with --watch
+    #[arg(
+        long = "every",
+        value_name = "MINUTES",
+        default_value_t = 1,
+        requires = "watch",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    every: u64,
+
+    /// Group ID to post heartbeats for
+    /// (`-` reads it from stdin)
+    #[arg(value_name = "GROUP_ID")]
+    group_id: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let selection = self.storage.resolve()?;
+        let selection =
+            selection.context("Hubert storage is required for heartbeats")?;
+
+        let registry_path = participants_file_path(self.registry.clone())?;
+        let registry = load_registry(&registry_path).with_context(|| {
+            format!("Failed to load registry at {}", registry_path.display())
+        })?;
+        let owner = registry
+            .owner()
+            .context("Registry owner is required")?
+            .clone();
+
+        let group_id =
+            parse_group_id(&registry, &read_ur_input(&self.group_id)?)?;
+        let group_record = registry
+            .group(&group_id)
+            .context("Group not found in registry")?;
+        let owner_xid = owner.xid();
+        let coordinator = *group_record.coordinator().xid();
+        let is_member = coordinator == owner_xid
+            || group_record
+                .participants()
+                .iter()
+                .any(|participant| participant.xid() == &owner_xid);
+        if !is_member {
+            bail!(
+                "Registry owner {} is not a member of this group",
+                owner_xid.ur_string()
+            );
+        }
+        if coordinator == owner_xid {
+            bail!(
+                "The coordinator does not post heartbeats; run `frost \
+                 heartbeat status` to see the other members"
+            );
+        }
+        let coordinator_doc = registry
+            .participant(&coordinator)
+            .with_context(|| {
+                format!(
+                    "Coordinator {} not found in registry",
+                    coordinator.ur_string()
+                )
+            })?
+            .xid_document();
+
+        let signer = owner
+            .xid_document()
+            .inception_private_keys()
+            .context("Owner XID document has no signing keys")?;
+        let secret = Heartbeat::shared_secret(signer, coordinator_doc)?;
+
+        let runtime = shared_runtime()?;
+        let client = runtime
+            .block_on(async { StorageClient::shared(selection).await })?;
+
+        loop {
+            let heartbeat = Heartbeat::new(group_id, owner_xid, Date::now());
+            let arid = heartbeat.arid(&secret);
+            match put_with_indicator(
+                runtime,
+                &client,
+                &arid,
+                &heartbeat.sign(signer),
+                "Heartbeat",
+            ) {
+                Ok(_) => {
+                    if is_verbose() {
+                        eprintln!(
+                            "Posted heartbeat at {} to {}",
+                            heartbeat.date,
+                            arid.ur_string()
+                        );
+                    }
+                }
+                // A missed beat only makes this participant look stale for
+                // a while, so a watch keeps going
+                Err(err) if self.watch => eprintln!(
+                    "Warning: heartbeat at {} was not posted: {err:#}",
+                    heartbeat.date
+                ),
+                Err(err) => return Err(err),
+            }
+            if !self.watch {
+                return Ok(());
+            }
+
+            // Sleep to the start of a later slot, since each slot's status
+            // ARID can only be written once
+            let next_slot = Heartbeat::slot_of(&heartbeat.date) + self.every;
+            let wait = (next_slot * HEARTBEAT_SLOT_SECS) as f64
+                - Date::now().timestamp();
+            thread::sleep(Duration::from_secs_f64(wait.max(0.0)));
+        }
+    }
+}
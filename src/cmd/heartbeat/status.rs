@@ -0,0 +1,268 @@
+use anyhow::{Context, Result};
+use bc_components::{ARID, SymmetricKey, XID, XIDProvider};
+use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
+use clap::Parser;
+use tokio::runtime::Runtime;
+
+use crate::{
+    cmd::{
+        common::{OutputFormat, load_registry, sort_for_display, tsv_record},
+        dkg::common::{
+            OptionalStorageSelector, ensure_owner_is_coordinator,
+            parse_group_id, read_ur_input,
+        },
+        is_verbose,
+        registry::participants_file_path,
+        storage::{StorageClient, shared_runtime},
+    },
+    dkg::Heartbeat,
+};
+
+/// Show when each group member last posted a heartbeat (coordinator only).
+///
+/// Looks back through the last `--lookback` minutes of each member's status
+/// ARIDs, keyed by the secret the coordinator shares with that member, for
+/// the newest heartbeat signed by that member, and flags members last seen
+/// more than `--stale-after` minutes ago, or not at all.
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(flatten)]
+    storage: OptionalStorageSelector,
+
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// How many minutes of heartbeats to search, counting back from now
+    #[arg(
+        long = "lookback",
+        value_name = "MINUTES",
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u64).range(1..=1440)
+    )]
+    lookback: u64,
+
+    /// Flag members whose last heartbeat is older than this many minutes
+    #[arg(long = "stale-after", value_name = "MINUTES", default_value_t = 3)]
+    stale_after: u64,
+
+    /// Output format. `tsv` prints one member per line with the columns
+    /// pet name or XID, XID, last heartbeat date, age in seconds, and
+    /// `stale` or `ok`; a member with no heartbeat has empty date and age
+    #[arg(long = "output", value_enum, value_name = "FORMAT")]
+    output: Option<OutputFormat>,
+
+    /// Print the members as JSON (same as `--output json`)
+    #[arg(long = "json", conflicts_with = "output")]
+    json: bool,
+
+    /// Group ID to show heartbeats for
+    /// (`-` reads it from stdin)
+    #[arg(value_name = "GROUP_ID")]
+    group_id: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let selection = self.storage.resolve()?;
+        let selection =
+            selection.context("Hubert storage is required for heartbeats")?;
+
+        let registry_path = participants_file_path(self.registry.clone())?;
+        let registry = load_registry(&registry_path).with_context(|| {
+            format!("Failed to load registry at {}", registry_path.display())
+        })?;
+        let owner = registry
+            .owner()
+            .context("Registry owner is required")?
+            .clone();
+
+        let group_id =
+            parse_group_id(&registry, &read_ur_input(&self.group_id)?)?;
+        let group_record = registry
+            .group(&group_id)
+            .context("Group not found in registry")?;
+        ensure_owner_is_coordinator(group_record, &owner)?;
+
+        let mut members: Vec<(String, XID, XIDDocument)> = Vec::new();
+        for participant in group_record.participants() {
+            let xid = *participant.xid();
+            if xid == owner.xid() {
+                continue;
+            }
+            let record = registry.participant(&xid).with_context(|| {
+                format!(
+                    "Participant not found in registry: {}",
+                    xid.ur_string()
+                )
+            })?;
+            let name = record
+                .pet_name()
+                .map(str::to_owned)
+                .unwrap_or_else(|| xid.ur_string());
+            members.push((name, xid, record.xid_document().clone()));
+        }
+        sort_for_display(&mut members, |(name, xid, _)| (name.clone(), *xid));
+
+        let format = if self.json {
+            OutputFormat::Json
+        } else {
+            self.output.unwrap_or_default()
+        };
+        let own_keys = owner
+            .xid_document()
+            .inception_private_keys()
+            .context("Owner XID document has no private keys")?;
+
+        let runtime = shared_runtime()?;
+        let client = runtime
+            .block_on(async { StorageClient::shared(selection).await })?;
+
+        let now = Date::now();
+        let stale_after = (self.stale_after * 60) as f64;
+        let mut statuses = Vec::new();
+        for (name, xid, document) in &members {
+            let secret = Heartbeat::shared_secret(own_keys, document)?;
+            let last_seen = latest_heartbeat(
+                runtime,
+                &client,
+                &secret,
+                &group_id,
+                document,
+                &now,
+                self.lookback,
+            )?;
+            let age = last_seen
+                .as_ref()
+                .map(|heartbeat| now.timestamp() - heartbeat.date.timestamp());
+            let stale = age.is_none_or(|age| age > stale_after);
+            statuses.push((name, xid, last_seen, age, stale));
+        }
+
+        match format {
+            OutputFormat::Json => {
+                let members: Vec<serde_json::Value> = statuses
+                    .iter()
+                    .map(|(name, xid, last_seen, age, stale)| {
+                        serde_json::json!({
+                            "name": name,
+                            "xid": xid.ur_string(),
+                            "last_seen": last_seen
+                                .as_ref()
+                                .map(|heartbeat| heartbeat.date.to_string()),
+                            "age_seconds": age.map(|age| age.max(0.0) as u64),
+                            "stale": stale,
+                        })
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&members)?);
+            }
+            OutputFormat::Tsv => {
+                for (name, xid, last_seen, age, stale) in &statuses {
+                    let xid = xid.ur_string();
+                    let date = last_seen
+                        .as_ref()
+                        .map(|heartbeat| heartbeat.date.to_string())
+                        .unwrap_or_default();
+                    let age = age
+                        .map(|age| (age.max(0.0) as u64).to_string())
+                        .unwrap_or_default();
+                    println!(
+                        "{}",
+                        tsv_record([
+                            name.as_str(),
+                            xid.as_str(),
+                            date.as_str(),
+                            age.as_str(),
+                            if *stale { "stale" } else { "ok" },
+                        ])
+                    );
+                }
+            }
+            OutputFormat::Human => {
+                for (name, _, last_seen, age, stale) in &statuses {
+                    let marker = if *stale { "  STALE" } else { "" };
+                    match (last_seen, age) {
+                        (Some(heartbeat), Some(age)) => println!(
+                            "{name}: last seen {} ({} ago){marker}",
+                            heartbeat.date,
+                            format_age(*age)
+                        ),
+                        _ => println!(
+                            "{name}: no heartbeat in the last {} minutes{marker}",
+                            self.lookback
+                        ),
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The newest valid heartbeat `participant` posted for `group_id` within
+/// `lookback` slots of `now`, at the status ARIDs keyed by `secret`.
+///
+/// A heartbeat counts only if the participant signed it for this group and
+/// it sits at the status ARID for its own date, so one copied to a later
+/// slot cannot make a participant look more recently alive.
+fn latest_heartbeat(
+    runtime: &Runtime,
+    client: &StorageClient,
+    secret: &SymmetricKey,
+    group_id: &ARID,
+    participant: &XIDDocument,
+    now: &Date,
+    lookback: u64,
+) -> Result<Option<Heartbeat>> {
+    let xid = participant.xid();
+    let current = Heartbeat::slot_of(now);
+    for slot in (current.saturating_sub(lookback - 1)..=current).rev() {
+        let arid = Heartbeat::status_arid(secret, group_id, &xid, slot);
+        let envelope = runtime
+            .block_on(async { client.get(&arid, Some(0)).await })
+            .map_err(|err| err.into_anyhow())?;
+        let Some(envelope) = envelope else {
+            continue;
+        };
+        match Heartbeat::verify(&envelope, participant) {
+            Ok(heartbeat)
+                if heartbeat.group_id == *group_id
+                    && Heartbeat::slot_of(&heartbeat.date) == slot =>
+            {
+                return Ok(Some(heartbeat));
+            }
+            Ok(_) => {
+                if is_verbose() {
+                    eprintln!(
+                        "Ignoring heartbeat at {} posted for another group or slot",
+                        arid.ur_string()
+                    );
+                }
+            }
+            Err(err) => {
+                if is_verbose() {
+                    eprintln!(
+                        "Ignoring heartbeat at {}: {err:#}",
+                        arid.ur_string()
+                    );
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Formats an age in seconds as whole seconds under two minutes, otherwise
+/// whole minutes.
+fn format_age(seconds: f64) -> String {
+    let seconds = seconds.max(0.0) as u64;
+    if seconds < 120 {
+        format!("{seconds}s")
+    } else {
+        format!("{}m", seconds / 60)
+    }
+}
@@ -0,0 +1,130 @@
+use anyhow::{Context, Result};
+use bc_envelope::prelude::*;
+use clap::{Parser, ValueEnum};
+
+use crate::cmd::{common::read_ur_input, dkg::common::parse_envelope_ur};
+
+/// How `inspect` renders an envelope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[doc(hidden)]
+pub enum InspectFormat {
+    /// Gordian Envelope notation, as `Envelope::format()` prints it
+    Envelope,
+    /// Indented tree of subjects and assertions, with each node's type
+    Tree,
+}
+
+/// Show the structure of an envelope, such as a sealed request or response.
+///
+/// Nothing is decrypted: encrypted, elided, and compressed parts are shown
+/// as such, with a short digest so they can be told apart.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Output format
+    #[arg(long = "format", value_enum, default_value = "envelope")]
+    format: InspectFormat,
+
+    /// Envelope to inspect (ur:envelope; `-` reads it from stdin)
+    #[arg(value_name = "ENVELOPE")]
+    envelope: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let envelope = parse_envelope_ur(&read_ur_input(&self.envelope)?)?;
+        match self.format {
+            InspectFormat::Envelope => println!("{}", envelope.format()),
+            InspectFormat::Tree => println!("{}", format_tree(&envelope)?),
+        }
+        Ok(())
+    }
+}
+
+/// Renders `envelope` as an indented tree, one node per line.
+///
+/// Each line names the node's role (subject, assertion, predicate, object,
+/// or wrapped content) and its type. Leaves and known values also show their
+/// value; encrypted, elided, and compressed nodes show a short digest.
+pub fn format_tree(envelope: &Envelope) -> Result<String> {
+    let mut lines = Vec::new();
+    push_tree(envelope, "envelope", 0, &mut lines)?;
+    Ok(lines.join("\n"))
+}
+
+fn push_tree(
+    envelope: &Envelope,
+    role: &str,
+    depth: usize,
+    lines: &mut Vec<String>,
+) -> Result<()> {
+    let indent = "  ".repeat(depth);
+    if envelope.is_node() {
+        lines.push(format!(
+            "{indent}{role}: node ({} assertions)",
+            envelope.assertions().len()
+        ));
+        push_tree(&envelope.subject(), "subject", depth + 1, lines)?;
+        for assertion in envelope.assertions() {
+            push_tree(&assertion, "assertion", depth + 1, lines)?;
+        }
+    } else if envelope.is_assertion() {
+        lines.push(format!("{indent}{role}: assertion"));
+        let predicate = envelope
+            .as_predicate()
+            .context("Assertion has no predicate")?;
+        let object = envelope.as_object().context("Assertion has no object")?;
+        push_tree(&predicate, "predicate", depth + 1, lines)?;
+        push_tree(&object, "object", depth + 1, lines)?;
+    } else if envelope.is_wrapped() {
+        lines.push(format!("{indent}{role}: wrapped"));
+        push_tree(&envelope.try_unwrap()?, "content", depth + 1, lines)?;
+    } else if envelope.is_encrypted() {
+        lines.push(format!(
+            "{indent}{role}: ENCRYPTED {}",
+            short_digest(envelope)
+        ));
+    } else if envelope.is_elided() {
+        lines
+            .push(format!("{indent}{role}: ELIDED {}", short_digest(envelope)));
+    } else if envelope.is_compressed() {
+        lines.push(format!(
+            "{indent}{role}: COMPRESSED {}",
+            short_digest(envelope)
+        ));
+    } else if envelope.is_known_value() {
+        lines.push(format!(
+            "{indent}{role}: known value {}",
+            envelope.format_flat()
+        ));
+    } else {
+        let kind = envelope.as_leaf().map_or("leaf", |cbor| leaf_kind(&cbor));
+        lines
+            .push(format!("{indent}{role}: {kind} {}", envelope.format_flat()));
+    }
+    Ok(())
+}
+
+fn leaf_kind(cbor: &CBOR) -> &'static str {
+    match cbor.as_case() {
+        CBORCase::Unsigned(_) | CBORCase::Negative(_) => "number",
+        CBORCase::Float(_) => "float",
+        CBORCase::ByteString(_) => "bytes",
+        CBORCase::Text(_) => "text",
+        CBORCase::Array(_) => "array",
+        CBORCase::Map(_) => "map",
+        CBORCase::Tagged(..) => "tagged",
+        CBORCase::Simple(_) => "simple",
+    }
+}
+
+/// The first four bytes of the node's digest, which stays the same however
+/// the node is encrypted, elided, or compressed.
+fn short_digest(envelope: &Envelope) -> String {
+    let digest = envelope.digest();
+    let hex: String = digest.data()[..4]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    format!("[{hex}]")
+}
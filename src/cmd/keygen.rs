@@ -0,0 +1,179 @@
+use std::{fs, str::FromStr};
+
+use anyhow::{Context, Result, bail};
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use clap::Parser;
+use frost_ed25519::{self as frost, rand_core::OsRng};
+
+use crate::{
+    cmd::{
+        common::{
+            GroupStatePaths, load_registry, signing_key_from_verifying,
+            write_atomically,
+        },
+        dkg::common::resolve_participants,
+        registry::participants_file_path,
+    },
+    dkg::{DealtShare, IdentifierScheme},
+    registry::{GroupParticipant, GroupProvenance, GroupRecord},
+};
+
+/// Generate a group with a trusted dealer instead of DKG.
+///
+/// The registry owner deals every participant's key share in one step and
+/// becomes the group's coordinator. The dealer sees all shares, so this is
+/// meant for testing and single-admin setups; the group is recorded with
+/// trusted-dealer provenance.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Minimum signers required; defaults to participant count
+    #[arg(long = "min-signers", value_name = "N")]
+    min_signers: Option<usize>,
+
+    /// Charter statement for the group
+    #[arg(long = "charter", value_name = "STRING", default_value = "")]
+    charter: String,
+
+    /// How participants derive FROST identifiers: `index` or `hashed`
+    #[arg(
+        long = "identifier-scheme",
+        value_name = "SCHEME",
+        default_value = "index",
+        value_parser = IdentifierScheme::from_str
+    )]
+    identifier_scheme: IdentifierScheme,
+
+    /// Participants to deal shares to, by pet name or ur:xid identifier
+    #[arg(required = true, value_name = "PARTICIPANT")]
+    participants: Vec<String>,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let registry_path = participants_file_path(self.registry)?;
+        let mut registry =
+            load_registry(&registry_path).with_context(|| {
+                format!(
+                    "Failed to load registry at {}",
+                    registry_path.display()
+                )
+            })?;
+        let owner = registry
+            .owner()
+            .context("Registry owner is required to deal key shares")?
+            .clone();
+        let dealer_keys = owner
+            .xid_document()
+            .inception_private_keys()
+            .context("Owner XID document has no signing keys")?;
+
+        let resolved = resolve_participants(&registry, &self.participants)?;
+        if resolved.iter().any(|(xid, _)| *xid == owner.xid()) {
+            bail!("The dealer cannot also be a participant");
+        }
+        let participant_count = resolved.len();
+        if participant_count < 2 {
+            bail!("At least two participants are required");
+        }
+        let max_signers = u16::try_from(participant_count)
+            .context("Too many participants for FROST identifiers")?;
+        let min_signers = self.min_signers.unwrap_or(participant_count);
+        if min_signers < 2 {
+            bail!("--min-signers must be at least 2");
+        }
+        if min_signers > participant_count {
+            bail!("--min-signers cannot exceed participant count");
+        }
+
+        let mut group_record = GroupRecord::new(
+            self.charter,
+            min_signers,
+            GroupParticipant::new(owner.xid()),
+            resolved
+                .iter()
+                .map(|(xid, _)| GroupParticipant::new(*xid))
+                .collect(),
+        );
+        group_record.set_identifier_scheme(self.identifier_scheme);
+        group_record.set_provenance(GroupProvenance::TrustedDealer);
+        let participants = group_record.identifier_order();
+        let identifiers = group_record.identifiers()?;
+        let identifier_list: Vec<frost::Identifier> =
+            participants.iter().map(|xid| identifiers[xid]).collect();
+
+        let (secret_shares, public_key_package) =
+            frost::keys::generate_with_dealer(
+                max_signers,
+                min_signers as u16,
+                frost::keys::IdentifierList::Custom(&identifier_list),
+                OsRng,
+            )
+            .context("Failed to generate key shares")?;
+        group_record.set_verifying_key(signing_key_from_verifying(
+            public_key_package.verifying_key(),
+        )?);
+
+        let group_id = ARID::new();
+        let paths = GroupStatePaths::new(&registry_path);
+        fs::create_dir_all(paths.dealt_shares_dir(&group_id))
+            .context("Failed to create group state directory")?;
+        let public_key_package_path = paths.public_key_package_path(&group_id);
+        write_atomically(
+            &public_key_package_path,
+            serde_json::to_vec_pretty(&public_key_package)?,
+        )
+        .with_context(|| {
+            format!("Failed to write {}", public_key_package_path.display())
+        })?;
+
+        let mut written = Vec::new();
+        for (xid, record) in &resolved {
+            let secret_share = secret_shares
+                .get(&identifiers[xid])
+                .context("Dealer produced no share for a participant")?;
+            let share = DealtShare {
+                group_id,
+                charter: group_record.charter().to_owned(),
+                min_signers,
+                coordinator: owner.xid(),
+                participants: participants.clone(),
+                identifier_scheme: self.identifier_scheme,
+                key_package: frost::keys::KeyPackage::try_from(
+                    secret_share.clone(),
+                )?,
+                public_key_package: public_key_package.clone(),
+            };
+            let sealed = share.seal(dealer_keys, record.xid_document())?;
+            let path = paths.dealt_share_path(&group_id, xid);
+            write_atomically(&path, sealed.ur_string()).with_context(|| {
+                format!("Failed to write {}", path.display())
+            })?;
+            let name = record
+                .pet_name()
+                .map(str::to_owned)
+                .unwrap_or_else(|| xid.ur_string());
+            written.push((name, path));
+        }
+
+        registry.record_group(group_id, group_record)?;
+        registry.save(&registry_path)?;
+
+        eprintln!(
+            "Warning: this group was generated by a trusted dealer. The \
+             dealer saw every key share, so the group key is only as \
+             trustworthy as this registry's owner."
+        );
+        for (name, path) in &written {
+            eprintln!("Key share for {name}: {}", path.display());
+        }
+        println!("{}", group_id.ur_string());
+
+        Ok(())
+    }
+}
@@ -1,16 +1,35 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::{
+    path::PathBuf,
+    str::FromStr,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 
+use crate::registry::RegistryFormat;
+
 pub mod busy;
 pub mod check;
 pub mod common;
 pub mod dkg;
+pub mod doctor;
+pub mod heartbeat;
+pub mod inspect;
+pub mod keygen;
 pub mod parallel;
 pub mod registry;
+pub mod report;
+pub mod schema;
 pub mod sign;
 pub mod storage;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+pub mod version;
 
 /// FROST command-line interface definition.
 #[derive(Debug, Parser)]
@@ -21,6 +40,99 @@ pub struct Cli {
     #[arg(long, global = true)]
     verbose: bool,
 
+    /// Compress request and response payloads before sealing them (compressed
+    /// payloads are always decompressed on receipt)
+    #[arg(long, global = true)]
+    compress: bool,
+
+    /// Disable colors and emoji in progress output (also enabled by setting
+    /// the NO_COLOR environment variable)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Milliseconds between progress spinner frames (20-1000)
+    #[arg(
+        long,
+        global = true,
+        value_name = "MS",
+        default_value_t = DEFAULT_TICK_INTERVAL_MS,
+        value_parser = clap::value_parser!(u64).range(
+            MIN_TICK_INTERVAL_MS..=MAX_TICK_INTERVAL_MS
+        )
+    )]
+    tick_interval_ms: u64,
+
+    /// Never animate progress spinners; lines only change when a status or
+    /// countdown changes
+    #[arg(long, global = true)]
+    no_animation: bool,
+
+    /// Milliseconds between Hubert polls while waiting for a message with
+    /// `--timeout` (100-60000). The overall wait is still bounded by
+    /// `--timeout`, so the number of polls is roughly timeout / interval
+    #[arg(
+        long,
+        global = true,
+        value_name = "MS",
+        default_value_t = DEFAULT_POLL_INTERVAL_MS,
+        value_parser = clap::value_parser!(u64).range(
+            MIN_POLL_INTERVAL_MS..=MAX_POLL_INTERVAL_MS
+        )
+    )]
+    poll_interval_ms: u64,
+
+    /// Refuse to store any envelope larger than this many bytes (after
+    /// `--compress`), failing before the upload instead of with a backend
+    /// error
+    #[arg(long, global = true, value_name = "BYTES")]
+    max_payload_bytes: Option<u64>,
+
+    /// Answer yes to every confirmation prompt without reading stdin
+    #[arg(long, global = true, visible_alias = "assume-yes")]
+    yes: bool,
+
+    /// Fail instead of prompting, so scripts never wait on stdin (takes
+    /// precedence over --yes)
+    #[arg(long, global = true)]
+    no_input: bool,
+
+    /// Registry encoding, `json` or `cbor`. Selects `registry.cbor` as the
+    /// default registry file; an explicit `--registry` path must use the
+    /// matching extension. Without this flag the extension decides
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        value_parser = RegistryFormat::from_str
+    )]
+    registry_format: Option<RegistryFormat>,
+
+    /// Seconds a received message's issuance date may lie in the future
+    /// before it is reported as probable clock skew
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECONDS",
+        default_value_t = DEFAULT_CLOCK_SKEW_TOLERANCE_SECS
+    )]
+    clock_skew_tolerance: u64,
+
+    /// Require each received message to come from the exact coordinator or
+    /// participant its protocol step expects, sealed with the key in their
+    /// registered XID document; invites then need --sender
+    #[arg(long, global = true)]
+    strict_sender: bool,
+
+    /// File whose first line is the passphrase for an encrypted registry
+    /// (otherwise FROST_PASSPHRASE, then an interactive prompt)
+    #[arg(long, global = true, value_name = "PATH")]
+    passphrase_file: Option<PathBuf>,
+
+    /// Append a newline-delimited JSON event to this file as each
+    /// participant's parallel fetch or send starts and finishes
+    #[arg(long, global = true, value_name = "PATH")]
+    progress_file: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -31,20 +143,59 @@ enum Commands {
     Registry(registry::CommandArgs),
     /// Check Hubert storage backend availability
     Check(check::CommandArgs),
+    /// Diagnose the registry, storage, and local group state
+    Doctor(doctor::CommandArgs),
     /// Distributed key generation operations
     Dkg(dkg::CommandArgs),
     /// Threshold signing operations
     Sign(sign::CommandArgs),
+    /// Participant presence heartbeats
+    Heartbeat(heartbeat::CommandArgs),
+    /// Generate a group's key shares with a trusted dealer (testing and
+    /// single-admin setups)
+    Keygen(keygen::CommandArgs),
+    /// Show version and build capability information
+    Version(version::CommandArgs),
+    /// Show the structure of an envelope for debugging
+    Inspect(inspect::CommandArgs),
+    /// Describe the JSON state files kept under group-state/
+    #[command(hide = true)]
+    Schema(schema::CommandArgs),
+    /// Maintain integration test fixtures
+    #[cfg(feature = "test-support")]
+    #[command(hide = true)]
+    TestSupport(test_support::CommandArgs),
 }
 
 impl Cli {
     pub fn exec(self) -> Result<()> {
         set_verbose(self.verbose);
+        set_compress(self.compress);
+        set_no_color(self.no_color);
+        set_tick_interval_ms(self.tick_interval_ms);
+        set_no_animation(self.no_animation);
+        set_poll_interval_ms(self.poll_interval_ms);
+        set_max_payload_bytes(self.max_payload_bytes);
+        set_assume_yes(self.yes);
+        set_no_input(self.no_input);
+        set_registry_format(self.registry_format);
+        set_clock_skew_tolerance_secs(self.clock_skew_tolerance);
+        set_strict_sender(self.strict_sender);
+        set_passphrase_file(self.passphrase_file);
+        set_progress_file(self.progress_file);
         match self.command {
             Commands::Registry(args) => args.exec(),
             Commands::Check(args) => args.exec(),
+            Commands::Doctor(args) => args.exec(),
             Commands::Dkg(args) => args.exec(),
             Commands::Sign(args) => args.exec(),
+            Commands::Heartbeat(args) => args.exec(),
+            Commands::Keygen(args) => args.exec(),
+            Commands::Version(args) => args.exec(),
+            Commands::Inspect(args) => args.exec(),
+            Commands::Schema(args) => args.exec(),
+            #[cfg(feature = "test-support")]
+            Commands::TestSupport(args) => args.exec(),
         }
     }
 }
@@ -54,3 +205,150 @@ static VERBOSE: AtomicBool = AtomicBool::new(false);
 pub fn set_verbose(value: bool) { VERBOSE.store(value, Ordering::Relaxed); }
 
 pub fn is_verbose() -> bool { VERBOSE.load(Ordering::Relaxed) }
+
+static COMPRESS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_compress(value: bool) { COMPRESS.store(value, Ordering::Relaxed); }
+
+pub fn is_compress() -> bool { COMPRESS.load(Ordering::Relaxed) }
+
+static NO_COLOR: AtomicBool = AtomicBool::new(false);
+
+pub fn set_no_color(value: bool) { NO_COLOR.store(value, Ordering::Relaxed); }
+
+/// Returns true if `--no-color` was given or `NO_COLOR` is set to a non-empty
+/// value (see <https://no-color.org>).
+pub fn is_no_color() -> bool {
+    NO_COLOR.load(Ordering::Relaxed)
+        || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+pub fn set_assume_yes(value: bool) {
+    ASSUME_YES.store(value, Ordering::Relaxed);
+}
+
+pub fn is_assume_yes() -> bool { ASSUME_YES.load(Ordering::Relaxed) }
+
+static NO_INPUT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_no_input(value: bool) { NO_INPUT.store(value, Ordering::Relaxed); }
+
+pub fn is_no_input() -> bool { NO_INPUT.load(Ordering::Relaxed) }
+
+static STRICT_SENDER: AtomicBool = AtomicBool::new(false);
+
+pub fn set_strict_sender(value: bool) {
+    STRICT_SENDER.store(value, Ordering::Relaxed);
+}
+
+pub fn is_strict_sender() -> bool { STRICT_SENDER.load(Ordering::Relaxed) }
+
+/// Zero means no `--registry-format` was given.
+static REGISTRY_FORMAT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_registry_format(value: Option<RegistryFormat>) {
+    let encoded = match value {
+        None => 0,
+        Some(RegistryFormat::Json) => 1,
+        Some(RegistryFormat::Cbor) => 2,
+    };
+    REGISTRY_FORMAT.store(encoded, Ordering::Relaxed);
+}
+
+pub fn registry_format() -> Option<RegistryFormat> {
+    match REGISTRY_FORMAT.load(Ordering::Relaxed) {
+        1 => Some(RegistryFormat::Json),
+        2 => Some(RegistryFormat::Cbor),
+        _ => None,
+    }
+}
+
+pub const DEFAULT_CLOCK_SKEW_TOLERANCE_SECS: u64 = 300;
+
+static CLOCK_SKEW_TOLERANCE_SECS: AtomicU64 =
+    AtomicU64::new(DEFAULT_CLOCK_SKEW_TOLERANCE_SECS);
+
+pub fn set_clock_skew_tolerance_secs(value: u64) {
+    CLOCK_SKEW_TOLERANCE_SECS.store(value, Ordering::Relaxed);
+}
+
+pub fn clock_skew_tolerance() -> Duration {
+    Duration::from_secs(CLOCK_SKEW_TOLERANCE_SECS.load(Ordering::Relaxed))
+}
+
+pub const DEFAULT_TICK_INTERVAL_MS: u64 = 100;
+pub const MIN_TICK_INTERVAL_MS: u64 = 20;
+pub const MAX_TICK_INTERVAL_MS: u64 = 1000;
+
+static TICK_INTERVAL_MS: AtomicU64 = AtomicU64::new(DEFAULT_TICK_INTERVAL_MS);
+
+pub fn set_tick_interval_ms(value: u64) {
+    TICK_INTERVAL_MS.store(value, Ordering::Relaxed);
+}
+
+static NO_ANIMATION: AtomicBool = AtomicBool::new(false);
+
+pub fn set_no_animation(value: bool) {
+    NO_ANIMATION.store(value, Ordering::Relaxed);
+}
+
+/// Interval between spinner frames, or `None` under `--no-animation`.
+pub fn spinner_tick() -> Option<Duration> {
+    if NO_ANIMATION.load(Ordering::Relaxed) {
+        None
+    } else {
+        Some(Duration::from_millis(
+            TICK_INTERVAL_MS.load(Ordering::Relaxed),
+        ))
+    }
+}
+
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 1000;
+pub const MIN_POLL_INTERVAL_MS: u64 = 100;
+pub const MAX_POLL_INTERVAL_MS: u64 = 60_000;
+
+static POLL_INTERVAL_MS: AtomicU64 = AtomicU64::new(DEFAULT_POLL_INTERVAL_MS);
+
+pub fn set_poll_interval_ms(value: u64) {
+    POLL_INTERVAL_MS.store(value, Ordering::Relaxed);
+}
+
+pub fn poll_interval() -> Duration {
+    Duration::from_millis(POLL_INTERVAL_MS.load(Ordering::Relaxed))
+}
+
+/// Zero means no limit.
+static MAX_PAYLOAD_BYTES: AtomicU64 = AtomicU64::new(0);
+
+pub fn set_max_payload_bytes(value: Option<u64>) {
+    MAX_PAYLOAD_BYTES.store(value.unwrap_or(0), Ordering::Relaxed);
+}
+
+pub fn max_payload_bytes() -> Option<u64> {
+    match MAX_PAYLOAD_BYTES.load(Ordering::Relaxed) {
+        0 => None,
+        limit => Some(limit),
+    }
+}
+
+static PASSPHRASE_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+pub fn set_passphrase_file(value: Option<PathBuf>) {
+    *PASSPHRASE_FILE.lock().unwrap() = value;
+}
+
+pub fn passphrase_file() -> Option<PathBuf> {
+    PASSPHRASE_FILE.lock().unwrap().clone()
+}
+
+static PROGRESS_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+pub fn set_progress_file(value: Option<PathBuf>) {
+    *PROGRESS_FILE.lock().unwrap() = value;
+}
+
+pub fn progress_file() -> Option<PathBuf> {
+    PROGRESS_FILE.lock().unwrap().clone()
+}
@@ -15,24 +15,43 @@
 //! - ⬆️ prefix for put (upload) operations with count-up timer
 //! - 🔄 animated spinner while in progress
 //! - ✅ on success, ❌ on failure
+//!
+//! With `--no-color` or `NO_COLOR`, the emoji are replaced by ASCII markers
+//! (`[get]`/`[put]`, `[..]`, `[ok]`, `[x]`) and spinner colors are dropped.
+//!
+//! # Progress Events
+//!
+//! With `--progress-file`, every participant's transitions are also appended
+//! to that file as newline-delimited JSON (see [`ProgressEvents`]), so a
+//! supervising process can render its own progress.
 
 use std::{
     collections::HashMap,
-    io::IsTerminal,
+    fmt,
+    fs::{File, OpenOptions},
+    io::{IsTerminal, Write},
+    path::Path,
     sync::{
         Arc,
-        atomic::{AtomicBool, AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
     },
     time::Duration,
 };
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use bc_components::{ARID, XID};
-use bc_envelope::Envelope;
+use bc_envelope::{Envelope, prelude::UREncodable};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
-use tokio::{sync::Mutex, time::Instant};
+use serde_json::json;
+use tokio::{
+    sync::{Mutex, Notify, Semaphore},
+    time::Instant,
+};
 
-use crate::cmd::storage::StorageClient;
+use crate::cmd::{
+    common::sort_for_display, is_no_color, progress_file, spinner_tick,
+    storage::StorageClient,
+};
 
 /// Status of a participant's response fetch.
 #[derive(Debug, Clone)]
@@ -49,17 +68,78 @@ pub enum FetchStatus {
     Timeout,
 }
 
+/// Why a participant's response could not be collected.
+///
+/// Fetch helpers and `parallel_fetch` validators raise these (through
+/// `anyhow`) so collection can tell a participant who has not answered yet
+/// from one whose answer is unusable. Errors that are not a `FetchError` are
+/// classified as validation failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// Nothing is stored at the response ARID yet
+    NotYetAvailable,
+    /// Participant explicitly rejected the request
+    Rejected(String),
+    /// Stored envelope could not be decrypted or decoded
+    DecodeError(String),
+    /// Response decoded but did not match the request
+    ValidationError(String),
+}
+
+impl FetchError {
+    /// Wrap a decryption or decoding failure.
+    pub fn decode(err: impl fmt::Display) -> Self {
+        Self::DecodeError(err.to_string())
+    }
+
+    /// Whether waiting longer could still produce a usable response.
+    pub fn is_retryable(&self) -> bool { matches!(self, Self::NotYetAvailable) }
+
+    /// Classify an error returned by a fetch helper or validator.
+    pub fn classify(err: &anyhow::Error) -> Self {
+        match err.downcast_ref::<FetchError>() {
+            Some(fetch_error) => fetch_error.clone(),
+            None => Self::ValidationError(err.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotYetAvailable => {
+                write!(f, "Response not yet available in Hubert storage")
+            }
+            Self::Rejected(reason) => write!(f, "{reason}"),
+            Self::DecodeError(reason) => {
+                write!(f, "Failed to decode response: {reason}")
+            }
+            Self::ValidationError(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
 /// Configuration for parallel fetch operations.
 #[derive(Debug, Clone)]
 pub struct ParallelFetchConfig {
     /// Maximum time to wait for all responses (in seconds)
     pub timeout_seconds: Option<u64>,
+    /// Stop as soon as this many responses validate, cancelling the fetches
+    /// still outstanding (`None` waits for every participant)
+    pub min_success: Option<usize>,
+    /// Per-participant overrides of `timeout_seconds`, for participants who
+    /// are known to respond slowly
+    pub participant_timeouts: HashMap<XID, u64>,
 }
 
 impl Default for ParallelFetchConfig {
     fn default() -> Self {
         Self {
             timeout_seconds: Some(600), // 10 minutes default
+            min_success: None,
+            participant_timeouts: HashMap::new(),
         }
     }
 }
@@ -67,7 +147,26 @@ impl Default for ParallelFetchConfig {
 impl ParallelFetchConfig {
     /// Create a new config with the specified timeout.
     pub fn with_timeout(timeout_seconds: Option<u64>) -> Self {
-        Self { timeout_seconds }
+        Self {
+            timeout_seconds,
+            min_success: None,
+            participant_timeouts: HashMap::new(),
+        }
+    }
+
+    /// Stop collecting once `min_success` responses have validated.
+    pub fn with_min_success(mut self, min_success: Option<usize>) -> Self {
+        self.min_success = min_success;
+        self
+    }
+
+    /// Wait longer than the overall timeout for the given participants.
+    pub fn with_participant_timeouts(
+        mut self,
+        participant_timeouts: HashMap<XID, u64>,
+    ) -> Self {
+        self.participant_timeouts = participant_timeouts;
+        self
     }
 }
 
@@ -82,9 +181,49 @@ pub struct CollectionResult<T> {
     pub errors: Vec<(XID, String)>,
     /// Participants who timed out
     pub timeouts: Vec<XID>,
+    /// Participants whose fetch was cancelled because `min_success` responses
+    /// had already arrived
+    pub cancelled: Vec<XID>,
+    /// How long each participant's fetch took, whatever its outcome
+    pub durations: HashMap<XID, Duration>,
+}
+
+impl<T> Default for CollectionResult<T> {
+    fn default() -> Self { Self::new() }
 }
 
 impl<T> CollectionResult<T> {
+    /// An empty result, for sequential collection to fill with
+    /// [`record`](Self::record).
+    pub fn new() -> Self {
+        Self {
+            successes: Vec::new(),
+            rejections: Vec::new(),
+            errors: Vec::new(),
+            timeouts: Vec::new(),
+            cancelled: Vec::new(),
+            durations: HashMap::new(),
+        }
+    }
+
+    /// File one participant's fetch outcome the way parallel collection
+    /// does: a [`FetchError::Rejected`] is a rejection, a response that is
+    /// [`FetchError::NotYetAvailable`] is a timeout, and anything else is an
+    /// error.
+    pub fn record(&mut self, xid: XID, result: Result<T>, duration: Duration) {
+        self.durations.insert(xid, duration);
+        match result {
+            Ok(value) => self.successes.push((xid, value)),
+            Err(err) => match FetchError::classify(&err) {
+                FetchError::Rejected(reason) => {
+                    self.rejections.push((xid, reason))
+                }
+                FetchError::NotYetAvailable => self.timeouts.push(xid),
+                _ => self.errors.push((xid, err.to_string())),
+            },
+        }
+    }
+
     /// Check if enough responses were received to proceed.
     pub fn can_proceed(&self, min_required: usize) -> bool {
         self.successes.len() >= min_required
@@ -96,6 +235,7 @@ impl<T> CollectionResult<T> {
             + self.rejections.len()
             + self.errors.len()
             + self.timeouts.len()
+            + self.cancelled.len()
     }
 
     /// Check if all responses succeeded
@@ -103,6 +243,7 @@ impl<T> CollectionResult<T> {
         self.rejections.is_empty()
             && self.errors.is_empty()
             && self.timeouts.is_empty()
+            && self.cancelled.is_empty()
     }
 }
 
@@ -116,22 +257,52 @@ pub enum Direction {
 }
 
 impl Direction {
-    /// Get the emoji prefix for this direction.
-    pub fn emoji(&self) -> &'static str {
+    /// Name of this direction in progress events.
+    pub fn name(&self) -> &'static str {
         match self {
-            Direction::Get => "⬇️",
-            Direction::Put => "⬆️",
+            Direction::Get => "get",
+            Direction::Put => "put",
+        }
+    }
+
+    /// Get the emoji prefix for this direction (ASCII when color is
+    /// disabled).
+    pub fn emoji(&self) -> &'static str {
+        match (self, is_no_color()) {
+            (Direction::Get, false) => "⬇️",
+            (Direction::Put, false) => "⬆️",
+            (Direction::Get, true) => "[get]",
+            (Direction::Put, true) => "[put]",
         }
     }
 }
 
+/// Spinner placeholder for progress templates.
+pub fn spinner_template() -> &'static str {
+    if is_no_color() {
+        "[..]"
+    } else {
+        "{spinner:.yellow}"
+    }
+}
+
+/// Marker shown when an operation succeeds.
+pub fn success_marker() -> &'static str {
+    if is_no_color() { "[ok]" } else { "✅" }
+}
+
+/// Marker shown when an operation fails or times out.
+pub fn failure_marker() -> &'static str {
+    if is_no_color() { "[x]" } else { "❌" }
+}
+
 /// Progress display for parallel operations.
 pub struct ProgressDisplay {
-    #[allow(dead_code)]
     multi: MultiProgress,
     bars: HashMap<XID, (ProgressBar, String)>,
     start_time: Instant,
     timeout_seconds: u64,
+    participant_timeouts: HashMap<XID, u64>,
     direction: Direction,
     stop_flag: Arc<AtomicBool>,
     elapsed_tracker: Arc<AtomicU64>,
@@ -143,23 +314,44 @@ impl ProgressDisplay {
         participants: &[(XID, String)],
         timeout_seconds: u64,
     ) -> Self {
-        Self::new_internal(participants, timeout_seconds, Direction::Get)
+        Self::new_internal(
+            participants,
+            timeout_seconds,
+            HashMap::new(),
+            Direction::Get,
+        )
+    }
+
+    /// Create a get progress display where some participants count down from
+    /// their own timeout instead of `timeout_seconds`.
+    pub fn new_get_with_timeouts(
+        participants: &[(XID, String)],
+        timeout_seconds: u64,
+        participant_timeouts: HashMap<XID, u64>,
+    ) -> Self {
+        Self::new_internal(
+            participants,
+            timeout_seconds,
+            participant_timeouts,
+            Direction::Get,
+        )
     }
 
     /// Create a new progress display for put operations (with count-up).
     pub fn new_put(participants: &[(XID, String)]) -> Self {
-        Self::new_internal(participants, 60, Direction::Put)
+        Self::new_internal(participants, 60, HashMap::new(), Direction::Put)
     }
 
     /// Create a new progress display for the given participants.
     pub fn new(participants: &[(XID, String)], timeout_seconds: u64) -> Self {
         // Default to Get for backward compatibility
-        Self::new_internal(participants, timeout_seconds, Direction::Get)
+        Self::new_get(participants, timeout_seconds)
     }
 
     fn new_internal(
         participants: &[(XID, String)],
         timeout_seconds: u64,
+        participant_timeouts: HashMap<XID, u64>,
         direction: Direction,
     ) -> Self {
         let multi = MultiProgress::new();
@@ -167,22 +359,30 @@ impl ProgressDisplay {
         let elapsed_tracker = Arc::new(AtomicU64::new(0));
         let start_time = Instant::now();
 
+        let mut ordered = participants.to_vec();
+        sort_for_display(&mut ordered, |(xid, name)| (name.clone(), *xid));
+
         let mut bars = HashMap::new();
-        for (xid, name) in participants {
+        for (xid, name) in &ordered {
             let bar = multi.add(ProgressBar::new_spinner());
             let template = match direction {
                 Direction::Get => {
                     format!(
-                        "{}  {{spinner:.yellow}} {}... -{}s",
+                        "{}  {} {}... -{}s",
                         direction.emoji(),
+                        spinner_template(),
                         name,
-                        timeout_seconds
+                        participant_timeouts
+                            .get(xid)
+                            .copied()
+                            .unwrap_or(timeout_seconds)
                     )
                 }
                 Direction::Put => {
                     format!(
-                        "{}  {{spinner:.yellow}} {}... +0s",
+                        "{}  {} {}... +0s",
                         direction.emoji(),
+                        spinner_template(),
                         name
                     )
                 }
@@ -192,7 +392,9 @@ impl ProgressDisplay {
                     .template(&template)
                     .expect("valid template"),
             );
-            bar.enable_steady_tick(Duration::from_millis(100));
+            if let Some(interval) = spinner_tick() {
+                bar.enable_steady_tick(interval);
+            }
             bars.insert(*xid, (bar, name.clone()));
         }
 
@@ -201,6 +403,7 @@ impl ProgressDisplay {
             bars,
             start_time,
             timeout_seconds,
+            participant_timeouts,
             direction,
             stop_flag,
             elapsed_tracker,
@@ -212,9 +415,15 @@ impl ProgressDisplay {
         let bars: Vec<_> = self
             .bars
             .iter()
-            .map(|(xid, (bar, name))| (*xid, bar.clone(), name.clone()))
+            .map(|(xid, (bar, name))| {
+                let timeout = self
+                    .participant_timeouts
+                    .get(xid)
+                    .copied()
+                    .unwrap_or(self.timeout_seconds);
+                (timeout, bar.clone(), name.clone())
+            })
             .collect();
-        let timeout = self.timeout_seconds;
         let direction = self.direction;
         let stop_flag = Arc::clone(&self.stop_flag);
         let elapsed_tracker = Arc::clone(&self.elapsed_tracker);
@@ -230,22 +439,24 @@ impl ProgressDisplay {
                 elapsed_tracker.store(elapsed, Ordering::Relaxed);
 
                 // Update individual bars that are still pending
-                for (_xid, bar, name) in &bars {
+                for (timeout, bar, name) in &bars {
                     if !bar.is_finished() {
                         let template = match direction {
                             Direction::Get => {
                                 let remaining = timeout.saturating_sub(elapsed);
                                 format!(
-                                    "{}  {{spinner:.yellow}} {}... -{}s",
+                                    "{}  {} {}... -{}s",
                                     direction.emoji(),
+                                    spinner_template(),
                                     name,
                                     remaining
                                 )
                             }
                             Direction::Put => {
                                 format!(
-                                    "{}  {{spinner:.yellow}} {}... +{}s",
+                                    "{}  {} {}... +{}s",
                                     direction.emoji(),
+                                    spinner_template(),
                                     name,
                                     elapsed
                                 )
@@ -271,8 +482,9 @@ impl ProgressDisplay {
             let elapsed = self.elapsed_seconds();
             // Both get and put show elapsed time on success
             let template = format!(
-                "{}  ✅ {}: {}s",
+                "{}  {} {}: {}s",
                 self.direction.emoji(),
+                success_marker(),
                 name,
                 elapsed
             );
@@ -288,8 +500,13 @@ impl ProgressDisplay {
     /// Mark a participant as failed with an error message.
     pub fn mark_error(&self, xid: &XID, error: &str) {
         if let Some((bar, name)) = self.bars.get(xid) {
-            let template =
-                format!("{}  ❌ {}: {}", self.direction.emoji(), name, error);
+            let template = format!(
+                "{}  {} {}: {}",
+                self.direction.emoji(),
+                failure_marker(),
+                name,
+                error
+            );
             bar.set_style(
                 ProgressStyle::default_spinner()
                     .template(&template)
@@ -301,9 +518,27 @@ impl ProgressDisplay {
 
     /// Mark a participant as timed out.
     pub fn mark_timeout(&self, xid: &XID) {
+        if let Some((bar, name)) = self.bars.get(xid) {
+            let template = format!(
+                "{}  {} {}: Timeout",
+                self.direction.emoji(),
+                failure_marker(),
+                name
+            );
+            bar.set_style(
+                ProgressStyle::default_spinner()
+                    .template(&template)
+                    .expect("valid template"),
+            );
+            bar.finish();
+        }
+    }
+
+    /// Mark a participant whose fetch was cancelled as no longer needed.
+    pub fn mark_cancelled(&self, xid: &XID) {
         if let Some((bar, name)) = self.bars.get(xid) {
             let template =
-                format!("{}  ❌ {}: Timeout", self.direction.emoji(), name);
+                format!("{}  {}: Not needed", self.direction.emoji(), name);
             bar.set_style(
                 ProgressStyle::default_spinner()
                     .template(&template)
@@ -315,12 +550,22 @@ impl ProgressDisplay {
 
     /// Finish all progress bars.
     pub fn finish(&self) {
+        // Nothing useful can be done if stderr is gone
+        let _ = self.finish_to(&mut std::io::stderr());
+    }
+
+    /// Finish all progress bars, writing the line break that follows drawn
+    /// bars to `out`. Nothing is written when no bars were drawn, because
+    /// there were no participants or the bars were hidden.
+    pub fn finish_to(&self, out: &mut impl Write) -> std::io::Result<()> {
         self.stop_flag.store(true, Ordering::Relaxed);
         for (bar, _) in self.bars.values() {
             bar.finish();
         }
-        // Ensure we're on a new line after all progress bars finish
-        eprintln!();
+        if !self.bars.is_empty() && !self.multi.is_hidden() {
+            writeln!(out)?;
+        }
+        Ok(())
     }
 
     /// Clear all progress bars without marking complete.
@@ -371,26 +616,163 @@ impl StreamingOutput {
     pub fn success(&self, name: &str, elapsed_secs: Option<u64>) {
         // Both get and put show elapsed time if available
         if let Some(secs) = elapsed_secs {
-            eprintln!("{}  ✅ {}: {}s", self.direction.emoji(), name, secs);
+            eprintln!(
+                "{}  {} {}: {}s",
+                self.direction.emoji(),
+                success_marker(),
+                name,
+                secs
+            );
         } else {
-            eprintln!("{}  ✅ {}", self.direction.emoji(), name);
+            eprintln!(
+                "{}  {} {}",
+                self.direction.emoji(),
+                success_marker(),
+                name
+            );
         }
     }
 
     /// Print an error message.
     pub fn error(&self, name: &str, error: &str) {
-        eprintln!("{}  ❌ {}: {}", self.direction.emoji(), name, error);
+        eprintln!(
+            "{}  {} {}: {}",
+            self.direction.emoji(),
+            failure_marker(),
+            name,
+            error
+        );
     }
 
     /// Print a timeout message.
     pub fn timeout(&self, name: &str) {
-        eprintln!("{}  ❌ {}: Timeout", self.direction.emoji(), name);
+        eprintln!(
+            "{}  {} {}: Timeout",
+            self.direction.emoji(),
+            failure_marker(),
+            name
+        );
+    }
+
+    /// Print that a fetch was cancelled as no longer needed.
+    pub fn cancelled(&self, name: &str) {
+        eprintln!("{}  {}: Not needed", self.direction.emoji(), name);
+    }
+}
+
+/// Newline-delimited JSON progress events for `--progress-file`.
+///
+/// Each line is one object with `direction` (`get` or `put`), the
+/// participant's `xid` and `name`, its `status`, and `elapsed_ms` since the
+/// operation started. Every participant first appears as `pending`, then
+/// moves to `success`, `error` (with an `error` message), `timeout`, or
+/// `cancelled`. Lines are appended and written whole, so the file may be a
+/// named pipe another process is reading.
+pub struct ProgressEvents {
+    direction: Direction,
+    file: std::sync::Mutex<File>,
+    start_time: Instant,
+}
+
+impl ProgressEvents {
+    /// Open `path` for appending events about operations in `direction`.
+    pub fn open(path: &Path, direction: Direction) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| {
+                format!("Failed to open progress file {}", path.display())
+            })?;
+        Ok(Self {
+            direction,
+            file: std::sync::Mutex::new(file),
+            start_time: Instant::now(),
+        })
+    }
+
+    /// Open the file named by `--progress-file`, if one was given.
+    ///
+    /// Progress events never affect the outcome of an operation, so a file
+    /// that cannot be opened is reported and otherwise ignored.
+    pub fn from_flag(direction: Direction) -> Option<Self> {
+        let path = progress_file()?;
+        match Self::open(&path, direction) {
+            Ok(events) => Some(events),
+            Err(err) => {
+                eprintln!("Warning: {err:#}");
+                None
+            }
+        }
+    }
+
+    /// Record that a participant's operation has started.
+    pub fn pending(&self, xid: &XID, name: &str) {
+        self.emit(xid, name, "pending", None);
+    }
+
+    /// Record a participant's success.
+    pub fn success(&self, xid: &XID, name: &str) {
+        self.emit(xid, name, "success", None);
+    }
+
+    /// Record a participant's failure with an error message.
+    pub fn error(&self, xid: &XID, name: &str, error: &str) {
+        self.emit(xid, name, "error", Some(error));
+    }
+
+    /// Record that a participant timed out.
+    pub fn timeout(&self, xid: &XID, name: &str) {
+        self.emit(xid, name, "timeout", None);
+    }
+
+    /// Record that a participant's fetch was cancelled as no longer needed.
+    pub fn cancelled(&self, xid: &XID, name: &str) {
+        self.emit(xid, name, "cancelled", None);
+    }
+
+    fn emit(&self, xid: &XID, name: &str, status: &str, error: Option<&str>) {
+        let mut event = json!({
+            "direction": self.direction.name(),
+            "xid": xid.ur_string(),
+            "name": name,
+            "status": status,
+            "elapsed_ms": self.start_time.elapsed().as_millis() as u64,
+        });
+        if let Some(error) = error {
+            event["error"] = json!(error);
+        }
+        let mut line = event.to_string();
+        line.push('\n');
+        // A write failure must not interrupt the operation being reported
+        let mut file = self.file.lock().unwrap();
+        let _ = file.write_all(line.as_bytes());
     }
 }
 
 /// Check if stderr is an interactive terminal.
 pub fn is_interactive_terminal() -> bool { std::io::stderr().is_terminal() }
 
+/// Whether a fetch error only means the response has not arrived yet.
+fn is_retryable(err: &anyhow::Error) -> bool {
+    FetchError::classify(err).is_retryable()
+}
+
+/// Counts a fetch task as finished when dropped, so a task that panics still
+/// releases [`parallel_fetch`] instead of leaving it waiting forever.
+struct TaskFinished {
+    remaining: Arc<AtomicUsize>,
+    done: Arc<Notify>,
+}
+
+impl Drop for TaskFinished {
+    fn drop(&mut self) {
+        if self.remaining.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.done.notify_one();
+        }
+    }
+}
+
 /// Fetch responses from multiple participants in parallel with progress
 /// display.
 ///
@@ -400,12 +782,15 @@ pub fn is_interactive_terminal() -> bool { std::io::stderr().is_terminal() }
 ///
 /// * `client` - The storage client to use for fetching
 /// * `requests` - List of (participant_xid, arid, display_name) tuples
-/// * `config` - Configuration including timeout
+/// * `config` - Configuration including timeouts and early-stop threshold
 /// * `validate` - Closure to validate and extract data from each envelope
 ///
 /// # Returns
 ///
-/// A `CollectionResult` containing categorized results from all participants.
+/// A `CollectionResult` containing categorized results from all participants,
+/// regardless of completion order. Successes are ordered by participant XID;
+/// rejections, errors, timeouts, and cancellations are in display order (see
+/// [`sort_for_display`]).
 pub async fn parallel_fetch<F, T>(
     client: Arc<StorageClient>,
     requests: Vec<(XID, ARID, String)>,
@@ -419,15 +804,17 @@ where
     let timeout_secs = config.timeout_seconds.unwrap_or(600);
     let is_interactive = is_interactive_terminal();
     let participant_count = requests.len();
+    let names: Vec<(XID, String)> = requests
+        .iter()
+        .map(|(xid, _, name)| (*xid, name.clone()))
+        .collect();
 
     // Set up progress display or streaming output
     let progress = if is_interactive {
-        let p = Arc::new(ProgressDisplay::new_get(
-            &requests
-                .iter()
-                .map(|(xid, _, name)| (*xid, name.clone()))
-                .collect::<Vec<_>>(),
+        let p = Arc::new(ProgressDisplay::new_get_with_timeouts(
+            &names,
             timeout_secs,
+            config.participant_timeouts.clone(),
         ));
         p.start_timer_updates();
         Some(p)
@@ -443,11 +830,25 @@ where
         None
     };
 
+    let events = ProgressEvents::from_flag(Direction::Get).map(Arc::new);
+    if let Some(ref ev) = events {
+        for (xid, name) in &names {
+            ev.pending(xid, name);
+        }
+    }
+
     // Shared results collection
     #[allow(clippy::type_complexity)]
-    let results: Arc<Mutex<Vec<(XID, String, Result<T>)>>> =
+    let results: Arc<Mutex<Vec<(XID, String, Result<T>, Duration)>>> =
         Arc::new(Mutex::new(Vec::new()));
 
+    // Signalled once every task has finished or, with `min_success`, once
+    // enough responses have validated.
+    let done = Arc::new(Notify::new());
+    let remaining = Arc::new(AtomicUsize::new(participant_count));
+    let succeeded = Arc::new(AtomicUsize::new(0));
+    let min_success = config.min_success;
+
     // Use LocalSet for !Send futures
     let local_set = tokio::task::LocalSet::new();
 
@@ -461,96 +862,255 @@ where
                 let results = Arc::clone(&results);
                 let progress = progress.clone();
                 let streaming = streaming.clone();
-                let timeout = timeout_secs;
+                let events = events.clone();
+                let timeout = config
+                    .participant_timeouts
+                    .get(&xid)
+                    .copied()
+                    .unwrap_or(timeout_secs);
+                let done = Arc::clone(&done);
+                let finished = TaskFinished {
+                    remaining: Arc::clone(&remaining),
+                    done: Arc::clone(&done),
+                };
+                let succeeded = Arc::clone(&succeeded);
+                let task_name = name.clone();
 
                 let handle = tokio::task::spawn_local(async move {
+                    let _finished = finished;
+                    let started = Instant::now();
                     let fetch_result = tokio::time::timeout(
                         Duration::from_secs(timeout),
                         client.get(&arid, Some(timeout)),
                     )
                     .await;
 
+                    // An empty slot, whether seen by Hubert's own polling or
+                    // by our overall timeout, means the participant has not
+                    // responded yet.
                     let result = match fetch_result {
                         Ok(Ok(Some(env))) => validate(&env, &xid),
-                        Ok(Ok(None)) => Err(anyhow::anyhow!("Not found")),
-                        Ok(Err(e)) => Err(e),
-                        Err(_) => Err(anyhow::anyhow!("Timeout")),
+                        Ok(Ok(None)) | Err(_) => {
+                            Err(FetchError::NotYetAvailable.into())
+                        }
+                        Ok(Err(e)) => Err(e.into_anyhow()),
                     };
 
                     // Update display
                     if let Some(ref p) = progress {
                         match &result {
                             Ok(_) => p.mark_success(&xid),
-                            Err(e) => {
-                                if e.to_string().contains("Timeout") {
-                                    p.mark_timeout(&xid);
-                                } else {
-                                    p.mark_error(&xid, &e.to_string());
-                                }
-                            }
+                            Err(e) if is_retryable(e) => p.mark_timeout(&xid),
+                            Err(e) => p.mark_error(&xid, &e.to_string()),
                         }
                     } else if let Some(ref s) = streaming {
                         match &result {
                             Ok(_) => s.success(&name, None),
-                            Err(e) => {
-                                if e.to_string().contains("Timeout") {
-                                    s.timeout(&name);
-                                } else {
-                                    s.error(&name, &e.to_string());
-                                }
+                            Err(e) if is_retryable(e) => s.timeout(&name),
+                            Err(e) => s.error(&name, &e.to_string()),
+                        }
+                    }
+                    if let Some(ref ev) = events {
+                        match &result {
+                            Ok(_) => ev.success(&xid, &name),
+                            Err(e) if is_retryable(e) => {
+                                ev.timeout(&xid, &name)
                             }
+                            Err(e) => ev.error(&xid, &name, &e.to_string()),
                         }
                     }
 
-                    results.lock().await.push((xid, name, result));
+                    let successes = if result.is_ok() {
+                        succeeded.fetch_add(1, Ordering::SeqCst) + 1
+                    } else {
+                        succeeded.load(Ordering::SeqCst)
+                    };
+                    results.lock().await.push((
+                        xid,
+                        name,
+                        result,
+                        started.elapsed(),
+                    ));
+                    if min_success.is_some_and(|n| successes >= n) {
+                        done.notify_one();
+                    }
                 });
-                handles.push(handle);
+                handles.push((xid, task_name, handle));
             }
 
-            // Wait for all tasks
-            for handle in handles {
-                let _ = handle.await;
+            // Wait for every task, or until enough responses arrived, then
+            // cancel whatever is still outstanding
+            if !handles.is_empty() {
+                done.notified().await;
+            }
+            for (_, _, handle) in &handles {
+                handle.abort();
+            }
+            // A task that panicked recorded nothing; report it as that
+            // participant's error rather than as a cancellation
+            for (xid, name, handle) in handles {
+                let Err(err) = handle.await else { continue };
+                if !err.is_panic() {
+                    continue;
+                }
+                let message = "fetch task panicked";
+                if let Some(ref p) = progress {
+                    p.mark_error(&xid, message);
+                } else if let Some(ref s) = streaming {
+                    s.error(&name, message);
+                }
+                if let Some(ref ev) = events {
+                    ev.error(&xid, &name, message);
+                }
+                results.lock().await.push((
+                    xid,
+                    name,
+                    Err(anyhow::anyhow!(message)),
+                    Duration::ZERO,
+                ));
             }
         })
         .await;
 
-    // Finish progress display
-    if let Some(ref p) = progress {
-        p.finish();
-    }
-
     // Build collection result
     let results = Arc::try_unwrap(results)
         .map_err(|_| anyhow::anyhow!("Failed to unwrap results"))?
         .into_inner();
 
+    let mut cancelled: Vec<(XID, String)> = names
+        .into_iter()
+        .filter(|(xid, _)| {
+            !results.iter().any(|(finished, ..)| finished == xid)
+        })
+        .collect();
+    for (xid, name) in &cancelled {
+        if let Some(ref p) = progress {
+            p.mark_cancelled(xid);
+        } else if let Some(ref s) = streaming {
+            s.cancelled(name);
+        }
+        if let Some(ref ev) = events {
+            ev.cancelled(xid, name);
+        }
+    }
+
+    // Finish progress display
+    if let Some(ref p) = progress {
+        p.finish();
+    }
+
     let mut successes = Vec::new();
     let mut rejections = Vec::new();
     let mut errors = Vec::new();
     let mut timeouts = Vec::new();
+    let mut durations = HashMap::new();
 
-    for (xid, name, result) in results {
+    for (xid, name, result, elapsed) in results {
+        durations.insert(xid, elapsed);
         match result {
             Ok(data) => successes.push((xid, data)),
-            Err(e) => {
-                let msg = e.to_string();
-                if msg.contains("Timeout") {
-                    timeouts.push(xid);
-                } else if msg.contains("rejected") || msg.contains("Rejected") {
-                    rejections.push((xid, format!("{}: {}", name, msg)));
-                } else {
-                    errors.push((xid, format!("{}: {}", name, msg)));
+            Err(e) => match FetchError::classify(&e) {
+                FetchError::NotYetAvailable => timeouts.push((xid, name)),
+                FetchError::Rejected(reason) => rejections.push((
+                    xid,
+                    name.clone(),
+                    format!("{}: {}", name, reason),
+                )),
+                FetchError::DecodeError(_) | FetchError::ValidationError(_) => {
+                    errors.push((xid, name.clone(), format!("{}: {}", name, e)))
                 }
-            }
+            },
         }
     }
 
-    Ok(CollectionResult { successes, rejections, errors, timeouts })
+    // Tasks finish in arbitrary order. Successes drive dispatch and previews,
+    // so they follow XID order; failures are only listed to people, so they
+    // follow display order.
+    successes.sort_by_key(|(xid, _)| *xid);
+    sort_for_display(&mut rejections, |(xid, name, _)| (name.clone(), *xid));
+    sort_for_display(&mut errors, |(xid, name, _)| (name.clone(), *xid));
+    sort_for_display(&mut timeouts, |(xid, name)| (name.clone(), *xid));
+    sort_for_display(&mut cancelled, |(xid, name)| (name.clone(), *xid));
+    let rejections = rejections
+        .into_iter()
+        .map(|(xid, _, reason)| (xid, reason))
+        .collect();
+    let errors = errors
+        .into_iter()
+        .map(|(xid, _, error)| (xid, error))
+        .collect();
+    let timeouts = timeouts.into_iter().map(|(xid, _)| xid).collect();
+    let cancelled = cancelled.into_iter().map(|(xid, _)| xid).collect();
+
+    Ok(CollectionResult {
+        successes,
+        rejections,
+        errors,
+        timeouts,
+        cancelled,
+        durations,
+    })
+}
+
+/// Result of probing one ARID for a stored value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProbeStatus {
+    /// A value is stored at the ARID
+    Present,
+    /// Nothing is stored at the ARID
+    Absent,
+    /// The lookup itself failed
+    Error(String),
+}
+
+/// Check whether each ARID has a value in storage, running at most
+/// `concurrency` lookups at once.
+///
+/// Each lookup is a single attempt that does not wait for a value to appear.
+/// Results are in the order of `arids`.
+pub async fn parallel_probe(
+    client: Arc<StorageClient>,
+    arids: Vec<ARID>,
+    concurrency: usize,
+) -> Vec<(ARID, ProbeStatus)> {
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let local_set = tokio::task::LocalSet::new();
+
+    local_set
+        .run_until(async {
+            let handles: Vec<_> = arids
+                .iter()
+                .map(|arid| {
+                    let client = Arc::clone(&client);
+                    let semaphore = Arc::clone(&semaphore);
+                    let arid = *arid;
+                    tokio::task::spawn_local(async move {
+                        let _permit = semaphore.acquire_owned().await;
+                        match client.get(&arid, Some(0)).await {
+                            Ok(Some(_)) => ProbeStatus::Present,
+                            Ok(None) => ProbeStatus::Absent,
+                            Err(e) => ProbeStatus::Error(e.to_string()),
+                        }
+                    })
+                })
+                .collect();
+
+            let mut results = Vec::with_capacity(handles.len());
+            for (arid, handle) in arids.iter().zip(handles) {
+                let status = handle
+                    .await
+                    .unwrap_or_else(|e| ProbeStatus::Error(e.to_string()));
+                results.push((*arid, status));
+            }
+            results
+        })
+        .await
 }
 
 /// Send messages to multiple participants in parallel.
 ///
 /// Uses `tokio::task::LocalSet` because Hubert's `KvStore` futures are `!Send`.
+/// Results are ordered by participant XID.
 pub async fn parallel_send(
     client: Arc<StorageClient>,
     messages: Vec<(XID, ARID, Envelope, String)>,
@@ -580,6 +1140,13 @@ pub async fn parallel_send(
         None
     };
 
+    let events = ProgressEvents::from_flag(Direction::Put).map(Arc::new);
+    if let Some(ref ev) = events {
+        for (xid, _, _, name) in &messages {
+            ev.pending(xid, name);
+        }
+    }
+
     // Track start time for elapsed calculation in streaming mode
     let start_time = std::time::Instant::now();
 
@@ -598,6 +1165,7 @@ pub async fn parallel_send(
                 let results = Arc::clone(&results);
                 let progress = progress.clone();
                 let streaming = streaming.clone();
+                let events = events.clone();
                 let start = start_time;
 
                 let handle = tokio::task::spawn_local(async move {
@@ -615,6 +1183,12 @@ pub async fn parallel_send(
                             Err(e) => s.error(&name, &e.to_string()),
                         }
                     }
+                    if let Some(ref ev) = events {
+                        match &result {
+                            Ok(()) => ev.success(&xid, &name),
+                            Err(e) => ev.error(&xid, &name, &e.to_string()),
+                        }
+                    }
 
                     results.lock().await.push((xid, result));
                 });
@@ -631,9 +1205,11 @@ pub async fn parallel_send(
         p.finish();
     }
 
-    Arc::try_unwrap(results)
+    let mut results = Arc::try_unwrap(results)
         .expect("all tasks completed")
-        .into_inner()
+        .into_inner();
+    results.sort_by_key(|(xid, _)| *xid);
+    results
 }
 
 /// Helper to build request tuples from pending requests and registry.
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+
+use crate::{
+    cmd::{common::load_registry, registry::participants_file_path},
+    registry::RegistryFormat,
+};
+
+/// Rewrite the registry in another encoding.
+///
+/// The output format follows the output file's extension: `.cbor` files are
+/// CBOR and everything else is JSON. The source registry is left in place.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Replace the output file if it already exists
+    #[arg(long = "force")]
+    force: bool,
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+    /// Where to write the converted registry
+    #[arg(value_name = "OUTPUT")]
+    output: PathBuf,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let path = participants_file_path(self.registry)?;
+        if !path.exists() {
+            bail!("No registry found at {}", path.display());
+        }
+        if self.output.exists() && !self.force {
+            bail!(
+                "{} already exists; pass --force to replace it",
+                self.output.display()
+            );
+        }
+
+        let registry = load_registry(&path)?;
+        registry.save(&self.output).with_context(|| {
+            format!("Failed to convert registry to {}", self.output.display())
+        })?;
+
+        eprintln!(
+            "Converted {} ({}) to {} ({})",
+            path.display(),
+            RegistryFormat::for_path(&path),
+            self.output.display(),
+            RegistryFormat::for_path(&self.output)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+
+use crate::cmd::{common::load_registry, registry::participants_file_path};
+
+/// Remove the passphrase encryption from the registry file.
+///
+/// The registry is rewritten in its plain JSON or CBOR encoding.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let path = participants_file_path(self.registry)?;
+        if !path.exists() {
+            bail!("No registry found at {}", path.display());
+        }
+
+        let mut registry = load_registry(&path)?;
+        if !registry.is_encrypted() {
+            bail!("{} is not encrypted", path.display());
+        }
+        registry.set_passphrase(None);
+        registry.save(&path).with_context(|| {
+            format!("Failed to decrypt registry {}", path.display())
+        })?;
+
+        eprintln!("Decrypted {}", path.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+
+use crate::cmd::{
+    common::{load_registry, registry_passphrase},
+    registry::participants_file_path,
+};
+
+/// Encrypt the registry file under a passphrase.
+///
+/// The passphrase comes from `--passphrase-file`, `FROST_PASSPHRASE`, or a
+/// prompt. Every later command then needs the same passphrase to read or
+/// update the registry; `registry decrypt` removes the encryption.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let path = participants_file_path(self.registry)?;
+        if !path.exists() {
+            bail!("No registry found at {}", path.display());
+        }
+
+        let mut registry = load_registry(&path)?;
+        if registry.is_encrypted() {
+            bail!("{} is already encrypted", path.display());
+        }
+        registry.set_passphrase(Some(registry_passphrase(true)?));
+        registry.save(&path).with_context(|| {
+            format!("Failed to encrypt registry {}", path.display())
+        })?;
+
+        eprintln!("Encrypted {}", path.display());
+        Ok(())
+    }
+}
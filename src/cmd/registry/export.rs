@@ -0,0 +1,138 @@
+use anyhow::{Context, Result};
+use bc_envelope::prelude::*;
+use clap::{Parser, ValueEnum};
+
+use crate::{
+    cmd::{common::load_registry, registry::participants_file_path},
+    registry::Registry,
+};
+
+/// Output format for a registry export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[doc(hidden)]
+pub enum ExportFormat {
+    /// JSON using the same record layout as registry.json
+    Json,
+    /// A single `ur:envelope` signed by the registry owner
+    Envelope,
+}
+
+/// Export the public participant roster (XID documents and pet names).
+///
+/// Groups and private key material are never exported.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Output format
+    #[arg(long = "format", value_enum, default_value = "json")]
+    format: ExportFormat,
+    /// Include the owner's public XID document (always included with
+    /// `--format envelope` so recipients can verify the signature)
+    #[arg(long = "include-owner")]
+    include_owner: bool,
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let path = participants_file_path(self.registry)?;
+        let registry = load_registry(&path)?;
+
+        match self.format {
+            ExportFormat::Json => {
+                let export = export_json(&registry, self.include_owner)?;
+                println!("{}", serde_json::to_string_pretty(&export)?);
+            }
+            ExportFormat::Envelope => {
+                println!("{}", export_envelope(&registry)?.ur_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn export_json(
+    registry: &Registry,
+    include_owner: bool,
+) -> Result<serde_json::Value> {
+    let mut root = serde_json::Map::new();
+    if include_owner {
+        let owner = registry
+            .owner()
+            .context("Registry has no owner to include in the export")?;
+        root.insert(
+            "owner".to_string(),
+            record_json(owner.public_xid_document_ur()?, owner.pet_name()),
+        );
+    }
+
+    let mut participants = serde_json::Map::new();
+    for (xid, record) in registry.participants() {
+        participants.insert(
+            xid.ur_string(),
+            record_json(record.public_xid_document_ur()?, record.pet_name()),
+        );
+    }
+    root.insert(
+        "participants".to_string(),
+        serde_json::Value::Object(participants),
+    );
+
+    Ok(serde_json::Value::Object(root))
+}
+
+fn record_json(
+    xid_document_ur: String,
+    pet_name: Option<&str>,
+) -> serde_json::Value {
+    let mut entry = serde_json::Map::new();
+    entry.insert(
+        "xid_document".to_string(),
+        serde_json::Value::String(xid_document_ur),
+    );
+    if let Some(name) = pet_name {
+        entry.insert(
+            "pet_name".to_string(),
+            serde_json::Value::String(name.to_owned()),
+        );
+    }
+    serde_json::Value::Object(entry)
+}
+
+fn export_envelope(registry: &Registry) -> Result<Envelope> {
+    let owner = registry
+        .owner()
+        .context("Registry owner is required to sign an envelope export")?;
+    let signer = owner
+        .xid_document()
+        .inception_private_keys()
+        .context("Owner XID document has no inception private keys")?;
+
+    let mut envelope =
+        Envelope::unit().add_type("registryExport").add_assertion(
+            "owner",
+            record_envelope(owner.public_xid_document_ur()?, owner.pet_name()),
+        );
+    for record in registry.participants().values() {
+        envelope = envelope.add_assertion(
+            "participant",
+            record_envelope(
+                record.public_xid_document_ur()?,
+                record.pet_name(),
+            ),
+        );
+    }
+
+    Ok(envelope.sign(signer))
+}
+
+fn record_envelope(
+    xid_document_ur: String,
+    pet_name: Option<&str>,
+) -> Envelope {
+    Envelope::new(xid_document_ur)
+        .add_optional_assertion("pet_name", pet_name.map(str::to_owned))
+}
@@ -0,0 +1,135 @@
+use std::fs;
+
+use anyhow::{Context, Result, bail};
+use bc_envelope::prelude::*;
+use clap::Parser;
+
+use crate::{
+    cmd::{
+        common::{
+            GroupStatePaths, load_registry, signing_key_from_verifying,
+            write_atomically,
+        },
+        dkg::common::{parse_envelope_ur, read_ur_input},
+        registry::participants_file_path,
+    },
+    dkg::DealtShare,
+    registry::{
+        ContributionPaths, GroupParticipant, GroupProvenance, GroupRecord,
+    },
+};
+
+/// Install a key share dealt by `frost keygen` into this registry.
+///
+/// The share must be encrypted to the registry owner and signed by the
+/// dealer, whose XID document must already be in the registry.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Sealed key share envelope (ur:envelope)
+    /// (`-` reads it from stdin)
+    #[arg(value_name = "SHARE")]
+    share: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let registry_path = participants_file_path(self.registry)?;
+        let mut registry =
+            load_registry(&registry_path).with_context(|| {
+                format!(
+                    "Failed to load registry at {}",
+                    registry_path.display()
+                )
+            })?;
+        let owner = registry
+            .owner()
+            .context("Registry owner is required")?
+            .clone();
+        let owner_keys = owner
+            .xid_document()
+            .inception_private_keys()
+            .context("Owner XID document has no private keys")?;
+
+        let sealed = parse_envelope_ur(&read_ur_input(&self.share)?)
+            .context("Invalid key share envelope")?;
+        let share = DealtShare::open(&sealed, owner_keys, |dealer| {
+            registry
+                .participant(dealer)
+                .map(|record| record.xid_document().clone())
+                .with_context(|| {
+                    format!(
+                        "Dealer {} not found in registry",
+                        dealer.ur_string()
+                    )
+                })
+        })?;
+        share.check_for(&owner.xid())?;
+        if registry.group(&share.group_id).is_some() {
+            bail!(
+                "Group {} is already in the registry",
+                share.group_id.ur_string()
+            );
+        }
+
+        let paths = GroupStatePaths::new(&registry_path);
+        let group_dir = paths.group_dir(&share.group_id);
+        fs::create_dir_all(&group_dir).with_context(|| {
+            format!(
+                "Failed to create group state directory {}",
+                group_dir.display()
+            )
+        })?;
+        let key_package_path = paths.key_package_path(&share.group_id);
+        write_atomically(
+            &key_package_path,
+            serde_json::to_vec_pretty(&share.key_package)?,
+        )
+        .with_context(|| {
+            format!("Failed to write {}", key_package_path.display())
+        })?;
+        let public_key_package_path =
+            paths.public_key_package_path(&share.group_id);
+        write_atomically(
+            &public_key_package_path,
+            serde_json::to_vec_pretty(&share.public_key_package)?,
+        )
+        .with_context(|| {
+            format!("Failed to write {}", public_key_package_path.display())
+        })?;
+
+        let mut group_record = GroupRecord::new(
+            share.charter.clone(),
+            share.min_signers,
+            GroupParticipant::new(share.coordinator),
+            share
+                .participants
+                .iter()
+                .map(|xid| GroupParticipant::new(*xid))
+                .collect(),
+        );
+        group_record.set_identifier_scheme(share.identifier_scheme);
+        group_record.set_provenance(GroupProvenance::TrustedDealer);
+        group_record.set_verifying_key(signing_key_from_verifying(
+            share.public_key_package.verifying_key(),
+        )?);
+        group_record.set_contributions(ContributionPaths {
+            key_package: Some(key_package_path.to_string_lossy().into_owned()),
+            ..Default::default()
+        });
+        registry.record_group(share.group_id, group_record)?;
+        registry.save(&registry_path)?;
+
+        eprintln!(
+            "Warning: this key share was generated by a trusted dealer, who \
+             saw every participant's share."
+        );
+        println!("{}", share.group_id.ur_string());
+
+        Ok(())
+    }
+}
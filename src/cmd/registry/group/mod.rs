@@ -0,0 +1,35 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+mod import_share;
+mod rename;
+mod verify_charter;
+
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Debug, Subcommand)]
+#[doc(hidden)]
+enum Commands {
+    /// Install a key share dealt by `frost keygen`
+    ImportShare(import_share::CommandArgs),
+    /// Verify the coordinator's signature over a group's charter and
+    /// participant set
+    VerifyCharter(verify_charter::CommandArgs),
+    /// Give a group a friendly name, or remove it
+    Rename(rename::CommandArgs),
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        match self.command {
+            Commands::ImportShare(args) => args.exec(),
+            Commands::VerifyCharter(args) => args.exec(),
+            Commands::Rename(args) => args.exec(),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::{Context, Result};
+use bc_envelope::prelude::*;
+use clap::Parser;
+
+use crate::cmd::{
+    common::load_registry,
+    dkg::common::{parse_group_id, read_ur_input},
+    registry::participants_file_path,
+};
+
+/// Give a group a friendly name, or remove it.
+///
+/// Commands that take a group accept its name in place of the `ur:arid`.
+/// Names are local to this registry and must be unique among its groups.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Remove the group's name instead of setting one
+    #[arg(long = "clear", conflicts_with = "name")]
+    clear: bool,
+
+    /// Group ID or current name
+    /// (`-` reads it from stdin)
+    #[arg(value_name = "GROUP")]
+    group: String,
+
+    /// New name for the group
+    #[arg(value_name = "NAME", required_unless_present = "clear")]
+    name: Option<String>,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let registry_path = participants_file_path(self.registry)?;
+        let mut registry =
+            load_registry(&registry_path).with_context(|| {
+                format!(
+                    "Failed to load registry at {}",
+                    registry_path.display()
+                )
+            })?;
+
+        let group_id = parse_group_id(&registry, &read_ur_input(&self.group)?)?;
+        registry.set_group_name(&group_id, self.name.clone())?;
+        registry.save(&registry_path)?;
+
+        match self.name {
+            Some(name) => {
+                eprintln!("Named group {} '{name}'", group_id.ur_string())
+            }
+            None => {
+                eprintln!("Removed the name of group {}", group_id.ur_string())
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::{Context, Result, bail};
+use bc_envelope::prelude::*;
+use clap::Parser;
+
+use crate::{
+    cmd::{
+        common::load_registry,
+        dkg::common::{parse_envelope_ur, parse_group_id, read_ur_input},
+        registry::participants_file_path,
+    },
+    dkg::CharterStatement,
+    registry::GroupProvenance,
+};
+
+/// Verify a group's charter attestation against the coordinator's XID
+/// document.
+///
+/// The attestation is the coordinator's signature over the charter, minimum
+/// signers, and participant set, received with the invite. It is checked
+/// against the coordinator document held in this registry rather than the
+/// transport that delivered it.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Group ID to verify
+    /// (`-` reads it from stdin)
+    #[arg(value_name = "GROUP_ID")]
+    group_id: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let registry_path = participants_file_path(self.registry)?;
+        let registry = load_registry(&registry_path).with_context(|| {
+            format!("Failed to load registry at {}", registry_path.display())
+        })?;
+
+        let group_id =
+            parse_group_id(&registry, &read_ur_input(&self.group_id)?)?;
+        let group_record = registry
+            .group(&group_id)
+            .context("Group not found in registry")?;
+        if group_record.provenance() == GroupProvenance::TrustedDealer {
+            bail!(
+                "Group was generated by a trusted dealer and has no charter attestation"
+            );
+        }
+        let attestation_ur = group_record.charter_attestation().context(
+            "Group has no charter attestation; its invite predates charter signing",
+        )?;
+        let attestation = parse_envelope_ur(attestation_ur)
+            .context("Invalid charter attestation in registry")?;
+
+        let coordinator_xid = *group_record.coordinator().xid();
+        let (coordinator_document, coordinator_name) = match registry.owner() {
+            Some(owner) if owner.xid() == coordinator_xid => {
+                (owner.xid_document().clone(), owner.pet_name())
+            }
+            _ => {
+                let record = registry
+                    .participant(&coordinator_xid)
+                    .with_context(|| {
+                        format!(
+                            "Coordinator {} not found in registry",
+                            coordinator_xid.ur_string()
+                        )
+                    })?;
+                (record.xid_document().clone(), record.pet_name())
+            }
+        };
+
+        let statement =
+            CharterStatement::verify(&attestation, &coordinator_document)?;
+        let expected = CharterStatement::new(
+            group_id,
+            group_record.charter(),
+            group_record.min_signers(),
+            coordinator_xid,
+            group_record.identifier_order(),
+        );
+        statement.check_matches(&expected)?;
+
+        println!("Charter attestation: valid");
+        println!(
+            "Coordinator: {}",
+            coordinator_name
+                .map(str::to_owned)
+                .unwrap_or_else(|| coordinator_xid.ur_string())
+        );
+        println!("Charter: {}", statement.charter);
+        println!("Min signers: {}", statement.min_signers);
+        println!("Participants: {}", statement.participants.len());
+
+        Ok(())
+    }
+}
@@ -3,6 +3,19 @@ use std::path::{Path, PathBuf};
 use anyhow::{Result, bail};
 use clap::{Parser, Subcommand};
 
+use super::registry_format;
+use crate::registry::RegistryFormat;
+
+#[doc(hidden)]
+mod convert;
+#[doc(hidden)]
+mod decrypt;
+#[doc(hidden)]
+mod encrypt;
+#[doc(hidden)]
+mod export;
+#[doc(hidden)]
+mod group;
 #[doc(hidden)]
 mod owner;
 #[doc(hidden)]
@@ -22,6 +35,16 @@ enum Commands {
     Participant(participant::CommandArgs),
     /// Manage the registry owner
     Owner(owner::CommandArgs),
+    /// Export public participant records for sharing
+    Export(export::CommandArgs),
+    /// Rewrite the registry as JSON or CBOR
+    Convert(convert::CommandArgs),
+    /// Inspect recorded DKG groups
+    Group(group::CommandArgs),
+    /// Encrypt the registry file under a passphrase
+    Encrypt(encrypt::CommandArgs),
+    /// Remove the registry file's passphrase encryption
+    Decrypt(decrypt::CommandArgs),
 }
 
 impl CommandArgs {
@@ -29,20 +52,35 @@ impl CommandArgs {
         match self.command {
             Commands::Participant(args) => args.exec(),
             Commands::Owner(args) => args.exec(),
+            Commands::Export(args) => args.exec(),
+            Commands::Convert(args) => args.exec(),
+            Commands::Group(args) => args.exec(),
+            Commands::Encrypt(args) => args.exec(),
+            Commands::Decrypt(args) => args.exec(),
         }
     }
 }
 
 /// Resolve the participants registry path, defaulting to `registry.json` in the
-/// current working directory.
+/// current working directory (`registry.cbor` with `--registry-format cbor`).
 pub fn participants_file_path(registry: Option<String>) -> Result<PathBuf> {
-    const DEFAULT_FILENAME: &str = "registry.json";
+    let format = registry_format();
+    let default_filename = format.unwrap_or_default().default_filename();
     let cwd = std::env::current_dir()?;
 
-    match registry {
-        None => Ok(cwd.join(DEFAULT_FILENAME)),
-        Some(raw) => resolve_registry_path(&cwd, DEFAULT_FILENAME, raw),
+    let path = match registry {
+        None => cwd.join(default_filename),
+        Some(raw) => resolve_registry_path(&cwd, default_filename, raw)?,
+    };
+    if let Some(format) = format
+        && RegistryFormat::for_path(&path) != format
+    {
+        bail!(
+            "--registry-format {format} does not match registry file {}",
+            path.display()
+        );
     }
+    Ok(path)
 }
 
 fn resolve_registry_path(
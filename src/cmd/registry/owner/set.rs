@@ -2,8 +2,8 @@ use anyhow::{Result, bail};
 use clap::Parser;
 
 use crate::{
-    cmd::registry::participants_file_path,
-    registry::{OwnerOutcome, OwnerRecord, Registry},
+    cmd::{common::load_registry, registry::participants_file_path},
+    registry::{OwnerOutcome, OwnerRecord},
 };
 
 #[derive(Debug, Parser)]
@@ -25,7 +25,7 @@ impl CommandArgs {
         let owner =
             OwnerRecord::from_signed_xid_ur(self.xid_document, pet_name)?;
         let path = participants_file_path(self.registry)?;
-        let mut registry = Registry::load(&path)?;
+        let mut registry = load_registry(&path)?;
 
         match registry.set_owner(owner)? {
             OwnerOutcome::AlreadyPresent => {
@@ -1,18 +1,46 @@
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
+use bc_components::XID;
+use bc_envelope::prelude::*;
 use clap::Parser;
 
+use super::{normalize_tag, normalize_text};
 use crate::{
-    cmd::registry::participants_file_path,
-    registry::{AddOutcome, ParticipantRecord, Registry},
+    cmd::{
+        busy::get_with_indicator,
+        common::{OptionalStorageSelector, load_registry, parse_arid_ur},
+        registry::participants_file_path,
+        storage::{StorageClient, StorageSelection, shared_runtime},
+    },
+    registry::{AddOutcome, ParticipantRecord},
 };
 
 #[derive(Debug, Parser)]
 #[doc(hidden)]
 pub struct CommandArgs {
     /// Signed ur:xid document containing the participant's XID document
-    xid_document: String,
+    /// (omitted with --fetch, whose only positional is the pet name)
+    #[arg(value_name = "XID_DOCUMENT", required_unless_present = "fetch")]
+    xid_document: Option<String>,
     /// Optional human readable alias
     pet_name: Option<String>,
+    /// Fetch the participant's published XID document from Hubert at this
+    /// ARID instead of taking it on the command line
+    #[arg(long = "fetch", value_name = "UR:ARID")]
+    fetch: Option<String>,
+    /// With --fetch, require the fetched document to have this XID
+    #[arg(long = "expect-xid", value_name = "UR:XID", requires = "fetch")]
+    expect_xid: Option<String>,
+    #[command(flatten)]
+    storage: OptionalStorageSelector,
+    /// Free-form note about the participant
+    #[arg(long = "note", value_name = "TEXT")]
+    note: Option<String>,
+    /// How to reach the participant out of band (email, URI, handle)
+    #[arg(long = "contact", value_name = "CONTACT")]
+    contact: Option<String>,
+    /// Role tag such as `signer`; may be repeated
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
     /// Optional registry path or filename override
     #[arg(long = "registry", value_name = "PATH")]
     registry: Option<String>,
@@ -20,16 +48,64 @@ pub struct CommandArgs {
 
 impl CommandArgs {
     pub fn exec(self) -> Result<()> {
-        let pet_name = normalize_pet_name(self.pet_name)?;
-        let participant =
-            ParticipantRecord::from_signed_xid_ur(self.xid_document, pet_name)?;
+        let (xid_document, pet_name) = match &self.fetch {
+            Some(arid) => {
+                if self.pet_name.is_some() {
+                    bail!(
+                        "With --fetch, the only positional argument is the \
+                         pet name"
+                    );
+                }
+                let expected = match &self.expect_xid {
+                    Some(raw) => Some(
+                        XID::from_ur_string(raw.trim())
+                            .context("Invalid --expect-xid")?,
+                    ),
+                    None => None,
+                };
+                let selection = self
+                    .storage
+                    .resolve()?
+                    .context("--fetch requires Hubert storage options")?;
+                let document = fetch_xid_document(selection, arid, expected)?;
+                (document, self.xid_document)
+            }
+            None => (
+                self.xid_document.context("XID document is required")?,
+                self.pet_name,
+            ),
+        };
+        let pet_name = normalize_pet_name(pet_name)?;
+        let note = normalize_text("Note", self.note)?;
+        let contact = normalize_text("Contact", self.contact)?;
+        let tags = self
+            .tags
+            .iter()
+            .map(|tag| normalize_tag(tag))
+            .collect::<Result<Vec<_>>>()?;
+        let has_metadata =
+            note.is_some() || contact.is_some() || !tags.is_empty();
+
+        let mut participant =
+            ParticipantRecord::from_signed_xid_ur(xid_document, pet_name)?;
+        participant.set_note(note);
+        participant.set_contact(contact);
+        for tag in tags {
+            participant.add_tag(tag);
+        }
         let xid = participant.xid();
         let path = participants_file_path(self.registry)?;
-        let mut registry = Registry::load(&path)?;
+        let mut registry = load_registry(&path)?;
 
         match registry.add_participant(xid, participant)? {
             AddOutcome::AlreadyPresent => {
                 println!("Participant already recorded");
+                if has_metadata {
+                    println!(
+                        "Metadata not changed; use `frost registry \
+                         participant edit` to update it"
+                    );
+                }
             }
             AddOutcome::Inserted => {
                 registry.save(&path)?;
@@ -41,6 +117,42 @@ impl CommandArgs {
     }
 }
 
+/// Retrieves a participant's published XID document from Hubert, returning
+/// its UR once it checks out as a signed XID document (with the expected XID,
+/// if one was given).
+fn fetch_xid_document(
+    selection: StorageSelection,
+    arid: &str,
+    expected: Option<XID>,
+) -> Result<String> {
+    let arid = parse_arid_ur(arid)?;
+    let runtime = shared_runtime()?;
+    let client =
+        runtime.block_on(async { StorageClient::shared(selection).await })?;
+    let envelope =
+        get_with_indicator(runtime, &client, &arid, "XID document", None)?
+            .context("Nothing posted yet at the XID document ARID")?;
+
+    let document_ur = envelope.ur_string();
+    let record = ParticipantRecord::from_signed_xid_ur(&document_ur, None)
+        .with_context(|| {
+            format!(
+                "Envelope at {} is not a signed XID document",
+                arid.ur_string()
+            )
+        })?;
+    if let Some(expected) = expected
+        && record.xid() != expected
+    {
+        bail!(
+            "Fetched XID document is for {}, expected {}",
+            record.xid().ur_string(),
+            expected.ur_string()
+        );
+    }
+    Ok(document_ur)
+}
+
 fn normalize_pet_name(pet_name: Option<String>) -> Result<Option<String>> {
     match pet_name {
         None => Ok(None),
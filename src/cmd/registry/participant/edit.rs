@@ -0,0 +1,98 @@
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+
+use super::{normalize_tag, normalize_text, resolve_participant_xid};
+use crate::cmd::{common::load_registry, registry::participants_file_path};
+
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Participant pet name or ur:xid identifier
+    participant: String,
+    /// Replace the participant's note
+    #[arg(long = "note", value_name = "TEXT", conflicts_with = "clear_note")]
+    note: Option<String>,
+    /// Remove the participant's note
+    #[arg(long = "clear-note")]
+    clear_note: bool,
+    /// Replace the participant's contact
+    #[arg(
+        long = "contact",
+        value_name = "CONTACT",
+        conflicts_with = "clear_contact"
+    )]
+    contact: Option<String>,
+    /// Remove the participant's contact
+    #[arg(long = "clear-contact")]
+    clear_contact: bool,
+    /// Add a role tag; may be repeated
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// Remove a role tag; may be repeated
+    #[arg(long = "untag", value_name = "TAG")]
+    untags: Vec<String>,
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        if self.note.is_none()
+            && !self.clear_note
+            && self.contact.is_none()
+            && !self.clear_contact
+            && self.tags.is_empty()
+            && self.untags.is_empty()
+        {
+            bail!(
+                "Nothing to change; pass --note, --contact, --tag, --untag, \
+                 --clear-note, or --clear-contact"
+            );
+        }
+        let note = normalize_text("Note", self.note)?;
+        let contact = normalize_text("Contact", self.contact)?;
+        let tags = self
+            .tags
+            .iter()
+            .map(|tag| normalize_tag(tag))
+            .collect::<Result<Vec<_>>>()?;
+        let untags = self
+            .untags
+            .iter()
+            .map(|tag| normalize_tag(tag))
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(tag) = tags.iter().find(|tag| untags.contains(tag)) {
+            bail!("Tag '{tag}' cannot be both added and removed");
+        }
+
+        let path = participants_file_path(self.registry)?;
+        let mut registry = load_registry(&path)?;
+        let xid = resolve_participant_xid(&registry, &self.participant)?;
+        let record = registry
+            .participant_mut(&xid)
+            .context("Participant not found in registry")?;
+        let before = record.clone();
+
+        if note.is_some() || self.clear_note {
+            record.set_note(note);
+        }
+        if contact.is_some() || self.clear_contact {
+            record.set_contact(contact);
+        }
+        for tag in untags {
+            record.remove_tag(&tag);
+        }
+        for tag in tags {
+            record.add_tag(tag);
+        }
+
+        if *record == before {
+            println!("Participant unchanged");
+        } else {
+            registry.save(&path)?;
+        }
+
+        Ok(())
+    }
+}
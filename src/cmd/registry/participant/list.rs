@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use bc_envelope::prelude::*;
+use clap::Parser;
+
+use super::normalize_tag;
+use crate::{
+    cmd::{
+        common::{OutputFormat, load_registry, sort_for_display, tsv_record},
+        registry::participants_file_path,
+    },
+    registry::ParticipantRecord,
+};
+
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Only list participants carrying this tag; may be repeated, in which
+    /// case participants must carry every tag
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// Output format. `tsv` prints one participant per line with the
+    /// columns XID, pet name, note, contact, and comma-separated tags;
+    /// missing values are empty
+    #[arg(long = "output", value_enum, value_name = "FORMAT")]
+    output: Option<OutputFormat>,
+    /// Print records as JSON using the same layout as registry.json (same
+    /// as `--output json`)
+    #[arg(long = "json", conflicts_with = "output")]
+    json: bool,
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let tags = self
+            .tags
+            .iter()
+            .map(|tag| normalize_tag(tag))
+            .collect::<Result<Vec<_>>>()?;
+        let path = participants_file_path(self.registry)?;
+        let registry = load_registry(&path)?;
+
+        let selected: BTreeMap<String, &ParticipantRecord> = registry
+            .participants()
+            .iter()
+            .filter(|(_, record)| tags.iter().all(|tag| record.has_tag(tag)))
+            .map(|(xid, record)| (xid.ur_string(), record))
+            .collect();
+
+        let format = if self.json {
+            OutputFormat::Json
+        } else {
+            self.output.unwrap_or_default()
+        };
+        if format == OutputFormat::Json {
+            println!("{}", serde_json::to_string_pretty(&selected)?);
+            return Ok(());
+        }
+
+        let mut listed: Vec<&ParticipantRecord> =
+            selected.into_values().collect();
+        sort_for_display(&mut listed, |record| {
+            let xid = record.xid();
+            let name = record
+                .pet_name()
+                .map(|n| n.to_owned())
+                .unwrap_or_else(|| xid.ur_string());
+            (name, xid)
+        });
+
+        if format == OutputFormat::Tsv {
+            for record in listed {
+                let xid = record.xid().ur_string();
+                let tags = record.tags().join(",");
+                println!(
+                    "{}",
+                    tsv_record([
+                        xid.as_str(),
+                        record.pet_name().unwrap_or_default(),
+                        record.note().unwrap_or_default(),
+                        record.contact().unwrap_or_default(),
+                        tags.as_str(),
+                    ])
+                );
+            }
+            return Ok(());
+        }
+
+        for record in listed {
+            let xid = record.xid().ur_string();
+            match record.pet_name() {
+                Some(name) => println!("{name} {xid}"),
+                None => println!("{xid}"),
+            }
+            if let Some(note) = record.note() {
+                println!("  Note: {note}");
+            }
+            if let Some(contact) = record.contact() {
+                println!("  Contact: {contact}");
+            }
+            if !record.tags().is_empty() {
+                println!("  Tags: {}", record.tags().join(", "));
+            }
+        }
+
+        Ok(())
+    }
+}
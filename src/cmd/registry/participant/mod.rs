@@ -1,8 +1,18 @@
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
+use bc_components::XID;
+use bc_envelope::prelude::*;
 use clap::{Parser, Subcommand};
 
+use crate::registry::Registry;
+
 #[doc(hidden)]
 mod add;
+#[doc(hidden)]
+mod edit;
+#[doc(hidden)]
+mod list;
+#[doc(hidden)]
+mod show;
 
 #[derive(Debug, Parser)]
 #[doc(hidden)]
@@ -16,12 +26,78 @@ pub struct CommandArgs {
 enum Commands {
     /// Add a participant using an ur:xid document
     Add(add::CommandArgs),
+    /// Change a participant's note, contact, or tags
+    Edit(edit::CommandArgs),
+    /// List recorded participants
+    List(list::CommandArgs),
+    /// Show one participant's metadata and full XID document
+    Show(show::CommandArgs),
 }
 
 impl CommandArgs {
     pub fn exec(self) -> Result<()> {
         match self.command {
             Commands::Add(args) => args.exec(),
+            Commands::Edit(args) => args.exec(),
+            Commands::List(args) => args.exec(),
+            Commands::Show(args) => args.exec(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Metadata
+// -----------------------------------------------------------------------------
+
+/// Resolve a participant by pet name or `ur:xid`.
+fn resolve_participant_xid(registry: &Registry, input: &str) -> Result<XID> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        bail!("Participant identifier cannot be empty");
+    }
+
+    if let Ok(xid) = XID::from_ur_string(trimmed) {
+        registry.participant(&xid).with_context(|| {
+            format!(
+                "Participant with XID {} not found in registry",
+                xid.ur_string()
+            )
+        })?;
+        Ok(xid)
+    } else {
+        let (xid, _) =
+            registry.participant_by_pet_name(trimmed).with_context(|| {
+                format!("Participant with pet name '{trimmed}' not found")
+            })?;
+        Ok(*xid)
+    }
+}
+
+/// Trim a free-form metadata value, rejecting blank input.
+fn normalize_text(
+    label: &str,
+    value: Option<String>,
+) -> Result<Option<String>> {
+    match value {
+        None => Ok(None),
+        Some(value) => {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                bail!("{label} cannot be empty");
+            }
+            Ok(Some(trimmed.to_owned()))
         }
     }
 }
+
+/// Trim a tag, rejecting blank tags and tags containing whitespace.
+fn normalize_tag(tag: &str) -> Result<String> {
+    let trimmed = tag.trim();
+    if trimmed.is_empty() {
+        bail!("Tag cannot be empty");
+    }
+    if trimmed.chars().any(char::is_whitespace) {
+        bail!("Tag cannot contain whitespace: '{trimmed}'");
+    }
+    Ok(trimmed.to_owned())
+}
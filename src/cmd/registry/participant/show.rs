@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use bc_envelope::prelude::*;
+use bc_xid::{XIDGeneratorOptions, XIDPrivateKeyOptions, XIDSigningOptions};
+use clap::Parser;
+
+use super::resolve_participant_xid;
+use crate::cmd::{common::load_registry, registry::participants_file_path};
+
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Participant pet name or ur:xid identifier
+    participant: String,
+    /// Print only the participant's public ur:xid document
+    #[arg(long = "ur")]
+    ur: bool,
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let path = participants_file_path(self.registry)?;
+        let registry = load_registry(&path)?;
+        let xid = resolve_participant_xid(&registry, &self.participant)?;
+        let record = registry
+            .participant(&xid)
+            .context("Participant not found in registry")?;
+
+        // Private keys should never reach the registry, but both outputs
+        // omit them in case a document carrying them was added.
+        if self.ur {
+            println!("{}", record.public_xid_document_ur()?);
+            return Ok(());
+        }
+
+        println!("XID: {}", xid.ur_string());
+        if let Some(name) = record.pet_name() {
+            println!("Pet name: {name}");
+        }
+        if let Some(note) = record.note() {
+            println!("Note: {note}");
+        }
+        if let Some(contact) = record.contact() {
+            println!("Contact: {contact}");
+        }
+        if !record.tags().is_empty() {
+            println!("Tags: {}", record.tags().join(", "));
+        }
+
+        let envelope = record.xid_document().to_envelope(
+            XIDPrivateKeyOptions::Omit,
+            XIDGeneratorOptions::Omit,
+            XIDSigningOptions::None,
+        )?;
+        println!();
+        println!("{}", envelope.format());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+//! JSON completion reports for coordinator collection phases.
+//!
+//! A report records, for one run of a collect command, the outcome of every
+//! participant's response along with how long it took and which files the
+//! run produced, so monitoring tools can track ceremony health over time.
+
+use std::{path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+use bc_components::{ARID, SigningPublicKey, XID};
+use bc_envelope::prelude::*;
+use serde::Serialize;
+
+use crate::{
+    cmd::{
+        common::write_atomically,
+        parallel::{CollectionResult, FetchError},
+        sign::common::hex_message,
+    },
+    registry::Registry,
+};
+
+/// Name recorded for `xid` in a report: its pet name in the registry.
+pub fn report_name(registry: &Registry, xid: &XID) -> Option<String> {
+    registry
+        .participant(xid)
+        .and_then(|record| record.pet_name())
+        .map(str::to_owned)
+}
+
+/// Outcome of collecting one participant's response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticipantStatus {
+    Success,
+    Rejected,
+    Error,
+    Timeout,
+    Cancelled,
+}
+
+impl ParticipantStatus {
+    /// Classify a failed fetch the same way parallel collection does.
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        match FetchError::classify(err) {
+            FetchError::NotYetAvailable => Self::Timeout,
+            FetchError::Rejected(_) => Self::Rejected,
+            FetchError::DecodeError(_) | FetchError::ValidationError(_) => {
+                Self::Error
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ParticipantReport {
+    pub xid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub status: ParticipantStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+/// Report written by `--report` after a collection phase.
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionReport {
+    pub phase: String,
+    pub group: String,
+    /// Signing session, for signing phases
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    pub timestamp: String,
+    pub participants: Vec<ParticipantReport>,
+    pub outputs: Vec<String>,
+    /// Hex of the message the group signed, once a signature is aggregated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_digest: Option<String>,
+    /// Group verifying key the aggregated signature was checked against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verifying_key: Option<String>,
+}
+
+impl CollectionReport {
+    pub fn new(phase: &str, group_id: &ARID) -> Self {
+        Self {
+            phase: phase.to_owned(),
+            group: group_id.ur_string(),
+            session: None,
+            timestamp: Date::now().to_string(),
+            participants: Vec::new(),
+            outputs: Vec::new(),
+            target_digest: None,
+            verifying_key: None,
+        }
+    }
+
+    /// Record the signing session the report belongs to.
+    pub fn with_session(mut self, session_id: &ARID) -> Self {
+        self.session = Some(session_id.ur_string());
+        self
+    }
+
+    /// Build a report from a parallel collection. `name_for` supplies the
+    /// display name recorded for each participant.
+    pub fn from_collection<T>(
+        phase: &str,
+        group_id: &ARID,
+        collection: &CollectionResult<T>,
+        name_for: impl Fn(&XID) -> Option<String>,
+    ) -> Self {
+        let mut report = Self::new(phase, group_id);
+        let duration = |xid: &XID| collection.durations.get(xid).copied();
+        for (xid, _) in &collection.successes {
+            report.record(
+                xid,
+                name_for(xid),
+                ParticipantStatus::Success,
+                None,
+                duration(xid),
+            );
+        }
+        for (xid, reason) in &collection.rejections {
+            report.record(
+                xid,
+                name_for(xid),
+                ParticipantStatus::Rejected,
+                Some(reason.clone()),
+                duration(xid),
+            );
+        }
+        for (xid, error) in &collection.errors {
+            report.record(
+                xid,
+                name_for(xid),
+                ParticipantStatus::Error,
+                Some(error.clone()),
+                duration(xid),
+            );
+        }
+        for xid in &collection.timeouts {
+            report.record(
+                xid,
+                name_for(xid),
+                ParticipantStatus::Timeout,
+                None,
+                duration(xid),
+            );
+        }
+        for xid in &collection.cancelled {
+            report.record(
+                xid,
+                name_for(xid),
+                ParticipantStatus::Cancelled,
+                None,
+                None,
+            );
+        }
+        report
+    }
+
+    /// Record one participant's outcome.
+    pub fn record(
+        &mut self,
+        xid: &XID,
+        name: Option<String>,
+        status: ParticipantStatus,
+        reason: Option<String>,
+        duration: Option<Duration>,
+    ) {
+        self.participants.push(ParticipantReport {
+            xid: xid.ur_string(),
+            name,
+            status,
+            reason,
+            duration_ms: duration.map(|d| d.as_millis() as u64),
+        });
+    }
+
+    /// Record a file written by the collection.
+    pub fn add_output(&mut self, path: &Path) {
+        self.outputs.push(path.display().to_string());
+    }
+
+    /// Record what an aggregated signature signed and the key it was
+    /// checked against.
+    pub fn set_signature(
+        &mut self,
+        message: &[u8],
+        verifying_key: &SigningPublicKey,
+    ) {
+        self.target_digest = Some(hex_message(message));
+        self.verifying_key = Some(verifying_key.ur_string());
+    }
+
+    /// Write the report to `path` when `--report` asked for one.
+    pub fn write_to(&self, path: Option<&Path>) -> Result<()> {
+        match path {
+            Some(path) => self.write(path),
+            None => Ok(()),
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_vec_pretty(self)?;
+        write_atomically(path, json).with_context(|| {
+            format!("Failed to write report to {}", path.display())
+        })
+    }
+}
@@ -0,0 +1,911 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result, anyhow, bail};
+use bc_components::{ARID, Signature, XID};
+use bc_envelope::prelude::*;
+use clap::Parser;
+use frost_ed25519::{self as frost, rand_core::OsRng};
+use serde_json::{Value, json};
+
+use crate::cmd::{
+    common::signing_key_from_verifying,
+    sign::common::{attach_signature, signing_message},
+};
+
+/// Describe the JSON files kept under `group-state/`.
+///
+/// Prints each file's location, the command that writes it, its fields, and
+/// an example built from real FROST values serialized the way the writing
+/// command serializes them.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// File to describe, e.g. `commitments.json` or `commitments` (omit to
+    /// list every file)
+    #[arg(value_name = "ARTIFACT")]
+    artifact: Option<String>,
+
+    /// Print the description as JSON for tooling
+    #[arg(long)]
+    json: bool,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let Some(name) = self.artifact else {
+            if self.json {
+                let all: Vec<Value> =
+                    ARTIFACTS.iter().map(Artifact::to_json).collect();
+                println!("{}", serde_json::to_string_pretty(&all)?);
+            } else {
+                for artifact in ARTIFACTS {
+                    println!("{:<26} {}", artifact.file, artifact.summary);
+                }
+            }
+            return Ok(());
+        };
+
+        let artifact = artifact(&name)?;
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&artifact.to_json())?);
+        } else {
+            artifact.print()?;
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Artifacts
+// -----------------------------------------------------------------------------
+
+/// A documented field. Path segments in angle brackets stand for map keys,
+/// so `commitments.<XID>.share_arid` is the `share_arid` of every entry in
+/// the `commitments` object, keyed by participant XID UR.
+#[derive(Debug, Clone, Copy)]
+pub struct Field {
+    pub path: &'static str,
+    pub ty: &'static str,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+/// A JSON file persisted under `group-state/`.
+#[derive(Debug, Clone, Copy)]
+pub struct Artifact {
+    pub file: &'static str,
+    pub location: &'static str,
+    pub written_by: &'static [&'static str],
+    pub summary: &'static str,
+    /// Documented fields; empty when the whole file is a single FROST value
+    pub fields: &'static [Field],
+    /// The FROST type the whole file (or each map value) is encoded as
+    pub frost_type: Option<&'static str>,
+    pub example: fn(&Sample) -> Value,
+}
+
+const fn field(
+    path: &'static str,
+    ty: &'static str,
+    description: &'static str,
+) -> Field {
+    Field { path, ty, required: true, description }
+}
+
+const fn optional(
+    path: &'static str,
+    ty: &'static str,
+    description: &'static str,
+) -> Field {
+    Field { path, ty, required: false, description }
+}
+
+const GROUP_DIR: &str = "group-state/<GROUP>/";
+const SESSION_DIR: &str = "group-state/<GROUP>/signing/<SESSION>/";
+
+/// Every documented artifact, in the order a ceremony writes them.
+pub const ARTIFACTS: &[Artifact] = &[
+    Artifact {
+        file: "round1_secret.json",
+        location: GROUP_DIR,
+        written_by: &["dkg participant round1"],
+        summary: "This participant's secret DKG Round 1 state",
+        fields: &[],
+        frost_type: Some("frost_ed25519::keys::dkg::round1::SecretPackage"),
+        example: |sample| to_value(&sample.round1_secret),
+    },
+    Artifact {
+        file: "round1_package.json",
+        location: GROUP_DIR,
+        written_by: &["dkg participant round1"],
+        summary: "This participant's public DKG Round 1 package",
+        fields: &[],
+        frost_type: Some("frost_ed25519::keys::dkg::round1::Package"),
+        example: |sample| to_value(&sample.round1_packages[&sample.xids[0]]),
+    },
+    Artifact {
+        file: "collected_round1.json",
+        location: GROUP_DIR,
+        written_by: &["dkg coordinator round1", "dkg participant round2"],
+        summary: "Every participant's DKG Round 1 package",
+        fields: &[field(
+            "<XID>",
+            "object (FROST Round 1 Package)",
+            "Round 1 package of the participant with this XID",
+        )],
+        frost_type: Some("frost_ed25519::keys::dkg::round1::Package"),
+        example: |sample| {
+            let map: serde_json::Map<String, Value> = sample
+                .round1_packages
+                .iter()
+                .map(|(xid, package)| (xid.ur_string(), to_value(package)))
+                .collect();
+            Value::Object(map)
+        },
+    },
+    Artifact {
+        file: "round2_secret.json",
+        location: GROUP_DIR,
+        written_by: &["dkg participant round2"],
+        summary: "This participant's secret DKG Round 2 state",
+        fields: &[],
+        frost_type: Some("frost_ed25519::keys::dkg::round2::SecretPackage"),
+        example: |sample| to_value(&sample.round2_secret),
+    },
+    Artifact {
+        file: "collected_round2.json",
+        location: GROUP_DIR,
+        written_by: &["dkg coordinator round2"],
+        summary: "DKG Round 2 packages, by sender and then recipient",
+        fields: &[
+            field(
+                "<XID>.response_arid",
+                "string (ur:arid)",
+                "Where the sender listens for the finalize request",
+            ),
+            field(
+                "<XID>.packages.<XID>",
+                "object (FROST Round 2 Package)",
+                "Package from the outer sender for this recipient",
+            ),
+            optional(
+                "<XID>.continuation",
+                "string (ur:envelope)",
+                "The sender's GSTP continuation, returned to it with the \
+                 finalize request",
+            ),
+        ],
+        frost_type: Some("frost_ed25519::keys::dkg::round2::Package"),
+        example: |sample| {
+            let (sender, recipient) = (&sample.xids[0], &sample.xids[1]);
+            json!({
+                sender.ur_string(): {
+                    "response_arid": sample.arid().ur_string(),
+                    "packages": {
+                        recipient.ur_string():
+                            to_value(&sample.round2_package),
+                    },
+                },
+            })
+        },
+    },
+    Artifact {
+        file: "collected_finalize.json",
+        location: GROUP_DIR,
+        written_by: &["dkg coordinator finalize"],
+        summary: "Each participant's finalize result",
+        fields: &[
+            field(
+                "<XID>.key_package",
+                "object (FROST KeyPackage)",
+                "Key package the participant reported",
+            ),
+            field(
+                "<XID>.public_key_package",
+                "object (FROST PublicKeyPackage)",
+                "Group public key package the participant derived",
+            ),
+        ],
+        frost_type: None,
+        example: |sample| {
+            json!({
+                sample.xids[0].ur_string(): {
+                    "key_package": to_value(&sample.key_package),
+                    "public_key_package":
+                        to_value(&sample.public_key_package),
+                },
+            })
+        },
+    },
+    Artifact {
+        file: "key_package.json",
+        location: GROUP_DIR,
+        written_by: &[
+            "dkg participant finalize",
+            "registry group import-share",
+        ],
+        summary: "This participant's key share",
+        fields: &[],
+        frost_type: Some("frost_ed25519::keys::KeyPackage"),
+        example: |sample| to_value(&sample.key_package),
+    },
+    Artifact {
+        file: "public_key_package.json",
+        location: GROUP_DIR,
+        written_by: &[
+            "dkg participant finalize",
+            "keygen",
+            "registry group import-share",
+        ],
+        summary: "The group verifying key and every verifying share",
+        fields: &[],
+        frost_type: Some("frost_ed25519::keys::PublicKeyPackage"),
+        example: |sample| to_value(&sample.public_key_package),
+    },
+    Artifact {
+        file: "start.json",
+        location: SESSION_DIR,
+        written_by: &["sign coordinator invite"],
+        summary: "The coordinator's record of a signing session it started",
+        fields: &[
+            field("session_id", "string (ur:arid)", "Signing session ID"),
+            field(
+                "start_arid",
+                "string (ur:arid)",
+                "Where the signInvite was posted",
+            ),
+            field("group", "string (ur:arid)", "Group ID"),
+            field("min_signers", "integer", "Signing threshold"),
+            field(
+                "participants.<XID>.commit_arid",
+                "string (ur:arid)",
+                "Where this participant posts its commitments",
+            ),
+            field(
+                "participants.<XID>.share_arid",
+                "string (ur:arid)",
+                "Where this participant posts its signature share",
+            ),
+            field("target", "string (ur:envelope)", "Envelope being signed"),
+            optional(
+                "target_arid",
+                "string (ur:arid)",
+                "Where the target was fetched from with --fetch-target",
+            ),
+            field(
+                "include_charter",
+                "boolean",
+                "Whether the target was wrapped with the group charter by \
+                 --include-charter",
+            ),
+        ],
+        frost_type: None,
+        example: |sample| {
+            let participants: serde_json::Map<String, Value> = sample
+                .xids
+                .iter()
+                .map(|xid| {
+                    (
+                        xid.ur_string(),
+                        json!({
+                            "commit_arid": sample.arid().ur_string(),
+                            "share_arid": sample.arid().ur_string(),
+                        }),
+                    )
+                })
+                .collect();
+            json!({
+                "session_id": sample.session.ur_string(),
+                "start_arid": sample.arid().ur_string(),
+                "group": sample.group.ur_string(),
+                "min_signers": 2,
+                "participants": participants,
+                "target": sample.target.ur_string(),
+                "include_charter": false,
+            })
+        },
+    },
+    Artifact {
+        file: "commitments.json",
+        location: SESSION_DIR,
+        written_by: &["sign coordinator round1"],
+        summary: "Signing commitments the coordinator collected",
+        fields: &[
+            field("group", "string (ur:arid)", "Group ID"),
+            field("session", "string (ur:arid)", "Signing session ID"),
+            field("target", "string (ur:envelope)", "Envelope being signed"),
+            field(
+                "commitments.<XID>.commitments",
+                "object (FROST SigningCommitments)",
+                "The participant's signing commitments",
+            ),
+            field(
+                "commitments.<XID>.share_arid",
+                "string (ur:arid)",
+                "Where the participant posts its signature share",
+            ),
+            field(
+                "commitments.<XID>.send_to_arid",
+                "string (ur:arid)",
+                "Where the participant listens for the signRound2 request",
+            ),
+            optional(
+                "recipient",
+                "string (ur:xid)",
+                "The aggregator signature shares are sealed to with --seal-to",
+            ),
+            optional(
+                "detached_statement",
+                "string (ur:envelope)",
+                "What the group signs in place of the target with --detached",
+            ),
+        ],
+        frost_type: Some("frost_ed25519::round1::SigningCommitments"),
+        example: |sample| {
+            let commitments: serde_json::Map<String, Value> = sample
+                .commitments
+                .iter()
+                .map(|(xid, commitments)| {
+                    (
+                        xid.ur_string(),
+                        json!({
+                            "commitments": to_value(commitments),
+                            "share_arid": sample.arid().ur_string(),
+                            "send_to_arid": sample.arid().ur_string(),
+                        }),
+                    )
+                })
+                .collect();
+            json!({
+                "group": sample.group.ur_string(),
+                "session": sample.session.ur_string(),
+                "target": sample.target.ur_string(),
+                "commitments": commitments,
+            })
+        },
+    },
+    Artifact {
+        file: "sign_receive.json",
+        location: SESSION_DIR,
+        written_by: &["sign participant receive"],
+        summary: "A participant's copy of a received signInvite",
+        fields: &[
+            field(
+                "request_envelope",
+                "string (ur:envelope)",
+                "The sealed signInvite request as received",
+            ),
+            field("group", "string (ur:arid)", "Group ID"),
+            field("session", "string (ur:arid)", "Signing session ID"),
+            field(
+                "coordinator",
+                "string (ur:xid)",
+                "XID of the coordinator that sent the invite",
+            ),
+            field("min_signers", "integer", "Signing threshold"),
+            field(
+                "response_arid",
+                "string (ur:arid)",
+                "Where the commitments response is posted",
+            ),
+            field(
+                "participants",
+                "array of string (ur:xid)",
+                "Every participant invited to sign",
+            ),
+            field("target", "string (ur:envelope)", "Envelope being signed"),
+        ],
+        frost_type: None,
+        example: |sample| {
+            json!({
+                "request_envelope":
+                    Envelope::new("signInvite").ur_string(),
+                "group": sample.group.ur_string(),
+                "session": sample.session.ur_string(),
+                "coordinator": sample.xids[0].ur_string(),
+                "min_signers": 2,
+                "response_arid": sample.arid().ur_string(),
+                "participants": sample
+                    .xids
+                    .iter()
+                    .map(|xid| xid.ur_string())
+                    .collect::<Vec<_>>(),
+                "target": sample.target.ur_string(),
+            })
+        },
+    },
+    Artifact {
+        file: "commit.json",
+        location: SESSION_DIR,
+        written_by: &["sign participant round1"],
+        summary: "A participant's nonces and commitments for one session",
+        fields: &[
+            field("session", "string (ur:arid)", "Signing session ID"),
+            field(
+                "response_arid",
+                "string (ur:arid)",
+                "Where the commitments response was posted",
+            ),
+            field(
+                "next_share_arid",
+                "string (ur:arid)",
+                "Where the participant listens for the signRound2 request",
+            ),
+            field("target", "string (ur:envelope)", "Envelope being signed"),
+            optional(
+                "signing_nonces",
+                "object (FROST SigningNonces)",
+                "Secret nonces; absent when an external signer holds them",
+            ),
+            field(
+                "signing_commitments",
+                "object (FROST SigningCommitments)",
+                "Commitments sent to the coordinator",
+            ),
+            optional(
+                "external_signer",
+                "boolean",
+                "true when the commitments came from --commitments-file",
+            ),
+            optional(
+                "nonce_handle",
+                "string",
+                "The external signer's opaque reference to its nonces",
+            ),
+        ],
+        frost_type: None,
+        example: |sample| {
+            json!({
+                "session": sample.session.ur_string(),
+                "response_arid": sample.arid().ur_string(),
+                "next_share_arid": sample.arid().ur_string(),
+                "target": sample.target.ur_string(),
+                "signing_nonces": to_value(&sample.nonces),
+                "signing_commitments":
+                    to_value(&sample.commitments[&sample.xids[0]]),
+            })
+        },
+    },
+    Artifact {
+        file: "share.json",
+        location: SESSION_DIR,
+        written_by: &["sign participant round2"],
+        summary: "A participant's signature share for one session",
+        fields: &[
+            field("session", "string (ur:arid)", "Signing session ID"),
+            field(
+                "response_arid",
+                "string (ur:arid)",
+                "Where the share was posted",
+            ),
+            field(
+                "finalize_arid",
+                "string (ur:arid)",
+                "Where the participant listens for the signFinalize event",
+            ),
+            field(
+                "signature_share",
+                "object (FROST SignatureShare)",
+                "The participant's signature share",
+            ),
+            field(
+                "commitments.<XID>",
+                "object (FROST SigningCommitments)",
+                "Every signer's commitments from the signRound2 request",
+            ),
+            optional(
+                "detached_statement",
+                "string (ur:envelope)",
+                "The coordinator's detached statement, signed in place of \
+                 the target",
+            ),
+        ],
+        frost_type: None,
+        example: |sample| {
+            json!({
+                "session": sample.session.ur_string(),
+                "response_arid": sample.arid().ur_string(),
+                "finalize_arid": sample.arid().ur_string(),
+                "signature_share":
+                    to_value(&sample.shares[&sample.xids[0]]),
+                "commitments": sample.commitments_json(),
+            })
+        },
+    },
+    Artifact {
+        file: "final.json",
+        location: SESSION_DIR,
+        written_by: &["sign coordinator round2", "sign participant finalize"],
+        summary: "The aggregated signature and the shares behind it",
+        fields: &[
+            field("group", "string (ur:arid)", "Group ID"),
+            field("session", "string (ur:arid)", "Signing session ID"),
+            field(
+                "signature",
+                "string (ur:signature)",
+                "Aggregated group signature",
+            ),
+            field(
+                "signature_shares.<XID>",
+                "object (FROST SignatureShare)",
+                "Each signer's share",
+            ),
+            field(
+                "signers",
+                "array of {xid: ur:xid, identifier: hex string}",
+                "The subset of the group whose shares were aggregated, \
+                 with each signer's FROST identifier",
+            ),
+            optional(
+                "finalize_arids.<XID>",
+                "string (ur:arid)",
+                "Where each signer was sent the signFinalize event \
+                 (coordinator only)",
+            ),
+            optional(
+                "commitments.<XID>",
+                "object (FROST SigningCommitments)",
+                "Each signer's commitments (participant only, or a \
+                 coordinator that signs)",
+            ),
+            optional(
+                "finalize_arid",
+                "string (ur:arid)",
+                "Where the signFinalize event was received (participant \
+                 only, or a coordinator that signs)",
+            ),
+            optional(
+                "signed_target",
+                "string (ur:envelope)",
+                "The target with the signature attached (participant only, \
+                 or a coordinator that signs)",
+            ),
+        ],
+        frost_type: None,
+        example: |sample| {
+            let shares: serde_json::Map<String, Value> = sample
+                .shares
+                .iter()
+                .map(|(xid, share)| (xid.ur_string(), to_value(share)))
+                .collect();
+            let finalize_arids: serde_json::Map<String, Value> = sample
+                .xids
+                .iter()
+                .map(|xid| (xid.ur_string(), json!(sample.arid().ur_string())))
+                .collect();
+            let signers: Vec<Value> = sample
+                .xids
+                .iter()
+                .zip(1u16..)
+                .map(|(xid, index)| {
+                    let identifier = frost::Identifier::try_from(index)
+                        .expect("small indexes are valid identifiers");
+                    json!({
+                        "xid": xid.ur_string(),
+                        "identifier": to_value(&identifier),
+                    })
+                })
+                .collect();
+            json!({
+                "group": sample.group.ur_string(),
+                "session": sample.session.ur_string(),
+                "signature": sample.signature.ur_string(),
+                "signature_shares": shares,
+                "signers": signers,
+                "finalize_arids": finalize_arids,
+                "commitments": sample.commitments_json(),
+                "finalize_arid": sample.arid().ur_string(),
+                "signed_target": sample.signed_target.ur_string(),
+            })
+        },
+    },
+];
+
+/// Looks up an artifact by file name, with or without `.json`.
+pub fn artifact(name: &str) -> Result<&'static Artifact> {
+    let file = if name.ends_with(".json") {
+        name.to_string()
+    } else {
+        format!("{name}.json")
+    };
+    ARTIFACTS
+        .iter()
+        .find(|artifact| artifact.file == file)
+        .ok_or_else(|| {
+            let known: Vec<&str> =
+                ARTIFACTS.iter().map(|artifact| artifact.file).collect();
+            anyhow!(
+                "Unknown artifact '{name}'; expected one of {}",
+                known.join(", ")
+            )
+        })
+}
+
+impl Artifact {
+    /// An example of this file, built from a fresh 2-of-2 ceremony.
+    pub fn example(&self) -> Result<Value> {
+        Ok((self.example)(&Sample::new()?))
+    }
+
+    fn print(&self) -> Result<()> {
+        println!("{}", self.file);
+        println!("  Location:   {}{}", self.location, self.file);
+        println!("  Written by: frost {}", self.written_by.join(", frost "));
+        println!("  {}", self.summary);
+        if let Some(frost_type) = self.frost_type {
+            println!("  FROST type: {frost_type} (serde JSON encoding)");
+        }
+        if !self.fields.is_empty() {
+            println!();
+            println!("Fields:");
+            for field in self.fields {
+                let presence = if field.required { "" } else { " (optional)" };
+                println!("  {}: {}{}", field.path, field.ty, presence);
+                println!("      {}", field.description);
+            }
+        }
+        println!();
+        println!("Example:");
+        println!("{}", serde_json::to_string_pretty(&self.example()?)?);
+        Ok(())
+    }
+
+    fn to_json(&self) -> Value {
+        let fields: Vec<Value> = self
+            .fields
+            .iter()
+            .map(|field| {
+                json!({
+                    "path": field.path,
+                    "type": field.ty,
+                    "required": field.required,
+                    "description": field.description,
+                })
+            })
+            .collect();
+        json!({
+            "file": self.file,
+            "location": format!("{}{}", self.location, self.file),
+            "written_by": self
+                .written_by
+                .iter()
+                .map(|command| format!("frost {command}"))
+                .collect::<Vec<_>>(),
+            "summary": self.summary,
+            "frost_type": self.frost_type,
+            "fields": fields,
+            "example": self.example().unwrap_or(Value::Null),
+        })
+    }
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).expect("FROST values serialize to JSON")
+}
+
+// -----------------------------------------------------------------------------
+// Sample ceremony
+// -----------------------------------------------------------------------------
+
+/// Values from a throwaway 2-of-2 DKG and signing session, used so every
+/// example holds real FROST encodings.
+pub struct Sample {
+    xids: Vec<XID>,
+    group: ARID,
+    session: ARID,
+    target: Envelope,
+    round1_secret: frost::keys::dkg::round1::SecretPackage,
+    round1_packages: BTreeMap<XID, frost::keys::dkg::round1::Package>,
+    round2_secret: frost::keys::dkg::round2::SecretPackage,
+    round2_package: frost::keys::dkg::round2::Package,
+    key_package: frost::keys::KeyPackage,
+    public_key_package: frost::keys::PublicKeyPackage,
+    nonces: frost::round1::SigningNonces,
+    commitments: BTreeMap<XID, frost::round1::SigningCommitments>,
+    shares: BTreeMap<XID, frost::round2::SignatureShare>,
+    signature: Signature,
+    signed_target: Envelope,
+}
+
+impl Sample {
+    fn new() -> Result<Self> {
+        let xids = vec![XID::from_data([1; 32]), XID::from_data([2; 32])];
+        let identifiers: Vec<frost::Identifier> = (1..=2u16)
+            .map(frost::Identifier::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(|e| anyhow!("{e}"))?;
+
+        let mut round1_secrets = Vec::new();
+        let mut round1_by_id = BTreeMap::new();
+        for identifier in &identifiers {
+            let (secret, package) =
+                frost::keys::dkg::part1(*identifier, 2, 2, OsRng)
+                    .map_err(|e| anyhow!("{e}"))?;
+            round1_secrets.push(secret);
+            round1_by_id.insert(*identifier, package);
+        }
+        let others = |me: &frost::Identifier| {
+            round1_by_id
+                .iter()
+                .filter(|(id, _)| *id != me)
+                .map(|(id, package)| (*id, package.clone()))
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        let mut round2_secrets = Vec::new();
+        let mut round2_outgoing = Vec::new();
+        for (secret, identifier) in round1_secrets.iter().zip(&identifiers) {
+            let (round2_secret, packages) =
+                frost::keys::dkg::part2(secret.clone(), &others(identifier))
+                    .map_err(|e| anyhow!("{e}"))?;
+            round2_secrets.push(round2_secret);
+            round2_outgoing.push(packages);
+        }
+
+        let mut key_packages = Vec::new();
+        let mut public_key_package = None;
+        for (index, identifier) in identifiers.iter().enumerate() {
+            let received: BTreeMap<_, _> = round2_outgoing
+                .iter()
+                .zip(&identifiers)
+                .filter(|(_, sender)| *sender != identifier)
+                .map(|(packages, sender)| {
+                    (*sender, packages[identifier].clone())
+                })
+                .collect();
+            let (key_package, public) = frost::keys::dkg::part3(
+                &round2_secrets[index],
+                &others(identifier),
+                &received,
+            )
+            .map_err(|e| anyhow!("{e}"))?;
+            key_packages.push(key_package);
+            public_key_package = Some(public);
+        }
+        let public_key_package =
+            public_key_package.context("Sample ceremony has no signers")?;
+
+        let target = Envelope::new("Budget");
+        let message = signing_message(&target)?;
+        let mut nonces = Vec::new();
+        let mut commitments_by_id = BTreeMap::new();
+        for key_package in &key_packages {
+            let (nonce, commitment) =
+                frost::round1::commit(key_package.signing_share(), &mut OsRng);
+            nonces.push(nonce);
+            commitments_by_id.insert(*key_package.identifier(), commitment);
+        }
+        let signing_package =
+            frost::SigningPackage::new(commitments_by_id.clone(), &message);
+        let mut shares_by_id = BTreeMap::new();
+        for (key_package, nonce) in key_packages.iter().zip(&nonces) {
+            let share =
+                frost::round2::sign(&signing_package, nonce, key_package)
+                    .map_err(|e| anyhow!("{e}"))?;
+            shares_by_id.insert(*key_package.identifier(), share);
+        }
+        let aggregate = frost::aggregate(
+            &signing_package,
+            &shares_by_id,
+            &public_key_package,
+        )
+        .map_err(|e| anyhow!("{e}"))?;
+        let signature_bytes: [u8; 64] = aggregate
+            .serialize()
+            .map_err(|e| anyhow!("{e}"))?
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow!("Aggregated signature is not 64 bytes"))?;
+        let signature = Signature::ed25519_from_data(signature_bytes);
+        let verifying_key =
+            signing_key_from_verifying(public_key_package.verifying_key())?;
+        let signed_target =
+            attach_signature(&target, &signature, &verifying_key)?;
+
+        let round1_packages = by_xid(&identifiers, &xids, &round1_by_id);
+        let commitments = by_xid(&identifiers, &xids, &commitments_by_id);
+        let shares = by_xid(&identifiers, &xids, &shares_by_id);
+        let round2_package = round2_outgoing[0][&identifiers[1]].clone();
+
+        Ok(Self {
+            group: ARID::new(),
+            session: ARID::new(),
+            round1_secret: round1_secrets.remove(0),
+            round1_packages,
+            round2_secret: round2_secrets.remove(0),
+            round2_package,
+            key_package: key_packages.remove(0),
+            public_key_package,
+            nonces: nonces.remove(0),
+            commitments,
+            shares,
+            signature,
+            signed_target,
+            target,
+            xids,
+        })
+    }
+
+    /// A fresh ARID for example fields that name a Hubert location.
+    fn arid(&self) -> ARID { ARID::new() }
+
+    fn commitments_json(&self) -> Value {
+        let map: serde_json::Map<String, Value> = self
+            .commitments
+            .iter()
+            .map(|(xid, commitments)| (xid.ur_string(), to_value(commitments)))
+            .collect();
+        Value::Object(map)
+    }
+}
+
+/// Rekeys a map by FROST identifier to the XID at the same position.
+fn by_xid<T: Clone>(
+    identifiers: &[frost::Identifier],
+    xids: &[XID],
+    map: &BTreeMap<frost::Identifier, T>,
+) -> BTreeMap<XID, T> {
+    identifiers
+        .iter()
+        .zip(xids)
+        .filter_map(|(id, xid)| Some((*xid, map.get(id)?.clone())))
+        .collect()
+}
+
+/// Checks that `example` has every required field of `artifact` and no
+/// undocumented ones. Used to keep the tables above in step with the
+/// examples, which mirror the writers' serialization.
+pub fn check_example(artifact: &Artifact, example: &Value) -> Result<()> {
+    if artifact.fields.is_empty() {
+        return Ok(());
+    }
+    let mut found = Vec::new();
+    collect_paths(artifact, example, "", &mut found);
+    for field in artifact.fields {
+        if field.required && !found.iter().any(|path| path == field.path) {
+            bail!("{} example is missing {}", artifact.file, field.path);
+        }
+    }
+    for path in &found {
+        if !artifact.fields.iter().any(|field| field.path == path) {
+            bail!("{} example has undocumented field {path}", artifact.file);
+        }
+    }
+    Ok(())
+}
+
+/// Walks `value`, recording the documented path of every leaf. Objects whose
+/// keys are XID URs contribute an `<XID>` segment.
+fn collect_paths(
+    artifact: &Artifact,
+    value: &Value,
+    prefix: &str,
+    found: &mut Vec<String>,
+) {
+    let documented = artifact.fields.iter().any(|field| field.path == prefix);
+    let Value::Object(map) = value.clone() else {
+        if !found.iter().any(|path| path == prefix) {
+            found.push(prefix.to_string());
+        }
+        return;
+    };
+    if documented {
+        if !found.iter().any(|path| path == prefix) {
+            found.push(prefix.to_string());
+        }
+        return;
+    }
+    for (key, child) in &map {
+        let segment = if key.starts_with("ur:xid/") {
+            "<XID>"
+        } else {
+            key
+        };
+        let path = if prefix.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{prefix}.{segment}")
+        };
+        collect_paths(artifact, child, &path, found);
+    }
+}
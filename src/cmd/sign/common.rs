@@ -5,34 +5,21 @@
 //!
 //! For cross-cutting utilities shared with DKG, see [`crate::cmd::common`].
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+};
 
-use bc_components::ARID;
+use anyhow::{Context, Result, bail};
+use bc_components::{
+    ARID, Digest, JSON, Signature, SigningPublicKey, Verifier, XID,
+};
 use bc_envelope::prelude::*;
+use frost_ed25519 as frost;
 
-use super::super::common::group_state_dir;
-
-/// Returns the signing state directory for a group (without session).
-///
-/// Path: `{registry_dir}/group-state/{group_id.hex()}/signing`
-pub fn signing_state_dir_for_group(
-    registry_path: &Path,
-    group_id: &ARID,
-) -> PathBuf {
-    group_state_dir(registry_path, group_id).join("signing")
-}
-
-/// Returns the signing state directory for a specific session.
-///
-/// Path: `{registry_dir}/group-state/{group_id.hex()}/signing/{session_id.
-/// hex()}`
-pub fn signing_state_dir(
-    registry_path: &Path,
-    group_id: &ARID,
-    session_id: &ARID,
-) -> PathBuf {
-    signing_state_dir_for_group(registry_path, group_id).join(session_id.hex())
-}
+use super::super::common::{GroupStatePaths, write_atomically};
+use crate::registry::GroupRecord;
 
 /// Content wrapper for signFinalize events.
 ///
@@ -84,3 +71,799 @@ impl TryFrom<Envelope> for SignFinalizeContent {
         Ok(Self { envelope })
     }
 }
+
+// -----------------------------------------------------------------------------
+// Signing target
+// -----------------------------------------------------------------------------
+
+/// Type of a target envelope whose subject is an externally computed message
+/// (for example the SHA-256 of a document) that is signed byte-for-byte.
+pub const PRE_HASHED_TYPE: &str = "preHashedMessage";
+
+/// Builds the target envelope for a pre-hashed message.
+pub fn pre_hashed_target(message: &[u8]) -> Envelope {
+    Envelope::new(CBOR::to_byte_string(message)).add_type(PRE_HASHED_TYPE)
+}
+
+/// Returns true if the target envelope carries a pre-hashed message.
+pub fn is_pre_hashed(target: &Envelope) -> bool {
+    target.check_type(PRE_HASHED_TYPE).is_ok()
+}
+
+/// Predicate naming the group charter embedded in a target.
+pub const CHARTER_PREDICATE: &str = "charter";
+
+/// Wraps `target`, asserts the group's `charter` on it, and wraps the result
+/// again so the charter is part of the subject digest the group signs.
+///
+/// This changes the digest, and so the signed bytes, relative to `target`.
+pub fn with_charter(target: &Envelope, charter: &str) -> Envelope {
+    target
+        .wrap()
+        .add_assertion(CHARTER_PREDICATE, charter)
+        .wrap()
+}
+
+/// Returns the charter embedded by [`with_charter`], if the target has one.
+pub fn embedded_charter(target: &Envelope) -> Option<String> {
+    target
+        .try_unwrap()
+        .ok()?
+        .extract_object_for_predicate(CHARTER_PREDICATE)
+        .ok()
+}
+
+/// Returns the exact bytes FROST signs for a target: the raw message for
+/// pre-hashed targets, otherwise the digest of the envelope's subject.
+///
+/// Assertions are not part of the subject digest, so a target that already
+/// carries other parties' signatures yields the same message they signed.
+pub fn signing_message(target: &Envelope) -> Result<Vec<u8>> {
+    if is_pre_hashed(target) {
+        target
+            .subject()
+            .try_leaf()?
+            .try_byte_string()
+            .context("Pre-hashed target subject is not a byte string")
+    } else {
+        Ok(target.subject().digest().data().to_vec())
+    }
+}
+
+/// Attaches the aggregated signature to the target envelope.
+///
+/// Existing assertions, including other signatures, are kept, so the group
+/// can co-sign an envelope that others have already signed.
+///
+/// Ordinary targets are also verified as signed envelopes. A pre-hashed
+/// signature covers the message bytes rather than the envelope digest, so it
+/// is only checkable against the message itself.
+pub fn attach_signature(
+    target: &Envelope,
+    signature: &Signature,
+    verifying_key: &SigningPublicKey,
+) -> Result<Envelope> {
+    let signed = target
+        .clone()
+        .add_assertion(bc_envelope::known_values::SIGNED, signature.clone());
+    if !is_pre_hashed(target) {
+        signed.verify_signature_from(verifying_key).context(
+            "Aggregated signature did not verify on target envelope",
+        )?;
+    }
+    Ok(signed)
+}
+
+/// Parses a hex-encoded pre-hashed message.
+pub fn parse_hex_message(input: &str) -> Result<Vec<u8>> {
+    let hex = input.trim();
+    if hex.is_empty() {
+        bail!("Message digest cannot be empty");
+    }
+    if hex.len() % 2 != 0 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        bail!("Message digest must be an even number of hex digits");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .context("Invalid hex in message digest")
+        })
+        .collect()
+}
+
+/// Hex-encodes the bytes FROST signed, the inverse of [`parse_hex_message`],
+/// so operators can record exactly what a session signed.
+pub fn hex_message(message: &[u8]) -> String {
+    message.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// -----------------------------------------------------------------------------
+// Detached signatures
+// -----------------------------------------------------------------------------
+
+/// Type of a detached group signature envelope.
+pub const DETACHED_SIGNATURE_TYPE: &str = "frostDetachedSignature";
+
+/// A group signature carried apart from its target, together with the
+/// metadata describing how it was produced.
+///
+/// The group signs a statement rather than the target itself: an envelope
+/// whose subject is what the target alone would have been signed over (the
+/// target subject's digest, or the raw message for pre-hashed targets),
+/// asserting the group, the signers, and the date, and wrapped so all of it
+/// falls under the signed digest. The coordinator builds the statement once
+/// round 1 has fixed the signer set, and every signer checks it against its
+/// own view of the session before signing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetachedSignature {
+    pub message: Vec<u8>,
+    pub pre_hashed: bool,
+    pub signature: Signature,
+    pub verifying_key: SigningPublicKey,
+    pub group_id: ARID,
+    pub signers: Vec<XID>,
+    pub date: Date,
+}
+
+impl DetachedSignature {
+    /// Builds the statement a detached session signs in place of `target`.
+    pub fn statement(
+        target: &Envelope,
+        group_id: ARID,
+        signers: impl IntoIterator<Item = XID>,
+        date: Date,
+    ) -> Result<Envelope> {
+        let mut signers: Vec<XID> = signers.into_iter().collect();
+        signers.sort();
+        signers.dedup();
+        statement_envelope(
+            &signing_message(target)?,
+            is_pre_hashed(target),
+            group_id,
+            &signers,
+            date,
+        )
+    }
+
+    /// Checks that a statement received in a signRound2 request describes
+    /// this session: `target`, `group_id`, and exactly the participants whose
+    /// commitments the request carries.
+    pub fn check_statement(
+        statement: &Envelope,
+        target: &Envelope,
+        group_id: &ARID,
+        signers: impl IntoIterator<Item = XID>,
+    ) -> Result<()> {
+        let (message, pre_hashed, statement_group, statement_signers, _) =
+            parse_statement(statement)?;
+        if pre_hashed != is_pre_hashed(target)
+            || message != signing_message(target)?
+        {
+            bail!("Detached statement does not cover the session target");
+        }
+        if statement_group != *group_id {
+            bail!(
+                "Detached statement names group {}, not this session's group",
+                statement_group.ur_string()
+            );
+        }
+        let mut signers: Vec<XID> = signers.into_iter().collect();
+        signers.sort();
+        signers.dedup();
+        if statement_signers != signers {
+            bail!(
+                "Detached statement signers do not match the participants \
+                 who committed"
+            );
+        }
+        Ok(())
+    }
+
+    /// Describes `signature`, made by the group over `statement`, as a
+    /// detached signature.
+    pub fn from_statement(
+        statement: &Envelope,
+        signature: Signature,
+        verifying_key: SigningPublicKey,
+    ) -> Result<Self> {
+        let (message, pre_hashed, group_id, signers, date) =
+            parse_statement(statement)?;
+        Ok(Self {
+            message,
+            pre_hashed,
+            signature,
+            verifying_key,
+            group_id,
+            signers,
+            date,
+        })
+    }
+
+    /// Returns the statement the group signed.
+    pub fn to_statement(&self) -> Result<Envelope> {
+        statement_envelope(
+            &self.message,
+            self.pre_hashed,
+            self.group_id,
+            &self.signers,
+            self.date,
+        )
+    }
+
+    pub fn to_envelope(&self) -> Result<Envelope> {
+        Ok(self
+            .to_statement()?
+            .add_assertion(
+                bc_envelope::known_values::SIGNED,
+                self.signature.clone(),
+            )
+            .add_assertion("verifyingKey", self.verifying_key.clone()))
+    }
+
+    /// Decodes a detached signature envelope and checks its signature, over
+    /// the whole statement, against the embedded verifying key.
+    ///
+    /// Callers still need to decide whether to trust that key, for example by
+    /// comparing it with the group's key in the registry.
+    pub fn verify(envelope: &Envelope) -> Result<Self> {
+        let statement = envelope.subject();
+        if !statement.is_wrapped()
+            || statement
+                .try_unwrap()?
+                .check_type(DETACHED_SIGNATURE_TYPE)
+                .is_err()
+        {
+            bail!("Envelope is not a detached group signature");
+        }
+        let signature: Signature = envelope
+            .extract_object_for_predicate(bc_envelope::known_values::SIGNED)?;
+        let verifying_key: SigningPublicKey =
+            envelope.extract_object_for_predicate("verifyingKey")?;
+        if envelope.verify_signature_from(&verifying_key).is_err() {
+            bail!("Detached signature does not verify against its group key");
+        }
+        Self::from_statement(&statement, signature, verifying_key)
+    }
+
+    /// Returns true if this signature was made over `target`.
+    pub fn covers(&self, target: &Envelope) -> Result<bool> {
+        Ok(is_pre_hashed(target) == self.pre_hashed
+            && signing_message(target)? == self.message)
+    }
+}
+
+fn statement_envelope(
+    message: &[u8],
+    pre_hashed: bool,
+    group_id: ARID,
+    signers: &[XID],
+    date: Date,
+) -> Result<Envelope> {
+    let subject = if pre_hashed {
+        Envelope::new(CBOR::to_byte_string(message))
+    } else {
+        Envelope::new(Digest::from_data_ref(message)?)
+    };
+    let mut statement = subject
+        .add_type(DETACHED_SIGNATURE_TYPE)
+        .add_assertion("group", group_id)
+        .add_assertion("date", date);
+    for signer in signers {
+        statement = statement.add_assertion("signer", *signer);
+    }
+    Ok(statement.wrap())
+}
+
+/// Reads the message, pre-hashed flag, group, sorted signers, and date from
+/// a wrapped statement.
+fn parse_statement(
+    statement: &Envelope,
+) -> Result<(Vec<u8>, bool, ARID, Vec<XID>, Date)> {
+    let inner = statement
+        .try_unwrap()
+        .context("Detached statement is not a wrapped envelope")?;
+    inner
+        .check_type(DETACHED_SIGNATURE_TYPE)
+        .context("Envelope is not a detached group signature statement")?;
+    let subject = inner.subject();
+    let (message, pre_hashed) = match subject.extract_subject::<Digest>() {
+        Ok(digest) => (digest.data().to_vec(), false),
+        Err(_) => (
+            subject.try_leaf()?.try_byte_string().context(
+                "Detached signature subject is neither a digest nor a message",
+            )?,
+            true,
+        ),
+    };
+    let group_id: ARID = inner.extract_object_for_predicate("group")?;
+    let date: Date = inner.extract_object_for_predicate("date")?;
+    let mut signers = inner
+        .objects_for_predicate("signer")
+        .iter()
+        .map(|object| object.extract_subject::<XID>())
+        .collect::<bc_envelope::Result<Vec<XID>>>()?;
+    if signers.is_empty() {
+        bail!("Detached signature lists no signers");
+    }
+    signers.sort();
+    Ok((message, pre_hashed, group_id, signers, date))
+}
+
+// -----------------------------------------------------------------------------
+// Group status
+// -----------------------------------------------------------------------------
+
+/// Refuses to sign with a group whose DKG has not been finalized: until then
+/// there is no group key and no key package to sign with.
+pub fn require_finalized(group_record: &GroupRecord) -> Result<()> {
+    if !group_record.status().is_finalized() {
+        bail!(
+            "Group DKG is not complete; run `frost dkg participant finalize` \
+             or `frost dkg coordinator finalize` before signing"
+        );
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// Commitment quorum
+// -----------------------------------------------------------------------------
+
+/// Ensures enough session participants committed to dispatch signRound2.
+///
+/// A normal run requires every participant; a resumed run proceeds once at
+/// least `min_signers` have committed. In an n-of-n group those are the same
+/// requirement, and a missing participant is reported as such.
+pub fn check_commitment_quorum(
+    participants: &[XID],
+    committed: &[XID],
+    min_signers: usize,
+    resume: bool,
+) -> Result<()> {
+    let missing: Vec<String> = participants
+        .iter()
+        .filter(|xid| !committed.contains(xid))
+        .map(|xid| xid.ur_string())
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    if min_signers >= participants.len() {
+        bail!(
+            "This is an n-of-n group; all {} participants must sign, but \
+             {} of {} committed; missing: {}",
+            participants.len(),
+            committed.len(),
+            participants.len(),
+            missing.join(", ")
+        );
+    }
+    if resume && committed.len() >= min_signers {
+        eprintln!(
+            "Proceeding with {} of {} participants; missing: {}",
+            committed.len(),
+            participants.len(),
+            missing.join(", ")
+        );
+        return Ok(());
+    }
+    if resume {
+        bail!(
+            "Only {} of {} required commitments collected; missing: {}",
+            committed.len(),
+            min_signers,
+            missing.join(", ")
+        );
+    }
+    bail!("Missing signInvite responses from: {}", missing.join(", "));
+}
+
+// -----------------------------------------------------------------------------
+// Group public key package
+// -----------------------------------------------------------------------------
+
+/// Loads the group's FROST public key package.
+///
+/// Participants read the `public_key_package.json` written by `dkg participant
+/// finalize`; the coordinator falls back to the packages it gathered in
+/// `collected_finalize.json`.
+pub fn load_public_key_package(
+    paths: &GroupStatePaths,
+    group_id: &ARID,
+) -> Result<frost_ed25519::keys::PublicKeyPackage> {
+    let direct_path = paths.public_key_package_path(group_id);
+    if direct_path.exists() {
+        let pkg: frost_ed25519::keys::PublicKeyPackage =
+            serde_json::from_slice(&fs::read(&direct_path).with_context(
+                || format!("Failed to read {}", direct_path.display()),
+            )?)
+            .context("Failed to parse public_key_package.json")?;
+        return Ok(pkg);
+    }
+
+    let collected_path = paths.collected_finalize_path(group_id);
+    if collected_path.exists() {
+        let raw: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(&fs::read(&collected_path).with_context(
+                || format!("Failed to read {}", collected_path.display()),
+            )?)
+            .context("Invalid collected_finalize.json")?;
+
+        let first_entry = raw
+            .values()
+            .next()
+            .context("collected_finalize.json is empty")?;
+        let public_key_value = first_entry
+            .as_object()
+            .and_then(|m| m.get("public_key_package"))
+            .cloned()
+            .context("public_key_package missing in collected_finalize.json")?;
+
+        let pkg: frost_ed25519::keys::PublicKeyPackage =
+            serde_json::from_value(public_key_value)
+                .context("Failed to parse public_key_package")?;
+        return Ok(pkg);
+    }
+
+    bail!(
+        "Public key package not found for group {}; run `frost dkg participant finalize` or `frost dkg coordinator finalize` first",
+        group_id.ur_string()
+    );
+}
+
+// -----------------------------------------------------------------------------
+// Signature share checks
+// -----------------------------------------------------------------------------
+
+/// Checks every signature share against its signer's verifying share before
+/// the shares are aggregated.
+///
+/// Aggregation fails on a bad share without saying whose it was; checking
+/// each share on its own names the participants who sent invalid ones.
+pub fn verify_signature_shares(
+    signing_package: &frost::SigningPackage,
+    signature_shares: &BTreeMap<XID, frost::round2::SignatureShare>,
+    xid_to_identifier: &HashMap<XID, frost::Identifier>,
+    public_key_package: &frost::keys::PublicKeyPackage,
+) -> Result<()> {
+    let mut invalid = Vec::new();
+    for (xid, share) in signature_shares {
+        let identifier = xid_to_identifier.get(xid).with_context(|| {
+            format!("Unknown participant {}", xid.ur_string())
+        })?;
+        let verifying_share = public_key_package
+            .verifying_shares()
+            .get(identifier)
+            .with_context(|| {
+                format!(
+                    "Group public key package has no verifying share for {}",
+                    xid.ur_string()
+                )
+            })?;
+        if frost_core::verify_signature_share(
+            *identifier,
+            verifying_share,
+            share,
+            signing_package,
+            public_key_package.verifying_key(),
+        )
+        .is_err()
+        {
+            invalid.push(xid.ur_string());
+        }
+    }
+    match invalid.as_slice() {
+        [] => Ok(()),
+        [xid] => {
+            bail!("Participant {xid} submitted an invalid signature share")
+        }
+        _ => bail!(
+            "Participants {} submitted invalid signature shares",
+            invalid.join(", ")
+        ),
+    }
+}
+
+/// Checks the commitments a participant echoes in its signRound2Response
+/// against the ones stored for it in commitments.json.
+///
+/// The participant signed with the commitments the coordinator sent it, so
+/// the two must be the same commitments. The echo is parsed and compared as
+/// commitments rather than as JSON text, so a participant whose encoder lays
+/// out the same JSON differently still passes. A difference means the stored
+/// commitments changed after collection.
+pub fn check_echoed_commitments(
+    response: &Envelope,
+    stored: &frost::round1::SigningCommitments,
+    participant: &XID,
+) -> Result<()> {
+    let echoed: JSON = response
+        .extract_object_for_predicate("commitments")
+        .with_context(|| {
+            format!(
+                "Signature share response from {} does not echo its \
+                 commitments",
+                participant.ur_string()
+            )
+        })?;
+    let echoed: frost::round1::SigningCommitments =
+        serde_json::from_slice(echoed.as_bytes()).with_context(|| {
+            format!(
+                "Signature share response from {} echoes invalid commitments",
+                participant.ur_string()
+            )
+        })?;
+    if echoed != *stored {
+        bail!(
+            "Commitments stored for {} in commitments.json differ from the \
+             ones it signed with; they may have been altered after collection",
+            participant.ur_string()
+        );
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// Final state
+// -----------------------------------------------------------------------------
+
+/// Refuses to replace a `final.json` whose signature verifies under the
+/// group key unless `force` is set. Even then the new signature must match
+/// the recorded one: a session's group key and target are fixed, so a
+/// different signature means the write belongs to another session.
+pub fn check_final_state_overwrite(
+    final_path: &Path,
+    signature: &Signature,
+    verifying_key: &SigningPublicKey,
+    message: &[u8],
+    force: bool,
+) -> Result<()> {
+    let Some(recorded) = recorded_signature(final_path)? else {
+        return Ok(());
+    };
+    if !verifying_key.verify(&recorded, message) {
+        return Ok(());
+    }
+    if !force {
+        bail!(
+            "{} already records a completed signature; pass --force to \
+             overwrite it",
+            final_path.display()
+        );
+    }
+    if recorded != *signature {
+        bail!(
+            "Signature does not match the one recorded in {}; refusing to \
+             overwrite it",
+            final_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// The `signers` array of `final.json`: the XID and FROST identifier of each
+/// participant whose share went into the aggregated signature, in XID order.
+///
+/// This is the signing subset, not the group roster, so an audit can tell
+/// exactly who signed.
+pub fn signers_json(
+    signature_shares: &BTreeMap<XID, frost::round2::SignatureShare>,
+    xid_to_identifier: &HashMap<XID, frost::Identifier>,
+) -> Result<serde_json::Value> {
+    let mut signers = Vec::new();
+    for xid in signature_shares.keys() {
+        let identifier = xid_to_identifier.get(xid).with_context(|| {
+            format!("Signer {} has no FROST identifier", xid.ur_string())
+        })?;
+        let mut entry = serde_json::Map::new();
+        entry.insert(
+            "xid".to_string(),
+            serde_json::Value::String(xid.ur_string()),
+        );
+        entry.insert(
+            "identifier".to_string(),
+            serde_json::to_value(identifier)
+                .context("Failed to serialize identifier")?,
+        );
+        signers.push(serde_json::Value::Object(entry));
+    }
+    Ok(serde_json::Value::Array(signers))
+}
+
+/// Adds a signer's finalize result to the `final.json` at `final_path`, with
+/// the fields `sign participant finalize` records after a signFinalize event:
+/// the session's commitments, the finalize ARID the result answers, and the
+/// signed target. The file must already hold the aggregated signature.
+pub fn record_finalize_result(
+    final_path: &Path,
+    finalize_arid: &ARID,
+    commitments: &BTreeMap<XID, frost::round1::SigningCommitments>,
+    signed_envelope: &Envelope,
+) -> Result<()> {
+    let mut root: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(&fs::read(final_path).with_context(|| {
+            format!("Failed to read {}", final_path.display())
+        })?)
+        .context("Invalid final.json")?;
+
+    let mut commitments_json = serde_json::Map::new();
+    for (xid, commits) in commitments {
+        commitments_json.insert(
+            xid.ur_string(),
+            serde_json::to_value(commits)
+                .context("Failed to serialize commitments")?,
+        );
+    }
+    root.insert(
+        "commitments".to_string(),
+        serde_json::Value::Object(commitments_json),
+    );
+    root.insert(
+        "finalize_arid".to_string(),
+        serde_json::Value::String(finalize_arid.ur_string()),
+    );
+    root.insert(
+        "signed_target".to_string(),
+        serde_json::Value::String(signed_envelope.ur_string()),
+    );
+    write_atomically(final_path, serde_json::to_vec_pretty(&root)?)
+        .with_context(|| format!("Failed to write {}", final_path.display()))
+}
+
+/// Whether the `final.json` at `final_path` already holds the finalize
+/// result for `finalize_arid`, as [`record_finalize_result`] records it.
+pub fn finalized_locally(
+    final_path: &Path,
+    finalize_arid: &ARID,
+) -> Result<bool> {
+    if !final_path.exists() {
+        return Ok(false);
+    }
+    let raw: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(&fs::read(final_path).with_context(|| {
+            format!("Failed to read {}", final_path.display())
+        })?)
+        .context("Invalid final.json")?;
+    Ok(raw.get("finalize_arid").and_then(|v| v.as_str())
+        == Some(finalize_arid.ur_string().as_str()))
+}
+
+fn recorded_signature(final_path: &Path) -> Result<Option<Signature>> {
+    if !final_path.exists() {
+        return Ok(None);
+    }
+    let root: serde_json::Value =
+        serde_json::from_slice(&fs::read(final_path).with_context(|| {
+            format!("Failed to read {}", final_path.display())
+        })?)
+        .with_context(|| {
+            format!("Invalid existing {}", final_path.display())
+        })?;
+    root.get("signature")
+        .and_then(|value| value.as_str())
+        .map(Signature::from_ur_string)
+        .transpose()
+        .context("Invalid signature in existing final.json")
+}
+
+// -----------------------------------------------------------------------------
+// External signers
+// -----------------------------------------------------------------------------
+
+/// Signing commitments generated outside this process, for example by an
+/// HSM that keeps the matching nonces in its secure element.
+#[derive(Debug, Clone)]
+pub struct ExternalCommitments {
+    pub signing_commitments: frost::round1::SigningCommitments,
+    /// Opaque reference the external signer uses to find its nonces; passed
+    /// back to it alongside the signing package.
+    pub nonce_handle: Option<String>,
+}
+
+/// Reads the file given to `sign participant round1 --commitments-file`: a
+/// JSON object with `signing_commitments` and an optional string
+/// `nonce_handle`.
+pub fn read_external_commitments(path: &Path) -> Result<ExternalCommitments> {
+    let raw = read_json_object(path)?;
+    let signing_commitments = serde_json::from_value(
+        raw.get("signing_commitments").cloned().with_context(|| {
+            format!("Missing signing_commitments in {}", path.display())
+        })?,
+    )
+    .with_context(|| {
+        format!("Invalid signing_commitments in {}", path.display())
+    })?;
+    let nonce_handle = match raw.get("nonce_handle") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(value) => Some(
+            value
+                .as_str()
+                .with_context(|| {
+                    format!(
+                        "nonce_handle in {} must be a string",
+                        path.display()
+                    )
+                })?
+                .to_string(),
+        ),
+    };
+    Ok(ExternalCommitments { signing_commitments, nonce_handle })
+}
+
+/// Writes the signing package an external signer needs to produce its
+/// share, together with the nonce handle recorded at commit time.
+pub fn write_external_signing_package(
+    path: &Path,
+    session_id: &ARID,
+    signing_package: &frost::SigningPackage,
+    nonce_handle: Option<&str>,
+) -> Result<()> {
+    let mut root = serde_json::Map::new();
+    root.insert(
+        "session".to_string(),
+        serde_json::Value::String(session_id.ur_string()),
+    );
+    root.insert(
+        "signing_package".to_string(),
+        serde_json::to_value(signing_package)
+            .context("Failed to serialize signing package")?,
+    );
+    if let Some(handle) = nonce_handle {
+        root.insert(
+            "nonce_handle".to_string(),
+            serde_json::Value::String(handle.to_string()),
+        );
+    }
+    write_atomically(path, serde_json::to_vec_pretty(&root)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Reads the file given to `sign participant round2 --share-file`: either a
+/// serialized `SignatureShare` or a JSON object holding one under
+/// `signature_share`.
+pub fn read_external_signature_share(
+    path: &Path,
+) -> Result<frost::round2::SignatureShare> {
+    let mut raw = read_json_object(path)?;
+    let value = match raw.remove("signature_share") {
+        Some(share) => share,
+        None => serde_json::Value::Object(raw),
+    };
+    serde_json::from_value(value).with_context(|| {
+        format!("Invalid signature share in {}", path.display())
+    })
+}
+
+/// Checks a signature share this participant obtained from an external
+/// signer against the signing package and its own verifying share, so a
+/// faulty or mismatched signer is caught before anything is posted.
+pub fn verify_own_signature_share(
+    signing_package: &frost::SigningPackage,
+    signature_share: &frost::round2::SignatureShare,
+    key_package: &frost::keys::KeyPackage,
+) -> Result<()> {
+    frost_core::verify_signature_share(
+        *key_package.identifier(),
+        key_package.verifying_share(),
+        signature_share,
+        signing_package,
+        key_package.verifying_key(),
+    )
+    .map_err(|_| {
+        anyhow::anyhow!(
+            "Signature share does not verify against this participant's \
+             verifying share and the signing package"
+        )
+    })
+}
+
+fn read_json_object(
+    path: &Path,
+) -> Result<serde_json::Map<String, serde_json::Value>> {
+    serde_json::from_slice(
+        &fs::read(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?,
+    )
+    .with_context(|| format!("{} is not a JSON object", path.display()))
+}
@@ -1,18 +1,27 @@
 use std::{collections::HashMap, fs, time::Duration};
 
 use anyhow::{Context, Result, bail};
-use bc_components::{ARID, Digest, XID};
+use bc_components::{ARID, XID};
 use bc_envelope::prelude::*;
 use bc_xid::XIDDocument;
 use clap::Parser;
 use gstp::SealedRequest;
-use tokio::runtime::Runtime;
 
 use crate::{
     cmd::{
-        busy::put_with_indicator, dkg::common::parse_arid_ur, is_verbose,
-        registry::participants_file_path, sign::common::signing_state_dir,
-        storage::StorageClient,
+        busy::{get_with_indicator, put_with_indicator},
+        common::{GroupStatePaths, load_registry, write_atomically},
+        dkg::common::{
+            ensure_owner_is_coordinator, parse_arid_ur, parse_group_id,
+            read_ur_input,
+        },
+        is_verbose,
+        registry::participants_file_path,
+        sign::common::{
+            is_pre_hashed, parse_hex_message, pre_hashed_target,
+            require_finalized, signing_message, with_charter,
+        },
+        storage::{StorageClient, StorageSelection, shared_runtime},
     },
     registry::{GroupParticipant, GroupRecord, OwnerRecord, Registry},
 };
@@ -33,10 +42,58 @@ pub struct CommandArgs {
     preview: bool,
 
     /// Path to a file containing the target envelope UR (will be signed)
-    #[arg(long = "target", value_name = "PATH")]
-    target_envelope: String,
+    #[arg(
+        long = "target",
+        value_name = "PATH",
+        required_unless_present_any = ["digest", "fetch_target"]
+    )]
+    target_envelope: Option<String>,
+
+    /// Download the target envelope already stored in Hubert at this ARID
+    /// and sign it; the ARID is recorded with the session and sent to
+    /// participants so they can fetch and compare the same artifact
+    #[arg(
+        long = "fetch-target",
+        value_name = "UR:ARID",
+        conflicts_with_all = ["target_envelope", "digest", "preview"]
+    )]
+    fetch_target: Option<String>,
+
+    /// Wait up to this many seconds for the --fetch-target envelope
+    #[arg(long = "timeout", value_name = "SECONDS", requires = "fetch_target")]
+    timeout: Option<u64>,
+
+    /// Hex-encoded message to sign exactly as given (for example the SHA-256
+    /// of an external document) instead of a target envelope's digest
+    #[arg(
+        long = "digest",
+        value_name = "HEX",
+        conflicts_with = "target_envelope",
+        requires = "pre_hashed"
+    )]
+    digest: Option<String>,
+
+    /// Confirm that --digest is a pre-hashed message. Participants sign these
+    /// bytes without being able to inspect what they represent
+    #[arg(long = "pre-hashed", requires = "digest")]
+    pre_hashed: bool,
+
+    /// Wrap the target with the group's charter before digesting, so the
+    /// group signs content that names its own charter. This changes the
+    /// digest and therefore the signed bytes
+    #[arg(
+        long = "include-charter",
+        conflicts_with_all = ["digest", "fetch_target"]
+    )]
+    include_charter: bool,
+
+    /// Start a new session even if an unfinished session already signs the
+    /// same target for this group
+    #[arg(long = "new")]
+    new: bool,
 
     /// Group ID to sign with
+    /// (`-` reads it from stdin)
     #[arg(value_name = "GROUP_ID")]
     group_id: String,
 }
@@ -47,9 +104,12 @@ impl CommandArgs {
         if selection.is_some() && self.preview {
             bail!("--preview cannot be used with Hubert storage options");
         }
+        if selection.is_none() && self.fetch_target.is_some() {
+            bail!("--fetch-target requires Hubert storage parameters");
+        }
 
         let registry_path = participants_file_path(self.registry.clone())?;
-        let registry = Registry::load(&registry_path).with_context(|| {
+        let registry = load_registry(&registry_path).with_context(|| {
             format!("Failed to load registry at {}", registry_path.display())
         })?;
 
@@ -58,16 +118,61 @@ impl CommandArgs {
             .context("Registry owner is required")?
             .clone();
 
-        let group_id = parse_arid_ur(&self.group_id)?;
+        let group_id =
+            parse_group_id(&registry, &read_ur_input(&self.group_id)?)?;
         let group_record = registry
             .group(&group_id)
             .context("Group not found in registry")?
             .clone();
 
-        validate_coordinator(&group_record, &owner)?;
+        ensure_owner_is_coordinator(&group_record, &owner)?;
+        require_finalized(&group_record)?;
 
-        let target_envelope = load_envelope_from_path(&self.target_envelope)?;
-        let _target_digest: Digest = target_envelope.subject().digest();
+        let target_arid = match &self.fetch_target {
+            Some(raw) => Some(parse_arid_ur(&read_ur_input(raw)?)?),
+            None => None,
+        };
+        let target_envelope =
+            match (&self.target_envelope, &self.digest, &target_arid) {
+                (Some(path), None, None) => load_envelope_from_path(path)?,
+                (None, Some(digest), None) if self.pre_hashed => {
+                    pre_hashed_target(&parse_hex_message(digest)?)
+                }
+                (None, None, Some(arid)) => {
+                    let selection = selection
+                        .as_ref()
+                        .context("Hubert storage is required")?;
+                    fetch_target(selection, arid, self.timeout)?
+                }
+                _ => {
+                    bail!(
+                        "Specify either --target, --fetch-target, or --digest \
+                     with --pre-hashed"
+                    )
+                }
+            };
+        let target_envelope = if self.include_charter {
+            if group_record.charter().is_empty() {
+                bail!("--include-charter requires a group with a charter");
+            }
+            with_charter(&target_envelope, group_record.charter())
+        } else {
+            target_envelope
+        };
+
+        let paths = GroupStatePaths::new(&registry_path);
+        if !self.preview
+            && !self.new
+            && let Some(existing) =
+                find_open_session(&paths, &group_id, &target_envelope)?
+        {
+            eprintln!(
+                "Warning: session {} is already signing this target and has not finalized; reusing it (pass --new to start another)",
+                existing.session_id.ur_string()
+            );
+            println!("{}", existing.start_arid.ur_string());
+            return Ok(());
+        }
 
         let participants: Vec<GroupParticipant> =
             group_record.participants().to_vec();
@@ -94,6 +199,7 @@ impl CommandArgs {
             owner: &owner,
             registry: &registry,
             participants: &participants,
+            target_arid: target_arid.as_ref(),
             valid_until,
         };
         let request = build_sign_invite_request(&ctx)?;
@@ -105,6 +211,8 @@ impl CommandArgs {
             &group_record,
             &participants,
             &target_envelope,
+            target_arid.as_ref(),
+            self.include_charter,
         );
 
         // Build envelope
@@ -123,12 +231,11 @@ impl CommandArgs {
         }
 
         // Persist and send
-        let signing_dir = signing_state_dir(
-            &registry_path,
-            &group_id,
-            &session_arids.session_id,
-        );
-        persist_session_state(&signing_dir, &state_json)?;
+        persist_session_state(
+            &paths.session_dir(&group_id, &session_arids.session_id),
+            &paths.start_state_path(&group_id, &session_arids.session_id),
+            &state_json,
+        )?;
 
         let selection =
             selection.context("Hubert storage is required for sign start")?;
@@ -173,22 +280,58 @@ impl SessionArids {
 }
 
 // -----------------------------------------------------------------------------
-// Validation
+// Duplicate session detection
 // -----------------------------------------------------------------------------
 
-fn validate_coordinator(
-    group_record: &GroupRecord,
-    owner: &OwnerRecord,
-) -> Result<()> {
-    if group_record.coordinator().xid() != &owner.xid() {
-        bail!(
-            "Only the coordinator can start signing. \
-             Coordinator: {}, Owner: {}",
-            group_record.coordinator().xid().ur_string(),
-            owner.xid().ur_string()
-        );
+struct OpenSession {
+    session_id: ARID,
+    start_arid: ARID,
+}
+
+/// Finds an unfinalized session of this group that signs the same message
+/// as `target_envelope`.
+fn find_open_session(
+    paths: &GroupStatePaths,
+    group_id: &ARID,
+    target_envelope: &Envelope,
+) -> Result<Option<OpenSession>> {
+    let message = signing_message(target_envelope)?;
+    let pre_hashed = is_pre_hashed(target_envelope);
+    for session_id in paths.sessions(group_id)? {
+        let start_path = paths.start_state_path(group_id, &session_id);
+        if !start_path.exists()
+            || paths.final_state_path(group_id, &session_id).exists()
+        {
+            continue;
+        }
+        let raw: serde_json::Value = serde_json::from_slice(
+            &fs::read(&start_path).with_context(|| {
+                format!("Failed to read {}", start_path.display())
+            })?,
+        )
+        .with_context(|| format!("Invalid JSON in {}", start_path.display()))?;
+        let get_str = |key: &str| -> Result<String> {
+            raw.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .with_context(|| {
+                    format!("Missing {key} in {}", start_path.display())
+                })
+        };
+        let target = Envelope::from_ur_string(&get_str("target")?)
+            .with_context(|| {
+                format!("Invalid target in {}", start_path.display())
+            })?;
+        if is_pre_hashed(&target) == pre_hashed
+            && signing_message(&target)? == message
+        {
+            return Ok(Some(OpenSession {
+                session_id,
+                start_arid: parse_arid_ur(&get_str("start_arid")?)?,
+            }));
+        }
     }
-    Ok(())
+    Ok(None)
 }
 
 // -----------------------------------------------------------------------------
@@ -233,6 +376,7 @@ struct SignInviteContext<'a> {
     owner: &'a OwnerRecord,
     registry: &'a Registry,
     participants: &'a [GroupParticipant],
+    target_arid: Option<&'a ARID>,
     valid_until: Date,
 }
 
@@ -250,6 +394,9 @@ fn build_sign_invite_request(
     .with_parameter("minSigners", ctx.group_record.min_signers() as u64)
     .with_date(Date::now())
     .with_parameter("validUntil", ctx.valid_until);
+    if let Some(target_arid) = ctx.target_arid {
+        request = request.with_parameter("targetArid", *target_arid);
+    }
 
     for participant in ctx.participants {
         let xid = participant.xid();
@@ -293,6 +440,8 @@ fn build_session_state_json(
     group_record: &GroupRecord,
     participants: &[GroupParticipant],
     target_envelope: &Envelope,
+    target_arid: Option<&ARID>,
+    include_charter: bool,
 ) -> serde_json::Map<String, serde_json::Value> {
     let mut participants_map = serde_json::Map::new();
     for participant in participants {
@@ -341,17 +490,27 @@ fn build_session_state_json(
         "target".to_string(),
         serde_json::Value::String(target_envelope.ur_string()),
     );
+    if let Some(target_arid) = target_arid {
+        root.insert(
+            "target_arid".to_string(),
+            serde_json::Value::String(target_arid.ur_string()),
+        );
+    }
+    root.insert(
+        "include_charter".to_string(),
+        serde_json::Value::Bool(include_charter),
+    );
 
     root
 }
 
 fn persist_session_state(
     signing_dir: &std::path::Path,
+    start_state_path: &std::path::Path,
     state_json: &serde_json::Map<String, serde_json::Value>,
 ) -> Result<()> {
     fs::create_dir_all(signing_dir)?;
-    let start_state_path = signing_dir.join("start.json");
-    fs::write(&start_state_path, serde_json::to_vec_pretty(state_json)?)?;
+    write_atomically(start_state_path, serde_json::to_vec_pretty(state_json)?)?;
     Ok(())
 }
 
@@ -360,24 +519,48 @@ fn persist_session_state(
 // -----------------------------------------------------------------------------
 
 fn post_to_hubert(
-    selection: &crate::cmd::storage::StorageSelection,
+    selection: &StorageSelection,
     arid: &ARID,
     envelope: &Envelope,
 ) -> Result<()> {
-    let runtime = Runtime::new()?;
-    let client = runtime.block_on(async {
-        StorageClient::from_selection(selection.clone()).await
-    })?;
+    let runtime = shared_runtime()?;
+    let client = runtime
+        .block_on(async { StorageClient::shared(selection.clone()).await })?;
 
     if is_verbose() {
         eprintln!("Posting signInvite request to {}", arid.ur_string());
     }
 
-    put_with_indicator(&runtime, &client, arid, envelope, "Signing invite")?;
+    put_with_indicator(runtime, &client, arid, envelope, "Signing invite")?;
 
     Ok(())
 }
 
+fn fetch_target(
+    selection: &StorageSelection,
+    arid: &ARID,
+    timeout: Option<u64>,
+) -> Result<Envelope> {
+    let runtime = shared_runtime()?;
+    let client = runtime
+        .block_on(async { StorageClient::shared(selection.clone()).await })?;
+
+    if is_verbose() {
+        eprintln!("Fetching target envelope from {}", arid.ur_string());
+    }
+
+    let target = get_with_indicator(runtime, &client, arid, "Target", timeout)?
+        .with_context(|| {
+            format!("Nothing posted at target ARID {}", arid.ur_string())
+        })?;
+
+    if is_verbose() {
+        eprintln!("Target digest: {}", target.digest());
+    }
+
+    Ok(target)
+}
+
 // -----------------------------------------------------------------------------
 // File loading
 // -----------------------------------------------------------------------------
@@ -3,10 +3,10 @@ use std::{
     fs,
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
 use bc_components::{ARID, JSON, XID, XIDProvider};
 use bc_envelope::prelude::*;
 use bc_xid::XIDDocument;
@@ -18,14 +18,30 @@ use tokio::runtime::Runtime;
 use crate::{
     cmd::{
         busy::{get_with_indicator, put_with_indicator},
-        dkg::{OptionalStorageSelector, common::parse_arid_ur},
+        common::{
+            GroupStatePaths, collection_timeout, load_registry,
+            slow_participant_timeouts, write_atomically,
+        },
+        dkg::{
+            OptionalStorageSelector,
+            common::{
+                ensure_owner_is_coordinator, parse_arid_ur, parse_group_id,
+                read_ur_input, resolve_participants,
+            },
+        },
         is_verbose,
-        parallel::{CollectionResult, ParallelFetchConfig, parallel_fetch},
+        parallel::{
+            CollectionResult, FetchError, ParallelFetchConfig, parallel_fetch,
+        },
         registry::participants_file_path,
-        sign::common::signing_state_dir,
-        storage::StorageClient,
+        report::{CollectionReport, report_name},
+        sign::common::{DetachedSignature, check_commitment_quorum},
+        storage::{
+            StorageClient, compress_payload, decompress_envelope,
+            shared_runtime,
+        },
     },
-    registry::Registry,
+    registry::{GroupRecord, OwnerRecord, Registry},
 };
 
 /// Collect signInvite responses and dispatch signRound2 requests (coordinator).
@@ -43,6 +59,20 @@ pub struct CommandArgs {
     #[arg(long = "timeout", value_name = "SECONDS")]
     timeout: Option<u64>,
 
+    /// Collect until this RFC 3339 date and time instead of waiting a
+    /// relative --timeout
+    #[arg(long = "deadline", value_name = "DATE", conflicts_with = "timeout")]
+    deadline: Option<String>,
+
+    /// Wait this long for a named slow participant (pet name or ur:xid)
+    /// instead of --timeout; repeatable
+    #[arg(
+        long = "slow-participant",
+        value_name = "PARTICIPANT:SECONDS",
+        conflicts_with = "deadline"
+    )]
+    slow_participants: Vec<String>,
+
     /// Optional group ID to constrain lookup when multiple groups exist
     #[arg(long = "group", value_name = "UR:ARID")]
     group_id: Option<String>,
@@ -55,7 +85,36 @@ pub struct CommandArgs {
     #[arg(long)]
     parallel: bool,
 
+    /// Have participants seal their signature shares to this group member
+    /// (pet name or ur:xid) instead of the coordinator, so it can aggregate
+    /// the signature with `sign coordinator round2`
+    #[arg(long = "seal-to", value_name = "PARTICIPANT")]
+    seal_to: Option<String>,
+
+    /// Have the group sign a detached statement of the target digest, group,
+    /// signers, and date instead of the target itself; `sign coordinator
+    /// round2` then prints a detached signature
+    #[arg(long = "detached")]
+    detached: bool,
+
+    /// Resume an interrupted collection: keep the commitments an earlier run
+    /// saved, fetch only the missing participants, and proceed once at least
+    /// min_signers have committed
+    #[arg(long)]
+    resume: bool,
+
+    /// Proceed as soon as this many participants have committed, cancelling
+    /// the fetches still outstanding (at least the group's min signers)
+    #[arg(long = "min-success", value_name = "N")]
+    min_success: Option<usize>,
+
+    /// Write a JSON report of the collection (per-participant status and
+    /// timing, output files) to this path, even if collection fails
+    #[arg(long = "report", value_name = "PATH")]
+    report: Option<PathBuf>,
+
     /// Signing session ID to collect
+    /// (`-` reads it from stdin)
     #[arg(value_name = "SESSION_ID")]
     session_id: String,
 }
@@ -65,9 +124,11 @@ impl CommandArgs {
         let selection = self.storage.resolve()?;
         let selection =
             selection.context("Hubert storage is required for sign collect")?;
+        let timeout =
+            collection_timeout(self.timeout, self.deadline.as_deref())?;
 
         let registry_path = participants_file_path(self.registry.clone())?;
-        let registry = Registry::load(&registry_path).with_context(|| {
+        let registry = load_registry(&registry_path).with_context(|| {
             format!("Failed to load registry at {}", registry_path.display())
         })?;
 
@@ -76,9 +137,9 @@ impl CommandArgs {
             .context("Registry owner is required")?
             .clone();
 
-        let session_id = parse_arid_ur(&self.session_id)?;
+        let session_id = parse_arid_ur(&read_ur_input(&self.session_id)?)?;
         let group_hint = match self.group_id {
-            Some(raw) => Some(parse_arid_ur(&raw)?),
+            Some(raw) => Some(parse_group_id(&registry, &raw)?),
             None => None,
         };
         let start_state =
@@ -89,47 +150,102 @@ impl CommandArgs {
             .context("Group not found in registry")?
             .clone();
 
-        if group_record.coordinator().xid() != &owner.xid() {
-            bail!(
-                "Only the coordinator can collect signInvite responses. \
-                 Coordinator: {}, Owner: {}",
-                group_record.coordinator().xid().ur_string(),
-                owner.xid().ur_string()
-            );
+        ensure_owner_is_coordinator(&group_record, &owner)?;
+        let slow_timeouts = slow_participant_timeouts(
+            &registry,
+            &group_record,
+            &self.slow_participants,
+        )?;
+        if let Some(min_success) = self.min_success {
+            check_min_success(&start_state, min_success)?;
         }
+        let share_recipient = match &self.seal_to {
+            Some(raw) => Some(resolve_share_recipient(
+                &registry,
+                &group_record,
+                &owner,
+                raw,
+            )?),
+            None => None,
+        };
 
-        let runtime = Runtime::new()?;
-        let client = runtime.block_on(async {
-            StorageClient::from_selection(selection).await
+        let paths = GroupStatePaths::new(&registry_path);
+        let signing_dir = paths.session_dir(&group_id, &session_id);
+        fs::create_dir_all(&signing_dir).with_context(|| {
+            format!(
+                "Failed to create signing state directory {}",
+                signing_dir.display()
+            )
         })?;
+        let commitments_path = paths.commitments_path(&group_id, &session_id);
+        let partial_path =
+            paths.partial_commitments_path(&group_id, &session_id);
+        let mut collected = if self.resume {
+            // Progress from a failed run, else a completed earlier run
+            let saved_path = if partial_path.exists() {
+                &partial_path
+            } else {
+                &commitments_path
+            };
+            load_collected_commitments(saved_path, &session_id, &start_state)?
+        } else {
+            BTreeMap::new()
+        };
+        if self.resume && is_verbose() {
+            eprintln!(
+                "Resuming with {} of {} commitments already collected",
+                collected.len(),
+                start_state.participants.len()
+            );
+        }
+
+        let runtime = shared_runtime()?;
+        let client = runtime
+            .block_on(async { StorageClient::shared(selection).await })?;
 
         if self.parallel {
             // Parallel path with progress display
-            let client = Arc::new(client);
-
             let collection = runtime.block_on(async {
                 collect_sign_round1_parallel(
                     Arc::clone(&client),
                     &registry,
                     &start_state,
+                    &collected,
                     owner.xid_document(),
                     &session_id,
-                    self.timeout,
+                    timeout.seconds(),
+                    slow_timeouts,
+                    self.min_success,
                 )
                 .await
             })?;
+            let report = CollectionReport::from_collection(
+                SIGN_ROUND1_PHASE,
+                &group_id,
+                &collection,
+                |xid| report_name(&registry, xid),
+            )
+            .with_session(&session_id);
 
             process_sign_round1_collection(
-                &runtime,
+                runtime,
                 client,
                 &registry,
                 &owner,
-                &registry_path,
+                &commitments_path,
+                &partial_path,
                 &group_id,
                 &session_id,
                 &start_state,
-                &collection,
+                collection,
+                collected,
                 self.preview_share,
+                share_recipient.as_ref(),
+                self.detached,
+                self.resume,
+                self.min_success,
+                report,
+                self.report.as_deref(),
             )?;
         } else {
             // Sequential path (original behavior)
@@ -141,117 +257,101 @@ impl CommandArgs {
                 );
             }
 
-            let mut commitments: BTreeMap<
-                XID,
-                frost::round1::SigningCommitments,
-            > = BTreeMap::new();
-            let mut send_to_arids: BTreeMap<XID, ARID> = BTreeMap::new();
-            let mut errors: Vec<(XID, String)> = Vec::new();
-
+            let mut collection = CollectionResult::new();
             for (participant, participant_state) in &start_state.participants {
+                if collected.contains_key(participant) {
+                    continue;
+                }
+                if self.min_success.is_some_and(|n| collected.len() >= n) {
+                    break;
+                }
                 let participant_name = registry
                     .participant(participant)
                     .and_then(|r| r.pet_name().map(|s| s.to_owned()))
                     .unwrap_or_else(|| participant.ur_string());
 
-                match fetch_commit_response(
-                    &runtime,
+                let started = Instant::now();
+                let result = fetch_commit_response(
+                    runtime,
                     &client,
                     &participant_state.commit_arid,
-                    self.timeout,
+                    slow_timeouts
+                        .get(participant)
+                        .copied()
+                        .or_else(|| timeout.seconds()),
                     owner.xid_document(),
                     participant,
                     &session_id,
                     &participant_name,
-                ) {
-                    Ok((participant_commitments, next_request_arid)) => {
-                        commitments
-                            .insert(*participant, participant_commitments);
-                        send_to_arids.insert(*participant, next_request_arid);
-                    }
-                    Err(e) => {
-                        errors.push((*participant, e.to_string()));
-                    }
+                );
+                if let Ok(data) = &result {
+                    collected.insert(
+                        *participant,
+                        CollectedCommit {
+                            commitments: data.commitments,
+                            send_to_arid: data.next_request_arid,
+                        },
+                    );
+                    // Save progress so an interrupted run can --resume
+                    persist_commitments(
+                        &partial_path,
+                        &group_id,
+                        &session_id,
+                        &start_state,
+                        &collected,
+                        share_recipient.as_ref(),
+                        None,
+                    )?;
                 }
+                collection.record(*participant, result, started.elapsed());
             }
 
-            if !errors.is_empty() {
-                bail!(
-                    "Sign commit collection incomplete: {} of {} responses failed",
-                    errors.len(),
+            let mut report = CollectionReport::from_collection(
+                SIGN_ROUND1_PHASE,
+                &group_id,
+                &collection,
+                |xid| report_name(&registry, xid),
+            )
+            .with_session(&session_id);
+
+            let partial =
+                accepts_partial(self.resume, self.min_success, &collected);
+            let outcome = if !collection.all_succeeded() && !partial {
+                Err(anyhow!(
+                    "Sign commit collection incomplete: {} of {} responses failed (rerun with --resume to fetch only the missing responses)",
+                    collection.total() - collection.successes.len(),
                     start_state.participants.len()
-                );
-            }
-
-            if commitments.len() != start_state.participants.len() {
-                let missing: Vec<String> = start_state
-                    .participants
-                    .keys()
-                    .filter(|xid| !commitments.contains_key(*xid))
-                    .map(|xid| xid.ur_string())
-                    .collect();
-                bail!(
-                    "Missing signInvite responses from: {}",
-                    missing.join(", ")
-                );
-            }
-
-            // Persist aggregated commitments for this session
-            let signing_dir =
-                signing_state_dir(&registry_path, &group_id, &session_id);
-            fs::create_dir_all(&signing_dir).with_context(|| {
-                format!(
-                    "Failed to create signing state directory {}",
-                    signing_dir.display()
-                )
-            })?;
-
-            let commitments_path = signing_dir.join("commitments.json");
-            let mut commitments_json = serde_json::Map::new();
-            for (xid, commits) in &commitments {
-                let participant_state = start_state.participants.get(xid).expect(
-                    "participant present in start state after earlier validation",
-                );
-
-                let mut entry = serde_json::Map::new();
-                entry.insert(
-                    "commitments".to_string(),
-                    serde_json::to_value(commits)
-                        .context("Failed to serialize commitments")?,
-                );
-                entry.insert(
-                    "share_arid".to_string(),
-                    serde_json::Value::String(
-                        participant_state.share_arid.ur_string(),
-                    ),
-                );
-
-                commitments_json
-                    .insert(xid.ur_string(), serde_json::Value::Object(entry));
+                ))
+            } else {
+                check_collected_commitments(&start_state, &collected, partial)
+            };
+            if !collected.is_empty() {
+                report.add_output(if outcome.is_ok() {
+                    &commitments_path
+                } else {
+                    &partial_path
+                });
             }
-
-            let mut root = serde_json::Map::new();
-            root.insert(
-                "group".to_string(),
-                serde_json::Value::String(group_id.ur_string()),
-            );
-            root.insert(
-                "session".to_string(),
-                serde_json::Value::String(session_id.ur_string()),
-            );
-            root.insert(
-                "target".to_string(),
-                serde_json::Value::String(start_state.target_ur.clone()),
-            );
-            root.insert(
-                "commitments".to_string(),
-                serde_json::Value::Object(commitments_json),
-            );
-
-            fs::write(&commitments_path, serde_json::to_vec_pretty(&root)?)
-                .with_context(|| {
-                    format!("Failed to write {}", commitments_path.display())
-                })?;
+            report.write_to(self.report.as_deref())?;
+            outcome?;
+            let statement = if self.detached {
+                Some(detached_statement(&start_state, &collected)?)
+            } else {
+                None
+            };
+            // Record the final commitment set with this run's share
+            // recipient and detached statement for `sign coordinator round2`
+            persist_commitments(
+                &commitments_path,
+                &group_id,
+                &session_id,
+                &start_state,
+                &collected,
+                share_recipient.as_ref(),
+                statement.as_ref(),
+            )?;
+            discard_partial_commitments(&partial_path)?;
+            let commitments = commitments_by_xid(&collected);
 
             // Build and send signRound2 requests
             let signer_keys = owner
@@ -264,7 +364,7 @@ impl CommandArgs {
             if is_verbose() {
                 eprintln!(
                     "Dispatching signRound2 requests to {} participants...",
-                    send_to_arids.len()
+                    collected.len()
                 );
             } else {
                 // Blank line to separate get phase from put phase
@@ -272,7 +372,7 @@ impl CommandArgs {
             }
 
             let mut preview_printed = false;
-            for (participant, send_to_arid) in &send_to_arids {
+            for (participant, entry) in &collected {
                 let participant_state =
                     start_state.participants.get(participant).expect(
                         "participant present in start state after earlier validation",
@@ -303,6 +403,8 @@ impl CommandArgs {
                     &session_id,
                     participant_state.share_arid,
                     &commitments,
+                    share_recipient.as_ref(),
+                    statement.as_ref(),
                 )?;
 
                 if self.preview_share && !preview_printed {
@@ -326,9 +428,9 @@ impl CommandArgs {
                 )?;
 
                 put_with_indicator(
-                    &runtime,
+                    runtime,
                     &client,
-                    send_to_arid,
+                    &entry.send_to_arid,
                     &sealed_envelope,
                     &participant_name,
                 )?;
@@ -358,6 +460,9 @@ impl CommandArgs {
     }
 }
 
+/// Phase name recorded in `--report` output.
+const SIGN_ROUND1_PHASE: &str = "sign_round1";
+
 #[allow(clippy::too_many_arguments)]
 fn fetch_commit_response(
     runtime: &Runtime,
@@ -368,7 +473,7 @@ fn fetch_commit_response(
     expected_sender: &XID,
     expected_session_id: &ARID,
     participant_name: &str,
-) -> Result<(frost::round1::SigningCommitments, ARID)> {
+) -> Result<SignRound1ResponseData> {
     let envelope = get_with_indicator(
         runtime,
         client,
@@ -376,7 +481,7 @@ fn fetch_commit_response(
         participant_name,
         timeout,
     )?
-    .context("Response not found in Hubert storage")?;
+    .ok_or(FetchError::NotYetAvailable)?;
 
     let coordinator_private_keys =
         coordinator.inception_private_keys().ok_or_else(|| {
@@ -385,59 +490,12 @@ fn fetch_commit_response(
             )
         })?;
 
-    let now = Date::now();
-    let sealed_response = SealedResponse::try_from_encrypted_envelope(
+    validate_and_extract_sign_round1_response(
         &envelope,
-        None,
-        Some(now),
         coordinator_private_keys,
-    )?;
-
-    if sealed_response.sender().xid() != *expected_sender {
-        bail!(
-            "Unexpected response sender: {} (expected {})",
-            sealed_response.sender().xid().ur_string(),
-            expected_sender.ur_string()
-        );
-    }
-
-    if let Ok(error) = sealed_response.error() {
-        let reason = error
-            .object_for_predicate("reason")
-            .ok()
-            .and_then(|e| e.extract_subject::<String>().ok())
-            .unwrap_or_else(|| "unknown reason".to_string());
-        bail!("Participant rejected signInvite: {}", reason);
-    }
-
-    let result = sealed_response
-        .result()
-        .context("Response has no result envelope")?;
-
-    result
-        .check_subject_unit()?
-        .check_type("signRound1Response")?;
-
-    let response_session: ARID =
-        result.extract_object_for_predicate("session")?;
-    if response_session != *expected_session_id {
-        bail!(
-            "Response session {} does not match expected {}",
-            response_session.ur_string(),
-            expected_session_id.ur_string()
-        );
-    }
-
-    let commitments_json: JSON =
-        result.extract_object_for_predicate("commitments")?;
-    let commitments: frost::round1::SigningCommitments =
-        serde_json::from_slice(commitments_json.as_bytes())
-            .context("Failed to deserialize commitments")?;
-
-    let next_request_arid: ARID =
-        result.extract_object_for_predicate("response_arid")?;
-
-    Ok((commitments, next_request_arid))
+        expected_sender,
+        expected_session_id,
+    )
 }
 
 fn build_sign_share_request(
@@ -446,61 +504,79 @@ fn build_sign_share_request(
     session_id: &ARID,
     response_arid: ARID,
     commitments: &BTreeMap<XID, frost::round1::SigningCommitments>,
+    share_recipient: Option<&XIDDocument>,
+    statement: Option<&Envelope>,
 ) -> Result<gstp::SealedRequest> {
     let mut request =
         gstp::SealedRequest::new("signRound2", *session_id, sender)
             .with_parameter("session", *session_id)
             .with_parameter("response_arid", response_arid);
+    if let Some(recipient) = share_recipient {
+        request = request.with_parameter("recipient", recipient.xid());
+    }
+    if let Some(statement) = statement {
+        request = request.with_parameter("detached", statement.clone());
+    }
 
     for (participant, commits) in commitments {
         let commits_json = JSON::from_data(serde_json::to_vec(commits)?);
-        let entry = Envelope::new(*participant)
-            .add_assertion("commitments", CBOR::from(commits_json));
+        let entry = compress_payload(
+            Envelope::new(*participant)
+                .add_assertion("commitments", CBOR::from(commits_json)),
+        )?;
         request = request.with_parameter("commitment", entry);
     }
 
     Ok(request)
 }
 
+/// Builds the `--detached` statement once the signer set is fixed: the
+/// participants whose commitments were collected.
+fn detached_statement(
+    start_state: &StartState,
+    collected: &BTreeMap<XID, CollectedCommit>,
+) -> Result<Envelope> {
+    let target = Envelope::from_ur_string(&start_state.target_ur)
+        .context("Invalid target UR in start state")?;
+    DetachedSignature::statement(
+        &target,
+        start_state.group_id,
+        collected.keys().copied(),
+        Date::now(),
+    )
+}
+
+/// Resolves `--seal-to` to the group member who will open the signature
+/// shares and aggregate the signature in place of the coordinator.
+fn resolve_share_recipient(
+    registry: &Registry,
+    group_record: &GroupRecord,
+    owner: &OwnerRecord,
+    raw: &str,
+) -> Result<XIDDocument> {
+    let (xid, record) =
+        resolve_participants(registry, &[raw.to_owned()])?.remove(0);
+    if xid == owner.xid() {
+        bail!("--seal-to names the coordinator, who is the default recipient");
+    }
+    if !group_record.identifiers()?.contains_key(&xid) {
+        bail!(
+            "--seal-to participant {} is not a member of this group",
+            xid.ur_string()
+        );
+    }
+    Ok(record.xid_document().clone())
+}
+
 fn load_start_state(
     registry_path: &Path,
     session_id: &ARID,
     group_hint: Option<ARID>,
 ) -> Result<StartState> {
-    let base = registry_path
-        .parent()
-        .map(Path::to_path_buf)
-        .unwrap_or_else(|| PathBuf::from("."));
-    let group_state_dir = base.join("group-state");
-
+    let paths = GroupStatePaths::new(registry_path);
     let mut candidate_paths = Vec::new();
-    let group_dirs: Vec<(ARID, PathBuf)> = if let Some(group) = group_hint {
-        vec![(group, group_state_dir.join(group.hex()))]
-    } else {
-        let mut dirs = Vec::new();
-        if group_state_dir.exists() {
-            for entry in fs::read_dir(&group_state_dir)? {
-                let entry = entry?;
-                if entry.file_type()?.is_dir() {
-                    let dir_name = entry.file_name();
-                    if let Some(name) = dir_name.to_str()
-                        && name.len() == 64
-                        && name.chars().all(|c| c.is_ascii_hexdigit())
-                    {
-                        let group_id = ARID::from_hex(name);
-                        dirs.push((group_id, entry.path()));
-                    }
-                }
-            }
-        }
-        dirs
-    };
-
-    for (group_id, group_dir) in group_dirs {
-        let candidate = group_dir
-            .join("signing")
-            .join(session_id.hex())
-            .join("start.json");
+    for group_id in paths.candidate_groups(group_hint)? {
+        let candidate = paths.start_state_path(&group_id, session_id);
         if candidate.exists() {
             candidate_paths.push((group_id, candidate));
         }
@@ -550,13 +626,19 @@ fn load_start_state(
         );
     }
     let target_ur = get_str("target")?;
+    let min_signers: usize = raw
+        .get("min_signers")
+        .and_then(|v| v.as_u64())
+        .context("Missing min_signers in start.json")?
+        .try_into()
+        .context("min_signers does not fit in usize")?;
 
     let participants_val = raw
         .get("participants")
         .and_then(|v| v.as_object())
         .context("Missing participants in start.json")?;
 
-    let mut participants = HashMap::new();
+    let mut participants = BTreeMap::new();
     for (xid_str, value) in participants_val {
         let xid = XID::from_ur_string(xid_str)
             .context("Invalid participant XID in start.json")?;
@@ -582,7 +664,12 @@ fn load_start_state(
         );
     }
 
-    Ok(StartState { group_id: *group_id, target_ur, participants })
+    Ok(StartState {
+        group_id: *group_id,
+        target_ur,
+        min_signers,
+        participants,
+    })
 }
 
 struct StartParticipant {
@@ -593,7 +680,235 @@ struct StartParticipant {
 struct StartState {
     group_id: ARID,
     target_ur: String,
-    participants: HashMap<XID, StartParticipant>,
+    min_signers: usize,
+    participants: BTreeMap<XID, StartParticipant>,
+}
+
+// -----------------------------------------------------------------------------
+// Commitment persistence
+// -----------------------------------------------------------------------------
+
+/// A participant's collected commitments and the ARID at which it awaits its
+/// signRound2 request.
+struct CollectedCommit {
+    commitments: frost::round1::SigningCommitments,
+    send_to_arid: ARID,
+}
+
+fn commitments_by_xid(
+    collected: &BTreeMap<XID, CollectedCommit>,
+) -> BTreeMap<XID, frost::round1::SigningCommitments> {
+    collected
+        .iter()
+        .map(|(xid, entry)| (*xid, entry.commitments))
+        .collect()
+}
+
+/// Ensure enough commitments were collected to dispatch signRound2.
+/// Checks that `--min-success` can be met and still produce a signature.
+fn check_min_success(
+    start_state: &StartState,
+    min_success: usize,
+) -> Result<()> {
+    let participants = start_state.participants.len();
+    if min_success < start_state.min_signers {
+        bail!(
+            "--min-success {min_success} is below the group's min signers \
+             ({})",
+            start_state.min_signers
+        );
+    }
+    if min_success > participants {
+        bail!(
+            "--min-success {min_success} exceeds the {participants} \
+             participants in this session"
+        );
+    }
+    Ok(())
+}
+
+/// Whether collection may proceed without every participant: on `--resume`,
+/// or once `--min-success` commitments are in hand.
+fn accepts_partial(
+    resume: bool,
+    min_success: Option<usize>,
+    collected: &BTreeMap<XID, CollectedCommit>,
+) -> bool {
+    resume || min_success.is_some_and(|n| collected.len() >= n)
+}
+
+fn check_collected_commitments(
+    start_state: &StartState,
+    collected: &BTreeMap<XID, CollectedCommit>,
+    resume: bool,
+) -> Result<()> {
+    let participants: Vec<XID> =
+        start_state.participants.keys().copied().collect();
+    let committed: Vec<XID> = collected.keys().copied().collect();
+    check_commitment_quorum(
+        &participants,
+        &committed,
+        start_state.min_signers,
+        resume,
+    )
+}
+
+fn persist_commitments(
+    path: &Path,
+    group_id: &ARID,
+    session_id: &ARID,
+    start_state: &StartState,
+    collected: &BTreeMap<XID, CollectedCommit>,
+    share_recipient: Option<&XIDDocument>,
+    statement: Option<&Envelope>,
+) -> Result<()> {
+    let mut commitments_json = serde_json::Map::new();
+    for (xid, collected_commit) in collected {
+        let participant_state = start_state
+            .participants
+            .get(xid)
+            .context("Collected commitments from unknown participant")?;
+
+        let mut entry = serde_json::Map::new();
+        entry.insert(
+            "commitments".to_string(),
+            serde_json::to_value(collected_commit.commitments)
+                .context("Failed to serialize commitments")?,
+        );
+        entry.insert(
+            "share_arid".to_string(),
+            serde_json::Value::String(participant_state.share_arid.ur_string()),
+        );
+        entry.insert(
+            "send_to_arid".to_string(),
+            serde_json::Value::String(
+                collected_commit.send_to_arid.ur_string(),
+            ),
+        );
+
+        commitments_json
+            .insert(xid.ur_string(), serde_json::Value::Object(entry));
+    }
+
+    let mut root = serde_json::Map::new();
+    root.insert(
+        "group".to_string(),
+        serde_json::Value::String(group_id.ur_string()),
+    );
+    root.insert(
+        "session".to_string(),
+        serde_json::Value::String(session_id.ur_string()),
+    );
+    root.insert(
+        "target".to_string(),
+        serde_json::Value::String(start_state.target_ur.clone()),
+    );
+    root.insert(
+        "commitments".to_string(),
+        serde_json::Value::Object(commitments_json),
+    );
+    if let Some(recipient) = share_recipient {
+        root.insert(
+            "recipient".to_string(),
+            serde_json::Value::String(recipient.xid().ur_string()),
+        );
+    }
+    if let Some(statement) = statement {
+        root.insert(
+            "detached_statement".to_string(),
+            serde_json::Value::String(statement.ur_string()),
+        );
+    }
+
+    write_atomically(path, serde_json::to_vec_pretty(&root)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Removes `partial_commitments.json` once the final commitment set has been
+/// written to `commitments.json`.
+fn discard_partial_commitments(partial_path: &Path) -> Result<()> {
+    if partial_path.exists() {
+        fs::remove_file(partial_path).with_context(|| {
+            format!("Failed to remove {}", partial_path.display())
+        })?;
+    }
+    Ok(())
+}
+
+/// Load commitments saved by an earlier (possibly interrupted) run.
+fn load_collected_commitments(
+    path: &Path,
+    session_id: &ARID,
+    start_state: &StartState,
+) -> Result<BTreeMap<XID, CollectedCommit>> {
+    let mut collected = BTreeMap::new();
+    if !path.exists() {
+        return Ok(collected);
+    }
+
+    let raw: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(
+            &fs::read(path).with_context(|| {
+                format!("Failed to read {}", path.display())
+            })?,
+        )
+        .context("Invalid commitments.json")?;
+
+    let session_in_state = raw
+        .get("session")
+        .and_then(|v| v.as_str())
+        .context("Missing or invalid session in commitments.json")?;
+    if parse_arid_ur(session_in_state)? != *session_id {
+        bail!(
+            "commitments.json session {} does not match requested session {}",
+            session_in_state,
+            session_id.ur_string()
+        );
+    }
+
+    let commitments_val = raw
+        .get("commitments")
+        .and_then(|v| v.as_object())
+        .context("Missing commitments map in commitments.json")?;
+    for (xid_str, value) in commitments_val {
+        let xid = XID::from_ur_string(xid_str)
+            .context("Invalid participant XID in commitments.json")?;
+        if !start_state.participants.contains_key(&xid) {
+            bail!(
+                "commitments.json lists {} who is not part of this session",
+                xid_str
+            );
+        }
+        let obj = value.as_object().context(
+            "Participant entry is not an object in commitments.json",
+        )?;
+
+        let commitments: frost::round1::SigningCommitments =
+            serde_json::from_value(
+                obj.get("commitments")
+                    .cloned()
+                    .context("Missing commitments value in commitments.json")?,
+            )
+            .context("Failed to parse SigningCommitments")?;
+        let send_to_arid = obj
+            .get("send_to_arid")
+            .and_then(|v| v.as_str())
+            .with_context(|| {
+                format!(
+                    "Missing send_to_arid for {xid_str} in commitments.json; it was written before --resume was supported"
+                )
+            })?;
+
+        collected.insert(
+            xid,
+            CollectedCommit {
+                commitments,
+                send_to_arid: parse_arid_ur(send_to_arid)?,
+            },
+        );
+    }
+
+    Ok(collected)
 }
 
 // -----------------------------------------------------------------------------
@@ -607,17 +922,22 @@ struct SignRound1ResponseData {
 }
 
 /// Collect sign round1 responses in parallel with progress display.
+#[allow(clippy::too_many_arguments)]
 async fn collect_sign_round1_parallel(
     client: Arc<StorageClient>,
     registry: &Registry,
     start_state: &StartState,
+    collected: &BTreeMap<XID, CollectedCommit>,
     coordinator: &XIDDocument,
     session_id: &ARID,
     timeout: Option<u64>,
+    participant_timeouts: HashMap<XID, u64>,
+    min_success: Option<usize>,
 ) -> Result<CollectionResult<SignRound1ResponseData>> {
     let requests: Vec<(XID, ARID, String)> = start_state
         .participants
         .iter()
+        .filter(|(xid, _)| !collected.contains_key(*xid))
         .map(|(xid, state)| {
             let name = registry
                 .participant(xid)
@@ -633,7 +953,12 @@ async fn collect_sign_round1_parallel(
         .clone();
     let session = *session_id;
 
-    let config = ParallelFetchConfig::with_timeout(timeout);
+    // Commitments kept from a resumed run count toward the threshold
+    let still_needed =
+        min_success.map(|n| n.saturating_sub(collected.len()).max(1));
+    let config = ParallelFetchConfig::with_timeout(timeout)
+        .with_min_success(still_needed)
+        .with_participant_timeouts(participant_timeouts);
 
     parallel_fetch(client, requests, config, move |envelope, xid| {
         validate_and_extract_sign_round1_response(
@@ -659,7 +984,8 @@ fn validate_and_extract_sign_round1_response(
         None,
         Some(now),
         coordinator_keys,
-    )?;
+    )
+    .map_err(FetchError::decode)?;
 
     if sealed_response.sender().xid() != *expected_sender {
         bail!(
@@ -675,12 +1001,18 @@ fn validate_and_extract_sign_round1_response(
             .ok()
             .and_then(|e| e.extract_subject::<String>().ok())
             .unwrap_or_else(|| "unknown reason".to_string());
-        bail!("Participant rejected signInvite: {}", reason);
+        return Err(FetchError::Rejected(format!(
+            "Participant rejected signInvite: {reason}"
+        ))
+        .into());
     }
 
-    let result = sealed_response
-        .result()
-        .context("Response has no result envelope")?;
+    let result = decompress_envelope(
+        sealed_response
+            .result()
+            .context("Response has no result envelope")?
+            .clone(),
+    )?;
 
     result
         .check_subject_unit()?
@@ -715,12 +1047,20 @@ fn process_sign_round1_collection(
     client: Arc<StorageClient>,
     registry: &Registry,
     owner: &crate::registry::OwnerRecord,
-    registry_path: &Path,
+    commitments_path: &Path,
+    partial_path: &Path,
     group_id: &ARID,
     session_id: &ARID,
     start_state: &StartState,
-    collection: &CollectionResult<SignRound1ResponseData>,
+    collection: CollectionResult<SignRound1ResponseData>,
+    mut collected: BTreeMap<XID, CollectedCommit>,
     preview_share: bool,
+    share_recipient: Option<&XIDDocument>,
+    detached: bool,
+    resume: bool,
+    min_success: Option<usize>,
+    mut report: CollectionReport,
+    report_path: Option<&Path>,
 ) -> Result<()> {
     use crate::cmd::parallel::parallel_send;
 
@@ -747,77 +1087,66 @@ fn process_sign_round1_collection(
         }
     }
 
-    if !collection.all_succeeded() {
-        bail!(
-            "Sign commit collection incomplete: {} succeeded, {} rejected, {} errors, {} timeouts",
+    // Save whatever arrived before deciding whether to proceed, so an
+    // interrupted or partial collection can be resumed.
+    for (xid, data) in &collection.successes {
+        collected.insert(
+            *xid,
+            CollectedCommit {
+                commitments: data.commitments,
+                send_to_arid: data.next_request_arid,
+            },
+        );
+    }
+    persist_commitments(
+        partial_path,
+        group_id,
+        session_id,
+        start_state,
+        &collected,
+        share_recipient,
+        None,
+    )?;
+
+    let partial = accepts_partial(resume, min_success, &collected);
+    let outcome = if !collection.all_succeeded() && !partial {
+        Err(anyhow!(
+            "Sign commit collection incomplete: {} succeeded, {} rejected, {} errors, {} timeouts (rerun with --resume to fetch only the missing responses)",
             collection.successes.len(),
             collection.rejections.len(),
             collection.errors.len(),
             collection.timeouts.len()
-        );
-    }
-
-    // Build commitments map
-    let commitments: BTreeMap<XID, frost::round1::SigningCommitments> =
-        collection
-            .successes
-            .iter()
-            .map(|(xid, data)| (*xid, data.commitments))
-            .collect();
-
-    // Persist aggregated commitments
-    let signing_dir = signing_state_dir(registry_path, group_id, session_id);
-    fs::create_dir_all(&signing_dir).with_context(|| {
-        format!(
-            "Failed to create signing state directory {}",
-            signing_dir.display()
-        )
-    })?;
-
-    let commitments_path = signing_dir.join("commitments.json");
-    let mut commitments_json = serde_json::Map::new();
-    for (xid, commits) in &commitments {
-        let participant_state = start_state.participants.get(xid).expect(
-            "participant present in start state after earlier validation",
-        );
-
-        let mut entry = serde_json::Map::new();
-        entry.insert(
-            "commitments".to_string(),
-            serde_json::to_value(commits)
-                .context("Failed to serialize commitments")?,
-        );
-        entry.insert(
-            "share_arid".to_string(),
-            serde_json::Value::String(participant_state.share_arid.ur_string()),
-        );
-
-        commitments_json
-            .insert(xid.ur_string(), serde_json::Value::Object(entry));
-    }
+        ))
+    } else {
+        check_collected_commitments(start_state, &collected, partial)
+    };
+    report.add_output(if outcome.is_ok() {
+        commitments_path
+    } else {
+        partial_path
+    });
+    report.write_to(report_path)?;
+    outcome?;
 
-    let mut root = serde_json::Map::new();
-    root.insert(
-        "group".to_string(),
-        serde_json::Value::String(group_id.ur_string()),
-    );
-    root.insert(
-        "session".to_string(),
-        serde_json::Value::String(session_id.ur_string()),
-    );
-    root.insert(
-        "target".to_string(),
-        serde_json::Value::String(start_state.target_ur.clone()),
-    );
-    root.insert(
-        "commitments".to_string(),
-        serde_json::Value::Object(commitments_json),
-    );
+    let statement = if detached {
+        Some(detached_statement(start_state, &collected)?)
+    } else {
+        None
+    };
+    // Record the final commitment set with this run's share recipient and
+    // detached statement for `sign coordinator round2`
+    persist_commitments(
+        commitments_path,
+        group_id,
+        session_id,
+        start_state,
+        &collected,
+        share_recipient,
+        statement.as_ref(),
+    )?;
+    discard_partial_commitments(partial_path)?;
 
-    fs::write(&commitments_path, serde_json::to_vec_pretty(&root)?)
-        .with_context(|| {
-            format!("Failed to write {}", commitments_path.display())
-        })?;
+    let commitments = commitments_by_xid(&collected);
 
     // Build and send signRound2 requests in parallel
     let signer_keys = owner
@@ -830,7 +1159,7 @@ fn process_sign_round1_collection(
     let mut messages: Vec<(XID, ARID, Envelope, String)> = Vec::new();
     let mut preview_printed = false;
 
-    for (xid, data) in &collection.successes {
+    for (xid, entry) in &collected {
         let participant_state = start_state.participants.get(xid).expect(
             "participant present in start state after earlier validation",
         );
@@ -860,6 +1189,8 @@ fn process_sign_round1_collection(
             session_id,
             participant_state.share_arid,
             &commitments,
+            share_recipient,
+            statement.as_ref(),
         )?;
 
         if preview_share && !preview_printed {
@@ -881,7 +1212,7 @@ fn process_sign_round1_collection(
 
         messages.push((
             *xid,
-            data.next_request_arid,
+            entry.send_to_arid,
             sealed_envelope,
             participant_name,
         ));
@@ -921,13 +1252,10 @@ fn process_sign_round1_collection(
         eprintln!();
         eprintln!(
             "Collected {} signInvite responses. Saved to {}",
-            collection.successes.len(),
+            collected.len(),
             display_path.display()
         );
-        eprintln!(
-            "Dispatched {} signRound2 requests.",
-            collection.successes.len()
-        );
+        eprintln!("Dispatched {} signRound2 requests.", collected.len());
     }
 
     Ok(())
@@ -3,10 +3,11 @@ use std::{
     fs,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Instant,
 };
 
 use anyhow::{Context, Result, bail};
-use bc_components::{ARID, Digest, JSON, Verifier, XID, XIDProvider};
+use bc_components::{ARID, JSON, SigningPublicKey, Verifier, XID, XIDProvider};
 use bc_envelope::prelude::*;
 use bc_xid::XIDDocument;
 use clap::Parser;
@@ -17,15 +18,28 @@ use tokio::runtime::Runtime;
 use crate::{
     cmd::{
         busy::{get_with_indicator, put_with_indicator},
-        dkg::common::{parse_arid_ur, signing_key_from_verifying},
+        common::{
+            CollectionTimeout, GroupStatePaths, collection_timeout,
+            load_registry, slow_participant_timeouts, write_atomically,
+        },
+        dkg::common::{
+            ensure_owner_is_coordinator, parse_arid_ur, parse_group_id,
+            read_ur_input, signing_key_from_verifying,
+        },
         is_verbose,
         parallel::{
-            CollectionResult, ParallelFetchConfig, parallel_fetch,
+            CollectionResult, FetchError, ParallelFetchConfig, parallel_fetch,
             parallel_send,
         },
         registry::participants_file_path,
-        sign::common::{SignFinalizeContent, signing_state_dir},
-        storage::StorageClient,
+        report::{CollectionReport, report_name},
+        sign::common::{
+            DetachedSignature, SignFinalizeContent, attach_signature,
+            check_echoed_commitments, check_final_state_overwrite, hex_message,
+            load_public_key_package, record_finalize_result, signers_json,
+            signing_message, verify_signature_shares,
+        },
+        storage::{StorageClient, decompress_envelope, shared_runtime},
     },
     registry::Registry,
 };
@@ -46,6 +60,20 @@ pub struct CommandArgs {
     #[arg(long = "timeout", value_name = "SECONDS")]
     timeout: Option<u64>,
 
+    /// Collect until this RFC 3339 date and time instead of waiting a
+    /// relative --timeout
+    #[arg(long = "deadline", value_name = "DATE", conflicts_with = "timeout")]
+    deadline: Option<String>,
+
+    /// Wait this long for a named slow participant (pet name or ur:xid)
+    /// instead of --timeout; repeatable
+    #[arg(
+        long = "slow-participant",
+        value_name = "PARTICIPANT:SECONDS",
+        conflicts_with = "deadline"
+    )]
+    slow_participants: Vec<String>,
+
     /// Optional group ID to constrain lookup when multiple groups exist
     #[arg(long = "group", value_name = "UR:ARID")]
     group_id: Option<String>,
@@ -58,7 +86,39 @@ pub struct CommandArgs {
     #[arg(long)]
     parallel: bool,
 
+    /// Replace an existing final.json (the new signature must match it)
+    #[arg(long = "force")]
+    force: bool,
+
+    /// Require a detached signature: the session must have been collected
+    /// with `sign coordinator round1 --detached`
+    #[arg(long = "detached")]
+    detached: bool,
+
+    /// Aggregate, verify, and save the signature without posting finalize
+    /// packages; participants waiting in `sign participant finalize` will
+    /// time out
+    #[arg(long = "no-dispatch", conflicts_with = "preview_finalize")]
+    no_dispatch: bool,
+
+    /// Post a finalize package to your own finalize ARID when you are one of
+    /// the signers, instead of recording your result locally
+    #[arg(long = "post-to-self")]
+    post_to_self: bool,
+
+    /// Print only the aggregated signature without attaching it to the
+    /// target; it is still verified against the target digest
+    #[arg(long = "aggregate-only", conflicts_with = "detached")]
+    aggregate_only: bool,
+
+    /// Write a JSON report of the collection (per-participant status and
+    /// timing, output files, and the signed digest and verifying key once
+    /// aggregated) to this path, even if collection fails
+    #[arg(long = "report", value_name = "PATH")]
+    report: Option<PathBuf>,
+
     /// Signing session ID to finalize
+    /// (`-` reads it from stdin)
     #[arg(value_name = "SESSION_ID")]
     session_id: String,
 }
@@ -68,9 +128,11 @@ impl CommandArgs {
         let selection = self.storage.resolve()?;
         let selection = selection
             .context("Hubert storage is required for sign finalize")?;
+        let timeout =
+            collection_timeout(self.timeout, self.deadline.as_deref())?;
 
         let registry_path = participants_file_path(self.registry.clone())?;
-        let registry = Registry::load(&registry_path).with_context(|| {
+        let registry = load_registry(&registry_path).with_context(|| {
             format!("Failed to load registry at {}", registry_path.display())
         })?;
         let owner = registry
@@ -78,9 +140,9 @@ impl CommandArgs {
             .context("Registry owner is required")?
             .clone();
 
-        let session_id = parse_arid_ur(&self.session_id)?;
+        let session_id = parse_arid_ur(&read_ur_input(&self.session_id)?)?;
         let group_hint = match self.group_id {
-            Some(raw) => Some(parse_arid_ur(&raw)?),
+            Some(raw) => Some(parse_group_id(&registry, &raw)?),
             None => None,
         };
 
@@ -92,320 +154,245 @@ impl CommandArgs {
             .context("Group not found in registry")?
             .clone();
 
-        if group_record.coordinator().xid() != &owner.xid() {
+        let commitments_state =
+            load_commitments_state(&registry_path, &group_id, &session_id)?;
+        // Shares sealed to a designated aggregator (`sign coordinator round1
+        // --seal-to`) can only be opened by that participant. Finalize
+        // packages must come from the coordinator, so it does not post them.
+        let aggregator = match commitments_state.recipient {
+            Some(recipient) if recipient == owner.xid() => true,
+            Some(recipient) => bail!(
+                "Signature shares for this session are sealed to {}; run this command from that participant's registry",
+                recipient.ur_string()
+            ),
+            None => {
+                ensure_owner_is_coordinator(&group_record, &owner)?;
+                false
+            }
+        };
+        if self.detached && commitments_state.statement.is_none() {
             bail!(
-                "Only the coordinator can finalize signing. Coordinator: {}, Owner: {}",
-                group_record.coordinator().xid().ur_string(),
-                owner.xid().ur_string()
+                "This session signs the target itself; a detached signature \
+                 needs --detached on `sign coordinator round1`"
             );
         }
+        if aggregator && self.preview_finalize {
+            bail!(
+                "--preview-finalize is unavailable to an aggregator, which does not post finalize packages"
+            );
+        }
+        let slow_timeouts = slow_participant_timeouts(
+            &registry,
+            &group_record,
+            &self.slow_participants,
+        )?;
 
-        let commitments_state =
-            load_commitments_state(&registry_path, &group_id, &session_id)?;
-
-        let runtime = Runtime::new()?;
-        let client = runtime.block_on(async {
-            StorageClient::from_selection(selection).await
-        })?;
+        let runtime = shared_runtime()?;
+        let client = runtime
+            .block_on(async { StorageClient::shared(selection).await })?;
 
-        let xid_to_identifier = xid_identifier_map(&start_state.participants)?;
+        let xid_to_identifier = group_record.identifiers()?;
+        if let Some(xid) = start_state
+            .participants
+            .iter()
+            .find(|xid| !xid_to_identifier.contains_key(xid))
+        {
+            bail!(
+                "Session participant {} is not a member of this group",
+                xid.ur_string()
+            );
+        }
 
         // Collect signature shares - either parallel or sequential
-        let (
-            signature_shares_by_identifier,
-            signature_shares_by_xid,
-            finalize_arids,
-        ) = if self.parallel {
-            // Parallel collection path
-            let client = Arc::new(client);
-            let collection = runtime.block_on(async {
+        let collection = if self.parallel {
+            runtime.block_on(async {
                 collect_shares_parallel(
                     Arc::clone(&client),
                     &registry,
                     &commitments_state,
                     owner.xid_document(),
                     &session_id,
-                    self.timeout,
+                    timeout.seconds(),
+                    slow_timeouts,
                 )
                 .await
-            })?;
-
-            if !collection.all_succeeded() {
-                // Report failures
-                if !collection.rejections.is_empty() {
-                    eprintln!("\nRejections:");
-                    for (xid, reason) in &collection.rejections {
-                        eprintln!("  {}: {}", xid.ur_string(), reason);
-                    }
-                }
-                if !collection.errors.is_empty() {
-                    eprintln!("\nErrors:");
-                    for (xid, error) in &collection.errors {
-                        eprintln!("  {}: {}", xid.ur_string(), error);
-                    }
-                }
-                if !collection.timeouts.is_empty() {
-                    eprintln!("\nTimeouts:");
-                    for xid in &collection.timeouts {
-                        eprintln!("  {}", xid.ur_string());
-                    }
-                }
-                bail!(
-                    "Signature share collection incomplete: {} succeeded, {} rejected, {} errors, {} timeouts",
-                    collection.successes.len(),
-                    collection.rejections.len(),
-                    collection.errors.len(),
-                    collection.timeouts.len()
-                );
-            }
-
-            // Convert collection to maps
-            let mut by_id = BTreeMap::new();
-            let mut by_xid = BTreeMap::new();
-            let mut fin_arids = HashMap::new();
-            for (xid, data) in collection.successes {
-                let identifier = xid_to_identifier
-                    .get(&xid)
-                    .context("Identifier mapping missing for participant")?;
-                by_id.insert(*identifier, data.signature_share);
-                by_xid.insert(xid, data.signature_share);
-                fin_arids.insert(xid, data.finalize_arid);
-            }
-
-            // Continue with parallel finalize dispatch
-            process_aggregation_and_finalize(
-                &runtime,
-                client,
+            })?
+        } else {
+            collect_shares(
+                runtime,
+                &client,
                 &registry,
-                &owner,
-                &registry_path,
-                &group_id,
-                &session_id,
-                &start_state,
                 &commitments_state,
-                &xid_to_identifier,
-                by_id,
-                by_xid,
-                fin_arids,
-                self.preview_finalize,
-                true, // parallel
-            )?;
-
-            return Ok(());
-        } else {
-            // Sequential path (original behavior)
-            if is_verbose() {
-                eprintln!(
-                    "Collecting signature shares for session {} from {} participants...",
-                    session_id.ur_string(),
-                    commitments_state.commitments.len()
-                );
-            }
-
-            let mut signature_shares_by_identifier: BTreeMap<
-                frost::Identifier,
-                frost::round2::SignatureShare,
-            > = BTreeMap::new();
-            let mut signature_shares_by_xid: BTreeMap<
-                XID,
-                frost::round2::SignatureShare,
-            > = BTreeMap::new();
-            let mut finalize_arids: HashMap<XID, ARID> = HashMap::new();
-
-            for (xid, entry) in &commitments_state.commitments {
-                let participant_name = registry
-                    .participant(xid)
-                    .and_then(|r| r.pet_name().map(|s| s.to_owned()))
-                    .unwrap_or_else(|| xid.ur_string());
-
-                let identifier = xid_to_identifier
-                    .get(xid)
-                    .context("Identifier mapping missing for participant")?;
-                let (signature_share, finalize_arid) = fetch_share_response(
-                    &runtime,
-                    &client,
-                    &entry.share_arid,
-                    self.timeout,
-                    owner.xid_document(),
-                    xid,
-                    &session_id,
-                    &participant_name,
-                )?;
-                signature_shares_by_identifier
-                    .insert(*identifier, signature_share);
-                signature_shares_by_xid.insert(*xid, signature_share);
-                finalize_arids.insert(*xid, finalize_arid);
-            }
-
-            (
-                signature_shares_by_identifier,
-                signature_shares_by_xid,
-                finalize_arids,
+                owner.xid_document(),
+                &session_id,
+                &timeout,
+                &slow_timeouts,
             )
         };
 
-        if signature_shares_by_identifier.len() < start_state.min_signers {
-            bail!(
-                "Only collected {} signature shares, need at least {}",
-                signature_shares_by_identifier.len(),
-                start_state.min_signers
-            );
-        }
-
-        // Build signing package
-        let signing_commitments = commitments_with_identifiers(
-            &commitments_state.commitments,
-            &xid_to_identifier,
-        )?;
-        let target_digest: Digest = {
-            let start_state_target =
-                Envelope::from_ur_string(&start_state.target_ur)
-                    .context("Invalid target UR in start state")?;
-            start_state_target.subject().digest()
-        };
-        let signing_package = frost::SigningPackage::new(
-            signing_commitments,
-            target_digest.data(),
-        );
-
-        // Public key package from finalize collection
-        let public_key_package =
-            load_public_key_package(&registry_path, &group_id)?;
-        let verifying_key =
-            signing_key_from_verifying(public_key_package.verifying_key())?;
-
-        let signature = frost_ed25519::aggregate(
-            &signing_package,
-            &signature_shares_by_identifier,
-            &public_key_package,
+        let mut report = CollectionReport::from_collection(
+            SIGN_ROUND2_PHASE,
+            &group_id,
+            &collection,
+            |xid| report_name(&registry, xid),
         )
-        .context("Failed to aggregate signature shares")?;
-
-        // Verify aggregated signature against target digest before dispatch
-        let sig_bytes_vec = signature.serialize()?;
-        let sig_array: [u8; 64] =
-            sig_bytes_vec.as_slice().try_into().map_err(|_| {
-                anyhow::anyhow!("Aggregated signature is not 64 bytes")
-            })?;
-        let final_signature =
-            bc_components::Signature::ed25519_from_data(sig_array);
-        let signature_ur = final_signature.ur_string();
-        if !verifying_key.verify(&final_signature, target_digest.data()) {
+        .with_session(&session_id);
+        if !collection.all_succeeded() {
+            print_collection_failures(&collection);
+            report.write_to(self.report.as_deref())?;
             bail!(
-                "Aggregated signature failed verification against target digest"
+                "Signature share collection incomplete: {} succeeded, {} rejected, {} errors, {} timeouts",
+                collection.successes.len(),
+                collection.rejections.len(),
+                collection.errors.len(),
+                collection.timeouts.len()
             );
         }
 
-        // Attach and verify on the target envelope
-        let signed_envelope = Envelope::from_ur_string(&start_state.target_ur)?
-            .add_assertion(
-                bc_envelope::known_values::SIGNED,
-                final_signature.clone(),
-            );
-        signed_envelope
-            .verify_signature_from(&verifying_key)
-            .context(
-                "Aggregated signature did not verify on target envelope",
-            )?;
-        let signed_envelope_ur = signed_envelope.ur_string();
+        let mut signature_shares_by_identifier = BTreeMap::new();
+        let mut signature_shares_by_xid = BTreeMap::new();
+        let mut finalize_arids = BTreeMap::new();
+        for (xid, data) in collection.successes {
+            let identifier = xid_to_identifier
+                .get(&xid)
+                .context("Identifier mapping missing for participant")?;
+            signature_shares_by_identifier
+                .insert(*identifier, data.signature_share);
+            signature_shares_by_xid.insert(xid, data.signature_share);
+            finalize_arids.insert(xid, data.finalize_arid);
+        }
 
-        persist_final_state(
+        let result = process_aggregation_and_finalize(
+            runtime,
+            client,
+            &registry,
+            &owner,
             &registry_path,
             &group_id,
             &session_id,
-            &final_signature,
-            &signature_shares_by_xid,
-            &finalize_arids,
-        )?;
-
-        if is_verbose() {
-            eprintln!();
-            eprintln!(
-                "Aggregated signature for session {} and prepared {} finalize packages.",
-                session_id.ur_string(),
-                finalize_arids.len()
-            );
-            eprintln!("Signature verified against target and group key.");
-        }
-
-        // Dispatch finalize events to participants (no response expected)
-        let signer_keys = owner
-            .xid_document()
-            .inception_private_keys()
-            .context("Coordinator XID document has no signing keys")?;
-
-        if is_verbose() {
-            eprintln!(
-                "Dispatching finalize packages to {} participants...",
-                finalize_arids.len()
-            );
-        } else {
-            // Blank line to separate get phase from put phase
-            eprintln!();
-        }
+            &start_state,
+            &commitments_state,
+            &xid_to_identifier,
+            signature_shares_by_identifier,
+            signature_shares_by_xid,
+            finalize_arids,
+            self.preview_finalize,
+            self.force,
+            self.no_dispatch || aggregator,
+            self.post_to_self,
+            self.aggregate_only,
+            self.parallel,
+            &mut report,
+        );
+        report.write_to(self.report.as_deref())?;
+        result
+    }
+}
 
-        let mut preview_printed = false;
-        for (participant, finalize_arid) in &finalize_arids {
-            let participant_name = registry
-                .participant(participant)
-                .and_then(|r| r.pet_name().map(|s| s.to_owned()))
-                .unwrap_or_else(|| participant.ur_string());
-
-            let recipient_doc: XIDDocument = if *participant == owner.xid() {
-                owner.xid_document().clone()
-            } else {
-                registry
-                    .participant(participant)
-                    .map(|r| r.xid_document().clone())
-                    .ok_or_else(|| {
-                        anyhow::anyhow!(
-                            "Participant {} not found in registry",
-                            participant.ur_string()
-                        )
-                    })?
-            };
-
-            let event = build_finalize_event(
-                owner.xid_document(),
-                &session_id,
-                &signature_shares_by_xid,
-            )?;
+/// Phase name recorded in `--report` output.
+const SIGN_ROUND2_PHASE: &str = "sign_round2";
 
-            if self.preview_finalize && !preview_printed {
-                // Preview as unsigned, unencrypted envelope
-                let preview = event.to_envelope(
-                    None, // No valid_until needed for events without state
-                    Some(signer_keys),
-                    None,
-                )?;
-                println!(
-                    "# signFinalize preview for {}",
-                    participant.ur_string()
-                );
-                println!("{}", preview.format());
-                preview_printed = true;
-            }
+/// Collect signature shares one participant at a time.
+#[allow(clippy::too_many_arguments)]
+fn collect_shares(
+    runtime: &Runtime,
+    client: &StorageClient,
+    registry: &Registry,
+    commitments_state: &CommitmentsState,
+    coordinator: &XIDDocument,
+    session_id: &ARID,
+    timeout: &CollectionTimeout,
+    participant_timeouts: &HashMap<XID, u64>,
+) -> CollectionResult<SignRound2ResponseData> {
+    if is_verbose() {
+        eprintln!(
+            "Collecting signature shares for session {} from {} participants...",
+            session_id.ur_string(),
+            commitments_state.commitments.len()
+        );
+    }
 
-            let sealed = event.to_envelope_for_recipients(
-                None, // No valid_until needed for events without state
-                Some(signer_keys),
-                &[&recipient_doc],
-            )?;
+    let mut collection = CollectionResult::new();
+    for (xid, entry) in &commitments_state.commitments {
+        let participant_name =
+            report_name(registry, xid).unwrap_or_else(|| xid.ur_string());
+        let started = Instant::now();
+        let result = fetch_share_response(
+            runtime,
+            client,
+            &entry.share_arid,
+            participant_timeouts
+                .get(xid)
+                .copied()
+                .or_else(|| timeout.seconds()),
+            coordinator,
+            xid,
+            session_id,
+            &entry.commitments,
+            &participant_name,
+        );
+        collection.record(*xid, result, started.elapsed());
+    }
+    collection
+}
 
-            put_with_indicator(
-                &runtime,
-                &client,
-                finalize_arid,
-                &sealed,
-                &participant_name,
-            )?;
+/// List participants who rejected, failed, or timed out, each under its own
+/// heading.
+fn print_collection_failures(
+    collection: &CollectionResult<SignRound2ResponseData>,
+) {
+    if !collection.rejections.is_empty() {
+        eprintln!("\nRejections:");
+        for (xid, reason) in &collection.rejections {
+            eprintln!("  {}: {}", xid.ur_string(), reason);
+        }
+    }
+    if !collection.errors.is_empty() {
+        eprintln!("\nErrors:");
+        for (xid, error) in &collection.errors {
+            eprintln!("  {}: {}", xid.ur_string(), error);
+        }
+    }
+    if !collection.timeouts.is_empty() {
+        eprintln!("\nTimeouts:");
+        for xid in &collection.timeouts {
+            eprintln!("  {}", xid.ur_string());
         }
+    }
+}
 
-        // Print the final signature and signed envelope UR after all dispatches
-        println!("{signature_ur}");
+/// Prints the signature UR, then the signed target or detached signature UR
+/// unless `--aggregate-only` left none.
+fn print_signature_output(
+    signature_ur: &str,
+    signed_envelope_ur: Option<&str>,
+) {
+    println!("{signature_ur}");
+    if let Some(signed_envelope_ur) = signed_envelope_ur {
         println!("{signed_envelope_ur}");
+    }
+}
 
-        Ok(())
+/// Reports the aggregated result, including the exact digest signed and the
+/// key it verifies under, so the outcome can be recorded and reconciled.
+fn print_aggregation_summary(
+    session_id: &ARID,
+    finalize_count: usize,
+    message: &[u8],
+    verifying_key: &SigningPublicKey,
+    self_finalized: bool,
+) {
+    eprintln!();
+    eprintln!(
+        "Aggregated signature for session {} and prepared {} finalize packages.",
+        session_id.ur_string(),
+        finalize_count
+    );
+    eprintln!("Signature verified against target and group key.");
+    eprintln!("Target digest: {}", hex_message(message));
+    eprintln!("Verifying key: {}", verifying_key.ur_string());
+    if self_finalized {
+        eprintln!("Recorded your own finalize result locally.");
     }
 }
 
@@ -418,8 +405,9 @@ fn fetch_share_response(
     coordinator: &XIDDocument,
     expected_sender: &XID,
     expected_session_id: &ARID,
+    stored_commitments: &frost::round1::SigningCommitments,
     participant_name: &str,
-) -> Result<(frost::round2::SignatureShare, ARID)> {
+) -> Result<SignRound2ResponseData> {
     let envelope = get_with_indicator(
         runtime,
         client,
@@ -427,7 +415,7 @@ fn fetch_share_response(
         participant_name,
         timeout,
     )?
-    .context("Signature share response not found in Hubert storage")?;
+    .ok_or(FetchError::NotYetAvailable)?;
 
     let coordinator_private_keys =
         coordinator.inception_private_keys().ok_or_else(|| {
@@ -436,59 +424,13 @@ fn fetch_share_response(
             )
         })?;
 
-    let now = Date::now();
-    let sealed_response = SealedResponse::try_from_encrypted_envelope(
+    validate_and_extract_share_response(
         &envelope,
-        None,
-        Some(now),
         coordinator_private_keys,
-    )?;
-
-    if sealed_response.sender().xid() != *expected_sender {
-        bail!(
-            "Unexpected response sender: {} (expected {})",
-            sealed_response.sender().xid().ur_string(),
-            expected_sender.ur_string()
-        );
-    }
-
-    if let Ok(error) = sealed_response.error() {
-        let reason = error
-            .object_for_predicate("reason")
-            .ok()
-            .and_then(|e| e.extract_subject::<String>().ok())
-            .unwrap_or_else(|| "unknown reason".to_string());
-        bail!("Participant rejected signRound2: {}", reason);
-    }
-
-    let result = sealed_response
-        .result()
-        .context("Response has no result envelope")?;
-
-    result
-        .check_subject_unit()?
-        .check_type("signRound2Response")?;
-
-    let response_session: ARID =
-        result.extract_object_for_predicate("session")?;
-    if response_session != *expected_session_id {
-        bail!(
-            "Response session {} does not match expected {}",
-            response_session.ur_string(),
-            expected_session_id.ur_string()
-        );
-    }
-
-    let signature_share_json: JSON =
-        result.extract_object_for_predicate("signature_share")?;
-    let signature_share: frost::round2::SignatureShare =
-        serde_json::from_slice(signature_share_json.as_bytes())
-            .context("Failed to deserialize signature share")?;
-
-    let finalize_arid: ARID =
-        result.extract_object_for_predicate("response_arid")?;
-
-    Ok((signature_share, finalize_arid))
+        expected_sender,
+        expected_session_id,
+        stored_commitments,
+    )
 }
 
 fn build_finalize_event(
@@ -519,40 +461,10 @@ fn load_start_state(
     session_id: &ARID,
     group_hint: Option<ARID>,
 ) -> Result<StartState> {
-    let base = registry_path
-        .parent()
-        .map(Path::to_path_buf)
-        .unwrap_or_else(|| PathBuf::from("."));
-    let group_state_dir = base.join("group-state");
-
+    let paths = GroupStatePaths::new(registry_path);
     let mut candidate_paths = Vec::new();
-    let group_dirs: Vec<(ARID, PathBuf)> = if let Some(group) = group_hint {
-        vec![(group, group_state_dir.join(group.hex()))]
-    } else {
-        let mut dirs = Vec::new();
-        if group_state_dir.exists() {
-            for entry in fs::read_dir(&group_state_dir)? {
-                let entry = entry?;
-                if entry.file_type()?.is_dir() {
-                    let dir_name = entry.file_name();
-                    if let Some(name) = dir_name.to_str()
-                        && name.len() == 64
-                        && name.chars().all(|c| c.is_ascii_hexdigit())
-                    {
-                        let group_id = ARID::from_hex(name);
-                        dirs.push((group_id, entry.path()));
-                    }
-                }
-            }
-        }
-        dirs
-    };
-
-    for (group_id, group_dir) in group_dirs {
-        let candidate = group_dir
-            .join("signing")
-            .join(session_id.hex())
-            .join("start.json");
+    for group_id in paths.candidate_groups(group_hint)? {
+        let candidate = paths.start_state_path(&group_id, session_id);
         if candidate.exists() {
             candidate_paths.push((group_id, candidate));
         }
@@ -639,6 +551,11 @@ struct ParticipantCommitment {
 
 struct CommitmentsState {
     commitments: BTreeMap<XID, ParticipantCommitment>,
+    /// The participant signature shares are sealed to, when not the
+    /// coordinator.
+    recipient: Option<XID>,
+    /// What a detached session signs in place of the target.
+    statement: Option<Envelope>,
 }
 
 fn load_commitments_state(
@@ -646,8 +563,8 @@ fn load_commitments_state(
     group_id: &ARID,
     session_id: &ARID,
 ) -> Result<CommitmentsState> {
-    let dir = signing_state_dir(registry_path, group_id, session_id);
-    let path = dir.join("commitments.json");
+    let path = GroupStatePaths::new(registry_path)
+        .commitments_path(group_id, session_id);
     if !path.exists() {
         bail!(
             "Commitments not found at {}. Run `frost sign coordinator collect` first",
@@ -717,51 +634,25 @@ fn load_commitments_state(
         );
     }
 
-    Ok(CommitmentsState { commitments })
-}
-
-fn load_public_key_package(
-    registry_path: &Path,
-    group_id: &ARID,
-) -> Result<frost_ed25519::keys::PublicKeyPackage> {
-    let base = registry_path
-        .parent()
-        .map(Path::to_path_buf)
-        .unwrap_or_else(|| PathBuf::from("."));
-    let path = base
-        .join("group-state")
-        .join(group_id.hex())
-        .join("collected_finalize.json");
-    if !path.exists() {
-        bail!(
-            "collected_finalize.json not found at {}. Run `frost dkg coordinator finalize collect` first",
-            path.display()
-        );
-    }
+    let recipient = raw
+        .get("recipient")
+        .and_then(|v| v.as_str())
+        .map(|raw| {
+            XID::from_ur_string(raw)
+                .context("Invalid recipient XID in commitments.json")
+        })
+        .transpose()?;
+
+    let statement = raw
+        .get("detached_statement")
+        .and_then(|v| v.as_str())
+        .map(|ur| {
+            Envelope::from_ur_string(ur)
+                .context("Invalid detached_statement in commitments.json")
+        })
+        .transpose()?;
 
-    let raw: serde_json::Map<String, serde_json::Value> =
-        serde_json::from_slice(
-            &fs::read(&path).with_context(|| {
-                format!("Failed to read {}", path.display())
-            })?,
-        )
-        .context("Invalid collected_finalize.json")?;
-
-    let first_entry = raw
-        .values()
-        .next()
-        .context("collected_finalize.json is empty")?;
-    let public_key_value = first_entry
-        .as_object()
-        .and_then(|m| m.get("public_key_package"))
-        .cloned()
-        .context("public_key_package missing in collected_finalize.json")?;
-
-    let pkg: frost_ed25519::keys::PublicKeyPackage =
-        serde_json::from_value(public_key_value)
-            .context("Failed to parse public_key_package")?;
-
-    Ok(pkg)
+    Ok(CommitmentsState { commitments, recipient, statement })
 }
 
 fn commitments_with_identifiers(
@@ -778,27 +669,18 @@ fn commitments_with_identifiers(
     Ok(mapped)
 }
 
-fn xid_identifier_map(
-    participants: &[XID],
-) -> Result<HashMap<XID, frost::Identifier>> {
-    let mut map = HashMap::new();
-    for (i, xid) in participants.iter().enumerate() {
-        let identifier = frost::Identifier::try_from((i + 1) as u16)
-            .context("Failed to derive Identifier from participant index")?;
-        map.insert(*xid, identifier);
-    }
-    Ok(map)
-}
-
+/// Writes final.json. The caller holds the group lock.
 fn persist_final_state(
     registry_path: &Path,
     group_id: &ARID,
     session_id: &ARID,
     signature: &bc_components::Signature,
     signature_shares: &BTreeMap<XID, frost::round2::SignatureShare>,
-    finalize_arids: &HashMap<XID, ARID>,
+    xid_to_identifier: &HashMap<XID, frost::Identifier>,
+    finalize_arids: &BTreeMap<XID, ARID>,
 ) -> Result<()> {
-    let dir = signing_state_dir(registry_path, group_id, session_id);
+    let paths = GroupStatePaths::new(registry_path);
+    let dir = paths.session_dir(group_id, session_id);
     fs::create_dir_all(&dir).with_context(|| {
         format!("Failed to create signing state directory {}", dir.display())
     })?;
@@ -837,15 +719,62 @@ fn persist_final_state(
         "signature_shares".to_string(),
         serde_json::Value::Object(shares_json),
     );
+    root.insert(
+        "signers".to_string(),
+        signers_json(signature_shares, xid_to_identifier)?,
+    );
     root.insert(
         "finalize_arids".to_string(),
         serde_json::Value::Object(finalize_json),
     );
 
-    fs::write(dir.join("final.json"), serde_json::to_vec_pretty(&root)?)
-        .with_context(|| {
-            format!("Failed to write {}", dir.join("final.json").display())
-        })
+    let final_path = paths.final_state_path(group_id, session_id);
+    write_atomically(&final_path, serde_json::to_vec_pretty(&root)?)
+        .with_context(|| format!("Failed to write {}", final_path.display()))
+}
+
+/// Records the coordinator's own finalize result in final.json, as `sign
+/// participant finalize` would after receiving a signFinalize event, and
+/// stops listening at the finalize ARID nothing will be posted to. Returns
+/// `false` when the coordinator is not one of the signers. The caller holds
+/// the group lock.
+#[allow(clippy::too_many_arguments)]
+fn record_self_finalize(
+    registry: &Registry,
+    registry_path: &Path,
+    group_id: &ARID,
+    session_id: &ARID,
+    coordinator: &XID,
+    finalize_arids: &BTreeMap<XID, ARID>,
+    commitments_state: &CommitmentsState,
+    signed_envelope: &Envelope,
+) -> Result<bool> {
+    let Some(finalize_arid) = finalize_arids.get(coordinator) else {
+        return Ok(false);
+    };
+
+    let paths = GroupStatePaths::new(registry_path);
+    let commitments = commitments_state
+        .commitments
+        .iter()
+        .map(|(xid, entry)| (*xid, entry.commitments))
+        .collect();
+    record_finalize_result(
+        &paths.final_state_path(group_id, session_id),
+        finalize_arid,
+        &commitments,
+        signed_envelope,
+    )?;
+
+    let mut registry = registry.reload(registry_path)?;
+    let group_record = registry
+        .group_mut(group_id)
+        .context("Group not found in registry")?;
+    if group_record.listening_at_arid() == Some(*finalize_arid) {
+        group_record.clear_listening_at_arid();
+        registry.save(registry_path)?;
+    }
+    Ok(true)
 }
 
 struct StartState {
@@ -873,6 +802,7 @@ async fn collect_shares_parallel(
     coordinator: &XIDDocument,
     session_id: &ARID,
     timeout: Option<u64>,
+    participant_timeouts: HashMap<XID, u64>,
 ) -> Result<CollectionResult<SignRound2ResponseData>> {
     let requests: Vec<(XID, ARID, String)> = commitments_state
         .commitments
@@ -891,15 +821,26 @@ async fn collect_shares_parallel(
         .context("Missing coordinator private keys")?
         .clone();
     let session = *session_id;
+    let stored_commitments: HashMap<XID, frost::round1::SigningCommitments> =
+        commitments_state
+            .commitments
+            .iter()
+            .map(|(xid, entry)| (*xid, entry.commitments))
+            .collect();
 
-    let config = ParallelFetchConfig::with_timeout(timeout);
+    let config = ParallelFetchConfig::with_timeout(timeout)
+        .with_participant_timeouts(participant_timeouts);
 
     parallel_fetch(client, requests, config, move |envelope, xid| {
+        let stored = stored_commitments
+            .get(xid)
+            .context("No stored commitments for participant")?;
         validate_and_extract_share_response(
             envelope,
             &coordinator_keys,
             xid,
             &session,
+            stored,
         )
     })
     .await
@@ -911,6 +852,7 @@ fn validate_and_extract_share_response(
     coordinator_keys: &bc_components::PrivateKeys,
     expected_sender: &XID,
     expected_session_id: &ARID,
+    stored_commitments: &frost::round1::SigningCommitments,
 ) -> Result<SignRound2ResponseData> {
     let now = Date::now();
     let sealed_response = SealedResponse::try_from_encrypted_envelope(
@@ -918,7 +860,8 @@ fn validate_and_extract_share_response(
         None,
         Some(now),
         coordinator_keys,
-    )?;
+    )
+    .map_err(FetchError::decode)?;
 
     if sealed_response.sender().xid() != *expected_sender {
         bail!(
@@ -934,12 +877,18 @@ fn validate_and_extract_share_response(
             .ok()
             .and_then(|e| e.extract_subject::<String>().ok())
             .unwrap_or_else(|| "unknown reason".to_string());
-        bail!("Participant rejected signRound2: {}", reason);
+        return Err(FetchError::Rejected(format!(
+            "Participant rejected signRound2: {reason}"
+        ))
+        .into());
     }
 
-    let result = sealed_response
-        .result()
-        .context("Response has no result envelope")?;
+    let result = decompress_envelope(
+        sealed_response
+            .result()
+            .context("Response has no result envelope")?
+            .clone(),
+    )?;
 
     result
         .check_subject_unit()?
@@ -963,11 +912,13 @@ fn validate_and_extract_share_response(
 
     let finalize_arid: ARID =
         result.extract_object_for_predicate("response_arid")?;
+    check_echoed_commitments(&result, stored_commitments, expected_sender)?;
 
     Ok(SignRound2ResponseData { signature_share, finalize_arid })
 }
 
-/// Process aggregation and finalize dispatch after parallel collection.
+/// Aggregate the collected signature shares, record the final state, and
+/// dispatch finalize events, in parallel or one at a time.
 #[allow(clippy::too_many_arguments)]
 fn process_aggregation_and_finalize(
     runtime: &Runtime,
@@ -985,9 +936,14 @@ fn process_aggregation_and_finalize(
         frost::round2::SignatureShare,
     >,
     signature_shares_by_xid: BTreeMap<XID, frost::round2::SignatureShare>,
-    finalize_arids: HashMap<XID, ARID>,
+    finalize_arids: BTreeMap<XID, ARID>,
     preview_finalize: bool,
+    force: bool,
+    no_dispatch: bool,
+    post_to_self: bool,
+    aggregate_only: bool,
     parallel: bool,
+    report: &mut CollectionReport,
 ) -> Result<()> {
     if signature_shares_by_identifier.len() < start_state.min_signers {
         bail!(
@@ -1002,20 +958,33 @@ fn process_aggregation_and_finalize(
         &commitments_state.commitments,
         xid_to_identifier,
     )?;
-    let target_digest: Digest = {
-        let start_state_target =
-            Envelope::from_ur_string(&start_state.target_ur)
-                .context("Invalid target UR in start state")?;
-        start_state_target.subject().digest()
-    };
+    let target_envelope = Envelope::from_ur_string(&start_state.target_ur)
+        .context("Invalid target UR in start state")?;
+    // A detached session signed the statement dispatched in round 1 rather
+    // than the target itself
+    let signing_target = commitments_state
+        .statement
+        .as_ref()
+        .unwrap_or(&target_envelope);
+    let message = signing_message(signing_target)?;
     let signing_package =
-        frost::SigningPackage::new(signing_commitments, target_digest.data());
+        frost::SigningPackage::new(signing_commitments, &message);
 
     // Public key package from finalize collection
-    let public_key_package = load_public_key_package(registry_path, group_id)?;
+    let public_key_package = load_public_key_package(
+        &GroupStatePaths::new(registry_path),
+        group_id,
+    )?;
     let verifying_key =
         signing_key_from_verifying(public_key_package.verifying_key())?;
 
+    verify_signature_shares(
+        &signing_package,
+        &signature_shares_by_xid,
+        xid_to_identifier,
+        &public_key_package,
+    )?;
+
     let signature = frost_ed25519::aggregate(
         &signing_package,
         &signature_shares_by_identifier,
@@ -1032,20 +1001,41 @@ fn process_aggregation_and_finalize(
     let final_signature =
         bc_components::Signature::ed25519_from_data(sig_array);
     let signature_ur = final_signature.ur_string();
-    if !verifying_key.verify(&final_signature, target_digest.data()) {
+    if !verifying_key.verify(&final_signature, &message) {
         bail!("Aggregated signature failed verification against target digest");
     }
+    report.set_signature(&message, &verifying_key);
+
+    // Hold the group lock from the overwrite check until final.json is
+    // complete, so a concurrent finalize cannot pass the same check
+    let paths = GroupStatePaths::new(registry_path);
+    let lock = paths.lock_group(group_id)?;
+    check_final_state_overwrite(
+        &paths.final_state_path(group_id, session_id),
+        &final_signature,
+        &verifying_key,
+        &message,
+        force,
+    )?;
 
-    // Attach and verify on the target envelope
-    let signed_envelope = Envelope::from_ur_string(&start_state.target_ur)?
-        .add_assertion(
-            bc_envelope::known_values::SIGNED,
-            final_signature.clone(),
-        );
-    signed_envelope
-        .verify_signature_from(&verifying_key)
-        .context("Aggregated signature did not verify on target envelope")?;
-    let signed_envelope_ur = signed_envelope.ur_string();
+    // Attach and verify on what was signed, unless only the signature is
+    // wanted; a detached session yields its detached signature
+    let signed_envelope = if aggregate_only {
+        None
+    } else {
+        let signed =
+            attach_signature(signing_target, &final_signature, &verifying_key)?;
+        Some(match &commitments_state.statement {
+            Some(statement) => DetachedSignature::from_statement(
+                statement,
+                final_signature.clone(),
+                verifying_key.clone(),
+            )?
+            .to_envelope()?,
+            None => signed,
+        })
+    };
+    let signed_envelope_ur = signed_envelope.as_ref().map(|e| e.ur_string());
 
     persist_final_state(
         registry_path,
@@ -1053,17 +1043,41 @@ fn process_aggregation_and_finalize(
         session_id,
         &final_signature,
         &signature_shares_by_xid,
+        xid_to_identifier,
         &finalize_arids,
     )?;
+    report.add_output(&paths.final_state_path(group_id, session_id));
+    let self_finalized = match &signed_envelope {
+        Some(signed) if !post_to_self => record_self_finalize(
+            registry,
+            registry_path,
+            group_id,
+            session_id,
+            &owner.xid(),
+            &finalize_arids,
+            commitments_state,
+            signed,
+        )?,
+        _ => false,
+    };
+    drop(lock);
 
     if is_verbose() {
-        eprintln!();
-        eprintln!(
-            "Aggregated signature for session {} and prepared {} finalize packages.",
-            session_id.ur_string(),
-            finalize_arids.len()
+        print_aggregation_summary(
+            session_id,
+            finalize_arids.len(),
+            &message,
+            &verifying_key,
+            self_finalized,
         );
-        eprintln!("Signature verified against target and group key.");
+    }
+
+    if no_dispatch {
+        if is_verbose() {
+            eprintln!("Skipping finalize dispatch (--no-dispatch).");
+        }
+        print_signature_output(&signature_ur, signed_envelope_ur.as_deref());
+        return Ok(());
     }
 
     // Dispatch finalize events to participants (no response expected)
@@ -1075,7 +1089,7 @@ fn process_aggregation_and_finalize(
     if is_verbose() {
         eprintln!(
             "Dispatching finalize packages to {} participants...",
-            finalize_arids.len()
+            finalize_arids.len() - usize::from(self_finalized)
         );
     }
 
@@ -1083,6 +1097,9 @@ fn process_aggregation_and_finalize(
     let mut messages: Vec<(XID, ARID, Envelope, String)> = Vec::new();
     let mut preview_printed = false;
     for (participant, finalize_arid) in &finalize_arids {
+        if self_finalized && *participant == owner.xid() {
+            continue;
+        }
         let participant_name = registry
             .participant(participant)
             .and_then(|r| r.pet_name().map(|s| s.to_owned()))
@@ -1147,6 +1164,10 @@ fn process_aggregation_and_finalize(
             bail!("Failed to send finalize packages: {}", errors.join("; "));
         }
     } else {
+        if !is_verbose() {
+            // Blank line to separate get phase from put phase
+            eprintln!();
+        }
         for (_, finalize_arid, sealed, participant_name) in messages {
             put_with_indicator(
                 runtime,
@@ -1159,8 +1180,7 @@ fn process_aggregation_and_finalize(
     }
 
     // Print the final signature and signed envelope UR after all dispatches
-    println!("{signature_ur}");
-    println!("{signed_envelope_ur}");
+    print_signature_output(&signature_ur, signed_envelope_ur.as_deref());
 
     Ok(())
 }
@@ -0,0 +1,398 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use bc_components::{ARID, Signature, Verifier, XID};
+use bc_envelope::prelude::*;
+use clap::Parser;
+
+use crate::{
+    cmd::{
+        common::{
+            GroupStatePaths, load_registry, signing_key_from_verifying,
+            write_atomically,
+        },
+        dkg::common::{parse_arid_ur, parse_group_id, read_ur_input},
+        is_verbose,
+        registry::participants_file_path,
+        sign::common::{
+            DetachedSignature, attach_signature, is_pre_hashed,
+            load_public_key_package, signing_message,
+        },
+    },
+    registry::Registry,
+};
+
+type JsonMap = serde_json::Map<String, serde_json::Value>;
+
+/// Export a completed signing session as a directory of artifacts.
+///
+/// Writes `signature.ur`, `signed.envelope`, `shares.json`,
+/// `participants.json`, and a `manifest.json` describing them. The signature
+/// is verified against the group key first and the result is recorded in the
+/// manifest.
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Optional group ID hint when multiple groups exist
+    #[arg(long = "group", value_name = "UR:ARID")]
+    group_id: Option<String>,
+
+    /// Directory to write the artifacts to (created if missing, must be
+    /// empty)
+    #[arg(long = "out", value_name = "DIR")]
+    out: PathBuf,
+
+    /// Signing session ID to export
+    /// (`-` reads it from stdin)
+    #[arg(value_name = "SESSION_ID")]
+    session: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let registry_path = participants_file_path(self.registry.clone())?;
+        let registry = load_registry(&registry_path).with_context(|| {
+            format!("Failed to load registry at {}", registry_path.display())
+        })?;
+
+        let session_id = parse_arid_ur(&read_ur_input(&self.session)?)?;
+        let group_hint = match &self.group_id {
+            Some(raw) => Some(parse_group_id(&registry, raw)?),
+            None => None,
+        };
+
+        let paths = GroupStatePaths::new(&registry_path);
+        let group_id = find_final_state(&paths, &session_id, group_hint)?;
+        let session = load_session(&paths, &group_id, &session_id)?;
+
+        let verifying_key = match registry
+            .group(&group_id)
+            .and_then(|record| record.verifying_key())
+        {
+            Some(key) => key.clone(),
+            None => signing_key_from_verifying(
+                load_public_key_package(&paths, &group_id)?.verifying_key(),
+            )?,
+        };
+
+        let signing_target =
+            session.statement.as_ref().unwrap_or(&session.target);
+        let message = signing_message(signing_target)?;
+        let verified = verifying_key.verify(&session.signature, &message);
+
+        prepare_out_dir(&self.out)?;
+        let mut files = Vec::new();
+        write_artifact(
+            &self.out,
+            "signature.ur",
+            format!("{}\n", session.signature.ur_string()).into_bytes(),
+            &mut files,
+        )?;
+        // A signature that does not verify is not attached to the target
+        if verified {
+            let signed = match &session.statement {
+                Some(statement) => DetachedSignature::from_statement(
+                    statement,
+                    session.signature.clone(),
+                    verifying_key.clone(),
+                )?
+                .to_envelope()?,
+                None => attach_signature(
+                    &session.target,
+                    &session.signature,
+                    &verifying_key,
+                )?,
+            };
+            write_artifact(
+                &self.out,
+                "signed.envelope",
+                format!("{}\n", signed.ur_string()).into_bytes(),
+                &mut files,
+            )?;
+        }
+
+        let mut shares = JsonMap::new();
+        shares.insert(
+            "signature_shares".to_string(),
+            serde_json::Value::Object(session.signature_shares.clone()),
+        );
+        shares.insert(
+            "commitments".to_string(),
+            serde_json::Value::Object(session.commitments),
+        );
+        write_artifact(
+            &self.out,
+            "shares.json",
+            serde_json::to_vec_pretty(&shares)?,
+            &mut files,
+        )?;
+
+        let participants: Vec<serde_json::Value> = session
+            .participants
+            .iter()
+            .map(|xid| {
+                let mut entry = JsonMap::new();
+                entry.insert(
+                    "xid".to_string(),
+                    serde_json::Value::String(xid.ur_string()),
+                );
+                if let Some(name) = pet_name(&registry, xid) {
+                    entry.insert(
+                        "name".to_string(),
+                        serde_json::Value::String(name),
+                    );
+                }
+                entry.insert(
+                    "signer".to_string(),
+                    serde_json::Value::Bool(
+                        session.signature_shares.contains_key(&xid.ur_string()),
+                    ),
+                );
+                serde_json::Value::Object(entry)
+            })
+            .collect();
+        write_artifact(
+            &self.out,
+            "participants.json",
+            serde_json::to_vec_pretty(&participants)?,
+            &mut files,
+        )?;
+
+        let manifest = serde_json::json!({
+            "group": group_id.ur_string(),
+            "session": session_id.ur_string(),
+            "role": session.role,
+            "exported": Date::now().to_string(),
+            "verifying_key": verifying_key.ur_string(),
+            "pre_hashed": is_pre_hashed(&session.target),
+            "verified": verified,
+            "files": files,
+        });
+        let manifest_path = self.out.join("manifest.json");
+        write_atomically(&manifest_path, serde_json::to_vec_pretty(&manifest)?)
+            .with_context(|| {
+                format!("Failed to write {}", manifest_path.display())
+            })?;
+
+        if is_verbose() {
+            eprintln!(
+                "Exported {} files for session {}",
+                files.len() + 1,
+                session_id.ur_string()
+            );
+        }
+        if !verified {
+            bail!(
+                "Signature does not verify against the group key; exported \
+                 to {} with \"verified\": false",
+                self.out.display()
+            );
+        }
+
+        println!("{}", self.out.display());
+        Ok(())
+    }
+}
+
+/// Signing state gathered from a session directory.
+struct SessionState {
+    role: &'static str,
+    signature: Signature,
+    target: Envelope,
+    /// What a detached session signed in place of the target.
+    statement: Option<Envelope>,
+    participants: Vec<XID>,
+    signature_shares: JsonMap,
+    commitments: JsonMap,
+}
+
+fn find_final_state(
+    paths: &GroupStatePaths,
+    session_id: &ARID,
+    group_hint: Option<ARID>,
+) -> Result<ARID> {
+    let mut candidates = Vec::new();
+    for group_id in paths.candidate_groups(group_hint)? {
+        if paths.final_state_path(&group_id, session_id).exists() {
+            candidates.push(group_id);
+        }
+    }
+
+    match candidates.as_slice() {
+        [] => bail!(
+            "No final.json found for this session; finish signing with `frost sign coordinator round2` or `frost sign participant finalize` first"
+        ),
+        [group_id] => Ok(*group_id),
+        _ => bail!(
+            "Multiple groups contain this session; use --group to disambiguate"
+        ),
+    }
+}
+
+fn read_json(path: &Path) -> Result<JsonMap> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    serde_json::from_slice(
+        &fs::read(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?,
+    )
+    .with_context(|| format!("Invalid {name}"))
+}
+
+fn get_str<'a>(raw: &'a JsonMap, key: &str, file: &str) -> Result<&'a str> {
+    raw.get(key)
+        .and_then(|v| v.as_str())
+        .with_context(|| format!("Missing or invalid {key} in {file}"))
+}
+
+/// Loads the final state plus the target and participants from whichever
+/// side of the session this registry took part in.
+fn load_session(
+    paths: &GroupStatePaths,
+    group_id: &ARID,
+    session_id: &ARID,
+) -> Result<SessionState> {
+    let final_state = read_json(&paths.final_state_path(group_id, session_id))?;
+    let signature = Signature::from_ur_string(get_str(
+        &final_state,
+        "signature",
+        "final.json",
+    )?)
+    .context("Invalid signature in final.json")?;
+    let signature_shares = final_state
+        .get("signature_shares")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .context("Missing signature_shares in final.json")?;
+
+    let start_path = paths.start_state_path(group_id, session_id);
+    let (role, state, file) = if start_path.exists() {
+        ("coordinator", read_json(&start_path)?, "start.json")
+    } else {
+        let receive_path = paths.receive_state_path(group_id, session_id);
+        (
+            "participant",
+            read_json(&receive_path)?,
+            "sign_receive.json",
+        )
+    };
+
+    let target = Envelope::from_ur_string(get_str(&state, "target", file)?)
+        .with_context(|| format!("Invalid target in {file}"))?;
+
+    // start.json keys participants by XID; sign_receive.json lists them
+    let participant_strs: Vec<&str> = match state.get("participants") {
+        Some(serde_json::Value::Object(map)) => {
+            map.keys().map(String::as_str).collect()
+        }
+        Some(serde_json::Value::Array(list)) => list
+            .iter()
+            .map(|v| v.as_str().context("Invalid participant entry"))
+            .collect::<Result<_>>()?,
+        _ => bail!("Missing participants in {file}"),
+    };
+    let mut participants = participant_strs
+        .into_iter()
+        .map(|s| {
+            XID::from_ur_string(s)
+                .with_context(|| format!("Invalid participant XID in {file}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    participants.sort();
+
+    let commitments = if role == "coordinator" {
+        let path = paths.commitments_path(group_id, session_id);
+        let raw = read_json(&path)?;
+        raw.get("commitments")
+            .and_then(|v| v.as_object())
+            .context("Missing commitments in commitments.json")?
+            .iter()
+            .filter_map(|(xid, entry)| {
+                entry.get("commitments").map(|c| (xid.clone(), c.clone()))
+            })
+            .collect()
+    } else {
+        final_state
+            .get("commitments")
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    // The coordinator records a detached session's statement alongside the
+    // commitments, and each participant alongside its share
+    let statement_file = if role == "coordinator" {
+        paths.commitments_path(group_id, session_id)
+    } else {
+        paths.share_state_path(group_id, session_id)
+    };
+    let statement = if statement_file.exists() {
+        read_json(&statement_file)?
+            .get("detached_statement")
+            .and_then(|v| v.as_str())
+            .map(|ur| {
+                Envelope::from_ur_string(ur).with_context(|| {
+                    format!(
+                        "Invalid detached_statement in {}",
+                        statement_file.display()
+                    )
+                })
+            })
+            .transpose()?
+    } else {
+        None
+    };
+
+    Ok(SessionState {
+        role,
+        signature,
+        target,
+        statement,
+        participants,
+        signature_shares,
+        commitments,
+    })
+}
+
+fn prepare_out_dir(out: &Path) -> Result<()> {
+    if out.exists() {
+        let mut entries = fs::read_dir(out)
+            .with_context(|| format!("Failed to read {}", out.display()))?;
+        if entries.next().is_some() {
+            bail!("Output directory {} is not empty", out.display());
+        }
+    }
+    fs::create_dir_all(out)
+        .with_context(|| format!("Failed to create {}", out.display()))
+}
+
+fn write_artifact(
+    out: &Path,
+    name: &str,
+    bytes: Vec<u8>,
+    files: &mut Vec<String>,
+) -> Result<()> {
+    let path = out.join(name);
+    write_atomically(&path, bytes)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    files.push(name.to_string());
+    Ok(())
+}
+
+fn pet_name(registry: &Registry, xid: &XID) -> Option<String> {
+    match registry.owner() {
+        Some(owner) if owner.xid() == *xid => {
+            owner.pet_name().map(str::to_owned)
+        }
+        _ => registry
+            .participant(xid)
+            .and_then(|record| record.pet_name())
+            .map(str::to_owned),
+    }
+}
@@ -1,6 +1,8 @@
 pub mod common;
 pub mod coordinator;
+pub mod export;
 pub mod participant;
+pub mod verify;
 
 use anyhow::Result;
 use clap::{Args, Subcommand};
@@ -19,6 +21,10 @@ enum Commands {
     Coordinator(coordinator::CommandArgs),
     /// Participant-only signing commands
     Participant(participant::CommandArgs),
+    /// Verify a detached group signature
+    Verify(verify::CommandArgs),
+    /// Export a completed signing session as a directory of artifacts
+    Export(export::CommandArgs),
 }
 
 impl CommandArgs {
@@ -26,6 +32,8 @@ impl CommandArgs {
         match self.command {
             Commands::Coordinator(args) => args.exec(),
             Commands::Participant(args) => args.exec(),
+            Commands::Verify(args) => args.exec(),
+            Commands::Export(args) => args.exec(),
         }
     }
 }
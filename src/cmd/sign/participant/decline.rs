@@ -0,0 +1,136 @@
+use anyhow::{Context, Result, bail};
+use bc_envelope::prelude::*;
+use clap::Parser;
+
+use super::round1::{
+    decline_response, load_receive_state, open_sign_invite, post_response,
+    print_response_preview,
+};
+use crate::cmd::{
+    common::load_registry,
+    dkg::{
+        OptionalStorageSelector,
+        common::{
+            parse_arid_ur, parse_group_id, read_ur_input, resolve_also_notify,
+        },
+    },
+    registry::participants_file_path,
+};
+
+/// Decline a signInvite request (participant).
+///
+/// Posts a failure response to the invite's response ARID, so the
+/// coordinator's commitment collection reports a rejection with the reason
+/// instead of waiting for a timeout.
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(flatten)]
+    storage: OptionalStorageSelector,
+
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Print the preview response envelope UR instead of sending
+    #[arg(long = "preview")]
+    preview: bool,
+
+    /// Why this participant will not sign, shown to the coordinator
+    #[arg(long = "reason", value_name = "TEXT")]
+    reason: String,
+
+    /// Also seal the response to this registered participant, such as a
+    /// backup coordinator or an observer (repeatable)
+    #[arg(long = "also-notify", value_name = "PARTICIPANT")]
+    also_notify: Vec<String>,
+
+    /// Optional group ID hint when multiple groups exist
+    #[arg(long = "group", value_name = "UR:ARID")]
+    group_id: Option<String>,
+
+    /// Signing session ID to decline
+    /// (`-` reads it from stdin)
+    #[arg(value_name = "SESSION_ID")]
+    session: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let selection = self.storage.resolve()?;
+        if selection.is_none() && !self.preview {
+            bail!("Hubert storage is required for sign decline");
+        }
+        if selection.is_some() && self.preview {
+            bail!("--preview cannot be used with Hubert storage options");
+        }
+        if self.reason.trim().is_empty() {
+            bail!("--reason cannot be empty");
+        }
+
+        let registry_path = participants_file_path(self.registry.clone())?;
+        let mut registry =
+            load_registry(&registry_path).with_context(|| {
+                format!(
+                    "Failed to load registry at {}",
+                    registry_path.display()
+                )
+            })?;
+
+        let owner = registry
+            .owner()
+            .context("Registry owner is required")?
+            .clone();
+        let also_notify = resolve_also_notify(&registry, &self.also_notify)?;
+
+        let session_id = parse_arid_ur(&read_ur_input(&self.session)?)?;
+        let group_hint = match &self.group_id {
+            Some(raw) => Some(parse_group_id(&registry, raw)?),
+            None => None,
+        };
+
+        let receive_state = load_receive_state(
+            &registry_path,
+            &session_id,
+            group_hint,
+            &registry,
+        )?;
+        let group_id = receive_state.group_id;
+        let sealed_request =
+            open_sign_invite(&receive_state, &session_id, &owner)?;
+
+        let signer_private_keys = owner
+            .xid_document()
+            .inception_private_keys()
+            .context("Owner XID document has no signing keys")?;
+        let sealed_response =
+            decline_response(&sealed_request, &owner, &group_id, &self.reason);
+
+        let Some(selection) = selection else {
+            return print_response_preview(
+                &sealed_response,
+                signer_private_keys,
+            );
+        };
+        post_response(
+            selection,
+            &sealed_request,
+            &sealed_response,
+            &receive_state,
+            &registry,
+            signer_private_keys,
+            &also_notify,
+            "Decline",
+        )?;
+
+        let group_record = registry
+            .group_mut(&group_id)
+            .context("Group not found in registry")?;
+        group_record.clear_listening_at_arid();
+        registry.save(&registry_path)?;
+
+        eprintln!("Declined signing session {}", session_id.ur_string());
+
+        Ok(())
+    }
+}
@@ -1,28 +1,36 @@
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
     fs,
-    path::{Path, PathBuf},
+    path::Path,
 };
 
 use anyhow::{Context, Result, bail};
-use bc_components::{ARID, Digest, JSON, Verifier, XID, XIDProvider};
+use bc_components::{ARID, JSON, Verifier, XID, XIDProvider};
 use bc_envelope::prelude::*;
 use clap::Parser;
 use frost_ed25519 as frost;
 use gstp::{SealedEvent, SealedEventBehavior};
-use tokio::runtime::Runtime;
 
 use crate::{
     cmd::{
         busy::get_with_indicator,
+        common::{GroupStatePaths, load_registry, write_atomically},
         dkg::{
             OptionalStorageSelector,
-            common::{parse_arid_ur, signing_key_from_verifying},
+            common::{
+                check_strict_sender, parse_arid_ur, parse_group_id,
+                read_ur_input, signing_key_from_verifying,
+            },
         },
         is_verbose,
         registry::participants_file_path,
-        sign::common::{SignFinalizeContent, signing_state_dir},
-        storage::StorageClient,
+        sign::common::{
+            DetachedSignature, SignFinalizeContent, attach_signature,
+            check_final_state_overwrite, finalized_locally,
+            load_public_key_package, signers_json, signing_message,
+            verify_signature_shares,
+        },
+        storage::{StorageClient, shared_runtime},
     },
     registry::{GroupRecord, Registry},
 };
@@ -46,7 +54,17 @@ pub struct CommandArgs {
     #[arg(long = "group", value_name = "UR:ARID")]
     group_id: Option<String>,
 
+    /// Replace an existing final.json (the new signature must match it)
+    #[arg(long = "force")]
+    force: bool,
+
+    /// Require a detached signature: the coordinator must have passed
+    /// --detached to `sign coordinator round1`
+    #[arg(long = "detached")]
+    detached: bool,
+
     /// Signing session ID to attach
+    /// (`-` reads it from stdin)
     #[arg(value_name = "SESSION_ID")]
     session: String,
 }
@@ -59,7 +77,7 @@ impl CommandArgs {
 
         let registry_path = participants_file_path(self.registry.clone())?;
         let mut registry =
-            Registry::load(&registry_path).with_context(|| {
+            load_registry(&registry_path).with_context(|| {
                 format!(
                     "Failed to load registry at {}",
                     registry_path.display()
@@ -71,9 +89,9 @@ impl CommandArgs {
             .context("Registry owner is required")?
             .clone();
 
-        let session_id = parse_arid_ur(&self.session)?;
+        let session_id = parse_arid_ur(&read_ur_input(&self.session)?)?;
         let group_hint = match &self.group_id {
-            Some(raw) => Some(parse_arid_ur(raw)?),
+            Some(raw) => Some(parse_group_id(&registry, raw)?),
             None => None,
         };
 
@@ -81,6 +99,10 @@ impl CommandArgs {
         let receive_state =
             load_receive_state(&registry_path, &session_id, group_hint)?;
         let group_id = receive_state.group_id;
+        // Held until the command returns, so the session state checked
+        // below cannot change before this command writes its own
+        let _lock =
+            GroupStatePaths::new(&registry_path).lock_group(&group_id)?;
         let group_record = registry
             .group(&group_id)
             .context("Group not found in registry")?
@@ -90,7 +112,32 @@ impl CommandArgs {
 
         let share_state =
             load_share_state(&registry_path, &group_id, &session_id)?;
+        if receive_state.coordinator == owner.xid()
+            && group_record.listening_at_arid().is_none()
+            && finalized_locally(
+                &GroupStatePaths::new(&registry_path)
+                    .final_state_path(&group_id, &session_id),
+                &share_state.finalize_arid,
+            )?
+        {
+            // Nothing will be posted for the coordinator to fetch, and
+            // there is nothing left to do
+            eprintln!(
+                "`sign coordinator round2` already recorded your finalize \
+                 result, including the signed target, in final.json; no \
+                 finalize package was posted for you (pass --post-to-self to \
+                 `sign coordinator round2` to finalize here instead)"
+            );
+            return Ok(());
+        }
         validate_share_state(&share_state, &receive_state, &group_record)?;
+        if self.detached && share_state.statement.is_none() {
+            bail!(
+                "This session signed the target itself; a detached signature \
+                 needs the coordinator to pass --detached to `sign \
+                 coordinator round1`"
+            );
+        }
 
         // Fetch finalize event
         let sealed_event = fetch_finalize_event(
@@ -102,6 +149,7 @@ impl CommandArgs {
 
         // Validate event
         validate_finalize_event(&sealed_event, &session_id, &group_record)?;
+        check_strict_sender(&registry, sealed_event.sender())?;
 
         // Extract and validate signature shares
         let signature_shares_by_xid = parse_signature_shares(&sealed_event)?;
@@ -117,19 +165,43 @@ impl CommandArgs {
             Envelope::from_ur_string(&receive_state.target_ur).with_context(
                 || "Invalid target envelope UR in persisted state".to_string(),
             )?;
-        let target_digest: Digest = target_envelope.subject().digest();
+        // A detached session signed the coordinator's statement of the
+        // target rather than the target itself
+        let signing_target =
+            share_state.statement.as_ref().unwrap_or(&target_envelope);
+        let message = signing_message(signing_target)?;
 
         // Aggregate signature
+        let xid_to_identifier = group_record.identifiers()?;
         let (final_signature, signed_envelope, verifying_key) =
             aggregate_and_verify_signature(
                 &registry_path,
                 &group_id,
-                &receive_state.participants,
+                &xid_to_identifier,
                 &share_state.commitments,
                 &signature_shares_by_xid,
-                &target_envelope,
-                &target_digest,
+                signing_target,
+                &message,
             )?;
+        let signed_envelope = match &share_state.statement {
+            Some(statement) => DetachedSignature::from_statement(
+                statement,
+                final_signature.clone(),
+                verifying_key.clone(),
+            )?
+            .to_envelope()?,
+            None => signed_envelope,
+        };
+
+        // Never clobber a completed signature from another run
+        check_final_state_overwrite(
+            &GroupStatePaths::new(&registry_path)
+                .final_state_path(&group_id, &session_id),
+            &final_signature,
+            &verifying_key,
+            &message,
+            self.force,
+        )?;
 
         // Update registry verifying key if needed
         update_registry_verifying_key(
@@ -148,6 +220,7 @@ impl CommandArgs {
             &final_signature,
             &signed_envelope,
             &signature_shares_by_xid,
+            &xid_to_identifier,
             &share_state,
         )?;
 
@@ -298,19 +371,18 @@ fn fetch_finalize_event(
         eprintln!("Fetching finalize package from Hubert...");
     }
 
-    let runtime = Runtime::new()?;
-    let client = runtime.block_on(async {
-        StorageClient::from_selection(selection.clone()).await
-    })?;
+    let runtime = shared_runtime()?;
+    let client = runtime
+        .block_on(async { StorageClient::shared(selection.clone()).await })?;
 
     let finalize_envelope = get_with_indicator(
-        &runtime,
+        runtime,
         &client,
         finalize_arid,
         "Finalize package",
         timeout,
     )?
-    .context("Finalize package not found in Hubert storage")?;
+    .context("Nothing posted yet at the finalize package ARID")?;
 
     let signer_keys = owner
         .xid_document()
@@ -333,31 +405,40 @@ fn fetch_finalize_event(
 fn aggregate_and_verify_signature(
     registry_path: &Path,
     group_id: &ARID,
-    participants: &[XID],
+    xid_to_identifier: &HashMap<XID, frost::Identifier>,
     commitments: &BTreeMap<XID, frost::round1::SigningCommitments>,
     signature_shares_by_xid: &BTreeMap<XID, frost::round2::SignatureShare>,
     target_envelope: &Envelope,
-    target_digest: &Digest,
+    message: &[u8],
 ) -> Result<(
     bc_components::Signature,
     Envelope,
     bc_components::SigningPublicKey,
 )> {
-    let xid_to_identifier = xid_identifier_map(participants)?;
     let signing_commitments =
-        commitments_with_identifiers(commitments, &xid_to_identifier)?;
+        commitments_with_identifiers(commitments, xid_to_identifier)?;
     let signing_package =
-        frost::SigningPackage::new(signing_commitments, target_digest.data());
+        frost::SigningPackage::new(signing_commitments, message);
 
     let signature_shares_by_identifier = signature_shares_with_identifiers(
         signature_shares_by_xid,
-        &xid_to_identifier,
+        xid_to_identifier,
     )?;
 
-    let public_key_package = load_public_key_package(registry_path, group_id)?;
+    let public_key_package = load_public_key_package(
+        &GroupStatePaths::new(registry_path),
+        group_id,
+    )?;
     let verifying_key =
         signing_key_from_verifying(public_key_package.verifying_key())?;
 
+    verify_signature_shares(
+        &signing_package,
+        signature_shares_by_xid,
+        xid_to_identifier,
+        &public_key_package,
+    )?;
+
     let aggregated_signature = frost_ed25519::aggregate(
         &signing_package,
         &signature_shares_by_identifier,
@@ -373,17 +454,12 @@ fn aggregate_and_verify_signature(
     let final_signature =
         bc_components::Signature::ed25519_from_data(sig_array);
 
-    if !verifying_key.verify(&final_signature, target_digest.data()) {
+    if !verifying_key.verify(&final_signature, message) {
         bail!("Aggregated signature failed verification against target digest");
     }
 
-    let signed_envelope = target_envelope.clone().add_assertion(
-        bc_envelope::known_values::SIGNED,
-        final_signature.clone(),
-    );
-    signed_envelope
-        .verify_signature_from(&verifying_key)
-        .context("Aggregated signature did not verify on target envelope")?;
+    let signed_envelope =
+        attach_signature(target_envelope, &final_signature, &verifying_key)?;
 
     Ok((final_signature, signed_envelope, verifying_key))
 }
@@ -436,18 +512,6 @@ fn parse_signature_shares(
     Ok(shares)
 }
 
-fn xid_identifier_map(
-    participants: &[XID],
-) -> Result<HashMap<XID, frost::Identifier>> {
-    let mut map = HashMap::new();
-    for (i, xid) in participants.iter().enumerate() {
-        let identifier = frost::Identifier::try_from((i + 1) as u16)
-            .context("Failed to derive Identifier from participant index")?;
-        map.insert(*xid, identifier);
-    }
-    Ok(map)
-}
-
 fn commitments_with_identifiers(
     commitments: &BTreeMap<XID, frost::round1::SigningCommitments>,
     xid_to_identifier: &HashMap<XID, frost::Identifier>,
@@ -481,40 +545,10 @@ fn load_receive_state(
     session_id: &ARID,
     group_hint: Option<ARID>,
 ) -> Result<ReceiveState> {
-    let base = registry_path
-        .parent()
-        .map(Path::to_path_buf)
-        .unwrap_or_else(|| PathBuf::from("."));
-    let group_state_dir = base.join("group-state");
-
-    let group_dirs: Vec<(ARID, PathBuf)> = if let Some(group) = group_hint {
-        vec![(group, group_state_dir.join(group.hex()))]
-    } else {
-        let mut dirs = Vec::new();
-        if group_state_dir.exists() {
-            for entry in fs::read_dir(&group_state_dir)? {
-                let entry = entry?;
-                if entry.file_type()?.is_dir() {
-                    let dir_name = entry.file_name();
-                    if let Some(name) = dir_name.to_str()
-                        && name.len() == 64
-                        && name.chars().all(|c| c.is_ascii_hexdigit())
-                    {
-                        let group_id = ARID::from_hex(name);
-                        dirs.push((group_id, entry.path()));
-                    }
-                }
-            }
-        }
-        dirs
-    };
-
+    let paths = GroupStatePaths::new(registry_path);
     let mut candidates = Vec::new();
-    for (group_id, group_dir) in group_dirs {
-        let candidate = group_dir
-            .join("signing")
-            .join(session_id.hex())
-            .join("sign_receive.json");
+    for group_id in paths.candidate_groups(group_hint)? {
+        let candidate = paths.receive_state_path(&group_id, session_id);
         if candidate.exists() {
             candidates.push((group_id, candidate));
         }
@@ -608,8 +642,8 @@ fn load_share_state(
     group_id: &ARID,
     session_id: &ARID,
 ) -> Result<ShareState> {
-    let dir = signing_state_dir(registry_path, group_id, session_id);
-    let path = dir.join("share.json");
+    let path = GroupStatePaths::new(registry_path)
+        .share_state_path(group_id, session_id);
     if !path.exists() {
         bail!(
             "Signature share state not found at {}. Run `frost sign participant share` first.",
@@ -666,64 +700,25 @@ fn load_share_state(
         commitments.insert(xid, commits);
     }
 
-    Ok(ShareState { finalize_arid, signature_share, commitments })
-}
+    let statement = raw
+        .get("detached_statement")
+        .and_then(|v| v.as_str())
+        .map(|ur| {
+            Envelope::from_ur_string(ur)
+                .context("Invalid detached_statement in share.json")
+        })
+        .transpose()?;
 
-fn load_public_key_package(
-    registry_path: &Path,
-    group_id: &ARID,
-) -> Result<frost_ed25519::keys::PublicKeyPackage> {
-    let base = registry_path
-        .parent()
-        .map(Path::to_path_buf)
-        .unwrap_or_else(|| PathBuf::from("."));
-    let direct_path = base
-        .join("group-state")
-        .join(group_id.hex())
-        .join("public_key_package.json");
-    if direct_path.exists() {
-        let pkg: frost_ed25519::keys::PublicKeyPackage =
-            serde_json::from_slice(&fs::read(&direct_path).with_context(
-                || format!("Failed to read {}", direct_path.display()),
-            )?)
-            .context("Failed to parse public_key_package.json")?;
-        return Ok(pkg);
-    }
-
-    // Fallback to collected_finalize.json (coordinator)
-    let collected_path = base
-        .join("group-state")
-        .join(group_id.hex())
-        .join("collected_finalize.json");
-    if collected_path.exists() {
-        let raw: serde_json::Map<String, serde_json::Value> =
-            serde_json::from_slice(&fs::read(&collected_path).with_context(
-                || format!("Failed to read {}", collected_path.display()),
-            )?)
-            .context("Invalid collected_finalize.json")?;
-
-        let first_entry = raw
-            .values()
-            .next()
-            .context("collected_finalize.json is empty")?;
-        let public_key_value = first_entry
-            .as_object()
-            .and_then(|m| m.get("public_key_package"))
-            .cloned()
-            .context("public_key_package missing in collected_finalize.json")?;
-
-        let pkg: frost_ed25519::keys::PublicKeyPackage =
-            serde_json::from_value(public_key_value)
-                .context("Failed to parse public_key_package")?;
-        return Ok(pkg);
-    }
-
-    bail!(
-        "Public key package not found for group {}; run finalize respond/collect first",
-        group_id.ur_string()
-    );
+    Ok(ShareState {
+        finalize_arid,
+        signature_share,
+        commitments,
+        statement,
+    })
 }
 
+/// Writes final.json. The caller holds the group lock.
+#[allow(clippy::too_many_arguments)]
 fn persist_final_state(
     registry_path: &Path,
     group_id: &ARID,
@@ -731,14 +726,16 @@ fn persist_final_state(
     signature: &bc_components::Signature,
     signed_envelope: &Envelope,
     signature_shares: &BTreeMap<XID, frost::round2::SignatureShare>,
+    xid_to_identifier: &HashMap<XID, frost::Identifier>,
     share_state: &ShareState,
 ) -> Result<()> {
-    let dir = signing_state_dir(registry_path, group_id, session_id);
+    let paths = GroupStatePaths::new(registry_path);
+    let dir = paths.session_dir(group_id, session_id);
     fs::create_dir_all(&dir).with_context(|| {
         format!("Failed to create signing state directory {}", dir.display())
     })?;
 
-    let final_path = dir.join("final.json");
+    let final_path = paths.final_state_path(group_id, session_id);
     let mut root = if final_path.exists() {
         serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(
             &fs::read(&final_path).with_context(|| {
@@ -784,6 +781,10 @@ fn persist_final_state(
         "signature_shares".to_string(),
         serde_json::Value::Object(shares_json),
     );
+    root.insert(
+        "signers".to_string(),
+        signers_json(signature_shares, xid_to_identifier)?,
+    );
     root.insert(
         "commitments".to_string(),
         serde_json::Value::Object(commitments_json),
@@ -797,9 +798,8 @@ fn persist_final_state(
         serde_json::Value::String(signed_envelope.ur_string()),
     );
 
-    fs::write(final_path, serde_json::to_vec_pretty(&root)?).with_context(
-        || format!("Failed to write {}", dir.join("final.json").display()),
-    )
+    write_atomically(&final_path, serde_json::to_vec_pretty(&root)?)
+        .with_context(|| format!("Failed to write {}", final_path.display()))
 }
 
 struct ReceiveState {
@@ -814,4 +814,6 @@ struct ShareState {
     finalize_arid: ARID,
     signature_share: frost::round2::SignatureShare,
     commitments: BTreeMap<XID, frost::round1::SigningCommitments>,
+    /// What a detached session signed in place of the target.
+    statement: Option<Envelope>,
 }
@@ -1,9 +1,11 @@
+pub mod decline;
 pub mod finalize;
 pub mod receive;
 pub mod round1;
 pub mod round2;
 
 use anyhow::Result;
+use bc_envelope::prelude::*;
 use clap::{Args, Subcommand};
 
 /// Participant-only signing commands.
@@ -20,6 +22,8 @@ enum Commands {
     Receive(receive::CommandArgs),
     /// Respond to a signing invite with commitments (Round 1)
     Round1(round1::CommandArgs),
+    /// Decline a signing invite without committing
+    Decline(decline::CommandArgs),
     /// Respond with signature share (Round 2)
     Round2(round2::CommandArgs),
     /// Attach finalized signature to target envelope (participant)
@@ -31,8 +35,70 @@ impl CommandArgs {
         match self.command {
             Commands::Receive(args) => args.exec(),
             Commands::Round1(args) => args.exec(),
+            Commands::Decline(args) => args.exec(),
             Commands::Round2(args) => args.exec(),
             Commands::Finalize(args) => args.exec(),
         }
     }
 }
+
+// -----------------------------------------------------------------------------
+// Target display
+// -----------------------------------------------------------------------------
+
+/// Targets whose CBOR encoding exceeds this many bytes are summarized instead
+/// of printed in full.
+pub const TARGET_SUMMARY_BYTES: usize = 4096;
+
+/// Formats a signing target for the participant to review.
+///
+/// Targets up to [`TARGET_SUMMARY_BYTES`] are shown with `format()`. Larger
+/// ones are summarized by subject type, subject digest (what FROST signs),
+/// size, and top-level assertion predicates so they cannot flood the
+/// terminal. `full` always shows the whole target.
+pub fn format_target(target: &Envelope, full: bool) -> String {
+    let size = target.to_cbor_data().len();
+    if full || size <= TARGET_SUMMARY_BYTES {
+        return target.format();
+    }
+
+    let subject = target.subject();
+    let mut predicates: Vec<String> = target
+        .assertions()
+        .iter()
+        .filter_map(|assertion| assertion.as_predicate())
+        .map(|predicate| predicate.format_flat())
+        .collect();
+    predicates.sort();
+    predicates.dedup();
+    let predicates = if predicates.is_empty() {
+        "none".to_string()
+    } else {
+        predicates.join(", ")
+    };
+
+    [
+        format!("Summary of a {size}-byte target (use --full to show it all)"),
+        format!("  Subject type: {}", subject_kind(&subject)),
+        format!("  Subject digest: {}", subject.digest().ur_string()),
+        format!("  Size: {size} bytes"),
+        format!("  Assertions: {} ({predicates})", target.assertions().len()),
+    ]
+    .join("\n")
+}
+
+fn subject_kind(subject: &Envelope) -> &'static str {
+    if subject.is_wrapped() {
+        "wrapped envelope"
+    } else if subject.is_encrypted() {
+        "encrypted"
+    } else if subject.is_compressed() {
+        "compressed"
+    } else if subject.is_elided() {
+        "elided"
+    } else if subject.is_known_value() {
+        "known value"
+    } else {
+        "leaf"
+    }
+}
@@ -5,21 +5,29 @@ use bc_components::{ARID, XID, XIDProvider};
 use bc_envelope::prelude::*;
 use clap::Parser;
 use gstp::{SealedRequest, SealedRequestBehavior};
-use tokio::runtime::Runtime;
 
 use crate::{
     cmd::{
         busy::get_with_indicator,
+        common::{
+            GroupStatePaths, check_clock_skew, expired_error, load_registry,
+            sort_for_display, write_atomically,
+        },
         dkg::{
             OptionalStorageSelector,
             common::{
-                format_name_with_owner_marker, parse_arid_ur,
-                parse_envelope_ur, resolve_sender, resolve_sender_name,
+                check_strict_sender, format_name_with_owner_marker,
+                parse_arid_ur, parse_envelope_ur, read_ur_input,
+                resolve_sender, resolve_sender_name,
             },
         },
+        is_strict_sender,
         registry::participants_file_path,
-        sign::common::signing_state_dir,
-        storage::{StorageClient, StorageSelection},
+        sign::{
+            common::{embedded_charter, is_pre_hashed, require_finalized},
+            participant::format_target,
+        },
+        storage::{StorageClient, StorageSelection, shared_runtime},
     },
     registry::Registry,
 };
@@ -43,12 +51,23 @@ pub struct CommandArgs {
     #[arg(long)]
     info: bool,
 
+    /// Show the whole target even when it is large enough to be summarized
+    #[arg(long)]
+    full: bool,
+
+    /// Download the target again from the ARID the coordinator fetched it
+    /// from (`sign coordinator invite --fetch-target`) and require it to
+    /// match the copy embedded in the request
+    #[arg(long = "verify-target")]
+    verify_target: bool,
+
     /// Optionally require the request to come from this sender (ur:xid or pet
     /// name in registry)
     #[arg(long = "sender", value_name = "SENDER")]
     sender: Option<String>,
 
     /// signInvite request ARID or envelope (ur:arid or ur:envelope)
+    /// (`-` reads it from stdin)
     #[arg(value_name = "REQUEST")]
     request: String,
 }
@@ -59,9 +78,12 @@ impl CommandArgs {
         if selection.is_none() && self.timeout.is_some() {
             bail!("--timeout requires Hubert storage parameters");
         }
+        if selection.is_none() && self.verify_target {
+            bail!("--verify-target requires Hubert storage parameters");
+        }
 
         let registry_path = participants_file_path(self.registry.clone())?;
-        let registry = Registry::load(&registry_path).with_context(|| {
+        let registry = load_registry(&registry_path).with_context(|| {
             format!("Failed to load registry at {}", registry_path.display())
         })?;
         let owner = registry
@@ -76,7 +98,7 @@ impl CommandArgs {
 
         let envelope = resolve_sign_request(
             selection.clone(),
-            &self.request,
+            &read_ur_input(&self.request)?,
             self.timeout,
         )?;
 
@@ -101,6 +123,11 @@ impl CommandArgs {
                     expected.xid().ur_string()
                 );
             }
+        } else if is_strict_sender() {
+            bail!(
+                "--strict-sender requires --sender to name who the request \
+                 must come from"
+            );
         } else {
             let sender_xid = sealed_request.sender().xid();
             let known_owner = registry
@@ -115,27 +142,45 @@ impl CommandArgs {
                 );
             }
         }
+        check_strict_sender(&registry, sealed_request.sender())?;
 
         // Validate function
         if sealed_request.function() != &Function::from("signInvite") {
             bail!("Unexpected request function: {}", sealed_request.function());
         }
 
+        check_clock_skew("signInvite request", sealed_request.date(), &now);
+
         // Parameters
         let valid_until: Date =
             sealed_request.extract_object_for_parameter("validUntil")?;
         if valid_until <= now {
-            bail!("signInvite request has expired");
+            return Err(expired_error(
+                "signInvite request",
+                &valid_until,
+                &now,
+            ));
         }
 
         let group_id: ARID =
             sealed_request.extract_object_for_parameter("group")?;
+        let group_record = registry.group(&group_id).with_context(|| {
+            format!("Group {} not found in registry", group_id.ur_string())
+        })?;
+        require_finalized(group_record)?;
         let session_id: ARID =
             sealed_request.extract_object_for_parameter("session")?;
         let min_signers: usize = sealed_request
             .extract_object_for_parameter::<u64>("minSigners")?
             .try_into()
             .context("minSigners does not fit in usize")?;
+        if min_signers != group_record.min_signers() {
+            bail!(
+                "signInvite asks for {min_signers} signers but the group \
+                 agreed on {}",
+                group_record.min_signers()
+            );
+        }
 
         // Participants and the recipient's commit ARID
         let mut participants: Vec<XID> = Vec::new();
@@ -171,6 +216,23 @@ impl CommandArgs {
         participants.sort();
 
         let target_envelope = sealed_request.object_for_parameter("target")?;
+        let target_arid: Option<ARID> = sealed_request
+            .objects_for_parameter("targetArid")
+            .first()
+            .map(|envelope| envelope.extract_subject::<ARID>())
+            .transpose()?;
+        if self.verify_target {
+            let target_arid = target_arid.as_ref().context(
+                "signInvite request does not name a target ARID to verify \
+                 against",
+            )?;
+            verify_fetched_target(
+                selection.clone().context("Hubert storage is required")?,
+                target_arid,
+                &target_envelope,
+                self.timeout,
+            )?;
+        }
 
         let coordinator_name =
             resolve_sender_name(&registry, sealed_request.sender())
@@ -182,14 +244,34 @@ impl CommandArgs {
         println!("Min signers: {}", min_signers);
         println!("Participants: {}", participant_names.join(", "));
         println!("Target:");
-        println!("{}", target_envelope.format());
+        println!("{}", format_target(&target_envelope, self.full));
+        if let Some(target_arid) = &target_arid {
+            println!("Target ARID: {}", target_arid.ur_string());
+        }
+        if let Some(charter) = embedded_charter(&target_envelope) {
+            println!("Charter: {charter}");
+            if charter != group_record.charter() {
+                eprintln!(
+                    "Warning: the charter in this target differs from the \
+                     group's charter: {}",
+                    group_record.charter()
+                );
+            }
+        }
+        if is_pre_hashed(&target_envelope) {
+            eprintln!(
+                "Warning: this session signs a pre-hashed message. Only \
+                 participate if you have verified out of band what it is a \
+                 hash of."
+            );
+        }
 
         // Primary output for scripting: session ID on its own line (no header).
         println!("{}", session_id.ur_string());
 
         // Persist request details for follow-up commands
-        let state_dir =
-            signing_state_dir(&registry_path, &group_id, &session_id);
+        let paths = GroupStatePaths::new(&registry_path);
+        let state_dir = paths.session_dir(&group_id, &session_id);
         fs::create_dir_all(&state_dir).with_context(|| {
             format!(
                 "Failed to create signing state directory {}",
@@ -237,8 +319,8 @@ impl CommandArgs {
             "target".to_string(),
             serde_json::Value::String(target_envelope.ur_string()),
         );
-        fs::write(
-            state_dir.join("sign_receive.json"),
+        write_atomically(
+            paths.receive_state_path(&group_id, &session_id),
             serde_json::to_vec_pretty(&root)?,
         )
         .context("Failed to persist signInvite request details")?;
@@ -254,18 +336,17 @@ fn resolve_sign_request(
 ) -> Result<Envelope> {
     if let Some(selection) = selection {
         if let Ok(arid) = parse_arid_ur(request) {
-            let runtime = Runtime::new()?;
-            let client = runtime.block_on(async {
-                StorageClient::from_selection(selection).await
-            })?;
+            let runtime = shared_runtime()?;
+            let client = runtime
+                .block_on(async { StorageClient::shared(selection).await })?;
             return get_with_indicator(
-                &runtime,
+                runtime,
                 &client,
                 &arid,
                 "Sign invite",
                 timeout,
             )?
-            .context("signInvite request not found in Hubert storage");
+            .context("Nothing posted yet at the signInvite request ARID");
         }
         if timeout.is_some() {
             bail!(
@@ -283,12 +364,38 @@ fn resolve_sign_request(
     parse_envelope_ur(request)
 }
 
+fn verify_fetched_target(
+    selection: StorageSelection,
+    target_arid: &ARID,
+    embedded: &Envelope,
+    timeout: Option<u64>,
+) -> Result<()> {
+    let runtime = shared_runtime()?;
+    let client =
+        runtime.block_on(async { StorageClient::shared(selection).await })?;
+    let fetched =
+        get_with_indicator(runtime, &client, target_arid, "Target", timeout)?
+            .with_context(|| {
+            format!("Nothing posted at target ARID {}", target_arid.ur_string())
+        })?;
+    if fetched.digest() != embedded.digest() {
+        bail!(
+            "Target stored at {} does not match the target in the \
+             signInvite request (digest {} vs {})",
+            target_arid.ur_string(),
+            fetched.digest(),
+            embedded.digest()
+        );
+    }
+    Ok(())
+}
+
 fn format_participant_names(
     registry: &Registry,
     participants: &[XID],
     owner: &crate::registry::OwnerRecord,
 ) -> Vec<String> {
-    participants
+    let mut names: Vec<(String, XID, bool)> = participants
         .iter()
         .map(|xid| {
             let is_owner = *xid == owner.xid();
@@ -303,6 +410,14 @@ fn format_participant_names(
                     .and_then(|r| r.pet_name().map(|n| n.to_owned()))
                     .unwrap_or_else(|| xid.ur_string())
             };
+            (name, *xid, is_owner)
+        })
+        .collect();
+    sort_for_display(&mut names, |(name, xid, _)| (name.clone(), *xid));
+
+    names
+        .into_iter()
+        .map(|(name, _, is_owner)| {
             format_name_with_owner_marker(name, is_owner)
         })
         .collect()
@@ -1,11 +1,12 @@
 use std::{
     fs,
+    io::{self, IsTerminal},
     path::{Path, PathBuf},
     time::Duration,
 };
 
 use anyhow::{Context, Result, bail};
-use bc_components::{ARID, JSON, XID};
+use bc_components::{ARID, JSON, PrivateKeys, XID};
 use bc_envelope::prelude::*;
 use clap::Parser;
 use frost_ed25519::{self as frost, rand_core::OsRng};
@@ -13,18 +14,28 @@ use gstp::{
     SealedRequest, SealedRequestBehavior, SealedResponse,
     SealedResponseBehavior,
 };
-use tokio::runtime::Runtime;
 
 use crate::{
     cmd::{
         busy::put_with_indicator,
-        dkg::{OptionalStorageSelector, common::parse_arid_ur},
+        common::{GroupStatePaths, confirm, load_registry, write_atomically},
+        dkg::{
+            OptionalStorageSelector,
+            common::{
+                parse_arid_ur, parse_group_id, read_ur_input,
+                resolve_also_notify, response_recipient, seal_response,
+            },
+        },
         is_verbose,
         registry::participants_file_path,
-        sign::common::signing_state_dir,
-        storage::StorageClient,
+        sign::common::{
+            ExternalCommitments, is_pre_hashed, read_external_commitments,
+        },
+        storage::{
+            StorageClient, StorageSelection, compress_payload, shared_runtime,
+        },
     },
-    registry::Registry,
+    registry::{OwnerRecord, Registry},
 };
 
 /// Respond to a signInvite request (participant).
@@ -46,11 +57,34 @@ pub struct CommandArgs {
     #[arg(long = "reject", value_name = "REASON")]
     reject_reason: Option<String>,
 
+    /// Show the target and require typing `yes` before committing (on by
+    /// default when stdin is a terminal, except with --preview; see --yes and
+    /// --no-input)
+    #[arg(long = "confirm-target", conflicts_with = "reject_reason")]
+    confirm_target: bool,
+
+    /// Commit with signing commitments generated by an external signer such
+    /// as an HSM, read from this JSON file (`signing_commitments` and an
+    /// optional opaque `nonce_handle`), instead of generating nonces here.
+    /// The share step then takes the signer's share from `--share-file`
+    #[arg(
+        long = "commitments-file",
+        value_name = "PATH",
+        conflicts_with = "reject_reason"
+    )]
+    commitments_file: Option<PathBuf>,
+
+    /// Also seal the response to this registered participant, such as a
+    /// backup coordinator or an observer (repeatable)
+    #[arg(long = "also-notify", value_name = "PARTICIPANT")]
+    also_notify: Vec<String>,
+
     /// Optional group ID hint when multiple groups exist
     #[arg(long = "group", value_name = "UR:ARID")]
     group_id: Option<String>,
 
     /// Signing session ID to respond to
+    /// (`-` reads it from stdin)
     #[arg(value_name = "SESSION_ID")]
     session: String,
 }
@@ -67,7 +101,7 @@ impl CommandArgs {
 
         let registry_path = participants_file_path(self.registry.clone())?;
         let mut registry =
-            Registry::load(&registry_path).with_context(|| {
+            load_registry(&registry_path).with_context(|| {
                 format!(
                     "Failed to load registry at {}",
                     registry_path.display()
@@ -79,9 +113,17 @@ impl CommandArgs {
             .context("Registry owner is required")?
             .clone();
 
-        let session_id = parse_arid_ur(&self.session)?;
+        let also_notify = resolve_also_notify(&registry, &self.also_notify)?;
+
+        let external = self
+            .commitments_file
+            .as_deref()
+            .map(read_external_commitments)
+            .transpose()?;
+
+        let session_id = parse_arid_ur(&read_ur_input(&self.session)?)?;
         let group_hint = match &self.group_id {
-            Some(raw) => Some(parse_arid_ur(raw)?),
+            Some(raw) => Some(parse_group_id(&registry, raw)?),
             None => None,
         };
 
@@ -92,49 +134,28 @@ impl CommandArgs {
             &registry,
         )?;
         let group_id = receive_state.group_id;
+        // Held until the command returns, so the session state checked
+        // below cannot change before this command writes its own
+        let _lock =
+            GroupStatePaths::new(&registry_path).lock_group(&group_id)?;
         let group_record = registry
             .group(&group_id)
             .context("Group not found in registry")?
             .clone();
 
         // Decrypt persisted request to validate and get peer continuation
-        let owner_keys = owner
-            .xid_document()
-            .inception_private_keys()
-            .context("Owner XID document has no private keys")?;
-        let sealed_request = SealedRequest::try_from_envelope(
-            &receive_state.request_envelope,
-            None,
-            Some(Date::now()),
-            owner_keys,
-        )?;
-
-        if sealed_request.function() != &Function::from("signInvite") {
-            bail!("Unexpected request function: {}", sealed_request.function());
-        }
-
-        if sealed_request.id() != session_id {
-            bail!(
-                "Session ID mismatch (state {}, request {})",
-                session_id.ur_string(),
-                sealed_request.id().ur_string()
-            );
-        }
+        let sealed_request =
+            open_sign_invite(&receive_state, &session_id, &owner)?;
 
-        let request_group: ARID =
-            sealed_request.extract_object_for_parameter("group")?;
-        if request_group != group_id {
-            bail!(
-                "Group ID mismatch (state {}, request {})",
-                group_id.ur_string(),
-                request_group.ur_string()
-            );
-        }
+        let target_envelope =
+            Envelope::from_ur_string(&receive_state.target_ur)
+                .context("Invalid target UR in persisted state")?;
 
-        if !receive_state.participants.contains(&owner.xid()) {
-            bail!(
-                "Persisted signInvite request does not include this participant"
-            );
+        // Committing nonces binds this participant to the target
+        let should_confirm =
+            self.confirm_target || (!self.preview && io::stdin().is_terminal());
+        if self.reject_reason.is_none() && should_confirm {
+            confirm_target(&target_envelope)?;
         }
 
         // Load key package
@@ -149,10 +170,6 @@ impl CommandArgs {
             )?)
             .context("Failed to parse key_package.json")?;
 
-        let target_envelope =
-            Envelope::from_ur_string(&receive_state.target_ur)
-                .context("Invalid target UR in persisted state")?;
-
         // Reject path
         let next_share_arid = if self.reject_reason.is_none() {
             Some(ARID::new())
@@ -165,22 +182,21 @@ impl CommandArgs {
             .inception_private_keys()
             .context("Owner XID document has no signing keys")?;
 
-        let sealed_response = if let Some(reason) = self.reject_reason.clone() {
-            let error_body = Envelope::new("signCommitReject")
-                .add_assertion("group", group_id)
-                .add_assertion("session", session_id)
-                .add_assertion("reason", reason.clone());
-
-            SealedResponse::new_failure(
-                sealed_request.id(),
-                owner.xid_document().clone(),
-            )
-            .with_error(error_body)
-            .with_peer_continuation(sealed_request.peer_continuation())
+        let sealed_response = if let Some(reason) = &self.reject_reason {
+            decline_response(&sealed_request, &owner, &group_id, reason)
         } else {
-            // Run signing part1
-            let (signing_nonces, signing_commitments) =
-                frost::round1::commit(key_package.signing_share(), &mut OsRng);
+            // Run signing part1, unless an external signer already did and
+            // keeps the nonces itself
+            let (signing_nonces, signing_commitments) = match &external {
+                Some(external) => (None, external.signing_commitments),
+                None => {
+                    let (nonces, commitments) = frost::round1::commit(
+                        key_package.signing_share(),
+                        &mut OsRng,
+                    );
+                    (Some(nonces), commitments)
+                }
+            };
 
             let commitments_json =
                 JSON::from_data(serde_json::to_vec(&signing_commitments)?);
@@ -201,7 +217,8 @@ impl CommandArgs {
                     &group_id,
                     &session_id,
                     &receive_state,
-                    &signing_nonces,
+                    signing_nonces.as_ref(),
+                    external.as_ref(),
                     &signing_commitments,
                     &target_envelope,
                     next_share,
@@ -218,45 +235,24 @@ impl CommandArgs {
                 sealed_request.id(),
                 owner.xid_document().clone(),
             )
-            .with_result(response_body)
+            .with_result(compress_payload(response_body)?)
             .with_peer_continuation(sealed_request.peer_continuation())
         };
 
-        if self.preview {
-            let unsealed = sealed_response.to_envelope(
-                None,
-                Some(signer_private_keys),
-                None,
-            )?;
-            println!("{}", unsealed.ur_string());
-            return Ok(());
-        }
-
-        let response_envelope = sealed_response.to_envelope(
-            Some(Date::with_duration_from_now(Duration::from_secs(60 * 60))),
-            Some(signer_private_keys),
-            Some(&receive_state.coordinator_doc),
-        )?;
-
-        let selection =
-            selection.context("Hubert storage is required for sign commit")?;
-        let runtime = Runtime::new()?;
-        let client = runtime.block_on(async {
-            StorageClient::from_selection(selection).await
-        })?;
-
-        if is_verbose() {
-            eprintln!(
-                "Posting signInvite response to {}",
-                receive_state.response_arid.ur_string()
+        let Some(selection) = selection else {
+            return print_response_preview(
+                &sealed_response,
+                signer_private_keys,
             );
-        }
-
-        put_with_indicator(
-            &runtime,
-            &client,
-            &receive_state.response_arid,
-            &response_envelope,
+        };
+        post_response(
+            selection,
+            &sealed_request,
+            &sealed_response,
+            &receive_state,
+            &registry,
+            signer_private_keys,
+            &also_notify,
             "Commitments",
         )?;
 
@@ -273,8 +269,158 @@ impl CommandArgs {
     }
 }
 
-struct ReceiveState {
-    group_id: ARID,
+/// Prints the target and requires the user to confirm it.
+fn confirm_target(target_envelope: &Envelope) -> Result<()> {
+    eprintln!("Target:");
+    eprintln!("{}", target_envelope.format());
+    if is_pre_hashed(target_envelope) {
+        eprintln!(
+            "Warning: this session signs a pre-hashed message. Only commit if \
+             you have verified out of band what it is a hash of."
+        );
+    }
+    if !confirm("Type 'yes' to commit to signing this target:")? {
+        bail!("Target not confirmed; no commitment was generated");
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// signInvite responses
+// -----------------------------------------------------------------------------
+
+/// Decrypts the persisted signInvite and checks that it belongs to
+/// `session_id`, the persisted group, and the registry owner.
+pub(super) fn open_sign_invite(
+    receive_state: &ReceiveState,
+    session_id: &ARID,
+    owner: &OwnerRecord,
+) -> Result<SealedRequest> {
+    let owner_keys = owner
+        .xid_document()
+        .inception_private_keys()
+        .context("Owner XID document has no private keys")?;
+    let sealed_request = SealedRequest::try_from_envelope(
+        &receive_state.request_envelope,
+        None,
+        Some(Date::now()),
+        owner_keys,
+    )?;
+
+    if sealed_request.function() != &Function::from("signInvite") {
+        bail!("Unexpected request function: {}", sealed_request.function());
+    }
+
+    if sealed_request.id() != *session_id {
+        bail!(
+            "Session ID mismatch (state {}, request {})",
+            session_id.ur_string(),
+            sealed_request.id().ur_string()
+        );
+    }
+
+    let request_group: ARID =
+        sealed_request.extract_object_for_parameter("group")?;
+    if request_group != receive_state.group_id {
+        bail!(
+            "Group ID mismatch (state {}, request {})",
+            receive_state.group_id.ur_string(),
+            request_group.ur_string()
+        );
+    }
+
+    if !receive_state.participants.contains(&owner.xid()) {
+        bail!("Persisted signInvite request does not include this participant");
+    }
+
+    Ok(sealed_request)
+}
+
+/// Builds the failure response that declines a signInvite. The coordinator
+/// classifies it as a rejection carrying `reason`.
+pub(super) fn decline_response(
+    sealed_request: &SealedRequest,
+    owner: &OwnerRecord,
+    group_id: &ARID,
+    reason: &str,
+) -> SealedResponse {
+    let error_body = Envelope::new("signCommitReject")
+        .add_assertion("group", *group_id)
+        .add_assertion("session", sealed_request.id())
+        .add_assertion("reason", reason);
+
+    SealedResponse::new_failure(
+        sealed_request.id(),
+        owner.xid_document().clone(),
+    )
+    .with_error(error_body)
+    .with_peer_continuation(sealed_request.peer_continuation())
+}
+
+/// Prints the response envelope, signed but not encrypted, instead of
+/// sending it.
+pub(super) fn print_response_preview(
+    sealed_response: &SealedResponse,
+    signer_private_keys: &PrivateKeys,
+) -> Result<()> {
+    let unsealed =
+        sealed_response.to_envelope(None, Some(signer_private_keys), None)?;
+    println!("{}", unsealed.ur_string());
+    Ok(())
+}
+
+/// Seals the response to the coordinator (or the invite's designated
+/// recipient) and posts it to the signInvite's response ARID.
+pub(super) fn post_response(
+    selection: StorageSelection,
+    sealed_request: &SealedRequest,
+    sealed_response: &SealedResponse,
+    receive_state: &ReceiveState,
+    registry: &Registry,
+    signer_private_keys: &PrivateKeys,
+    also_notify: &[bc_xid::XIDDocument],
+    label: &str,
+) -> Result<()> {
+    let recipient_doc = response_recipient(
+        sealed_request,
+        registry,
+        receive_state.coordinator_doc.clone(),
+    )?;
+    let response_envelope = seal_response(
+        sealed_response,
+        Some(Date::with_duration_from_now(Duration::from_secs(60 * 60))),
+        signer_private_keys,
+        &recipient_doc,
+        also_notify,
+    )?;
+
+    let runtime = shared_runtime()?;
+    let client =
+        runtime.block_on(async { StorageClient::shared(selection).await })?;
+
+    if is_verbose() {
+        eprintln!(
+            "Posting signInvite response to {}",
+            receive_state.response_arid.ur_string()
+        );
+    }
+
+    put_with_indicator(
+        runtime,
+        &client,
+        &receive_state.response_arid,
+        &response_envelope,
+        label,
+    )?;
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// Receive state
+// -----------------------------------------------------------------------------
+
+pub(super) struct ReceiveState {
+    pub(super) group_id: ARID,
     coordinator_doc: bc_xid::XIDDocument,
     response_arid: ARID,
     target_ur: String,
@@ -282,46 +428,16 @@ struct ReceiveState {
     request_envelope: Envelope,
 }
 
-fn load_receive_state(
+pub(super) fn load_receive_state(
     registry_path: &Path,
     session_id: &ARID,
     group_hint: Option<ARID>,
     registry: &Registry,
 ) -> Result<ReceiveState> {
-    let base = registry_path
-        .parent()
-        .map(Path::to_path_buf)
-        .unwrap_or_else(|| PathBuf::from("."));
-    let group_state_dir = base.join("group-state");
-
-    let group_dirs: Vec<(ARID, PathBuf)> = if let Some(group) = group_hint {
-        vec![(group, group_state_dir.join(group.hex()))]
-    } else {
-        let mut dirs = Vec::new();
-        if group_state_dir.exists() {
-            for entry in fs::read_dir(&group_state_dir)? {
-                let entry = entry?;
-                if entry.file_type()?.is_dir() {
-                    let dir_name = entry.file_name();
-                    if let Some(name) = dir_name.to_str()
-                        && name.len() == 64
-                        && name.chars().all(|c| c.is_ascii_hexdigit())
-                    {
-                        let group_id = ARID::from_hex(name);
-                        dirs.push((group_id, entry.path()));
-                    }
-                }
-            }
-        }
-        dirs
-    };
-
+    let paths = GroupStatePaths::new(registry_path);
     let mut candidates = Vec::new();
-    for (group_id, group_dir) in group_dirs {
-        let candidate = group_dir
-            .join("signing")
-            .join(session_id.hex())
-            .join("sign_receive.json");
+    for group_id in paths.candidate_groups(group_hint)? {
+        let candidate = paths.receive_state_path(&group_id, session_id);
         if candidate.exists() {
             candidates.push((group_id, candidate));
         }
@@ -410,18 +526,21 @@ fn load_receive_state(
     })
 }
 
+/// Writes commit.json. The caller holds the group lock.
 #[allow(clippy::too_many_arguments)]
 fn persist_commit_state(
     registry_path: &Path,
     group_id: &ARID,
     session_id: &ARID,
     receive_state: &ReceiveState,
-    signing_nonces: &frost::round1::SigningNonces,
+    signing_nonces: Option<&frost::round1::SigningNonces>,
+    external: Option<&ExternalCommitments>,
     signing_commitments: &frost::round1::SigningCommitments,
     target_envelope: &Envelope,
     next_share_arid: ARID,
 ) -> Result<()> {
-    let dir = signing_state_dir(registry_path, group_id, session_id);
+    let paths = GroupStatePaths::new(registry_path);
+    let dir = paths.session_dir(group_id, session_id);
     fs::create_dir_all(&dir).with_context(|| {
         format!("Failed to create signing state directory {}", dir.display())
     })?;
@@ -443,19 +562,34 @@ fn persist_commit_state(
         "target".to_string(),
         serde_json::Value::String(target_envelope.ur_string()),
     );
-    root.insert(
-        "signing_nonces".to_string(),
-        serde_json::to_value(signing_nonces)
-            .context("Failed to serialize signing nonces")?,
-    );
+    if let Some(signing_nonces) = signing_nonces {
+        root.insert(
+            "signing_nonces".to_string(),
+            serde_json::to_value(signing_nonces)
+                .context("Failed to serialize signing nonces")?,
+        );
+    }
+    if let Some(external) = external {
+        // The nonces stay with the external signer; only its handle to them
+        // is recorded
+        root.insert(
+            "external_signer".to_string(),
+            serde_json::Value::Bool(true),
+        );
+        if let Some(handle) = &external.nonce_handle {
+            root.insert(
+                "nonce_handle".to_string(),
+                serde_json::Value::String(handle.clone()),
+            );
+        }
+    }
     root.insert(
         "signing_commitments".to_string(),
         serde_json::to_value(signing_commitments)
             .context("Failed to serialize signing commitments")?,
     );
 
-    fs::write(dir.join("commit.json"), serde_json::to_vec_pretty(&root)?)
-        .with_context(|| {
-            format!("Failed to write {}", dir.join("commit.json").display())
-        })
+    let path = paths.commit_state_path(group_id, session_id);
+    write_atomically(&path, serde_json::to_vec_pretty(&root)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
 }
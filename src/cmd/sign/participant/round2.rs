@@ -6,7 +6,7 @@ use std::{
 };
 
 use anyhow::{Context, Result, bail};
-use bc_components::{ARID, Digest, JSON, XID, XIDProvider};
+use bc_components::{ARID, JSON, XID, XIDProvider};
 use bc_envelope::prelude::*;
 use clap::Parser;
 use frost_ed25519 as frost;
@@ -14,18 +14,26 @@ use gstp::{
     SealedRequest, SealedRequestBehavior, SealedResponse,
     SealedResponseBehavior,
 };
-use tokio::runtime::Runtime;
-
-use crate::{
-    cmd::{
-        busy::{get_with_indicator, put_with_indicator},
-        dkg::{OptionalStorageSelector, common::parse_arid_ur},
-        is_verbose,
-        registry::participants_file_path,
-        sign::common::signing_state_dir,
-        storage::StorageClient,
+
+use crate::cmd::{
+    busy::{get_with_indicator, put_with_indicator},
+    common::{GroupStatePaths, load_registry, write_atomically},
+    dkg::{
+        OptionalStorageSelector,
+        common::{
+            check_strict_sender, parse_arid_ur, parse_group_id, read_ur_input,
+            resolve_also_notify, response_recipient, seal_response,
+        },
+    },
+    is_verbose,
+    registry::participants_file_path,
+    sign::common::{
+        DetachedSignature, read_external_signature_share, signing_message,
+        verify_own_signature_share, write_external_signing_package,
+    },
+    storage::{
+        StorageClient, compress_payload, decompress_envelope, shared_runtime,
     },
-    registry::Registry,
 };
 
 /// Respond to a signRound2 request (participant).
@@ -43,15 +51,38 @@ pub struct CommandArgs {
     #[arg(long = "timeout", value_name = "SECONDS")]
     timeout: Option<u64>,
 
-    /// Print the unsealed response envelope UR instead of sending
+    /// Print the unsealed response envelope UR instead of sending it; the
+    /// share state and the registry's listening ARID are left untouched
     #[arg(long = "preview")]
     preview: bool,
 
+    /// Write the signing package (with the commit's nonce handle, if any) to
+    /// this file for an external signer and exit without responding
+    #[arg(
+        long = "signing-package-out",
+        value_name = "PATH",
+        conflicts_with_all = ["preview", "share_file"]
+    )]
+    signing_package_out: Option<PathBuf>,
+
+    /// Respond with a signature share computed by an external or air-gapped
+    /// signer, read from this JSON file, instead of signing here. The share
+    /// must verify against the signing package and this participant's
+    /// verifying share
+    #[arg(long = "share-file", value_name = "PATH")]
+    share_file: Option<PathBuf>,
+
+    /// Also seal the response to this registered participant, such as a
+    /// backup coordinator or an observer (repeatable)
+    #[arg(long = "also-notify", value_name = "PARTICIPANT")]
+    also_notify: Vec<String>,
+
     /// Optional group ID hint when multiple groups contain this session
     #[arg(long = "group", value_name = "UR:ARID")]
     group_id: Option<String>,
 
     /// Signing session ID to respond to
+    /// (`-` reads it from stdin)
     #[arg(value_name = "SESSION_ID")]
     session: String,
 }
@@ -64,7 +95,7 @@ impl CommandArgs {
 
         let registry_path = participants_file_path(self.registry.clone())?;
         let mut registry =
-            Registry::load(&registry_path).with_context(|| {
+            load_registry(&registry_path).with_context(|| {
                 format!(
                     "Failed to load registry at {}",
                     registry_path.display()
@@ -75,16 +106,21 @@ impl CommandArgs {
             .owner()
             .context("Registry owner is required")?
             .clone();
+        let also_notify = resolve_also_notify(&registry, &self.also_notify)?;
 
-        let session_id = parse_arid_ur(&self.session)?;
+        let session_id = parse_arid_ur(&read_ur_input(&self.session)?)?;
         let group_hint = match &self.group_id {
-            Some(raw) => Some(parse_arid_ur(raw)?),
+            Some(raw) => Some(parse_group_id(&registry, raw)?),
             None => None,
         };
 
         let receive_state =
             load_receive_state(&registry_path, &session_id, group_hint)?;
         let group_id = receive_state.group_id;
+        // Held until the command returns, so the session state checked
+        // below cannot change before this command writes its own
+        let _lock =
+            GroupStatePaths::new(&registry_path).lock_group(&group_id)?;
         let group_record = registry
             .group(&group_id)
             .context("Group not found in registry")?
@@ -137,6 +173,22 @@ impl CommandArgs {
             );
         }
 
+        if commit_state.external_signer
+            && self.signing_package_out.is_none()
+            && self.share_file.is_none()
+        {
+            bail!(
+                "This session was committed by an external signer; export \
+                 what it must sign with --signing-package-out, then respond \
+                 with its share using --share-file"
+            );
+        }
+        let external_share = self
+            .share_file
+            .as_deref()
+            .map(read_external_signature_share)
+            .transpose()?;
+
         let key_package_path = group_record
             .contributions()
             .key_package
@@ -150,30 +202,27 @@ impl CommandArgs {
 
         let finalize_arid = ARID::new();
 
-        // Compute target digest from persisted target envelope
         let target_envelope =
             Envelope::from_ur_string(&receive_state.target_ur).with_context(
                 || "Invalid target envelope UR in persisted state".to_string(),
             )?;
-        let target_digest: Digest = target_envelope.subject().digest();
 
         if is_verbose() {
             eprintln!("Fetching signRound2 request from Hubert...");
         }
 
-        let runtime = Runtime::new()?;
-        let client = runtime.block_on(async {
-            StorageClient::from_selection(selection).await
-        })?;
+        let runtime = shared_runtime()?;
+        let client = runtime
+            .block_on(async { StorageClient::shared(selection).await })?;
 
         let request_envelope = get_with_indicator(
-            &runtime,
+            runtime,
             &client,
             &listening_at_arid,
             "signRound2 request",
             self.timeout,
         )?
-        .context("signRound2 request not found in Hubert storage")?;
+        .context("Nothing posted yet at the signRound2 request ARID")?;
 
         let signer_private_keys = owner
             .xid_document()
@@ -209,6 +258,7 @@ impl CommandArgs {
                 expected_coordinator.ur_string()
             );
         }
+        check_strict_sender(&registry, sealed_request.sender())?;
 
         let response_arid: ARID =
             sealed_request.extract_object_for_parameter("response_arid")?;
@@ -226,9 +276,8 @@ impl CommandArgs {
             );
         }
 
-        // Map XIDs to identifiers (sorted participant order)
-        let xid_to_identifier =
-            xid_identifier_map(&receive_state.participants)?;
+        // Map XIDs to identifiers under the group's identifier scheme
+        let xid_to_identifier = group_record.identifiers()?;
 
         let my_identifier = xid_to_identifier
             .get(&owner.xid())
@@ -258,22 +307,64 @@ impl CommandArgs {
             );
         }
 
+        // A detached session signs the coordinator's statement of the
+        // target, group, and signers, once it checks out against this
+        // participant's own view of the session
+        let statement = sealed_request
+            .objects_for_parameter("detached")
+            .first()
+            .cloned();
+        if let Some(statement) = &statement {
+            DetachedSignature::check_statement(
+                statement,
+                &target_envelope,
+                &group_id,
+                commitments_by_xid.keys().copied(),
+            )?;
+        }
+        let message =
+            signing_message(statement.as_ref().unwrap_or(&target_envelope))?;
+
         let signing_commitments = commitments_with_identifiers(
             &commitments_by_xid,
             &xid_to_identifier,
         )?;
 
-        let signing_package = frost::SigningPackage::new(
-            signing_commitments,
-            target_digest.data(),
-        );
+        let signing_package =
+            frost::SigningPackage::new(signing_commitments, &message);
 
-        let signature_share = frost::round2::sign(
-            &signing_package,
-            &commit_state.signing_nonces,
-            &key_package,
-        )
-        .map_err(|e| anyhow::anyhow!("FROST signing failed: {}", e))?;
+        if let Some(path) = &self.signing_package_out {
+            write_external_signing_package(
+                path,
+                &session_id,
+                &signing_package,
+                commit_state.nonce_handle.as_deref(),
+            )?;
+            println!("{}", path.display());
+            return Ok(());
+        }
+
+        let signature_share = match external_share {
+            // The external signer's share is checked here so a faulty device
+            // is caught before the coordinator aggregates
+            Some(share) => {
+                verify_own_signature_share(
+                    &signing_package,
+                    &share,
+                    &key_package,
+                )?;
+                share
+            }
+            None => frost::round2::sign(
+                &signing_package,
+                commit_state
+                    .signing_nonces
+                    .as_ref()
+                    .context("Missing signing_nonces in commit.json")?,
+                &key_package,
+            )
+            .map_err(|e| anyhow::anyhow!("FROST signing failed: {}", e))?,
+        };
 
         let response_body = Envelope::unit()
             .add_type("signRound2Response")
@@ -284,13 +375,19 @@ impl CommandArgs {
                     &signature_share,
                 )?)),
             )
-            .add_assertion("response_arid", finalize_arid);
+            .add_assertion("response_arid", finalize_arid)
+            .add_assertion(
+                "commitments",
+                CBOR::from(JSON::from_data(serde_json::to_vec(
+                    my_commitments,
+                )?)),
+            );
 
         let sealed_response = SealedResponse::new_success(
             sealed_request.id(),
             owner.xid_document().clone(),
         )
-        .with_result(response_body)
+        .with_result(compress_payload(response_body)?)
         .with_peer_continuation(sealed_request.peer_continuation());
 
         if self.preview {
@@ -316,15 +413,19 @@ impl CommandArgs {
                     )
                 })?
         };
+        let recipient_doc =
+            response_recipient(&sealed_request, &registry, coordinator_doc)?;
 
-        let response_envelope = sealed_response.to_envelope(
+        let response_envelope = seal_response(
+            &sealed_response,
             Some(Date::with_duration_from_now(Duration::from_secs(60 * 60))),
-            Some(signer_private_keys),
-            Some(&coordinator_doc),
+            signer_private_keys,
+            &recipient_doc,
+            &also_notify,
         )?;
 
         put_with_indicator(
-            &runtime,
+            runtime,
             &client,
             &response_arid,
             &response_envelope,
@@ -339,6 +440,7 @@ impl CommandArgs {
             &finalize_arid,
             &signature_share,
             &commitments_by_xid,
+            statement.as_ref(),
         )?;
 
         // Set listening ARID for finalize
@@ -365,6 +467,7 @@ fn parse_commitments(
 ) -> Result<BTreeMap<XID, frost::round1::SigningCommitments>> {
     let mut commitments = BTreeMap::new();
     for entry in request.objects_for_parameter("commitment") {
+        let entry = decompress_envelope(entry)?;
         let xid: XID = entry.extract_subject()?;
         let commitments_json: JSON =
             entry.extract_object_for_predicate("commitments")?;
@@ -405,18 +508,6 @@ fn parse_commitments(
     Ok(commitments)
 }
 
-fn xid_identifier_map(
-    participants: &[XID],
-) -> Result<HashMap<XID, frost::Identifier>> {
-    let mut map = HashMap::new();
-    for (i, xid) in participants.iter().enumerate() {
-        let identifier = frost::Identifier::try_from((i + 1) as u16)
-            .context("Failed to derive Identifier from participant index")?;
-        map.insert(*xid, identifier);
-    }
-    Ok(map)
-}
-
 fn commitments_with_identifiers(
     commitments: &BTreeMap<XID, frost::round1::SigningCommitments>,
     xid_to_identifier: &HashMap<XID, frost::Identifier>,
@@ -431,6 +522,8 @@ fn commitments_with_identifiers(
     Ok(mapped)
 }
 
+/// Writes share.json. The caller holds the group lock.
+#[allow(clippy::too_many_arguments)]
 fn persist_share_state(
     registry_path: &Path,
     group_id: &ARID,
@@ -439,8 +532,10 @@ fn persist_share_state(
     finalize_arid: &ARID,
     signature_share: &frost::round2::SignatureShare,
     commitments: &BTreeMap<XID, frost::round1::SigningCommitments>,
+    statement: Option<&Envelope>,
 ) -> Result<()> {
-    let dir = signing_state_dir(registry_path, group_id, session_id);
+    let paths = GroupStatePaths::new(registry_path);
+    let dir = paths.session_dir(group_id, session_id);
     fs::create_dir_all(&dir).with_context(|| {
         format!("Failed to create signing state directory {}", dir.display())
     })?;
@@ -480,11 +575,16 @@ fn persist_share_state(
         "commitments".to_string(),
         serde_json::Value::Object(commitments_json),
     );
+    if let Some(statement) = statement {
+        root.insert(
+            "detached_statement".to_string(),
+            serde_json::Value::String(statement.ur_string()),
+        );
+    }
 
-    fs::write(dir.join("share.json"), serde_json::to_vec_pretty(&root)?)
-        .with_context(|| {
-            format!("Failed to write {}", dir.join("share.json").display())
-        })
+    let path = paths.share_state_path(group_id, session_id);
+    write_atomically(&path, serde_json::to_vec_pretty(&root)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
 }
 
 fn load_receive_state(
@@ -492,40 +592,10 @@ fn load_receive_state(
     session_id: &ARID,
     group_hint: Option<ARID>,
 ) -> Result<ReceiveState> {
-    let base = registry_path
-        .parent()
-        .map(Path::to_path_buf)
-        .unwrap_or_else(|| PathBuf::from("."));
-    let group_state_dir = base.join("group-state");
-
-    let group_dirs: Vec<(ARID, PathBuf)> = if let Some(group) = group_hint {
-        vec![(group, group_state_dir.join(group.hex()))]
-    } else {
-        let mut dirs = Vec::new();
-        if group_state_dir.exists() {
-            for entry in fs::read_dir(&group_state_dir)? {
-                let entry = entry?;
-                if entry.file_type()?.is_dir() {
-                    let dir_name = entry.file_name();
-                    if let Some(name) = dir_name.to_str()
-                        && name.len() == 64
-                        && name.chars().all(|c| c.is_ascii_hexdigit())
-                    {
-                        let group_id = ARID::from_hex(name);
-                        dirs.push((group_id, entry.path()));
-                    }
-                }
-            }
-        }
-        dirs
-    };
-
+    let paths = GroupStatePaths::new(registry_path);
     let mut candidates = Vec::new();
-    for (group_id, group_dir) in group_dirs {
-        let candidate = group_dir
-            .join("signing")
-            .join(session_id.hex())
-            .join("sign_receive.json");
+    for group_id in paths.candidate_groups(group_hint)? {
+        let candidate = paths.receive_state_path(&group_id, session_id);
         if candidate.exists() {
             candidates.push((group_id, candidate));
         }
@@ -613,8 +683,8 @@ fn load_commit_state(
     group_id: &ARID,
     session_id: &ARID,
 ) -> Result<CommitState> {
-    let dir = signing_state_dir(registry_path, group_id, session_id);
-    let path = dir.join("commit.json");
+    let path = GroupStatePaths::new(registry_path)
+        .commit_state_path(group_id, session_id);
     if !path.exists() {
         bail!(
             "Commit state not found at {}. Run `frost sign participant commit` first.",
@@ -649,12 +719,28 @@ fn load_commit_state(
     let next_share_arid = parse_arid_ur(&get_str("next_share_arid")?)?;
     let target_ur = get_str("target")?;
 
-    let signing_nonces: frost::round1::SigningNonces = serde_json::from_value(
-        raw.get("signing_nonces")
-            .cloned()
-            .context("Missing signing_nonces in commit.json")?,
-    )
-    .context("Failed to deserialize signing_nonces")?;
+    // Sessions committed by an external signer record no nonces
+    let external_signer = raw
+        .get("external_signer")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let nonce_handle = raw
+        .get("nonce_handle")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+    let signing_nonces: Option<frost::round1::SigningNonces> =
+        if external_signer {
+            None
+        } else {
+            Some(
+                serde_json::from_value(
+                    raw.get("signing_nonces")
+                        .cloned()
+                        .context("Missing signing_nonces in commit.json")?,
+                )
+                .context("Failed to deserialize signing_nonces")?,
+            )
+        };
 
     let signing_commitments: frost::round1::SigningCommitments =
         serde_json::from_value(
@@ -669,6 +755,8 @@ fn load_commit_state(
         target_ur,
         signing_nonces,
         signing_commitments,
+        external_signer,
+        nonce_handle,
     })
 }
 
@@ -682,6 +770,8 @@ struct ReceiveState {
 struct CommitState {
     next_share_arid: ARID,
     target_ur: String,
-    signing_nonces: frost::round1::SigningNonces,
+    signing_nonces: Option<frost::round1::SigningNonces>,
     signing_commitments: frost::round1::SigningCommitments,
+    external_signer: bool,
+    nonce_handle: Option<String>,
 }
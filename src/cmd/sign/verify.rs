@@ -0,0 +1,101 @@
+use anyhow::{Context, Result, bail};
+use bc_envelope::prelude::*;
+use clap::Parser;
+
+use crate::cmd::{
+    common::load_registry,
+    dkg::common::{parse_envelope_ur, read_ur_input},
+    registry::participants_file_path,
+    sign::common::DetachedSignature,
+};
+
+/// Verify a detached group signature.
+///
+/// The signature is checked against the group key it carries, and that key
+/// must match the key the registry records for the group. The signature
+/// covers the target digest, group, signer list, and date alike.
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// Optional registry path or filename override
+    #[arg(long = "registry", value_name = "PATH")]
+    registry: Option<String>,
+
+    /// Target envelope the signature is expected to cover
+    #[arg(long = "target", value_name = "UR:ENVELOPE")]
+    target: Option<String>,
+
+    /// Accept the group key carried by the signature when the registry has
+    /// no key for the group; a key that differs from the registry's is
+    /// still rejected
+    #[arg(long = "trust-key")]
+    trust_key: bool,
+
+    /// Detached signature envelope to verify
+    /// (`-` reads it from stdin)
+    #[arg(value_name = "SIGNATURE")]
+    signature: String,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        let registry_path = participants_file_path(self.registry)?;
+        let registry = load_registry(&registry_path).with_context(|| {
+            format!("Failed to load registry at {}", registry_path.display())
+        })?;
+
+        let envelope = parse_envelope_ur(&read_ur_input(&self.signature)?)
+            .context("Invalid detached signature envelope")?;
+        let detached = DetachedSignature::verify(&envelope)?;
+
+        let group_key = match registry
+            .group(&detached.group_id)
+            .and_then(|record| record.verifying_key())
+        {
+            Some(key) if *key == detached.verifying_key => "matches registry",
+            Some(_) => bail!(
+                "Detached signature group key does not match the registry"
+            ),
+            None if self.trust_key => "not in registry, trusted by --trust-key",
+            None => bail!(
+                "The registry has no group key for {}; pass --trust-key to \
+                 accept the key the signature carries",
+                detached.group_id.ur_string()
+            ),
+        };
+
+        if let Some(target) = &self.target {
+            let target =
+                parse_envelope_ur(target).context("Invalid target envelope")?;
+            if !detached.covers(&target)? {
+                bail!("Detached signature does not cover the target envelope");
+            }
+        }
+
+        let signers: Vec<String> = detached
+            .signers
+            .iter()
+            .map(|xid| match registry.owner() {
+                Some(owner) if owner.xid() == *xid => owner
+                    .pet_name()
+                    .map(str::to_owned)
+                    .unwrap_or_else(|| xid.ur_string()),
+                _ => registry
+                    .participant(xid)
+                    .and_then(|r| r.pet_name().map(str::to_owned))
+                    .unwrap_or_else(|| xid.ur_string()),
+            })
+            .collect();
+
+        println!("Signature: valid");
+        println!("Group: {}", detached.group_id.ur_string());
+        println!("Group key: {group_key}");
+        if self.target.is_some() {
+            println!("Target: matches");
+        }
+        println!("Signers: {}", signers.join(", "));
+        println!("Date: {}", detached.date);
+
+        Ok(())
+    }
+}
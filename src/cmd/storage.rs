@@ -1,13 +1,26 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    io,
+    sync::{Arc, LazyLock, Mutex, MutexGuard, OnceLock},
+    time::Duration,
+};
+
 use anyhow::{Result, anyhow, bail};
 use bc_components::ARID;
-use bc_envelope::Envelope;
+use bc_envelope::{
+    Envelope,
+    prelude::{CBOREncodable, UREncodable},
+};
 use clap::{Args, ValueEnum};
 use hubert::{
     KvStore, hybrid::HybridKv, ipfs::IpfsKv, mainline::MainlineDhtKv,
     server::ServerKvClient,
 };
+use tokio::{runtime::Runtime, time::Instant};
 
-use super::is_verbose;
+use super::{is_compress, is_verbose, max_payload_bytes, poll_interval};
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
 #[doc(hidden)]
@@ -39,7 +52,7 @@ pub struct StorageSelector {
     pub port: Option<u16>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum StorageSelection {
     Mainline,
     Ipfs { port: u16 },
@@ -76,11 +89,14 @@ impl StorageSelector {
 }
 
 /// Helper that opens the selected Hubert storage backend.
+///
+/// Backends reached over HTTP keep their `host:port` so connection failures
+/// can name it.
 pub enum StorageClient {
     Mainline(MainlineDhtKv),
-    Ipfs(IpfsKv),
-    Hybrid(HybridKv),
-    Server(ServerKvClient),
+    Ipfs(IpfsKv, String),
+    Hybrid(HybridKv, String),
+    Server(ServerKvClient, String),
 }
 
 impl StorageClient {
@@ -90,62 +106,458 @@ impl StorageClient {
                 Ok(Self::Mainline(MainlineDhtKv::new().await?))
             }
             StorageSelection::Ipfs { port } => {
-                let url = format!("http://127.0.0.1:{port}");
-                Ok(Self::Ipfs(IpfsKv::new(&url)))
+                let endpoint = format!("127.0.0.1:{port}");
+                let url = format!("http://{endpoint}");
+                Ok(Self::Ipfs(IpfsKv::new(&url), endpoint))
             }
             StorageSelection::Hybrid { port } => {
-                let url = format!("http://127.0.0.1:{port}");
-                Ok(Self::Hybrid(HybridKv::new(&url).await?))
+                let endpoint = format!("127.0.0.1:{port}");
+                let url = format!("http://{endpoint}");
+                Ok(Self::Hybrid(HybridKv::new(&url).await?, endpoint))
             }
             StorageSelection::Server { host, port } => {
-                let url = format!("http://{host}:{port}");
-                Ok(Self::Server(ServerKvClient::new(&url)))
+                let endpoint = format!("{host}:{port}");
+                let url = format!("http://{endpoint}");
+                Ok(Self::Server(ServerKvClient::new(&url), endpoint))
             }
         }
     }
 
+    /// Returns the client shared by every command in this process for
+    /// `selection`, opening it on first use.
+    ///
+    /// Clients hold connections and background tasks on the runtime that
+    /// opened them, so callers must drive them with [`shared_runtime`]. A
+    /// client that fails to reach its endpoint is dropped from the cache, and
+    /// the next call opens a fresh one.
+    pub async fn shared(selection: StorageSelection) -> Result<Arc<Self>> {
+        if let Some(client) = shared_clients().get(&selection) {
+            return Ok(Arc::clone(client));
+        }
+        let client = Arc::new(Self::from_selection(selection.clone()).await?);
+        // Another command may have opened the same backend meanwhile; keep
+        // whichever client was cached first
+        Ok(Arc::clone(
+            shared_clients().entry(selection).or_insert(client),
+        ))
+    }
+
     pub async fn put(
         &self,
         arid: &ARID,
         envelope: &Envelope,
     ) -> Result<String> {
+        check_payload_size(envelope, max_payload_bytes(), is_compress())?;
         match self {
             StorageClient::Mainline(store) => {
                 store.put(arid, envelope, None, is_verbose()).await
             }
-            StorageClient::Ipfs(store) => {
+            StorageClient::Ipfs(store, _) => {
                 store.put(arid, envelope, None, is_verbose()).await
             }
-            StorageClient::Hybrid(store) => {
+            StorageClient::Hybrid(store, _) => {
                 store.put(arid, envelope, None, is_verbose()).await
             }
-            StorageClient::Server(store) => {
+            StorageClient::Server(store, _) => {
                 store.put(arid, envelope, None, is_verbose()).await
             }
         }
-        .map_err(|err| anyhow!(err))
+        .map_err(|err| self.classify(anyhow!(err)).into_anyhow())
     }
 
+    /// Retrieves the envelope stored at `arid`.
+    ///
+    /// With a timeout, the backend is polled once every `--poll-interval-ms`
+    /// until the envelope appears or the timeout elapses. Without one, the
+    /// backend performs a single lookup with its own default wait.
+    ///
+    /// `Ok(None)` means the backend answered and nothing has been posted to
+    /// `arid` yet; a backend that could not be contacted is reported as
+    /// [`StorageError::Unreachable`].
     pub async fn get(
         &self,
         arid: &ARID,
         timeout_seconds: Option<u64>,
+    ) -> Result<Option<Envelope>, StorageError> {
+        let envelope = match timeout_seconds {
+            Some(seconds) => {
+                // A zero timeout makes each backend perform a single lookup
+                // without waiting, so the cadence is controlled here.
+                poll_until(
+                    Duration::from_secs(seconds),
+                    poll_interval(),
+                    || self.backend_get(arid, Some(0)),
+                )
+                .await
+            }
+            None => self.backend_get(arid, None).await,
+        }
+        .map_err(StorageError::from)?;
+        envelope
+            .map(decompress_envelope)
+            .transpose()
+            .map_err(StorageError::Other)
+    }
+
+    /// Removes the envelope stored at `arid`.
+    ///
+    /// Hubert entries are write-once: DHT and IPFS records lapse on their
+    /// own schedule and the server expires entries by TTL, so none of the
+    /// current backends can remove an entry early and all of them report
+    /// [`DeleteOutcome::Unsupported`].
+    pub async fn delete(&self, _arid: &ARID) -> Result<DeleteOutcome> {
+        match self {
+            StorageClient::Mainline(_)
+            | StorageClient::Ipfs(..)
+            | StorageClient::Hybrid(..)
+            | StorageClient::Server(..) => Ok(DeleteOutcome::Unsupported),
+        }
+    }
+
+    async fn backend_get(
+        &self,
+        arid: &ARID,
+        timeout_seconds: Option<u64>,
     ) -> Result<Option<Envelope>> {
         match self {
             StorageClient::Mainline(store) => {
                 store.get(arid, timeout_seconds, is_verbose()).await
             }
-            StorageClient::Ipfs(store) => {
+            StorageClient::Ipfs(store, _) => {
                 store.get(arid, timeout_seconds, is_verbose()).await
             }
-            StorageClient::Hybrid(store) => {
+            StorageClient::Hybrid(store, _) => {
                 store.get(arid, timeout_seconds, is_verbose()).await
             }
-            StorageClient::Server(store) => {
+            StorageClient::Server(store, _) => {
                 store.get(arid, timeout_seconds, is_verbose()).await
             }
         }
-        .map_err(|err| anyhow!(err))
+        .map_err(|err| self.classify(anyhow!(err)).into_anyhow())
+    }
+
+    /// Reports `err` as [`StorageError::Unreachable`] when it is a failure to
+    /// contact this client's endpoint.
+    fn classify(&self, err: anyhow::Error) -> StorageError {
+        let (service, endpoint) = match self {
+            StorageClient::Mainline(_) => return StorageError::Other(err),
+            StorageClient::Ipfs(_, endpoint)
+            | StorageClient::Hybrid(_, endpoint) => ("IPFS daemon", endpoint),
+            StorageClient::Server(_, endpoint) => ("Hubert server", endpoint),
+        };
+        match connection_failure(&err) {
+            Some(reason) => {
+                self.evict();
+                StorageError::Unreachable {
+                    service,
+                    endpoint: endpoint.clone(),
+                    reason,
+                }
+            }
+            None => StorageError::Other(err),
+        }
+    }
+
+    /// Removes this client from the shared cache, if it is cached.
+    fn evict(&self) {
+        shared_clients().retain(|_, client| !std::ptr::eq(&**client, self));
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Shared clients
+// -----------------------------------------------------------------------------
+
+static SHARED_RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// Clients opened by [`StorageClient::shared`], keyed by selection.
+type ClientCache = HashMap<StorageSelection, Arc<StorageClient>>;
+
+static SHARED_CLIENTS: LazyLock<Mutex<ClientCache>> =
+    LazyLock::new(Mutex::default);
+
+/// Returns the Tokio runtime shared by every command in this process.
+///
+/// One-shot invocations behave as if each command built its own runtime;
+/// a long-running process keeps the runtime, and with it the clients from
+/// [`StorageClient::shared`], alive between commands.
+pub fn shared_runtime() -> Result<&'static Runtime> {
+    if let Some(runtime) = SHARED_RUNTIME.get() {
+        return Ok(runtime);
+    }
+    let runtime = Runtime::new()?;
+    Ok(SHARED_RUNTIME.get_or_init(|| runtime))
+}
+
+fn shared_clients() -> MutexGuard<'static, ClientCache> {
+    // A panic while holding the lock cannot leave the map inconsistent
+    SHARED_CLIENTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// -----------------------------------------------------------------------------
+// Errors
+// -----------------------------------------------------------------------------
+
+/// Failure of a Hubert storage operation.
+///
+/// An empty slot is not an error: [`StorageClient::get`] returns `Ok(None)`
+/// for it, so callers can tell "nothing posted yet" from "could not ask".
+#[derive(Debug)]
+pub enum StorageError {
+    /// The backend endpoint could not be contacted
+    Unreachable {
+        service: &'static str,
+        endpoint: String,
+        reason: String,
+    },
+    /// Any other failure, such as a stored envelope that fails to decode
+    Other(anyhow::Error),
+}
+
+impl StorageError {
+    pub fn is_unreachable(&self) -> bool {
+        matches!(self, StorageError::Unreachable { .. })
+    }
+
+    /// Converts to `anyhow::Error`, unwrapping `Other` so its context chain
+    /// is preserved.
+    pub fn into_anyhow(self) -> anyhow::Error {
+        match self {
+            StorageError::Other(err) => err,
+            unreachable => anyhow::Error::new(unreachable),
+        }
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Unreachable { service, endpoint, reason } => {
+                write!(f, "{service} unreachable at {endpoint}: {reason}")
+            }
+            StorageError::Other(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+/// Recovers a `StorageError` that was carried through an `anyhow` chain.
+impl From<anyhow::Error> for StorageError {
+    fn from(err: anyhow::Error) -> Self {
+        err.downcast().unwrap_or_else(StorageError::Other)
+    }
+}
+
+/// I/O error kinds that mean the endpoint could not be reached at all.
+const UNREACHABLE_KINDS: &[io::ErrorKind] = &[
+    io::ErrorKind::ConnectionRefused,
+    io::ErrorKind::ConnectionReset,
+    io::ErrorKind::ConnectionAborted,
+    io::ErrorKind::NotConnected,
+    io::ErrorKind::AddrNotAvailable,
+    io::ErrorKind::HostUnreachable,
+    io::ErrorKind::NetworkUnreachable,
+];
+
+/// Messages the HTTP client uses for connection failures when no
+/// `io::Error` survives in the source chain.
+const UNREACHABLE_MESSAGES: &[&str] = &[
+    "connection refused",
+    "tcp connect error",
+    "error trying to connect",
+    "dns error",
+    "no route to host",
+    "network is unreachable",
+];
+
+/// Returns the most specific description of a connection failure in `err`'s
+/// source chain, or `None` if the endpoint was reached.
+pub fn connection_failure(err: &anyhow::Error) -> Option<String> {
+    let io_failure = err.chain().find_map(|cause| {
+        cause
+            .downcast_ref::<io::Error>()
+            .filter(|io| UNREACHABLE_KINDS.contains(&io.kind()))
+            .map(ToString::to_string)
+    });
+    io_failure.or_else(|| {
+        err.chain().map(ToString::to_string).find(|message| {
+            let message = message.to_lowercase();
+            UNREACHABLE_MESSAGES.iter().any(|m| message.contains(m))
+        })
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Cleanup
+// -----------------------------------------------------------------------------
+
+/// Result of asking a backend to delete an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteOutcome {
+    /// The entry was removed
+    Deleted,
+    /// The backend cannot remove entries before they expire
+    Unsupported,
+}
+
+/// Deletes response envelopes that have been fetched and persisted locally
+/// so they do not linger on shared storage. Failures are reported as
+/// warnings; a backend that cannot delete is reported once.
+pub async fn cleanup_collected(client: &StorageClient, arids: &[ARID]) {
+    delete_collected(
+        arids,
+        move |arid| async move { client.delete(&arid).await },
+    )
+    .await;
+}
+
+/// Calls `delete` for each of `arids`, as [`cleanup_collected`] does with a
+/// storage client, and returns how many entries were removed.
+///
+/// Stops at the first [`DeleteOutcome::Unsupported`], since the backend will
+/// not remove the rest either, and warns how many entries remain.
+pub async fn delete_collected<F, Fut>(arids: &[ARID], mut delete: F) -> usize
+where
+    F: FnMut(ARID) -> Fut,
+    Fut: Future<Output = Result<DeleteOutcome>>,
+{
+    let mut deleted = 0;
+    for arid in arids {
+        match delete(*arid).await {
+            Ok(DeleteOutcome::Deleted) => deleted += 1,
+            Ok(DeleteOutcome::Unsupported) => {
+                eprintln!(
+                    "Warning: storage backend does not support deletion; {} \
+                     collected responses remain until they expire",
+                    arids.len() - deleted
+                );
+                return deleted;
+            }
+            Err(err) => {
+                eprintln!(
+                    "Warning: failed to delete {}: {err:#}",
+                    arid.ur_string()
+                );
+            }
+        }
+    }
+    if is_verbose() {
+        eprintln!("Deleted {deleted} collected responses from storage");
+    }
+    deleted
+}
+
+// -----------------------------------------------------------------------------
+// Polling
+// -----------------------------------------------------------------------------
+
+/// Calls `attempt` every `interval` until it yields a value or `timeout` has
+/// elapsed.
+///
+/// Attempts are scheduled against a deadline fixed when polling starts, so
+/// time spent inside slow attempts counts against `timeout` instead of
+/// stretching the wait. An attempt that overruns its slot delays the next
+/// one by a full `interval` rather than triggering a burst of catch-up
+/// attempts.
+pub async fn poll_until<T, F, Fut>(
+    timeout: Duration,
+    interval: Duration,
+    mut attempt: F,
+) -> Result<Option<T>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<T>>>,
+{
+    let start = Instant::now();
+    let deadline = start + timeout;
+    let mut next = start;
+    loop {
+        if let Some(value) = attempt().await? {
+            return Ok(Some(value));
+        }
+        next += interval;
+        let now = Instant::now();
+        if next < now {
+            next = now + interval;
+        }
+        if next >= deadline {
+            return Ok(None);
+        }
+        tokio::time::sleep_until(next).await;
+    }
+}
+
+/// Checks the serialized size of an envelope about to be stored against
+/// `limit`, reporting the size when verbose.
+///
+/// `compressed` says whether the envelope's payloads were compressed before
+/// sealing, which decides what the error suggests.
+pub fn check_payload_size(
+    envelope: &Envelope,
+    limit: Option<u64>,
+    compressed: bool,
+) -> Result<()> {
+    let size = envelope.to_cbor_data().len() as u64;
+    if is_verbose() {
+        eprintln!("Payload size: {size} bytes");
+    }
+    if let Some(limit) = limit
+        && size > limit
+    {
+        let suggestion = if compressed {
+            "use a smaller group or a storage backend with a larger limit"
+        } else {
+            "retry with --compress, or use a smaller group"
+        };
+        bail!(
+            "Payload of {size} bytes exceeds --max-payload-bytes {limit}; \
+             {suggestion}"
+        );
+    }
+    Ok(())
+}
+
+/// Compresses a request parameter or response result before it is sealed
+/// when `--compress` is set; otherwise returns it unchanged.
+///
+/// A sealed message is ciphertext, which barely compresses, so the payloads
+/// are compressed while they are still plaintext. Compression keeps the
+/// digest, so the sender's signature covers the same content either way, and
+/// readers undo it with [`decompress_envelope`].
+pub fn compress_payload(envelope: Envelope) -> Result<Envelope> {
+    if is_compress() {
+        compress_envelope(&envelope)
+    } else {
+        Ok(envelope)
+    }
+}
+
+/// Compresses an envelope, reporting the size change when verbose.
+pub fn compress_envelope(envelope: &Envelope) -> Result<Envelope> {
+    let compressed = envelope.compress()?;
+    if is_verbose() {
+        eprintln!(
+            "Compressed envelope: {} -> {} bytes",
+            envelope.to_cbor_data().len(),
+            compressed.to_cbor_data().len()
+        );
+    }
+    Ok(compressed)
+}
+
+/// Decompresses a request parameter or response result if the sender
+/// compressed it; uncompressed envelopes are returned unchanged.
+///
+/// Envelopes retrieved from storage also pass through here, so messages
+/// posted whole-compressed by older releases still open.
+pub fn decompress_envelope(envelope: Envelope) -> Result<Envelope> {
+    if envelope.is_compressed() {
+        Ok(envelope.decompress()?)
+    } else {
+        Ok(envelope)
     }
 }
 
@@ -0,0 +1,191 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use bc_components::{ARID, PrivateKeyBase};
+use bc_envelope::prelude::*;
+use bc_rand::{RandomNumberGenerator, make_fake_random_number_generator};
+use bc_xid::{
+    XIDDocument, XIDGeneratorOptions, XIDGenesisMarkOptions,
+    XIDInceptionKeyOptions, XIDPrivateKeyOptions, XIDSigningOptions,
+};
+use clap::Parser;
+use provenance_mark::ProvenanceMarkResolution;
+
+use crate::{
+    DkgInvite,
+    cmd::common::write_atomically,
+    registry::{OwnerRecord, ParticipantRecord, Registry},
+};
+
+/// Names used for generated identities; the first is the registry owner.
+const NAMES: [&str; 8] = [
+    "alice", "bob", "carol", "dan", "eve", "frank", "grace", "heidi",
+];
+
+/// Generate test fixtures from a fixed seed.
+///
+/// Key material and XIDs are identical on every run. Signatures are not, since
+/// signing draws fresh randomness, so regenerated documents differ byte-wise
+/// while still describing the same identities.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Number of participant documents to generate besides the owner
+    #[arg(
+        long = "participants",
+        value_name = "N",
+        default_value_t = 3,
+        value_parser = clap::value_parser!(u16).range(2..)
+    )]
+    participants: u16,
+
+    /// Overwrite fixture files that already exist
+    #[arg(long = "force")]
+    force: bool,
+
+    /// Directory to write fixtures into (e.g. tests/fixtures)
+    #[arg(value_name = "DIR")]
+    dir: PathBuf,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        std::fs::create_dir_all(&self.dir).with_context(|| {
+            format!("Failed to create directory {}", self.dir.display())
+        })?;
+        let writer = FixtureWriter { dir: &self.dir, force: self.force };
+
+        let date = Date::from_ymd(2025, 1, 1);
+        let mut rng = make_fake_random_number_generator();
+        let identities: Vec<(String, XIDDocument)> = (0..=self.participants)
+            .map(|index| {
+                (
+                    fixture_name(usize::from(index)),
+                    make_document(&mut rng, date),
+                )
+            })
+            .collect();
+
+        let mut registry = Registry::default();
+        let mut participant_urs = Vec::new();
+        for (index, (name, document)) in identities.iter().enumerate() {
+            let signed = document_ur(document, XIDPrivateKeyOptions::Omit)?;
+            let private = document_ur(document, XIDPrivateKeyOptions::Include)?;
+            writer.write(&format!("{name}_signed_xid.txt"), &signed)?;
+            writer.write(&format!("{name}_private_xid.txt"), &private)?;
+
+            let pet_name = Some(capitalize(name));
+            if index == 0 {
+                registry.set_owner(OwnerRecord::from_signed_xid_ur(
+                    private, pet_name,
+                )?)?;
+            } else {
+                let record = ParticipantRecord::from_signed_xid_ur(
+                    signed.clone(),
+                    pet_name,
+                )?;
+                registry.add_participant(record.xid(), record)?;
+                participant_urs.push(signed);
+            }
+        }
+
+        let registry_json = serde_json::to_string_pretty(&registry)?;
+        writer.write("sample_registry.json", &registry_json)?;
+
+        let (_, owner) = &identities[0];
+        let response_arids = participant_urs
+            .iter()
+            .map(|_| make_arid(&mut rng))
+            .collect();
+        let invite = DkgInvite::new(
+            make_arid(&mut rng),
+            owner.clone(),
+            make_arid(&mut rng),
+            date,
+            Date::from_ymd(2099, 12, 31),
+            2,
+            "Fixture charter".to_string(),
+            participant_urs,
+            response_arids,
+        )?;
+        writer.write(
+            "sample_invite.txt",
+            &invite.to_unsealed_envelope()?.ur_string(),
+        )?;
+
+        println!(
+            "Wrote fixtures for {} identities to {}",
+            identities.len(),
+            self.dir.display()
+        );
+        Ok(())
+    }
+}
+
+struct FixtureWriter<'a> {
+    dir: &'a Path,
+    force: bool,
+}
+
+impl FixtureWriter<'_> {
+    /// Write a fixture without a trailing newline, matching the hand-made
+    /// fixtures.
+    fn write(&self, name: &str, contents: &str) -> Result<()> {
+        let path = self.dir.join(name);
+        if path.exists() && !self.force {
+            bail!(
+                "{} already exists; pass --force to regenerate it",
+                path.display()
+            );
+        }
+        write_atomically(&path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+fn fixture_name(index: usize) -> String {
+    NAMES
+        .get(index)
+        .map(|name| (*name).to_owned())
+        .unwrap_or_else(|| format!("participant{index}"))
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn make_document(
+    rng: &mut impl RandomNumberGenerator,
+    date: Date,
+) -> XIDDocument {
+    XIDDocument::new(
+        XIDInceptionKeyOptions::PrivateKeyBase(PrivateKeyBase::new_using(rng)),
+        XIDGenesisMarkOptions::Passphrase(
+            "fixture".to_string(),
+            Some(ProvenanceMarkResolution::Quartile),
+            Some(date),
+            None,
+        ),
+    )
+}
+
+fn make_arid(rng: &mut impl RandomNumberGenerator) -> ARID {
+    ARID::from_data_ref(rng.random_data(ARID::ARID_SIZE))
+        .expect("random data has ARID length")
+}
+
+fn document_ur(
+    document: &XIDDocument,
+    private_keys: XIDPrivateKeyOptions,
+) -> Result<String> {
+    let envelope = document.to_envelope(
+        private_keys,
+        XIDGeneratorOptions::default(),
+        XIDSigningOptions::Inception,
+    )?;
+    Ok(envelope.ur_string())
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+#[doc(hidden)]
+mod gen_fixtures;
+
+/// Maintenance commands for the integration test suite.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Debug, Subcommand)]
+#[doc(hidden)]
+enum Commands {
+    /// Generate XID document, registry, and invite fixtures
+    GenFixtures(gen_fixtures::CommandArgs),
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        match self.command {
+            Commands::GenFixtures(args) => args.exec(),
+        }
+    }
+}
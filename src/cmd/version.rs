@@ -0,0 +1,80 @@
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use frost_ed25519::{Ciphersuite, Ed25519Sha512};
+
+use crate::cmd::storage::StorageBackend;
+
+/// Show version information.
+#[derive(Debug, Parser)]
+#[doc(hidden)]
+pub struct CommandArgs {
+    /// Also report ciphersuites, storage backends, features, and protocol
+    /// library versions compiled into this binary
+    #[arg(long)]
+    full: bool,
+}
+
+impl CommandArgs {
+    pub fn exec(self) -> Result<()> {
+        println!("frost {}", env!("CARGO_PKG_VERSION"));
+        if self.full {
+            let capabilities = Capabilities::current();
+            println!("Ciphersuites: {}", capabilities.ciphersuites.join(", "));
+            println!(
+                "Storage backends: {}",
+                capabilities.storage_backends.join(", ")
+            );
+            let features: Vec<String> = capabilities
+                .features
+                .iter()
+                .map(|(name, enabled)| {
+                    let state = if *enabled { "enabled" } else { "disabled" };
+                    format!("{name} ({state})")
+                })
+                .collect();
+            println!("Features: {}", features.join(", "));
+            for (name, requirement) in &capabilities.libraries {
+                println!("{name}: {requirement}");
+            }
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Capabilities
+// -----------------------------------------------------------------------------
+
+/// Capabilities compiled into this binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// FROST ciphersuite identifiers
+    pub ciphersuites: Vec<&'static str>,
+    /// Names accepted by `--storage`
+    pub storage_backends: Vec<String>,
+    /// Protocol libraries and their version requirements from Cargo.toml
+    pub libraries: Vec<(&'static str, &'static str)>,
+    /// Every feature declared in Cargo.toml and whether it is enabled
+    pub features: Vec<(&'static str, bool)>,
+}
+
+/// Library versions and features recorded by the build script.
+mod build_info {
+    include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
+}
+
+impl Capabilities {
+    pub fn current() -> Self {
+        let storage_backends = StorageBackend::value_variants()
+            .iter()
+            .filter_map(|backend| backend.to_possible_value())
+            .map(|value| value.get_name().to_owned())
+            .collect();
+        Self {
+            ciphersuites: vec![Ed25519Sha512::ID],
+            storage_backends,
+            libraries: build_info::LIBRARIES.to_vec(),
+            features: build_info::FEATURES.to_vec(),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use anyhow::{Context, Result, bail};
+use bc_components::{ARID, PrivateKeys, XID, XIDProvider};
+use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
+
+/// The committee a coordinator convened for a DKG group.
+///
+/// Signed by the coordinator's inception key and carried in the invite as a
+/// `charterAttestation`, so anyone holding the coordinator's public XID
+/// document can later confirm who was invited under which charter without
+/// relying on the Hubert messages that delivered it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharterStatement {
+    pub group_id: ARID,
+    pub charter: String,
+    pub min_signers: usize,
+    pub coordinator: XID,
+    /// Invited participants, sorted by XID
+    pub participants: Vec<XID>,
+}
+
+impl CharterStatement {
+    pub fn new(
+        group_id: ARID,
+        charter: impl Into<String>,
+        min_signers: usize,
+        coordinator: XID,
+        mut participants: Vec<XID>,
+    ) -> Self {
+        participants.sort();
+        participants.dedup();
+        Self {
+            group_id,
+            charter: charter.into(),
+            min_signers,
+            coordinator,
+            participants,
+        }
+    }
+
+    pub fn to_envelope(&self) -> Envelope {
+        let mut envelope = Envelope::new(self.charter.clone())
+            .add_type("groupCharter")
+            .add_assertion("group", self.group_id)
+            .add_assertion("minSigners", self.min_signers as u64)
+            .add_assertion("coordinator", self.coordinator);
+        for participant in &self.participants {
+            envelope = envelope.add_assertion("participant", *participant);
+        }
+        envelope
+    }
+
+    /// Wrap and sign the statement with the coordinator's private keys.
+    pub fn sign(&self, signer: &PrivateKeys) -> Envelope {
+        self.to_envelope().sign(signer)
+    }
+
+    /// Verify `attestation` was signed by `coordinator`'s inception key and
+    /// decode the statement it carries.
+    pub fn verify(
+        attestation: &Envelope,
+        coordinator: &XIDDocument,
+    ) -> Result<Self> {
+        let signing_key = coordinator
+            .inception_key()
+            .context("Coordinator XID document missing inception key")?
+            .public_keys()
+            .signing_public_key();
+        let envelope = attestation
+            .verify_signature_from(signing_key)
+            .context("Charter attestation is not signed by the coordinator")?
+            .try_unwrap()?;
+        envelope.check_type("groupCharter")?;
+
+        let statement = Self::new(
+            envelope.extract_object_for_predicate("group")?,
+            envelope.extract_subject::<String>()?,
+            envelope.extract_object_for_predicate("minSigners")?,
+            envelope.extract_object_for_predicate("coordinator")?,
+            envelope
+                .objects_for_predicate("participant")
+                .iter()
+                .map(|object| object.extract_subject::<XID>())
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+        if statement.coordinator != coordinator.xid() {
+            bail!("Charter attestation names a different coordinator");
+        }
+        Ok(statement)
+    }
+
+    /// Fail with the first field where `self` differs from `expected`.
+    pub fn check_matches(&self, expected: &CharterStatement) -> Result<()> {
+        let mismatch = if self.group_id != expected.group_id {
+            "group ID"
+        } else if self.charter != expected.charter {
+            "charter"
+        } else if self.min_signers != expected.min_signers {
+            "min signers"
+        } else if self.coordinator != expected.coordinator {
+            "coordinator"
+        } else if self.participants != expected.participants {
+            "participants"
+        } else {
+            return Ok(());
+        };
+        bail!("Charter attestation does not match the {mismatch}");
+    }
+}
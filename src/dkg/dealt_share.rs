@@ -0,0 +1,151 @@
+use anyhow::{Context, Result, bail};
+use bc_components::{ARID, JSON, PrivateKeys, XID, XIDProvider};
+use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
+use frost_ed25519 as frost;
+
+use super::IdentifierScheme;
+
+/// One participant's key share from a trusted-dealer key generation
+/// (`frost keygen`).
+///
+/// The dealer signs the share with its inception key and encrypts it to the
+/// participant, who installs it with `frost registry group import-share`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DealtShare {
+    pub group_id: ARID,
+    pub charter: String,
+    pub min_signers: usize,
+    pub coordinator: XID,
+    /// All participants, sorted by XID
+    pub participants: Vec<XID>,
+    pub identifier_scheme: IdentifierScheme,
+    pub key_package: frost::keys::KeyPackage,
+    pub public_key_package: frost::keys::PublicKeyPackage,
+}
+
+impl DealtShare {
+    pub fn to_envelope(&self) -> Result<Envelope> {
+        let key_json = JSON::from_data(serde_json::to_vec(&self.key_package)?);
+        let pub_json =
+            JSON::from_data(serde_json::to_vec(&self.public_key_package)?);
+        let mut envelope = Envelope::new(self.group_id)
+            .add_type("dealtKeyShare")
+            .add_assertion("charter", self.charter.clone())
+            .add_assertion("minSigners", self.min_signers as u64)
+            .add_assertion("coordinator", self.coordinator)
+            .add_assertion("key_package", CBOR::from(key_json))
+            .add_assertion("public_key_package", CBOR::from(pub_json));
+        if !self.identifier_scheme.is_index() {
+            envelope = envelope.add_assertion(
+                "identifierScheme",
+                self.identifier_scheme.as_str(),
+            );
+        }
+        for participant in &self.participants {
+            envelope = envelope.add_assertion("participant", *participant);
+        }
+        Ok(envelope)
+    }
+
+    /// Sign the share with the dealer's private keys and encrypt it to
+    /// `recipient`.
+    pub fn seal(
+        &self,
+        dealer: &PrivateKeys,
+        recipient: &XIDDocument,
+    ) -> Result<Envelope> {
+        let encryption_key = recipient
+            .encryption_key()
+            .context("Participant XID document has no encryption key")?;
+        Ok(self
+            .to_envelope()?
+            .sign(dealer)
+            .encrypt_to_recipient(encryption_key))
+    }
+
+    /// Check that the share is `participant`'s and agrees with the group's
+    /// public key package.
+    pub fn check_for(&self, participant: &XID) -> Result<()> {
+        let identifiers =
+            self.identifier_scheme.identifiers(&self.participants)?;
+        let identifier = identifiers
+            .get(participant)
+            .context("Key share does not include this participant")?;
+        if self.key_package.identifier() != identifier {
+            bail!("Key share identifier does not match this participant");
+        }
+        if self.public_key_package.verifying_shares().get(identifier)
+            != Some(self.key_package.verifying_share())
+            || self.public_key_package.verifying_key()
+                != self.key_package.verifying_key()
+        {
+            bail!("Key share does not match the group's public key package");
+        }
+        if usize::from(*self.key_package.min_signers()) != self.min_signers {
+            bail!("Key share threshold does not match the group's min signers");
+        }
+        Ok(())
+    }
+
+    /// Decrypt a sealed share and verify it was signed by the dealer it
+    /// names.
+    ///
+    /// `resolve_dealer` maps the coordinator XID in the share to the XID
+    /// document its signature is checked against.
+    pub fn open(
+        sealed: &Envelope,
+        recipient: &PrivateKeys,
+        resolve_dealer: impl FnOnce(&XID) -> Result<XIDDocument>,
+    ) -> Result<Self> {
+        let signed = sealed
+            .decrypt_to_recipient(recipient)
+            .context("Key share is not encrypted to this registry's owner")?;
+        let coordinator: XID = signed
+            .try_unwrap()?
+            .extract_object_for_predicate("coordinator")?;
+        let dealer = resolve_dealer(&coordinator)?;
+        if dealer.xid() != coordinator {
+            bail!("Resolved dealer does not match the share's coordinator");
+        }
+        let signing_key = dealer
+            .inception_key()
+            .context("Dealer XID document missing inception key")?
+            .public_keys()
+            .signing_public_key();
+        let envelope = signed
+            .verify_signature_from(signing_key)
+            .context("Key share is not signed by its dealer")?
+            .try_unwrap()?;
+        envelope.check_type("dealtKeyShare")?;
+
+        let key_json: JSON =
+            envelope.extract_object_for_predicate("key_package")?;
+        let pub_json: JSON =
+            envelope.extract_object_for_predicate("public_key_package")?;
+        let mut participants = envelope
+            .objects_for_predicate("participant")
+            .iter()
+            .map(|object| object.extract_subject::<XID>())
+            .collect::<Result<Vec<_>, _>>()?;
+        participants.sort();
+        let identifier_scheme =
+            match envelope.objects_for_predicate("identifierScheme").first() {
+                Some(object) => object.extract_subject::<String>()?.parse()?,
+                None => IdentifierScheme::Index,
+            };
+
+        Ok(Self {
+            group_id: envelope.extract_subject()?,
+            charter: envelope.extract_object_for_predicate("charter")?,
+            min_signers: envelope.extract_object_for_predicate("minSigners")?,
+            coordinator,
+            participants,
+            identifier_scheme,
+            key_package: serde_json::from_slice(key_json.as_bytes())
+                .context("Failed to deserialize key package")?,
+            public_key_package: serde_json::from_slice(pub_json.as_bytes())
+                .context("Failed to deserialize public key package")?,
+        })
+    }
+}
@@ -1,4 +1,6 @@
 #![allow(dead_code)]
+use std::collections::HashSet;
+
 use anyhow::Result;
 use bc_components::{ARID, XIDProvider};
 use bc_envelope::prelude::*;
@@ -8,7 +10,10 @@ use gstp::{
     SealedResponseBehavior,
 };
 
-use super::DkgProposedParticipant;
+use super::{
+    CharterStatement, DkgProposedParticipant, IdentifierScheme, ShareRepair,
+    extract_identifier_scheme,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct DkgInvite {
@@ -22,12 +27,22 @@ pub struct DkgInvite {
     date: Date,
     // Expiration date of the invite
     valid_until: Date,
+    // Earliest date participants may respond (None = immediately)
+    valid_from: Option<Date>,
     // Minimum signers required for the DKG group
     min_signers: usize,
     // Charter statement for the DKG group (may be empty)
     charter: String,
     // Identifies participants and their indexes
     ordered_participants: Vec<DkgProposedParticipant>,
+    // Coordinator-signed charter statement (None = not attested)
+    charter_attestation: Option<Envelope>,
+    // How participant XIDs map to FROST identifiers
+    identifier_scheme: IdentifierScheme,
+    // Group whose key shares this DKG refreshes (None = new group)
+    refresh_of: Option<ARID>,
+    // Lost share the refresh rebuilds for a replacement (None = no repair)
+    share_repair: Option<ShareRepair>,
 }
 
 impl DkgInvite {
@@ -53,6 +68,11 @@ impl DkgInvite {
         if min_signers < 2 {
             anyhow::bail!("min_signers must be at least 2");
         }
+        // Responses posted to a shared ARID would overwrite each other
+        let mut seen_arids = HashSet::new();
+        if !response_arids.iter().all(|arid| seen_arids.insert(*arid)) {
+            anyhow::bail!("Response ARIDs must be unique across participants");
+        }
         let mut ordered_participants = participants
             .into_iter()
             .zip(response_arids.into_iter())
@@ -70,12 +90,87 @@ impl DkgInvite {
             group_id,
             date,
             valid_until,
+            valid_from: None,
             min_signers,
             charter,
             ordered_participants,
+            charter_attestation: None,
+            identifier_scheme: IdentifierScheme::default(),
+            refresh_of: None,
+            share_repair: None,
         })
     }
 
+    /// Choose how participants derive their FROST identifiers.
+    ///
+    /// Fails if `Hashed` identifiers of two participants collide.
+    pub fn with_identifier_scheme(
+        mut self,
+        identifier_scheme: IdentifierScheme,
+    ) -> Result<Self> {
+        let xids: Vec<_> =
+            self.ordered_participants.iter().map(|p| p.xid()).collect();
+        identifier_scheme.identifiers(&xids)?;
+        self.identifier_scheme = identifier_scheme;
+        Ok(self)
+    }
+
+    /// Defer the invite so participants cannot respond before `valid_from`.
+    pub fn with_valid_from(mut self, valid_from: Date) -> Result<Self> {
+        if valid_from >= self.valid_until {
+            anyhow::bail!("validFrom must be earlier than validUntil");
+        }
+        self.valid_from = Some(valid_from);
+        Ok(self)
+    }
+
+    /// Mark the invite as a refresh of the existing group `group_id`:
+    /// participants rotate their shares of that group's key instead of
+    /// generating a new one.
+    pub fn with_refresh_of(mut self, group_id: ARID) -> Self {
+        self.refresh_of = Some(group_id);
+        self
+    }
+
+    /// Have a refresh rebuild a lost share for the replacement `repair`
+    /// names. Fails unless the replacement and every helper is one of the
+    /// invite's participants.
+    pub fn with_share_repair(mut self, repair: ShareRepair) -> Result<Self> {
+        let mut xids: Vec<_> =
+            self.ordered_participants.iter().map(|p| p.xid()).collect();
+        xids.sort();
+        if !repair.identifiers.keys().copied().eq(xids) {
+            anyhow::bail!(
+                "Share repair identifiers do not cover the invite's \
+                 participants"
+            );
+        }
+        self.share_repair = Some(repair);
+        Ok(self)
+    }
+
+    /// Attach a charter attestation signed with the sender's inception keys,
+    /// binding the charter, threshold, and participant set to the coordinator.
+    pub fn with_signed_charter(mut self) -> Result<Self> {
+        let signer = self.sender.inception_private_keys().ok_or_else(|| {
+            anyhow::anyhow!("Sender XID document has no inception signing key")
+        })?;
+        let attestation = self.charter_statement().sign(signer);
+        self.charter_attestation = Some(attestation);
+        Ok(self)
+    }
+
+    /// The statement a charter attestation for this invite covers.
+    pub fn charter_statement(&self) -> CharterStatement {
+        CharterStatement::new(
+            self.group_id,
+            self.charter.clone(),
+            self.min_signers,
+            self.sender.xid(),
+            self.ordered_participants.iter().map(|p| p.xid()).collect(),
+        )
+    }
+
     pub fn request_id(&self) -> ARID { self.request_id }
 
     pub fn sender(&self) -> XIDDocument { self.sender.clone() }
@@ -86,6 +181,8 @@ impl DkgInvite {
 
     pub fn valid_until(&self) -> Date { self.valid_until }
 
+    pub fn valid_from(&self) -> Option<Date> { self.valid_from }
+
     pub fn min_signers(&self) -> usize { self.min_signers }
 
     pub fn charter(&self) -> &str { &self.charter }
@@ -94,6 +191,20 @@ impl DkgInvite {
         &self.ordered_participants
     }
 
+    pub fn charter_attestation(&self) -> Option<&Envelope> {
+        self.charter_attestation.as_ref()
+    }
+
+    pub fn identifier_scheme(&self) -> IdentifierScheme {
+        self.identifier_scheme
+    }
+
+    pub fn refresh_of(&self) -> Option<ARID> { self.refresh_of }
+
+    pub fn share_repair(&self) -> Option<&ShareRepair> {
+        self.share_repair.as_ref()
+    }
+
     pub fn to_request(&self) -> Result<SealedRequest> {
         let mut request =
             SealedRequest::new("dkgInvite", self.request_id(), self.sender())
@@ -102,6 +213,26 @@ impl DkgInvite {
                 .with_parameter("charter", self.charter.clone())
                 .with_date(self.date())
                 .with_parameter("validUntil", self.valid_until());
+        if let Some(valid_from) = self.valid_from() {
+            request = request.with_parameter("validFrom", valid_from);
+        }
+        if !self.identifier_scheme.is_index() {
+            request = request.with_parameter(
+                "identifierScheme",
+                self.identifier_scheme.as_str(),
+            );
+        }
+        if let Some(attestation) = self.charter_attestation() {
+            request = request
+                .with_parameter("charterAttestation", attestation.clone());
+        }
+        if let Some(refresh_of) = self.refresh_of() {
+            request = request.with_parameter("refreshOf", refresh_of);
+        }
+        if let Some(repair) = self.share_repair() {
+            request =
+                request.with_parameter("shareRepair", repair.to_envelope());
+        }
         for participant in self.participants() {
             let xid_document_envelope = participant.xid_document_envelope();
             let response_arid = participant.response_arid();
@@ -176,6 +307,7 @@ pub enum DkgInvitationResult {
 pub struct DkgInvitation {
     response_arid: ARID, // Hubert ARID at which to post the response
     valid_until: Date,   // Expiration date of the invite
+    valid_from: Option<Date>, // Earliest date to respond (None = immediately)
     sender: XIDDocument, // Coordinator who sent the invite
     request_id: ARID,    // The GSTP request ID for correlated responses
     peer_continuation: Option<Envelope>, /* Continuation (if any) to return
@@ -183,6 +315,10 @@ pub struct DkgInvitation {
     min_signers: usize, // Minimum signers required
     charter: String,    // Charter text (may be empty)
     group_id: ARID,     // Identifier for the DKG group
+    charter_attestation: Option<Envelope>, // Verified coordinator signature
+    identifier_scheme: IdentifierScheme, // XID to FROST identifier mapping
+    refresh_of: Option<ARID>, // Group being refreshed, if any
+    share_repair: Option<ShareRepair>, // Lost share being rebuilt, if any
 }
 
 impl DkgInvitation {
@@ -190,6 +326,18 @@ impl DkgInvitation {
 
     pub fn valid_until(&self) -> Date { self.valid_until }
 
+    pub fn valid_from(&self) -> Option<Date> { self.valid_from }
+
+    /// Fail if the invite's `validFrom` date has not yet been reached.
+    pub fn ensure_open(&self, now: Date) -> Result<()> {
+        if let Some(valid_from) = self.valid_from
+            && now < valid_from
+        {
+            anyhow::bail!("Invitation is not open until {valid_from}");
+        }
+        Ok(())
+    }
+
     pub fn sender(&self) -> XIDDocument { self.sender.clone() }
 
     pub fn request_id(&self) -> ARID { self.request_id }
@@ -204,6 +352,20 @@ impl DkgInvitation {
 
     pub fn group_id(&self) -> ARID { self.group_id }
 
+    pub fn charter_attestation(&self) -> Option<&Envelope> {
+        self.charter_attestation.as_ref()
+    }
+
+    pub fn identifier_scheme(&self) -> IdentifierScheme {
+        self.identifier_scheme
+    }
+
+    pub fn refresh_of(&self) -> Option<ARID> { self.refresh_of }
+
+    pub fn share_repair(&self) -> Option<&ShareRepair> {
+        self.share_repair.as_ref()
+    }
+
     /// Build a GSTP response for this invitation result.
     pub fn to_response(
         &self,
@@ -252,6 +414,12 @@ impl DkgInvitation {
     /// - Decrypts the participant's response ARID.
     /// - Extracts the `valid_until` date and ensures that it has not expired (>
     ///   now).
+    /// - Extracts the optional `valid_from` date without enforcing it, so a
+    ///   deferred invite can still be inspected; see [`Self::ensure_open`].
+    /// - Verifies the optional charter attestation against the sender and the
+    ///   invite's charter, threshold, and participants.
+    /// - Extracts the optional `refreshOf` group ID and the `shareRepair` a
+    ///   refresh may carry, whose identifiers must cover the participants.
     pub fn from_invite(
         invite: Envelope,
         now: Date,
@@ -288,6 +456,10 @@ impl DkgInvitation {
         if valid_until <= now {
             anyhow::bail!("Invitation expired");
         }
+        let valid_from = extract_valid_from(sealed_request.request())?;
+        if valid_from.is_some_and(|valid_from| valid_from >= valid_until) {
+            anyhow::bail!("Invitation validFrom is not before validUntil");
+        }
 
         let recipient_xid = recipient.xid();
         let min_signers: usize = sealed_request
@@ -309,6 +481,8 @@ impl DkgInvitation {
             anyhow::bail!("min_signers exceeds participant count");
         }
 
+        let mut participant_xids = Vec::new();
+        let mut response_arid = None;
         for participant in participant_objects {
             let xid_document_envelope = participant.try_unwrap()?;
             let xid_document = XIDDocument::from_envelope(
@@ -316,6 +490,7 @@ impl DkgInvitation {
                 None,
                 XIDVerifySignature::Inception,
             )?;
+            participant_xids.push(xid_document.xid());
 
             if xid_document.xid() != recipient_xid {
                 continue;
@@ -325,21 +500,112 @@ impl DkgInvitation {
                 participant.object_for_predicate("response_arid")?;
             let response_arid_envelope = encrypted_response_arid
                 .decrypt_to_recipient(recipient_private_keys)?;
-            let response_arid =
-                response_arid_envelope.extract_subject::<ARID>()?;
-
-            return Ok(Self {
-                response_arid,
-                valid_until,
-                sender: sealed_request.sender().clone(),
-                request_id: sealed_request.request().id(),
-                peer_continuation: sealed_request.peer_continuation().cloned(),
-                min_signers,
-                charter,
+            response_arid =
+                Some(response_arid_envelope.extract_subject::<ARID>()?);
+        }
+        let Some(response_arid) = response_arid else {
+            anyhow::bail!("Recipient not found in invite");
+        };
+        let identifier_scheme =
+            extract_identifier_scheme(sealed_request.request())?;
+        identifier_scheme.identifiers(&participant_xids)?;
+
+        let refresh_of = extract_refresh_of(sealed_request.request())?;
+        let share_repair = extract_share_repair(sealed_request.request())?;
+        if let Some(repair) = &share_repair {
+            if refresh_of.is_none() {
+                anyhow::bail!(
+                    "Share repair in an invite that is not a refresh"
+                );
+            }
+            let mut xids = participant_xids.clone();
+            xids.sort();
+            if !repair.identifiers.keys().copied().eq(xids) {
+                anyhow::bail!(
+                    "Share repair identifiers do not cover the invite's \
+                     participants"
+                );
+            }
+        }
+
+        let sender = sealed_request.sender().clone();
+        let charter_attestation =
+            extract_charter_attestation(sealed_request.request())?;
+        if let Some(attestation) = &charter_attestation {
+            let expected = CharterStatement::new(
                 group_id,
-            });
+                charter.clone(),
+                min_signers,
+                sender.xid(),
+                participant_xids,
+            );
+            CharterStatement::verify(attestation, &sender)?
+                .check_matches(&expected)?;
         }
 
-        anyhow::bail!("Recipient not found in invite");
+        Ok(Self {
+            response_arid,
+            valid_until,
+            valid_from,
+            sender,
+            request_id: sealed_request.request().id(),
+            peer_continuation: sealed_request.peer_continuation().cloned(),
+            min_signers,
+            charter,
+            group_id,
+            charter_attestation,
+            identifier_scheme,
+            refresh_of,
+            share_repair,
+        })
     }
 }
+
+/// Extract the optional `validFrom` parameter from a DKG invite request.
+///
+/// Invites created before deferred publication carry no `validFrom` and are
+/// valid immediately.
+pub fn extract_valid_from(request: &Request) -> Result<Option<Date>> {
+    request
+        .objects_for_parameter("validFrom")
+        .first()
+        .map(|envelope| envelope.extract_subject::<Date>())
+        .transpose()
+        .map_err(Into::into)
+}
+
+/// Extract the optional `charterAttestation` parameter from a DKG invite
+/// request.
+///
+/// Invites created before charter signing carry no attestation.
+pub fn extract_charter_attestation(
+    request: &Request,
+) -> Result<Option<Envelope>> {
+    Ok(request
+        .objects_for_parameter("charterAttestation")
+        .first()
+        .cloned())
+}
+
+/// Extract the optional `refreshOf` parameter from a DKG invite request.
+///
+/// Only invites sent by `dkg coordinator refresh` carry it.
+pub fn extract_refresh_of(request: &Request) -> Result<Option<ARID>> {
+    request
+        .objects_for_parameter("refreshOf")
+        .first()
+        .map(|envelope| envelope.extract_subject::<ARID>())
+        .transpose()
+        .map_err(Into::into)
+}
+
+/// Extract the optional `shareRepair` parameter from a DKG invite request.
+///
+/// Only refreshes that replace a lost participant carry it.
+pub fn extract_share_repair(request: &Request) -> Result<Option<ShareRepair>> {
+    request
+        .objects_for_parameter("shareRepair")
+        .first()
+        .map(ShareRepair::from_envelope)
+        .transpose()
+}
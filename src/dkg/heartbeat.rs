@@ -0,0 +1,129 @@
+use anyhow::{Context, Result, bail};
+use bc_components::{
+    ARID, Decrypter, Digest, EncapsulationPrivateKey, EncapsulationPublicKey,
+    Encrypter, PrivateKeys, SymmetricKey, XID, XIDProvider,
+};
+use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
+
+/// Length of one heartbeat slot. Hubert entries are write-once, so each
+/// slot has its own status ARID and a participant posts at most one
+/// heartbeat per slot.
+pub const HEARTBEAT_SLOT_SECS: u64 = 60;
+
+/// A participant's signed statement that it was online for a group at
+/// `date`.
+///
+/// Heartbeats are a liveness signal only and take no part in the DKG or
+/// signing rounds. Each is posted to [`Heartbeat::status_arid`] for the slot
+/// containing its date. That ARID is keyed by [`Heartbeat::shared_secret`],
+/// so only the participant and the coordinator can tell where it goes, and
+/// nobody else can fill a future slot first to make the participant look
+/// offline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heartbeat {
+    pub group_id: ARID,
+    pub participant: XID,
+    pub date: Date,
+}
+
+impl Heartbeat {
+    pub fn new(group_id: ARID, participant: XID, date: Date) -> Self {
+        Self { group_id, participant, date }
+    }
+
+    /// The slot containing `date`: whole [`HEARTBEAT_SLOT_SECS`] periods
+    /// since the Unix epoch.
+    pub fn slot_of(date: &Date) -> u64 {
+        (date.timestamp().max(0.0) as u64) / HEARTBEAT_SLOT_SECS
+    }
+
+    /// The X25519 agreement secret between the holder of `own_keys` and
+    /// `peer`, which the participant and the coordinator each compute from
+    /// their own inception private keys and the other's registered XID
+    /// document.
+    pub fn shared_secret(
+        own_keys: &PrivateKeys,
+        peer: &XIDDocument,
+    ) -> Result<SymmetricKey> {
+        let peer_key = peer
+            .inception_key()
+            .context("Peer XID document missing inception key")?
+            .public_keys()
+            .encapsulation_public_key();
+        match (own_keys.encapsulation_private_key(), peer_key) {
+            (
+                EncapsulationPrivateKey::X25519(private_key),
+                EncapsulationPublicKey::X25519(public_key),
+            ) => Ok(private_key.shared_key_with(&public_key)),
+            _ => bail!("Heartbeats need X25519 inception agreement keys"),
+        }
+    }
+
+    /// The ARID `participant` posts its heartbeat for `slot` to, keyed by
+    /// the `secret` it shares with the coordinator.
+    pub fn status_arid(
+        secret: &SymmetricKey,
+        group_id: &ARID,
+        participant: &XID,
+        slot: u64,
+    ) -> ARID {
+        let mut image = b"frost-heartbeat".to_vec();
+        image.extend_from_slice(secret.data());
+        image.extend_from_slice(group_id.data());
+        image.extend_from_slice(participant.data());
+        image.extend_from_slice(&slot.to_be_bytes());
+        ARID::from_data(*Digest::from_image(image).data())
+    }
+
+    /// The ARID this heartbeat is posted to, keyed by the `secret` the
+    /// participant shares with the coordinator.
+    pub fn arid(&self, secret: &SymmetricKey) -> ARID {
+        Self::status_arid(
+            secret,
+            &self.group_id,
+            &self.participant,
+            Self::slot_of(&self.date),
+        )
+    }
+
+    pub fn to_envelope(&self) -> Envelope {
+        Envelope::new(self.participant)
+            .add_type("heartbeat")
+            .add_assertion("group", self.group_id)
+            .add_assertion("date", self.date)
+    }
+
+    /// Wrap and sign the heartbeat with the participant's private keys.
+    pub fn sign(&self, signer: &PrivateKeys) -> Envelope {
+        self.to_envelope().sign(signer)
+    }
+
+    /// Verify `signed` was signed by `participant`'s inception key and
+    /// decode the heartbeat it carries.
+    pub fn verify(
+        signed: &Envelope,
+        participant: &XIDDocument,
+    ) -> Result<Self> {
+        let signing_key = participant
+            .inception_key()
+            .context("Participant XID document missing inception key")?
+            .public_keys()
+            .signing_public_key();
+        let envelope = signed
+            .verify_signature_from(signing_key)
+            .context("Heartbeat is not signed by the participant")?
+            .try_unwrap()?;
+        envelope.check_type("heartbeat")?;
+
+        let heartbeat = Self::new(
+            envelope.extract_object_for_predicate("group")?,
+            envelope.extract_subject()?,
+            envelope.extract_object_for_predicate("date")?,
+        );
+        if heartbeat.participant != participant.xid() {
+            bail!("Heartbeat names a different participant");
+        }
+        Ok(heartbeat)
+    }
+}
@@ -0,0 +1,118 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use anyhow::{Context, Result, bail};
+use bc_components::XID;
+use bc_envelope::prelude::*;
+use frost_ed25519::Identifier;
+use serde::{Deserialize, Serialize};
+
+/// Domain separator hashed ahead of the XID for `hashed` identifiers.
+const HASHED_IDENTIFIER_DOMAIN: &[u8] = b"frost-hubert/identifier/v1";
+
+/// How a group's participant XIDs map to FROST identifiers.
+///
+/// `Index` assigns identifiers 1..=n in XID order, so adding or removing a
+/// participant shifts everyone after them. `Hashed` derives each identifier
+/// from the participant's own XID, so it does not depend on the rest of the
+/// roster.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum IdentifierScheme {
+    #[default]
+    Index,
+    Hashed,
+}
+
+impl IdentifierScheme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdentifierScheme::Index => "index",
+            IdentifierScheme::Hashed => "hashed",
+        }
+    }
+
+    pub fn is_index(&self) -> bool { *self == IdentifierScheme::Index }
+
+    /// Map each of `participants` to its FROST identifier.
+    ///
+    /// `participants` must be the group's full identifier order (see
+    /// `GroupRecord::identifier_order`), since `Index` identifiers depend on
+    /// each XID's position in it.
+    pub fn identifiers(
+        &self,
+        participants: &[XID],
+    ) -> Result<HashMap<XID, Identifier>> {
+        let mut map = HashMap::new();
+        let mut owners: HashMap<Identifier, XID> = HashMap::new();
+        for (i, xid) in participants.iter().enumerate() {
+            let identifier = match self {
+                IdentifierScheme::Index => {
+                    let index = u16::try_from(i + 1).context(
+                        "Too many participants for FROST identifiers",
+                    )?;
+                    Identifier::try_from(index).context(
+                        "Failed to derive Identifier from participant index",
+                    )?
+                }
+                IdentifierScheme::Hashed => hashed_identifier(xid)?,
+            };
+            if let Some(other) = owners.insert(identifier, *xid)
+                && other != *xid
+            {
+                bail!(
+                    "FROST identifier collision between {} and {}; use --identifier-scheme index",
+                    other.ur_string(),
+                    xid.ur_string()
+                );
+            }
+            map.insert(*xid, identifier);
+        }
+        Ok(map)
+    }
+}
+
+impl fmt::Display for IdentifierScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IdentifierScheme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "index" => Ok(IdentifierScheme::Index),
+            "hashed" => Ok(IdentifierScheme::Hashed),
+            other => bail!(
+                "Unknown identifier scheme '{other}' (expected index or hashed)"
+            ),
+        }
+    }
+}
+
+fn hashed_identifier(xid: &XID) -> Result<Identifier> {
+    let mut input = HASHED_IDENTIFIER_DOMAIN.to_vec();
+    input.extend_from_slice(xid.data());
+    Identifier::derive(&input).map_err(|err| {
+        anyhow::anyhow!(
+            "Failed to derive FROST identifier for {}: {err}",
+            xid.ur_string()
+        )
+    })
+}
+
+/// Extract the optional `identifierScheme` parameter from a DKG invite
+/// request.
+///
+/// Invites that omit it use the original index scheme.
+pub fn extract_identifier_scheme(
+    request: &Request,
+) -> Result<IdentifierScheme> {
+    match request.objects_for_parameter("identifierScheme").first() {
+        Some(envelope) => envelope.extract_subject::<String>()?.parse(),
+        None => Ok(IdentifierScheme::Index),
+    }
+}
@@ -1,5 +1,15 @@
+mod charter;
+mod dealt_share;
 mod group_invite;
+mod heartbeat;
+mod identifier_scheme;
 mod proposed_participant;
+mod share_repair;
 
+pub use charter::*;
+pub use dealt_share::*;
 pub use group_invite::*;
+pub use heartbeat::*;
+pub use identifier_scheme::*;
 pub use proposed_participant::*;
+pub use share_repair::*;
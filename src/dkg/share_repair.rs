@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result, bail};
+use bc_components::XID;
+use bc_envelope::prelude::*;
+use frost_core::{Field, keys::repairable};
+use frost_ed25519::{
+    self as frost, Ed25519ScalarField, Ed25519Sha512, Identifier,
+    rand_core::OsRng,
+};
+
+/// A scalar the repairable threshold scheme passes between participants: a
+/// helper's delta for another helper, or the sigma it sums them into.
+pub type RepairScalar = <Ed25519ScalarField as Field>::Scalar;
+
+/// A refresh invite's request to rebuild the share of a participant who lost
+/// it, for a replacement who takes over its identifier.
+///
+/// The other participants of the refresh act as helpers: in Round 1 each
+/// splits its own share into deltas, one per helper; in Round 2 each sums the
+/// deltas addressed to it into a sigma; at finalize the replacement sums the
+/// sigmas into the lost share and refreshes it like everyone else.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareRepair {
+    /// Participant who receives the rebuilt share
+    pub replacement: XID,
+    /// Participant of the refreshed group whose share was lost
+    pub replaces: XID,
+    /// FROST identifier of every refresh participant, with the replacement
+    /// holding the identifier of the participant it replaces
+    pub identifiers: BTreeMap<XID, Identifier>,
+}
+
+impl ShareRepair {
+    /// The replacement's identifier, which is the lost share's.
+    pub fn identifier(&self) -> Result<Identifier> {
+        self.identifiers
+            .get(&self.replacement)
+            .copied()
+            .context("Share repair has no identifier for the replacement")
+    }
+
+    /// Identifiers of the helpers: every participant but the replacement.
+    pub fn helpers(&self) -> Vec<Identifier> {
+        self.identifiers
+            .iter()
+            .filter(|(xid, _)| **xid != self.replacement)
+            .map(|(_, identifier)| *identifier)
+            .collect()
+    }
+
+    pub fn to_envelope(&self) -> Envelope {
+        let mut envelope = Envelope::new(self.replacement)
+            .add_assertion("replaces", self.replaces);
+        for (xid, identifier) in &self.identifiers {
+            envelope = envelope.add_assertion(
+                "participantIdentifier",
+                Envelope::new(*xid).add_assertion(
+                    "identifier",
+                    CBOR::to_byte_string(identifier.serialize()),
+                ),
+            );
+        }
+        envelope
+    }
+
+    pub fn from_envelope(envelope: &Envelope) -> Result<Self> {
+        let mut identifiers = BTreeMap::new();
+        for object in envelope.objects_for_predicate("participantIdentifier") {
+            let xid: XID = object.extract_subject()?;
+            let bytes = object
+                .object_for_predicate("identifier")?
+                .try_leaf()?
+                .try_byte_string()
+                .context("Share repair identifier is not a byte string")?;
+            let identifier = Identifier::deserialize(&bytes)
+                .context("Invalid identifier in share repair")?;
+            identifiers.insert(xid, identifier);
+        }
+        let repair = Self {
+            replacement: envelope.extract_subject()?,
+            replaces: envelope.extract_object_for_predicate("replaces")?,
+            identifiers,
+        };
+        repair.identifier()?;
+        if repair.identifiers.contains_key(&repair.replaces) {
+            bail!(
+                "Share repair lists {}, whose share it replaces, as a \
+                 participant",
+                repair.replaces.ur_string()
+            );
+        }
+        Ok(repair)
+    }
+}
+
+/// Round 1 of a repair, run by each helper: splits the helper's share of the
+/// refreshed group into one delta per helper, keyed by identifier.
+///
+/// Only the identifier and signing share of the secret share FROST builds
+/// here enter the computation; `commitment` fills its commitment slot.
+pub fn repair_deltas(
+    helpers: &[Identifier],
+    key_package: &frost::keys::KeyPackage,
+    commitment: &frost::keys::VerifiableSecretSharingCommitment,
+    replacement: Identifier,
+) -> Result<BTreeMap<Identifier, RepairScalar>> {
+    let share = frost::keys::SecretShare::new(
+        *key_package.identifier(),
+        *key_package.signing_share(),
+        commitment.clone(),
+    );
+    repairable::repair_share_step_1::<Ed25519Sha512, _>(
+        helpers,
+        &share,
+        &mut OsRng,
+        replacement,
+    )
+    .map_err(|e| anyhow::anyhow!("FROST share repair step 1 failed: {}", e))
+}
+
+/// Round 2 of a repair, run by each helper: sums the deltas every helper
+/// addressed to it.
+pub fn repair_sigma(deltas: &[RepairScalar]) -> RepairScalar {
+    repairable::repair_share_step_2::<Ed25519Sha512>(deltas)
+}
+
+/// Final step of a repair, run by the replacement: sums the helpers' sigmas
+/// into the lost share and rebuilds its key package, failing unless the
+/// share matches the verifying share `public_key_package` records for
+/// `identifier`.
+pub fn repaired_key_package(
+    sigmas: &[RepairScalar],
+    identifier: Identifier,
+    commitment: &frost::keys::VerifiableSecretSharingCommitment,
+    public_key_package: &frost::keys::PublicKeyPackage,
+    min_signers: u16,
+) -> Result<frost::keys::KeyPackage> {
+    let share = repairable::repair_share_step_3::<Ed25519Sha512>(
+        sigmas, identifier, commitment,
+    );
+    let signing_share = *share.signing_share();
+    let verifying_share = frost::keys::VerifyingShare::from(signing_share);
+    if public_key_package.verifying_shares().get(&identifier)
+        != Some(&verifying_share)
+    {
+        bail!(
+            "The repaired share does not match the group's verifying share; \
+             a helper sent a bad delta or sigma"
+        );
+    }
+    Ok(frost::keys::KeyPackage::new(
+        identifier,
+        signing_share,
+        verifying_share,
+        *public_key_package.verifying_key(),
+        min_signers,
+    ))
+}
+
+/// A repair scalar as an envelope whose subject is its byte encoding.
+pub fn repair_scalar_envelope(scalar: &RepairScalar) -> Envelope {
+    Envelope::new(CBOR::to_byte_string(Ed25519ScalarField::serialize(scalar)))
+}
+
+/// Reverses [`repair_scalar_envelope`], ignoring any assertions.
+pub fn extract_repair_scalar(envelope: &Envelope) -> Result<RepairScalar> {
+    let bytes = envelope
+        .subject()
+        .try_leaf()?
+        .try_byte_string()
+        .context("Repair scalar is not a byte string")?;
+    let bytes: [u8; 32] = bytes
+        .as_slice()
+        .try_into()
+        .context("Repair scalar is not 32 bytes")?;
+    Ed25519ScalarField::deserialize(&bytes)
+        .map_err(|_| anyhow::anyhow!("Repair scalar is not a valid scalar"))
+}
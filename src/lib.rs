@@ -1,6 +1,10 @@
+use std::process::ExitCode;
+
 use anyhow::Result;
 use clap::Parser;
 
+use crate::cmd::common::{PARTIAL_COLLECTION_EXIT_CODE, PartialCollection};
+
 pub mod cmd;
 pub mod dkg;
 pub mod registry;
@@ -17,3 +21,13 @@ pub fn run() -> Result<()> {
     let cli = cmd::Cli::parse();
     cli.exec()
 }
+
+/// Process exit status for a failed run: distinct for a partial collection
+/// saved with `--continue-on-error`, otherwise the generic failure.
+pub fn exit_code(err: &anyhow::Error) -> ExitCode {
+    if err.downcast_ref::<PartialCollection>().is_some() {
+        ExitCode::from(PARTIAL_COLLECTION_EXIT_CODE)
+    } else {
+        ExitCode::FAILURE
+    }
+}
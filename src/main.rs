@@ -1,3 +1,11 @@
-use anyhow::Result;
+use std::process::ExitCode;
 
-fn main() -> Result<()> { frost_hubert::run() }
+fn main() -> ExitCode {
+    match frost_hubert::run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+            frost_hubert::exit_code(&err)
+        }
+    }
+}
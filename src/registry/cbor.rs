@@ -0,0 +1,59 @@
+//! Helpers for the dCBOR encoding of registry records.
+//!
+//! Each record is a map keyed by the same field names as its JSON form.
+//! XIDs, ARIDs, and keys are stored as their tagged CBOR rather than as UR
+//! strings; XID documents and attestations keep the UR text they were
+//! registered with.
+
+use anyhow::{Context, Result, anyhow};
+use bc_envelope::prelude::*;
+
+/// Opens the map that encodes a registry record.
+pub(super) fn record_map(cbor: CBOR, what: &str) -> Result<Map> {
+    cbor.try_into_map()
+        .with_context(|| format!("Expected a CBOR map for the {what}"))
+}
+
+/// Reads `key` from a record map, if present.
+pub(super) fn optional<T>(map: &Map, key: &str) -> Result<Option<T>>
+where
+    T: TryFrom<CBOR>,
+    T::Error: Into<anyhow::Error>,
+{
+    map.get::<&str, CBOR>(key)
+        .map(|value| T::try_from(value).map_err(Into::into))
+        .transpose()
+        .with_context(|| format!("Invalid `{key}` in registry CBOR"))
+}
+
+/// Reads `key` from a record map, failing if it is absent.
+pub(super) fn required<T>(map: &Map, key: &str) -> Result<T>
+where
+    T: TryFrom<CBOR>,
+    T::Error: Into<anyhow::Error>,
+{
+    optional(map, key)?
+        .ok_or_else(|| anyhow!("Missing `{key}` in registry CBOR"))
+}
+
+/// Reads `key` from a record map as an array of `T`. An absent key is an
+/// empty array.
+pub(super) fn array<T>(map: &Map, key: &str) -> Result<Vec<T>>
+where
+    T: TryFrom<CBOR>,
+    T::Error: Into<anyhow::Error>,
+{
+    let Some(items) = optional::<CBOR>(map, key)? else {
+        return Ok(Vec::new());
+    };
+    items
+        .try_into_array()
+        .map_err(anyhow::Error::from)
+        .and_then(|items| {
+            items
+                .into_iter()
+                .map(|item| T::try_from(item).map_err(Into::into))
+                .collect()
+        })
+        .with_context(|| format!("Invalid `{key}` in registry CBOR"))
+}
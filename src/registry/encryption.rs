@@ -0,0 +1,75 @@
+//! Passphrase encryption of whole registry files.
+//!
+//! An encrypted registry starts with [`ENCRYPTED_REGISTRY_MAGIC`] followed by
+//! a `ur:envelope` whose subject is the registry's usual JSON or CBOR
+//! encoding, locked with a key derived from the passphrase by Argon2id.
+//! [`Registry::load_with_passphrase`](super::Registry::load_with_passphrase)
+//! and [`Registry::save`](super::Registry::save) detect and apply it, so
+//! commands never handle the ciphertext themselves. The caller supplies the
+//! passphrase, and the loaded registry keeps it for its own saves.
+
+use std::fmt;
+
+use anyhow::{Context, Result, anyhow, bail};
+use bc_components::KeyDerivationMethod;
+use bc_envelope::prelude::*;
+
+/// First line of every encrypted registry file.
+pub const ENCRYPTED_REGISTRY_MAGIC: &[u8] = b"frost-encrypted-registry/1\n";
+
+/// The passphrase an encrypted registry is locked with. Its `Debug` output
+/// is redacted, so a loaded registry can be printed without revealing it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Passphrase(String);
+
+impl Passphrase {
+    pub fn new(passphrase: impl Into<String>) -> Result<Self> {
+        let passphrase = passphrase.into();
+        if passphrase.is_empty() {
+            bail!("Registry passphrase cannot be empty");
+        }
+        Ok(Self(passphrase))
+    }
+
+    pub fn as_str(&self) -> &str { &self.0 }
+}
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Passphrase(..)")
+    }
+}
+
+/// Whether `data` is an encrypted registry file.
+pub fn is_encrypted_registry(data: &[u8]) -> bool {
+    data.starts_with(ENCRYPTED_REGISTRY_MAGIC)
+}
+
+/// Encrypts an encoded registry under `passphrase`.
+pub fn encrypt_registry(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let locked = Envelope::new(ByteString::from(plaintext.to_vec()))
+        .lock(KeyDerivationMethod::Argon2id, passphrase.as_bytes())
+        .context("Failed to encrypt registry")?;
+    let mut data = ENCRYPTED_REGISTRY_MAGIC.to_vec();
+    data.extend_from_slice(locked.ur_string().as_bytes());
+    data.push(b'\n');
+    Ok(data)
+}
+
+/// Decrypts an encrypted registry file, returning its encoded registry.
+pub fn decrypt_registry(data: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let body = data
+        .strip_prefix(ENCRYPTED_REGISTRY_MAGIC)
+        .context("Registry is not encrypted")?;
+    let ur = std::str::from_utf8(body)
+        .context("Encrypted registry is not valid UTF-8")?;
+    let locked = Envelope::from_ur_string(ur.trim())
+        .context("Encrypted registry is corrupted")?;
+    let unlocked = locked.unlock(passphrase.as_bytes()).map_err(|_| {
+        anyhow!("Wrong passphrase or corrupted encrypted registry")
+    })?;
+    let plaintext: ByteString = unlocked
+        .extract_subject()
+        .context("Encrypted registry is corrupted")?;
+    Ok(plaintext.into())
+}
@@ -1,8 +1,68 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{Context, bail};
 use bc_components::{SigningPublicKey, XID};
-use bc_envelope::prelude::UREncodable;
+use bc_envelope::prelude::{CBOR, Map, UREncodable};
 use bc_ur::URDecodable;
+use frost_ed25519::Identifier;
 use serde::{Deserialize, Serialize};
 
+use super::cbor::{array, optional, record_map, required};
+use crate::dkg::IdentifierScheme;
+
+/// How a group's key shares were created.
+///
+/// `TrustedDealer` groups come from `frost keygen`: one party generated every
+/// share and could have kept a copy, so the group key is only as trustworthy
+/// as that dealer. A `Repaired` share was rebuilt by the other participants
+/// of a refresh (`dkg coordinator refresh --replace-participant`) for a
+/// participant who never held the group's original share.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupProvenance {
+    #[default]
+    Dkg,
+    TrustedDealer,
+    Repaired,
+}
+
+impl GroupProvenance {
+    pub fn is_dkg(&self) -> bool { *self == GroupProvenance::Dkg }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            GroupProvenance::Dkg => "dkg",
+            GroupProvenance::TrustedDealer => "trusted_dealer",
+            GroupProvenance::Repaired => "repaired",
+        }
+    }
+
+    fn from_name(name: &str) -> anyhow::Result<Self> {
+        match name {
+            "dkg" => Ok(GroupProvenance::Dkg),
+            "trusted_dealer" => Ok(GroupProvenance::TrustedDealer),
+            "repaired" => Ok(GroupProvenance::Repaired),
+            other => bail!("Unknown group provenance '{other}'"),
+        }
+    }
+}
+
+/// Where a group is in its lifecycle.
+///
+/// Derived from the record rather than stored: a group is `Finalized` once
+/// finalize (or `keygen`/`import-share`) has recorded its verifying key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupStatus {
+    Pending,
+    Finalized,
+}
+
+impl GroupStatus {
+    pub fn is_finalized(&self) -> bool { *self == GroupStatus::Finalized }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(transparent)]
 pub struct GroupParticipant {
@@ -89,6 +149,14 @@ struct PendingRequest {
     collect_from_arid: bc_components::ARID,
 }
 
+impl PendingRequest {
+    /// Added with `add_send_only`, whose placeholder collection ARID is the
+    /// send ARID.
+    fn is_unsent(&self) -> bool {
+        self.send_to_arid == Some(self.collect_from_arid)
+    }
+}
+
 impl PendingRequests {
     pub fn new() -> Self { Self { requests: Vec::new() } }
 
@@ -140,6 +208,49 @@ impl PendingRequests {
 
     pub fn is_empty(&self) -> bool { self.requests.is_empty() }
 
+    /// Whether responses have been collected and the next request has not
+    /// yet gone out to every participant: each request was added with
+    /// `add_send_only` and is either still unsent or has been marked sent
+    /// with `mark_sent`, and at least one is still unsent.
+    pub fn is_send_only(&self) -> bool {
+        self.requests.iter().any(PendingRequest::is_unsent)
+            && self
+                .requests
+                .iter()
+                .all(|r| r.is_unsent() || r.send_to_arid.is_none())
+    }
+
+    /// Whether every request has gone out and only responses remain to be
+    /// collected: each was added with `add_collect_only` or marked sent with
+    /// `mark_sent`.
+    pub fn is_collect_only(&self) -> bool {
+        !self.requests.is_empty()
+            && self.requests.iter().all(|r| r.send_to_arid.is_none())
+    }
+
+    /// Whether the request to `participant` was added with `add_send_only`
+    /// and has not been marked sent.
+    pub fn is_unsent(&self, participant: &XID) -> bool {
+        self.requests
+            .iter()
+            .any(|r| &r.participant == participant && r.is_unsent())
+    }
+
+    /// Record that the next request to `participant` has been sent; from
+    /// now on only its response is collected, from `collect_from_arid`.
+    pub fn mark_sent(
+        &mut self,
+        participant: &XID,
+        collect_from_arid: bc_components::ARID,
+    ) {
+        for request in &mut self.requests {
+            if &request.participant == participant {
+                request.send_to_arid = None;
+                request.collect_from_arid = collect_from_arid;
+            }
+        }
+    }
+
     /// Iterate over (participant, collect_from_arid) pairs.
     /// Used when collecting responses.
     pub fn iter_collect(
@@ -181,14 +292,39 @@ impl PendingRequests {
     }
 
     pub fn len(&self) -> usize { self.requests.len() }
+
+    /// Collection ARIDs of the given participants, in request order.
+    pub fn collect_arids_for(
+        &self,
+        participants: &[XID],
+    ) -> Vec<bc_components::ARID> {
+        self.requests
+            .iter()
+            .filter(|r| participants.contains(&r.participant))
+            .map(|r| r.collect_from_arid)
+            .collect()
+    }
+
+    /// Drop any pending request for `participant`.
+    pub fn remove(&mut self, participant: &XID) {
+        self.requests.retain(|r| &r.participant != participant);
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct GroupRecord {
+    /// Friendly name that commands accept in place of the group's ur:arid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
     charter: String,
     min_signers: usize,
     coordinator: GroupParticipant,
     participants: Vec<GroupParticipant>,
+    /// Participants removed from the group after Round 1 (see `dkg
+    /// coordinator round1 --exclude`). They keep their place in the identifier
+    /// order so the remaining participants' FROST identifiers do not change.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    excluded: Vec<GroupParticipant>,
     #[serde(default, skip_serializing_if = "ContributionPaths::is_empty")]
     contributions: ContributionPaths,
     /// ARID where this participant is listening for the coordinator's next
@@ -211,6 +347,32 @@ pub struct GroupRecord {
         skip_serializing_if = "Option::is_none"
     )]
     verifying_key: Option<SigningPublicKey>,
+    /// Coordinator-signed charter statement (envelope UR) received with the
+    /// invite; see `frost registry group verify-charter`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    charter_attestation: Option<String>,
+    /// How participant XIDs map to FROST identifiers (absent = index)
+    #[serde(default, skip_serializing_if = "IdentifierScheme::is_index")]
+    identifier_scheme: IdentifierScheme,
+    /// FROST identifier of every participant, fixed when the invite is sent
+    /// or answered (absent = recomputed from the scheme and roster)
+    #[serde(
+        default,
+        with = "serde_identifier_map",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    identifier_map: BTreeMap<XID, Identifier>,
+    /// How the key shares were created (absent = DKG)
+    #[serde(default, skip_serializing_if = "GroupProvenance::is_dkg")]
+    provenance: GroupProvenance,
+    /// Group whose key shares this group's DKG rotates (see `dkg coordinator
+    /// refresh`); the two groups share a verifying key
+    #[serde(
+        default,
+        with = "serde_option_arid",
+        skip_serializing_if = "Option::is_none"
+    )]
+    refresh_of: Option<bc_components::ARID>,
 }
 
 impl GroupRecord {
@@ -221,25 +383,113 @@ impl GroupRecord {
         participants: Vec<GroupParticipant>,
     ) -> Self {
         Self {
+            name: None,
             charter,
             min_signers,
             coordinator,
             participants,
+            excluded: Vec::new(),
             contributions: ContributionPaths::default(),
             listening_at_arid: None,
             pending_requests: PendingRequests::default(),
             verifying_key: None,
+            charter_attestation: None,
+            identifier_scheme: IdentifierScheme::default(),
+            identifier_map: BTreeMap::new(),
+            provenance: GroupProvenance::default(),
+            refresh_of: None,
         }
     }
 
+    pub fn name(&self) -> Option<&str> { self.name.as_deref() }
+
+    /// Sets or clears the name without checking it is unique; use
+    /// [`Registry::set_group_name`](super::Registry::set_group_name) for
+    /// recorded groups.
+    pub fn set_name(&mut self, name: Option<String>) { self.name = name; }
+
     pub fn coordinator(&self) -> &GroupParticipant { &self.coordinator }
 
     pub fn participants(&self) -> &[GroupParticipant] { &self.participants }
 
+    pub fn excluded(&self) -> &[GroupParticipant] { &self.excluded }
+
+    /// Moves `xid` from the participants to the excluded list and drops any
+    /// pending request for it. Returns false if `xid` is not a current
+    /// participant.
+    pub fn exclude_participant(&mut self, xid: &XID) -> bool {
+        let Some(index) = self.participants.iter().position(|p| p.xid() == xid)
+        else {
+            return false;
+        };
+        let participant = self.participants.remove(index);
+        self.excluded.push(participant);
+        self.pending_requests.remove(xid);
+        true
+    }
+
+    /// All participant XIDs, including excluded ones, sorted into FROST
+    /// identifier order (under the index scheme, the identifier of each XID is
+    /// its index + 1).
+    pub fn identifier_order(&self) -> Vec<XID> {
+        let mut xids: Vec<XID> = self
+            .participants
+            .iter()
+            .chain(&self.excluded)
+            .map(|p| *p.xid())
+            .collect();
+        xids.sort();
+        xids.dedup();
+        xids
+    }
+
+    pub fn identifier_scheme(&self) -> IdentifierScheme {
+        self.identifier_scheme
+    }
+
+    pub fn set_identifier_scheme(&mut self, scheme: IdentifierScheme) {
+        self.identifier_scheme = scheme;
+    }
+
+    /// FROST identifier of every participant (including excluded ones).
+    ///
+    /// Uses the map persisted by `set_identifiers` when there is one, so later
+    /// phases do not depend on every party sorting the roster identically.
+    /// Older records without a map fall back to the identifier scheme.
+    pub fn identifiers(&self) -> anyhow::Result<HashMap<XID, Identifier>> {
+        if !self.identifier_map.is_empty() {
+            return Ok(self
+                .identifier_map
+                .iter()
+                .map(|(xid, identifier)| (*xid, *identifier))
+                .collect());
+        }
+        self.identifier_scheme.identifiers(&self.identifier_order())
+    }
+
+    pub fn set_identifiers(&mut self, identifiers: HashMap<XID, Identifier>) {
+        self.identifier_map = identifiers.into_iter().collect();
+    }
+
+    /// Adopts `other`'s persisted identifiers if this record has none.
+    pub fn merge_identifiers(&mut self, other: &GroupRecord) {
+        if self.identifier_map.is_empty() {
+            self.identifier_map = other.identifier_map.clone();
+        }
+    }
+
     pub fn min_signers(&self) -> usize { self.min_signers }
 
     pub fn charter(&self) -> &str { &self.charter }
 
+    /// Whether the coordinator is also one of the group's signers, answering
+    /// its own invite like any other participant.
+    pub fn coordinator_signs(&self) -> bool {
+        self.participants
+            .iter()
+            .any(|participant| participant.xid() == self.coordinator.xid())
+    }
+
     pub fn contributions(&self) -> &ContributionPaths { &self.contributions }
 
     pub fn set_contributions(&mut self, contributions: ContributionPaths) {
@@ -279,7 +529,9 @@ impl GroupRecord {
         self.charter == other.charter
             && self.min_signers == other.min_signers
             && self.coordinator == other.coordinator
-            && self.participants == other.participants
+            && same_participants(&self.participants, &other.participants)
+            && self.identifier_scheme == other.identifier_scheme
+            && self.provenance == other.provenance
     }
 
     pub fn verifying_key(&self) -> Option<&SigningPublicKey> {
@@ -289,6 +541,214 @@ impl GroupRecord {
     pub fn set_verifying_key(&mut self, key: SigningPublicKey) {
         self.verifying_key = Some(key);
     }
+
+    pub fn status(&self) -> GroupStatus {
+        if self.verifying_key.is_some() {
+            GroupStatus::Finalized
+        } else {
+            GroupStatus::Pending
+        }
+    }
+
+    pub fn charter_attestation(&self) -> Option<&str> {
+        self.charter_attestation.as_deref()
+    }
+
+    pub fn set_charter_attestation(&mut self, attestation_ur: String) {
+        self.charter_attestation = Some(attestation_ur);
+    }
+
+    pub fn provenance(&self) -> GroupProvenance { self.provenance }
+
+    pub fn set_provenance(&mut self, provenance: GroupProvenance) {
+        self.provenance = provenance;
+    }
+
+    /// The group whose shares this group refreshes, if it is a refresh.
+    pub fn refresh_of(&self) -> Option<bc_components::ARID> { self.refresh_of }
+
+    pub fn set_refresh_of(&mut self, group_id: bc_components::ARID) {
+        self.refresh_of = Some(group_id);
+    }
+}
+
+impl From<&ContributionPaths> for CBOR {
+    fn from(paths: &ContributionPaths) -> Self {
+        let mut map = Map::new();
+        for (key, path) in [
+            ("round1_secret", &paths.round1_secret),
+            ("round1_package", &paths.round1_package),
+            ("round2_secret", &paths.round2_secret),
+            ("key_package", &paths.key_package),
+        ] {
+            if let Some(path) = path {
+                map.insert(key, path.as_str());
+            }
+        }
+        map.into()
+    }
+}
+
+impl TryFrom<CBOR> for ContributionPaths {
+    type Error = anyhow::Error;
+
+    fn try_from(cbor: CBOR) -> anyhow::Result<Self> {
+        let map = record_map(cbor, "contribution paths")?;
+        Ok(Self {
+            round1_secret: optional(&map, "round1_secret")?,
+            round1_package: optional(&map, "round1_package")?,
+            round2_secret: optional(&map, "round2_secret")?,
+            key_package: optional(&map, "key_package")?,
+        })
+    }
+}
+
+impl From<&PendingRequest> for CBOR {
+    fn from(request: &PendingRequest) -> Self {
+        let mut map = Map::new();
+        map.insert("participant", request.participant);
+        if let Some(arid) = request.send_to_arid {
+            map.insert("send_to_arid", arid);
+        }
+        map.insert("collect_from_arid", request.collect_from_arid);
+        map.into()
+    }
+}
+
+impl TryFrom<CBOR> for PendingRequest {
+    type Error = anyhow::Error;
+
+    fn try_from(cbor: CBOR) -> anyhow::Result<Self> {
+        let map = record_map(cbor, "pending request")?;
+        Ok(Self {
+            participant: required(&map, "participant")?,
+            send_to_arid: optional(&map, "send_to_arid")?,
+            collect_from_arid: required(&map, "collect_from_arid")?,
+        })
+    }
+}
+
+impl From<&GroupRecord> for CBOR {
+    fn from(record: &GroupRecord) -> Self {
+        let xids = |participants: &[GroupParticipant]| -> Vec<XID> {
+            participants.iter().map(|p| p.xid).collect()
+        };
+        let mut map = Map::new();
+        if let Some(name) = &record.name {
+            map.insert("name", name.as_str());
+        }
+        map.insert("charter", record.charter.as_str());
+        map.insert("min_signers", record.min_signers);
+        map.insert("coordinator", record.coordinator.xid);
+        map.insert("participants", xids(&record.participants));
+        if !record.excluded.is_empty() {
+            map.insert("excluded", xids(&record.excluded));
+        }
+        if !record.contributions.is_empty() {
+            map.insert("contributions", &record.contributions);
+        }
+        if let Some(arid) = record.listening_at_arid {
+            map.insert("listening_at_arid", arid);
+        }
+        if !record.pending_requests.is_empty() {
+            let requests: Vec<CBOR> = record
+                .pending_requests
+                .requests
+                .iter()
+                .map(CBOR::from)
+                .collect();
+            map.insert("pending_requests", requests);
+        }
+        if let Some(key) = &record.verifying_key {
+            map.insert("verifying_key", key.clone());
+        }
+        if let Some(attestation) = &record.charter_attestation {
+            map.insert("charter_attestation", attestation.as_str());
+        }
+        if !record.identifier_scheme.is_index() {
+            map.insert("identifier_scheme", record.identifier_scheme.as_str());
+        }
+        if !record.identifier_map.is_empty() {
+            let mut identifiers = Map::new();
+            for (xid, identifier) in &record.identifier_map {
+                identifiers
+                    .insert(*xid, CBOR::to_byte_string(identifier.serialize()));
+            }
+            map.insert("identifier_map", identifiers);
+        }
+        if !record.provenance.is_dkg() {
+            map.insert("provenance", record.provenance.as_str());
+        }
+        if let Some(arid) = record.refresh_of {
+            map.insert("refresh_of", arid);
+        }
+        map.into()
+    }
+}
+
+impl TryFrom<CBOR> for GroupRecord {
+    type Error = anyhow::Error;
+
+    fn try_from(cbor: CBOR) -> anyhow::Result<Self> {
+        let map = record_map(cbor, "group record")?;
+        let participants =
+            |key: &str| -> anyhow::Result<Vec<GroupParticipant>> {
+                Ok(array::<XID>(&map, key)?
+                    .into_iter()
+                    .map(GroupParticipant::new)
+                    .collect())
+            };
+
+        let mut identifier_map = BTreeMap::new();
+        if let Some(identifiers) = optional::<CBOR>(&map, "identifier_map")? {
+            let identifiers = record_map(identifiers, "identifier map")?;
+            for (xid, identifier) in identifiers.iter() {
+                let xid = XID::try_from(xid.clone())
+                    .context("Invalid XID in identifier map")?;
+                let bytes = CBOR::try_into_byte_string(identifier.clone())
+                    .context("Invalid identifier in identifier map")?;
+                let identifier = Identifier::deserialize(&bytes)
+                    .context("Invalid identifier in identifier map")?;
+                identifier_map.insert(xid, identifier);
+            }
+        }
+
+        Ok(Self {
+            name: optional(&map, "name")?,
+            charter: required(&map, "charter")?,
+            min_signers: required(&map, "min_signers")?,
+            coordinator: GroupParticipant::new(required(&map, "coordinator")?),
+            participants: participants("participants")?,
+            excluded: participants("excluded")?,
+            contributions: optional(&map, "contributions")?.unwrap_or_default(),
+            listening_at_arid: optional(&map, "listening_at_arid")?,
+            pending_requests: PendingRequests {
+                requests: array(&map, "pending_requests")?,
+            },
+            verifying_key: optional(&map, "verifying_key")?,
+            charter_attestation: optional(&map, "charter_attestation")?,
+            identifier_scheme: optional::<String>(&map, "identifier_scheme")?
+                .map(|scheme| scheme.parse())
+                .transpose()?
+                .unwrap_or_default(),
+            identifier_map,
+            provenance: optional::<String>(&map, "provenance")?
+                .map(|provenance| GroupProvenance::from_name(&provenance))
+                .transpose()?
+                .unwrap_or_default(),
+            refresh_of: optional(&map, "refresh_of")?,
+        })
+    }
+}
+
+/// Compares rosters regardless of order: a coordinator records its invite in
+/// the order participants were listed, a participant in XID order.
+fn same_participants(a: &[GroupParticipant], b: &[GroupParticipant]) -> bool {
+    let mut a: Vec<&XID> = a.iter().map(GroupParticipant::xid).collect();
+    let mut b: Vec<&XID> = b.iter().map(GroupParticipant::xid).collect();
+    a.sort();
+    b.sort();
+    a == b
 }
 
 mod serde_xid {
@@ -312,6 +772,41 @@ mod serde_xid {
     }
 }
 
+mod serde_identifier_map {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    pub fn serialize<S>(
+        map: &BTreeMap<XID, Identifier>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        map.iter()
+            .map(|(xid, identifier)| (xid.ur_string(), identifier))
+            .collect::<BTreeMap<_, _>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<BTreeMap<XID, Identifier>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BTreeMap::<String, Identifier>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(raw, identifier)| {
+                let xid = XID::from_ur_string(&raw)
+                    .map_err(serde::de::Error::custom)?;
+                Ok((xid, identifier))
+            })
+            .collect()
+    }
+}
+
 mod serde_arid {
     use bc_components::ARID;
     use bc_envelope::prelude::CBOR;
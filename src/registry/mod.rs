@@ -1,10 +1,17 @@
+mod cbor;
+mod encryption;
 mod group_record;
 mod owner_record;
 mod participant_record;
 mod registry_impl;
 
+pub use encryption::{
+    ENCRYPTED_REGISTRY_MAGIC, Passphrase, decrypt_registry, encrypt_registry,
+    is_encrypted_registry,
+};
 pub use group_record::{
-    ContributionPaths, GroupParticipant, GroupRecord, PendingRequests,
+    ContributionPaths, GroupParticipant, GroupProvenance, GroupRecord,
+    GroupStatus, PendingRequests,
 };
 pub use owner_record::OwnerRecord;
 pub use participant_record::ParticipantRecord;
@@ -1,13 +1,18 @@
 use anyhow::{Context, Result, bail};
 use bc_components::{XID, XIDProvider};
 use bc_envelope::prelude::*;
-use bc_xid::{XIDDocument, XIDVerifySignature};
+use bc_xid::{
+    XIDDocument, XIDGeneratorOptions, XIDPrivateKeyOptions, XIDSigningOptions,
+    XIDVerifySignature,
+};
 use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
     de::{self, MapAccess, Visitor},
     ser::SerializeStruct,
 };
 
+use super::cbor::{optional, record_map, required};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OwnerRecord {
     xid_document_ur: String,
@@ -38,6 +43,17 @@ impl OwnerRecord {
     pub fn xid_document_ur(&self) -> &str { &self.xid_document_ur }
 
     pub fn pet_name(&self) -> Option<&str> { self.pet_name.as_deref() }
+
+    /// The owner's XID document UR with private keys removed, re-signed with
+    /// the inception key so others can verify it.
+    pub fn public_xid_document_ur(&self) -> Result<String> {
+        let envelope = self.xid_document.to_envelope(
+            XIDPrivateKeyOptions::default(),
+            XIDGeneratorOptions::default(),
+            XIDSigningOptions::Inception,
+        )?;
+        Ok(envelope.ur_string())
+    }
 }
 
 impl Serialize for OwnerRecord {
@@ -161,6 +177,29 @@ impl<'de> Deserialize<'de> for OwnerRecord {
     }
 }
 
+impl From<&OwnerRecord> for CBOR {
+    fn from(record: &OwnerRecord) -> Self {
+        let mut map = Map::new();
+        map.insert("xid_document", record.xid_document_ur.as_str());
+        if let Some(name) = &record.pet_name {
+            map.insert("pet_name", name.as_str());
+        }
+        map.into()
+    }
+}
+
+impl TryFrom<CBOR> for OwnerRecord {
+    type Error = anyhow::Error;
+
+    fn try_from(cbor: CBOR) -> Result<Self> {
+        let map = record_map(cbor, "owner record")?;
+        Self::from_signed_xid_ur(
+            required::<String>(&map, "xid_document")?,
+            optional(&map, "pet_name")?,
+        )
+    }
+}
+
 fn parse_relaxed_xid_document(
     xid_document_ur: impl Into<String>,
 ) -> Result<(String, XIDDocument)> {
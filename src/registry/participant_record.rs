@@ -1,19 +1,27 @@
 use anyhow::{Context, Result, anyhow, bail};
 use bc_components::{PublicKeys, XID, XIDProvider};
 use bc_envelope::prelude::*;
-use bc_xid::{XIDDocument, XIDVerifySignature};
+use bc_xid::{
+    XIDDocument, XIDGeneratorOptions, XIDPrivateKeyOptions, XIDSigningOptions,
+    XIDVerifySignature,
+};
 use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
     de::{self, MapAccess, Visitor},
     ser::SerializeStruct,
 };
 
+use super::cbor::{array, optional, record_map, required};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParticipantRecord {
     xid_document_ur: String,
     xid_document: XIDDocument,
     public_keys: PublicKeys,
     pet_name: Option<String>,
+    note: Option<String>,
+    contact: Option<String>,
+    tags: Vec<String>,
 }
 
 impl ParticipantRecord {
@@ -30,6 +38,50 @@ impl ParticipantRecord {
     pub fn xid(&self) -> XID { self.xid_document.xid() }
     pub fn xid_document(&self) -> &XIDDocument { &self.xid_document }
     pub fn xid_document_ur(&self) -> &str { &self.xid_document_ur }
+    pub fn note(&self) -> Option<&str> { self.note.as_deref() }
+    pub fn contact(&self) -> Option<&str> { self.contact.as_deref() }
+    pub fn tags(&self) -> &[String] { &self.tags }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    pub fn set_note(&mut self, note: Option<String>) { self.note = note; }
+
+    pub fn set_contact(&mut self, contact: Option<String>) {
+        self.contact = contact;
+    }
+
+    /// Add a tag, returning `false` if it was already present.
+    pub fn add_tag(&mut self, tag: impl Into<String>) -> bool {
+        let tag = tag.into();
+        if self.has_tag(&tag) {
+            return false;
+        }
+        self.tags.push(tag);
+        true
+    }
+
+    /// Remove a tag, returning `false` if it was not present.
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let before = self.tags.len();
+        self.tags.retain(|t| t != tag);
+        self.tags.len() != before
+    }
+
+    /// The XID document UR with any private keys removed, suitable for
+    /// sharing. Documents stored without private keys are returned as-is.
+    pub fn public_xid_document_ur(&self) -> Result<String> {
+        if self.xid_document.inception_private_keys().is_none() {
+            return Ok(self.xid_document_ur.clone());
+        }
+        let envelope = self.xid_document.to_envelope(
+            XIDPrivateKeyOptions::default(),
+            XIDGeneratorOptions::default(),
+            XIDSigningOptions::Inception,
+        )?;
+        Ok(envelope.ur_string())
+    }
 
     fn build_from_parts(
         document: XIDDocument,
@@ -46,6 +98,9 @@ impl ParticipantRecord {
             xid_document: document,
             public_keys,
             pet_name,
+            note: None,
+            contact: None,
+            tags: Vec::new(),
         };
         Ok(record)
     }
@@ -64,13 +119,26 @@ impl Serialize for ParticipantRecord {
     where
         S: Serializer,
     {
-        let field_count = if self.pet_name.is_some() { 2 } else { 1 };
+        let field_count = 1
+            + usize::from(self.pet_name.is_some())
+            + usize::from(self.note.is_some())
+            + usize::from(self.contact.is_some())
+            + usize::from(!self.tags.is_empty());
         let mut state =
             serializer.serialize_struct("ParticipantRecord", field_count)?;
         state.serialize_field("xid_document", &self.xid_document_ur)?;
         if let Some(name) = &self.pet_name {
             state.serialize_field("pet_name", name)?;
         }
+        if let Some(note) = &self.note {
+            state.serialize_field("note", note)?;
+        }
+        if let Some(contact) = &self.contact {
+            state.serialize_field("contact", contact)?;
+        }
+        if !self.tags.is_empty() {
+            state.serialize_field("tags", &self.tags)?;
+        }
         state.end()
     }
 }
@@ -80,9 +148,15 @@ impl<'de> Deserialize<'de> for ParticipantRecord {
     where
         D: Deserializer<'de>,
     {
+        const FIELDS: &[&str] =
+            &["xid_document", "pet_name", "note", "contact", "tags"];
+
         enum Field {
             XidDocument,
             PetName,
+            Note,
+            Contact,
+            Tags,
         }
 
         impl<'de> Deserialize<'de> for Field {
@@ -99,7 +173,10 @@ impl<'de> Deserialize<'de> for ParticipantRecord {
                         &self,
                         formatter: &mut std::fmt::Formatter,
                     ) -> std::fmt::Result {
-                        formatter.write_str("`xid_document` or `pet_name`")
+                        formatter.write_str(
+                            "`xid_document`, `pet_name`, `note`, `contact`, \
+                             or `tags`",
+                        )
                     }
 
                     fn visit_str<E>(self, value: &str) -> Result<Field, E>
@@ -109,10 +186,10 @@ impl<'de> Deserialize<'de> for ParticipantRecord {
                         match value {
                             "xid_document" => Ok(Field::XidDocument),
                             "pet_name" => Ok(Field::PetName),
-                            _ => Err(de::Error::unknown_field(
-                                value,
-                                &["xid_document", "pet_name"],
-                            )),
+                            "note" => Ok(Field::Note),
+                            "contact" => Ok(Field::Contact),
+                            "tags" => Ok(Field::Tags),
+                            _ => Err(de::Error::unknown_field(value, FIELDS)),
                         }
                     }
                 }
@@ -139,6 +216,9 @@ impl<'de> Deserialize<'de> for ParticipantRecord {
             {
                 let mut xid_document_ur: Option<String> = None;
                 let mut pet_name: Option<Option<String>> = None;
+                let mut note: Option<Option<String>> = None;
+                let mut contact: Option<Option<String>> = None;
+                let mut tags: Option<Vec<String>> = None;
 
                 while let Some(field) = map.next_key()? {
                     match field {
@@ -158,28 +238,88 @@ impl<'de> Deserialize<'de> for ParticipantRecord {
                             }
                             pet_name = Some(map.next_value()?);
                         }
+                        Field::Note => {
+                            if note.is_some() {
+                                return Err(de::Error::duplicate_field("note"));
+                            }
+                            note = Some(map.next_value()?);
+                        }
+                        Field::Contact => {
+                            if contact.is_some() {
+                                return Err(de::Error::duplicate_field(
+                                    "contact",
+                                ));
+                            }
+                            contact = Some(map.next_value()?);
+                        }
+                        Field::Tags => {
+                            if tags.is_some() {
+                                return Err(de::Error::duplicate_field("tags"));
+                            }
+                            tags = Some(map.next_value()?);
+                        }
                     }
                 }
 
                 let xid_document_ur = xid_document_ur
                     .ok_or_else(|| de::Error::missing_field("xid_document"))?;
 
-                ParticipantRecord::recreate_from_serialized(
+                let mut record = ParticipantRecord::recreate_from_serialized(
                     xid_document_ur,
                     pet_name.unwrap_or(None),
                 )
-                .map_err(de::Error::custom)
+                .map_err(de::Error::custom)?;
+                record.note = note.unwrap_or(None);
+                record.contact = contact.unwrap_or(None);
+                record.tags = tags.unwrap_or_default();
+                Ok(record)
             }
         }
 
         deserializer.deserialize_struct(
             "ParticipantRecord",
-            &["xid_document", "pet_name"],
+            FIELDS,
             ParticipantRecordVisitor,
         )
     }
 }
 
+impl From<&ParticipantRecord> for CBOR {
+    fn from(record: &ParticipantRecord) -> Self {
+        let mut map = Map::new();
+        map.insert("xid_document", record.xid_document_ur.as_str());
+        if let Some(name) = &record.pet_name {
+            map.insert("pet_name", name.as_str());
+        }
+        if let Some(note) = &record.note {
+            map.insert("note", note.as_str());
+        }
+        if let Some(contact) = &record.contact {
+            map.insert("contact", contact.as_str());
+        }
+        if !record.tags.is_empty() {
+            map.insert("tags", record.tags.clone());
+        }
+        map.into()
+    }
+}
+
+impl TryFrom<CBOR> for ParticipantRecord {
+    type Error = anyhow::Error;
+
+    fn try_from(cbor: CBOR) -> Result<Self> {
+        let map = record_map(cbor, "participant record")?;
+        let mut record = Self::recreate_from_serialized(
+            required(&map, "xid_document")?,
+            optional(&map, "pet_name")?,
+        )?;
+        record.note = optional(&map, "note")?;
+        record.contact = optional(&map, "contact")?;
+        record.tags = array(&map, "tags")?;
+        Ok(record)
+    }
+}
+
 fn parse_signed_xid_document(
     xid_document_ur: impl Into<String>,
 ) -> Result<(String, XIDDocument)> {
@@ -1,13 +1,74 @@
-use std::{collections::BTreeMap, fs, path::Path};
+use std::{collections::BTreeMap, fmt, fs, path::Path, str::FromStr};
 
 use anyhow::{Context, Result, bail};
 use bc_components::{ARID, XID};
-use bc_envelope::prelude::{URDecodable, UREncodable};
+use bc_envelope::prelude::{CBOR, Map, URDecodable, UREncodable};
 use serde::{
     Deserialize, Deserializer, Serialize, Serializer, ser::SerializeMap,
 };
 
-use super::{GroupRecord, OwnerRecord, ParticipantRecord};
+use super::{
+    GroupRecord, OwnerRecord, ParticipantRecord,
+    cbor::{optional, record_map},
+    encryption::{
+        Passphrase, decrypt_registry, encrypt_registry, is_encrypted_registry,
+    },
+};
+use crate::cmd::common::write_atomically;
+
+/// On-disk encoding of a registry file.
+///
+/// Both encodings hold the same records under the same field names. JSON is
+/// the default and stays human-editable; CBOR is deterministic dCBOR, more
+/// compact, and stores XIDs and ARIDs as tagged values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RegistryFormat {
+    #[default]
+    Json,
+    Cbor,
+}
+
+impl RegistryFormat {
+    /// The format implied by a registry path: `.cbor` files are CBOR and
+    /// everything else is JSON.
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("cbor") => Self::Cbor,
+            _ => Self::Json,
+        }
+    }
+
+    /// The file name used for a registry in this format when none is given.
+    pub fn default_filename(self) -> &'static str {
+        match self {
+            Self::Json => "registry.json",
+            Self::Cbor => "registry.cbor",
+        }
+    }
+}
+
+impl FromStr for RegistryFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "cbor" => Ok(Self::Cbor),
+            other => {
+                bail!("Unknown registry format '{other}'; use json or cbor")
+            }
+        }
+    }
+}
+
+impl fmt::Display for RegistryFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Cbor => write!(f, "cbor"),
+        }
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Registry {
@@ -17,35 +78,162 @@ pub struct Registry {
     participants: BTreeMap<XID, ParticipantRecord>,
     #[serde(default)]
     groups: BTreeMap<String, GroupRecord>,
+    /// The passphrase `save` encrypts the file under; set by
+    /// `load_with_passphrase` when it found an encrypted registry.
+    #[serde(skip)]
+    passphrase: Option<Passphrase>,
 }
 
 impl Registry {
+    /// An empty registry, for embedding applications that keep it in memory
+    /// and persist it themselves with `to_json`.
+    pub fn new() -> Self { Self::default() }
+
+    /// Builder form of `set_owner`.
+    pub fn with_owner(mut self, owner: OwnerRecord) -> Result<Self> {
+        self.set_owner(owner)?;
+        Ok(self)
+    }
+
+    /// Builder form of `add_participant`.
+    pub fn with_participant(
+        mut self,
+        xid: XID,
+        record: ParticipantRecord,
+    ) -> Result<Self> {
+        self.add_participant(xid, record)?;
+        Ok(self)
+    }
+
+    /// Decodes a registry from its JSON form. Empty input (including
+    /// whitespace only) is an empty registry.
+    pub fn from_json(data: &[u8]) -> Result<Self> {
+        if data.trim_ascii().is_empty() {
+            return Ok(Self::default());
+        }
+        serde_json::from_slice(data).context("Invalid registry JSON")
+    }
+
+    /// Encodes the registry as the pretty-printed JSON that `save` writes.
+    pub fn to_json(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec_pretty(self).context("Failed to encode registry")
+    }
+
+    /// Decodes a registry from its dCBOR form. Empty input is an empty
+    /// registry.
+    pub fn from_cbor(data: &[u8]) -> Result<Self> {
+        if data.is_empty() {
+            return Ok(Self::default());
+        }
+        let cbor =
+            CBOR::try_from_data(data).context("Invalid registry CBOR")?;
+        Self::try_from(cbor)
+    }
+
+    /// Encodes the registry as the dCBOR that `save` writes to `.cbor` files.
+    pub fn to_cbor(&self) -> Result<Vec<u8>> {
+        Ok(CBOR::from(self).to_cbor_data())
+    }
+
+    /// Decodes a registry in the given format.
+    pub fn decode(data: &[u8], format: RegistryFormat) -> Result<Self> {
+        match format {
+            RegistryFormat::Json => Self::from_json(data),
+            RegistryFormat::Cbor => Self::from_cbor(data),
+        }
+    }
+
+    /// Encodes the registry in the given format.
+    pub fn encode(&self, format: RegistryFormat) -> Result<Vec<u8>> {
+        match format {
+            RegistryFormat::Json => self.to_json(),
+            RegistryFormat::Cbor => self.to_cbor(),
+        }
+    }
+
+    /// Loads the registry at `path`, in the format its extension implies
+    /// (see [`RegistryFormat::for_path`]). A missing file is an empty
+    /// registry. An encrypted registry cannot be loaded this way; use
+    /// [`Registry::load_with_passphrase`].
     pub fn load(path: &Path) -> Result<Self> {
+        Self::load_with_passphrase(path, || {
+            bail!("{} is encrypted and needs a passphrase", path.display())
+        })
+    }
+
+    /// Loads the registry at `path` like [`Registry::load`], calling
+    /// `passphrase` only if the file turns out to be encrypted. The registry
+    /// keeps that passphrase, so `save` writes it back encrypted.
+    pub fn load_with_passphrase(
+        path: &Path,
+        passphrase: impl FnOnce() -> Result<Passphrase>,
+    ) -> Result<Self> {
         if !path.exists() {
             return Ok(Self::default());
         }
 
-        let data = fs::read_to_string(path)
+        let format = RegistryFormat::for_path(path);
+        let mut data = fs::read(path)
             .with_context(|| format!("Failed to read {}", path.display()))?;
-        if data.trim().is_empty() {
-            return Ok(Self::default());
+        let mut unlocked_with = None;
+        if is_encrypted_registry(&data) {
+            let passphrase = passphrase()?;
+            data = decrypt_registry(&data, passphrase.as_str()).with_context(
+                || format!("Failed to decrypt {}", path.display()),
+            )?;
+            unlocked_with = Some(passphrase);
         }
+        let mut registry = Self::decode(&data, format).with_context(|| {
+            format!(
+                "Invalid {} in {}",
+                format.to_string().to_uppercase(),
+                path.display()
+            )
+        })?;
+        registry.passphrase = unlocked_with;
+        Ok(registry)
+    }
 
-        serde_json::from_str(&data)
-            .with_context(|| format!("Invalid JSON in {}", path.display()))
+    /// Loads the registry at `path` again, unlocking it with the passphrase
+    /// this registry was loaded with, so a command that rereads its registry
+    /// never asks for the passphrase twice.
+    pub fn reload(&self, path: &Path) -> Result<Self> {
+        Self::load_with_passphrase(path, || {
+            self.passphrase.clone().with_context(|| {
+                format!(
+                    "{} is encrypted and needs a passphrase",
+                    path.display()
+                )
+            })
+        })
     }
 
+    /// Writes the registry to `path` in the format its extension implies,
+    /// encrypted if it has a passphrase. The file is replaced atomically, so
+    /// an interrupted save leaves the previous registry intact.
     pub fn save(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).with_context(|| {
                 format!("Failed to create directory {}", parent.display())
             })?;
         }
-        let json = serde_json::to_string_pretty(self)?;
-        fs::write(path, json)
+        let mut data = self.encode(RegistryFormat::for_path(path))?;
+        if let Some(passphrase) = &self.passphrase {
+            data = encrypt_registry(&data, passphrase.as_str())?;
+        }
+        write_atomically(path, data)
             .with_context(|| format!("Failed to write {}", path.display()))
     }
 
+    /// Whether `save` writes the registry encrypted under a passphrase.
+    pub fn is_encrypted(&self) -> bool { self.passphrase.is_some() }
+
+    /// Chooses the passphrase `save` encrypts the registry under, or with
+    /// `None`, that it is saved unencrypted.
+    pub fn set_passphrase(&mut self, passphrase: Option<Passphrase>) {
+        self.passphrase = passphrase;
+    }
+
     pub fn set_owner(&mut self, owner: OwnerRecord) -> Result<OwnerOutcome> {
         if let Some(name) = owner.pet_name()
             && let Some((_, existing)) = self.participant_by_pet_name(name)
@@ -123,10 +311,21 @@ impl Registry {
         }
     }
 
+    pub fn participants(&self) -> &BTreeMap<XID, ParticipantRecord> {
+        &self.participants
+    }
+
     pub fn participant(&self, xid: &XID) -> Option<&ParticipantRecord> {
         self.participants.get(xid)
     }
 
+    pub fn participant_mut(
+        &mut self,
+        xid: &XID,
+    ) -> Option<&mut ParticipantRecord> {
+        self.participants.get_mut(xid)
+    }
+
     pub fn participant_by_pet_name(
         &self,
         pet_name: &str,
@@ -140,10 +339,49 @@ impl Registry {
         self.groups.get(&group_key(group_id))
     }
 
+    /// Iterate over all recorded groups with their IDs.
+    pub fn groups(&self) -> impl Iterator<Item = (ARID, &GroupRecord)> {
+        self.groups.iter().filter_map(|(key, record)| {
+            ARID::from_ur_string(key).ok().map(|id| (id, record))
+        })
+    }
+
     pub fn group_mut(&mut self, group_id: &ARID) -> Option<&mut GroupRecord> {
         self.groups.get_mut(&group_key(group_id))
     }
 
+    /// Finds the group with the given friendly name.
+    pub fn group_by_name(&self, name: &str) -> Option<(ARID, &GroupRecord)> {
+        self.groups()
+            .find(|(_, record)| record.name() == Some(name))
+    }
+
+    /// Names a recorded group, or removes its name with `None`. Names must be
+    /// unique among the registry's groups and cannot look like a UR, so they
+    /// never shadow a `ur:arid`.
+    pub fn set_group_name(
+        &mut self,
+        group_id: &ARID,
+        name: Option<String>,
+    ) -> Result<()> {
+        if let Some(name) = &name {
+            validate_group_name(name)?;
+            if let Some((other, _)) = self.group_by_name(name)
+                && other != *group_id
+            {
+                bail!(
+                    "Group name '{name}' is already used by group {}",
+                    other.ur_string()
+                );
+            }
+        }
+        let record = self.group_mut(group_id).with_context(|| {
+            format!("Group {} not found", group_id.ur_string())
+        })?;
+        record.set_name(name);
+        Ok(())
+    }
+
     pub fn record_group(
         &mut self,
         group_id: ARID,
@@ -160,6 +398,19 @@ impl Registry {
                 }
                 let mut merged = existing.clone();
                 merged.merge_contributions(record.contributions());
+                merged.merge_identifiers(&record);
+                // A coordinator that signs records the invite as coordinator
+                // and then again as a participant listening for Round 2
+                if merged.listening_at_arid().is_none()
+                    && let Some(arid) = record.listening_at_arid()
+                {
+                    merged.set_listening_at_arid(arid);
+                }
+                if merged.charter_attestation().is_none()
+                    && let Some(attestation) = record.charter_attestation()
+                {
+                    merged.set_charter_attestation(attestation.to_owned());
+                }
                 if merged.verifying_key().is_none()
                     && record.verifying_key().is_some()
                 {
@@ -186,6 +437,61 @@ impl Registry {
     }
 }
 
+impl From<&Registry> for CBOR {
+    fn from(registry: &Registry) -> Self {
+        let mut map = Map::new();
+        if let Some(owner) = &registry.owner {
+            map.insert("owner", owner);
+        }
+        let mut participants = Map::new();
+        for (xid, record) in &registry.participants {
+            participants.insert(*xid, record);
+        }
+        map.insert("participants", participants);
+        let mut groups = Map::new();
+        for (group_id, record) in registry.groups() {
+            groups.insert(group_id, record);
+        }
+        map.insert("groups", groups);
+        map.into()
+    }
+}
+
+impl TryFrom<CBOR> for Registry {
+    type Error = anyhow::Error;
+
+    fn try_from(cbor: CBOR) -> Result<Self> {
+        let map = record_map(cbor, "registry")?;
+        let mut registry =
+            Self { owner: optional(&map, "owner")?, ..Self::default() };
+        if let Some(participants) = optional(&map, "participants")? {
+            for (xid, record) in
+                record_map(participants, "participants")?.iter()
+            {
+                let xid = XID::try_from(xid.clone())
+                    .context("Invalid participant XID in registry CBOR")?;
+                let record = ParticipantRecord::try_from(record.clone())
+                    .with_context(|| {
+                        format!("Invalid participant {}", xid.ur_string())
+                    })?;
+                registry.participants.insert(xid, record);
+            }
+        }
+        if let Some(groups) = optional(&map, "groups")? {
+            for (group_id, record) in record_map(groups, "groups")?.iter() {
+                let group_id = ARID::try_from(group_id.clone())
+                    .context("Invalid group ID in registry CBOR")?;
+                let record = GroupRecord::try_from(record.clone())
+                    .with_context(|| {
+                        format!("Invalid group {}", group_id.ur_string())
+                    })?;
+                registry.groups.insert(group_key(&group_id), record);
+            }
+        }
+        Ok(registry)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AddOutcome {
     Inserted,
@@ -206,6 +512,19 @@ pub enum GroupOutcome {
 
 fn group_key(group_id: &ARID) -> String { group_id.ur_string() }
 
+fn validate_group_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        bail!("Group name cannot be empty");
+    }
+    if name.trim() != name {
+        bail!("Group name '{name}' cannot start or end with whitespace");
+    }
+    if name.to_ascii_lowercase().starts_with("ur:") || name == "-" {
+        bail!("Group name '{name}' would be mistaken for a UR argument");
+    }
+    Ok(())
+}
+
 mod serde_participants_map {
     use super::*;
 
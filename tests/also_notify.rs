@@ -0,0 +1,87 @@
+use std::path::Path;
+
+mod common;
+use bc_components::{ARID, XIDProvider};
+use bc_envelope::prelude::*;
+use common::{owner, registry_file, run_frost, setup_alice_registry};
+use frost_hubert::{
+    cmd::dkg::common::{resolve_also_notify, seal_response},
+    registry::Registry,
+};
+use gstp::{SealedResponse, SealedResponseBehavior};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// Alice's registry, knowing Bob (the coordinator) and Carol.
+fn setup_registry(dir: &Path) -> Registry {
+    setup_alice_registry(dir);
+    Registry::load(&registry_file(dir)).unwrap()
+}
+
+#[test]
+fn also_notify_parties_can_open_the_response() {
+    let temp = TempDir::new().unwrap();
+    let registry = setup_registry(temp.path());
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let carol = owner("carol", None);
+
+    let also_notify =
+        resolve_also_notify(&registry, &["Carol".to_string()]).unwrap();
+    let response =
+        SealedResponse::new_success(ARID::new(), alice.xid_document().clone())
+            .with_result(Envelope::new("ok"));
+    let envelope = seal_response(
+        &response,
+        None,
+        alice.xid_document().inception_private_keys().unwrap(),
+        bob.xid_document(),
+        &also_notify,
+    )
+    .unwrap();
+
+    for reader in [&bob, &carol] {
+        let opened = SealedResponse::try_from_encrypted_envelope(
+            &envelope,
+            None,
+            None,
+            reader.xid_document().inception_private_keys().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(opened.sender().xid(), alice.xid());
+    }
+}
+
+#[test]
+fn also_notify_requires_a_registered_participant() {
+    let temp = TempDir::new().unwrap();
+    let registry = setup_registry(temp.path());
+
+    let err = resolve_also_notify(&registry, &["Dan".to_string()]).unwrap_err();
+    assert!(err.to_string().contains("not found"), "{err}");
+}
+
+#[test]
+fn secret_carrying_responses_refuse_also_notify() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path());
+
+    // Round 2 responses carry secret shares and finalize responses carry
+    // key packages, so neither can be sealed to anyone but the coordinator
+    for step in ["round2", "finalize"] {
+        run_frost(
+            temp.path(),
+            &[
+                "dkg",
+                "participant",
+                step,
+                "--also-notify",
+                "Carol",
+                &ARID::new().ur_string(),
+            ],
+        )
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unexpected argument"));
+    }
+}
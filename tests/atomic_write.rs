@@ -0,0 +1,38 @@
+use std::{ffi::OsString, fs, path::Path};
+
+use frost_hubert::cmd::common::write_atomically;
+use tempfile::TempDir;
+
+fn dir_entries(dir: &Path) -> Vec<OsString> {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect()
+}
+
+#[test]
+fn write_atomically_replaces_existing_file() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("collected_round1.json");
+    fs::write(&path, b"{\"old\":true}").unwrap();
+
+    write_atomically(&path, b"{\"new\":true}").unwrap();
+
+    assert_eq!(fs::read(&path).unwrap(), b"{\"new\":true}");
+    assert_eq!(dir_entries(temp.path()), ["collected_round1.json"]);
+}
+
+#[test]
+fn write_atomically_failure_keeps_previous_contents() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("collected_round1.json");
+    fs::write(&path, b"{\"complete\":true}").unwrap();
+
+    // A trailing `.` names the same file but only resolves as a directory,
+    // so the temporary file is written in full and then the rename over the
+    // target fails.
+    assert!(write_atomically(path.join("."), b"{\"truncated\":").is_err());
+
+    assert_eq!(fs::read(&path).unwrap(), b"{\"complete\":true}");
+    assert_eq!(dir_entries(temp.path()), ["collected_round1.json"]);
+}
@@ -0,0 +1,107 @@
+use std::fs;
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::run_frost;
+use frost_hubert::cmd::check::parse_arid_list;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn arid_list_skips_blank_lines_and_comments() {
+    let first = ARID::new();
+    let second = ARID::new();
+    let text = format!(
+        "# Round 1 responses\n{}\n\n  {}  \n",
+        first.ur_string(),
+        second.ur_string()
+    );
+    assert_eq!(parse_arid_list(&text).unwrap(), vec![first, second]);
+}
+
+#[test]
+fn arid_list_reports_invalid_line() {
+    let text = format!("{}\nnot-an-arid\n", ARID::new().ur_string());
+    let message = format!("{:#}", parse_arid_list(&text).unwrap_err());
+    assert!(message.contains("Invalid ARID on line 2"));
+
+    assert!(parse_arid_list("# nothing here\n").is_err());
+}
+
+#[test]
+fn probe_errors_are_summarized_and_fail() {
+    let temp = TempDir::new().unwrap();
+    let arids = [ARID::new(), ARID::new()];
+    let file = temp.path().join("arids.txt");
+    fs::write(
+        &file,
+        arids
+            .iter()
+            .map(|a| a.ur_string() + "\n")
+            .collect::<String>(),
+    )
+    .unwrap();
+
+    // Nothing listens on port 1, so every probe errors
+    let output = run_frost(
+        temp.path(),
+        &[
+            "check",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--arids",
+            file.to_str().unwrap(),
+            "--concurrency",
+            "2",
+            "--json",
+        ],
+    )
+    .output()
+    .unwrap();
+    assert!(!output.status.success());
+
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["present"], 0);
+    assert_eq!(report["absent"], 0);
+    assert_eq!(report["errors"], 2);
+    let entries = report["arids"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    for (entry, arid) in entries.iter().zip(&arids) {
+        assert_eq!(entry["arid"], arid.ur_string());
+        assert_eq!(entry["status"], "error");
+    }
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("2 of 2 ARIDs are not present")
+    );
+}
+
+#[test]
+fn probe_options_require_arid_file() {
+    let temp = TempDir::new().unwrap();
+    run_frost(temp.path(), &["check", "--json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--arids"));
+
+    let file = temp.path().join("arids.txt");
+    fs::write(&file, ARID::new().ur_string()).unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "check",
+            "--arids",
+            file.to_str().unwrap(),
+            "--concurrency",
+            "0",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("--concurrency"));
+}
@@ -0,0 +1,153 @@
+use std::path::Path;
+
+mod common;
+use bc_components::{ARID, Ed25519PrivateKey, SigningPrivateKey};
+use bc_envelope::prelude::*;
+use common::{owner, registry_file, run_frost, setup_alice_registry};
+use frost_hubert::registry::{GroupParticipant, GroupRecord, Registry};
+use gstp::SealedRequest;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn offset_from_now(seconds: f64) -> Date {
+    Date::from_timestamp(Date::now().timestamp() + seconds)
+}
+
+/// Sets up Alice's registry with a finalized group coordinated by Bob and
+/// returns Bob's signInvite, issued at `issued` and valid until
+/// `valid_until`.
+fn sign_invite(dir: &Path, issued: Date, valid_until: Date) -> String {
+    setup_alice_registry(dir);
+
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let carol = owner("carol", None);
+    let group_id = ARID::new();
+    let session_id = ARID::new();
+
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(bob.xid()),
+        vec![
+            GroupParticipant::new(alice.xid()),
+            GroupParticipant::new(carol.xid()),
+        ],
+    );
+    record.set_verifying_key(
+        SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new())
+            .public_key()
+            .unwrap(),
+    );
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    registry.record_group(group_id, record).unwrap();
+    registry.save(&path).unwrap();
+
+    let mut request = SealedRequest::new(
+        "signInvite",
+        session_id,
+        bob.xid_document().clone(),
+    )
+    .with_parameter("group", group_id)
+    .with_parameter("session", session_id)
+    .with_parameter("target", Envelope::new("Quarterly budget"))
+    .with_parameter("minSigners", 2u64)
+    .with_parameter("validUntil", valid_until)
+    .with_date(issued);
+    for participant in [&alice, &carol] {
+        let encryption_key =
+            participant.xid_document().encryption_key().unwrap();
+        let entry = Envelope::new(participant.xid()).add_assertion(
+            "response_arid",
+            ARID::new()
+                .to_envelope()
+                .encrypt_to_recipient(encryption_key),
+        );
+        request = request.with_parameter("participant", entry);
+    }
+    request
+        .to_envelope(
+            None,
+            Some(bob.xid_document().inception_private_keys().unwrap()),
+            Some(alice.xid_document()),
+        )
+        .unwrap()
+        .ur_string()
+}
+
+#[test]
+fn invite_issued_in_the_future_warns_about_clock_skew() {
+    let temp = TempDir::new().unwrap();
+    let invite = sign_invite(
+        temp.path(),
+        offset_from_now(3600.0),
+        offset_from_now(7200.0),
+    );
+
+    run_frost(temp.path(), &["sign", "participant", "receive", &invite])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("check your system clock"));
+}
+
+#[test]
+fn skew_within_tolerance_is_silent() {
+    let temp = TempDir::new().unwrap();
+    let invite = sign_invite(
+        temp.path(),
+        offset_from_now(60.0),
+        offset_from_now(3600.0),
+    );
+
+    run_frost(temp.path(), &["sign", "participant", "receive", &invite])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("check your system clock").not());
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "participant",
+            "receive",
+            "--clock-skew-tolerance",
+            "10",
+            &invite,
+        ],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains("check your system clock"));
+}
+
+#[test]
+fn verbose_reports_the_clock_delta() {
+    let temp = TempDir::new().unwrap();
+    let invite = sign_invite(temp.path(), Date::now(), offset_from_now(3600.0));
+
+    run_frost(
+        temp.path(),
+        &["sign", "participant", "receive", "--verbose", &invite],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains("signInvite request issued at"))
+    .stderr(predicate::str::contains("local time is"));
+}
+
+#[test]
+fn expired_invite_points_at_the_clock() {
+    let temp = TempDir::new().unwrap();
+    let invite = sign_invite(
+        temp.path(),
+        offset_from_now(-7200.0),
+        offset_from_now(-3600.0),
+    );
+
+    run_frost(temp.path(), &["sign", "participant", "receive", &invite])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("signInvite request expired at"))
+        .stderr(predicate::str::contains("check your system clock"));
+}
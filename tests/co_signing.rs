@@ -0,0 +1,92 @@
+use bc_components::{
+    Ed25519PrivateKey, Signature, SigningPrivateKey, SigningPublicKey,
+};
+use bc_envelope::prelude::*;
+use frost_ed25519::{self as frost, rand_core::OsRng};
+use frost_hubert::cmd::{
+    common::signing_key_from_verifying,
+    sign::common::{attach_signature, signing_message},
+};
+
+fn external_key() -> (SigningPrivateKey, SigningPublicKey) {
+    let private_key = SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new());
+    let public_key = private_key.public_key().unwrap();
+    (private_key, public_key)
+}
+
+fn group_sign(target: &Envelope) -> (Signature, SigningPublicKey) {
+    let signing_key = frost::SigningKey::new(&mut OsRng);
+    let message = signing_message(target).unwrap();
+    let signature = signing_key.sign(OsRng, &message);
+    let bytes: [u8; 64] = signature
+        .serialize()
+        .unwrap()
+        .as_slice()
+        .try_into()
+        .unwrap();
+    let verifying_key =
+        signing_key_from_verifying(&frost::VerifyingKey::from(&signing_key))
+            .unwrap();
+    (Signature::ed25519_from_data(bytes), verifying_key)
+}
+
+#[test]
+fn group_co_signs_subject_signed_envelope() {
+    let document = Envelope::new("Quarterly budget")
+        .add_assertion("department", "Engineering");
+    let (external_private, external_public) = external_key();
+    let pre_signed = document.add_signature(&external_private);
+
+    // Prior signatures are assertions, not part of the subject digest
+    assert_eq!(
+        signing_message(&pre_signed).unwrap(),
+        signing_message(&document).unwrap()
+    );
+
+    let (signature, group_key) = group_sign(&pre_signed);
+    let co_signed =
+        attach_signature(&pre_signed, &signature, &group_key).unwrap();
+
+    assert_eq!(
+        co_signed
+            .assertions_with_predicate(known_values::SIGNED)
+            .len(),
+        2
+    );
+    assert_eq!(co_signed.assertions_with_predicate("department").len(), 1);
+    co_signed.verify_signature_from(&external_public).unwrap();
+    co_signed.verify_signature_from(&group_key).unwrap();
+}
+
+#[test]
+fn group_co_signs_wrapped_signed_envelope() {
+    let (external_private, external_public) = external_key();
+    let pre_signed = Envelope::new("Quarterly budget").sign(&external_private);
+
+    let (signature, group_key) = group_sign(&pre_signed);
+    let co_signed =
+        attach_signature(&pre_signed, &signature, &group_key).unwrap();
+
+    co_signed.verify_signature_from(&external_public).unwrap();
+    co_signed.verify_signature_from(&group_key).unwrap();
+    assert_eq!(
+        co_signed
+            .verify_signature_from(&group_key)
+            .unwrap()
+            .try_unwrap()
+            .unwrap()
+            .extract_subject::<String>()
+            .unwrap(),
+        "Quarterly budget"
+    );
+}
+
+#[test]
+fn co_signing_with_the_wrong_key_fails() {
+    let (external_private, _) = external_key();
+    let pre_signed = Envelope::new("Quarterly budget").sign(&external_private);
+    let (signature, _) = group_sign(&pre_signed);
+    let (_, unrelated_key) = group_sign(&pre_signed);
+
+    assert!(attach_signature(&pre_signed, &signature, &unrelated_key).is_err());
+}
@@ -0,0 +1,88 @@
+use std::{thread, time::Duration};
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::run_frost;
+use frost_hubert::cmd::common::collection_timeout;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+const COLLECT_COMMANDS: [[&str; 3]; 5] = [
+    ["dkg", "coordinator", "round1"],
+    ["dkg", "coordinator", "round2"],
+    ["dkg", "coordinator", "finalize"],
+    ["sign", "coordinator", "round1"],
+    ["sign", "coordinator", "round2"],
+];
+
+fn collect(command: &[&str; 3], extra: &[&str]) -> assert_cmd::Command {
+    let temp = TempDir::new().unwrap();
+    let mut args = command.to_vec();
+    args.extend_from_slice(&["--storage", "server", "--port", "1"]);
+    args.extend_from_slice(extra);
+    let id = ARID::new().ur_string();
+    args.push(&id);
+    run_frost(temp.path(), &args)
+}
+
+#[test]
+fn timeout_is_used_without_deadline() {
+    let timeout = collection_timeout(Some(30), None).unwrap();
+    assert_eq!(timeout.seconds(), Some(30));
+    assert_eq!(collection_timeout(None, None).unwrap().seconds(), None);
+}
+
+#[test]
+fn deadline_becomes_remaining_seconds() {
+    let deadline = Date::with_duration_from_now(Duration::from_secs(600));
+    let timeout = collection_timeout(None, Some(&deadline.to_string()))
+        .unwrap()
+        .seconds()
+        .unwrap();
+    assert!((590..=601).contains(&timeout), "{timeout}");
+}
+
+#[test]
+fn deadline_is_recomputed_for_each_response() {
+    let deadline = Date::with_duration_from_now(Duration::from_secs(3));
+    let timeout =
+        collection_timeout(None, Some(&deadline.to_string())).unwrap();
+    assert!(timeout.seconds().unwrap() <= 3);
+
+    // A sequential collection that spent the whole window on earlier
+    // participants gives the rest a single lookup
+    thread::sleep(Duration::from_secs(4));
+    assert_eq!(timeout.seconds(), Some(0));
+}
+
+#[test]
+fn past_deadline_is_refused() {
+    for command in &COLLECT_COMMANDS {
+        collect(command, &["--deadline", "2020-01-01T00:00:00Z"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("has already passed"));
+    }
+}
+
+#[test]
+fn invalid_deadline_is_refused() {
+    collect(&COLLECT_COMMANDS[0], &["--deadline", "tomorrow"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected RFC 3339"));
+}
+
+#[test]
+fn deadline_conflicts_with_timeout() {
+    for command in &COLLECT_COMMANDS {
+        collect(
+            command,
+            &["--timeout", "60", "--deadline", "2030-01-01T00:00:00Z"],
+        )
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
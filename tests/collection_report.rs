@@ -0,0 +1,351 @@
+use std::{collections::HashMap, fs, path::Path, time::Duration};
+
+mod common;
+use anyhow::anyhow;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{
+    fixture_xid, record_group, registry_file, run_frost, setup_alice_registry,
+};
+use frost_hubert::{
+    cmd::{
+        dkg::coordinator::round2::check_round2_collection,
+        parallel::{CollectionResult, FetchError},
+        report::CollectionReport,
+    },
+    registry::{GroupParticipant, GroupRecord, PendingRequests, Registry},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn setup_registry_with_group(dir: &Path) -> ARID {
+    setup_alice_registry(dir);
+
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(fixture_xid("alice")),
+        ["bob", "carol"]
+            .into_iter()
+            .map(|name| GroupParticipant::new(fixture_xid(name)))
+            .collect(),
+    );
+    let mut pending = PendingRequests::new();
+    for participant in record.participants().to_vec() {
+        pending.add_collect_only(*participant.xid(), ARID::new());
+    }
+    record.set_pending_requests(pending);
+
+    record_group(dir, record)
+}
+
+fn read_report(dir: &Path) -> serde_json::Value {
+    serde_json::from_slice(&fs::read(dir.join("report.json")).unwrap()).unwrap()
+}
+
+#[test]
+fn failed_sequential_collection_still_writes_report() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path());
+
+    // Nothing listens on port 1, so every fetch fails
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "round1",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--report",
+            temp.path().join("report.json").to_str().unwrap(),
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Round 1 collection incomplete"));
+
+    let report = read_report(temp.path());
+    assert_eq!(report["phase"], "dkg_round1");
+    assert_eq!(report["group"], group_id.ur_string());
+    assert!(report["timestamp"].is_string());
+    assert!(report["outputs"].as_array().unwrap().is_empty());
+
+    let participants = report["participants"].as_array().unwrap();
+    assert_eq!(participants.len(), 2);
+    let mut names: Vec<&str> = participants
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["Bob", "Carol"]);
+    for participant in participants {
+        assert_eq!(participant["status"], "error");
+        assert!(participant["reason"].is_string());
+        assert!(participant["duration_ms"].is_u64());
+    }
+}
+
+#[test]
+fn parallel_collection_writes_report() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path());
+
+    // Nothing listens on port 1, so every fetch fails
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "round1",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--parallel",
+            "--report",
+            temp.path().join("report.json").to_str().unwrap(),
+            &group_id.ur_string(),
+        ],
+    )
+    .output()
+    .unwrap();
+
+    let report = read_report(temp.path());
+    assert_eq!(report["phase"], "dkg_round1");
+    assert_eq!(report["outputs"].as_array().unwrap().len(), 1);
+    let participants = report["participants"].as_array().unwrap();
+    assert_eq!(participants.len(), 2);
+    for participant in participants {
+        assert_ne!(participant["status"], "success");
+        assert!(participant["duration_ms"].is_u64());
+    }
+}
+
+#[test]
+fn continue_on_error_saves_partial_round1() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path());
+
+    // Nothing listens on port 1, so every fetch fails
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "round1",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--continue-on-error",
+            "--report",
+            temp.path().join("report.json").to_str().unwrap(),
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .code(3)
+    .stderr(predicate::str::contains(
+        "Round 1 collection incomplete: 2 responses failed; saved the 0 \
+         collected",
+    ));
+
+    let collected = temp
+        .path()
+        .join("group-state")
+        .join(group_id.hex())
+        .join("partial_round1.json");
+    let packages: serde_json::Value =
+        serde_json::from_slice(&fs::read(&collected).unwrap()).unwrap();
+    assert!(packages.as_object().unwrap().is_empty());
+
+    // A partial Round 1 is not a collected one, so the wizard stays on it
+    assert!(!collected.with_file_name("collected_round1.json").exists());
+
+    let report = read_report(temp.path());
+    assert_eq!(report["outputs"].as_array().unwrap().len(), 1);
+
+    // Pending requests are untouched, so collection can simply be rerun
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let group = registry.group(&group_id).unwrap();
+    assert_eq!(group.pending_requests().len(), 2);
+}
+
+#[test]
+fn report_from_collection_records_every_outcome() {
+    let [a, b, c, d] = ["alice", "bob", "carol", "dan"].map(fixture_xid);
+    let collection = CollectionResult {
+        successes: vec![(a, ())],
+        rejections: vec![(b, "Bob: declined".to_string())],
+        errors: vec![(c, "Carol: bad package".to_string())],
+        timeouts: vec![d],
+        cancelled: vec![],
+        durations: HashMap::from([
+            (a, Duration::from_millis(1500)),
+            (d, Duration::from_secs(60)),
+        ]),
+    };
+    let group_id = ARID::new();
+    let report = CollectionReport::from_collection(
+        "dkg_round1",
+        &group_id,
+        &collection,
+        |xid| (*xid == a).then(|| "Alice".to_string()),
+    );
+
+    let json = serde_json::to_value(&report).unwrap();
+    let participants = json["participants"].as_array().unwrap();
+    let statuses: Vec<&str> = participants
+        .iter()
+        .map(|p| p["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["success", "rejected", "error", "timeout"]);
+    assert_eq!(participants[0]["name"], "Alice");
+    assert_eq!(participants[0]["duration_ms"], 1500);
+    assert!(participants[0].get("reason").is_none());
+    assert_eq!(participants[1]["reason"], "Bob: declined");
+    assert!(participants[1].get("name").is_none());
+    assert!(participants[1].get("duration_ms").is_none());
+    assert_eq!(participants[3]["duration_ms"], 60000);
+    assert_eq!(participants[3]["xid"], d.ur_string());
+}
+
+/// Runs a DKG coordinator collect command against unreachable storage,
+/// writing its report into `dir`.
+fn collect_with_report(
+    dir: &Path,
+    command: &str,
+    group_id: &ARID,
+) -> assert_cmd::Command {
+    run_frost(
+        dir,
+        &[
+            "dkg",
+            "coordinator",
+            command,
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--report",
+            dir.join("report.json").to_str().unwrap(),
+            &group_id.ur_string(),
+        ],
+    )
+}
+
+#[test]
+fn failed_round2_collection_writes_report() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path());
+
+    collect_with_report(temp.path(), "round2", &group_id)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Round 2 collection incomplete"));
+
+    let report = read_report(temp.path());
+    assert_eq!(report["phase"], "dkg_round2");
+    assert!(report.get("session").is_none());
+    assert!(report["outputs"].as_array().unwrap().is_empty());
+    let participants = report["participants"].as_array().unwrap();
+    assert_eq!(participants.len(), 2);
+    for participant in participants {
+        assert_eq!(participant["status"], "error");
+        assert!(participant["duration_ms"].is_u64());
+    }
+}
+
+#[test]
+fn failed_finalize_collection_writes_report() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path());
+
+    collect_with_report(temp.path(), "finalize", &group_id)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Finalize collection incomplete"));
+
+    let report = read_report(temp.path());
+    assert_eq!(report["phase"], "dkg_finalize");
+    assert!(report["outputs"].as_array().unwrap().is_empty());
+    assert_eq!(report["participants"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn signing_reports_name_the_session() {
+    let group_id = ARID::new();
+    let session_id = ARID::new();
+
+    let report = CollectionReport::new("sign_round2", &group_id)
+        .with_session(&session_id);
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["session"], session_id.ur_string());
+    // Not aggregated yet, so there is no digest or key to report
+    assert!(json.get("target_digest").is_none());
+    assert!(json.get("verifying_key").is_none());
+
+    let report = CollectionReport::new("dkg_round2", &group_id);
+    let json = serde_json::to_value(&report).unwrap();
+    assert!(json.get("session").is_none());
+}
+
+#[test]
+fn sequential_round2_keeps_rejections_apart_from_errors() {
+    let [alice, bob, carol, dan] =
+        ["alice", "bob", "carol", "dan"].map(fixture_xid);
+    let declined = "Participant reported error: declined".to_string();
+
+    // Outcomes as collect_round2 sees them from fetch_round2_response
+    let mut collection = CollectionResult::new();
+    collection.record(alice, Ok(()), Duration::from_millis(10));
+    collection.record(
+        bob,
+        Err(FetchError::Rejected(declined.clone()).into()),
+        Duration::from_millis(20),
+    );
+    collection.record(
+        carol,
+        Err(FetchError::NotYetAvailable.into()),
+        Duration::from_secs(30),
+    );
+    collection.record(
+        dan,
+        Err(anyhow!("Response group ID does not match")),
+        Duration::from_millis(40),
+    );
+
+    assert_eq!(collection.rejections, [(bob, declined)]);
+    assert_eq!(collection.timeouts, [carol]);
+    assert_eq!(
+        collection.errors,
+        [(dan, "Response group ID does not match".to_string())]
+    );
+    assert_eq!(collection.durations.len(), 4);
+
+    let err = check_round2_collection(&collection).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Round 2 collection incomplete: 3 of 4 responses failed (1 \
+         rejected, 1 errors, 1 timeouts)"
+    );
+
+    let report = CollectionReport::from_collection(
+        "dkg_round2",
+        &ARID::new(),
+        &collection,
+        |_| None,
+    );
+    let json = serde_json::to_value(&report).unwrap();
+    let statuses: Vec<&str> = json["participants"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["success", "rejected", "error", "timeout"]);
+}
@@ -31,6 +31,10 @@ macro_rules! assert_actual_expected {
 use std::{fs, path::Path};
 
 use assert_cmd::{Command, cargo::cargo_bin_cmd};
+use bc_components::{ARID, XID};
+use frost_hubert::registry::{
+    GroupParticipant, GroupRecord, OwnerRecord, ParticipantRecord, Registry,
+};
 
 /// Run the frost binary with the provided args in the given working directory.
 pub fn run_frost(cwd: &Path, args: &[&str]) -> Command {
@@ -52,3 +56,114 @@ pub fn fixture(name: &str) -> String {
 pub fn registry_file(dir: &Path) -> std::path::PathBuf {
     dir.join("registry.json")
 }
+
+/// The XID of the `tests/fixtures/<name>_signed_xid.txt` document.
+pub fn fixture_xid(name: &str) -> XID { participant(name, None).xid() }
+
+/// A participant record for the `tests/fixtures/<name>_signed_xid.txt`
+/// document, with an optional pet name.
+pub fn participant(name: &str, pet_name: Option<&str>) -> ParticipantRecord {
+    ParticipantRecord::from_signed_xid_ur(
+        fixture(&format!("{name}_signed_xid.txt")),
+        pet_name.map(str::to_owned),
+    )
+    .unwrap()
+}
+
+/// An owner record for the `tests/fixtures/<name>_private_xid.txt` document,
+/// with an optional pet name.
+pub fn owner(name: &str, pet_name: Option<&str>) -> OwnerRecord {
+    OwnerRecord::from_signed_xid_ur(
+        fixture(&format!("{name}_private_xid.txt")),
+        pet_name.map(str::to_owned),
+    )
+    .unwrap()
+}
+
+/// Bob and Carol, as `(fixture name, pet name)` pairs for [`setup_registry`].
+pub const BOB_AND_CAROL: &[(&str, &str)] =
+    &[("bob", "Bob"), ("carol", "Carol")];
+
+/// Bob, Carol, and Dan, as `(fixture name, pet name)` pairs for
+/// [`setup_registry`].
+pub const BOB_CAROL_AND_DAN: &[(&str, &str)] =
+    &[("bob", "Bob"), ("carol", "Carol"), ("dan", "Dan")];
+
+/// Makes `owner`'s private XID document the owner of the registry in `dir`.
+pub fn set_owner(dir: &Path, owner: &str, pet_name: Option<&str>) {
+    let xid_document = fixture(&format!("{owner}_private_xid.txt"));
+    let mut args = vec!["registry", "owner", "set", xid_document.as_str()];
+    args.extend(pet_name);
+    run_frost(dir, &args).assert().success();
+}
+
+/// Adds each `(fixture name, pet name)` pair to the registry in `dir` as a
+/// participant.
+pub fn add_participants(dir: &Path, participants: &[(&str, &str)]) {
+    for (key, name) in participants {
+        run_frost(
+            dir,
+            &[
+                "registry",
+                "participant",
+                "add",
+                &fixture(&format!("{key}_signed_xid.txt")),
+                name,
+            ],
+        )
+        .assert()
+        .success();
+    }
+}
+
+/// Makes `owner`'s private XID document the owner of the registry in `dir`
+/// and adds each `(fixture name, pet name)` pair as a participant.
+pub fn setup_registry(dir: &Path, owner: &str, participants: &[(&str, &str)]) {
+    set_owner(dir, owner, None);
+    add_participants(dir, participants);
+}
+
+/// Alice owns the registry in `dir`; Bob and Carol are participants.
+pub fn setup_alice_registry(dir: &Path) {
+    setup_registry(dir, "alice", BOB_AND_CAROL);
+}
+
+/// Records `record` in the registry in `dir` under a new group ID.
+pub fn record_group(dir: &Path, record: GroupRecord) -> ARID {
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    let group_id = ARID::new();
+    registry.record_group(group_id, record).unwrap();
+    registry.save(&path).unwrap();
+    group_id
+}
+
+/// An in-memory registry owned by Alice that also knows Bob, Carol, and Dan,
+/// and a 2-of-3 group of Alice, Bob, and Carol coordinated by Alice. Dan is
+/// known but not a member.
+pub fn mixed_committee() -> (Registry, GroupRecord) {
+    let owner = owner("alice", Some("Alice"));
+    let bob = participant("bob", Some("Bob"));
+    let carol = participant("carol", Some("Carol"));
+    let dan = participant("dan", Some("Dan"));
+    let group = GroupRecord::new(
+        "Mixed committee".to_string(),
+        2,
+        GroupParticipant::new(owner.xid()),
+        vec![
+            GroupParticipant::new(owner.xid()),
+            GroupParticipant::new(bob.xid()),
+            GroupParticipant::new(carol.xid()),
+        ],
+    );
+    let registry = Registry::new()
+        .with_owner(owner)
+        .unwrap()
+        .with_participant(bob.xid(), bob)
+        .unwrap()
+        .with_participant(carol.xid(), carol)
+        .unwrap()
+        .with_participant(dan.xid(), dan)
+        .unwrap();
+    (registry, group)
+}
@@ -0,0 +1,122 @@
+mod common;
+use bc_components::{ARID, JSON, XID, XIDProvider};
+use bc_envelope::prelude::*;
+use common::owner;
+use frost_ed25519::{self as frost, Identifier, rand_core::OsRng};
+use frost_hubert::cmd::storage::{compress_envelope, decompress_envelope};
+use gstp::{SealedRequest, SealedRequestBehavior};
+
+/// A moderate group: 5-of-7.
+const MAX_SIGNERS: u16 = 7;
+const MIN_SIGNERS: u16 = 5;
+
+/// A Round 2 request from Alice as the coordinator builds it for one
+/// participant of the group: the other six participants' Round 1 packages,
+/// each as JSON with a participant assertion, compressed before sealing when
+/// `compress` is set.
+fn round2_request(compress: bool) -> SealedRequest {
+    let alice = owner("alice", None);
+    let mut request =
+        SealedRequest::new("dkgRound2", ARID::new(), alice.xid_document())
+            .with_parameter("group", ARID::new())
+            .with_parameter("responseArid", ARID::new());
+    for index in 2..=MAX_SIGNERS {
+        let identifier = Identifier::try_from(index).unwrap();
+        let (_, package) = frost::keys::dkg::part1(
+            identifier,
+            MAX_SIGNERS,
+            MIN_SIGNERS,
+            OsRng,
+        )
+        .unwrap();
+        let json = JSON::from_data(serde_json::to_vec(&package).unwrap());
+        let xid = XID::from_data_ref([index as u8; 32]).unwrap();
+        let mut package_envelope =
+            Envelope::new(CBOR::from(json)).add_assertion("participant", xid);
+        if compress {
+            package_envelope = compress_envelope(&package_envelope).unwrap();
+        }
+        request = request.with_parameter("round1Package", package_envelope);
+    }
+    request
+}
+
+/// The request signed by Alice and sealed to Bob, exactly as it is posted to
+/// Hubert.
+fn posted_request(request: &SealedRequest) -> Envelope {
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let signer_keys = alice.xid_document().inception_private_keys().unwrap();
+    request
+        .to_envelope_for_recipients(
+            None,
+            Some(signer_keys),
+            &[bob.xid_document()],
+        )
+        .unwrap()
+}
+
+#[test]
+fn compression_round_trips_and_keeps_the_digest() {
+    let envelope = Envelope::new("Round 1 package ".repeat(16))
+        .add_assertion("participant", ARID::new());
+
+    let compressed = compress_envelope(&envelope).unwrap();
+    assert!(compressed.is_compressed());
+    assert_eq!(compressed.digest(), envelope.digest());
+
+    let decompressed = decompress_envelope(compressed).unwrap();
+    assert_eq!(decompressed.to_cbor_data(), envelope.to_cbor_data());
+}
+
+#[test]
+fn uncompressed_envelopes_pass_through_unchanged() {
+    let envelope = Envelope::new("Round 1 package");
+    let passed = decompress_envelope(envelope.clone()).unwrap();
+    assert_eq!(passed.to_cbor_data(), envelope.to_cbor_data());
+}
+
+#[test]
+fn sealed_request_carries_compressed_packages() {
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let posted = posted_request(&round2_request(true));
+
+    let request = SealedRequest::try_from_envelope(
+        &posted,
+        None,
+        None,
+        bob.xid_document().inception_private_keys().unwrap(),
+    )
+    .unwrap();
+    assert_eq!(request.sender().xid(), alice.xid());
+
+    let packages = request.objects_for_parameter("round1Package");
+    assert_eq!(packages.len(), usize::from(MAX_SIGNERS - 1));
+    for package_envelope in packages {
+        assert!(package_envelope.is_compressed());
+        let package_envelope = decompress_envelope(package_envelope).unwrap();
+        let _: XID = package_envelope
+            .extract_object_for_predicate("participant")
+            .unwrap();
+        let json: JSON = package_envelope.extract_subject().unwrap();
+        let _: frost::keys::dkg::round1::Package =
+            serde_json::from_slice(json.as_bytes()).unwrap();
+    }
+}
+
+/// Measures the 5-of-7 Round 2 request as it is posted to Hubert, sealed to
+/// its recipient, with and without `--compress`. Run with `--nocapture` to
+/// see the sizes that the README describes.
+#[test]
+fn round2_request_size_for_a_moderate_group() {
+    let before = posted_request(&round2_request(false)).to_cbor_data().len();
+    let after = posted_request(&round2_request(true)).to_cbor_data().len();
+    eprintln!(
+        "Posted 5-of-7 Round 2 request: {before} -> {after} bytes ({}%)",
+        after * 100 / before
+    );
+    // The Round 1 packages are hex-encoded JSON, so compressing them while
+    // they are still plaintext shrinks the sealed request
+    assert!(after < before, "{before} -> {after} bytes");
+}
@@ -0,0 +1,37 @@
+mod common;
+use common::owner;
+use frost_hubert::{
+    cmd::dkg::common::ensure_owner_is_coordinator,
+    registry::{GroupParticipant, GroupRecord, OwnerRecord},
+};
+
+fn group_coordinated_by(coordinator: &OwnerRecord) -> GroupRecord {
+    GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(coordinator.xid()),
+        vec![
+            GroupParticipant::new(owner("bob", None).xid()),
+            GroupParticipant::new(owner("carol", None).xid()),
+        ],
+    )
+}
+
+#[test]
+fn coordinator_owner_is_accepted() {
+    let alice = owner("alice", None);
+    ensure_owner_is_coordinator(&group_coordinated_by(&alice), &alice).unwrap();
+}
+
+#[test]
+fn non_coordinator_owner_is_rejected() {
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let err = ensure_owner_is_coordinator(&group_coordinated_by(&alice), &bob)
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Only the group's coordinator can run this command"),
+        "{err}"
+    );
+}
@@ -0,0 +1,216 @@
+use std::path::Path;
+
+mod common;
+use bc_components::{ARID, XID};
+use bc_envelope::prelude::*;
+use common::{add_participants, fixture_xid, run_frost, setup_alice_registry};
+use frost_ed25519::{self as frost, Identifier, rand_core::OsRng};
+use frost_hubert::{
+    cmd::dkg::common::check_round1_signers,
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// A group coordinated by Alice, with Alice among the signers or not.
+fn make_group(coordinator_signs: bool) -> GroupRecord {
+    let mut signers = vec!["bob", "carol"];
+    if coordinator_signs {
+        signers.push("alice");
+    }
+    GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(fixture_xid("alice")),
+        signers
+            .into_iter()
+            .map(|name| GroupParticipant::new(fixture_xid(name)))
+            .collect(),
+    )
+}
+
+/// Alice owns the registry; Bob and Carol are participants, and so is Alice
+/// when `alice_signs`.
+fn setup_registry(dir: &Path, alice_signs: bool) {
+    setup_alice_registry(dir);
+    if alice_signs {
+        add_participants(dir, &[("alice", "Alice")]);
+    }
+}
+
+#[test]
+fn coordinator_signs_only_when_listed_as_a_participant() {
+    assert!(make_group(true).coordinator_signs());
+    assert!(!make_group(false).coordinator_signs());
+}
+
+#[test]
+fn invite_listing_coordinator_makes_it_a_signer() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), true);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--min-signers",
+            "2",
+            "Alice",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::starts_with("ur:envelope/"))
+    .stderr(predicate::str::contains(
+        "you are a signer in this group as well as its coordinator",
+    ));
+}
+
+#[test]
+fn external_coordinator_invites_only_the_signers() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), false);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--external-coordinator",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains("you are a signer").not());
+}
+
+#[test]
+fn external_coordinator_checks_threshold_against_signers() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), false);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--external-coordinator",
+            "--min-signers",
+            "3",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "--min-signers cannot exceed participant count",
+    ));
+}
+
+fn packages(names: &[&str]) -> Vec<(XID, frost::keys::dkg::round1::Package)> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let identifier = Identifier::try_from(index as u16 + 1).unwrap();
+            let (_, package) =
+                frost::keys::dkg::part1(identifier, 3, 2, OsRng).unwrap();
+            (fixture_xid(name), package)
+        })
+        .collect()
+}
+
+#[test]
+fn coordinator_only_mode_expects_exactly_the_invited_signers() {
+    let group = make_group(false);
+    assert!(!group.coordinator_signs());
+
+    check_round1_signers(&packages(&["bob", "carol"]), &group).unwrap();
+
+    let err =
+        check_round1_signers(&packages(&["alice", "bob", "carol"]), &group)
+            .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("with the coordinator only coordinating"),
+        "{err}"
+    );
+
+    let err = check_round1_signers(&packages(&["bob"]), &group).unwrap_err();
+    assert!(
+        err.to_string().contains(&format!(
+            "No Round 1 package from invited signer {}",
+            fixture_xid("carol").ur_string()
+        )),
+        "{err}"
+    );
+
+    let err = check_round1_signers(&packages(&["bob", "carol", "dan"]), &group)
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("was not invited as a signer"),
+        "{err}"
+    );
+}
+
+#[test]
+fn signing_coordinator_mode_expects_a_self_response() {
+    let group = make_group(true);
+    assert!(group.coordinator_signs());
+
+    check_round1_signers(&packages(&["alice", "bob", "carol"]), &group)
+        .unwrap();
+
+    let err =
+        check_round1_signers(&packages(&["bob", "carol"]), &group).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("its own Round 1 package was not collected"),
+        "{err}"
+    );
+}
+
+#[test]
+fn signing_coordinator_keeps_its_participant_listening_arid() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("registry.json");
+    let mut registry = Registry::default();
+    let group_id = ARID::new();
+    registry.record_group(group_id, make_group(true)).unwrap();
+
+    // Answering its own invite records the group again, in XID order and
+    // now listening for the Round 2 request
+    let mut participants: Vec<GroupParticipant> = ["alice", "bob", "carol"]
+        .into_iter()
+        .map(|name| GroupParticipant::new(fixture_xid(name)))
+        .collect();
+    participants.sort_by_key(|participant| *participant.xid());
+    let mut answered = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(fixture_xid("alice")),
+        participants,
+    );
+    let listening = ARID::new();
+    answered.set_listening_at_arid(listening);
+    registry.record_group(group_id, answered).unwrap();
+    registry.save(&path).unwrap();
+
+    let registry = Registry::load(&path).unwrap();
+    assert_eq!(
+        registry.group(&group_id).unwrap().listening_at_arid(),
+        Some(listening)
+    );
+}
@@ -0,0 +1,362 @@
+use std::{fs, path::Path};
+
+mod common;
+
+use bc_components::{ARID, Digest, Signature, SigningPublicKey, XID};
+use bc_envelope::prelude::*;
+use bc_rand::{RandomNumberGenerator, make_fake_random_number_generator};
+use common::{
+    fixture_xid, record_group, registry_file, run_frost, setup_alice_registry,
+};
+use frost_ed25519::{self as frost, rand_core::OsRng};
+use frost_hubert::{
+    cmd::{
+        common::{GroupStatePaths, signing_key_from_verifying},
+        sign::common::{DetachedSignature, pre_hashed_target, signing_message},
+    },
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn group_sign(target: &Envelope) -> (Signature, SigningPublicKey) {
+    let signing_key = frost::SigningKey::new(&mut OsRng);
+    let message = signing_message(target).unwrap();
+    let signature = signing_key.sign(OsRng, &message);
+    let bytes: [u8; 64] = signature
+        .serialize()
+        .unwrap()
+        .as_slice()
+        .try_into()
+        .unwrap();
+    let verifying_key =
+        signing_key_from_verifying(&frost::VerifyingKey::from(&signing_key))
+            .unwrap();
+    (Signature::ed25519_from_data(bytes), verifying_key)
+}
+
+fn signers() -> Vec<XID> {
+    let mut rng = make_fake_random_number_generator();
+    (0..2)
+        .map(|_| XID::from_data_ref(rng.random_data(32)).unwrap())
+        .collect()
+}
+
+fn statement_for(target: &Envelope) -> Envelope {
+    DetachedSignature::statement(
+        target,
+        ARID::new(),
+        signers(),
+        Date::from_ymd(2025, 12, 31),
+    )
+    .unwrap()
+}
+
+fn detached_for(target: &Envelope) -> DetachedSignature {
+    let statement = statement_for(target);
+    let (signature, verifying_key) = group_sign(&statement);
+    DetachedSignature::from_statement(&statement, signature, verifying_key)
+        .unwrap()
+}
+
+#[test]
+fn detached_signature_round_trips() {
+    let target = Envelope::new("Hello, world!");
+    let detached = detached_for(&target);
+
+    let envelope = detached.to_envelope().unwrap();
+    let subject: Digest =
+        envelope.try_unwrap().unwrap().extract_subject().unwrap();
+    assert_eq!(subject.data(), target.subject().digest().data());
+
+    let decoded = DetachedSignature::verify(&envelope).unwrap();
+    assert_eq!(decoded, detached);
+    assert!(decoded.covers(&target).unwrap());
+    assert!(!decoded.covers(&Envelope::new("Goodbye")).unwrap());
+}
+
+#[test]
+fn detached_signature_supports_pre_hashed_targets() {
+    let target = pre_hashed_target(&[0xab; 32]);
+    let detached = detached_for(&target);
+
+    let decoded =
+        DetachedSignature::verify(&detached.to_envelope().unwrap()).unwrap();
+    assert!(decoded.pre_hashed);
+    assert!(decoded.covers(&target).unwrap());
+    assert!(
+        !decoded
+            .covers(&Envelope::new(CBOR::to_byte_string([0xab; 32])))
+            .unwrap()
+    );
+}
+
+#[test]
+fn detached_signature_covers_its_metadata() {
+    let target = Envelope::new("Hello, world!");
+    let detached = detached_for(&target);
+
+    let mut more_signers = detached.clone();
+    more_signers.signers.push(fixture_xid("alice"));
+    let mut later = detached.clone();
+    later.date = Date::from_ymd(2026, 1, 1);
+    let mut other_group = detached;
+    other_group.group_id = ARID::new();
+
+    for forged in [more_signers, later, other_group] {
+        let err = DetachedSignature::verify(&forged.to_envelope().unwrap())
+            .unwrap_err();
+        assert!(err.to_string().contains("does not verify"), "{err}");
+    }
+}
+
+#[test]
+fn statement_must_describe_the_session() {
+    let target = Envelope::new("Hello, world!");
+    let group_id = ARID::new();
+    let statement =
+        DetachedSignature::statement(&target, group_id, signers(), Date::now())
+            .unwrap();
+
+    DetachedSignature::check_statement(
+        &statement,
+        &target,
+        &group_id,
+        signers(),
+    )
+    .unwrap();
+
+    let err = DetachedSignature::check_statement(
+        &statement,
+        &Envelope::new("Goodbye"),
+        &group_id,
+        signers(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("does not cover"), "{err}");
+
+    let err = DetachedSignature::check_statement(
+        &statement,
+        &target,
+        &ARID::new(),
+        signers(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("names group"), "{err}");
+
+    let err = DetachedSignature::check_statement(
+        &statement,
+        &target,
+        &group_id,
+        signers().into_iter().take(1),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("signers do not match"), "{err}");
+}
+
+#[test]
+fn detached_signature_rejects_foreign_key() {
+    let target = Envelope::new("Hello, world!");
+    let mut detached = detached_for(&target);
+    detached.verifying_key = group_sign(&statement_for(&target)).1;
+
+    let err = DetachedSignature::verify(&detached.to_envelope().unwrap())
+        .unwrap_err();
+    assert!(err.to_string().contains("does not verify"));
+}
+
+#[test]
+fn sign_verify_checks_detached_signature() {
+    let temp = TempDir::new().unwrap();
+    let target = Envelope::new("Hello, world!");
+    let signature = detached_for(&target).to_envelope().unwrap().ur_string();
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "verify",
+            "--target",
+            &target.ur_string(),
+            &signature,
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("pass --trust-key"));
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "verify",
+            "--trust-key",
+            "--target",
+            &target.ur_string(),
+            &signature,
+        ],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Signature: valid"))
+    .stdout(predicate::str::contains(
+        "Group key: not in registry, trusted by --trust-key",
+    ))
+    .stdout(predicate::str::contains("Target: matches"))
+    .stdout(predicate::str::contains("Signers: "));
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "verify",
+            "--trust-key",
+            "--target",
+            &Envelope::new("Goodbye").ur_string(),
+            &signature,
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("does not cover the target"));
+
+    run_frost(temp.path(), &["sign", "verify", &target.ur_string()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not a detached group signature"));
+}
+
+/// Records the detached signature's group in Alice's registry with
+/// `verifying_key` as its group key.
+fn record_signature_group(
+    dir: &Path,
+    detached: &DetachedSignature,
+    verifying_key: SigningPublicKey,
+) {
+    setup_alice_registry(dir);
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(fixture_xid("alice")),
+        ["alice", "bob"]
+            .into_iter()
+            .map(|name| GroupParticipant::new(fixture_xid(name)))
+            .collect(),
+    );
+    record.set_verifying_key(verifying_key);
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    registry.record_group(detached.group_id, record).unwrap();
+    registry.save(&path).unwrap();
+}
+
+#[test]
+fn sign_verify_accepts_the_registry_group_key() {
+    let temp = TempDir::new().unwrap();
+    let detached = detached_for(&Envelope::new("Hello, world!"));
+    record_signature_group(
+        temp.path(),
+        &detached,
+        detached.verifying_key.clone(),
+    );
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "verify",
+            &detached.to_envelope().unwrap().ur_string(),
+        ],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Group key: matches registry"));
+}
+
+#[test]
+fn sign_verify_rejects_a_key_other_than_the_registry_s() {
+    let temp = TempDir::new().unwrap();
+    let target = Envelope::new("Hello, world!");
+    let detached = detached_for(&target);
+    record_signature_group(
+        temp.path(),
+        &detached,
+        group_sign(&statement_for(&target)).1,
+    );
+
+    // --trust-key only covers groups the registry has no key for
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "verify",
+            "--trust-key",
+            &detached.to_envelope().unwrap().ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("does not match the registry"));
+}
+
+#[test]
+fn round2_detached_requires_a_detached_session() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+    let [alice, bob] = ["alice", "bob"].map(fixture_xid);
+    let group_id = record_group(
+        temp.path(),
+        GroupRecord::new(
+            "Test group".to_string(),
+            2,
+            GroupParticipant::new(alice),
+            vec![GroupParticipant::new(alice), GroupParticipant::new(bob)],
+        ),
+    );
+
+    // Round 1 ran without --detached, so no statement was recorded
+    let session_id = ARID::new();
+    let paths = GroupStatePaths::new(&registry_file(temp.path()));
+    let write = |file: &Path, value: serde_json::Value| {
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, serde_json::to_vec_pretty(&value).unwrap()).unwrap();
+    };
+    write(
+        &paths.start_state_path(&group_id, &session_id),
+        serde_json::json!({
+            "group": group_id.ur_string(),
+            "session_id": session_id.ur_string(),
+            "min_signers": 2,
+            "participants": {},
+            "target": Envelope::new("Hello, world!").ur_string(),
+        }),
+    );
+    write(
+        &paths.commitments_path(&group_id, &session_id),
+        serde_json::json!({
+            "group": group_id.ur_string(),
+            "session": session_id.ur_string(),
+            "commitments": {},
+        }),
+    );
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "round2",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--detached",
+            &session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "needs --detached on `sign coordinator round1`",
+    ));
+}
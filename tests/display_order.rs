@@ -0,0 +1,90 @@
+use std::path::Path;
+
+mod common;
+use bc_components::XID;
+use bc_envelope::prelude::*;
+use common::{fixture, fixture_xid, run_frost};
+use frost_hubert::cmd::common::sort_for_display;
+use tempfile::TempDir;
+
+/// Adds Bob, Carol, and Dan under pet names chosen so that display order
+/// differs from XID order for at least one pair.
+fn add_participants(dir: &Path) -> Vec<(&'static str, XID)> {
+    let names = [("bob", "Zed"), ("carol", "Mia"), ("dan", "Abe")];
+    for (key, name) in names {
+        run_frost(
+            dir,
+            &[
+                "registry",
+                "participant",
+                "add",
+                &fixture(&format!("{key}_signed_xid.txt")),
+                name,
+            ],
+        )
+        .assert()
+        .success();
+    }
+    names
+        .into_iter()
+        .map(|(key, name)| (name, fixture_xid(key)))
+        .collect()
+}
+
+fn list(dir: &Path) -> String {
+    let output = run_frost(dir, &["registry", "participant", "list"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn participant_list_is_sorted_by_pet_name() {
+    let temp = TempDir::new().unwrap();
+    let added = add_participants(temp.path());
+
+    let first = list(temp.path());
+    let listed: Vec<&str> = first
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .collect();
+    assert_eq!(listed, ["Abe", "Mia", "Zed"]);
+    for (name, xid) in added {
+        assert!(first.contains(&format!("{name} {}", xid.ur_string())));
+    }
+
+    assert_eq!(list(temp.path()), first);
+}
+
+#[test]
+fn equal_names_fall_back_to_xid_order() {
+    let mut xids: Vec<XID> = ["bob", "carol", "dan"]
+        .into_iter()
+        .map(fixture_xid)
+        .collect();
+    let mut by_name: Vec<(String, XID)> =
+        xids.iter().map(|xid| ("Same".to_string(), *xid)).collect();
+    sort_for_display(&mut by_name, |(name, xid)| (name.clone(), *xid));
+    xids.sort_by_key(|xid| xid.ur_string());
+    let sorted: Vec<XID> = by_name.into_iter().map(|(_, xid)| xid).collect();
+    assert_eq!(sorted, xids);
+}
+
+#[test]
+fn unnamed_participants_sort_by_xid_ur() {
+    let mut entries: Vec<(Option<&str>, XID)> = vec![
+        (None, fixture_xid("bob")),
+        (Some("Carol"), fixture_xid("carol")),
+        (None, fixture_xid("dan")),
+    ];
+    sort_for_display(&mut entries, |(name, xid)| {
+        (
+            name.map(str::to_owned).unwrap_or_else(|| xid.ur_string()),
+            *xid,
+        )
+    });
+    // "Carol" sorts before any "ur:xid/..." string.
+    assert_eq!(entries[0].0, Some("Carol"));
+    assert!(entries[1].1.ur_string() < entries[2].1.ur_string());
+}
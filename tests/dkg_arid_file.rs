@@ -0,0 +1,79 @@
+use std::fs;
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{registry_file, run_frost, setup_alice_registry};
+use frost_hubert::registry::Registry;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn arid_file_records_response_arids_before_posting() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+    let arid_file = temp.path().join("arids.json");
+
+    // Nothing listens on port 1, so posting fails after the file is written
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--storage",
+            "server",
+            "--host",
+            "127.0.0.1",
+            "--port",
+            "1",
+            "--arid-file",
+            arid_file.to_str().unwrap(),
+            "Bob",
+            "Carol",
+        ],
+    )
+    .assert()
+    .failure();
+
+    let recorded: serde_json::Value =
+        serde_json::from_slice(&fs::read(&arid_file).unwrap()).unwrap();
+    ARID::from_ur_string(recorded["invite_arid"].as_str().unwrap()).unwrap();
+    let group_id =
+        ARID::from_ur_string(recorded["group"].as_str().unwrap()).unwrap();
+
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let group = registry.group(&group_id).unwrap();
+    let participants = recorded["participants"].as_object().unwrap();
+    assert_eq!(participants.len(), 2);
+    for (xid, arid) in group.pending_requests().iter_collect() {
+        let recorded_arid = participants[&xid.ur_string()].as_str().unwrap();
+        assert_eq!(ARID::from_ur_string(recorded_arid).unwrap(), *arid);
+        assert!(group.participants().iter().any(|p| p.xid() == xid));
+    }
+}
+
+#[test]
+fn arid_file_requires_storage() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--arid-file",
+            "arids.json",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "--arid-file requires Hubert storage",
+    ));
+    assert!(!temp.path().join("arids.json").exists());
+}
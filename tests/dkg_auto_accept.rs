@@ -0,0 +1,160 @@
+mod common;
+use common::{run_frost, setup_registry};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// Alice invites Bob, Carol, and Dan with the given threshold; returns the
+/// registry directories of both and the sealed invite.
+fn invite_bob(min_signers: &str) -> (TempDir, TempDir, String) {
+    let alice = TempDir::new().unwrap();
+    setup_registry(
+        alice.path(),
+        "alice",
+        &[("bob", "Bob"), ("carol", "Carol"), ("dan", "Dan")],
+    );
+    let output = run_frost(
+        alice.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--min-signers",
+            min_signers,
+            "Bob",
+            "Carol",
+            "Dan",
+        ],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let invite = String::from_utf8(output.stdout).unwrap().trim().to_owned();
+
+    let bob = TempDir::new().unwrap();
+    setup_registry(
+        bob.path(),
+        "bob",
+        &[("alice", "Alice"), ("carol", "Carol"), ("dan", "Dan")],
+    );
+    (alice, bob, invite)
+}
+
+#[test]
+fn auto_accept_accepts_invite_within_policy() {
+    let (_alice, bob, invite) = invite_bob("2");
+    run_frost(
+        bob.path(),
+        &[
+            "dkg",
+            "participant",
+            "round1",
+            "--auto-accept",
+            "--preview",
+            &invite,
+        ],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains("Auto-rejecting").not());
+}
+
+#[test]
+fn auto_accept_rejects_too_many_participants() {
+    let (_alice, bob, invite) = invite_bob("2");
+    run_frost(
+        bob.path(),
+        &[
+            "dkg",
+            "participant",
+            "round1",
+            "--auto-accept",
+            "--max-participants",
+            "2",
+            "--preview",
+            &invite,
+        ],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains(
+        "Auto-rejecting invite: Invite lists 3 participants, more than the \
+         accepted maximum of 2",
+    ));
+}
+
+#[test]
+fn auto_accept_rejects_threshold_out_of_range() {
+    let (_alice, bob, invite) = invite_bob("3");
+    run_frost(
+        bob.path(),
+        &[
+            "dkg",
+            "participant",
+            "round1",
+            "--auto-accept",
+            "--max-threshold",
+            "2",
+            "--preview",
+            &invite,
+        ],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains(
+        "Min signers 3 is above the accepted maximum 2",
+    ));
+
+    run_frost(
+        bob.path(),
+        &[
+            "dkg",
+            "participant",
+            "round1",
+            "--auto-accept",
+            "--min-threshold",
+            "4",
+            "--preview",
+            &invite,
+        ],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains(
+        "Min signers 3 is below the accepted minimum 4",
+    ));
+}
+
+#[test]
+fn policy_options_require_auto_accept() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "participant",
+            "round1",
+            "--max-threshold",
+            "2",
+            "ur:envelope/placeholder",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("--auto-accept"));
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "participant",
+            "round1",
+            "--auto-accept",
+            "--reject",
+            "busy",
+            "ur:envelope/placeholder",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
@@ -0,0 +1,57 @@
+mod common;
+use common::{fixture, run_frost};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn finalize_all_reports_when_no_groups_are_waiting() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &["registry", "owner", "set", &fixture("bob_private_xid.txt")],
+    )
+    .assert()
+    .success();
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "participant",
+            "finalize",
+            "--all",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+        ],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains(
+        "No groups are awaiting a finalize request.",
+    ));
+}
+
+#[test]
+fn finalize_requires_group_or_all() {
+    let temp = TempDir::new().unwrap();
+    run_frost(temp.path(), &["dkg", "participant", "finalize"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("<GROUP_ID>"));
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "participant",
+            "finalize",
+            "--all",
+            "ur:arid/placeholder",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
@@ -0,0 +1,98 @@
+use std::fs;
+
+use bc_components::XID;
+use bc_envelope::prelude::*;
+use frost_ed25519::{self as frost, keys::IdentifierList, rand_core::OsRng};
+use frost_hubert::cmd::{
+    common::signing_key_from_verifying,
+    dkg::coordinator::finalize::{
+        CollectedFinalize, FinalizeResponseData, load_collected_finalize,
+        merge_collected_finalize,
+    },
+};
+use tempfile::TempDir;
+
+/// Finalize packages for a dealt 2-of-3 group, one per participant XID
+/// `[seed + i; 32]`.
+fn dealt_group(seed: u8) -> Vec<(XID, FinalizeResponseData)> {
+    let (secret_shares, public_key_package) =
+        frost::keys::generate_with_dealer(3, 2, IdentifierList::Default, OsRng)
+            .unwrap();
+    secret_shares
+        .into_values()
+        .enumerate()
+        .map(|(i, share)| {
+            (
+                XID::from_data([seed + i as u8; 32]),
+                FinalizeResponseData {
+                    key_package: frost::keys::KeyPackage::try_from(share)
+                        .unwrap(),
+                    public_key_package: public_key_package.clone(),
+                },
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn merge_combines_passes_and_dedups_by_participant() {
+    let group = dealt_group(1);
+    let mut existing: CollectedFinalize = group[..2].iter().cloned().collect();
+
+    let key = merge_collected_finalize(&mut existing, group[1..].to_vec())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(existing.len(), 3);
+    assert_eq!(
+        key,
+        signing_key_from_verifying(
+            group[0].1.public_key_package.verifying_key()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn merge_rejects_conflicting_verifying_keys() {
+    let group = dealt_group(1);
+    let other = dealt_group(10);
+    let mut existing: CollectedFinalize = group[..1].iter().cloned().collect();
+
+    let err = merge_collected_finalize(&mut existing, other[..1].to_vec())
+        .unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains("disagree on the group verifying key")
+    );
+    // A failed merge leaves the earlier collection untouched
+    assert_eq!(existing.len(), 1);
+    assert!(existing.contains_key(&group[0].0));
+}
+
+#[test]
+fn load_collected_finalize_reads_saved_entries() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("collected_finalize.json");
+    assert!(load_collected_finalize(&path).unwrap().is_empty());
+
+    let group = dealt_group(1);
+    let mut root = serde_json::Map::new();
+    for (xid, data) in &group {
+        root.insert(
+            xid.ur_string(),
+            serde_json::json!({
+                "key_package": data.key_package,
+                "public_key_package": data.public_key_package,
+            }),
+        );
+    }
+    fs::write(&path, serde_json::to_vec_pretty(&root).unwrap()).unwrap();
+
+    let loaded = load_collected_finalize(&path).unwrap();
+    assert_eq!(loaded.len(), 3);
+    for (xid, data) in &group {
+        assert_eq!(loaded[xid].key_package, data.key_package);
+    }
+}
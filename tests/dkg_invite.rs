@@ -0,0 +1,333 @@
+use std::fs;
+
+mod common;
+use common::{BOB_CAROL_AND_DAN, fixture, run_frost, setup_registry};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn invite_reads_participants_file() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", BOB_CAROL_AND_DAN);
+
+    let participants_path = temp.path().join("committee.txt");
+    fs::write(
+        &participants_path,
+        "# Standing committee\nBob\n\n  Carol  \n# Dan is on leave\n",
+    )
+    .unwrap();
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--participants-file",
+            participants_path.to_str().unwrap(),
+        ],
+    )
+    .assert()
+    .success();
+}
+
+#[test]
+fn invite_rejects_duplicates_across_participants_file() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", BOB_CAROL_AND_DAN);
+
+    let participants_path = temp.path().join("committee.txt");
+    fs::write(&participants_path, "Bob\nCarol\n").unwrap();
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--participants-file",
+            participants_path.to_str().unwrap(),
+            "Bob",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Duplicate participant argument: Bob",
+    ));
+}
+
+#[test]
+fn inspect_displays_preview_invite() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", BOB_CAROL_AND_DAN);
+
+    let output = run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--min-signers",
+            "2",
+            "--charter",
+            "Quarterly budget",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let preview = String::from_utf8(output.stdout).unwrap();
+
+    run_frost(
+        temp.path(),
+        &["dkg", "coordinator", "inspect", preview.trim()],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Sealed: no"))
+    .stdout(predicate::str::contains("Charter: Quarterly budget"))
+    .stdout(predicate::str::contains("Min signers: 2"))
+    .stdout(predicate::str::contains("Participants: "))
+    .stdout(predicate::str::contains("Bob"))
+    .stdout(predicate::str::contains("Carol"));
+}
+
+#[test]
+fn inspect_shows_sealed_invite_metadata() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", BOB_CAROL_AND_DAN);
+
+    let output = run_frost(
+        temp.path(),
+        &["dkg", "coordinator", "invite", "Bob", "Carol", "Dan"],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let sealed = String::from_utf8(output.stdout).unwrap();
+
+    run_frost(
+        temp.path(),
+        &["dkg", "coordinator", "inspect", sealed.trim()],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Sealed: yes"))
+    .stdout(predicate::str::contains("Recipients: 3"));
+}
+
+#[test]
+fn invite_external_coordinator_rejects_coordinator_as_participant() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", BOB_CAROL_AND_DAN);
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "add",
+            &fixture("alice_signed_xid.txt"),
+            "Alice",
+        ],
+    )
+    .assert()
+    .success();
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--external-coordinator",
+            "Alice",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "The coordinator cannot also be a DKG participant: Alice",
+    ));
+}
+
+#[test]
+fn invite_enforces_max_participants() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", BOB_CAROL_AND_DAN);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--max-participants",
+            "2",
+            "Bob",
+            "Carol",
+            "Dan",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Invite lists 3 participants, more than the limit of 2",
+    ));
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--max-participants",
+            "3",
+            "Bob",
+            "Carol",
+            "Dan",
+        ],
+    )
+    .assert()
+    .success();
+}
+
+#[test]
+fn invite_rejects_max_participants_outside_identifier_range() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", BOB_CAROL_AND_DAN);
+
+    for value in ["1", "65536"] {
+        run_frost(
+            temp.path(),
+            &[
+                "dkg",
+                "coordinator",
+                "invite",
+                "--preview",
+                "--max-participants",
+                value,
+                "Bob",
+                "Carol",
+            ],
+        )
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--max-participants"));
+    }
+}
+
+#[test]
+fn inspect_shows_not_before_bound() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", BOB_CAROL_AND_DAN);
+
+    let output = run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--not-before",
+            "2099-01-01T12:00:00Z",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let preview = String::from_utf8(output.stdout).unwrap();
+
+    run_frost(
+        temp.path(),
+        &["dkg", "coordinator", "inspect", preview.trim()],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Valid from: 2099-01-01"))
+    .stdout(predicate::str::contains("Valid until: 2099-01-01"));
+}
+
+#[test]
+fn invite_rejects_not_before_in_the_past() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", BOB_CAROL_AND_DAN);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--not-before",
+            "2001-01-01T00:00:00Z",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "--not-before must be in the future",
+    ));
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--not-before",
+            "next tuesday",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("--not-before"));
+}
+
+#[test]
+fn invite_review_prints_summary() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", BOB_CAROL_AND_DAN);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--review",
+            "--min-signers",
+            "2",
+            "--charter",
+            "Treasury",
+            "Carol",
+            "Bob",
+            "Dan",
+        ],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains("Participants: Bob, Carol, Dan"))
+    .stderr(predicate::str::contains("Min signers: 2"))
+    .stderr(predicate::str::contains("Charter: Treasury"))
+    .stderr(predicate::str::contains("Valid until: "));
+}
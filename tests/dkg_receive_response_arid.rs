@@ -0,0 +1,94 @@
+use std::path::Path;
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{fixture, owner, participant, run_frost, setup_registry};
+use gstp::SealedRequest;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// Builds Alice's dkgInvite to Bob and Carol by hand, the way
+/// `DkgInvite::to_request` lays it out, with the given response ARIDs. The
+/// coordinator-side constructor refuses to reuse an ARID, so this is the
+/// only way to see what a participant does with one.
+fn invite(response_arids: [ARID; 2]) -> String {
+    let alice = owner("alice", None);
+    let now = Date::now();
+    let valid_until = Date::from_timestamp(now.timestamp() + 3600.0);
+
+    let mut request =
+        SealedRequest::new("dkgInvite", ARID::new(), alice.xid_document())
+            .with_parameter("group", ARID::new())
+            .with_parameter("minSigners", 2u64)
+            .with_parameter("charter", "Test charter".to_string())
+            .with_date(now)
+            .with_parameter("validUntil", valid_until);
+    for (name, response_arid) in
+        ["bob", "carol"].into_iter().zip(response_arids)
+    {
+        let document = Envelope::from_ur_string(fixture(&format!(
+            "{name}_signed_xid.txt"
+        )))
+        .unwrap();
+        let recipient = participant(name, None);
+        let encryption_key = recipient.xid_document().encryption_key().unwrap();
+        let entry = document.wrap().add_assertion(
+            "response_arid",
+            response_arid
+                .to_envelope()
+                .encrypt_to_recipient(encryption_key),
+        );
+        request = request.with_parameter("participant", entry);
+    }
+    request
+        .to_envelope_for_recipients(
+            Some(valid_until),
+            Some(alice.xid_document().inception_private_keys().unwrap()),
+            &[
+                participant("bob", None).xid_document(),
+                participant("carol", None).xid_document(),
+            ],
+        )
+        .unwrap()
+        .ur_string()
+}
+
+fn setup_bob_registry(dir: &Path) {
+    setup_registry(dir, "bob", &[("alice", "Alice"), ("carol", "Carol")]);
+}
+
+#[test]
+fn receive_rejects_a_response_arid_shared_across_participants() {
+    let temp = TempDir::new().unwrap();
+    setup_bob_registry(temp.path());
+    let shared = ARID::new();
+
+    run_frost(
+        temp.path(),
+        &["dkg", "participant", "receive", &invite([shared, shared])],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Invite reuses a response ARID across participants",
+    ));
+}
+
+#[test]
+fn receive_accepts_distinct_response_arids() {
+    let temp = TempDir::new().unwrap();
+    setup_bob_registry(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "participant",
+            "receive",
+            &invite([ARID::new(), ARID::new()]),
+        ],
+    )
+    .assert()
+    .success();
+}
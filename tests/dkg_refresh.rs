@@ -0,0 +1,448 @@
+use std::path::Path;
+
+mod common;
+use bc_components::{ARID, Ed25519PrivateKey, SigningPrivateKey};
+use bc_envelope::prelude::*;
+use common::{
+    BOB_CAROL_AND_DAN, fixture, fixture_xid, record_group, registry_file,
+    run_frost, setup_registry,
+};
+use frost_ed25519::{self as frost, Identifier, rand_core::OsRng};
+use frost_hubert::{
+    cmd::dkg::common::{
+        check_group_round1_commitments, check_refreshed_verifying_key,
+        refresh_identifiers, share_repair,
+    },
+    dkg::{repair_deltas, repair_sigma, repaired_key_package},
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn make_group(min_signers: usize, finalized: bool) -> GroupRecord {
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        min_signers,
+        GroupParticipant::new(fixture_xid("alice")),
+        ["bob", "carol", "dan"]
+            .into_iter()
+            .map(|name| GroupParticipant::new(fixture_xid(name)))
+            .collect(),
+    );
+    record.set_identifiers(record.identifiers().unwrap());
+    if finalized {
+        record.set_verifying_key(
+            SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new())
+                .public_key()
+                .unwrap(),
+        );
+    }
+    record
+}
+
+/// Sets up Alice's registry, coordinating a group of Bob, Carol, and Dan.
+fn setup_registry_with_group(
+    dir: &Path,
+    min_signers: usize,
+    finalized: bool,
+) -> ARID {
+    setup_registry(dir, "alice", BOB_CAROL_AND_DAN);
+
+    record_group(dir, make_group(min_signers, finalized))
+}
+
+#[test]
+fn refresh_requires_finalized_group() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2, false);
+
+    run_frost(
+        temp.path(),
+        &["dkg", "coordinator", "refresh", &group_id.ur_string()],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("has not finished its DKG"));
+}
+
+#[test]
+fn refresh_preview_prints_an_invite() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2, true);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "refresh",
+            "--preview",
+            "--remove-participant",
+            "Dan",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::starts_with("ur:envelope/"));
+}
+
+#[test]
+fn refresh_cannot_remove_below_min_signers() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 3, true);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "refresh",
+            "--preview",
+            "--remove-participant",
+            "Dan",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Only 2 participants would remain, fewer than the group's 3 min \
+         signers",
+    ));
+}
+
+#[test]
+fn refresh_rejects_removing_a_non_member() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2, true);
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "add",
+            &fixture("alice_signed_xid.txt"),
+            "Alice",
+        ],
+    )
+    .assert()
+    .success();
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "refresh",
+            "--preview",
+            "--remove-participant",
+            "Alice",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Alice is not a participant in the group being refreshed",
+    ));
+}
+
+#[test]
+fn refresh_requires_confirmation() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2, true);
+
+    run_frost(
+        temp.path(),
+        &[
+            "--no-input",
+            "dkg",
+            "coordinator",
+            "refresh",
+            "--storage",
+            "server",
+            "--host",
+            "127.0.0.1",
+            "--port",
+            "1",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Confirmation required"));
+
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    assert_eq!(registry.groups().count(), 1);
+}
+
+#[test]
+fn refresh_keeps_original_identifiers() {
+    let original = make_group(2, true);
+    let expected = original.identifiers().unwrap();
+    let kept = vec![fixture_xid("bob"), fixture_xid("dan")];
+
+    let identifiers = refresh_identifiers(&original, &kept).unwrap();
+    assert_eq!(identifiers.len(), 2);
+    for xid in &kept {
+        assert_eq!(identifiers[xid], expected[xid]);
+    }
+
+    let err =
+        refresh_identifiers(&original, &[fixture_xid("alice")]).unwrap_err();
+    assert!(err.to_string().contains("is not a participant"));
+}
+
+#[test]
+fn refresh_round1_packages_commit_to_one_fewer_coefficient() {
+    let mut record = make_group(2, false);
+    let refresh_package = |index: u16| {
+        let identifier = Identifier::try_from(index).unwrap();
+        frost::keys::refresh::refresh_dkg_part1(identifier, 3, 2, OsRng)
+            .unwrap()
+            .1
+    };
+    let packages = vec![
+        (fixture_xid("bob"), refresh_package(1)),
+        (fixture_xid("carol"), refresh_package(2)),
+        (fixture_xid("dan"), refresh_package(3)),
+    ];
+
+    // Ordinary DKG rounds expect a full-length commitment
+    check_group_round1_commitments(&packages, &record).unwrap_err();
+
+    record.set_refresh_of(ARID::new());
+    check_group_round1_commitments(&packages, &record).unwrap();
+}
+
+#[test]
+fn refreshed_verifying_key_must_match() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2, true);
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let original_key = registry
+        .group(&group_id)
+        .unwrap()
+        .verifying_key()
+        .unwrap()
+        .clone();
+
+    let mut refresh = make_group(2, false);
+    refresh.set_refresh_of(group_id);
+    check_refreshed_verifying_key(&registry, &refresh, &original_key).unwrap();
+
+    let other_key = SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new())
+        .public_key()
+        .unwrap();
+    let err = check_refreshed_verifying_key(&registry, &refresh, &other_key)
+        .unwrap_err();
+    assert!(err.to_string().contains("must not be used"));
+}
+
+#[test]
+fn refresh_of_survives_a_registry_round_trip() {
+    let mut record = make_group(2, true);
+    let original_id = ARID::new();
+    record.set_refresh_of(original_id);
+
+    let json = serde_json::to_string(&record).unwrap();
+    assert!(json.contains("refresh_of"));
+    let decoded: GroupRecord = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.refresh_of(), Some(original_id));
+
+    let plain = serde_json::to_string(&make_group(2, true)).unwrap();
+    assert!(!plain.contains("refresh_of"));
+}
+
+/// Adds Alice to her own registry as a participant, so she can stand in for
+/// a participant outside the group.
+fn add_alice(dir: &Path) {
+    run_frost(
+        dir,
+        &[
+            "registry",
+            "participant",
+            "add",
+            &fixture("alice_signed_xid.txt"),
+            "Alice",
+        ],
+    )
+    .assert()
+    .success();
+}
+
+#[test]
+fn refresh_replace_participant_requires_a_replacement() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2, true);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "refresh",
+            "--preview",
+            "--replace-participant",
+            "Dan",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("--replacement"));
+}
+
+#[test]
+fn refresh_replacement_must_be_new_to_the_group() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2, true);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "refresh",
+            "--preview",
+            "--replace-participant",
+            "Dan",
+            "--replacement",
+            "Bob",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "the replacement must be a new participant",
+    ));
+}
+
+#[test]
+fn refresh_cannot_remove_and_replace_the_same_participant() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2, true);
+    add_alice(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "refresh",
+            "--preview",
+            "--remove-participant",
+            "Dan",
+            "--replace-participant",
+            "Dan",
+            "--replacement",
+            "Alice",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Dan cannot be both removed and replaced",
+    ));
+}
+
+#[test]
+fn refresh_preview_with_a_replacement_prints_an_invite() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2, true);
+    add_alice(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "refresh",
+            "--preview",
+            "--replace-participant",
+            "Dan",
+            "--replacement",
+            "Alice",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::starts_with("ur:envelope/"));
+}
+
+#[test]
+fn share_repair_gives_the_replacement_the_lost_identifier() {
+    let original = make_group(2, true);
+    let expected = original.identifiers().unwrap();
+    let (bob, carol, dan, alice) = (
+        fixture_xid("bob"),
+        fixture_xid("carol"),
+        fixture_xid("dan"),
+        fixture_xid("alice"),
+    );
+
+    let repair =
+        share_repair(&original, &[bob, carol, alice], alice, dan).unwrap();
+    assert_eq!(repair.identifier().unwrap(), expected[&dan]);
+    assert_eq!(repair.identifiers[&bob], expected[&bob]);
+    assert_eq!(repair.helpers().len(), 2);
+
+    // The lost participant takes no part, and enough helpers must remain
+    share_repair(&original, &[bob, carol, dan, alice], alice, dan).unwrap_err();
+    let err = share_repair(&original, &[bob, alice], alice, dan).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("fewer than the group's 2 min signers")
+    );
+}
+
+#[test]
+fn repaired_share_matches_the_lost_one() {
+    let (shares, public_key_package) = frost::keys::generate_with_dealer(
+        3,
+        2,
+        frost::keys::IdentifierList::Default,
+        OsRng,
+    )
+    .unwrap();
+    let identifiers: Vec<Identifier> = shares.keys().copied().collect();
+    let (helpers, lost) = (&identifiers[..2], identifiers[2]);
+    let commitment = shares[&lost].commitment();
+
+    let deltas: Vec<_> = helpers
+        .iter()
+        .map(|helper| {
+            let key_package =
+                frost::keys::KeyPackage::try_from(shares[helper].clone())
+                    .unwrap();
+            repair_deltas(helpers, &key_package, commitment, lost).unwrap()
+        })
+        .collect();
+    let sigmas: Vec<_> = helpers
+        .iter()
+        .map(|helper| {
+            let received: Vec<_> =
+                deltas.iter().map(|deltas| deltas[helper]).collect();
+            repair_sigma(&received)
+        })
+        .collect();
+
+    let key_package =
+        repaired_key_package(&sigmas, lost, commitment, &public_key_package, 2)
+            .unwrap();
+    assert_eq!(key_package.signing_share(), shares[&lost].signing_share());
+
+    let err = repaired_key_package(
+        &sigmas[..1],
+        lost,
+        commitment,
+        &public_key_package,
+        2,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("does not match"));
+}
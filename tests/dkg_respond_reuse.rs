@@ -0,0 +1,91 @@
+use std::{fs, path::Path};
+
+mod common;
+use common::{registry_file, run_frost, setup_registry};
+use frost_hubert::cmd::common::GroupStatePaths;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// Alice invites Bob and Carol; returns Bob's registry directory and the
+/// sealed invite.
+fn invite_bob() -> (TempDir, String) {
+    let alice = TempDir::new().unwrap();
+    setup_registry(
+        alice.path(),
+        "alice",
+        &[("bob", "Bob"), ("carol", "Carol")],
+    );
+    let output = run_frost(
+        alice.path(),
+        &["dkg", "coordinator", "invite", "Bob", "Carol"],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let invite = String::from_utf8(output.stdout).unwrap().trim().to_owned();
+
+    let bob = TempDir::new().unwrap();
+    setup_registry(
+        bob.path(),
+        "bob",
+        &[("alice", "Alice"), ("carol", "Carol")],
+    );
+    (bob, invite)
+}
+
+/// Answers the invite against an unreachable server. Round 1 state is
+/// persisted before the upload, which then fails.
+fn respond(dir: &Path, invite: &str, extra: &[&str]) -> assert_cmd::Command {
+    let mut args = vec!["dkg", "participant", "round1", "--storage", "server"];
+    args.extend_from_slice(&["--port", "1"]);
+    args.extend_from_slice(extra);
+    args.push(invite);
+    run_frost(dir, &args)
+}
+
+/// Reads every persisted round1_package.json under the group-state tree.
+fn round1_packages(dir: &Path) -> Vec<String> {
+    let paths = GroupStatePaths::new(&registry_file(dir));
+    let mut packages = Vec::new();
+    for group in fs::read_dir(paths.root()).unwrap() {
+        let path = group.unwrap().path().join("round1_package.json");
+        if path.exists() {
+            packages.push(fs::read_to_string(path).unwrap());
+        }
+    }
+    packages
+}
+
+#[test]
+fn second_respond_reuses_original_package() {
+    let (bob, invite) = invite_bob();
+
+    respond(bob.path(), &invite, &[])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Re-sending").not());
+    let original = round1_packages(bob.path());
+    assert_eq!(original.len(), 1);
+
+    respond(bob.path(), &invite, &[])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Re-sending the Round 1 package"));
+    assert_eq!(round1_packages(bob.path()), original);
+}
+
+#[test]
+fn force_generates_new_package() {
+    let (bob, invite) = invite_bob();
+
+    respond(bob.path(), &invite, &[]).assert().failure();
+    let original = round1_packages(bob.path());
+
+    respond(bob.path(), &invite, &["--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Re-sending").not());
+    let regenerated = round1_packages(bob.path());
+    assert_eq!(regenerated.len(), 1);
+    assert_ne!(regenerated, original);
+}
@@ -0,0 +1,187 @@
+use std::{fs, path::Path};
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{fixture, registry_file, run_frost, setup_registry};
+use frost_hubert::registry::Registry;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// Alice invites Bob and Carol; returns Bob's registry directory and the
+/// sealed invite.
+fn invite_bob() -> (TempDir, String) {
+    let alice = TempDir::new().unwrap();
+    setup_registry(
+        alice.path(),
+        "alice",
+        &[("bob", "Bob"), ("carol", "Carol")],
+    );
+    let output = run_frost(
+        alice.path(),
+        &["dkg", "coordinator", "invite", "Bob", "Carol"],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let invite = String::from_utf8(output.stdout).unwrap().trim().to_owned();
+
+    let bob = TempDir::new().unwrap();
+    setup_registry(
+        bob.path(),
+        "bob",
+        &[("alice", "Alice"), ("carol", "Carol")],
+    );
+    (bob, invite)
+}
+
+/// Answers the invite against an unreachable server. The group is recorded
+/// before the upload, which then fails.
+fn respond(dir: &Path, invite: &str, arid_file: &Path) -> assert_cmd::Command {
+    run_frost(
+        dir,
+        &[
+            "dkg",
+            "participant",
+            "round1",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--response-arid-file",
+            arid_file.to_str().unwrap(),
+            invite,
+        ],
+    )
+}
+
+fn listening_arids(dir: &Path) -> Vec<ARID> {
+    let registry = Registry::load(&registry_file(dir)).unwrap();
+    registry
+        .groups()
+        .filter_map(|(_, group)| group.listening_at_arid())
+        .collect()
+}
+
+#[test]
+fn missing_file_is_created_and_then_reused() {
+    let (bob, invite) = invite_bob();
+    let arid_file = bob.path().join("next_arid.txt");
+
+    respond(bob.path(), &invite, &arid_file).assert().failure();
+    let recorded = fs::read_to_string(&arid_file).unwrap();
+    let arid = ARID::from_ur_string(recorded.trim()).unwrap();
+    assert_eq!(listening_arids(bob.path()), vec![arid]);
+
+    respond(bob.path(), &invite, &arid_file).assert().failure();
+    assert_eq!(fs::read_to_string(&arid_file).unwrap(), recorded);
+    assert_eq!(listening_arids(bob.path()), vec![arid]);
+}
+
+#[test]
+fn existing_file_supplies_the_arid() {
+    let (bob, invite) = invite_bob();
+    let arid_file = bob.path().join("next_arid.txt");
+    let arid = ARID::new();
+    fs::write(&arid_file, format!("{}\n", arid.ur_string())).unwrap();
+
+    respond(bob.path(), &invite, &arid_file).assert().failure();
+    assert_eq!(listening_arids(bob.path()), vec![arid]);
+}
+
+#[test]
+fn file_with_several_entries_is_rejected() {
+    let (bob, invite) = invite_bob();
+    let arid_file = bob.path().join("next_arid.txt");
+    fs::write(
+        &arid_file,
+        format!("{}\n{}\n", ARID::new().ur_string(), ARID::new().ur_string()),
+    )
+    .unwrap();
+
+    respond(bob.path(), &invite, &arid_file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must contain a single ur:arid"));
+    assert!(listening_arids(bob.path()).is_empty());
+}
+
+#[test]
+fn file_with_other_ur_is_rejected() {
+    let (bob, invite) = invite_bob();
+    let arid_file = bob.path().join("next_arid.txt");
+    fs::write(&arid_file, fixture("bob_signed_xid.txt")).unwrap();
+
+    respond(bob.path(), &invite, &arid_file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid response ARID file"));
+}
+
+#[test]
+fn preview_does_not_create_the_file() {
+    let (bob, invite) = invite_bob();
+    let arid_file = bob.path().join("next_arid.txt");
+
+    run_frost(
+        bob.path(),
+        &[
+            "dkg",
+            "participant",
+            "round1",
+            "--preview",
+            "--response-arid-file",
+            arid_file.to_str().unwrap(),
+            &invite,
+        ],
+    )
+    .assert()
+    .success();
+    assert!(!arid_file.exists());
+}
+
+#[test]
+fn preview_reads_an_existing_file() {
+    let (bob, invite) = invite_bob();
+    let arid_file = bob.path().join("next_arid.txt");
+    fs::write(&arid_file, "not-an-arid\n").unwrap();
+
+    run_frost(
+        bob.path(),
+        &[
+            "dkg",
+            "participant",
+            "round1",
+            "--preview",
+            "--response-arid-file",
+            arid_file.to_str().unwrap(),
+            &invite,
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Invalid response ARID file"));
+    assert_eq!(fs::read_to_string(&arid_file).unwrap(), "not-an-arid\n");
+}
+
+#[test]
+fn conflicts_with_response_arid() {
+    let (bob, invite) = invite_bob();
+
+    run_frost(
+        bob.path(),
+        &[
+            "dkg",
+            "participant",
+            "round1",
+            "--response-arid",
+            &ARID::new().ur_string(),
+            "--response-arid-file",
+            "next_arid.txt",
+            &invite,
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
@@ -0,0 +1,273 @@
+use std::{fs, path::Path};
+
+mod common;
+use bc_components::{ARID, XID};
+use bc_envelope::prelude::*;
+use common::{
+    fixture_xid, record_group, registry_file, run_frost, setup_alice_registry,
+};
+use frost_hubert::{
+    cmd::common::GroupStatePaths,
+    registry::{GroupParticipant, GroupRecord, PendingRequests, Registry},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// A registry whose group has collected Round 2 responses but not yet sent
+/// finalize requests.
+fn setup_collected_group(dir: &Path) -> ARID {
+    setup_alice_registry(dir);
+
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(fixture_xid("alice")),
+        ["bob", "carol"]
+            .into_iter()
+            .map(|name| GroupParticipant::new(fixture_xid(name)))
+            .collect(),
+    );
+    let mut pending = PendingRequests::new();
+    for participant in record.participants().to_vec() {
+        pending.add_send_only(*participant.xid(), ARID::new());
+    }
+    record.set_pending_requests(pending);
+
+    record_group(dir, record)
+}
+
+#[test]
+fn pending_requests_report_send_phase() {
+    let xid = fixture_xid("bob");
+
+    assert!(!PendingRequests::new().is_send_only());
+
+    let mut collect = PendingRequests::new();
+    collect.add_send_and_collect(xid, ARID::new(), ARID::new());
+    assert!(!collect.is_send_only());
+
+    let mut send = PendingRequests::new();
+    send.add_send_only(xid, ARID::new());
+    assert!(send.is_send_only());
+}
+
+#[test]
+fn partially_sent_requests_stay_in_send_phase() {
+    let bob = fixture_xid("bob");
+    let carol = fixture_xid("carol");
+    let mut pending = PendingRequests::new();
+    pending.add_send_only(bob, ARID::new());
+    pending.add_send_only(carol, ARID::new());
+
+    // Bob's request went out; a rerun must send only to Carol
+    let bob_collect = ARID::new();
+    pending.mark_sent(&bob, bob_collect);
+    assert!(pending.is_send_only());
+    assert!(!pending.is_unsent(&bob));
+    assert!(pending.is_unsent(&carol));
+
+    assert!(!pending.is_collect_only());
+
+    let carol_collect = ARID::new();
+    pending.mark_sent(&carol, carol_collect);
+    assert!(!pending.is_send_only());
+    assert!(pending.is_collect_only());
+    let collect: Vec<(XID, ARID)> = pending
+        .iter_collect()
+        .map(|(xid, arid)| (*xid, *arid))
+        .collect();
+    assert_eq!(collect, vec![(bob, bob_collect), (carol, carol_collect)]);
+}
+
+#[test]
+fn packages_only_refuses_to_collect_twice() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_collected_group(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "round2",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--packages-only",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Round 2 packages have already been collected for this group",
+    ));
+}
+
+#[test]
+fn dispatch_requires_persisted_packages() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_collected_group(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "round2",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Round 2 packages not found at"));
+}
+
+#[test]
+fn packages_only_conflicts_with_preview() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "round2",
+            "--packages-only",
+            "--preview",
+            &ARID::new().ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
+
+/// Runs `round2 --from-collected` for `group_id` against unreachable
+/// storage.
+fn round2_from_collected(dir: &Path, group_id: &ARID) -> assert_cmd::Command {
+    run_frost(
+        dir,
+        &[
+            "dkg",
+            "coordinator",
+            "round2",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--from-collected",
+            &group_id.ur_string(),
+        ],
+    )
+}
+
+/// Drops the group's pending requests, as on a machine whose registry never
+/// saw the Round 2 collection.
+fn clear_pending(dir: &Path, group_id: &ARID) {
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    registry
+        .group_mut(group_id)
+        .unwrap()
+        .clear_pending_requests();
+    registry.save(&path).unwrap();
+}
+
+#[test]
+fn from_collected_ignores_pending_requests() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_collected_group(temp.path());
+    clear_pending(temp.path(), &group_id);
+
+    round2_from_collected(temp.path(), &group_id)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No pending requests").not())
+        .stderr(predicate::str::contains("Round 2 packages not found at"));
+}
+
+#[test]
+fn from_collected_requires_packages_from_every_participant() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_collected_group(temp.path());
+    clear_pending(temp.path(), &group_id);
+
+    let collected = serde_json::json!({
+        fixture_xid("bob").ur_string(): {
+            "response_arid": ARID::new().ur_string(),
+            "packages": {},
+        },
+    });
+    let path = GroupStatePaths::new(&registry_file(temp.path()))
+        .collected_round2_path(&group_id);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, serde_json::to_vec_pretty(&collected).unwrap()).unwrap();
+
+    round2_from_collected(temp.path(), &group_id)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "holds packages from 1 participants, but the group has 2",
+        ));
+}
+
+#[test]
+fn from_collected_refuses_after_finalize_collection() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_collected_group(temp.path());
+    let path = GroupStatePaths::new(&registry_file(temp.path()))
+        .collected_finalize_path(&group_id);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "{}").unwrap();
+
+    round2_from_collected(temp.path(), &group_id)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("nothing left to send"));
+}
+
+#[test]
+fn from_collected_refuses_after_finalize_dispatch() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_collected_group(temp.path());
+    let path = registry_file(temp.path());
+    let mut registry = Registry::load(&path).unwrap();
+    let record = registry.group_mut(&group_id).unwrap();
+    let mut pending = record.pending_requests().clone();
+    for participant in record.participants().to_vec() {
+        pending.mark_sent(participant.xid(), ARID::new());
+    }
+    record.set_pending_requests(pending);
+    registry.save(&path).unwrap();
+
+    round2_from_collected(temp.path(), &group_id)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Finalize requests have already been sent to every participant",
+        ));
+}
+
+#[test]
+fn from_collected_conflicts_with_packages_only() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "round2",
+            "--from-collected",
+            "--packages-only",
+            &ARID::new().ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
@@ -0,0 +1,188 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+mod common;
+use bc_components::{ARID, XID, XIDProvider};
+use bc_envelope::prelude::*;
+use common::{fixture, registry_file, run_frost, set_owner};
+use frost_ed25519::{self as frost, Identifier, rand_core::OsRng};
+use frost_hubert::{
+    cmd::{common::GroupStatePaths, dkg::common::signing_key_from_verifying},
+    registry::{GroupParticipant, GroupRecord, OwnerRecord, Registry},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+type Round1Packages = BTreeMap<XID, frost::keys::dkg::round1::Package>;
+
+fn xid(name: &str) -> XID {
+    OwnerRecord::from_signed_xid_ur(
+        fixture(&format!("{name}_private_xid.txt")),
+        None,
+    )
+    .unwrap()
+    .xid()
+}
+
+/// Runs a full 2-of-3 DKG in process and returns every participant's Round 1
+/// package along with the group verifying key from part3.
+fn run_dkg(
+    identifiers: &BTreeMap<XID, Identifier>,
+) -> (Round1Packages, frost::VerifyingKey) {
+    let mut packages = BTreeMap::new();
+    let mut round1_secrets = BTreeMap::new();
+    let mut round1 = BTreeMap::new();
+    for (xid, identifier) in identifiers {
+        let (secret, package) =
+            frost::keys::dkg::part1(*identifier, 3, 2, OsRng).unwrap();
+        round1_secrets.insert(*identifier, secret);
+        round1.insert(*identifier, package.clone());
+        packages.insert(*xid, package);
+    }
+    let others = |me: &Identifier| {
+        let mut map = round1.clone();
+        map.remove(me);
+        map
+    };
+
+    let mut round2_secrets = BTreeMap::new();
+    let mut inbox: BTreeMap<Identifier, BTreeMap<Identifier, _>> =
+        BTreeMap::new();
+    for (id, secret) in round1_secrets {
+        let (secret, outgoing) =
+            frost::keys::dkg::part2(secret, &others(&id)).unwrap();
+        round2_secrets.insert(id, secret);
+        for (to, package) in outgoing {
+            inbox.entry(to).or_default().insert(id, package);
+        }
+    }
+
+    let (id, secret) = round2_secrets.iter().next().unwrap();
+    let (_, public_key_package) =
+        frost::keys::dkg::part3(secret, &others(id), &inbox[id]).unwrap();
+    (packages, *public_key_package.verifying_key())
+}
+
+fn write_collected(dir: &Path, packages: &Round1Packages) {
+    let json: serde_json::Map<String, serde_json::Value> = packages
+        .iter()
+        .map(|(xid, package)| {
+            (xid.ur_string(), serde_json::to_value(package).unwrap())
+        })
+        .collect();
+    fs::create_dir_all(dir).unwrap();
+    fs::write(
+        dir.join("collected_round1.json"),
+        serde_json::to_vec_pretty(&json).unwrap(),
+    )
+    .unwrap();
+}
+
+/// Alice coordinated a finished DKG with Bob, Carol and Dan. Returns the
+/// group ID and the group state directory holding the transcript.
+fn setup(dir: &Path) -> (ARID, Round1Packages, std::path::PathBuf) {
+    set_owner(dir, "alice", None);
+
+    let participants = [xid("bob"), xid("carol"), xid("dan")];
+    let identifiers: BTreeMap<XID, Identifier> = participants
+        .iter()
+        .zip(1u16..)
+        .map(|(xid, index)| (*xid, Identifier::try_from(index).unwrap()))
+        .collect();
+    let (packages, verifying_key) = run_dkg(&identifiers);
+
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(xid("alice")),
+        participants
+            .iter()
+            .copied()
+            .map(GroupParticipant::new)
+            .collect(),
+    );
+    record.set_identifiers(identifiers.into_iter().collect());
+    record
+        .set_verifying_key(signing_key_from_verifying(&verifying_key).unwrap());
+    let group_id = ARID::new();
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    registry.record_group(group_id, record).unwrap();
+    registry.save(&path).unwrap();
+
+    let group_dir = GroupStatePaths::new(&path).group_dir(&group_id);
+    write_collected(&group_dir, &packages);
+    (group_id, packages, group_dir)
+}
+
+fn verify(dir: &Path, group_id: &ARID, extra: &[&str]) -> assert_cmd::Command {
+    let group = group_id.ur_string();
+    let mut args = vec!["dkg", "verify-transcript", "--group", &group];
+    args.extend_from_slice(extra);
+    run_frost(dir, &args)
+}
+
+#[test]
+fn intact_transcript_reproduces_group_key() {
+    let temp = TempDir::new().unwrap();
+    let (group_id, _, _) = setup(temp.path());
+
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let recorded = registry
+        .group(&group_id)
+        .unwrap()
+        .verifying_key()
+        .unwrap()
+        .ur_string();
+    verify(temp.path(), &group_id, &[])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(recorded));
+}
+
+#[test]
+fn transcript_in_another_directory_is_verified() {
+    let temp = TempDir::new().unwrap();
+    let (group_id, packages, _) = setup(temp.path());
+    let copy = temp.path().join("copy");
+    write_collected(&copy, &packages);
+
+    verify(temp.path(), &group_id, &[copy.to_str().unwrap()])
+        .assert()
+        .success();
+}
+
+#[test]
+fn replaced_package_is_detected() {
+    let temp = TempDir::new().unwrap();
+    let (group_id, mut packages, group_dir) = setup(temp.path());
+    let (_, forged) = frost::keys::dkg::part1(
+        Identifier::try_from(2u16).unwrap(),
+        3,
+        2,
+        OsRng,
+    )
+    .unwrap();
+    packages.insert(xid("carol"), forged);
+    write_collected(&group_dir, &packages);
+
+    verify(temp.path(), &group_id, &[])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not match the key"));
+}
+
+#[test]
+fn missing_package_is_reported() {
+    let temp = TempDir::new().unwrap();
+    let (group_id, mut packages, group_dir) = setup(temp.path());
+    packages.remove(&xid("dan"));
+    write_collected(&group_dir, &packages);
+
+    verify(temp.path(), &group_id, &[])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(format!(
+            "Transcript has no Round 1 package from {}",
+            xid("dan").ur_string()
+        )));
+}
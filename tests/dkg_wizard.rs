@@ -0,0 +1,172 @@
+use std::{fs, path::Path};
+
+mod common;
+use bc_components::{ARID, Ed25519PrivateKey, SigningPrivateKey};
+use bc_envelope::prelude::*;
+use common::{
+    fixture, fixture_xid, record_group, registry_file, run_frost, set_owner,
+};
+use frost_hubert::{
+    cmd::{
+        common::GroupStatePaths,
+        dkg::wizard::{WizardStep, next_step},
+    },
+    registry::{GroupParticipant, GroupRecord, PendingRequests},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// A group coordinated by Alice, with invites out to Bob and Carol.
+fn invited_group() -> GroupRecord {
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(fixture_xid("alice")),
+        ["bob", "carol"]
+            .into_iter()
+            .map(|name| GroupParticipant::new(fixture_xid(name)))
+            .collect(),
+    );
+    let mut pending = PendingRequests::new();
+    for name in ["bob", "carol"] {
+        pending.add_collect_only(fixture_xid(name), ARID::new());
+    }
+    record.set_pending_requests(pending);
+    record
+}
+
+/// Sets up Alice's registry with `record`, returning the group ID.
+fn setup_registry(dir: &Path, record: GroupRecord) -> ARID {
+    set_owner(dir, "alice", None);
+    run_frost(
+        dir,
+        &[
+            "registry",
+            "participant",
+            "add",
+            &fixture("bob_signed_xid.txt"),
+            "Bob",
+        ],
+    )
+    .assert()
+    .success();
+
+    record_group(dir, record)
+}
+
+fn touch(path: &Path) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "{}").unwrap();
+}
+
+#[test]
+fn next_step_follows_the_files_each_step_leaves() {
+    let temp = TempDir::new().unwrap();
+    let paths = GroupStatePaths::new(&registry_file(temp.path()));
+    let group_id = ARID::new();
+    let mut record = invited_group();
+    assert_eq!(next_step(&paths, &group_id, &record), WizardStep::Round1);
+
+    // `round1 --continue-on-error` saved only some of the packages
+    touch(&paths.partial_round1_path(&group_id));
+    assert_eq!(next_step(&paths, &group_id, &record), WizardStep::Round1);
+
+    touch(&paths.collected_round1_path(&group_id));
+    assert_eq!(next_step(&paths, &group_id, &record), WizardStep::Round2);
+
+    // `round2 --packages-only` collected the packages but sent nothing
+    touch(&paths.collected_round2_path(&group_id));
+    let mut send_only = PendingRequests::new();
+    send_only.add_send_only(fixture_xid("bob"), ARID::new());
+    record.set_pending_requests(send_only);
+    assert_eq!(next_step(&paths, &group_id, &record), WizardStep::Round2);
+
+    let mut sent = PendingRequests::new();
+    sent.add_send_and_collect(fixture_xid("bob"), ARID::new(), ARID::new());
+    record.set_pending_requests(sent);
+    assert_eq!(next_step(&paths, &group_id, &record), WizardStep::Finalize);
+
+    record.set_verifying_key(
+        SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new())
+            .public_key()
+            .unwrap(),
+    );
+    assert_eq!(next_step(&paths, &group_id, &record), WizardStep::Done);
+}
+
+#[test]
+fn wizard_shows_the_next_step_and_who_it_waits_on() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry(temp.path(), invited_group());
+
+    run_frost(
+        temp.path(),
+        &["--no-input", "dkg", "wizard", &group_id.ur_string()],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Next: collect invite responses and send Round 2 requests",
+    ))
+    .stderr(predicate::str::contains(
+        "Participants: 2 (2 needed to sign)",
+    ))
+    .stderr(predicate::str::contains("Waiting on 2 participants:"))
+    .stderr(predicate::str::contains("  Bob: ur:arid/"))
+    .stderr(predicate::str::contains("Confirmation required"));
+}
+
+#[test]
+fn declining_stops_before_running_the_step() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry(temp.path(), invited_group());
+
+    run_frost(temp.path(), &["dkg", "wizard", &group_id.ur_string()])
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Run dkg coordinator round1 now? [Y/n]",
+        ))
+        .stderr(predicate::str::contains("Stopped before round1"));
+
+    let paths = GroupStatePaths::new(&registry_file(temp.path()));
+    assert!(!paths.collected_round1_path(&group_id).exists());
+}
+
+#[test]
+fn wizard_requires_a_sent_invite() {
+    let temp = TempDir::new().unwrap();
+    let mut record = invited_group();
+    record.clear_pending_requests();
+    let group_id = setup_registry(temp.path(), record);
+
+    run_frost(temp.path(), &["dkg", "wizard", &group_id.ur_string()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No invite has been sent"));
+}
+
+#[test]
+fn wizard_has_nothing_to_do_for_a_finished_group() {
+    let temp = TempDir::new().unwrap();
+    let mut record = invited_group();
+    record.clear_pending_requests();
+    record.set_verifying_key(
+        SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new())
+            .public_key()
+            .unwrap(),
+    );
+    let group_id = setup_registry(temp.path(), record);
+
+    run_frost(
+        temp.path(),
+        &["--no-input", "dkg", "wizard", &group_id.ur_string()],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains(
+        "Next: nothing; the DKG is finished",
+    ))
+    .stderr(predicate::str::contains("Verifying key: ur:"));
+}
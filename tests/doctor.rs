@@ -0,0 +1,136 @@
+use std::{fs, path::Path};
+
+mod common;
+use common::{fixture, registry_file, run_frost};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+const ORPHAN: &str =
+    "0000000000000000000000000000000000000000000000000000000000000001";
+
+fn set_owner(dir: &Path) {
+    let owner = fixture("alice_private_xid.txt");
+    run_frost(dir, &["registry", "owner", "set", &owner])
+        .assert()
+        .success();
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+}
+
+#[test]
+fn missing_registry_fails() {
+    let temp = TempDir::new().unwrap();
+    run_frost(temp.path(), &["--no-color", "doctor"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[fail] No registry at"))
+        .stdout(predicate::str::contains("frost registry owner set"))
+        .stderr(predicate::str::contains("1 check failed"));
+}
+
+#[test]
+fn registry_without_owner_fails() {
+    let temp = TempDir::new().unwrap();
+    let bob = fixture("bob_signed_xid.txt");
+    run_frost(
+        temp.path(),
+        &["registry", "participant", "add", &bob, "Bob"],
+    )
+    .assert()
+    .success();
+
+    run_frost(temp.path(), &["--no-color", "doctor"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[pass] Registry loads from"))
+        .stdout(predicate::str::contains("[fail] No owner is set"));
+}
+
+#[test]
+fn healthy_registry_passes_with_storage_warning() {
+    let temp = TempDir::new().unwrap();
+    set_owner(temp.path());
+    #[cfg(unix)]
+    set_mode(&registry_file(temp.path()), 0o600);
+
+    run_frost(temp.path(), &["--no-color", "doctor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[pass] Registry loads from"))
+        .stdout(predicate::str::contains("is set with private keys"))
+        .stdout(predicate::str::contains(
+            "[warn] No storage backend selected",
+        ))
+        .stdout(predicate::str::contains("[fail]").not());
+}
+
+#[test]
+fn orphaned_group_state_is_warned() {
+    let temp = TempDir::new().unwrap();
+    set_owner(temp.path());
+    fs::create_dir_all(temp.path().join("group-state").join(ORPHAN)).unwrap();
+
+    run_frost(temp.path(), &["--no-color", "doctor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "{ORPHAN} has no matching group in the registry"
+        )));
+}
+
+#[test]
+fn unreachable_storage_fails() {
+    let temp = TempDir::new().unwrap();
+    set_owner(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "--no-color",
+            "doctor",
+            "--storage",
+            "server",
+            "--host",
+            "127.0.0.1",
+            "--port",
+            "1",
+        ],
+    )
+    .assert()
+    .failure()
+    .stdout(predicate::str::contains(
+        "[fail] Server is not available at 127.0.0.1:1",
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn world_readable_secret_is_warned() {
+    let temp = TempDir::new().unwrap();
+    set_owner(temp.path());
+    set_mode(&registry_file(temp.path()), 0o600);
+    let group_dir = temp.path().join("group-state").join(ORPHAN);
+    fs::create_dir_all(&group_dir).unwrap();
+    let secret = group_dir.join("key_package.json");
+    fs::write(&secret, "{}").unwrap();
+    set_mode(&secret, 0o644);
+
+    run_frost(temp.path(), &["--no-color", "doctor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "[warn] {} is readable by all users",
+            secret.display()
+        )))
+        .stdout(predicate::str::contains("chmod 600"));
+
+    set_mode(&secret, 0o600);
+    run_frost(temp.path(), &["--no-color", "doctor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 secret files are not"));
+}
@@ -0,0 +1,149 @@
+use std::{fs, path::Path};
+
+mod common;
+use bc_components::{ARID, Ed25519PrivateKey, SigningPrivateKey};
+use bc_envelope::prelude::*;
+use common::{fixture_xid, registry_file, run_frost, setup_alice_registry};
+use frost_hubert::{
+    cmd::common::GroupStatePaths,
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn setup_registry_with_group(dir: &Path) -> ARID {
+    setup_alice_registry(dir);
+
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    let group_id = ARID::new();
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(fixture_xid("alice")),
+        ["bob", "carol"]
+            .into_iter()
+            .map(|name| GroupParticipant::new(fixture_xid(name)))
+            .collect(),
+    );
+    // Signing requires a finalized group
+    record.set_verifying_key(
+        SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new())
+            .public_key()
+            .unwrap(),
+    );
+    registry.record_group(group_id, record).unwrap();
+    registry.save(&path).unwrap();
+    group_id
+}
+
+/// Records an existing session for `target` and returns its start ARID.
+fn write_start_state(
+    dir: &Path,
+    group_id: &ARID,
+    session_id: &ARID,
+    target: &Envelope,
+) -> ARID {
+    let paths = GroupStatePaths::new(&registry_file(dir));
+    let start_arid = ARID::new();
+    fs::create_dir_all(paths.session_dir(group_id, session_id)).unwrap();
+    let state = serde_json::json!({
+        "session_id": session_id.ur_string(),
+        "start_arid": start_arid.ur_string(),
+        "group": group_id.ur_string(),
+        "min_signers": 2,
+        "participants": {},
+        "target": target.ur_string(),
+    });
+    fs::write(
+        paths.start_state_path(group_id, session_id),
+        serde_json::to_vec_pretty(&state).unwrap(),
+    )
+    .unwrap();
+    start_arid
+}
+
+fn invite(dir: &Path, group_id: &ARID, target: &Envelope, extra: &[&str]) {
+    let target_path = dir.join("target.txt");
+    fs::write(&target_path, target.ur_string()).unwrap();
+    let mut args = vec!["sign", "coordinator", "invite"];
+    args.extend_from_slice(extra);
+    let target_path = target_path.to_str().unwrap().to_owned();
+    let group = group_id.ur_string();
+    args.extend_from_slice(&["--target", &target_path, &group]);
+    run_frost(dir, &args)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Hubert storage is required"));
+}
+
+#[test]
+fn invite_reuses_open_session_for_same_target() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path());
+    let session_id = ARID::new();
+    let target = Envelope::new("Hello.");
+    let start_arid =
+        write_start_state(temp.path(), &group_id, &session_id, &target);
+
+    // Different assertions on the same subject sign the same message
+    let target_path = temp.path().join("target.txt");
+    fs::write(
+        &target_path,
+        target.clone().add_assertion("note", "x").ur_string(),
+    )
+    .unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "invite",
+            "--target",
+            target_path.to_str().unwrap(),
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(start_arid.ur_string()))
+    .stderr(predicate::str::contains(session_id.ur_string()))
+    .stderr(predicate::str::contains("--new"));
+}
+
+#[test]
+fn invite_starts_new_session_for_different_target() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path());
+    write_start_state(
+        temp.path(),
+        &group_id,
+        &ARID::new(),
+        &Envelope::new("Hello."),
+    );
+
+    invite(temp.path(), &group_id, &Envelope::new("Goodbye."), &[]);
+}
+
+#[test]
+fn invite_new_flag_skips_duplicate_detection() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path());
+    let target = Envelope::new("Hello.");
+    write_start_state(temp.path(), &group_id, &ARID::new(), &target);
+
+    invite(temp.path(), &group_id, &target, &["--new"]);
+}
+
+#[test]
+fn invite_ignores_finalized_sessions() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path());
+    let session_id = ARID::new();
+    let target = Envelope::new("Hello.");
+    write_start_state(temp.path(), &group_id, &session_id, &target);
+    let paths = GroupStatePaths::new(&registry_file(temp.path()));
+    fs::write(paths.final_state_path(&group_id, &session_id), "{}").unwrap();
+
+    invite(temp.path(), &group_id, &target, &[]);
+}
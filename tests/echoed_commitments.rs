@@ -0,0 +1,104 @@
+use bc_components::{ARID, JSON, XID};
+use bc_envelope::prelude::*;
+use frost_ed25519::{self as frost, keys::IdentifierList, rand_core::OsRng};
+use frost_hubert::cmd::sign::common::check_echoed_commitments;
+
+fn commitments() -> frost::round1::SigningCommitments {
+    let (secret_shares, _) =
+        frost::keys::generate_with_dealer(2, 2, IdentifierList::Default, OsRng)
+            .unwrap();
+    let share = secret_shares.into_values().next().unwrap();
+    let key_package = frost::keys::KeyPackage::try_from(share).unwrap();
+    let (_, commitments) =
+        frost::round1::commit(key_package.signing_share(), &mut OsRng);
+    commitments
+}
+
+/// A signRound2Response body echoing `commitments`, as a participant
+/// builds it.
+fn response(commitments: &frost::round1::SigningCommitments) -> Envelope {
+    Envelope::unit()
+        .add_type("signRound2Response")
+        .add_assertion("session", ARID::new())
+        .add_assertion(
+            "commitments",
+            CBOR::from(JSON::from_data(
+                serde_json::to_vec(commitments).unwrap(),
+            )),
+        )
+}
+
+fn participant() -> XID { XID::from_data_ref([7u8; 32]).unwrap() }
+
+#[test]
+fn matching_commitments_pass() {
+    let signed_with = commitments();
+    check_echoed_commitments(
+        &response(&signed_with),
+        &signed_with,
+        &participant(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn altered_stored_commitments_are_caught() {
+    let signed_with = commitments();
+    // commitments.json was rewritten with another participant's commitments
+    // after collection
+    let stored = commitments();
+
+    let error = check_echoed_commitments(
+        &response(&signed_with),
+        &stored,
+        &participant(),
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(error.contains("may have been altered"), "{error}");
+}
+
+#[test]
+fn responses_must_echo_commitments() {
+    let body = Envelope::unit()
+        .add_type("signRound2Response")
+        .add_assertion("session", ARID::new());
+
+    let error = check_echoed_commitments(&body, &commitments(), &participant())
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("does not echo its commitments"), "{error}");
+}
+
+#[test]
+fn echoes_compare_as_commitments_not_text() {
+    let signed_with = commitments();
+    // The same commitments, laid out by a pretty-printing encoder
+    let body = Envelope::unit()
+        .add_type("signRound2Response")
+        .add_assertion("session", ARID::new())
+        .add_assertion(
+            "commitments",
+            CBOR::from(JSON::from_data(
+                serde_json::to_vec_pretty(&signed_with).unwrap(),
+            )),
+        );
+
+    check_echoed_commitments(&body, &signed_with, &participant()).unwrap();
+}
+
+#[test]
+fn unparseable_echoes_are_rejected() {
+    let body = Envelope::unit()
+        .add_type("signRound2Response")
+        .add_assertion("session", ARID::new())
+        .add_assertion(
+            "commitments",
+            CBOR::from(JSON::from_data(b"{\"hiding\": 1}".to_vec())),
+        );
+
+    let error = check_echoed_commitments(&body, &commitments(), &participant())
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("echoes invalid commitments"), "{error}");
+}
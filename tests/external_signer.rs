@@ -0,0 +1,353 @@
+use std::{collections::BTreeMap, fs, path::Path, time::Duration};
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{owner, registry_file, run_frost, setup_alice_registry};
+use frost_ed25519::{self as frost, keys::IdentifierList, rand_core::OsRng};
+use frost_hubert::{
+    cmd::{
+        common::GroupStatePaths,
+        sign::common::{
+            read_external_signature_share, verify_own_signature_share,
+        },
+    },
+    registry::{ContributionPaths, GroupParticipant, GroupRecord, Registry},
+};
+use gstp::SealedRequest;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+struct Session {
+    group_id: ARID,
+    session_id: ARID,
+    nonces: frost::round1::SigningNonces,
+    commitments: frost::round1::SigningCommitments,
+}
+
+/// Sets up Alice's registry with a finished group, a dealt key package, and
+/// a signInvite from Bob already received.
+fn setup(dir: &Path) -> Session {
+    setup_alice_registry(dir);
+
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let carol = owner("carol", None);
+    let group_id = ARID::new();
+    let session_id = ARID::new();
+
+    let (secret_shares, _) =
+        frost::keys::generate_with_dealer(2, 2, IdentifierList::Default, OsRng)
+            .unwrap();
+    let share = secret_shares.into_values().next().unwrap();
+    let key_package = frost::keys::KeyPackage::try_from(share).unwrap();
+    let (nonces, commitments) =
+        frost::round1::commit(key_package.signing_share(), &mut OsRng);
+
+    let path = registry_file(dir);
+    let paths = GroupStatePaths::new(&path);
+    let key_package_path = paths.key_package_path(&group_id);
+    fs::create_dir_all(key_package_path.parent().unwrap()).unwrap();
+    fs::write(&key_package_path, serde_json::to_vec(&key_package).unwrap())
+        .unwrap();
+
+    let mut registry = Registry::load(&path).unwrap();
+    let mut group = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(bob.xid()),
+        vec![
+            GroupParticipant::new(alice.xid()),
+            GroupParticipant::new(carol.xid()),
+        ],
+    );
+    group.set_contributions(ContributionPaths {
+        key_package: Some(key_package_path.to_string_lossy().into_owned()),
+        ..Default::default()
+    });
+    registry.record_group(group_id, group).unwrap();
+    registry.save(&path).unwrap();
+
+    let request = SealedRequest::new(
+        "signInvite",
+        session_id,
+        bob.xid_document().clone(),
+    )
+    .with_parameter("group", group_id)
+    .with_parameter("session", session_id);
+    let request_envelope = request
+        .to_envelope(
+            Some(Date::with_duration_from_now(Duration::from_secs(3600))),
+            Some(bob.xid_document().inception_private_keys().unwrap()),
+            Some(alice.xid_document()),
+        )
+        .unwrap();
+
+    let state = serde_json::json!({
+        "request_envelope": request_envelope.ur_string(),
+        "group": group_id.ur_string(),
+        "session": session_id.ur_string(),
+        "coordinator": bob.xid().ur_string(),
+        "min_signers": 2,
+        "response_arid": ARID::new().ur_string(),
+        "participants": [alice.xid().ur_string(), carol.xid().ur_string()],
+        "target": Envelope::new("Budget").ur_string(),
+    });
+    let state_path = paths.receive_state_path(&group_id, &session_id);
+    fs::write(&state_path, serde_json::to_vec_pretty(&state).unwrap()).unwrap();
+
+    Session { group_id, session_id, nonces, commitments }
+}
+
+/// Writes a commit.json as `sign participant round1` would, external or not,
+/// and points the registry's listening ARID at it.
+fn write_commit_state(dir: &Path, session: &Session, external: bool) {
+    let path = registry_file(dir);
+    let next_share_arid = ARID::new();
+    let mut state = serde_json::json!({
+        "session": session.session_id.ur_string(),
+        "response_arid": ARID::new().ur_string(),
+        "next_share_arid": next_share_arid.ur_string(),
+        "target": Envelope::new("Budget").ur_string(),
+        "signing_commitments": session.commitments,
+    });
+    if external {
+        state["external_signer"] = serde_json::Value::Bool(true);
+        state["nonce_handle"] = "slot-7".into();
+    } else {
+        state["signing_nonces"] =
+            serde_json::to_value(&session.nonces).unwrap();
+    }
+    let commit_path = GroupStatePaths::new(&path)
+        .commit_state_path(&session.group_id, &session.session_id);
+    fs::create_dir_all(commit_path.parent().unwrap()).unwrap();
+    fs::write(&commit_path, serde_json::to_vec_pretty(&state).unwrap())
+        .unwrap();
+
+    let mut registry = Registry::load(&path).unwrap();
+    registry
+        .group_mut(&session.group_id)
+        .unwrap()
+        .set_listening_at_arid(next_share_arid);
+    registry.save(&path).unwrap();
+}
+
+fn share(dir: &Path, session: &Session, extra: &[&str]) -> assert_cmd::Command {
+    let session_ur = session.session_id.ur_string();
+    let mut args = vec![
+        "sign",
+        "participant",
+        "round2",
+        "--storage",
+        "server",
+        "--host",
+        "127.0.0.1",
+        "--port",
+        "1",
+        "--timeout",
+        "1",
+    ];
+    args.extend_from_slice(extra);
+    args.push(&session_ur);
+    run_frost(dir, &args)
+}
+
+#[test]
+fn external_commitments_are_used_instead_of_local_nonces() {
+    let temp = TempDir::new().unwrap();
+    let session = setup(temp.path());
+    let file = temp.path().join("hsm_commitments.json");
+    fs::write(
+        &file,
+        serde_json::to_vec(&serde_json::json!({
+            "signing_commitments": session.commitments,
+            "nonce_handle": "slot-7",
+        }))
+        .unwrap(),
+    )
+    .unwrap();
+
+    let session_ur = session.session_id.ur_string();
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "participant",
+            "round1",
+            "--preview",
+            "--commitments-file",
+            file.to_str().unwrap(),
+            &session_ur,
+        ],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::starts_with("ur:envelope/"));
+}
+
+#[test]
+fn malformed_commitments_file_is_rejected() {
+    let temp = TempDir::new().unwrap();
+    let session = setup(temp.path());
+    let file = temp.path().join("hsm_commitments.json");
+    fs::write(&file, r#"{"nonce_handle": "slot-7"}"#).unwrap();
+
+    let session_ur = session.session_id.ur_string();
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "participant",
+            "round1",
+            "--preview",
+            "--commitments-file",
+            file.to_str().unwrap(),
+            &session_ur,
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Missing signing_commitments"));
+}
+
+#[test]
+fn commitments_file_conflicts_with_reject() {
+    let temp = TempDir::new().unwrap();
+    let session = setup(temp.path());
+
+    let session_ur = session.session_id.ur_string();
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "participant",
+            "round1",
+            "--preview",
+            "--commitments-file",
+            "hsm_commitments.json",
+            "--reject",
+            "Busy",
+            &session_ur,
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn external_session_requires_external_share() {
+    let temp = TempDir::new().unwrap();
+    let session = setup(temp.path());
+    write_commit_state(temp.path(), &session, true);
+
+    share(temp.path(), &session, &[])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("committed by an external signer"))
+        .stderr(predicate::str::contains("--share-file"));
+}
+
+#[test]
+fn unreadable_share_file_fails_before_fetching() {
+    let temp = TempDir::new().unwrap();
+    let session = setup(temp.path());
+    write_commit_state(temp.path(), &session, false);
+    let file = temp.path().join("hsm_share.json");
+    fs::write(&file, r#"{"signature_share": "not a share"}"#).unwrap();
+
+    share(
+        temp.path(),
+        &session,
+        &["--share-file", file.to_str().unwrap()],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Invalid signature share"));
+}
+
+#[test]
+fn share_file_has_no_alias() {
+    let temp = TempDir::new().unwrap();
+    let session = setup(temp.path());
+    let file = temp.path().join("hsm_share.json");
+
+    share(
+        temp.path(),
+        &session,
+        &["--signature-share-file", file.to_str().unwrap()],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("unexpected argument"));
+}
+
+/// A 2-of-2 signing package over "Budget", with each signer's key package
+/// and the share it produced.
+fn signed_package() -> (
+    frost::SigningPackage,
+    Vec<(frost::keys::KeyPackage, frost::round2::SignatureShare)>,
+) {
+    let (secret_shares, _) =
+        frost::keys::generate_with_dealer(2, 2, IdentifierList::Default, OsRng)
+            .unwrap();
+    let key_packages: Vec<frost::keys::KeyPackage> = secret_shares
+        .into_values()
+        .map(|share| frost::keys::KeyPackage::try_from(share).unwrap())
+        .collect();
+    let mut nonces = Vec::new();
+    let mut commitments = BTreeMap::new();
+    for key_package in &key_packages {
+        let (nonce, commitment) =
+            frost::round1::commit(key_package.signing_share(), &mut OsRng);
+        nonces.push(nonce);
+        commitments.insert(*key_package.identifier(), commitment);
+    }
+    let message = Envelope::new("Budget").subject().digest().data().to_vec();
+    let signing_package = frost::SigningPackage::new(commitments, &message);
+    let signers = key_packages
+        .into_iter()
+        .zip(&nonces)
+        .map(|(key_package, nonce)| {
+            let share =
+                frost::round2::sign(&signing_package, nonce, &key_package)
+                    .unwrap();
+            (key_package, share)
+        })
+        .collect();
+    (signing_package, signers)
+}
+
+#[test]
+fn external_share_is_verified_against_own_verifying_share() {
+    let (signing_package, signers) = signed_package();
+    let (alice_key, alice_share) = &signers[0];
+    let (_, bob_share) = &signers[1];
+
+    verify_own_signature_share(&signing_package, alice_share, alice_key)
+        .unwrap();
+    let error =
+        verify_own_signature_share(&signing_package, bob_share, alice_key)
+            .unwrap_err();
+    assert!(error.to_string().contains("does not verify"));
+}
+
+#[test]
+fn share_file_accepts_bare_or_wrapped_share() {
+    let temp = TempDir::new().unwrap();
+    let (_, signers) = signed_package();
+    let (_, share) = &signers[0];
+
+    let bare = temp.path().join("bare.json");
+    fs::write(&bare, serde_json::to_vec(share).unwrap()).unwrap();
+    assert_eq!(read_external_signature_share(&bare).unwrap(), *share);
+
+    let wrapped = temp.path().join("wrapped.json");
+    fs::write(
+        &wrapped,
+        serde_json::to_vec(&serde_json::json!({ "signature_share": share }))
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(read_external_signature_share(&wrapped).unwrap(), *share);
+}
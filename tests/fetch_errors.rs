@@ -0,0 +1,60 @@
+use anyhow::{Context, anyhow};
+use frost_hubert::cmd::parallel::FetchError;
+
+#[test]
+fn empty_slot_is_retryable() {
+    let err: anyhow::Error = FetchError::NotYetAvailable.into();
+    let classified = FetchError::classify(&err);
+    assert_eq!(classified, FetchError::NotYetAvailable);
+    assert!(classified.is_retryable());
+    assert_eq!(
+        err.to_string(),
+        "Response not yet available in Hubert storage"
+    );
+}
+
+#[test]
+fn decode_failure_is_not_retryable() {
+    let err: anyhow::Error =
+        FetchError::decode(anyhow!("envelope is not encrypted")).into();
+    let classified = FetchError::classify(&err);
+    assert_eq!(
+        classified,
+        FetchError::DecodeError("envelope is not encrypted".into())
+    );
+    assert!(!classified.is_retryable());
+    assert_eq!(
+        err.to_string(),
+        "Failed to decode response: envelope is not encrypted"
+    );
+}
+
+#[test]
+fn rejection_is_not_retryable() {
+    let err: anyhow::Error =
+        FetchError::Rejected("Participant rejected invite: busy".into()).into();
+    let classified = FetchError::classify(&err);
+    assert!(matches!(classified, FetchError::Rejected(_)));
+    assert!(!classified.is_retryable());
+    assert_eq!(err.to_string(), "Participant rejected invite: busy");
+}
+
+#[test]
+fn untyped_errors_are_validation_failures() {
+    let err = anyhow!("Response group ID does not match expected");
+    let classified = FetchError::classify(&err);
+    assert_eq!(
+        classified,
+        FetchError::ValidationError(
+            "Response group ID does not match expected".into()
+        )
+    );
+    assert!(!classified.is_retryable());
+}
+
+#[test]
+fn classification_sees_through_context() {
+    let result: anyhow::Result<()> = Err(FetchError::NotYetAvailable.into());
+    let err = result.context("Collecting from Bob").unwrap_err();
+    assert!(FetchError::classify(&err).is_retryable());
+}
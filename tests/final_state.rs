@@ -0,0 +1,89 @@
+use std::{fs, path::Path};
+
+use bc_components::{
+    Ed25519PrivateKey, Signature, Signer, SigningPrivateKey, SigningPublicKey,
+};
+use bc_envelope::prelude::*;
+use frost_hubert::cmd::sign::common::check_final_state_overwrite;
+use tempfile::TempDir;
+
+const MESSAGE: &[u8] = b"quarterly budget";
+
+fn signing_key() -> (SigningPrivateKey, SigningPublicKey) {
+    let private_key = SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new());
+    let public_key = private_key.public_key().unwrap();
+    (private_key, public_key)
+}
+
+fn write_final(path: &Path, signature: &Signature) {
+    let root = serde_json::json!({ "signature": signature.ur_string() });
+    fs::write(path, serde_json::to_vec_pretty(&root).unwrap()).unwrap();
+}
+
+#[test]
+fn missing_final_state_can_be_written() {
+    let temp = TempDir::new().unwrap();
+    let (private_key, public_key) = signing_key();
+    let signature = private_key.sign(&MESSAGE).unwrap();
+
+    check_final_state_overwrite(
+        &temp.path().join("final.json"),
+        &signature,
+        &public_key,
+        MESSAGE,
+        false,
+    )
+    .unwrap();
+}
+
+#[test]
+fn completed_signature_requires_force() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("final.json");
+    let (private_key, public_key) = signing_key();
+    let signature = private_key.sign(&MESSAGE).unwrap();
+    write_final(&path, &signature);
+
+    let err = check_final_state_overwrite(
+        &path,
+        &signature,
+        &public_key,
+        MESSAGE,
+        false,
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("already records a completed signature")
+    );
+
+    check_final_state_overwrite(&path, &signature, &public_key, MESSAGE, true)
+        .unwrap();
+}
+
+#[test]
+fn forced_overwrite_must_match_recorded_signature() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("final.json");
+    let (private_key, public_key) = signing_key();
+    write_final(&path, &private_key.sign(&MESSAGE).unwrap());
+
+    let other = private_key.sign(&b"another session").unwrap();
+    let err =
+        check_final_state_overwrite(&path, &other, &public_key, MESSAGE, true)
+            .unwrap_err();
+    assert!(err.to_string().contains("does not match the one recorded"));
+}
+
+#[test]
+fn unverifiable_final_state_can_be_replaced() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("final.json");
+    let (stranger, _) = signing_key();
+    write_final(&path, &stranger.sign(&MESSAGE).unwrap());
+
+    let (private_key, public_key) = signing_key();
+    let signature = private_key.sign(&MESSAGE).unwrap();
+    check_final_state_overwrite(&path, &signature, &public_key, MESSAGE, false)
+        .unwrap();
+}
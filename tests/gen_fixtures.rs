@@ -0,0 +1,89 @@
+#![cfg(feature = "test-support")]
+
+use std::{fs, path::Path};
+
+use bc_components::XID;
+use frost_hubert::registry::{ParticipantRecord, Registry};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::run_frost;
+
+fn generate(dir: &Path, extra: &[&str]) -> assert_cmd::Command {
+    let mut args = vec!["test-support", "gen-fixtures"];
+    args.extend_from_slice(extra);
+    args.push(dir.to_str().unwrap());
+    run_frost(dir, &args)
+}
+
+fn xid_of(dir: &Path, name: &str) -> XID {
+    let ur =
+        fs::read_to_string(dir.join(format!("{name}_signed_xid.txt"))).unwrap();
+    ParticipantRecord::from_signed_xid_ur(ur, None)
+        .unwrap()
+        .xid()
+}
+
+#[test]
+fn gen_fixtures_writes_loadable_registry_and_invite() {
+    let temp = TempDir::new().unwrap();
+    generate(temp.path(), &[]).assert().success();
+
+    for name in ["alice", "bob", "carol", "dan"] {
+        let signed = fs::read_to_string(
+            temp.path().join(format!("{name}_signed_xid.txt")),
+        )
+        .unwrap();
+        assert!(signed.starts_with("ur:xid/"));
+        assert!(!signed.ends_with('\n'));
+        assert!(temp.path().join(format!("{name}_private_xid.txt")).exists());
+    }
+
+    let registry =
+        Registry::load(&temp.path().join("sample_registry.json")).unwrap();
+    assert_eq!(registry.owner().unwrap().pet_name(), Some("Alice"));
+    assert_eq!(registry.participants().len(), 3);
+
+    let invite =
+        fs::read_to_string(temp.path().join("sample_invite.txt")).unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "inspect",
+            "--registry",
+            "sample_registry.json",
+            &invite,
+        ],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Charter: Fixture charter"));
+}
+
+#[test]
+fn gen_fixtures_is_deterministic_and_guards_existing_files() {
+    let first = TempDir::new().unwrap();
+    let second = TempDir::new().unwrap();
+    generate(first.path(), &["--participants", "2"])
+        .assert()
+        .success();
+    generate(second.path(), &["--participants", "2"])
+        .assert()
+        .success();
+
+    for name in ["alice", "bob", "carol"] {
+        assert_eq!(xid_of(first.path(), name), xid_of(second.path(), name));
+    }
+    assert!(!first.path().join("dan_signed_xid.txt").exists());
+
+    generate(first.path(), &["--participants", "2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --force"));
+    generate(first.path(), &["--participants", "2", "--force"])
+        .assert()
+        .success();
+}
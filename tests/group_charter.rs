@@ -0,0 +1,233 @@
+mod common;
+
+use std::{path::Path, time::Duration};
+
+use bc_components::{ARID, PrivateKeyBase, XID, XIDProvider};
+use bc_envelope::prelude::*;
+use bc_rand::{RandomNumberGenerator, make_fake_random_number_generator};
+use bc_xid::{
+    XIDDocument, XIDGeneratorOptions, XIDGenesisMarkOptions,
+    XIDInceptionKeyOptions, XIDPrivateKeyOptions, XIDSigningOptions,
+};
+use common::{
+    BOB_AND_CAROL, add_participants, registry_file, run_frost, set_owner,
+};
+use frost_hubert::{
+    DkgInvitation, DkgInvite,
+    dkg::CharterStatement,
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use predicates::prelude::*;
+use provenance_mark::ProvenanceMarkResolution;
+use tempfile::TempDir;
+
+fn make_xid_document(
+    rng: &mut impl RandomNumberGenerator,
+    date: Date,
+) -> XIDDocument {
+    XIDDocument::new(
+        XIDInceptionKeyOptions::PrivateKeyBase(PrivateKeyBase::new_using(rng)),
+        XIDGenesisMarkOptions::Passphrase(
+            "password".to_string(),
+            Some(ProvenanceMarkResolution::Quartile),
+            Some(date),
+            None,
+        ),
+    )
+}
+
+fn make_arid(rng: &mut impl RandomNumberGenerator) -> ARID {
+    ARID::from_data_ref(rng.random_data(ARID::ARID_SIZE)).unwrap()
+}
+
+fn signed_ur(document: &XIDDocument) -> String {
+    document
+        .clone()
+        .to_envelope(
+            XIDPrivateKeyOptions::default(),
+            XIDGeneratorOptions::default(),
+            XIDSigningOptions::Inception,
+        )
+        .unwrap()
+        .ur_string()
+}
+
+#[test]
+fn signed_charter_survives_invite_round_trip() {
+    provenance_mark::register_tags();
+
+    let mut rng = make_fake_random_number_generator();
+    let date = Date::from_ymd(2025, 12, 31);
+    let expiry = date + Duration::from_secs(60 * 60);
+
+    let coordinator = make_xid_document(&mut rng, date);
+    let impostor = make_xid_document(&mut rng, date);
+    let alice = make_xid_document(&mut rng, date);
+    let bob = make_xid_document(&mut rng, date);
+    let group_id = make_arid(&mut rng);
+
+    let invite = DkgInvite::new(
+        make_arid(&mut rng),
+        coordinator.clone(),
+        group_id,
+        date,
+        expiry,
+        2,
+        "Test charter".to_string(),
+        vec![signed_ur(&alice), signed_ur(&bob)],
+        vec![make_arid(&mut rng), make_arid(&mut rng)],
+    )
+    .unwrap()
+    .with_signed_charter()
+    .unwrap();
+
+    let invitation = DkgInvitation::from_invite(
+        invite.to_envelope().unwrap(),
+        date,
+        Some(&coordinator),
+        &alice,
+    )
+    .unwrap();
+    let attestation = invitation.charter_attestation().unwrap();
+
+    let statement =
+        CharterStatement::verify(attestation, &coordinator).unwrap();
+    let mut participants = vec![alice.xid(), bob.xid()];
+    participants.sort();
+    assert_eq!(statement.group_id, group_id);
+    assert_eq!(statement.charter, "Test charter");
+    assert_eq!(statement.min_signers, 2);
+    assert_eq!(statement.coordinator, coordinator.xid());
+    assert_eq!(statement.participants, participants);
+    statement
+        .check_matches(&invite.charter_statement())
+        .unwrap();
+
+    // Only the coordinator's key verifies the attestation
+    assert!(CharterStatement::verify(attestation, &impostor).is_err());
+
+    // A statement with a different participant set does not match
+    let altered = CharterStatement::new(
+        group_id,
+        "Test charter",
+        2,
+        coordinator.xid(),
+        vec![alice.xid(), impostor.xid()],
+    );
+    assert_eq!(
+        statement.check_matches(&altered).unwrap_err().to_string(),
+        "Charter attestation does not match the participants"
+    );
+}
+
+/// Alice, with the pet name "Alice", owns the registry; Bob and Carol are
+/// participants.
+fn setup_alice_registry(dir: &Path) {
+    set_owner(dir, "alice", Some("Alice"));
+    add_participants(dir, BOB_AND_CAROL);
+}
+
+/// Record a group coordinated by the registry owner, attested with `charter`
+/// signed over `signed_participants`.
+fn record_attested_group(
+    dir: &Path,
+    charter: &str,
+    signed_participants: Vec<XID>,
+) -> ARID {
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    let owner = registry.owner().unwrap().clone();
+    let participants: Vec<XID> =
+        registry.participants().keys().copied().collect();
+
+    let group_id = ARID::new();
+    let signer = owner.xid_document().inception_private_keys().unwrap();
+    let attestation = CharterStatement::new(
+        group_id,
+        charter,
+        2,
+        owner.xid(),
+        signed_participants,
+    )
+    .sign(signer);
+
+    let mut record = GroupRecord::new(
+        charter.to_string(),
+        2,
+        GroupParticipant::new(owner.xid()),
+        participants
+            .into_iter()
+            .map(GroupParticipant::new)
+            .collect(),
+    );
+    record.set_charter_attestation(attestation.ur_string());
+    registry.record_group(group_id, record).unwrap();
+    registry.save(&path).unwrap();
+    group_id
+}
+
+#[test]
+fn verify_charter_accepts_recorded_attestation() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+    let participants: Vec<XID> = Registry::load(&registry_file(temp.path()))
+        .unwrap()
+        .participants()
+        .keys()
+        .copied()
+        .collect();
+    let group_id =
+        record_attested_group(temp.path(), "Quarterly budget", participants);
+
+    run_frost(
+        temp.path(),
+        &["registry", "group", "verify-charter", &group_id.ur_string()],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Charter attestation: valid"))
+    .stdout(predicate::str::contains("Coordinator: Alice"))
+    .stdout(predicate::str::contains("Charter: Quarterly budget"))
+    .stdout(predicate::str::contains("Participants: 2"));
+}
+
+#[test]
+fn verify_charter_rejects_mismatched_participants() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+    let group_id =
+        record_attested_group(temp.path(), "Quarterly budget", Vec::new());
+
+    run_frost(
+        temp.path(),
+        &["registry", "group", "verify-charter", &group_id.ur_string()],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Charter attestation does not match the participants",
+    ));
+}
+
+#[test]
+fn inspect_reports_verified_charter_attestation() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+
+    let output = run_frost(
+        temp.path(),
+        &["dkg", "coordinator", "invite", "--preview", "Bob", "Carol"],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let preview = String::from_utf8(output.stdout).unwrap();
+
+    run_frost(
+        temp.path(),
+        &["dkg", "coordinator", "inspect", preview.trim()],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Charter attestation: verified"));
+}
@@ -0,0 +1,203 @@
+use std::{collections::HashMap, path::Path};
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{
+    BOB_CAROL_AND_DAN, fixture, fixture_xid, record_group, registry_file,
+    run_frost, setup_registry,
+};
+use frost_hubert::{
+    cmd::{
+        dkg::coordinator::round1::tolerate_rejections,
+        parallel::CollectionResult,
+    },
+    registry::{GroupParticipant, GroupRecord, PendingRequests, Registry},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn make_group(min_signers: usize) -> GroupRecord {
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        min_signers,
+        GroupParticipant::new(fixture_xid("alice")),
+        ["bob", "carol", "dan"]
+            .into_iter()
+            .map(|name| GroupParticipant::new(fixture_xid(name)))
+            .collect(),
+    );
+    let mut pending = PendingRequests::new();
+    for participant in record.participants().to_vec() {
+        pending.add_collect_only(*participant.xid(), ARID::new());
+    }
+    record.set_pending_requests(pending);
+    record
+}
+
+#[test]
+fn exclusion_keeps_identifier_order() {
+    let mut record = make_group(2);
+    let before = record.identifier_order();
+    let middle = before[1];
+
+    assert!(record.exclude_participant(&middle));
+    assert!(!record.exclude_participant(&middle));
+
+    assert_eq!(record.identifier_order(), before);
+    assert_eq!(record.participants().len(), 2);
+    assert_eq!(record.excluded().len(), 1);
+    assert_eq!(record.pending_requests().len(), 2);
+    assert!(
+        record
+            .pending_requests()
+            .iter_collect()
+            .all(|(x, _)| *x != middle)
+    );
+}
+
+fn setup_registry_with_group(dir: &Path, min_signers: usize) -> ARID {
+    setup_registry(dir, "alice", BOB_CAROL_AND_DAN);
+
+    record_group(dir, make_group(min_signers))
+}
+
+#[test]
+fn round1_exclude_rejects_dropping_below_min_signers() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 3);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "round1",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--exclude",
+            "Dan",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Excluding Dan would leave 2 participants, fewer than min signers 3",
+    ));
+}
+
+#[test]
+fn round1_exclude_rejects_non_member() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2);
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "add",
+            &fixture("alice_signed_xid.txt"),
+            "Alice",
+        ],
+    )
+    .assert()
+    .success();
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "round1",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--exclude",
+            "Alice",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Alice is not a participant in this group",
+    ));
+}
+
+#[test]
+fn round1_tolerate_rejections_still_fails_on_fetch_errors() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2);
+
+    // Nothing listens on port 1: fetch failures are not rejections
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "round1",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--tolerate-rejections",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Round 1 collection incomplete"));
+
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let group = registry.group(&group_id).unwrap();
+    assert!(group.excluded().is_empty());
+    assert_eq!(group.pending_requests().len(), 3);
+}
+
+/// A parallel Round 1 collection in which Bob and Carol responded and Dan
+/// rejected the invite.
+fn collection_with_rejection() -> CollectionResult<()> {
+    CollectionResult {
+        successes: vec![(fixture_xid("bob"), ()), (fixture_xid("carol"), ())],
+        rejections: vec![(fixture_xid("dan"), "declined".to_string())],
+        errors: vec![],
+        timeouts: vec![],
+        cancelled: vec![],
+        durations: HashMap::new(),
+    }
+}
+
+#[test]
+fn tolerated_rejections_leave_a_complete_collection() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 2);
+    let mut registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let mut collection = collection_with_rejection();
+
+    tolerate_rejections(&mut registry, &group_id, &mut collection).unwrap();
+
+    // The summary sees only the responses that were collected
+    assert!(collection.all_succeeded());
+    assert_eq!(collection.successes.len(), 2);
+    let group = registry.group(&group_id).unwrap();
+    let excluded: Vec<_> = group.excluded().iter().map(|p| *p.xid()).collect();
+    assert_eq!(excluded, vec![fixture_xid("dan")]);
+    assert_eq!(group.participants().len(), 2);
+    assert_eq!(group.pending_requests().len(), 2);
+}
+
+#[test]
+fn tolerated_rejections_still_need_min_signers() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry_with_group(temp.path(), 3);
+    let mut registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let mut collection = collection_with_rejection();
+
+    let err = tolerate_rejections(&mut registry, &group_id, &mut collection)
+        .unwrap_err();
+    assert!(err.to_string().contains("fewer than min signers 3"));
+}
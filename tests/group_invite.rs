@@ -268,3 +268,113 @@ fn test_dkg_group_invite() {
         expected_encrypted_format
     );
 }
+
+#[test]
+fn test_dkg_group_invite_rejects_duplicate_response_arid() {
+    provenance_mark::register_tags();
+
+    let mut rng = make_fake_random_number_generator();
+
+    let date = Date::from_ymd(2025, 12, 31);
+
+    let coordinator = make_xid_document(&mut rng, date);
+
+    let participants: Vec<String> = (0..3)
+        .map(|_| {
+            make_xid_document(&mut rng, date)
+                .to_envelope(
+                    XIDPrivateKeyOptions::default(),
+                    XIDGeneratorOptions::default(),
+                    XIDSigningOptions::Inception,
+                )
+                .unwrap()
+                .ur_string()
+        })
+        .collect();
+
+    let shared_response_arid = make_arid(&mut rng);
+    let response_arids = vec![
+        make_arid(&mut rng),
+        shared_response_arid,
+        shared_response_arid,
+    ];
+
+    let err = DkgInvite::new(
+        make_arid(&mut rng),
+        coordinator,
+        make_arid(&mut rng),
+        date,
+        date + Duration::from_secs(7 * 24 * 60 * 60),
+        2,
+        "Test charter".to_string(),
+        participants,
+        response_arids,
+    )
+    .unwrap_err();
+
+    assert_actual_expected!(
+        err.to_string(),
+        "Response ARIDs must be unique across participants"
+    );
+}
+
+#[test]
+fn test_dkg_group_invite_valid_from() {
+    provenance_mark::register_tags();
+
+    let mut rng = make_fake_random_number_generator();
+
+    let date = Date::from_ymd(2025, 12, 31);
+    let opens = date + Duration::from_secs(24 * 60 * 60);
+    let expiry = date + Duration::from_secs(7 * 24 * 60 * 60);
+
+    let coordinator = make_xid_document(&mut rng, date);
+    let alice = make_xid_document(&mut rng, date);
+    let bob = make_xid_document(&mut rng, date);
+    let participants: Vec<String> = [&alice, &bob]
+        .into_iter()
+        .map(|doc| {
+            doc.clone()
+                .to_envelope(
+                    XIDPrivateKeyOptions::default(),
+                    XIDGeneratorOptions::default(),
+                    XIDSigningOptions::Inception,
+                )
+                .unwrap()
+                .ur_string()
+        })
+        .collect();
+    let response_arids = vec![make_arid(&mut rng), make_arid(&mut rng)];
+
+    let invite = DkgInvite::new(
+        make_arid(&mut rng),
+        coordinator.clone(),
+        make_arid(&mut rng),
+        date,
+        expiry,
+        2,
+        "Test charter".to_string(),
+        participants,
+        response_arids,
+    )
+    .unwrap();
+    assert!(invite.clone().with_valid_from(expiry).is_err());
+    let invite = invite.with_valid_from(opens).unwrap();
+    assert_eq!(invite.valid_from(), Some(opens));
+
+    // A deferred invite still decodes before it opens, but cannot be
+    // answered until `validFrom`
+    let alice_invite = DkgInvitation::from_invite(
+        invite.to_envelope().unwrap(),
+        date,
+        Some(&coordinator),
+        &alice,
+    )
+    .unwrap();
+    assert_eq!(alice_invite.valid_from(), Some(opens));
+    assert_actual_expected!(
+        alice_invite.ensure_open(date).unwrap_err().to_string(),
+        format!("Invitation is not open until {opens}")
+    );
+    alice_invite.ensure_open(opens).unwrap();
+}
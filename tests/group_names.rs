@@ -0,0 +1,150 @@
+use std::{fs, path::Path};
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{fixture_xid, registry_file, run_frost, set_owner};
+use frost_hubert::registry::{GroupParticipant, GroupRecord, Registry};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn group_record(charter: &str) -> GroupRecord {
+    GroupRecord::new(
+        charter.to_string(),
+        2,
+        GroupParticipant::new(fixture_xid("alice")),
+        ["bob", "carol"]
+            .into_iter()
+            .map(|name| GroupParticipant::new(fixture_xid(name)))
+            .collect(),
+    )
+}
+
+/// Records two unnamed groups and returns their IDs.
+fn setup_registry(dir: &Path) -> (ARID, ARID) {
+    set_owner(dir, "alice", None);
+
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    let first = ARID::new();
+    let second = ARID::new();
+    registry
+        .record_group(first, group_record("Treasury"))
+        .unwrap();
+    registry
+        .record_group(second, group_record("Payroll"))
+        .unwrap();
+    registry.save(&path).unwrap();
+    (first, second)
+}
+
+fn rename(dir: &Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    let mut full = vec!["registry", "group", "rename"];
+    full.extend_from_slice(args);
+    run_frost(dir, &full).assert()
+}
+
+#[test]
+fn rename_records_a_unique_name() {
+    let temp = TempDir::new().unwrap();
+    let (first, second) = setup_registry(temp.path());
+
+    rename(temp.path(), &[&first.ur_string(), "treasury"]).success();
+
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let (found, record) = registry.group_by_name("treasury").unwrap();
+    assert_eq!(found, first);
+    assert_eq!(record.name(), Some("treasury"));
+    assert_eq!(registry.group(&second).unwrap().name(), None);
+
+    // A group can be renamed by its current name.
+    rename(temp.path(), &["treasury", "vault"]).success();
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    assert!(registry.group_by_name("treasury").is_none());
+    assert_eq!(registry.group_by_name("vault").unwrap().0, first);
+}
+
+#[test]
+fn commands_resolve_group_names() {
+    let temp = TempDir::new().unwrap();
+    let (first, _) = setup_registry(temp.path());
+    rename(temp.path(), &[&first.ur_string(), "treasury"]).success();
+    let target_path = temp.path().join("target.txt");
+    fs::write(&target_path, Envelope::new("Budget").ur_string()).unwrap();
+
+    // Resolving the name reaches the group's own checks.
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--target",
+            target_path.to_str().unwrap(),
+            "treasury",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Group DKG is not complete"));
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--target",
+            target_path.to_str().unwrap(),
+            "payroll",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("No group named 'payroll'"));
+}
+
+#[test]
+fn duplicate_names_are_rejected() {
+    let temp = TempDir::new().unwrap();
+    let (first, second) = setup_registry(temp.path());
+    rename(temp.path(), &[&first.ur_string(), "treasury"]).success();
+
+    rename(temp.path(), &[&second.ur_string(), "treasury"])
+        .failure()
+        .stderr(predicate::str::contains("already used by group"));
+
+    // Renaming a group to its own name is not a collision.
+    rename(temp.path(), &["treasury", "treasury"]).success();
+
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    assert_eq!(registry.group(&second).unwrap().name(), None);
+}
+
+#[test]
+fn names_cannot_look_like_urs() {
+    let temp = TempDir::new().unwrap();
+    let (first, second) = setup_registry(temp.path());
+
+    rename(temp.path(), &[&first.ur_string(), &second.ur_string()])
+        .failure()
+        .stderr(predicate::str::contains("mistaken for a UR"));
+    rename(temp.path(), &[&first.ur_string(), " padded"])
+        .failure()
+        .stderr(predicate::str::contains("whitespace"));
+}
+
+#[test]
+fn clear_removes_the_name() {
+    let temp = TempDir::new().unwrap();
+    let (first, _) = setup_registry(temp.path());
+    rename(temp.path(), &[&first.ur_string(), "treasury"]).success();
+
+    rename(temp.path(), &["--clear", "treasury"]).success();
+
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    assert!(registry.group_by_name("treasury").is_none());
+    assert_eq!(registry.group(&first).unwrap().name(), None);
+}
@@ -0,0 +1,108 @@
+use bc_components::{ARID, PrivateKeyBase, XID, XIDProvider};
+use bc_envelope::prelude::*;
+use bc_rand::{RandomNumberGenerator, make_fake_random_number_generator};
+use bc_xid::{XIDDocument, XIDGenesisMarkOptions, XIDInceptionKeyOptions};
+use frost_hubert::{
+    cmd::dkg::common::{verify_group_parameters, with_group_parameters},
+    registry::{GroupParticipant, GroupRecord},
+};
+use gstp::SealedRequest;
+use provenance_mark::ProvenanceMarkResolution;
+
+fn make_xid_document(
+    rng: &mut impl RandomNumberGenerator,
+    date: Date,
+) -> XIDDocument {
+    XIDDocument::new(
+        XIDInceptionKeyOptions::PrivateKeyBase(PrivateKeyBase::new_using(rng)),
+        XIDGenesisMarkOptions::Passphrase(
+            "password".to_string(),
+            Some(ProvenanceMarkResolution::Quartile),
+            Some(date),
+            None,
+        ),
+    )
+}
+
+struct Ceremony {
+    coordinator: XIDDocument,
+    participants: Vec<XID>,
+}
+
+impl Ceremony {
+    fn new() -> Self {
+        let mut rng = make_fake_random_number_generator();
+        let date = Date::from_ymd(2025, 12, 31);
+        let coordinator = make_xid_document(&mut rng, date);
+        let participants = (0..3)
+            .map(|_| make_xid_document(&mut rng, date).xid())
+            .collect();
+        Self { coordinator, participants }
+    }
+
+    fn group(&self, charter: &str, min_signers: usize) -> GroupRecord {
+        GroupRecord::new(
+            charter.to_string(),
+            min_signers,
+            GroupParticipant::new(self.coordinator.xid()),
+            self.participants
+                .iter()
+                .copied()
+                .map(GroupParticipant::new)
+                .collect(),
+        )
+    }
+
+    fn round2_request(&self) -> SealedRequest {
+        SealedRequest::new("dkgRound2", ARID::new(), self.coordinator.clone())
+            .with_parameter("group", ARID::new())
+    }
+}
+
+#[test]
+fn unchanged_parameters_are_accepted() {
+    let ceremony = Ceremony::new();
+    let agreed = ceremony.group("Treasury", 2);
+    let request = with_group_parameters(ceremony.round2_request(), &agreed);
+    verify_group_parameters(&request, &agreed).unwrap();
+}
+
+#[test]
+fn requests_without_parameters_are_rejected() {
+    let ceremony = Ceremony::new();
+    let agreed = ceremony.group("Treasury", 2);
+    let err = verify_group_parameters(&ceremony.round2_request(), &agreed)
+        .unwrap_err();
+    assert!(err.to_string().contains("min signers"), "{err}");
+
+    // A request that keeps the threshold but drops the charter
+    let request = ceremony.round2_request().with_parameter("minSigners", 2u64);
+    let err = verify_group_parameters(&request, &agreed).unwrap_err();
+    assert!(err.to_string().contains("group charter"), "{err}");
+}
+
+#[test]
+fn min_signers_changed_between_rounds_is_rejected() {
+    let ceremony = Ceremony::new();
+    let agreed = ceremony.group("Treasury", 2);
+    let mutated = ceremony.group("Treasury", 3);
+    let request = with_group_parameters(ceremony.round2_request(), &mutated);
+    let err = verify_group_parameters(&request, &agreed).unwrap_err();
+    assert!(
+        err.to_string().contains("changed min signers from 2 to 3"),
+        "{err}"
+    );
+}
+
+#[test]
+fn charter_changed_between_rounds_is_rejected() {
+    let ceremony = Ceremony::new();
+    let agreed = ceremony.group("Treasury", 2);
+    let mutated = ceremony.group("Payroll", 2);
+    let request = with_group_parameters(ceremony.round2_request(), &mutated);
+    let err = verify_group_parameters(&request, &agreed).unwrap_err();
+    assert!(
+        err.to_string().contains("changed the group charter"),
+        "{err}"
+    );
+}
@@ -0,0 +1,150 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    path::Path,
+    process::{Child, Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{owner, registry_file, setup_alice_registry};
+use frost_hubert::{
+    cmd::common::GroupStatePaths,
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use gstp::SealedRequest;
+use tempfile::TempDir;
+
+#[test]
+fn lock_group_creates_lock_file_in_group_dir() {
+    let temp = TempDir::new().unwrap();
+    let paths = GroupStatePaths::new(&temp.path().join("registry.json"));
+    let group_id = ARID::new();
+
+    let _lock = paths.lock_group(&group_id).unwrap();
+
+    assert_eq!(
+        paths.lock_path(&group_id),
+        paths.group_dir(&group_id).join("group.lock")
+    );
+    assert!(paths.lock_path(&group_id).is_file());
+    // The lock file is not mistaken for a group
+    assert_eq!(paths.candidate_groups(None).unwrap(), vec![group_id]);
+}
+
+/// Sets up Alice's registry with a signInvite from Bob already received,
+/// returning the session ID. The group has no key package, so a commit that
+/// gets past its checks fails loading it.
+fn setup_received_invite(dir: &Path) -> ARID {
+    setup_alice_registry(dir);
+
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let carol = owner("carol", None);
+    let group_id = ARID::new();
+    let session_id = ARID::new();
+
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    registry
+        .record_group(
+            group_id,
+            GroupRecord::new(
+                "Test group".to_string(),
+                2,
+                GroupParticipant::new(bob.xid()),
+                vec![
+                    GroupParticipant::new(alice.xid()),
+                    GroupParticipant::new(carol.xid()),
+                ],
+            ),
+        )
+        .unwrap();
+    registry.save(&path).unwrap();
+
+    let request_envelope = SealedRequest::new(
+        "signInvite",
+        session_id,
+        bob.xid_document().clone(),
+    )
+    .with_parameter("group", group_id)
+    .with_parameter("session", session_id)
+    .to_envelope(
+        Some(Date::with_duration_from_now(Duration::from_secs(3600))),
+        Some(bob.xid_document().inception_private_keys().unwrap()),
+        Some(alice.xid_document()),
+    )
+    .unwrap();
+    let state = serde_json::json!({
+        "request_envelope": request_envelope.ur_string(),
+        "group": group_id.ur_string(),
+        "session": session_id.ur_string(),
+        "coordinator": bob.xid().ur_string(),
+        "min_signers": 2,
+        "response_arid": ARID::new().ur_string(),
+        "participants": [alice.xid().ur_string(), carol.xid().ur_string()],
+        "target": Envelope::new("Budget").ur_string(),
+    });
+    let state_path =
+        GroupStatePaths::new(&path).receive_state_path(&group_id, &session_id);
+    fs::create_dir_all(state_path.parent().unwrap()).unwrap();
+    fs::write(&state_path, serde_json::to_vec_pretty(&state).unwrap()).unwrap();
+
+    session_id
+}
+
+fn spawn_commit(dir: &Path, session_id: &ARID, extra: &[&str]) -> Child {
+    Command::new(env!("CARGO_BIN_EXE_frost"))
+        .current_dir(dir)
+        .args(["sign", "participant", "round1", "--preview"])
+        .args(extra)
+        .arg(session_id.ur_string())
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap()
+}
+
+#[test]
+fn concurrent_session_commands_run_one_at_a_time() {
+    let temp = TempDir::new().unwrap();
+    let session_id = setup_received_invite(temp.path());
+
+    // The first commit takes the group lock and then waits at the target
+    // confirmation prompt
+    let mut first =
+        spawn_commit(temp.path(), &session_id, &["--confirm-target"]);
+    let mut prompt = Vec::new();
+    let mut byte = [0u8];
+    let mut first_stderr = first.stderr.take().unwrap();
+    while !String::from_utf8_lossy(&prompt).contains("Type 'yes'") {
+        assert_eq!(first_stderr.read(&mut byte).unwrap(), 1);
+        prompt.push(byte[0]);
+    }
+
+    // A second commit for the same session has to wait for it
+    let mut second = spawn_commit(temp.path(), &session_id, &[]);
+    thread::sleep(Duration::from_millis(500));
+    assert!(second.try_wait().unwrap().is_none());
+
+    first.stdin.take().unwrap().write_all(b"no\n").unwrap();
+    let mut rest = String::new();
+    first_stderr.read_to_string(&mut rest).unwrap();
+    assert!(rest.contains("Target not confirmed"));
+    assert!(!first.wait().unwrap().success());
+
+    // Once the first has released the lock, the second runs its checks
+    let mut second_stderr = String::new();
+    second
+        .stderr
+        .take()
+        .unwrap()
+        .read_to_string(&mut second_stderr)
+        .unwrap();
+    assert!(second_stderr.contains("Key package path not found"));
+    assert!(!second.wait().unwrap().success());
+}
@@ -0,0 +1,56 @@
+use std::{fs, path::Path};
+
+use bc_components::ARID;
+use frost_hubert::cmd::{
+    common::GroupStatePaths, sign::common::load_public_key_package,
+};
+use tempfile::TempDir;
+
+#[test]
+fn group_state_paths_follow_registry_layout() {
+    let group_id = ARID::new();
+    let session_id = ARID::new();
+    let paths = GroupStatePaths::new(Path::new("/tmp/team/registry.json"));
+
+    let group_dir = Path::new("/tmp/team/group-state").join(group_id.hex());
+    let session_dir = group_dir.join("signing").join(session_id.hex());
+    assert_eq!(paths.root(), Path::new("/tmp/team/group-state"));
+    assert_eq!(paths.group_dir(&group_id), group_dir);
+    assert_eq!(
+        paths.collected_finalize_path(&group_id),
+        group_dir.join("collected_finalize.json")
+    );
+    assert_eq!(paths.session_dir(&group_id, &session_id), session_dir);
+    assert_eq!(
+        paths.final_state_path(&group_id, &session_id),
+        session_dir.join("final.json")
+    );
+
+    // A bare registry filename resolves against the current directory
+    let paths = GroupStatePaths::new(Path::new("registry.json"));
+    assert_eq!(paths.root(), Path::new("group-state"));
+}
+
+#[test]
+fn candidate_groups_lists_group_directories() {
+    let temp = TempDir::new().unwrap();
+    let paths = GroupStatePaths::new(&temp.path().join("registry.json"));
+    assert!(paths.candidate_groups(None).unwrap().is_empty());
+
+    let group_id = ARID::new();
+    fs::create_dir_all(paths.group_dir(&group_id)).unwrap();
+    fs::create_dir_all(paths.root().join("not-a-group")).unwrap();
+    assert_eq!(paths.candidate_groups(None).unwrap(), vec![group_id]);
+
+    let hint = ARID::new();
+    assert_eq!(paths.candidate_groups(Some(hint)).unwrap(), vec![hint]);
+}
+
+#[test]
+fn load_public_key_package_reports_missing_state() {
+    let temp = TempDir::new().unwrap();
+    let paths = GroupStatePaths::new(&temp.path().join("registry.json"));
+
+    let err = load_public_key_package(&paths, &ARID::new()).unwrap_err();
+    assert!(err.to_string().contains("Public key package not found"));
+}
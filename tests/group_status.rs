@@ -0,0 +1,64 @@
+use std::{fs, path::Path};
+
+mod common;
+use bc_components::{ARID, Ed25519PrivateKey, SigningPrivateKey};
+use bc_envelope::prelude::*;
+use common::{fixture_xid, record_group, run_frost, set_owner};
+use frost_hubert::registry::{GroupParticipant, GroupRecord, GroupStatus};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn group_record() -> GroupRecord {
+    GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(fixture_xid("alice")),
+        ["bob", "carol"]
+            .into_iter()
+            .map(|name| GroupParticipant::new(fixture_xid(name)))
+            .collect(),
+    )
+}
+
+fn setup_registry(dir: &Path, record: GroupRecord) -> ARID {
+    set_owner(dir, "alice", None);
+
+    record_group(dir, record)
+}
+
+#[test]
+fn group_is_finalized_once_verifying_key_is_recorded() {
+    let mut record = group_record();
+    assert_eq!(record.status(), GroupStatus::Pending);
+
+    record.set_verifying_key(
+        SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new())
+            .public_key()
+            .unwrap(),
+    );
+    assert_eq!(record.status(), GroupStatus::Finalized);
+}
+
+#[test]
+fn sign_invite_rejects_unfinalized_group() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_registry(temp.path(), group_record());
+    let target_path = temp.path().join("target.txt");
+    fs::write(&target_path, Envelope::new("Budget").ur_string()).unwrap();
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--target",
+            target_path.to_str().unwrap(),
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Group DKG is not complete"));
+}
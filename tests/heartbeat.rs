@@ -0,0 +1,194 @@
+use std::path::Path;
+
+mod common;
+use bc_components::{ARID, SymmetricKey};
+use bc_envelope::prelude::*;
+use common::{fixture_xid, owner, record_group, run_frost, set_owner};
+use frost_hubert::{
+    dkg::{HEARTBEAT_SLOT_SECS, Heartbeat},
+    registry::{GroupParticipant, GroupRecord, OwnerRecord},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn heartbeat_verifies_against_its_signer() {
+    let alice = owner("alice", None);
+    let date = Date::from_timestamp(1_700_000_000.0);
+    let heartbeat = Heartbeat::new(ARID::new(), alice.xid(), date);
+    let signed =
+        heartbeat.sign(alice.xid_document().inception_private_keys().unwrap());
+
+    let verified = Heartbeat::verify(&signed, alice.xid_document()).unwrap();
+    assert_eq!(verified, heartbeat);
+
+    // Another participant's key does not vouch for Alice being online
+    let err = Heartbeat::verify(&signed, owner("bob", None).xid_document())
+        .unwrap_err();
+    assert!(err.to_string().contains("not signed by the participant"));
+}
+
+#[test]
+fn shared_secret_is_known_only_to_the_pair() {
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let carol = owner("carol", None);
+    let keys = |record: &OwnerRecord| {
+        record
+            .xid_document()
+            .inception_private_keys()
+            .unwrap()
+            .clone()
+    };
+
+    let secret =
+        Heartbeat::shared_secret(&keys(&alice), bob.xid_document()).unwrap();
+    assert_eq!(
+        secret,
+        Heartbeat::shared_secret(&keys(&bob), alice.xid_document()).unwrap()
+    );
+    assert_ne!(
+        secret,
+        Heartbeat::shared_secret(&keys(&carol), alice.xid_document()).unwrap()
+    );
+}
+
+#[test]
+fn status_arid_is_per_secret_group_participant_and_slot() {
+    let group_id = ARID::new();
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let secret = Heartbeat::shared_secret(
+        alice.xid_document().inception_private_keys().unwrap(),
+        bob.xid_document(),
+    )
+    .unwrap();
+    let (alice, bob) = (alice.xid(), bob.xid());
+
+    let arid = Heartbeat::status_arid(&secret, &group_id, &alice, 7);
+    assert_eq!(arid, Heartbeat::status_arid(&secret, &group_id, &alice, 7));
+    assert_ne!(arid, Heartbeat::status_arid(&secret, &group_id, &alice, 8));
+    assert_ne!(arid, Heartbeat::status_arid(&secret, &group_id, &bob, 7));
+    assert_ne!(
+        arid,
+        Heartbeat::status_arid(&secret, &ARID::new(), &alice, 7)
+    );
+    // Without the secret, the group ID, XID and slot do not locate the slot
+    assert_ne!(
+        arid,
+        Heartbeat::status_arid(&SymmetricKey::new(), &group_id, &alice, 7)
+    );
+
+    let date = Date::from_timestamp((7 * HEARTBEAT_SLOT_SECS + 30) as f64);
+    assert_eq!(Heartbeat::slot_of(&date), 7);
+    assert_eq!(Heartbeat::new(group_id, alice, date).arid(&secret), arid);
+}
+
+/// `owner_name` owns the registry, which records a group of Bob and Carol
+/// coordinated by `coordinator`.
+fn setup_group(dir: &Path, owner_name: &str, coordinator: &str) -> ARID {
+    set_owner(dir, owner_name, None);
+    record_group(
+        dir,
+        GroupRecord::new(
+            "Test group".to_string(),
+            2,
+            GroupParticipant::new(owner(coordinator, None).xid()),
+            ["bob", "carol"]
+                .into_iter()
+                .map(|name| GroupParticipant::new(fixture_xid(name)))
+                .collect(),
+        ),
+    )
+}
+
+#[test]
+fn heartbeat_send_requires_group_membership() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_group(temp.path(), "dan", "bob");
+
+    run_frost(
+        temp.path(),
+        &[
+            "heartbeat",
+            "send",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("is not a member of this group"));
+}
+
+#[test]
+fn heartbeat_status_is_coordinator_only() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_group(temp.path(), "carol", "bob");
+
+    run_frost(
+        temp.path(),
+        &[
+            "heartbeat",
+            "status",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Only the group's coordinator can run this command",
+    ));
+}
+
+#[test]
+fn heartbeat_send_is_for_participants() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_group(temp.path(), "bob", "bob");
+
+    run_frost(
+        temp.path(),
+        &[
+            "heartbeat",
+            "send",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "The coordinator does not post heartbeats",
+    ));
+}
+
+#[test]
+fn heartbeat_status_json_conflicts_with_output() {
+    let temp = TempDir::new().unwrap();
+    let group_id = setup_group(temp.path(), "bob", "bob");
+
+    run_frost(
+        temp.path(),
+        &[
+            "heartbeat",
+            "status",
+            "--json",
+            "--output",
+            "tsv",
+            &group_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
@@ -0,0 +1,325 @@
+mod common;
+
+use std::time::Duration;
+
+use bc_components::{ARID, PrivateKeyBase, XID, XIDProvider};
+use bc_envelope::prelude::*;
+use bc_rand::{RandomNumberGenerator, make_fake_random_number_generator};
+use bc_xid::{
+    XIDDocument, XIDGeneratorOptions, XIDGenesisMarkOptions,
+    XIDInceptionKeyOptions, XIDPrivateKeyOptions, XIDSigningOptions,
+};
+use common::{
+    BOB_CAROL_AND_DAN, registry_file, run_frost, setup_alice_registry,
+    setup_registry,
+};
+use frost_hubert::{
+    DkgInvitation, DkgInvite,
+    dkg::IdentifierScheme,
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use predicates::prelude::*;
+use provenance_mark::ProvenanceMarkResolution;
+use tempfile::TempDir;
+
+fn make_xid_document(
+    rng: &mut impl RandomNumberGenerator,
+    date: Date,
+) -> XIDDocument {
+    XIDDocument::new(
+        XIDInceptionKeyOptions::PrivateKeyBase(PrivateKeyBase::new_using(rng)),
+        XIDGenesisMarkOptions::Passphrase(
+            "password".to_string(),
+            Some(ProvenanceMarkResolution::Quartile),
+            Some(date),
+            None,
+        ),
+    )
+}
+
+fn make_arid(rng: &mut impl RandomNumberGenerator) -> ARID {
+    ARID::from_data_ref(rng.random_data(ARID::ARID_SIZE)).unwrap()
+}
+
+fn signed_ur(document: &XIDDocument) -> String {
+    document
+        .clone()
+        .to_envelope(
+            XIDPrivateKeyOptions::default(),
+            XIDGeneratorOptions::default(),
+            XIDSigningOptions::Inception,
+        )
+        .unwrap()
+        .ur_string()
+}
+
+fn make_xids(count: usize) -> Vec<XID> {
+    let mut rng = make_fake_random_number_generator();
+    let date = Date::from_ymd(2025, 12, 31);
+    let mut xids: Vec<XID> = (0..count)
+        .map(|_| make_xid_document(&mut rng, date).xid())
+        .collect();
+    xids.sort();
+    xids
+}
+
+#[test]
+fn hashed_identifiers_ignore_roster_changes() {
+    let xids = make_xids(3);
+    let without_first = &xids[1..];
+
+    let full = IdentifierScheme::Hashed.identifiers(&xids).unwrap();
+    let reduced = IdentifierScheme::Hashed.identifiers(without_first).unwrap();
+    for xid in without_first {
+        assert_eq!(full[xid], reduced[xid]);
+    }
+
+    // Index identifiers shift when an earlier participant leaves
+    let full = IdentifierScheme::Index.identifiers(&xids).unwrap();
+    let reduced = IdentifierScheme::Index.identifiers(without_first).unwrap();
+    assert_ne!(full[&xids[1]], reduced[&xids[1]]);
+}
+
+#[test]
+fn identifier_scheme_parses_and_displays() {
+    for scheme in [IdentifierScheme::Index, IdentifierScheme::Hashed] {
+        assert_eq!(
+            scheme.to_string().parse::<IdentifierScheme>().unwrap(),
+            scheme
+        );
+    }
+    assert_eq!(IdentifierScheme::default(), IdentifierScheme::Index);
+    assert!("sequential".parse::<IdentifierScheme>().is_err());
+}
+
+#[test]
+fn group_record_omits_default_identifier_scheme() {
+    let xids = make_xids(3);
+    let mut record = GroupRecord::new(
+        String::new(),
+        2,
+        GroupParticipant::new(xids[0]),
+        xids[1..]
+            .iter()
+            .copied()
+            .map(GroupParticipant::new)
+            .collect(),
+    );
+    let json = serde_json::to_string(&record).unwrap();
+    assert!(!json.contains("identifier_scheme"));
+    assert!(!json.contains("identifier_map"));
+
+    record.set_identifier_scheme(IdentifierScheme::Hashed);
+    let json = serde_json::to_string(&record).unwrap();
+    assert!(json.contains(r#""identifier_scheme":"hashed""#));
+    let decoded: GroupRecord = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.identifier_scheme(), IdentifierScheme::Hashed);
+    assert_eq!(
+        decoded.identifiers().unwrap(),
+        IdentifierScheme::Hashed
+            .identifiers(&decoded.identifier_order())
+            .unwrap()
+    );
+}
+
+/// Alice invites Bob, Carol, and Dan under `scheme`, and each of them
+/// answers with `dkg participant round1` in a registry of their own. The
+/// group record is persisted before the upload to the unreachable server
+/// fails; returns each participant's record.
+fn answer_invite(scheme: IdentifierScheme) -> Vec<GroupRecord> {
+    let alice = TempDir::new().unwrap();
+    setup_registry(alice.path(), "alice", BOB_CAROL_AND_DAN);
+    let scheme = scheme.to_string();
+    let output = run_frost(
+        alice.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--identifier-scheme",
+            &scheme,
+            "Bob",
+            "Carol",
+            "Dan",
+        ],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let invite = String::from_utf8(output.stdout).unwrap().trim().to_owned();
+
+    let everyone = [
+        ("alice", "Alice"),
+        ("bob", "Bob"),
+        ("carol", "Carol"),
+        ("dan", "Dan"),
+    ];
+    ["bob", "carol", "dan"]
+        .into_iter()
+        .map(|name| {
+            let dir = TempDir::new().unwrap();
+            let others: Vec<(&str, &str)> = everyone
+                .into_iter()
+                .filter(|(other, _)| *other != name)
+                .collect();
+            setup_registry(dir.path(), name, &others);
+            run_frost(
+                dir.path(),
+                &[
+                    "dkg",
+                    "participant",
+                    "round1",
+                    "--storage",
+                    "server",
+                    "--port",
+                    "1",
+                    &invite,
+                ],
+            )
+            .assert()
+            .failure();
+
+            let registry = Registry::load(&registry_file(dir.path())).unwrap();
+            let (_, record) = registry.groups().next().unwrap();
+            record.clone()
+        })
+        .collect()
+}
+
+#[test]
+fn participants_persist_identical_identifier_maps() {
+    for scheme in [IdentifierScheme::Index, IdentifierScheme::Hashed] {
+        let records = answer_invite(scheme);
+        let map = records[0].identifiers().unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            map,
+            scheme.identifiers(&records[0].identifier_order()).unwrap()
+        );
+        let persisted = serde_json::to_value(&records[0]).unwrap();
+        assert!(persisted["identifier_map"].is_object());
+        for record in &records[1..] {
+            assert_eq!(record.identifiers().unwrap(), map);
+            assert_eq!(
+                serde_json::to_value(record).unwrap()["identifier_map"],
+                persisted["identifier_map"]
+            );
+        }
+    }
+}
+
+#[test]
+fn persisted_identifier_map_is_not_recomputed() {
+    let mut record = answer_invite(IdentifierScheme::Index).remove(0);
+    let recorded = record.identifiers().unwrap();
+
+    // Changing the scheme afterwards must not move anyone's identifier
+    record.set_identifier_scheme(IdentifierScheme::Hashed);
+    assert_eq!(record.identifiers().unwrap(), recorded);
+    assert_ne!(
+        IdentifierScheme::Hashed
+            .identifiers(&record.identifier_order())
+            .unwrap(),
+        recorded
+    );
+}
+
+#[test]
+fn invite_carries_identifier_scheme() {
+    provenance_mark::register_tags();
+
+    let mut rng = make_fake_random_number_generator();
+    let date = Date::from_ymd(2025, 12, 31);
+    let expiry = date + Duration::from_secs(60 * 60);
+
+    let coordinator = make_xid_document(&mut rng, date);
+    let alice = make_xid_document(&mut rng, date);
+    let bob = make_xid_document(&mut rng, date);
+
+    let invite = DkgInvite::new(
+        make_arid(&mut rng),
+        coordinator.clone(),
+        make_arid(&mut rng),
+        date,
+        expiry,
+        2,
+        "Test charter".to_string(),
+        vec![signed_ur(&alice), signed_ur(&bob)],
+        vec![make_arid(&mut rng), make_arid(&mut rng)],
+    )
+    .unwrap();
+
+    let decode = |invite: &DkgInvite| {
+        DkgInvitation::from_invite(
+            invite.to_envelope().unwrap(),
+            date,
+            Some(&coordinator),
+            &alice,
+        )
+        .unwrap()
+    };
+    assert_eq!(decode(&invite).identifier_scheme(), IdentifierScheme::Index);
+
+    let invite = invite
+        .with_identifier_scheme(IdentifierScheme::Hashed)
+        .unwrap();
+    assert_eq!(
+        decode(&invite).identifier_scheme(),
+        IdentifierScheme::Hashed
+    );
+}
+
+#[test]
+fn inspect_shows_hashed_identifier_scheme() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+
+    let output = run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--identifier-scheme",
+            "hashed",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let preview = String::from_utf8(output.stdout).unwrap();
+
+    run_frost(
+        temp.path(),
+        &["dkg", "coordinator", "inspect", preview.trim()],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Identifier scheme: hashed"));
+}
+
+#[test]
+fn invite_rejects_unknown_identifier_scheme() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--identifier-scheme",
+            "sequential",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Unknown identifier scheme"));
+}
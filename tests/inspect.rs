@@ -0,0 +1,66 @@
+mod common;
+use bc_components::SymmetricKey;
+use bc_envelope::prelude::*;
+use common::run_frost;
+use frost_hubert::cmd::inspect::format_tree;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn budget() -> Envelope {
+    Envelope::new("Budget")
+        .add_assertion("note", "Q3")
+        .add_assertion("amount", 42)
+}
+
+#[test]
+fn tree_shows_roles_types_and_depth() {
+    let tree = format_tree(&budget().wrap()).unwrap();
+    let lines: Vec<&str> = tree.lines().collect();
+
+    assert_eq!(lines[0], "envelope: wrapped");
+    assert_eq!(lines[1], "  content: node (2 assertions)");
+    assert_eq!(lines[2], "    subject: text \"Budget\"");
+    assert!(lines.contains(&"      predicate: text \"note\""), "{tree}");
+    assert!(lines.contains(&"      object: number 42"), "{tree}");
+}
+
+#[test]
+fn tree_marks_encrypted_and_elided_nodes() {
+    let envelope = budget();
+    let note = envelope.assertion_with_predicate("note").unwrap();
+    let elided = envelope.elide_removing_target(&note);
+    let tree = format_tree(&elided).unwrap();
+    assert!(tree.contains("  assertion: ELIDED ["), "{tree}");
+
+    let encrypted = budget().encrypt_subject(&SymmetricKey::new()).unwrap();
+    let tree = format_tree(&encrypted).unwrap();
+    assert!(tree.contains("  subject: ENCRYPTED ["), "{tree}");
+}
+
+#[test]
+fn inspect_prints_tree_or_envelope_notation() {
+    let temp = TempDir::new().unwrap();
+    let ur = budget().ur_string();
+
+    run_frost(temp.path(), &["inspect", "--format", "tree", &ur])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("envelope: node (2 assertions)"));
+
+    run_frost(temp.path(), &["inspect", &ur])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"Budget\" ["));
+}
+
+#[test]
+fn inspect_rejects_other_urs() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &["inspect", &bc_components::ARID::new().ur_string()],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Expected a ur:envelope"));
+}
@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+
+mod common;
+use bc_components::XID;
+use bc_envelope::prelude::*;
+use common::{BOB_CAROL_AND_DAN, fixture_xid, run_frost, setup_registry};
+use frost_ed25519::{self as frost, rand_core::OsRng};
+use frost_hubert::cmd::sign::common::check_commitment_quorum;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// Signs with the first `signers` members of a dealt 3-of-3 group and
+/// verifies the aggregate signature against the group key.
+fn sign_with(signers: usize) -> Result<(), frost::Error> {
+    let (shares, public_key_package) = frost::keys::generate_with_dealer(
+        3,
+        3,
+        frost::keys::IdentifierList::Default,
+        OsRng,
+    )?;
+    let key_packages: BTreeMap<_, _> = shares
+        .into_iter()
+        .take(signers)
+        .map(|(id, share)| Ok((id, frost::keys::KeyPackage::try_from(share)?)))
+        .collect::<Result<_, frost::Error>>()?;
+
+    let mut nonces = BTreeMap::new();
+    let mut commitments = BTreeMap::new();
+    for (id, key_package) in &key_packages {
+        let (nonce, commitment) =
+            frost::round1::commit(key_package.signing_share(), &mut OsRng);
+        nonces.insert(*id, nonce);
+        commitments.insert(*id, commitment);
+    }
+
+    let message = b"n-of-n";
+    let signing_package = frost::SigningPackage::new(commitments, message);
+    let mut signature_shares = BTreeMap::new();
+    for (id, key_package) in &key_packages {
+        let share =
+            frost::round2::sign(&signing_package, &nonces[id], key_package)?;
+        signature_shares.insert(*id, share);
+    }
+    let signature = frost::aggregate(
+        &signing_package,
+        &signature_shares,
+        &public_key_package,
+    )?;
+    public_key_package
+        .verifying_key()
+        .verify(message, &signature)
+}
+
+#[test]
+fn invite_notes_n_of_n_groups() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", BOB_CAROL_AND_DAN);
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "Bob",
+            "Carol",
+            "Dan",
+        ],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains(
+        "this is an n-of-n group (3 of 3); all participants must sign",
+    ));
+
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "invite",
+            "--preview",
+            "--min-signers",
+            "2",
+            "Bob",
+            "Carol",
+            "Dan",
+        ],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains("n-of-n").not());
+}
+
+#[test]
+fn n_of_n_quorum_requires_every_participant() {
+    let participants: Vec<XID> = ["bob", "carol", "dan"]
+        .into_iter()
+        .map(fixture_xid)
+        .collect();
+
+    check_commitment_quorum(&participants, &participants, 3, false).unwrap();
+
+    for resume in [false, true] {
+        let err = check_commitment_quorum(
+            &participants,
+            &participants[..2],
+            3,
+            resume,
+        )
+        .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("This is an n-of-n group"), "{message}");
+        assert!(message.contains("2 of 3 committed"), "{message}");
+        assert!(message.contains(&participants[2].ur_string()), "{message}");
+    }
+}
+
+#[test]
+fn threshold_quorum_resumes_at_min_signers() {
+    let participants: Vec<XID> = ["bob", "carol", "dan"]
+        .into_iter()
+        .map(fixture_xid)
+        .collect();
+
+    check_commitment_quorum(&participants, &participants[..2], 2, true)
+        .unwrap();
+    let err =
+        check_commitment_quorum(&participants, &participants[..2], 2, false)
+            .unwrap_err();
+    assert!(err.to_string().contains("Missing signInvite responses"));
+}
+
+#[test]
+fn n_of_n_group_signs_only_with_every_participant() {
+    sign_with(3).unwrap();
+    assert!(sign_with(2).is_err());
+}
@@ -0,0 +1,17 @@
+use frost_hubert::cmd::parallel::{
+    Direction, failure_marker, spinner_template, success_marker,
+};
+
+#[test]
+fn no_color_env_selects_plain_templates() {
+    // SAFETY: this is the only test in this binary, so no other thread reads
+    // the environment concurrently.
+    unsafe { std::env::set_var("NO_COLOR", "1") };
+
+    assert_eq!(spinner_template(), "[..]");
+    assert_eq!(success_marker(), "[ok]");
+    assert_eq!(failure_marker(), "[x]");
+    assert_eq!(Direction::Get.emoji(), "[get]");
+    assert_eq!(Direction::Put.emoji(), "[put]");
+    assert!(!spinner_template().contains(':'));
+}
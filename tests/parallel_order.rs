@@ -0,0 +1,91 @@
+mod common;
+use std::sync::Arc;
+
+use bc_components::{ARID, XID};
+use common::fixture_xid;
+use frost_hubert::cmd::{
+    parallel::{ParallelFetchConfig, parallel_fetch},
+    storage::{StorageClient, StorageSelection},
+};
+
+#[test]
+fn parallel_fetch_orders_results_by_xid() {
+    let mut xids: Vec<XID> = ["alice", "bob", "carol", "dan"]
+        .into_iter()
+        .map(fixture_xid)
+        .collect();
+    xids.sort();
+    xids.reverse();
+    let requests: Vec<(XID, ARID, String)> = xids
+        .iter()
+        .map(|xid| (*xid, ARID::new(), "participant".to_owned()))
+        .collect();
+
+    // Nothing listens on port 1, so every request fails or times out; the
+    // point is that each bucket comes back sorted regardless of which task
+    // finished first
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let collection = runtime
+        .block_on(async {
+            let client =
+                StorageClient::from_selection(StorageSelection::Server {
+                    host: "127.0.0.1".to_owned(),
+                    port: 1,
+                })
+                .await
+                .unwrap();
+            parallel_fetch(
+                Arc::new(client),
+                requests,
+                ParallelFetchConfig::with_timeout(Some(1)),
+                |_, _| Ok(()),
+            )
+            .await
+        })
+        .unwrap();
+
+    let error_xids: Vec<XID> =
+        collection.errors.iter().map(|(xid, _)| *xid).collect();
+    assert!(error_xids.is_sorted());
+    assert!(collection.timeouts.is_sorted());
+    assert_eq!(error_xids.len() + collection.timeouts.len(), xids.len());
+}
+
+#[test]
+fn unmet_min_success_still_waits_for_everyone() {
+    let xids: Vec<XID> = ["alice", "bob", "carol"]
+        .into_iter()
+        .map(fixture_xid)
+        .collect();
+    let requests: Vec<(XID, ARID, String)> = xids
+        .iter()
+        .map(|xid| (*xid, ARID::new(), "participant".to_owned()))
+        .collect();
+
+    // No fetch can succeed against port 1, so the threshold is never reached
+    // and every participant gets an outcome instead of being cancelled
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let collection = runtime
+        .block_on(async {
+            let client =
+                StorageClient::from_selection(StorageSelection::Server {
+                    host: "127.0.0.1".to_owned(),
+                    port: 1,
+                })
+                .await
+                .unwrap();
+            parallel_fetch(
+                Arc::new(client),
+                requests,
+                ParallelFetchConfig::with_timeout(Some(1))
+                    .with_min_success(Some(2)),
+                |_, _| Ok(()),
+            )
+            .await
+        })
+        .unwrap();
+
+    assert!(collection.cancelled.is_empty());
+    assert!(!collection.can_proceed(2));
+    assert_eq!(collection.total(), xids.len());
+}
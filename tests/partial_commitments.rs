@@ -0,0 +1,129 @@
+use std::{fs, path::Path};
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{
+    BOB_CAROL_AND_DAN, fixture_xid, record_group, registry_file, run_frost,
+    setup_registry,
+};
+use frost_ed25519::{self as frost, keys::IdentifierList, rand_core::OsRng};
+use frost_hubert::{
+    cmd::common::GroupStatePaths,
+    registry::{GroupParticipant, GroupRecord},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn commitments() -> frost::round1::SigningCommitments {
+    let (secret_shares, _) =
+        frost::keys::generate_with_dealer(2, 2, IdentifierList::Default, OsRng)
+            .unwrap();
+    let share = secret_shares.into_values().next().unwrap();
+    let key_package = frost::keys::KeyPackage::try_from(share).unwrap();
+    let (_, commitments) =
+        frost::round1::commit(key_package.signing_share(), &mut OsRng);
+    commitments
+}
+
+fn write_json(file: &Path, value: serde_json::Value) {
+    fs::create_dir_all(file.parent().unwrap()).unwrap();
+    fs::write(file, serde_json::to_vec_pretty(&value).unwrap()).unwrap();
+}
+
+/// A 2-of-3 signing session of Bob, Carol, and Dan coordinated by Alice, in
+/// which an earlier run saved Bob's commitments as partial progress.
+fn interrupted_session(dir: &Path) -> (GroupStatePaths, ARID, ARID) {
+    setup_registry(dir, "alice", BOB_CAROL_AND_DAN);
+    let [alice, bob, carol, dan] =
+        ["alice", "bob", "carol", "dan"].map(fixture_xid);
+    let group_id = record_group(
+        dir,
+        GroupRecord::new(
+            "Test group".to_string(),
+            2,
+            GroupParticipant::new(alice),
+            [bob, carol, dan].map(GroupParticipant::new).to_vec(),
+        ),
+    );
+
+    let session_id = ARID::new();
+    let target = Envelope::new("Budget").ur_string();
+    let paths = GroupStatePaths::new(&registry_file(dir));
+    let participants: serde_json::Map<_, _> = [bob, carol, dan]
+        .iter()
+        .map(|xid| {
+            (
+                xid.ur_string(),
+                serde_json::json!({
+                    "commit_arid": ARID::new().ur_string(),
+                    "share_arid": ARID::new().ur_string(),
+                }),
+            )
+        })
+        .collect();
+    write_json(
+        &paths.start_state_path(&group_id, &session_id),
+        serde_json::json!({
+            "group": group_id.ur_string(),
+            "session_id": session_id.ur_string(),
+            "min_signers": 2,
+            "participants": participants,
+            "target": target,
+        }),
+    );
+    let mut saved = serde_json::Map::new();
+    saved.insert(
+        bob.ur_string(),
+        serde_json::json!({
+            "commitments": commitments(),
+            "share_arid": ARID::new().ur_string(),
+            "send_to_arid": ARID::new().ur_string(),
+        }),
+    );
+    write_json(
+        &paths.partial_commitments_path(&group_id, &session_id),
+        serde_json::json!({
+            "group": group_id.ur_string(),
+            "session": session_id.ur_string(),
+            "target": target,
+            "commitments": saved,
+        }),
+    );
+    (paths, group_id, session_id)
+}
+
+#[test]
+fn failed_collection_leaves_no_commitments_file() {
+    let temp = TempDir::new().unwrap();
+    let (paths, group_id, session_id) = interrupted_session(temp.path());
+
+    // The resumed run picks up Bob's saved commitments, then fails for lack
+    // of quorum when Carol and Dan cannot be fetched
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "round1",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--resume",
+            &session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Only 1 of 2 required commitments collected",
+    ));
+
+    assert!(!paths.commitments_path(&group_id, &session_id).exists());
+    assert!(
+        paths
+            .partial_commitments_path(&group_id, &session_id)
+            .exists()
+    );
+}
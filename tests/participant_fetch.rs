@@ -0,0 +1,88 @@
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{fixture, registry_file, run_frost};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn add(dir: &std::path::Path, extra: &[&str]) -> assert_cmd::Command {
+    let mut args = vec!["registry", "participant", "add"];
+    args.extend_from_slice(extra);
+    run_frost(dir, &args)
+}
+
+#[test]
+fn fetch_requires_storage() {
+    let temp = TempDir::new().unwrap();
+    add(temp.path(), &["--fetch", &ARID::new().ur_string(), "Bob"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--fetch requires Hubert storage options",
+        ));
+    assert!(!registry_file(temp.path()).exists());
+}
+
+#[test]
+fn fetch_takes_only_a_pet_name() {
+    let temp = TempDir::new().unwrap();
+    add(
+        temp.path(),
+        &[
+            "--fetch",
+            &ARID::new().ur_string(),
+            &fixture("bob_signed_xid.txt"),
+            "Bob",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "the only positional argument is the pet name",
+    ));
+}
+
+#[test]
+fn fetch_from_unreachable_server_fails_without_writing() {
+    let temp = TempDir::new().unwrap();
+    add(
+        temp.path(),
+        &[
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--fetch",
+            &ARID::new().ur_string(),
+            "Bob",
+        ],
+    )
+    .assert()
+    .failure();
+    assert!(!registry_file(temp.path()).exists());
+}
+
+#[test]
+fn expect_xid_requires_fetch() {
+    let temp = TempDir::new().unwrap();
+    add(
+        temp.path(),
+        &[
+            "--expect-xid",
+            "ur:xid/hdcxuysflgfsmwjseozmhplehywpwdcnfwmtvskkkbtieerpsfmtwegoiysaeeylfsecdsfxhljz",
+            &fixture("bob_signed_xid.txt"),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("--fetch"));
+}
+
+#[test]
+fn document_is_required_without_fetch() {
+    let temp = TempDir::new().unwrap();
+    add(temp.path(), &[])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("XID_DOCUMENT"));
+}
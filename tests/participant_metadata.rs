@@ -0,0 +1,238 @@
+use std::fs;
+
+use bc_envelope::prelude::*;
+use frost_hubert::registry::Registry;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::{fixture, registry_file, run_frost};
+
+#[test]
+fn participant_add_records_metadata() {
+    let temp = TempDir::new().unwrap();
+
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "add",
+            "--note",
+            "Treasury desk",
+            "--contact",
+            "mailto:bob@example.com",
+            "--tag",
+            "signer",
+            "--tag",
+            "ops",
+            &fixture("bob_signed_xid.txt"),
+            "Bob",
+        ],
+    )
+    .assert()
+    .success();
+
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let (_, bob) = registry.participant_by_pet_name("Bob").unwrap();
+    assert_eq!(bob.note(), Some("Treasury desk"));
+    assert_eq!(bob.contact(), Some("mailto:bob@example.com"));
+    assert_eq!(bob.tags(), ["signer", "ops"]);
+}
+
+#[test]
+fn records_without_metadata_still_load() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "add",
+            &fixture("bob_signed_xid.txt"),
+            "Bob",
+        ],
+    )
+    .assert()
+    .success();
+
+    let path = registry_file(temp.path());
+    let saved = fs::read_to_string(&path).unwrap();
+    assert!(!saved.contains("\"tags\""));
+    assert!(!saved.contains("\"note\""));
+
+    let registry = Registry::load(&path).unwrap();
+    let (_, bob) = registry.participant_by_pet_name("Bob").unwrap();
+    assert_eq!(bob.note(), None);
+    assert_eq!(bob.contact(), None);
+    assert!(bob.tags().is_empty());
+}
+
+#[test]
+fn participant_edit_updates_metadata() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "add",
+            "--note",
+            "Old note",
+            "--tag",
+            "observer",
+            &fixture("bob_signed_xid.txt"),
+            "Bob",
+        ],
+    )
+    .assert()
+    .success();
+
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "edit",
+            "Bob",
+            "--clear-note",
+            "--contact",
+            "https://bob.example.com",
+            "--tag",
+            "signer",
+            "--untag",
+            "observer",
+        ],
+    )
+    .assert()
+    .success();
+
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let (_, bob) = registry.participant_by_pet_name("Bob").unwrap();
+    assert_eq!(bob.note(), None);
+    assert_eq!(bob.contact(), Some("https://bob.example.com"));
+    assert_eq!(bob.tags(), ["signer"]);
+
+    run_frost(temp.path(), &["registry", "participant", "edit", "Bob"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing to change"));
+}
+
+#[test]
+fn participant_list_filters_by_tag() {
+    let temp = TempDir::new().unwrap();
+    for (key, name, tag) in
+        [("bob", "Bob", "signer"), ("carol", "Carol", "ops")]
+    {
+        run_frost(
+            temp.path(),
+            &[
+                "registry",
+                "participant",
+                "add",
+                "--tag",
+                tag,
+                &fixture(&format!("{key}_signed_xid.txt")),
+                name,
+            ],
+        )
+        .assert()
+        .success();
+    }
+
+    run_frost(
+        temp.path(),
+        &["registry", "participant", "list", "--tag", "signer"],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Bob"))
+    .stdout(predicate::str::contains("Tags: signer"))
+    .stdout(predicate::str::contains("Carol").not());
+
+    let output = run_frost(
+        temp.path(),
+        &["registry", "participant", "list", "--json", "--tag", "ops"],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json.as_object().unwrap();
+    assert_eq!(entries.len(), 1);
+    let carol = entries.values().next().unwrap();
+    assert_eq!(carol["pet_name"], "Carol");
+    assert_eq!(carol["tags"], serde_json::json!(["ops"]));
+}
+
+#[test]
+fn participant_list_prints_tsv() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "add",
+            "--note",
+            "Treasury\tdesk",
+            "--tag",
+            "signer",
+            "--tag",
+            "ops",
+            &fixture("bob_signed_xid.txt"),
+            "Bob",
+        ],
+    )
+    .assert()
+    .success();
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "add",
+            &fixture("carol_signed_xid.txt"),
+            "Carol",
+        ],
+    )
+    .assert()
+    .success();
+
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let (bob, _) = registry.participant_by_pet_name("Bob").unwrap();
+    let (carol, _) = registry.participant_by_pet_name("Carol").unwrap();
+
+    run_frost(
+        temp.path(),
+        &["registry", "participant", "list", "--output", "tsv"],
+    )
+    .assert()
+    .success()
+    .stdout(format!(
+        "{}\tBob\tTreasury desk\t\tsigner,ops\n{}\tCarol\t\t\t\n",
+        bob.ur_string(),
+        carol.ur_string()
+    ));
+}
+
+#[test]
+fn participant_list_json_conflicts_with_output() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "list",
+            "--json",
+            "--output",
+            "tsv",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
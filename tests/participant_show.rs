@@ -0,0 +1,120 @@
+use bc_envelope::prelude::*;
+use bc_xid::{XIDGeneratorOptions, XIDPrivateKeyOptions, XIDSigningOptions};
+use frost_hubert::registry::{OwnerRecord, ParticipantRecord};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::{fixture, run_frost};
+
+fn add_bob(dir: &std::path::Path, document: &str) {
+    run_frost(
+        dir,
+        &[
+            "registry",
+            "participant",
+            "add",
+            "--note",
+            "Treasury desk",
+            "--tag",
+            "signer",
+            document,
+            "Bob",
+        ],
+    )
+    .assert()
+    .success();
+}
+
+#[test]
+fn show_prints_metadata_and_document() {
+    let temp = TempDir::new().unwrap();
+    add_bob(temp.path(), &fixture("bob_signed_xid.txt"));
+    let xid = ParticipantRecord::from_signed_xid_ur(
+        fixture("bob_signed_xid.txt"),
+        None,
+    )
+    .unwrap()
+    .xid()
+    .ur_string();
+
+    for participant in ["Bob", xid.as_str()] {
+        run_frost(
+            temp.path(),
+            &["registry", "participant", "show", participant],
+        )
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("XID: {xid}")))
+        .stdout(predicate::str::contains("Pet name: Bob"))
+        .stdout(predicate::str::contains("Note: Treasury desk"))
+        .stdout(predicate::str::contains("Tags: signer"))
+        .stdout(predicate::str::contains("'key': PublicKeys"));
+    }
+}
+
+#[test]
+fn show_ur_prints_the_registered_document() {
+    let temp = TempDir::new().unwrap();
+    add_bob(temp.path(), &fixture("bob_signed_xid.txt"));
+
+    run_frost(
+        temp.path(),
+        &["registry", "participant", "show", "Bob", "--ur"],
+    )
+    .assert()
+    .success()
+    .stdout(format!("{}\n", fixture("bob_signed_xid.txt")));
+}
+
+/// Bob's document with his private keys embedded, signed so that
+/// `participant add` accepts it.
+fn bob_signed_private_xid() -> String {
+    let owner =
+        OwnerRecord::from_signed_xid_ur(fixture("bob_private_xid.txt"), None)
+            .unwrap();
+    owner
+        .xid_document()
+        .to_envelope(
+            XIDPrivateKeyOptions::Include,
+            XIDGeneratorOptions::default(),
+            XIDSigningOptions::Inception,
+        )
+        .unwrap()
+        .ur_string()
+}
+
+#[test]
+fn show_never_prints_private_keys() {
+    let temp = TempDir::new().unwrap();
+    add_bob(temp.path(), &bob_signed_private_xid());
+
+    run_frost(temp.path(), &["registry", "participant", "show", "Bob"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PrivateKeys").not());
+
+    let output = run_frost(
+        temp.path(),
+        &["registry", "participant", "show", "Bob", "--ur"],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let ur = String::from_utf8(output.stdout).unwrap();
+    let shown = ParticipantRecord::from_signed_xid_ur(ur.trim(), None).unwrap();
+    assert!(shown.xid_document().inception_private_keys().is_none());
+}
+
+#[test]
+fn show_rejects_unknown_participants() {
+    let temp = TempDir::new().unwrap();
+    add_bob(temp.path(), &fixture("bob_signed_xid.txt"));
+
+    run_frost(temp.path(), &["registry", "participant", "show", "Carol"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Participant with pet name 'Carol' not found",
+        ));
+}
@@ -0,0 +1,85 @@
+mod common;
+use bc_envelope::prelude::*;
+use common::{fixture, run_frost};
+use frost_hubert::cmd::storage::check_payload_size;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn payload_within_limit_is_accepted() {
+    let envelope = Envelope::new("Hello");
+    let size = envelope.to_cbor_data().len() as u64;
+
+    check_payload_size(&envelope, None, false).unwrap();
+    check_payload_size(&envelope, Some(size), false).unwrap();
+}
+
+#[test]
+fn oversized_payload_suggests_compression() {
+    let envelope = Envelope::new("x".repeat(200));
+    let size = envelope.to_cbor_data().len();
+
+    let message = check_payload_size(&envelope, Some(100), false)
+        .unwrap_err()
+        .to_string();
+    assert!(message.contains(&format!("{size} bytes")));
+    assert!(message.contains("--max-payload-bytes 100"));
+    assert!(message.contains("--compress"));
+
+    let message = check_payload_size(&envelope, Some(100), true)
+        .unwrap_err()
+        .to_string();
+    assert!(!message.contains("--compress"));
+    assert!(message.contains("smaller group"));
+}
+
+#[test]
+fn oversized_invite_fails_before_upload() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "owner",
+            "set",
+            &fixture("alice_private_xid.txt"),
+        ],
+    )
+    .assert()
+    .success();
+    for (key, name) in [("bob", "Bob"), ("carol", "Carol")] {
+        run_frost(
+            temp.path(),
+            &[
+                "registry",
+                "participant",
+                "add",
+                &fixture(&format!("{key}_signed_xid.txt")),
+                name,
+            ],
+        )
+        .assert()
+        .success();
+    }
+
+    // Nothing listens on port 1; the size check must fail first
+    run_frost(
+        temp.path(),
+        &[
+            "--max-payload-bytes",
+            "64",
+            "dkg",
+            "coordinator",
+            "invite",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("exceeds --max-payload-bytes 64"));
+}
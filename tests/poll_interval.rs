@@ -0,0 +1,113 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+mod common;
+use common::run_frost;
+use frost_hubert::cmd::storage::poll_until;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// Counts the attempts `poll_until` makes within `timeout` when nothing is
+/// ever found and each attempt returns promptly.
+fn attempts_within(timeout: Duration, interval: Duration) -> u64 {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let calls = AtomicU64::new(0);
+    let result: Option<()> = runtime
+        .block_on(poll_until(timeout, interval, || {
+            calls.fetch_add(1, Ordering::Relaxed);
+            async { Ok(None) }
+        }))
+        .unwrap();
+    assert!(result.is_none());
+    calls.load(Ordering::Relaxed)
+}
+
+#[test]
+fn poll_until_attempts_once_per_interval() {
+    let millis = Duration::from_millis;
+    assert_eq!(attempts_within(millis(1000), millis(250)), 4);
+    assert_eq!(attempts_within(millis(500), millis(100)), 5);
+    // A timeout shorter than one interval still polls once.
+    assert_eq!(attempts_within(millis(0), millis(1000)), 1);
+    assert_eq!(attempts_within(millis(300), millis(60_000)), 1);
+}
+
+#[test]
+fn poll_until_makes_expected_attempts() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let calls = AtomicU64::new(0);
+
+    let result: Option<()> = runtime
+        .block_on(poll_until(
+            Duration::from_secs(1),
+            Duration::from_millis(100),
+            || {
+                calls.fetch_add(1, Ordering::Relaxed);
+                async { Ok(None) }
+            },
+        ))
+        .unwrap();
+
+    assert!(result.is_none());
+    assert_eq!(calls.load(Ordering::Relaxed), 10);
+}
+
+#[test]
+fn poll_until_stops_when_found() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let calls = AtomicU64::new(0);
+
+    let result = runtime
+        .block_on(poll_until(
+            Duration::from_secs(1),
+            Duration::from_millis(100),
+            || {
+                let n = calls.fetch_add(1, Ordering::Relaxed) + 1;
+                async move { Ok((n == 3).then_some(n)) }
+            },
+        ))
+        .unwrap();
+
+    assert_eq!(result, Some(3));
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn poll_until_slow_attempts_do_not_extend_the_timeout() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let calls = AtomicU64::new(0);
+    let started = Instant::now();
+
+    let result: Option<()> = runtime
+        .block_on(poll_until(
+            Duration::from_millis(500),
+            Duration::from_millis(100),
+            || {
+                calls.fetch_add(1, Ordering::Relaxed);
+                async {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    Ok(None)
+                }
+            },
+        ))
+        .unwrap();
+
+    assert!(result.is_none());
+    // Counting attempts would wait 1.4 s here: five 200 ms attempts and four
+    // 100 ms sleeps. The deadline stops after the attempt that runs past it.
+    assert!(calls.load(Ordering::Relaxed) < 5);
+    assert!(started.elapsed() < Duration::from_millis(1000));
+}
+
+#[test]
+fn poll_interval_out_of_range_is_rejected() {
+    let temp = TempDir::new().unwrap();
+    for value in ["99", "60001"] {
+        run_frost(temp.path(), &["--poll-interval-ms", value, "version"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--poll-interval-ms"));
+    }
+}
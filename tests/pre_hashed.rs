@@ -0,0 +1,71 @@
+mod common;
+use bc_envelope::prelude::*;
+use common::run_frost;
+use frost_hubert::cmd::sign::common::{
+    hex_message, is_pre_hashed, parse_hex_message, pre_hashed_target,
+    signing_message,
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn pre_hashed_target_signs_exact_bytes() {
+    let message = parse_hex_message(
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+    )
+    .unwrap();
+    assert_eq!(message.len(), 32);
+
+    let target = pre_hashed_target(&message);
+    assert!(is_pre_hashed(&target));
+    assert_eq!(signing_message(&target).unwrap(), message);
+
+    // Round-trips through the UR stored in session state
+    let restored = Envelope::from_ur_string(target.ur_string()).unwrap();
+    assert_eq!(signing_message(&restored).unwrap(), message);
+}
+
+#[test]
+fn envelope_target_signs_subject_digest() {
+    let target = Envelope::new("Hello.").add_assertion("note", "unchanged");
+    assert!(!is_pre_hashed(&target));
+    assert_eq!(
+        signing_message(&target).unwrap(),
+        target.subject().digest().data().to_vec()
+    );
+}
+
+#[test]
+fn parse_hex_message_rejects_invalid_input() {
+    assert!(parse_hex_message("").is_err());
+    assert!(parse_hex_message("abc").is_err());
+    assert!(parse_hex_message("zz").is_err());
+    assert_eq!(parse_hex_message(" 00ff ").unwrap(), vec![0x00, 0xff]);
+}
+
+#[test]
+fn hex_message_round_trips() {
+    let digest =
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    assert_eq!(hex_message(&parse_hex_message(digest).unwrap()), digest);
+    assert_eq!(hex_message(&[0x00, 0x0a, 0xff]), "000aff");
+}
+
+#[test]
+fn digest_requires_pre_hashed_flag() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "invite",
+            "--digest",
+            "00ff",
+            "ur:arid/placeholder",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("--pre-hashed"));
+}
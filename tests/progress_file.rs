@@ -0,0 +1,86 @@
+use std::fs;
+
+mod common;
+use bc_components::XID;
+use bc_envelope::prelude::*;
+use common::run_frost;
+use frost_hubert::cmd::parallel::{Direction, ProgressEvents};
+use serde_json::Value;
+use tempfile::TempDir;
+
+fn read_events(path: &std::path::Path) -> Vec<Value> {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn progress_events_are_one_json_object_per_line() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("progress.ndjson");
+    let alice = XID::from_data([1; 32]);
+    let bob = XID::from_data([2; 32]);
+
+    let events = ProgressEvents::open(&path, Direction::Get).unwrap();
+    events.pending(&alice, "Alice");
+    events.pending(&bob, "Bob");
+    events.success(&alice, "Alice");
+    events.error(&bob, "Bob", "Failed to decode response: bad CBOR");
+
+    let lines = read_events(&path);
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0]["direction"], "get");
+    assert_eq!(lines[0]["xid"], alice.ur_string());
+    assert_eq!(lines[0]["name"], "Alice");
+    assert_eq!(lines[0]["status"], "pending");
+    assert!(lines[0]["elapsed_ms"].is_u64());
+    assert_eq!(lines[2]["status"], "success");
+    assert!(lines[2].get("error").is_none());
+    assert_eq!(lines[3]["xid"], bob.ur_string());
+    assert_eq!(lines[3]["status"], "error");
+    assert_eq!(lines[3]["error"], "Failed to decode response: bad CBOR");
+}
+
+#[test]
+fn progress_events_append_across_operations() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("progress.ndjson");
+    let carol = XID::from_data([3; 32]);
+
+    let fetch = ProgressEvents::open(&path, Direction::Get).unwrap();
+    fetch.pending(&carol, "Carol");
+    fetch.timeout(&carol, "Carol");
+    drop(fetch);
+    let send = ProgressEvents::open(&path, Direction::Put).unwrap();
+    send.pending(&carol, "Carol");
+    send.cancelled(&carol, "Carol");
+
+    let statuses: Vec<_> = read_events(&path)
+        .iter()
+        .map(|event| {
+            format!("{} {}", event["direction"], event["status"])
+                .replace('"', "")
+        })
+        .collect();
+    assert_eq!(
+        statuses,
+        ["get pending", "get timeout", "put pending", "put cancelled"]
+    );
+}
+
+#[test]
+fn progress_file_is_a_global_flag() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("progress.ndjson");
+
+    run_frost(
+        temp.path(),
+        &["--progress-file", path.to_str().unwrap(), "version"],
+    )
+    .assert()
+    .success();
+    // Only parallel fetches and sends write events
+    assert!(!path.exists());
+}
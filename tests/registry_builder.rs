@@ -0,0 +1,85 @@
+use std::fs;
+
+mod common;
+use bc_components::ARID;
+use common::{owner, participant};
+use frost_hubert::registry::{GroupParticipant, GroupRecord, Registry};
+use tempfile::TempDir;
+
+fn build_registry() -> (Registry, ARID) {
+    let owner = owner("alice", Some("Alice"));
+    let bob = participant("bob", Some("Bob"));
+    let carol = participant("carol", Some("Carol"));
+
+    let group = GroupRecord::new(
+        "Embedded group".to_string(),
+        2,
+        GroupParticipant::new(owner.xid()),
+        vec![
+            GroupParticipant::new(owner.xid()),
+            GroupParticipant::new(bob.xid()),
+            GroupParticipant::new(carol.xid()),
+        ],
+    );
+    let group_id = ARID::new();
+
+    let mut registry = Registry::new()
+        .with_owner(owner)
+        .unwrap()
+        .with_participant(bob.xid(), bob)
+        .unwrap();
+    registry.add_participant(carol.xid(), carol).unwrap();
+    registry.record_group(group_id, group).unwrap();
+    (registry, group_id)
+}
+
+#[test]
+fn registry_builds_and_round_trips_in_memory() {
+    let (registry, group_id) = build_registry();
+
+    let json = registry.to_json().unwrap();
+    let decoded = Registry::from_json(&json).unwrap();
+    assert_eq!(decoded.owner().unwrap().pet_name(), Some("Alice"));
+    assert_eq!(decoded.participants().len(), 2);
+    assert!(decoded.participant_by_pet_name("Carol").is_some());
+    assert_eq!(
+        decoded.group(&group_id).unwrap().charter(),
+        "Embedded group"
+    );
+    assert_eq!(decoded.to_json().unwrap(), json);
+}
+
+#[test]
+fn builder_rejects_conflicting_pet_names() {
+    let bob = participant("bob", Some("Bob"));
+    let carol_as_bob = participant("carol", Some("Bob"));
+    let result = Registry::new()
+        .with_participant(bob.xid(), bob)
+        .unwrap()
+        .with_participant(carol_as_bob.xid(), carol_as_bob);
+    assert!(result.is_err());
+}
+
+#[test]
+fn save_and_load_use_the_json_form() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("registry.json");
+    let (registry, group_id) = build_registry();
+
+    registry.save(&path).unwrap();
+    assert_eq!(fs::read(&path).unwrap(), registry.to_json().unwrap());
+
+    let loaded = Registry::load(&path).unwrap();
+    assert!(loaded.group(&group_id).is_some());
+}
+
+#[test]
+fn empty_json_is_an_empty_registry() {
+    for data in [&b""[..], &b"  \n"[..]] {
+        let registry = Registry::from_json(data).unwrap();
+        assert!(registry.owner().is_none());
+        assert!(registry.participants().is_empty());
+        assert_eq!(registry.groups().count(), 0);
+    }
+    assert!(Registry::from_json(b"{not json").is_err());
+}
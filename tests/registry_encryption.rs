@@ -0,0 +1,180 @@
+use std::{fs, path::Path};
+
+mod common;
+use common::{fixture, registry_file, run_frost};
+use frost_hubert::registry::{
+    ENCRYPTED_REGISTRY_MAGIC, Passphrase, Registry, decrypt_registry,
+    encrypt_registry, is_encrypted_registry,
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+const PASSPHRASE: &str = "correct horse battery staple";
+
+fn populate(dir: &Path) {
+    let owner = fixture("alice_private_xid.txt");
+    run_frost(dir, &["registry", "owner", "set", &owner])
+        .assert()
+        .success();
+    let bob = fixture("bob_signed_xid.txt");
+    run_frost(dir, &["registry", "participant", "add", &bob, "Bob"])
+        .assert()
+        .success();
+}
+
+fn encrypted(dir: &Path) {
+    populate(dir);
+    run_frost(dir, &["registry", "encrypt"])
+        .env("FROST_PASSPHRASE", PASSPHRASE)
+        .assert()
+        .success();
+}
+
+#[test]
+fn encrypt_round_trips_in_memory() {
+    let plaintext = br#"{"participants":{}}"#;
+    let data = encrypt_registry(plaintext, PASSPHRASE).unwrap();
+    assert!(is_encrypted_registry(&data));
+    assert!(data.starts_with(ENCRYPTED_REGISTRY_MAGIC));
+    assert_eq!(decrypt_registry(&data, PASSPHRASE).unwrap(), plaintext);
+    assert!(decrypt_registry(&data, "wrong").is_err());
+    assert!(!is_encrypted_registry(plaintext));
+}
+
+#[test]
+fn encrypted_registry_is_used_transparently() {
+    let temp = TempDir::new().unwrap();
+    encrypted(temp.path());
+
+    let data = fs::read(registry_file(temp.path())).unwrap();
+    assert!(data.starts_with(ENCRYPTED_REGISTRY_MAGIC));
+    assert!(!String::from_utf8_lossy(&data).contains("Bob"));
+
+    let carol = fixture("carol_signed_xid.txt");
+    run_frost(
+        temp.path(),
+        &["registry", "participant", "add", &carol, "Carol"],
+    )
+    .env("FROST_PASSPHRASE", PASSPHRASE)
+    .assert()
+    .success();
+
+    run_frost(temp.path(), &["registry", "participant", "list"])
+        .env("FROST_PASSPHRASE", PASSPHRASE)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Bob"))
+        .stdout(predicate::str::contains("Carol"));
+
+    // Updates are written back encrypted.
+    let data = fs::read(registry_file(temp.path())).unwrap();
+    assert!(data.starts_with(ENCRYPTED_REGISTRY_MAGIC));
+}
+
+#[test]
+fn passphrase_file_takes_precedence_over_environment() {
+    let temp = TempDir::new().unwrap();
+    encrypted(temp.path());
+    let passphrase_path = temp.path().join("passphrase.txt");
+    fs::write(&passphrase_path, format!("{PASSPHRASE}\n")).unwrap();
+
+    run_frost(
+        temp.path(),
+        &[
+            "--passphrase-file",
+            passphrase_path.to_str().unwrap(),
+            "registry",
+            "participant",
+            "list",
+        ],
+    )
+    .env("FROST_PASSPHRASE", "not the passphrase")
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Bob"));
+}
+
+#[test]
+fn loaded_registry_keeps_its_passphrase() {
+    let temp = TempDir::new().unwrap();
+    encrypted(temp.path());
+    let path = registry_file(temp.path());
+    assert!(Registry::load(&path).is_err());
+
+    let registry =
+        Registry::load_with_passphrase(&path, || Passphrase::new(PASSPHRASE))
+            .unwrap();
+    assert!(registry.is_encrypted());
+    assert!(!format!("{registry:?}").contains(PASSPHRASE));
+
+    // Saving and rereading reuse the passphrase without asking again
+    registry.save(&path).unwrap();
+    assert!(is_encrypted_registry(&fs::read(&path).unwrap()));
+    let reloaded = registry.reload(&path).unwrap();
+    assert_eq!(reloaded.participants().len(), 1);
+}
+
+#[test]
+fn plain_registry_never_asks_for_a_passphrase() {
+    let temp = TempDir::new().unwrap();
+    populate(temp.path());
+
+    let registry =
+        Registry::load_with_passphrase(&registry_file(temp.path()), || {
+            panic!("a plain registry needs no passphrase")
+        })
+        .unwrap();
+    assert!(!registry.is_encrypted());
+    assert!(Passphrase::new("").is_err());
+}
+
+#[test]
+fn wrong_passphrase_fails() {
+    let temp = TempDir::new().unwrap();
+    encrypted(temp.path());
+
+    run_frost(temp.path(), &["registry", "participant", "list"])
+        .env("FROST_PASSPHRASE", "not the passphrase")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Wrong passphrase"));
+}
+
+#[test]
+fn missing_passphrase_fails_without_prompting() {
+    let temp = TempDir::new().unwrap();
+    encrypted(temp.path());
+
+    run_frost(
+        temp.path(),
+        &["--no-input", "registry", "participant", "list"],
+    )
+    .env_remove("FROST_PASSPHRASE")
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("--passphrase-file"));
+}
+
+#[test]
+fn decrypt_restores_plain_registry() {
+    let temp = TempDir::new().unwrap();
+    encrypted(temp.path());
+
+    run_frost(temp.path(), &["registry", "decrypt"])
+        .env("FROST_PASSPHRASE", PASSPHRASE)
+        .assert()
+        .success();
+
+    let data = fs::read(registry_file(temp.path())).unwrap();
+    assert!(!is_encrypted_registry(&data));
+    run_frost(temp.path(), &["registry", "participant", "list"])
+        .env_remove("FROST_PASSPHRASE")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Bob"));
+
+    run_frost(temp.path(), &["registry", "decrypt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not encrypted"));
+}
@@ -0,0 +1,66 @@
+use std::path::Path;
+
+mod common;
+use common::{run_frost, setup_alice_registry};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn export_json(dir: &Path, extra: &[&str]) -> serde_json::Value {
+    let mut args = vec!["registry", "export"];
+    args.extend_from_slice(extra);
+    let output = run_frost(dir, &args).output().unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn export_json_lists_participants_without_owner() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+
+    let export = export_json(temp.path(), &[]);
+    assert!(export.get("owner").is_none());
+    assert!(export.get("groups").is_none());
+
+    let participants = export["participants"].as_object().unwrap();
+    let mut names: Vec<&str> = participants
+        .values()
+        .map(|record| record["pet_name"].as_str().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["Bob", "Carol"]);
+}
+
+#[test]
+fn export_owner_omits_private_keys() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+
+    let export = export_json(temp.path(), &["--include-owner"]);
+    let owner_ur = export["owner"]["xid_document"].as_str().unwrap();
+
+    // An owner document without private keys cannot become a registry owner,
+    // but is accepted as a participant by whoever receives the export.
+    let other = TempDir::new().unwrap();
+    run_frost(other.path(), &["registry", "owner", "set", owner_ur])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must include private keys"));
+    run_frost(
+        other.path(),
+        &["registry", "participant", "add", owner_ur, "Alice"],
+    )
+    .assert()
+    .success();
+}
+
+#[test]
+fn export_envelope_is_single_ur() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+
+    run_frost(temp.path(), &["registry", "export", "--format", "envelope"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("ur:envelope/"));
+}
@@ -0,0 +1,201 @@
+use std::path::Path;
+
+mod common;
+use bc_components::{ARID, XID};
+use bc_envelope::prelude::*;
+use common::{fixture, fixture_xid, record_group, registry_file, run_frost};
+use frost_hubert::{
+    dkg::IdentifierScheme,
+    registry::{
+        GroupParticipant, GroupRecord, PendingRequests, Registry,
+        RegistryFormat,
+    },
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn populate(dir: &Path, extra: &[&str]) {
+    let owner = fixture("alice_private_xid.txt");
+    let mut args = vec!["registry", "owner", "set"];
+    args.extend_from_slice(extra);
+    args.push(&owner);
+    run_frost(dir, &args).assert().success();
+
+    for (key, name) in [("bob", "Bob"), ("carol", "Carol")] {
+        let document = fixture(&format!("{key}_signed_xid.txt"));
+        let mut args = vec!["registry", "participant", "add"];
+        args.extend_from_slice(extra);
+        args.push(&document);
+        args.push(name);
+        run_frost(dir, &args).assert().success();
+    }
+}
+
+#[test]
+fn format_follows_extension() {
+    assert_eq!(
+        RegistryFormat::for_path(Path::new("registry.json")),
+        RegistryFormat::Json
+    );
+    assert_eq!(
+        RegistryFormat::for_path(Path::new("dir/registry.CBOR")),
+        RegistryFormat::Cbor
+    );
+    assert_eq!(
+        RegistryFormat::for_path(Path::new("registry")),
+        RegistryFormat::Json
+    );
+}
+
+/// A group exercising the optional fields: an exclusion, pending requests,
+/// a hashed identifier scheme, and a persisted identifier map.
+fn populate_group(dir: &Path) -> ARID {
+    let [alice, bob, carol] = ["alice", "bob", "carol"].map(fixture_xid);
+    let mut record = GroupRecord::new(
+        "Test charter".to_string(),
+        2,
+        GroupParticipant::new(alice),
+        [alice, bob, carol].map(GroupParticipant::new).to_vec(),
+    );
+    record.set_identifier_scheme(IdentifierScheme::Hashed);
+    record.set_identifiers(
+        IdentifierScheme::Hashed
+            .identifiers(&[alice, bob, carol])
+            .unwrap(),
+    );
+    let mut pending = PendingRequests::new();
+    pending.add_send_only(bob, ARID::new());
+    pending.add_send_and_collect(carol, ARID::new(), ARID::new());
+    record.set_pending_requests(pending);
+    record.exclude_participant(&alice);
+    record_group(dir, record)
+}
+
+#[test]
+fn cbor_round_trips_the_json_model() {
+    let temp = TempDir::new().unwrap();
+    populate(temp.path(), &[]);
+    populate_group(temp.path());
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+
+    let cbor = registry.to_cbor().unwrap();
+    assert!(cbor.len() < registry.to_json().unwrap().len());
+    let decoded = Registry::from_cbor(&cbor).unwrap();
+    assert_eq!(decoded.to_json().unwrap(), registry.to_json().unwrap());
+}
+
+#[test]
+fn cbor_stores_xids_and_group_ids_natively() {
+    let temp = TempDir::new().unwrap();
+    populate(temp.path(), &[]);
+    let group_id = populate_group(temp.path());
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+
+    let cbor = CBOR::try_from_data(registry.to_cbor().unwrap()).unwrap();
+    let map = cbor.try_into_map().unwrap();
+    let entries = |key: &str| -> Vec<CBOR> {
+        let nested = map.get::<&str, CBOR>(key).unwrap();
+        nested
+            .try_into_map()
+            .unwrap()
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect()
+    };
+
+    let participants: Vec<XID> = entries("participants")
+        .into_iter()
+        .map(|key| XID::try_from(key).unwrap())
+        .collect();
+    assert_eq!(participants.len(), 2);
+    assert!(participants.contains(&fixture_xid("bob")));
+    assert!(participants.contains(&fixture_xid("carol")));
+
+    let groups: Vec<ARID> = entries("groups")
+        .into_iter()
+        .map(|key| ARID::try_from(key).unwrap())
+        .collect();
+    assert_eq!(groups, vec![group_id]);
+}
+
+#[test]
+fn registry_format_flag_uses_cbor_default_file() {
+    let temp = TempDir::new().unwrap();
+    populate(temp.path(), &["--registry-format", "cbor"]);
+
+    assert!(!registry_file(temp.path()).exists());
+    let registry = Registry::load(&temp.path().join("registry.cbor")).unwrap();
+    assert!(registry.owner().is_some());
+    assert_eq!(registry.participants().len(), 2);
+
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "list",
+            "--registry-format",
+            "cbor",
+        ],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Bob"))
+    .stdout(predicate::str::contains("Carol"));
+}
+
+#[test]
+fn registry_format_flag_must_match_explicit_path() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "participant",
+            "list",
+            "--registry-format",
+            "cbor",
+            "--registry",
+            "registry.json",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "--registry-format cbor does not match registry file",
+    ));
+}
+
+#[test]
+fn convert_migrates_between_formats() {
+    let temp = TempDir::new().unwrap();
+    populate(temp.path(), &[]);
+    let original = Registry::load(&registry_file(temp.path())).unwrap();
+
+    run_frost(temp.path(), &["registry", "convert", "registry.cbor"])
+        .assert()
+        .success();
+    let converted = Registry::load(&temp.path().join("registry.cbor")).unwrap();
+    assert_eq!(converted.to_json().unwrap(), original.to_json().unwrap());
+
+    run_frost(temp.path(), &["registry", "convert", "registry.cbor"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --force to replace it"));
+
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "convert",
+            "--registry",
+            "registry.cbor",
+            "--force",
+            "registry.json",
+        ],
+    )
+    .assert()
+    .success();
+    let restored = Registry::load(&registry_file(temp.path())).unwrap();
+    assert_eq!(restored.to_json().unwrap(), original.to_json().unwrap());
+}
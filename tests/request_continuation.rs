@@ -0,0 +1,122 @@
+use bc_components::{ARID, PrivateKeyBase};
+use bc_envelope::prelude::*;
+use bc_rand::{RandomNumberGenerator, make_fake_random_number_generator};
+use bc_xid::{XIDDocument, XIDGenesisMarkOptions, XIDInceptionKeyOptions};
+use frost_hubert::cmd::dkg::common::check_request_continuation;
+use gstp::{SealedRequest, SealedResponse};
+use provenance_mark::ProvenanceMarkResolution;
+
+fn make_xid_document(
+    rng: &mut impl RandomNumberGenerator,
+    date: Date,
+) -> XIDDocument {
+    XIDDocument::new(
+        XIDInceptionKeyOptions::PrivateKeyBase(PrivateKeyBase::new_using(rng)),
+        XIDGenesisMarkOptions::Passphrase(
+            "password".to_string(),
+            Some(ProvenanceMarkResolution::Quartile),
+            Some(date),
+            None,
+        ),
+    )
+}
+
+struct Exchange {
+    coordinator: XIDDocument,
+    participant: XIDDocument,
+}
+
+impl Exchange {
+    fn new() -> Self {
+        let mut rng = make_fake_random_number_generator();
+        let date = Date::from_ymd(2025, 12, 31);
+        let coordinator = make_xid_document(&mut rng, date);
+        let participant = make_xid_document(&mut rng, date);
+        Self { coordinator, participant }
+    }
+
+    /// The participant's response, carrying `listening_at` as its state,
+    /// as the coordinator decrypts it.
+    fn response(&self, listening_at: ARID) -> SealedResponse {
+        let envelope =
+            SealedResponse::new_success(ARID::new(), self.participant.clone())
+                .with_result(Envelope::unit())
+                .with_state(listening_at)
+                .to_envelope(
+                    None,
+                    Some(self.participant.inception_private_keys().unwrap()),
+                    Some(&self.coordinator),
+                )
+                .unwrap();
+        SealedResponse::try_from_encrypted_envelope(
+            &envelope,
+            None,
+            None,
+            self.coordinator.inception_private_keys().unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// The coordinator's next request returning `continuation`, as the
+    /// participant decrypts it.
+    fn request(&self, continuation: Option<&Envelope>) -> SealedRequest {
+        let envelope = SealedRequest::new(
+            "dkgRound2",
+            ARID::new(),
+            self.coordinator.clone(),
+        )
+        .with_parameter("group", ARID::new())
+        .with_peer_continuation(continuation)
+        .to_envelope_for_recipients(
+            None,
+            Some(self.coordinator.inception_private_keys().unwrap()),
+            &[&self.participant],
+        )
+        .unwrap();
+        SealedRequest::try_from_envelope(
+            &envelope,
+            None,
+            None,
+            self.participant.inception_private_keys().unwrap(),
+        )
+        .unwrap()
+    }
+}
+
+#[test]
+fn returned_continuation_is_accepted() {
+    let exchange = Exchange::new();
+    let listening_at = ARID::new();
+    let response = exchange.response(listening_at);
+
+    let request = exchange.request(response.peer_continuation());
+    check_request_continuation(&request, &listening_at).unwrap();
+}
+
+#[test]
+fn continuation_from_another_step_is_rejected() {
+    let exchange = Exchange::new();
+    let listening_at = ARID::new();
+    // A request replayed from an earlier step returns that step's
+    // continuation, which names a different listening ARID
+    let earlier = exchange.response(ARID::new());
+
+    let request = exchange.request(earlier.peer_continuation());
+    let error = check_request_continuation(&request, &listening_at)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("replayed from another step"), "{error}");
+}
+
+#[test]
+fn request_without_continuation_is_rejected() {
+    let exchange = Exchange::new();
+    let request = exchange.request(None);
+    let error = check_request_continuation(&request, &ARID::new())
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("does not return the continuation"),
+        "{error}"
+    );
+}
@@ -0,0 +1,232 @@
+use std::{fs, path::Path};
+
+mod common;
+use bc_components::{ARID, XIDProvider};
+use bc_envelope::prelude::*;
+use common::{
+    fixture_xid, owner, record_group, registry_file, run_frost,
+    setup_alice_registry, setup_registry,
+};
+use frost_hubert::{
+    cmd::{common::GroupStatePaths, dkg::common::response_recipient},
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use gstp::SealedRequest;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// Alice's registry, knowing Bob (the coordinator) and Carol.
+fn alice_registry(dir: &Path) -> Registry {
+    setup_alice_registry(dir);
+    Registry::load(&registry_file(dir)).unwrap()
+}
+
+fn request_from_bob() -> SealedRequest {
+    SealedRequest::new(
+        "signRound2",
+        ARID::new(),
+        owner("bob", None).xid_document().clone(),
+    )
+    .with_parameter("session", ARID::new())
+}
+
+#[test]
+fn responses_are_sealed_to_the_sender_by_default() {
+    let temp = TempDir::new().unwrap();
+    let registry = alice_registry(temp.path());
+    let bob = owner("bob", None).xid_document().clone();
+
+    let recipient =
+        response_recipient(&request_from_bob(), &registry, bob.clone())
+            .unwrap();
+    assert_eq!(recipient.xid(), bob.xid());
+}
+
+#[test]
+fn registered_recipient_overrides_the_sender() {
+    let temp = TempDir::new().unwrap();
+    let registry = alice_registry(temp.path());
+    let bob = owner("bob", None).xid_document().clone();
+    let carol = owner("carol", None).xid();
+
+    let request = request_from_bob().with_parameter("recipient", carol);
+    let recipient = response_recipient(&request, &registry, bob).unwrap();
+    assert_eq!(recipient.xid(), carol);
+}
+
+#[test]
+fn unregistered_recipient_is_rejected() {
+    let temp = TempDir::new().unwrap();
+    let registry = alice_registry(temp.path());
+    let bob = owner("bob", None).xid_document().clone();
+    let dan = owner("dan", None).xid();
+
+    let request = request_from_bob().with_parameter("recipient", dan);
+    let err = response_recipient(&request, &registry, bob).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("is not a participant in the registry"),
+        "{err}"
+    );
+}
+
+/// Records a 2-of-3 group of Alice, Bob, and Carol coordinated by Alice, and
+/// a signing session whose commitments.json names `recipient` (if any) as
+/// the aggregator. No commitments are recorded.
+fn write_session(dir: &Path, recipient: Option<&str>) -> ARID {
+    let [alice, bob, carol] = ["alice", "bob", "carol"].map(fixture_xid);
+    let group_id = record_group(
+        dir,
+        GroupRecord::new(
+            "Test group".to_string(),
+            2,
+            GroupParticipant::new(alice),
+            [alice, bob, carol].map(GroupParticipant::new).to_vec(),
+        ),
+    );
+
+    let session_id = ARID::new();
+    let paths = GroupStatePaths::new(&registry_file(dir));
+    let write = |file: &Path, value: serde_json::Value| {
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, serde_json::to_vec_pretty(&value).unwrap()).unwrap();
+    };
+    write(
+        &paths.start_state_path(&group_id, &session_id),
+        serde_json::json!({
+            "group": group_id.ur_string(),
+            "session_id": session_id.ur_string(),
+            "min_signers": 2,
+            "participants": {},
+            "target": Envelope::new("Budget").ur_string(),
+        }),
+    );
+    let mut commitments = serde_json::json!({
+        "group": group_id.ur_string(),
+        "session": session_id.ur_string(),
+        "commitments": {},
+    });
+    if let Some(name) = recipient {
+        commitments["recipient"] = fixture_xid(name).ur_string().into();
+    }
+    write(&paths.commitments_path(&group_id, &session_id), commitments);
+    session_id
+}
+
+#[test]
+fn coordinator_cannot_collect_shares_sealed_to_an_aggregator() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+    let session_id = write_session(temp.path(), Some("bob"));
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "round2",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            &session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(format!(
+        "Signature shares for this session are sealed to {}",
+        fixture_xid("bob").ur_string()
+    )));
+}
+
+#[test]
+fn named_aggregator_runs_share_collection() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(
+        temp.path(),
+        "bob",
+        &[("alice", "Alice"), ("carol", "Carol")],
+    );
+    let session_id = write_session(temp.path(), Some("bob"));
+
+    // Bob is not the coordinator, yet gets past the ownership check to
+    // aggregation, which fails only for want of shares
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "round2",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            &session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Only collected 0 signature shares, need at least 2",
+    ));
+}
+
+#[test]
+fn other_participants_cannot_run_share_collection() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "carol", &[("alice", "Alice"), ("bob", "Bob")]);
+    let session_id = write_session(temp.path(), None);
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "round2",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            &session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Only the group's coordinator can run this command",
+    ));
+}
+
+#[test]
+fn seal_to_must_name_a_group_member() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(
+        temp.path(),
+        "alice",
+        &[("bob", "Bob"), ("carol", "Carol"), ("dan", "Dan")],
+    );
+    let session_id = write_session(temp.path(), None);
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "round1",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--seal-to",
+            "Dan",
+            &session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(format!(
+        "--seal-to participant {} is not a member of this group",
+        fixture_xid("dan").ur_string()
+    )));
+}
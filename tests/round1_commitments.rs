@@ -0,0 +1,56 @@
+mod common;
+use bc_components::{XID, XIDProvider};
+use bc_envelope::prelude::*;
+use common::fixture;
+use frost_ed25519::{self as frost, Identifier, rand_core::OsRng};
+use frost_hubert::{
+    cmd::dkg::common::check_round1_commitments, registry::OwnerRecord,
+};
+
+fn xid(name: &str) -> XID {
+    OwnerRecord::from_signed_xid_ur(
+        fixture(&format!("{name}_private_xid.txt")),
+        None,
+    )
+    .unwrap()
+    .xid()
+}
+
+fn round1_package(
+    index: u16,
+    min_signers: u16,
+) -> frost::keys::dkg::round1::Package {
+    let identifier = Identifier::try_from(index).unwrap();
+    frost::keys::dkg::part1(identifier, 3, min_signers, OsRng)
+        .unwrap()
+        .1
+}
+
+#[test]
+fn packages_matching_the_threshold_are_accepted() {
+    let packages = vec![
+        (xid("alice"), round1_package(1, 2)),
+        (xid("bob"), round1_package(2, 2)),
+        (xid("carol"), round1_package(3, 2)),
+    ];
+    check_round1_commitments(&packages, 2).unwrap();
+}
+
+#[test]
+fn package_with_wrong_threshold_names_the_participant() {
+    let bob = xid("bob");
+    let packages = vec![
+        (xid("alice"), round1_package(1, 2)),
+        (bob, round1_package(2, 3)),
+        (xid("carol"), round1_package(3, 2)),
+    ];
+    let err = check_round1_commitments(&packages, 2).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains(&bob.ur_string()), "{message}");
+    assert!(
+        message.contains(
+            "commits to 3 coefficients, but the group's min signers is 2"
+        ),
+        "{message}"
+    );
+}
@@ -0,0 +1,153 @@
+use std::fs;
+
+mod common;
+use bc_components::{ARID, Ed25519PrivateKey, SigningPrivateKey};
+use bc_envelope::prelude::*;
+use common::{
+    fixture_xid, record_group, registry_file, run_frost, setup_alice_registry,
+};
+use frost_hubert::{
+    cmd::{
+        common::GroupStatePaths,
+        schema::{ARTIFACTS, artifact, check_example},
+    },
+    registry::{GroupParticipant, GroupRecord},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn every_example_matches_its_documented_fields() {
+    for artifact in ARTIFACTS {
+        let example = artifact.example().unwrap();
+        check_example(artifact, &example).unwrap();
+    }
+}
+
+/// Runs `sign coordinator invite` without storage, which writes start.json
+/// before failing to post, and returns the file it wrote.
+fn written_start_state(extra: &[&str]) -> serde_json::Value {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(fixture_xid("alice")),
+        ["bob", "carol"]
+            .into_iter()
+            .map(|name| GroupParticipant::new(fixture_xid(name)))
+            .collect(),
+    );
+    record.set_verifying_key(
+        SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new())
+            .public_key()
+            .unwrap(),
+    );
+    let group_id = record_group(temp.path(), record);
+
+    let target_path = temp.path().join("target.txt");
+    fs::write(&target_path, Envelope::new("Hello").ur_string()).unwrap();
+    let mut args = vec!["sign", "coordinator", "invite"];
+    args.extend_from_slice(extra);
+    let group = group_id.ur_string();
+    args.extend_from_slice(&["--target", target_path.to_str().unwrap()]);
+    args.push(&group);
+    run_frost(temp.path(), &args)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Hubert storage is required"));
+
+    let paths = GroupStatePaths::new(&registry_file(temp.path()));
+    let sessions: Vec<ARID> = paths.sessions(&group_id).unwrap();
+    assert_eq!(sessions.len(), 1);
+    serde_json::from_slice(
+        &fs::read(paths.start_state_path(&group_id, &sessions[0])).unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn start_state_matches_its_documented_fields() {
+    let start = artifact("start.json").unwrap();
+    for extra in [&[][..], &["--include-charter"][..]] {
+        let written = written_start_state(extra);
+        check_example(start, &written).unwrap();
+        assert_eq!(written["include_charter"], !extra.is_empty());
+    }
+}
+
+#[test]
+fn artifacts_are_found_with_or_without_extension() {
+    assert_eq!(artifact("share").unwrap().file, "share.json");
+    assert_eq!(artifact("share.json").unwrap().file, "share.json");
+    let error = artifact("shares").unwrap_err().to_string();
+    assert!(error.contains("Unknown artifact 'shares'"));
+    assert!(error.contains("commitments.json"));
+}
+
+#[test]
+fn schema_lists_every_artifact() {
+    let temp = TempDir::new().unwrap();
+    let mut assert = run_frost(temp.path(), &["schema"]).assert().success();
+    for artifact in ARTIFACTS {
+        assert = assert.stdout(predicate::str::contains(artifact.file));
+    }
+}
+
+#[test]
+fn schema_describes_one_artifact() {
+    let temp = TempDir::new().unwrap();
+    run_frost(temp.path(), &["schema", "commit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "group-state/<GROUP>/signing/<SESSION>/commit.json",
+        ))
+        .stdout(predicate::str::contains("frost sign participant round1"))
+        .stdout(predicate::str::contains("signing_nonces"))
+        .stdout(predicate::str::contains("(optional)"))
+        .stdout(predicate::str::contains("\"signing_commitments\": {"));
+}
+
+#[test]
+fn schema_json_is_machine_readable() {
+    let temp = TempDir::new().unwrap();
+    let output = run_frost(temp.path(), &["schema", "--json", "final.json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let description: serde_json::Value =
+        serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(description["file"], "final.json");
+    assert!(
+        description["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|field| field["path"] == "signature_shares.<XID>")
+    );
+    assert!(
+        description["example"]["signature"]
+            .as_str()
+            .unwrap()
+            .starts_with("ur:signature/")
+    );
+}
+
+#[test]
+fn schema_is_hidden_from_help() {
+    let temp = TempDir::new().unwrap();
+    run_frost(temp.path(), &["--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("schema").not());
+}
+
+#[test]
+fn unknown_artifact_fails() {
+    let temp = TempDir::new().unwrap();
+    run_frost(temp.path(), &["schema", "nonsense"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown artifact 'nonsense'"));
+}
@@ -0,0 +1,112 @@
+//! Golden-fixture tests for the FROST types persisted as JSON in group-state.
+//!
+//! The fixtures in `tests/fixtures/serde` were written by a real ceremony (see
+//! `demo-log.md`). A participant may upgrade the crate between rounds, so the
+//! new build must load these files unchanged and write them back byte for
+//! byte. If a `frost-ed25519` upgrade changes its serde representation, these
+//! tests fail instead of a ceremony in the field.
+//!
+//! To accept a deliberate format change, rerun with `FROST_UPDATE_GOLDEN=1`
+//! and review the fixture diff.
+
+use std::{fmt::Debug, fs, path::Path};
+
+mod common;
+use common::fixture;
+use frost_ed25519::{
+    self as frost,
+    keys::{KeyPackage, PublicKeyPackage, dkg},
+    round1::SigningCommitments,
+    round2::SignatureShare,
+};
+use serde::{Serialize, de::DeserializeOwned};
+
+const HEADER: &str =
+    r#"{"header":{"version":0,"ciphersuite":"FROST-ED25519-SHA512-v1"},"#;
+
+fn update_golden() -> bool { std::env::var_os("FROST_UPDATE_GOLDEN").is_some() }
+
+/// Loads `serde/<name>.json`, checks that it deserializes and reserializes to
+/// the same bytes, and returns the decoded value.
+fn assert_stable<T>(name: &str) -> T
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let golden = fixture(&format!("serde/{name}.json"));
+    let value: T = serde_json::from_str(&golden).unwrap_or_else(|err| {
+        panic!(
+            "{name}.json no longer deserializes, so existing group-state \
+             files would fail to load: {err}"
+        )
+    });
+
+    let serialized = serde_json::to_string(&value).unwrap();
+    if update_golden() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/serde")
+            .join(format!("{name}.json"));
+        fs::write(path, &serialized).unwrap();
+    } else {
+        assert_actual_expected!(
+            serialized,
+            golden,
+            "{name} serializes differently than its golden fixture; the \
+             frost-ed25519 serde representation changed"
+        );
+    }
+
+    let round_trip: T = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(round_trip, value);
+    value
+}
+
+#[test]
+fn golden_fixtures_carry_the_ciphersuite_header() {
+    for name in [
+        "round1_package",
+        "round2_package",
+        "signing_commitments",
+        "signature_share",
+        "key_package",
+        "public_key_package",
+    ] {
+        let golden = fixture(&format!("serde/{name}.json"));
+        assert!(golden.starts_with(HEADER), "{name}.json header changed");
+    }
+}
+
+#[test]
+fn round1_package_is_stable() {
+    assert_stable::<dkg::round1::Package>("round1_package");
+}
+
+#[test]
+fn round2_package_is_stable() {
+    assert_stable::<dkg::round2::Package>("round2_package");
+}
+
+#[test]
+fn signing_commitments_are_stable() {
+    assert_stable::<SigningCommitments>("signing_commitments");
+}
+
+#[test]
+fn signature_share_is_stable() {
+    assert_stable::<SignatureShare>("signature_share");
+}
+
+#[test]
+fn key_package_is_stable() {
+    let package = assert_stable::<KeyPackage>("key_package");
+    assert_eq!(*package.min_signers(), 2);
+    assert_eq!(
+        *package.identifier(),
+        frost::Identifier::try_from(1u16).unwrap()
+    );
+}
+
+#[test]
+fn public_key_package_is_stable() {
+    let package = assert_stable::<PublicKeyPackage>("public_key_package");
+    assert_eq!(package.verifying_shares().len(), 3);
+}
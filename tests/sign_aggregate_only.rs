@@ -0,0 +1,34 @@
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::run_frost;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn aggregate_only_conflicts_with_detached() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "round2",
+            "--aggregate-only",
+            "--detached",
+            &ARID::new().ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn aggregate_only_is_documented() {
+    let temp = TempDir::new().unwrap();
+    run_frost(temp.path(), &["sign", "coordinator", "round2", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--aggregate-only"));
+}
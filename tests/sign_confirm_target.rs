@@ -0,0 +1,184 @@
+use std::{fs, path::Path, time::Duration};
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{owner, registry_file, run_frost, setup_alice_registry};
+use frost_hubert::{
+    cmd::common::GroupStatePaths,
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use gstp::SealedRequest;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// Sets up Alice's registry with a signInvite from Bob already received,
+/// returning the session ID. The group has no key package, so a commit that
+/// gets past the confirmation fails loading it.
+fn setup_received_invite(dir: &Path) -> ARID {
+    setup_alice_registry(dir);
+
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let carol = owner("carol", None);
+    let group_id = ARID::new();
+    let session_id = ARID::new();
+
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    registry
+        .record_group(
+            group_id,
+            GroupRecord::new(
+                "Test group".to_string(),
+                2,
+                GroupParticipant::new(bob.xid()),
+                vec![
+                    GroupParticipant::new(alice.xid()),
+                    GroupParticipant::new(carol.xid()),
+                ],
+            ),
+        )
+        .unwrap();
+    registry.save(&path).unwrap();
+
+    let request = SealedRequest::new(
+        "signInvite",
+        session_id,
+        bob.xid_document().clone(),
+    )
+    .with_parameter("group", group_id)
+    .with_parameter("session", session_id);
+    let request_envelope = request
+        .to_envelope(
+            Some(Date::with_duration_from_now(Duration::from_secs(3600))),
+            Some(bob.xid_document().inception_private_keys().unwrap()),
+            Some(alice.xid_document()),
+        )
+        .unwrap();
+
+    let state = serde_json::json!({
+        "request_envelope": request_envelope.ur_string(),
+        "group": group_id.ur_string(),
+        "session": session_id.ur_string(),
+        "coordinator": bob.xid().ur_string(),
+        "min_signers": 2,
+        "response_arid": ARID::new().ur_string(),
+        "participants": [alice.xid().ur_string(), carol.xid().ur_string()],
+        "target": Envelope::new("Budget").ur_string(),
+    });
+    let state_path =
+        GroupStatePaths::new(&path).receive_state_path(&group_id, &session_id);
+    fs::create_dir_all(state_path.parent().unwrap()).unwrap();
+    fs::write(&state_path, serde_json::to_vec_pretty(&state).unwrap()).unwrap();
+
+    session_id
+}
+
+fn commit(
+    dir: &Path,
+    session_id: &ARID,
+    extra: &[&str],
+) -> assert_cmd::Command {
+    let session = session_id.ur_string();
+    let mut args = vec!["sign", "participant", "round1", "--preview"];
+    args.extend_from_slice(extra);
+    args.push(&session);
+    run_frost(dir, &args)
+}
+
+#[test]
+fn confirm_target_shows_target_and_refuses_without_yes() {
+    let temp = TempDir::new().unwrap();
+    let session_id = setup_received_invite(temp.path());
+
+    commit(temp.path(), &session_id, &["--confirm-target"])
+        .write_stdin("no\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("\"Budget\""))
+        .stderr(predicate::str::contains("Type 'yes'"))
+        .stderr(predicate::str::contains("Target not confirmed"));
+}
+
+#[test]
+fn confirm_target_proceeds_after_yes() {
+    let temp = TempDir::new().unwrap();
+    let session_id = setup_received_invite(temp.path());
+
+    commit(temp.path(), &session_id, &["--confirm-target"])
+        .write_stdin("yes\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Target not confirmed").not())
+        .stderr(predicate::str::contains("Key package path not found"));
+}
+
+#[test]
+fn non_interactive_commit_does_not_prompt() {
+    let temp = TempDir::new().unwrap();
+    let session_id = setup_received_invite(temp.path());
+
+    commit(temp.path(), &session_id, &[])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Type 'yes'").not())
+        .stderr(predicate::str::contains("Key package path not found"));
+}
+
+#[test]
+fn confirm_target_conflicts_with_reject() {
+    let temp = TempDir::new().unwrap();
+    let session_id = setup_received_invite(temp.path());
+
+    commit(
+        temp.path(),
+        &session_id,
+        &["--confirm-target", "--reject", "Out of office"],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn yes_confirms_without_reading_stdin() {
+    let temp = TempDir::new().unwrap();
+    let session_id = setup_received_invite(temp.path());
+
+    commit(temp.path(), &session_id, &["--confirm-target", "--yes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("yes (--yes)"))
+        .stderr(predicate::str::contains("Key package path not found"));
+}
+
+#[test]
+fn no_input_fails_instead_of_prompting() {
+    let temp = TempDir::new().unwrap();
+    let session_id = setup_received_invite(temp.path());
+
+    // --no-input takes precedence over --yes
+    for extra in [&["--no-input"][..], &["--no-input", "--yes"]] {
+        let mut args = vec!["--confirm-target"];
+        args.extend_from_slice(extra);
+        commit(temp.path(), &session_id, &args)
+            .write_stdin("yes\n")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--no-input was given"))
+            .stderr(predicate::str::contains("Type 'yes'").not());
+    }
+}
+
+#[test]
+fn closed_stdin_is_an_error_not_a_hang() {
+    let temp = TempDir::new().unwrap();
+    let session_id = setup_received_invite(temp.path());
+
+    commit(temp.path(), &session_id, &["--confirm-target"])
+        .write_stdin("")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No confirmation on stdin"));
+}
@@ -0,0 +1,170 @@
+use std::{fs, path::Path, time::Duration};
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{owner, registry_file, run_frost, setup_alice_registry};
+use frost_hubert::{
+    cmd::common::GroupStatePaths,
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use gstp::SealedRequest;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// Sets up Alice's registry with a signInvite from Bob already received,
+/// returning the session ID.
+fn setup_received_invite(dir: &Path) -> ARID {
+    setup_alice_registry(dir);
+
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let carol = owner("carol", None);
+    let group_id = ARID::new();
+    let session_id = ARID::new();
+
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    registry
+        .record_group(
+            group_id,
+            GroupRecord::new(
+                "Test group".to_string(),
+                2,
+                GroupParticipant::new(bob.xid()),
+                vec![
+                    GroupParticipant::new(alice.xid()),
+                    GroupParticipant::new(carol.xid()),
+                ],
+            ),
+        )
+        .unwrap();
+    registry.save(&path).unwrap();
+
+    let request = SealedRequest::new(
+        "signInvite",
+        session_id,
+        bob.xid_document().clone(),
+    )
+    .with_parameter("group", group_id)
+    .with_parameter("session", session_id);
+    let request_envelope = request
+        .to_envelope(
+            Some(Date::with_duration_from_now(Duration::from_secs(3600))),
+            Some(bob.xid_document().inception_private_keys().unwrap()),
+            Some(alice.xid_document()),
+        )
+        .unwrap();
+
+    let state = serde_json::json!({
+        "request_envelope": request_envelope.ur_string(),
+        "group": group_id.ur_string(),
+        "session": session_id.ur_string(),
+        "coordinator": bob.xid().ur_string(),
+        "min_signers": 2,
+        "response_arid": ARID::new().ur_string(),
+        "participants": [alice.xid().ur_string(), carol.xid().ur_string()],
+        "target": Envelope::new("Budget").ur_string(),
+    });
+    let state_path =
+        GroupStatePaths::new(&path).receive_state_path(&group_id, &session_id);
+    fs::create_dir_all(state_path.parent().unwrap()).unwrap();
+    fs::write(&state_path, serde_json::to_vec_pretty(&state).unwrap()).unwrap();
+
+    session_id
+}
+
+#[test]
+fn decline_preview_is_a_rejection_with_reason() {
+    let temp = TempDir::new().unwrap();
+    let session_id = setup_received_invite(temp.path());
+
+    let output = run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "participant",
+            "decline",
+            "--preview",
+            "--reason",
+            "Out of office",
+            &session_id.ur_string(),
+        ],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+
+    let ur = String::from_utf8(output.stdout).unwrap();
+    let response = Envelope::from_ur_string(ur.trim()).unwrap();
+    let formatted = response.format();
+    assert!(formatted.contains("'error'"));
+    assert!(formatted.contains("signCommitReject"));
+    assert!(formatted.contains("Out of office"));
+    assert!(!formatted.contains("'result'"));
+}
+
+#[test]
+fn decline_requires_storage_or_preview() {
+    let temp = TempDir::new().unwrap();
+    let session_id = setup_received_invite(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "participant",
+            "decline",
+            "--reason",
+            "Out of office",
+            &session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Hubert storage is required"));
+}
+
+#[test]
+fn decline_requires_a_reason() {
+    let temp = TempDir::new().unwrap();
+    let session_id = setup_received_invite(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "participant",
+            "decline",
+            "--preview",
+            "--reason",
+            " ",
+            &session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("--reason cannot be empty"));
+}
+
+#[test]
+fn decline_unknown_session_fails() {
+    let temp = TempDir::new().unwrap();
+    setup_received_invite(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "participant",
+            "decline",
+            "--preview",
+            "--reason",
+            "Out of office",
+            &ARID::new().ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("No sign_receive.json"));
+}
@@ -0,0 +1,251 @@
+use std::{fs, path::Path};
+
+mod common;
+use bc_components::{
+    ARID, Ed25519PrivateKey, Signer, SigningPrivateKey, SigningPublicKey,
+};
+use bc_envelope::prelude::*;
+use common::{fixture_xid, registry_file, run_frost, setup_alice_registry};
+use frost_hubert::{
+    cmd::common::GroupStatePaths,
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+struct Session {
+    group_id: ARID,
+    session_id: ARID,
+    target: Envelope,
+    verifying_key: SigningPublicKey,
+}
+
+/// Writes a coordinator's completed session for Alice, Bob, and Carol in
+/// which Bob and Carol signed. `signing_key` produces the final signature;
+/// the registry records `group_key` as the group's verifying key.
+fn setup_completed_session(
+    dir: &Path,
+    signing_key: &SigningPrivateKey,
+    group_key: SigningPublicKey,
+) -> Session {
+    setup_alice_registry(dir);
+
+    let [bob, carol] = ["bob", "carol"].map(fixture_xid);
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(fixture_xid("alice")),
+        vec![GroupParticipant::new(bob), GroupParticipant::new(carol)],
+    );
+    record.set_verifying_key(group_key.clone());
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    let group_id = ARID::new();
+    registry.record_group(group_id, record).unwrap();
+    registry.save(&path).unwrap();
+
+    let session_id = ARID::new();
+    let target = Envelope::new("Budget");
+    let message = target.subject().digest().data().to_vec();
+    let signature = signing_key.sign(&message).unwrap();
+
+    let paths = GroupStatePaths::new(&path);
+    let write = |file: &Path, value: serde_json::Value| {
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, serde_json::to_vec_pretty(&value).unwrap()).unwrap();
+    };
+    write(
+        &paths.start_state_path(&group_id, &session_id),
+        serde_json::json!({
+            "group": group_id.ur_string(),
+            "session_id": session_id.ur_string(),
+            "min_signers": 2,
+            "participants": {
+                bob.ur_string(): {},
+                carol.ur_string(): {},
+            },
+            "target": target.ur_string(),
+        }),
+    );
+    write(
+        &paths.commitments_path(&group_id, &session_id),
+        serde_json::json!({
+            "group": group_id.ur_string(),
+            "session": session_id.ur_string(),
+            "commitments": {
+                bob.ur_string(): { "commitments": "bob-commitments" },
+                carol.ur_string(): { "commitments": "carol-commitments" },
+            },
+        }),
+    );
+    write(
+        &paths.final_state_path(&group_id, &session_id),
+        serde_json::json!({
+            "group": group_id.ur_string(),
+            "session": session_id.ur_string(),
+            "signature": signature.ur_string(),
+            "signature_shares": {
+                bob.ur_string(): "bob-share",
+                carol.ur_string(): "carol-share",
+            },
+        }),
+    );
+
+    Session {
+        group_id,
+        session_id,
+        target,
+        verifying_key: group_key,
+    }
+}
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_slice(&fs::read(path).unwrap()).unwrap()
+}
+
+#[test]
+fn export_writes_verified_bundle() {
+    let temp = TempDir::new().unwrap();
+    let signing_key = SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new());
+    let session = setup_completed_session(
+        temp.path(),
+        &signing_key,
+        signing_key.public_key().unwrap(),
+    );
+    let out = temp.path().join("bundle");
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "export",
+            "--out",
+            out.to_str().unwrap(),
+            &session.session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("bundle"));
+
+    let manifest = read_json(&out.join("manifest.json"));
+    assert_eq!(manifest["group"], session.group_id.ur_string());
+    assert_eq!(manifest["session"], session.session_id.ur_string());
+    assert_eq!(manifest["role"], "coordinator");
+    assert_eq!(manifest["verified"], true);
+    assert_eq!(manifest["pre_hashed"], false);
+    assert_eq!(manifest["verifying_key"], session.verifying_key.ur_string());
+    assert_eq!(
+        manifest["files"],
+        serde_json::json!([
+            "signature.ur",
+            "signed.envelope",
+            "shares.json",
+            "participants.json",
+        ])
+    );
+
+    let signed = Envelope::from_ur_string(
+        fs::read_to_string(out.join("signed.envelope"))
+            .unwrap()
+            .trim(),
+    )
+    .unwrap();
+    assert_eq!(signed.subject().digest(), session.target.subject().digest());
+    signed
+        .verify_signature_from(&session.verifying_key)
+        .unwrap();
+
+    let shares = read_json(&out.join("shares.json"));
+    assert_eq!(shares["signature_shares"].as_object().unwrap().len(), 2);
+    let bob = fixture_xid("bob").ur_string();
+    assert_eq!(shares["commitments"][&bob], "bob-commitments");
+
+    let participants = read_json(&out.join("participants.json"));
+    let participants = participants.as_array().unwrap();
+    assert_eq!(participants.len(), 2);
+    assert!(participants.iter().all(|p| p["signer"] == true));
+    let mut names: Vec<&str> = participants
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["Bob", "Carol"]);
+}
+
+#[test]
+fn export_records_failed_verification() {
+    let temp = TempDir::new().unwrap();
+    let signing_key = SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new());
+    let other_key = SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new());
+    let session = setup_completed_session(
+        temp.path(),
+        &signing_key,
+        other_key.public_key().unwrap(),
+    );
+    let out = temp.path().join("bundle");
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "export",
+            "--out",
+            out.to_str().unwrap(),
+            &session.session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("does not verify"));
+
+    let manifest = read_json(&out.join("manifest.json"));
+    assert_eq!(manifest["verified"], false);
+    assert!(!out.join("signed.envelope").exists());
+}
+
+#[test]
+fn export_refuses_non_empty_directory() {
+    let temp = TempDir::new().unwrap();
+    let signing_key = SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new());
+    let session = setup_completed_session(
+        temp.path(),
+        &signing_key,
+        signing_key.public_key().unwrap(),
+    );
+    let out = temp.path().join("bundle");
+    fs::create_dir_all(&out).unwrap();
+    fs::write(out.join("keep.txt"), "existing").unwrap();
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "export",
+            "--out",
+            out.to_str().unwrap(),
+            &session.session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("is not empty"));
+}
+
+#[test]
+fn export_requires_completed_session() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "export",
+            "--out",
+            temp.path().join("bundle").to_str().unwrap(),
+            &ARID::new().ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("No final.json found"));
+}
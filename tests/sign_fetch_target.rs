@@ -0,0 +1,90 @@
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::run_frost;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn fetch_target_requires_storage() {
+    let temp = TempDir::new().unwrap();
+    let arid = ARID::new().ur_string();
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "invite",
+            "--fetch-target",
+            &arid,
+            "ur:arid/placeholder",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "--fetch-target requires Hubert storage",
+    ));
+}
+
+#[test]
+fn fetch_target_conflicts_with_other_targets() {
+    let temp = TempDir::new().unwrap();
+    let arid = ARID::new().ur_string();
+    for extra in [
+        &["--target", "target.txt"][..],
+        &["--digest", "00ff", "--pre-hashed"][..],
+        &["--preview"][..],
+    ] {
+        let mut args =
+            vec!["sign", "coordinator", "invite", "--fetch-target", &arid];
+        args.extend_from_slice(extra);
+        args.push("ur:arid/placeholder");
+        run_frost(temp.path(), &args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
+
+#[test]
+fn timeout_requires_fetch_target() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "invite",
+            "--target",
+            "target.txt",
+            "--timeout",
+            "5",
+            "ur:arid/placeholder",
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("--fetch-target"));
+}
+
+#[test]
+fn verify_target_requires_storage() {
+    let temp = TempDir::new().unwrap();
+    let request = Envelope::new("Not a request").ur_string();
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "participant",
+            "receive",
+            "--verify-target",
+            &request,
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "--verify-target requires Hubert storage",
+    ));
+}
@@ -0,0 +1,34 @@
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::run_frost;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn no_dispatch_conflicts_with_preview_finalize() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "coordinator",
+            "round2",
+            "--no-dispatch",
+            "--preview-finalize",
+            &ARID::new().ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn no_dispatch_is_documented() {
+    let temp = TempDir::new().unwrap();
+    run_frost(temp.path(), &["sign", "coordinator", "round2", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--no-dispatch"));
+}
@@ -0,0 +1,205 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+mod common;
+use bc_components::{ARID, XID};
+use bc_envelope::prelude::*;
+use common::{
+    fixture_xid, record_group, registry_file, run_frost, setup_alice_registry,
+};
+use frost_ed25519::{self as frost, rand_core::OsRng};
+use frost_hubert::{
+    cmd::{
+        common::GroupStatePaths,
+        sign::common::{finalized_locally, record_finalize_result},
+    },
+    registry::{GroupParticipant, GroupRecord},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn post_to_self_is_documented() {
+    let temp = TempDir::new().unwrap();
+    run_frost(temp.path(), &["sign", "coordinator", "round2", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--post-to-self"));
+}
+
+/// Alice's and Bob's signing commitments and signature shares for a 2-of-2
+/// session over `message`.
+fn signing_round(
+    message: &[u8],
+) -> (
+    BTreeMap<XID, frost::round1::SigningCommitments>,
+    BTreeMap<XID, frost::round2::SignatureShare>,
+) {
+    let (shares, _) = frost::keys::generate_with_dealer(
+        2,
+        2,
+        frost::keys::IdentifierList::Default,
+        OsRng,
+    )
+    .unwrap();
+    let signers: Vec<(XID, frost::keys::KeyPackage)> =
+        [fixture_xid("alice"), fixture_xid("bob")]
+            .into_iter()
+            .zip(shares.into_values())
+            .map(|(xid, share)| {
+                (xid, frost::keys::KeyPackage::try_from(share).unwrap())
+            })
+            .collect();
+    let rounds: Vec<_> = signers
+        .iter()
+        .map(|(_, key_package)| {
+            frost::round1::commit(key_package.signing_share(), &mut OsRng)
+        })
+        .collect();
+    let signing_package = frost::SigningPackage::new(
+        signers
+            .iter()
+            .zip(&rounds)
+            .map(|((_, key_package), (_, commitments))| {
+                (*key_package.identifier(), *commitments)
+            })
+            .collect(),
+        message,
+    );
+    let mut commitments = BTreeMap::new();
+    let mut signature_shares = BTreeMap::new();
+    for ((xid, key_package), (nonces, commits)) in signers.iter().zip(&rounds) {
+        commitments.insert(*xid, *commits);
+        signature_shares.insert(
+            *xid,
+            frost::round2::sign(&signing_package, nonces, key_package).unwrap(),
+        );
+    }
+    (commitments, signature_shares)
+}
+
+fn write_json(path: &Path, value: serde_json::Value) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, serde_json::to_vec_pretty(&value).unwrap()).unwrap();
+}
+
+#[test]
+fn recorded_finalize_result_counts_as_finalized_locally() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("final.json");
+    let finalize_arid = ARID::new();
+    let (commitments, _) = signing_round(b"quarterly budget");
+    let signed = Envelope::new("quarterly budget");
+
+    assert!(!finalized_locally(&path, &finalize_arid).unwrap());
+
+    write_json(&path, serde_json::json!({ "signature": "recorded" }));
+    assert!(!finalized_locally(&path, &finalize_arid).unwrap());
+
+    record_finalize_result(&path, &finalize_arid, &commitments, &signed)
+        .unwrap();
+    assert!(finalized_locally(&path, &finalize_arid).unwrap());
+    assert!(!finalized_locally(&path, &ARID::new()).unwrap());
+
+    let root: serde_json::Value =
+        serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    assert_eq!(root["signature"], "recorded");
+    assert_eq!(root["signed_target"], signed.ur_string());
+    assert_eq!(
+        root["commitments"].as_object().unwrap().len(),
+        commitments.len()
+    );
+}
+
+#[test]
+fn recording_a_finalize_result_needs_an_aggregated_signature() {
+    let temp = TempDir::new().unwrap();
+    let (commitments, _) = signing_round(b"quarterly budget");
+
+    let err = record_finalize_result(
+        &temp.path().join("final.json"),
+        &ARID::new(),
+        &commitments,
+        &Envelope::new("quarterly budget"),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Failed to read"));
+}
+
+#[test]
+fn participant_finalize_succeeds_after_self_finalize() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+    let alice = fixture_xid("alice");
+    let group_id = record_group(
+        temp.path(),
+        GroupRecord::new(
+            "Self-finalized group".to_string(),
+            2,
+            GroupParticipant::new(alice),
+            vec![
+                GroupParticipant::new(alice),
+                GroupParticipant::new(fixture_xid("bob")),
+            ],
+        ),
+    );
+
+    // The state `sign coordinator round2` leaves for a coordinator that
+    // signs without --post-to-self
+    let session_id = ARID::new();
+    let finalize_arid = ARID::new();
+    let target = Envelope::new("quarterly budget");
+    let (commitments, signature_shares) = signing_round(b"quarterly budget");
+    let paths = GroupStatePaths::new(&registry_file(temp.path()));
+    write_json(
+        &paths.receive_state_path(&group_id, &session_id),
+        serde_json::json!({
+            "session": session_id.ur_string(),
+            "group": group_id.ur_string(),
+            "coordinator": alice.ur_string(),
+            "participants": commitments
+                .keys()
+                .map(|xid| xid.ur_string())
+                .collect::<Vec<_>>(),
+            "min_signers": 2,
+            "target": target.ur_string(),
+        }),
+    );
+    write_json(
+        &paths.share_state_path(&group_id, &session_id),
+        serde_json::json!({
+            "session": session_id.ur_string(),
+            "finalize_arid": finalize_arid.ur_string(),
+            "signature_share": signature_shares[&alice],
+            "commitments": commitments
+                .iter()
+                .map(|(xid, commits)| (xid.ur_string(), commits))
+                .collect::<BTreeMap<_, _>>(),
+        }),
+    );
+    let final_path = paths.final_state_path(&group_id, &session_id);
+    write_json(&final_path, serde_json::json!({ "signature": "recorded" }));
+    record_finalize_result(&final_path, &finalize_arid, &commitments, &target)
+        .unwrap();
+
+    // No storage is reachable: finalize must stop before fetching
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "participant",
+            "finalize",
+            "--storage",
+            "server",
+            "--host",
+            "127.0.0.1",
+            "--port",
+            "1",
+            &session_id.ur_string(),
+        ],
+    )
+    .assert()
+    .success()
+    .stderr(predicate::str::contains(
+        "already recorded your finalize result",
+    ));
+}
@@ -0,0 +1,186 @@
+use std::{fs, path::Path, time::Duration};
+
+mod common;
+use bc_components::{ARID, Ed25519PrivateKey, SigningPrivateKey};
+use bc_envelope::prelude::*;
+use common::{owner, registry_file, run_frost, setup_alice_registry};
+use frost_hubert::{
+    cmd::sign::{
+        common::{embedded_charter, signing_message, with_charter},
+        participant::{TARGET_SUMMARY_BYTES, format_target},
+    },
+    registry::{GroupParticipant, GroupRecord, Registry},
+};
+use gstp::SealedRequest;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn large_target() -> Envelope {
+    Envelope::new("x".repeat(TARGET_SUMMARY_BYTES))
+        .add_assertion("note", "Quarterly budget")
+        .add_assertion("note", "Second note")
+        .add_assertion("amount", 42)
+}
+
+/// Sets up Alice's registry with a finalized group coordinated by Bob and
+/// writes Bob's signInvite for `target` to a file, returning its path.
+fn setup_sign_invite(dir: &Path, target: &Envelope) -> String {
+    setup_alice_registry(dir);
+
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let carol = owner("carol", None);
+    let group_id = ARID::new();
+    let session_id = ARID::new();
+
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(bob.xid()),
+        vec![
+            GroupParticipant::new(alice.xid()),
+            GroupParticipant::new(carol.xid()),
+        ],
+    );
+    record.set_verifying_key(
+        SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new())
+            .public_key()
+            .unwrap(),
+    );
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    registry.record_group(group_id, record).unwrap();
+    registry.save(&path).unwrap();
+
+    let valid_until = Date::with_duration_from_now(Duration::from_secs(3600));
+    let mut request = SealedRequest::new(
+        "signInvite",
+        session_id,
+        bob.xid_document().clone(),
+    )
+    .with_parameter("group", group_id)
+    .with_parameter("session", session_id)
+    .with_parameter("target", target.clone())
+    .with_parameter("minSigners", 2u64)
+    .with_parameter("validUntil", valid_until);
+    for participant in [&alice, &carol] {
+        let encryption_key =
+            participant.xid_document().encryption_key().unwrap();
+        let entry = Envelope::new(participant.xid()).add_assertion(
+            "response_arid",
+            ARID::new()
+                .to_envelope()
+                .encrypt_to_recipient(encryption_key),
+        );
+        request = request.with_parameter("participant", entry);
+    }
+    let envelope = request
+        .to_envelope(
+            Some(valid_until),
+            Some(bob.xid_document().inception_private_keys().unwrap()),
+            Some(alice.xid_document()),
+        )
+        .unwrap();
+
+    let invite_path = dir.join("invite.txt");
+    fs::write(&invite_path, envelope.ur_string()).unwrap();
+    invite_path.to_str().unwrap().to_owned()
+}
+
+fn receive(dir: &Path, invite_path: &str, extra: &[&str]) -> String {
+    let invite = fs::read_to_string(invite_path).unwrap();
+    let mut args = vec!["sign", "participant", "receive"];
+    args.extend_from_slice(extra);
+    args.push(&invite);
+    let output = run_frost(dir, &args).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn small_target_is_formatted_in_full() {
+    let target = Envelope::new("Budget").add_assertion("note", "Q3");
+    assert_eq!(format_target(&target, false), target.format());
+}
+
+#[test]
+fn large_target_is_summarized() {
+    let target = large_target();
+    let summary = format_target(&target, false);
+    assert!(!summary.contains(&"x".repeat(100)));
+    assert!(summary.contains("Subject type: leaf"));
+    assert!(summary.contains(&target.subject().digest().ur_string()));
+    assert!(
+        summary
+            .contains(&format!("Size: {} bytes", target.to_cbor_data().len()))
+    );
+    assert!(summary.contains("Assertions: 3 (\"amount\", \"note\")"));
+
+    assert_eq!(format_target(&target, true), target.format());
+}
+
+#[test]
+fn receive_summarizes_large_target_unless_full() {
+    let temp = TempDir::new().unwrap();
+    let target = large_target();
+    let invite = setup_sign_invite(temp.path(), &target);
+
+    let stdout = receive(temp.path(), &invite, &[]);
+    assert!(stdout.contains("use --full to show it all"));
+    assert!(!stdout.contains(&"x".repeat(100)));
+
+    let stdout = receive(temp.path(), &invite, &["--full"]);
+    assert!(stdout.contains(&"x".repeat(TARGET_SUMMARY_BYTES)));
+}
+
+#[test]
+fn receive_shows_small_target_in_full() {
+    let temp = TempDir::new().unwrap();
+    let invite =
+        setup_sign_invite(temp.path(), &Envelope::new("Quarterly budget"));
+
+    run_frost(
+        temp.path(),
+        &[
+            "sign",
+            "participant",
+            "receive",
+            &fs::read_to_string(invite).unwrap(),
+        ],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("\"Quarterly budget\""))
+    .stdout(predicate::str::contains("--full").not());
+}
+
+#[test]
+fn embedded_charter_changes_the_signed_digest() {
+    let target = Envelope::new("Quarterly budget");
+    let chartered = with_charter(&target, "Test group");
+
+    assert_eq!(embedded_charter(&target), None);
+    assert_eq!(embedded_charter(&chartered).as_deref(), Some("Test group"));
+    assert_ne!(
+        signing_message(&chartered).unwrap(),
+        signing_message(&target).unwrap()
+    );
+    assert_ne!(
+        signing_message(&chartered).unwrap(),
+        signing_message(&with_charter(&target, "Other group")).unwrap()
+    );
+}
+
+#[test]
+fn receive_shows_embedded_charter() {
+    let temp = TempDir::new().unwrap();
+    let target = with_charter(&Envelope::new("Quarterly budget"), "Test group");
+    let invite = setup_sign_invite(temp.path(), &target);
+
+    let stdout = receive(temp.path(), &invite, &[]);
+    assert!(stdout.contains("Charter: Test group"));
+}
@@ -0,0 +1,149 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bc_components::XID;
+use bc_envelope::prelude::*;
+use frost_ed25519::{self as frost, keys::IdentifierList, rand_core::OsRng};
+use frost_hubert::cmd::sign::common::{signers_json, verify_signature_shares};
+
+struct Session {
+    signing_package: frost::SigningPackage,
+    shares: BTreeMap<XID, frost::round2::SignatureShare>,
+    identifiers: HashMap<XID, frost::Identifier>,
+    public_key_package: frost::keys::PublicKeyPackage,
+}
+
+/// Signs a message with two of three dealt shares.
+fn session() -> Session {
+    let (secret_shares, public_key_package) =
+        frost::keys::generate_with_dealer(3, 2, IdentifierList::Default, OsRng)
+            .unwrap();
+    let identifiers: HashMap<XID, frost::Identifier> = secret_shares
+        .keys()
+        .enumerate()
+        .map(|(i, identifier)| (XID::from_data([i as u8 + 1; 32]), *identifier))
+        .collect();
+
+    let signers: Vec<(XID, frost::keys::KeyPackage)> = identifiers
+        .iter()
+        .take(2)
+        .map(|(xid, identifier)| {
+            let share = secret_shares[identifier].clone();
+            (*xid, frost::keys::KeyPackage::try_from(share).unwrap())
+        })
+        .collect();
+
+    let mut nonces = BTreeMap::new();
+    let mut commitments = BTreeMap::new();
+    for (xid, key_package) in &signers {
+        let (nonce, commitment) =
+            frost::round1::commit(key_package.signing_share(), &mut OsRng);
+        nonces.insert(*xid, nonce);
+        commitments.insert(*key_package.identifier(), commitment);
+    }
+    let message = Envelope::new("Budget").subject().digest().data().to_vec();
+    let signing_package = frost::SigningPackage::new(commitments, &message);
+
+    let shares = signers
+        .iter()
+        .map(|(xid, key_package)| {
+            let share = frost::round2::sign(
+                &signing_package,
+                &nonces[xid],
+                key_package,
+            )
+            .unwrap();
+            (*xid, share)
+        })
+        .collect();
+
+    Session {
+        signing_package,
+        shares,
+        identifiers,
+        public_key_package,
+    }
+}
+
+#[test]
+fn valid_shares_pass() {
+    let session = session();
+    verify_signature_shares(
+        &session.signing_package,
+        &session.shares,
+        &session.identifiers,
+        &session.public_key_package,
+    )
+    .unwrap();
+}
+
+#[test]
+fn invalid_share_is_attributed_to_its_sender() {
+    let mut session = session();
+    let xids: Vec<XID> = session.shares.keys().copied().collect();
+    let (honest, cheater) = (xids[0], xids[1]);
+
+    // The cheater resends the honest signer's share as their own.
+    let copied = session.shares[&honest];
+    session.shares.insert(cheater, copied);
+
+    let error = verify_signature_shares(
+        &session.signing_package,
+        &session.shares,
+        &session.identifiers,
+        &session.public_key_package,
+    )
+    .unwrap_err()
+    .to_string();
+    assert_eq!(
+        error,
+        format!(
+            "Participant {} submitted an invalid signature share",
+            cheater.ur_string()
+        )
+    );
+}
+
+#[test]
+fn every_invalid_share_is_reported() {
+    let mut session = session();
+    let xids: Vec<XID> = session.shares.keys().copied().collect();
+    let (first, second) = (session.shares[&xids[0]], session.shares[&xids[1]]);
+    session.shares.insert(xids[0], second);
+    session.shares.insert(xids[1], first);
+
+    let error = verify_signature_shares(
+        &session.signing_package,
+        &session.shares,
+        &session.identifiers,
+        &session.public_key_package,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(error.starts_with("Participants "));
+    for xid in xids {
+        assert!(error.contains(&xid.ur_string()));
+    }
+}
+
+#[test]
+fn signers_lists_only_the_signing_subset() {
+    let session = session();
+    let signers = signers_json(&session.shares, &session.identifiers).unwrap();
+    let signers = signers.as_array().unwrap();
+
+    // Two of the three group members signed
+    assert_eq!(session.identifiers.len(), 3);
+    assert_eq!(signers.len(), 2);
+    for (signer, xid) in signers.iter().zip(session.shares.keys()) {
+        assert_eq!(signer["xid"], xid.ur_string());
+        assert_eq!(
+            signer["identifier"],
+            serde_json::to_value(session.identifiers[xid]).unwrap()
+        );
+    }
+
+    let error = signers_json(&session.shares, &HashMap::new())
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("has no FROST identifier"), "{error}");
+}
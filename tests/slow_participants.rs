@@ -0,0 +1,82 @@
+mod common;
+use bc_components::{ARID, XID};
+use common::{mixed_committee, run_frost};
+use frost_hubert::{
+    cmd::common::slow_participant_timeouts, registry::Registry,
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn xid_of(registry: &Registry, pet_name: &str) -> XID {
+    *registry.participant_by_pet_name(pet_name).unwrap().0
+}
+
+fn error(specs: &[&str]) -> String {
+    let (registry, group) = mixed_committee();
+    let specs: Vec<String> = specs.iter().map(|s| s.to_string()).collect();
+    slow_participant_timeouts(&registry, &group, &specs)
+        .unwrap_err()
+        .to_string()
+}
+
+#[test]
+fn members_resolve_by_pet_name_or_xid() {
+    let (registry, group) = mixed_committee();
+    let bob = xid_of(&registry, "Bob");
+    let carol = xid_of(&registry, "Carol");
+    let alice = registry.owner().unwrap().xid();
+    let specs = vec![
+        "Bob:300".to_string(),
+        format!("{}:120", carol.ur_string()),
+        "Alice:60".to_string(),
+    ];
+
+    let timeouts =
+        slow_participant_timeouts(&registry, &group, &specs).unwrap();
+    assert_eq!(timeouts.len(), 3);
+    assert_eq!(timeouts[&bob], 300);
+    assert_eq!(timeouts[&carol], 120);
+    assert_eq!(timeouts[&alice], 60);
+}
+
+#[test]
+fn non_members_are_rejected() {
+    assert!(error(&["Dan:60"]).contains("is not a member of this group"));
+    assert!(error(&["Erin:60"]).contains("'Erin' not found"));
+}
+
+#[test]
+fn malformed_specs_are_rejected() {
+    assert!(error(&["Bob"]).contains("expected PARTICIPANT:SECONDS"));
+    assert!(
+        error(&["Bob:soon"]).contains("Invalid --slow-participant timeout")
+    );
+    assert!(error(&["Bob:60", "Bob:90"]).contains("more than once"));
+}
+
+#[test]
+fn slow_participants_conflict_with_deadline() {
+    let temp = TempDir::new().unwrap();
+    for command in [
+        ["sign", "coordinator", "round1"],
+        ["sign", "coordinator", "round2"],
+    ] {
+        let id = ARID::new().ur_string();
+        let mut args = command.to_vec();
+        args.extend_from_slice(&[
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--deadline",
+            "2999-01-01T00:00:00Z",
+            "--slow-participant",
+            "Bob:600",
+            &id,
+        ]);
+        run_frost(temp.path(), &args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
@@ -0,0 +1,52 @@
+use std::io::IsTerminal;
+
+mod common;
+use common::{fixture_xid, run_frost};
+use frost_hubert::cmd::parallel::ProgressDisplay;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn tick_interval_out_of_range_is_rejected() {
+    let temp = TempDir::new().unwrap();
+    for value in ["19", "1001"] {
+        run_frost(temp.path(), &["--tick-interval-ms", value, "version"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--tick-interval-ms"));
+    }
+}
+
+#[test]
+fn tick_settings_are_accepted() {
+    let temp = TempDir::new().unwrap();
+    let runs: [&[&str]; 2] = [
+        &["--no-animation", "version"],
+        &["--tick-interval-ms", "250", "version"],
+    ];
+    for args in runs {
+        run_frost(temp.path(), args).assert().success();
+    }
+}
+
+#[test]
+fn finishing_without_participants_writes_nothing() {
+    let mut out = Vec::new();
+    ProgressDisplay::new_get(&[], 10)
+        .finish_to(&mut out)
+        .unwrap();
+    assert!(out.is_empty());
+}
+
+#[test]
+fn finishing_hidden_bars_writes_nothing() {
+    let participants = [(fixture_xid("bob"), "Bob".to_string())];
+    let mut out = Vec::new();
+    ProgressDisplay::new_get(&participants, 10)
+        .finish_to(&mut out)
+        .unwrap();
+    // The bars draw to stderr and are hidden when it is not a terminal, as
+    // under `cargo test` with captured output
+    let drawn = std::io::stderr().is_terminal();
+    assert_eq!(out, if drawn { b"\n".to_vec() } else { Vec::new() });
+}
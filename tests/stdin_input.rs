@@ -0,0 +1,73 @@
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{run_frost, setup_alice_registry};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn invite_preview_pipes_into_inspect() {
+    let temp = TempDir::new().unwrap();
+    setup_alice_registry(temp.path());
+
+    let output = run_frost(
+        temp.path(),
+        &["dkg", "coordinator", "invite", "--preview", "Bob", "Carol"],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+
+    // The preview is piped verbatim, trailing newline included
+    run_frost(temp.path(), &["dkg", "coordinator", "inspect", "-"])
+        .write_stdin(output.stdout)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Identifier scheme"));
+}
+
+#[test]
+fn dash_reads_arid_from_stdin() {
+    let temp = TempDir::new().unwrap();
+    let out = temp.path().join("bundle");
+    run_frost(
+        temp.path(),
+        &["sign", "export", "--out", out.to_str().unwrap(), "-"],
+    )
+    .write_stdin(format!("{}\n", ARID::new().ur_string()))
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("No final.json found"));
+}
+
+#[test]
+fn dash_requires_a_ur_on_stdin() {
+    let temp = TempDir::new().unwrap();
+    let out = temp.path().join("bundle");
+    run_frost(
+        temp.path(),
+        &["sign", "export", "--out", out.to_str().unwrap(), "-"],
+    )
+    .write_stdin("\n")
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Expected a UR on stdin"));
+}
+
+#[test]
+fn dash_reads_only_one_ur() {
+    let temp = TempDir::new().unwrap();
+    let out = temp.path().join("bundle");
+    run_frost(
+        temp.path(),
+        &["sign", "export", "--out", out.to_str().unwrap(), "-"],
+    )
+    .write_stdin(format!(
+        "{}\n{}\n",
+        ARID::new().ur_string(),
+        ARID::new().ur_string()
+    ))
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Expected a single UR on stdin"));
+}
@@ -0,0 +1,109 @@
+use std::{collections::HashMap, sync::Mutex};
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::fixture_xid;
+use frost_hubert::{
+    cmd::storage::{
+        DeleteOutcome, StorageClient, StorageSelection, delete_collected,
+    },
+    registry::PendingRequests,
+};
+
+#[test]
+fn delete_reports_unsupported_backends() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let outcome = runtime.block_on(async {
+        let client = StorageClient::from_selection(StorageSelection::Server {
+            host: "127.0.0.1".to_owned(),
+            port: 1,
+        })
+        .await
+        .unwrap();
+        client.delete(&ARID::new()).await.unwrap()
+    });
+    assert_eq!(outcome, DeleteOutcome::Unsupported);
+}
+
+#[test]
+fn cleanup_targets_only_collected_participants() {
+    let bob = fixture_xid("bob");
+    let carol = fixture_xid("carol");
+    let dan = fixture_xid("dan");
+    let (bob_arid, carol_arid, dan_arid) =
+        (ARID::new(), ARID::new(), ARID::new());
+
+    let mut pending = PendingRequests::new();
+    pending.add_collect_only(bob, bob_arid);
+    pending.add_collect_only(carol, carol_arid);
+    pending.add_collect_only(dan, dan_arid);
+
+    assert_eq!(
+        pending.collect_arids_for(&[dan, bob]),
+        vec![bob_arid, dan_arid]
+    );
+    assert!(pending.collect_arids_for(&[]).is_empty());
+}
+
+/// Hubert storage holding one response envelope per participant, with a
+/// backend that can delete.
+fn stored_responses(arids: &[ARID]) -> Mutex<HashMap<ARID, Envelope>> {
+    Mutex::new(
+        arids
+            .iter()
+            .map(|arid| (*arid, Envelope::new("response")))
+            .collect(),
+    )
+}
+
+#[test]
+fn cleanup_removes_collected_keys() {
+    let bob = fixture_xid("bob");
+    let carol = fixture_xid("carol");
+    let dan = fixture_xid("dan");
+    let (bob_arid, carol_arid, dan_arid) =
+        (ARID::new(), ARID::new(), ARID::new());
+    let mut pending = PendingRequests::new();
+    pending.add_collect_only(bob, bob_arid);
+    pending.add_collect_only(carol, carol_arid);
+    pending.add_collect_only(dan, dan_arid);
+    let storage = stored_responses(&[bob_arid, carol_arid, dan_arid]);
+
+    // Carol's response was not collected, so it must stay
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let deleted = runtime.block_on(delete_collected(
+        &pending.collect_arids_for(&[bob, dan]),
+        |arid| {
+            let removed = storage.lock().unwrap().remove(&arid).is_some();
+            async move {
+                assert!(removed, "deleted an ARID that was not stored");
+                Ok(DeleteOutcome::Deleted)
+            }
+        },
+    ));
+
+    assert_eq!(deleted, 2);
+    let storage = storage.into_inner().unwrap();
+    assert_eq!(
+        storage.keys().copied().collect::<Vec<_>>(),
+        vec![carol_arid]
+    );
+}
+
+#[test]
+fn cleanup_stops_at_a_backend_that_cannot_delete() {
+    let arids = [ARID::new(), ARID::new()];
+    let storage = stored_responses(&arids);
+    let mut attempts = 0;
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let deleted = runtime.block_on(delete_collected(&arids, |_| {
+        attempts += 1;
+        async { Ok(DeleteOutcome::Unsupported) }
+    }));
+
+    assert_eq!(deleted, 0);
+    assert_eq!(attempts, 1);
+    assert_eq!(storage.into_inner().unwrap().len(), arids.len());
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use bc_components::ARID;
+use frost_hubert::cmd::storage::{
+    StorageClient, StorageSelection, shared_runtime,
+};
+
+fn server(port: u16) -> StorageSelection {
+    StorageSelection::Server { host: "127.0.0.1".to_owned(), port }
+}
+
+#[test]
+fn shared_runtime_is_reused() {
+    let first = shared_runtime().unwrap();
+    let second = shared_runtime().unwrap();
+    assert!(std::ptr::eq(first, second));
+}
+
+#[test]
+fn shared_clients_are_keyed_by_selection() {
+    let runtime = shared_runtime().unwrap();
+    let (first, second, other) = runtime.block_on(async {
+        (
+            StorageClient::shared(server(2)).await.unwrap(),
+            StorageClient::shared(server(2)).await.unwrap(),
+            StorageClient::shared(server(3)).await.unwrap(),
+        )
+    });
+    assert!(Arc::ptr_eq(&first, &second));
+    assert!(!Arc::ptr_eq(&first, &other));
+}
+
+#[test]
+fn unreachable_client_is_evicted() {
+    let runtime = shared_runtime().unwrap();
+    runtime.block_on(async {
+        let client = StorageClient::shared(server(1)).await.unwrap();
+        let error = client.get(&ARID::new(), None).await.unwrap_err();
+        assert!(error.is_unreachable(), "{error}");
+
+        let reopened = StorageClient::shared(server(1)).await.unwrap();
+        assert!(!Arc::ptr_eq(&client, &reopened));
+        let cached = StorageClient::shared(server(1)).await.unwrap();
+        assert!(Arc::ptr_eq(&reopened, &cached));
+    });
+}
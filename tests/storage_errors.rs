@@ -0,0 +1,97 @@
+use std::io;
+
+mod common;
+use anyhow::{Context, anyhow};
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{fixture, run_frost};
+use frost_hubert::cmd::storage::{StorageError, connection_failure};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn connection_refused_is_a_connection_failure() {
+    let err =
+        anyhow::Error::new(io::Error::from(io::ErrorKind::ConnectionRefused))
+            .context("tcp connect error")
+            .context("error sending request for url (http://127.0.0.1:1/get)");
+    let reason = connection_failure(&err).unwrap();
+    assert!(reason.to_lowercase().contains("refused"), "{reason}");
+}
+
+#[test]
+fn connection_failure_falls_back_to_client_message() {
+    let err = anyhow!("client error (Connect)").context("tcp connect error");
+    assert_eq!(connection_failure(&err).unwrap(), "tcp connect error");
+}
+
+#[test]
+fn missing_key_is_not_a_connection_failure() {
+    let err = anyhow!("Envelope not found").context("Server returned 404");
+    assert!(connection_failure(&err).is_none());
+
+    let err = anyhow::Error::new(io::Error::from(io::ErrorKind::InvalidData))
+        .context("Invalid envelope");
+    assert!(connection_failure(&err).is_none());
+}
+
+#[test]
+fn unreachable_error_names_endpoint() {
+    let err = StorageError::Unreachable {
+        service: "Hubert server",
+        endpoint: "127.0.0.1:45678".to_string(),
+        reason: "Connection refused".to_string(),
+    };
+    assert!(err.is_unreachable());
+    assert_eq!(
+        err.to_string(),
+        "Hubert server unreachable at 127.0.0.1:45678: Connection refused"
+    );
+
+    // The typed error survives a trip through anyhow
+    let recovered = StorageError::from(err.into_anyhow());
+    assert!(recovered.is_unreachable());
+
+    let other = StorageError::from(anyhow!("Invalid envelope"));
+    assert!(!other.is_unreachable());
+    assert_eq!(other.into_anyhow().to_string(), "Invalid envelope");
+}
+
+#[test]
+fn inspect_reports_unreachable_server() {
+    let temp = TempDir::new().unwrap();
+    run_frost(
+        temp.path(),
+        &[
+            "registry",
+            "owner",
+            "set",
+            &fixture("alice_private_xid.txt"),
+        ],
+    )
+    .assert()
+    .success();
+
+    // Nothing listens on port 1
+    run_frost(
+        temp.path(),
+        &[
+            "dkg",
+            "coordinator",
+            "inspect",
+            "--storage",
+            "server",
+            "--port",
+            "1",
+            "--timeout",
+            "1",
+            &ARID::new().ur_string(),
+        ],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Hubert server unreachable at 127.0.0.1:1",
+    ))
+    .stderr(predicate::str::contains("Nothing posted yet").not());
+}
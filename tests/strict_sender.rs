@@ -0,0 +1,132 @@
+use std::path::Path;
+
+mod common;
+use bc_components::{ARID, Ed25519PrivateKey, SigningPrivateKey};
+use bc_envelope::prelude::*;
+use common::{fixture, owner, registry_file, run_frost, set_owner};
+use frost_hubert::registry::{GroupParticipant, GroupRecord, Registry};
+use gstp::SealedRequest;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+/// Sets up Alice's registry with a finalized group coordinated by Bob and
+/// returns Bob's signInvite to Alice.
+fn sign_invite(dir: &Path) -> String {
+    set_owner(dir, "alice", None);
+    run_frost(
+        dir,
+        &[
+            "registry",
+            "participant",
+            "add",
+            &fixture("bob_signed_xid.txt"),
+            "Bob",
+        ],
+    )
+    .assert()
+    .success();
+
+    let alice = owner("alice", None);
+    let bob = owner("bob", None);
+    let group_id = ARID::new();
+    let session_id = ARID::new();
+
+    let mut record = GroupRecord::new(
+        "Test group".to_string(),
+        2,
+        GroupParticipant::new(bob.xid()),
+        vec![
+            GroupParticipant::new(alice.xid()),
+            GroupParticipant::new(bob.xid()),
+        ],
+    );
+    record.set_verifying_key(
+        SigningPrivateKey::new_ed25519(Ed25519PrivateKey::new())
+            .public_key()
+            .unwrap(),
+    );
+    let path = registry_file(dir);
+    let mut registry = Registry::load(&path).unwrap();
+    registry.record_group(group_id, record).unwrap();
+    registry.save(&path).unwrap();
+
+    let mut request = SealedRequest::new(
+        "signInvite",
+        session_id,
+        bob.xid_document().clone(),
+    )
+    .with_parameter("group", group_id)
+    .with_parameter("session", session_id)
+    .with_parameter("target", Envelope::new("Quarterly budget"))
+    .with_parameter("minSigners", 2u64)
+    .with_parameter(
+        "validUntil",
+        Date::from_timestamp(Date::now().timestamp() + 3600.0),
+    );
+    for participant in [&alice, &bob] {
+        let encryption_key =
+            participant.xid_document().encryption_key().unwrap();
+        let entry = Envelope::new(participant.xid()).add_assertion(
+            "response_arid",
+            ARID::new()
+                .to_envelope()
+                .encrypt_to_recipient(encryption_key),
+        );
+        request = request.with_parameter("participant", entry);
+    }
+    request
+        .to_envelope(
+            None,
+            Some(bob.xid_document().inception_private_keys().unwrap()),
+            Some(alice.xid_document()),
+        )
+        .unwrap()
+        .ur_string()
+}
+
+#[test]
+fn strict_sender_requires_an_expected_sender_for_invites() {
+    let temp = TempDir::new().unwrap();
+    let invite = sign_invite(temp.path());
+
+    run_frost(
+        temp.path(),
+        &["--strict-sender", "sign", "participant", "receive", &invite],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "--strict-sender requires --sender",
+    ));
+}
+
+#[test]
+fn strict_sender_accepts_the_registered_sender() {
+    let temp = TempDir::new().unwrap();
+    let invite = sign_invite(temp.path());
+
+    run_frost(
+        temp.path(),
+        &[
+            "--strict-sender",
+            "sign",
+            "participant",
+            "receive",
+            "--sender",
+            "Bob",
+            &invite,
+        ],
+    )
+    .assert()
+    .success();
+}
+
+#[test]
+fn strict_sender_is_off_by_default() {
+    let temp = TempDir::new().unwrap();
+    let invite = sign_invite(temp.path());
+
+    run_frost(temp.path(), &["sign", "participant", "receive", &invite])
+        .assert()
+        .success();
+}
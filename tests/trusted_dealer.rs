@@ -0,0 +1,164 @@
+use std::{fs, path::Path};
+
+mod common;
+use bc_components::ARID;
+use bc_envelope::prelude::*;
+use common::{registry_file, run_frost, setup_registry};
+use frost_hubert::{
+    cmd::common::GroupStatePaths,
+    registry::{GroupProvenance, Registry},
+};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn keygen(dir: &Path) -> ARID {
+    let output = run_frost(
+        dir,
+        &[
+            "keygen",
+            "--min-signers",
+            "2",
+            "--charter",
+            "Test",
+            "Bob",
+            "Carol",
+        ],
+    )
+    .output()
+    .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("trusted dealer"));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    ARID::from_ur_string(stdout.trim()).unwrap()
+}
+
+fn share_for(dealer_dir: &Path, group_id: &ARID, pet_name: &str) -> String {
+    let registry = Registry::load(&registry_file(dealer_dir)).unwrap();
+    let (xid, _) = registry.participant_by_pet_name(pet_name).unwrap();
+    let path = GroupStatePaths::new(&registry_file(dealer_dir))
+        .dealt_share_path(group_id, xid);
+    fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn keygen_records_trusted_dealer_group() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", &[("bob", "Bob"), ("carol", "Carol")]);
+    let group_id = keygen(temp.path());
+
+    let registry = Registry::load(&registry_file(temp.path())).unwrap();
+    let group = registry.group(&group_id).unwrap();
+    assert_eq!(group.provenance(), GroupProvenance::TrustedDealer);
+    assert_eq!(group.min_signers(), 2);
+    assert_eq!(group.participants().len(), 2);
+    assert!(group.verifying_key().is_some());
+    assert_eq!(*group.coordinator().xid(), registry.owner().unwrap().xid());
+
+    let raw = fs::read_to_string(registry_file(temp.path())).unwrap();
+    assert!(raw.contains("\"provenance\": \"trusted_dealer\""));
+    let paths = GroupStatePaths::new(&registry_file(temp.path()));
+    assert!(paths.public_key_package_path(&group_id).exists());
+    assert_eq!(
+        fs::read_dir(paths.dealt_shares_dir(&group_id))
+            .unwrap()
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn keygen_rejects_dealer_as_participant() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", &[("bob", "Bob")]);
+    let owner = Registry::load(&registry_file(temp.path()))
+        .unwrap()
+        .owner()
+        .unwrap()
+        .xid()
+        .ur_string();
+
+    run_frost(temp.path(), &["keygen", "Bob", &owner])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "dealer cannot also be a participant",
+        ));
+}
+
+#[test]
+fn imported_share_matches_dealer_group() {
+    let dealer = TempDir::new().unwrap();
+    setup_registry(
+        dealer.path(),
+        "alice",
+        &[("bob", "Bob"), ("carol", "Carol")],
+    );
+    let group_id = keygen(dealer.path());
+    let share = share_for(dealer.path(), &group_id, "Bob");
+
+    let bob = TempDir::new().unwrap();
+    setup_registry(bob.path(), "bob", &[("alice", "Alice")]);
+    run_frost(bob.path(), &["registry", "group", "import-share", &share])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(group_id.ur_string()));
+
+    let dealer_registry =
+        Registry::load(&registry_file(dealer.path())).unwrap();
+    let bob_registry = Registry::load(&registry_file(bob.path())).unwrap();
+    let dealt = dealer_registry.group(&group_id).unwrap();
+    let imported = bob_registry.group(&group_id).unwrap();
+    assert_eq!(imported.provenance(), GroupProvenance::TrustedDealer);
+    assert_eq!(imported.verifying_key(), dealt.verifying_key());
+    assert_eq!(imported.coordinator(), dealt.coordinator());
+    assert_eq!(imported.participants(), dealt.participants());
+    assert!(
+        GroupStatePaths::new(&registry_file(bob.path()))
+            .key_package_path(&group_id)
+            .exists()
+    );
+
+    // Importing the same share twice is refused
+    run_frost(bob.path(), &["registry", "group", "import-share", &share])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already in the registry"));
+}
+
+#[test]
+fn import_share_rejects_share_for_someone_else() {
+    let dealer = TempDir::new().unwrap();
+    setup_registry(
+        dealer.path(),
+        "alice",
+        &[("bob", "Bob"), ("carol", "Carol")],
+    );
+    let group_id = keygen(dealer.path());
+    let carol_share = share_for(dealer.path(), &group_id, "Carol");
+
+    let bob = TempDir::new().unwrap();
+    setup_registry(bob.path(), "bob", &[("alice", "Alice")]);
+    run_frost(
+        bob.path(),
+        &["registry", "group", "import-share", &carol_share],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("not encrypted to this registry"));
+}
+
+#[test]
+fn verify_charter_reports_trusted_dealer_groups() {
+    let temp = TempDir::new().unwrap();
+    setup_registry(temp.path(), "alice", &[("bob", "Bob"), ("carol", "Carol")]);
+    let group_id = keygen(temp.path());
+
+    run_frost(
+        temp.path(),
+        &["registry", "group", "verify-charter", &group_id.ur_string()],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("trusted dealer"));
+}
@@ -0,0 +1,32 @@
+mod common;
+use common::run_frost;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn version_prints_crate_version() {
+    let temp = TempDir::new().unwrap();
+    run_frost(temp.path(), &["version"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(env!("CARGO_PKG_VERSION")))
+        .stdout(predicate::str::contains("Ciphersuites").not());
+}
+
+#[test]
+fn version_full_reports_capabilities() {
+    let temp = TempDir::new().unwrap();
+    run_frost(temp.path(), &["version", "--full"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Ciphersuites: FROST-ED25519-SHA512-v1",
+        ))
+        .stdout(predicate::str::contains(
+            "Storage backends: mainline, ipfs, hybrid, server",
+        ))
+        .stdout(predicate::str::contains("Features: test-support ("))
+        .stdout(predicate::str::contains("gstp: "))
+        .stdout(predicate::str::contains("bc-envelope: "))
+        .stdout(predicate::str::contains("unknown").not());
+}